@@ -0,0 +1,25 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ContentSearchMatchRange } from "./ContentSearchMatchRange";
+
+/**
+ * Superset of [`codex_file_search::ContentMatch`]
+ */
+export type ContentSearchResult = { root: string, path: string, fileName: string,
+/**
+ * 1-based line number.
+ */
+lineNumber: number,
+/**
+ * 1-based byte column of the first match on the line.
+ */
+column: number,
+/**
+ * The matching line, truncated for long lines.
+ */
+preview: string,
+/**
+ * Byte ranges of each match within `preview`.
+ */
+ranges: Array<ContentSearchMatchRange>, };
@@ -0,0 +1,14 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ContentSearchResult } from "./ContentSearchResult";
+
+export type ContentSearchSessionUpdatedNotification = { sessionId: string, query: string, matches: Array<ContentSearchResult>,
+/**
+ * Number of matching lines found so far, which may exceed `matches.len()`.
+ */
+totalMatchCount: number,
+/**
+ * Set when the query is not a valid regular expression.
+ */
+error: string | null, };
@@ -0,0 +1,17 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * The part of one file in a proposed patch that the user accepted.
+ */
+export type PatchFileApproval = {
+/**
+ * Path as it appears in the approval request's `changes`.
+ */
+path: string,
+/**
+ * Zero-based indices of the accepted `@@` hunks in the file's
+ * `unified_diff`. `None` accepts every change to the file.
+ */
+hunks?: Array<number>, };
@@ -10,6 +10,10 @@ export type { ClientNotification } from "./ClientNotification";
 export type { ClientRequest } from "./ClientRequest";
 export type { CollaborationMode } from "./CollaborationMode";
 export type { ContentItem } from "./ContentItem";
+export type { ContentSearchMatchRange } from "./ContentSearchMatchRange";
+export type { ContentSearchResult } from "./ContentSearchResult";
+export type { ContentSearchSessionCompletedNotification } from "./ContentSearchSessionCompletedNotification";
+export type { ContentSearchSessionUpdatedNotification } from "./ContentSearchSessionUpdatedNotification";
 export type { ConversationGitInfo } from "./ConversationGitInfo";
 export type { ConversationSummary } from "./ConversationSummary";
 export type { ExecCommandApprovalParams } from "./ExecCommandApprovalParams";
@@ -46,6 +50,7 @@ export type { ModeKind } from "./ModeKind";
 export type { NetworkPolicyAmendment } from "./NetworkPolicyAmendment";
 export type { NetworkPolicyRuleAction } from "./NetworkPolicyRuleAction";
 export type { ParsedCommand } from "./ParsedCommand";
+export type { PatchFileApproval } from "./PatchFileApproval";
 export type { Personality } from "./Personality";
 export type { PlanType } from "./PlanType";
 export type { RealtimeConversationVersion } from "./RealtimeConversationVersion";
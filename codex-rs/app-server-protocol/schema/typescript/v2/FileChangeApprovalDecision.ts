@@ -1,5 +1,6 @@
 // GENERATED CODE! DO NOT MODIFY BY HAND!
 
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { FileChangeApprovalSelection } from "./FileChangeApprovalSelection";
 
-export type FileChangeApprovalDecision = "accept" | "acceptForSession" | "decline" | "cancel";
+export type FileChangeApprovalDecision = "accept" | "acceptForSession" | { "acceptPartial": { files: Array<FileChangeApprovalSelection>, } } | { "acceptEdited": { patch: string, } } | "decline" | "cancel";
@@ -0,0 +1,17 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * The accepted part of one file in a `fileChange` item.
+ */
+export type FileChangeApprovalSelection = {
+/**
+ * Path of the change, as reported in the item's `changes`.
+ */
+path: string,
+/**
+ * Zero-based indices of the accepted `@@` hunks in the change's `diff`. When omitted,
+ * every change to the file is accepted.
+ */
+hunks?: Array<number>, };
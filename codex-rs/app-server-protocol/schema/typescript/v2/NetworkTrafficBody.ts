@@ -0,0 +1,9 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type NetworkTrafficBody = {
+/**
+ * Full body length in bytes; `text` holds at most the recorded prefix.
+ */
+size: bigint, text: string, truncated: boolean, };
@@ -0,0 +1,21 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { NetworkTrafficBody } from "./NetworkTrafficBody";
+import type { NetworkTrafficHeader } from "./NetworkTrafficHeader";
+
+export type NetworkTrafficEntry = {
+/**
+ * Unix timestamp in milliseconds at which the proxy received the request.
+ */
+startedAtMs: bigint, durationMs: bigint, protocol: string, method: string | null, url: string, /**
+ * Null when the request was blocked or the upstream request failed.
+ */
+status: number | null, requestHeaders: Array<NetworkTrafficHeader>, responseHeaders: Array<NetworkTrafficHeader>, requestBody: NetworkTrafficBody | null, responseBody: NetworkTrafficBody | null, /**
+ * `allow` for forwarded requests; the policy decision otherwise.
+ */
+decision: string,
+/**
+ * Which policy layer blocked the request; not tracked for allowed requests.
+ */
+source: string | null, reason: string | null, client: string | null, };
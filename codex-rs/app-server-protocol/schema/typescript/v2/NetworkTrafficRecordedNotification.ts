@@ -0,0 +1,10 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { NetworkTrafficEntry } from "./NetworkTrafficEntry";
+
+/**
+ * A request that went through the managed network proxy while
+ * `network.record_traffic` is enabled.
+ */
+export type NetworkTrafficRecordedNotification = { threadId: string, entry: NetworkTrafficEntry, };
@@ -109,6 +109,7 @@ export type { ExternalAgentConfigMigrationItemType } from "./ExternalAgentConfig
 export type { FeedbackUploadParams } from "./FeedbackUploadParams";
 export type { FeedbackUploadResponse } from "./FeedbackUploadResponse";
 export type { FileChangeApprovalDecision } from "./FileChangeApprovalDecision";
+export type { FileChangeApprovalSelection } from "./FileChangeApprovalSelection";
 export type { FileChangeOutputDeltaNotification } from "./FileChangeOutputDeltaNotification";
 export type { FileChangePatchUpdatedNotification } from "./FileChangePatchUpdatedNotification";
 export type { FileChangeRequestApprovalParams } from "./FileChangeRequestApprovalParams";
@@ -243,6 +244,10 @@ export type { NetworkDomainPermission } from "./NetworkDomainPermission";
 export type { NetworkPolicyAmendment } from "./NetworkPolicyAmendment";
 export type { NetworkPolicyRuleAction } from "./NetworkPolicyRuleAction";
 export type { NetworkRequirements } from "./NetworkRequirements";
+export type { NetworkTrafficBody } from "./NetworkTrafficBody";
+export type { NetworkTrafficEntry } from "./NetworkTrafficEntry";
+export type { NetworkTrafficHeader } from "./NetworkTrafficHeader";
+export type { NetworkTrafficRecordedNotification } from "./NetworkTrafficRecordedNotification";
 export type { NetworkUnixSocketPermission } from "./NetworkUnixSocketPermission";
 export type { NonSteerableTurnKind } from "./NonSteerableTurnKind";
 export type { OverriddenMetadata } from "./OverriddenMetadata";
@@ -648,6 +648,21 @@ client_request_definitions! {
         params: FuzzyFileSearchSessionStopParams,
         response: FuzzyFileSearchSessionStopResponse,
     },
+    #[experimental("contentSearch/sessionStart")]
+    ContentSearchSessionStart => "contentSearch/sessionStart" {
+        params: ContentSearchSessionStartParams,
+        response: ContentSearchSessionStartResponse,
+    },
+    #[experimental("contentSearch/sessionUpdate")]
+    ContentSearchSessionUpdate => "contentSearch/sessionUpdate" {
+        params: ContentSearchSessionUpdateParams,
+        response: ContentSearchSessionUpdateResponse,
+    },
+    #[experimental("contentSearch/sessionStop")]
+    ContentSearchSessionStop => "contentSearch/sessionStop" {
+        params: ContentSearchSessionStopParams,
+        response: ContentSearchSessionStopResponse,
+    },
 }
 
 /// Generates an `enum ServerRequest` where each variant is a request that the
@@ -1017,6 +1032,93 @@ pub struct FuzzyFileSearchSessionCompletedNotification {
     pub session_id: String,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(rename_all = "camelCase")]
+pub struct ContentSearchSessionStartParams {
+    pub session_id: String,
+    pub roots: Vec<String>,
+    /// Treat queries as regular expressions instead of literal strings.
+    #[serde(default)]
+    pub regex: bool,
+    #[serde(default)]
+    pub case_sensitive: bool,
+    /// File type names such as `rust` or `py`; empty searches every file.
+    #[serde(default)]
+    pub file_types: Vec<String>,
+    /// Globs a file must match to be searched; empty searches every file.
+    #[serde(default)]
+    pub globs: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS, Default)]
+pub struct ContentSearchSessionStartResponse {}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(rename_all = "camelCase")]
+pub struct ContentSearchSessionUpdateParams {
+    pub session_id: String,
+    pub query: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS, Default)]
+pub struct ContentSearchSessionUpdateResponse {}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(rename_all = "camelCase")]
+pub struct ContentSearchSessionStopParams {
+    pub session_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS, Default)]
+pub struct ContentSearchSessionStopResponse {}
+
+/// Superset of [`codex_file_search::ContentMatch`]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(rename_all = "camelCase")]
+pub struct ContentSearchResult {
+    pub root: String,
+    pub path: String,
+    pub file_name: String,
+    /// 1-based line number.
+    pub line_number: u32,
+    /// 1-based byte column of the first match on the line.
+    pub column: u32,
+    /// The matching line, truncated for long lines.
+    pub preview: String,
+    /// Byte ranges of each match within `preview`.
+    pub ranges: Vec<ContentSearchMatchRange>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema, TS)]
+pub struct ContentSearchMatchRange {
+    pub start: u32,
+    pub end: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(rename_all = "camelCase")]
+pub struct ContentSearchSessionUpdatedNotification {
+    pub session_id: String,
+    pub query: String,
+    pub matches: Vec<ContentSearchResult>,
+    /// Number of matching lines found so far, which may exceed `matches.len()`.
+    pub total_match_count: u32,
+    /// Set when the query is not a valid regular expression.
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(rename_all = "camelCase")]
+pub struct ContentSearchSessionCompletedNotification {
+    pub session_id: String,
+}
+
 server_notification_definitions! {
     /// NEW NOTIFICATIONS
     Error => "error" (v2::ErrorNotification),
@@ -1032,6 +1134,7 @@ server_notification_definitions! {
     HookStarted => "hook/started" (v2::HookStartedNotification),
     TurnCompleted => "turn/completed" (v2::TurnCompletedNotification),
     HookCompleted => "hook/completed" (v2::HookCompletedNotification),
+    NetworkTrafficRecorded => "thread/networkTraffic/recorded" (v2::NetworkTrafficRecordedNotification),
     TurnDiffUpdated => "turn/diff/updated" (v2::TurnDiffUpdatedNotification),
     TurnPlanUpdated => "turn/plan/updated" (v2::TurnPlanUpdatedNotification),
     ItemStarted => "item/started" (v2::ItemStartedNotification),
@@ -1071,6 +1174,8 @@ server_notification_definitions! {
     ConfigWarning => "configWarning" (v2::ConfigWarningNotification),
     FuzzyFileSearchSessionUpdated => "fuzzyFileSearch/sessionUpdated" (FuzzyFileSearchSessionUpdatedNotification),
     FuzzyFileSearchSessionCompleted => "fuzzyFileSearch/sessionCompleted" (FuzzyFileSearchSessionCompletedNotification),
+    ContentSearchSessionUpdated => "contentSearch/sessionUpdated" (ContentSearchSessionUpdatedNotification),
+    ContentSearchSessionCompleted => "contentSearch/sessionCompleted" (ContentSearchSessionCompletedNotification),
     #[experimental("thread/realtime/started")]
     ThreadRealtimeStarted => "thread/realtime/started" (v2::ThreadRealtimeStartedNotification),
     #[experimental("thread/realtime/itemAdded")]
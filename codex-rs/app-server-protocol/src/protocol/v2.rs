@@ -78,8 +78,12 @@ use codex_protocol::protocol::HookSource as CoreHookSource;
 use codex_protocol::protocol::ModelRerouteReason as CoreModelRerouteReason;
 use codex_protocol::protocol::ModelVerification as CoreModelVerification;
 use codex_protocol::protocol::NetworkAccess as CoreNetworkAccess;
+use codex_protocol::protocol::NetworkTrafficBody as CoreNetworkTrafficBody;
+use codex_protocol::protocol::NetworkTrafficHeader as CoreNetworkTrafficHeader;
+use codex_protocol::protocol::NetworkTrafficRecordedEvent as CoreNetworkTrafficRecordedEvent;
 use codex_protocol::protocol::NonSteerableTurnKind as CoreNonSteerableTurnKind;
 use codex_protocol::protocol::PatchApplyStatus as CorePatchApplyStatus;
+use codex_protocol::protocol::PatchFileApproval as CorePatchFileApproval;
 use codex_protocol::protocol::RateLimitReachedType as CoreRateLimitReachedType;
 use codex_protocol::protocol::RateLimitSnapshot as CoreRateLimitSnapshot;
 use codex_protocol::protocol::RateLimitWindow as CoreRateLimitWindow;
@@ -440,7 +444,8 @@ v2_enum_from_core!(
 
 v2_enum_from_core!(
     pub enum HookEventName from CoreHookEventName {
-        PreToolUse, PermissionRequest, PostToolUse, SessionStart, UserPromptSubmit, Stop
+        PreToolUse, PermissionRequest, PostToolUse, SessionStart, UserPromptSubmit, Stop,
+        PreCompact, SessionEnd, SubagentStart, SubagentStop, Notification
     }
 );
 
@@ -985,6 +990,21 @@ pub struct ManagedHooksRequirements {
     #[serde(rename = "Stop")]
     #[ts(rename = "Stop")]
     pub stop: Vec<ConfiguredHookMatcherGroup>,
+    #[serde(rename = "PreCompact")]
+    #[ts(rename = "PreCompact")]
+    pub pre_compact: Vec<ConfiguredHookMatcherGroup>,
+    #[serde(rename = "SessionEnd")]
+    #[ts(rename = "SessionEnd")]
+    pub session_end: Vec<ConfiguredHookMatcherGroup>,
+    #[serde(rename = "SubagentStart")]
+    #[ts(rename = "SubagentStart")]
+    pub subagent_start: Vec<ConfiguredHookMatcherGroup>,
+    #[serde(rename = "SubagentStop")]
+    #[ts(rename = "SubagentStop")]
+    pub subagent_stop: Vec<ConfiguredHookMatcherGroup>,
+    #[serde(rename = "Notification")]
+    #[ts(rename = "Notification")]
+    pub notification: Vec<ConfiguredHookMatcherGroup>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema, TS)]
@@ -1013,10 +1033,28 @@ pub enum ConfiguredHookHandler {
     },
     #[serde(rename = "prompt")]
     #[ts(rename = "prompt")]
-    Prompt {},
+    Prompt {
+        prompt: String,
+        model: Option<String>,
+        #[serde(rename = "timeoutSec")]
+        #[ts(rename = "timeoutSec")]
+        timeout_sec: Option<u64>,
+        #[serde(rename = "statusMessage")]
+        #[ts(rename = "statusMessage")]
+        status_message: Option<String>,
+    },
     #[serde(rename = "agent")]
     #[ts(rename = "agent")]
-    Agent {},
+    Agent {
+        prompt: String,
+        model: Option<String>,
+        #[serde(rename = "timeoutSec")]
+        #[ts(rename = "timeoutSec")]
+        timeout_sec: Option<u64>,
+        #[serde(rename = "statusMessage")]
+        #[ts(rename = "statusMessage")]
+        status_message: Option<String>,
+    },
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
@@ -1243,6 +1281,9 @@ impl From<CoreReviewDecision> for CommandExecutionApprovalDecision {
             CoreReviewDecision::Abort => Self::Cancel,
             CoreReviewDecision::Denied => Self::Decline,
             CoreReviewDecision::TimedOut => Self::Decline,
+            // Partial and edited approvals only apply to patches.
+            CoreReviewDecision::ApprovedPartialPatch { .. }
+            | CoreReviewDecision::ApprovedEditedPatch { .. } => Self::Decline,
         }
     }
 }
@@ -1704,12 +1745,52 @@ pub enum FileChangeApprovalDecision {
     Accept,
     /// User approved the file changes and future changes to the same files should run without prompting.
     AcceptForSession,
+    /// User approved only some of the file changes. Files that are not listed, and hunks
+    /// that are not selected, are rejected and reported back to the agent.
+    AcceptPartial {
+        files: Vec<FileChangeApprovalSelection>,
+    },
+    /// User approved an edited patch, in `apply_patch` format, that is applied instead of
+    /// the proposed one.
+    AcceptEdited { patch: String },
     /// User denied the file changes. The agent will continue the turn.
     Decline,
     /// User denied the file changes. The turn will also be immediately interrupted.
     Cancel,
 }
 
+/// The accepted part of one file in a `fileChange` item.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct FileChangeApprovalSelection {
+    /// Path of the change, as reported in the item's `changes`.
+    pub path: String,
+    /// Zero-based indices of the accepted `@@` hunks in the change's `diff`. When omitted,
+    /// every change to the file is accepted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub hunks: Option<Vec<u32>>,
+}
+
+impl From<FileChangeApprovalSelection> for CorePatchFileApproval {
+    fn from(value: FileChangeApprovalSelection) -> Self {
+        Self {
+            path: PathBuf::from(value.path),
+            hunks: value.hunks,
+        }
+    }
+}
+
+impl From<CorePatchFileApproval> for FileChangeApprovalSelection {
+    fn from(value: CorePatchFileApproval) -> Self {
+        Self {
+            path: value.path.to_string_lossy().into_owned(),
+            hunks: value.hunks,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
@@ -6297,6 +6378,98 @@ pub struct HookCompletedNotification {
     pub run: HookRunSummary,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct NetworkTrafficHeader {
+    pub name: String,
+    pub value: String,
+}
+
+impl From<CoreNetworkTrafficHeader> for NetworkTrafficHeader {
+    fn from(value: CoreNetworkTrafficHeader) -> Self {
+        Self {
+            name: value.name,
+            value: value.value,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct NetworkTrafficBody {
+    /// Full body length in bytes; `text` holds at most the recorded prefix.
+    pub size: i64,
+    pub text: String,
+    pub truncated: bool,
+}
+
+impl From<CoreNetworkTrafficBody> for NetworkTrafficBody {
+    fn from(value: CoreNetworkTrafficBody) -> Self {
+        Self {
+            size: i64::try_from(value.size).unwrap_or(i64::MAX),
+            text: value.text,
+            truncated: value.truncated,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct NetworkTrafficEntry {
+    /// Unix timestamp in milliseconds at which the proxy received the request.
+    pub started_at_ms: i64,
+    pub duration_ms: i64,
+    pub protocol: String,
+    pub method: Option<String>,
+    pub url: String,
+    /// Null when the request was blocked or the upstream request failed.
+    pub status: Option<u16>,
+    pub request_headers: Vec<NetworkTrafficHeader>,
+    pub response_headers: Vec<NetworkTrafficHeader>,
+    pub request_body: Option<NetworkTrafficBody>,
+    pub response_body: Option<NetworkTrafficBody>,
+    /// `allow` for forwarded requests; the policy decision otherwise.
+    pub decision: String,
+    /// Which policy layer blocked the request; not tracked for allowed requests.
+    pub source: Option<String>,
+    pub reason: Option<String>,
+    pub client: Option<String>,
+}
+
+impl From<CoreNetworkTrafficRecordedEvent> for NetworkTrafficEntry {
+    fn from(value: CoreNetworkTrafficRecordedEvent) -> Self {
+        Self {
+            started_at_ms: value.started_at_ms,
+            duration_ms: i64::try_from(value.duration_ms).unwrap_or(i64::MAX),
+            protocol: value.protocol,
+            method: value.method,
+            url: value.url,
+            status: value.status,
+            request_headers: value.request_headers.into_iter().map(Into::into).collect(),
+            response_headers: value.response_headers.into_iter().map(Into::into).collect(),
+            request_body: value.request_body.map(Into::into),
+            response_body: value.response_body.map(Into::into),
+            decision: value.decision,
+            source: value.source,
+            reason: value.reason,
+            client: value.client,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+/// A request that went through the managed network proxy while
+/// `network.record_traffic` is enabled.
+pub struct NetworkTrafficRecordedNotification {
+    pub thread_id: String,
+    pub entry: NetworkTrafficEntry,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
@@ -8116,6 +8289,43 @@ mod tests {
         assert_eq!(decoded, response);
     }
 
+    #[test]
+    fn file_change_partial_approval_response_round_trip() {
+        let response = FileChangeRequestApprovalResponse {
+            decision: FileChangeApprovalDecision::AcceptPartial {
+                files: vec![
+                    FileChangeApprovalSelection {
+                        path: "src/lib.rs".to_string(),
+                        hunks: Some(vec![1]),
+                    },
+                    FileChangeApprovalSelection {
+                        path: "README.md".to_string(),
+                        hunks: None,
+                    },
+                ],
+            },
+        };
+
+        let value = serde_json::to_value(&response).expect("serialize file change response");
+        assert_eq!(
+            value,
+            json!({
+                "decision": {
+                    "acceptPartial": {
+                        "files": [
+                            {"path": "src/lib.rs", "hunks": [1]},
+                            {"path": "README.md"},
+                        ],
+                    },
+                },
+            })
+        );
+
+        let decoded = serde_json::from_value::<FileChangeRequestApprovalResponse>(value)
+            .expect("deserialize file change response");
+        assert_eq!(decoded, response);
+    }
+
     #[test]
     fn fs_read_file_params_round_trip() {
         let params = FsReadFileParams {
@@ -10551,4 +10761,20 @@ mod tests {
             "unexpected error: {err}"
         );
     }
+
+    #[test]
+    fn patch_only_review_decisions_decline_command_execution() {
+        assert_eq!(
+            CommandExecutionApprovalDecision::from(CoreReviewDecision::ApprovedPartialPatch {
+                accepted_files: Vec::new(),
+            }),
+            CommandExecutionApprovalDecision::Decline
+        );
+        assert_eq!(
+            CommandExecutionApprovalDecision::from(CoreReviewDecision::ApprovedEditedPatch {
+                patch: "*** Begin Patch\n*** End Patch".to_string(),
+            }),
+            CommandExecutionApprovalDecision::Decline
+        );
+    }
 }
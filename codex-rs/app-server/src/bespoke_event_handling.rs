@@ -65,6 +65,7 @@ use codex_app_server_protocol::ModelVerificationNotification;
 use codex_app_server_protocol::NetworkApprovalContext as V2NetworkApprovalContext;
 use codex_app_server_protocol::NetworkPolicyAmendment as V2NetworkPolicyAmendment;
 use codex_app_server_protocol::NetworkPolicyRuleAction as V2NetworkPolicyRuleAction;
+use codex_app_server_protocol::NetworkTrafficRecordedNotification;
 use codex_app_server_protocol::PatchApplyStatus;
 use codex_app_server_protocol::PermissionsRequestApprovalParams;
 use codex_app_server_protocol::PermissionsRequestApprovalResponse;
@@ -1612,6 +1613,19 @@ pub(crate) async fn apply_bespoke_event_handling(
                     .await;
             }
         }
+        EventMsg::NetworkTrafficRecorded(event) => {
+            if let ApiVersion::V2 = api_version {
+                let notification = NetworkTrafficRecordedNotification {
+                    thread_id: conversation_id.to_string(),
+                    entry: event.into(),
+                };
+                outgoing
+                    .send_server_notification(ServerNotification::NetworkTrafficRecorded(
+                        notification,
+                    ))
+                    .await;
+            }
+        }
         EventMsg::ExitedReviewMode(review_event) => {
             let review = match review_event.review_output {
                 Some(output) => render_review_output_text(&output),
@@ -2774,6 +2788,15 @@ fn map_file_change_approval_decision(
     match decision {
         FileChangeApprovalDecision::Accept => (ReviewDecision::Approved, None),
         FileChangeApprovalDecision::AcceptForSession => (ReviewDecision::ApprovedForSession, None),
+        FileChangeApprovalDecision::AcceptPartial { files } => (
+            ReviewDecision::ApprovedPartialPatch {
+                accepted_files: files.into_iter().map(Into::into).collect(),
+            },
+            None,
+        ),
+        FileChangeApprovalDecision::AcceptEdited { patch } => {
+            (ReviewDecision::ApprovedEditedPatch { patch }, None)
+        }
         FileChangeApprovalDecision::Decline => {
             (ReviewDecision::Denied, Some(PatchApplyStatus::Declined))
         }
@@ -3770,6 +3793,27 @@ mod tests {
         assert_eq!(completion_status, None);
     }
 
+    #[test]
+    fn file_change_accept_partial_maps_to_approved_partial_patch() {
+        let (decision, completion_status) =
+            map_file_change_approval_decision(FileChangeApprovalDecision::AcceptPartial {
+                files: vec![codex_app_server_protocol::FileChangeApprovalSelection {
+                    path: "src/lib.rs".to_string(),
+                    hunks: Some(vec![0]),
+                }],
+            });
+        assert_eq!(
+            decision,
+            ReviewDecision::ApprovedPartialPatch {
+                accepted_files: vec![codex_protocol::protocol::PatchFileApproval {
+                    path: PathBuf::from("src/lib.rs"),
+                    hunks: Some(vec![0]),
+                }],
+            }
+        );
+        assert_eq!(completion_status, None);
+    }
+
     #[test]
     fn mcp_server_elicitation_turn_transition_error_maps_to_cancel() {
         let error = JSONRPCErrorError {
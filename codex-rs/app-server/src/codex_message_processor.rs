@@ -3,6 +3,8 @@ use crate::bespoke_event_handling::maybe_emit_hook_prompt_item_completed;
 use crate::command_exec::CommandExecManager;
 use crate::command_exec::StartCommandExecParams;
 use crate::config_manager::ConfigManager;
+use crate::content_search::ContentSearchSession;
+use crate::content_search::start_content_search_session;
 use crate::error_code::INPUT_TOO_LARGE_ERROR_CODE;
 use crate::error_code::INTERNAL_ERROR_CODE;
 use crate::error_code::INVALID_PARAMS_ERROR_CODE;
@@ -49,6 +51,12 @@ use codex_app_server_protocol::CommandExecParams;
 use codex_app_server_protocol::CommandExecResizeParams;
 use codex_app_server_protocol::CommandExecTerminateParams;
 use codex_app_server_protocol::CommandExecWriteParams;
+use codex_app_server_protocol::ContentSearchSessionStartParams;
+use codex_app_server_protocol::ContentSearchSessionStartResponse;
+use codex_app_server_protocol::ContentSearchSessionStopParams;
+use codex_app_server_protocol::ContentSearchSessionStopResponse;
+use codex_app_server_protocol::ContentSearchSessionUpdateParams;
+use codex_app_server_protocol::ContentSearchSessionUpdateResponse;
 use codex_app_server_protocol::ConversationGitInfo;
 use codex_app_server_protocol::ConversationSummary;
 use codex_app_server_protocol::DynamicToolSpec as ApiDynamicToolSpec;
@@ -500,6 +508,7 @@ pub(crate) struct CodexMessageProcessor {
     command_exec_manager: CommandExecManager,
     pending_fuzzy_searches: Arc<Mutex<HashMap<String, Arc<AtomicBool>>>>,
     fuzzy_search_sessions: Arc<Mutex<HashMap<String, FuzzyFileSearchSession>>>,
+    content_search_sessions: Arc<Mutex<HashMap<String, ContentSearchSession>>>,
     background_tasks: TaskTracker,
     feedback: CodexFeedback,
     log_db: Option<LogDbLayer>,
@@ -764,6 +773,7 @@ impl CodexMessageProcessor {
             command_exec_manager: CommandExecManager::default(),
             pending_fuzzy_searches: Arc::new(Mutex::new(HashMap::new())),
             fuzzy_search_sessions: Arc::new(Mutex::new(HashMap::new())),
+            content_search_sessions: Arc::new(Mutex::new(HashMap::new())),
             background_tasks: TaskTracker::new(),
             feedback,
             log_db,
@@ -1160,6 +1170,18 @@ impl CodexMessageProcessor {
                 self.fuzzy_file_search_session_stop(to_connection_request_id(request_id), params)
                     .await;
             }
+            ClientRequest::ContentSearchSessionStart { request_id, params } => {
+                self.content_search_session_start(to_connection_request_id(request_id), params)
+                    .await;
+            }
+            ClientRequest::ContentSearchSessionUpdate { request_id, params } => {
+                self.content_search_session_update(to_connection_request_id(request_id), params)
+                    .await;
+            }
+            ClientRequest::ContentSearchSessionStop { request_id, params } => {
+                self.content_search_session_stop(to_connection_request_id(request_id), params)
+                    .await;
+            }
             ClientRequest::OneOffCommandExec { request_id, params } => {
                 self.exec_one_off_command(to_connection_request_id(request_id), params)
                     .await;
@@ -8335,6 +8357,89 @@ impl CodexMessageProcessor {
             .await;
     }
 
+    async fn content_search_session_start(
+        &self,
+        request_id: ConnectionRequestId,
+        params: ContentSearchSessionStartParams,
+    ) {
+        if params.session_id.is_empty() {
+            let error = JSONRPCErrorError {
+                code: INVALID_REQUEST_ERROR_CODE,
+                message: "sessionId must not be empty".to_string(),
+                data: None,
+            };
+            self.outgoing.send_error(request_id, error).await;
+            return;
+        }
+
+        let session_id = params.session_id.clone();
+        match start_content_search_session(params, self.outgoing.clone()) {
+            Ok(session) => {
+                self.content_search_sessions
+                    .lock()
+                    .await
+                    .insert(session_id, session);
+                self.outgoing
+                    .send_response(request_id, ContentSearchSessionStartResponse {})
+                    .await;
+            }
+            Err(err) => {
+                let error = JSONRPCErrorError {
+                    code: INVALID_REQUEST_ERROR_CODE,
+                    message: format!("failed to start content search session: {err}"),
+                    data: None,
+                };
+                self.outgoing.send_error(request_id, error).await;
+            }
+        }
+    }
+
+    async fn content_search_session_update(
+        &self,
+        request_id: ConnectionRequestId,
+        params: ContentSearchSessionUpdateParams,
+    ) {
+        let ContentSearchSessionUpdateParams { session_id, query } = params;
+        let found = {
+            let sessions = self.content_search_sessions.lock().await;
+            if let Some(session) = sessions.get(&session_id) {
+                session.update_query(query);
+                true
+            } else {
+                false
+            }
+        };
+        if !found {
+            let error = JSONRPCErrorError {
+                code: INVALID_REQUEST_ERROR_CODE,
+                message: format!("content search session not found: {session_id}"),
+                data: None,
+            };
+            self.outgoing.send_error(request_id, error).await;
+            return;
+        }
+
+        self.outgoing
+            .send_response(request_id, ContentSearchSessionUpdateResponse {})
+            .await;
+    }
+
+    async fn content_search_session_stop(
+        &self,
+        request_id: ConnectionRequestId,
+        params: ContentSearchSessionStopParams,
+    ) {
+        let ContentSearchSessionStopParams { session_id } = params;
+        {
+            let mut sessions = self.content_search_sessions.lock().await;
+            sessions.remove(&session_id);
+        }
+
+        self.outgoing
+            .send_response(request_id, ContentSearchSessionStopResponse {})
+            .await;
+    }
+
     async fn upload_feedback(&self, request_id: ConnectionRequestId, params: FeedbackUploadParams) {
         if !self.config.feedback_enabled {
             let error = JSONRPCErrorError {
@@ -10656,6 +10761,7 @@ mod tests {
             auth: None,
             aws: None,
             wire_api: WireApi::Responses,
+            messages: None,
             query_params: None,
             http_headers: None,
             env_http_headers: None,
@@ -318,6 +318,11 @@ fn map_hooks_requirements_to_api(hooks: ManagedHooksRequirementsToml) -> Managed
         session_start,
         user_prompt_submit,
         stop,
+        pre_compact,
+        session_end,
+        subagent_start,
+        subagent_stop,
+        notification,
     } = hooks;
 
     ManagedHooksRequirements {
@@ -329,6 +334,11 @@ fn map_hooks_requirements_to_api(hooks: ManagedHooksRequirementsToml) -> Managed
         session_start: map_hook_matcher_groups_to_api(session_start),
         user_prompt_submit: map_hook_matcher_groups_to_api(user_prompt_submit),
         stop: map_hook_matcher_groups_to_api(stop),
+        pre_compact: map_hook_matcher_groups_to_api(pre_compact),
+        session_end: map_hook_matcher_groups_to_api(session_end),
+        subagent_start: map_hook_matcher_groups_to_api(subagent_start),
+        subagent_stop: map_hook_matcher_groups_to_api(subagent_stop),
+        notification: map_hook_matcher_groups_to_api(notification),
     }
 }
 
@@ -365,8 +375,28 @@ fn map_hook_handler_to_api(handler: CoreHookHandlerConfig) -> ConfiguredHookHand
             r#async,
             status_message,
         },
-        CoreHookHandlerConfig::Prompt {} => ConfiguredHookHandler::Prompt {},
-        CoreHookHandlerConfig::Agent {} => ConfiguredHookHandler::Agent {},
+        CoreHookHandlerConfig::Prompt {
+            prompt,
+            model,
+            timeout_sec,
+            status_message,
+        } => ConfiguredHookHandler::Prompt {
+            prompt,
+            model,
+            timeout_sec,
+            status_message,
+        },
+        CoreHookHandlerConfig::Agent {
+            prompt,
+            model,
+            timeout_sec,
+            status_message,
+        } => ConfiguredHookHandler::Agent {
+            prompt,
+            model,
+            timeout_sec,
+            status_message,
+        },
     }
 }
 
@@ -650,6 +680,11 @@ mod tests {
                 session_start: Vec::new(),
                 user_prompt_submit: Vec::new(),
                 stop: Vec::new(),
+                pre_compact: Vec::new(),
+                session_end: Vec::new(),
+                subagent_start: Vec::new(),
+                subagent_stop: Vec::new(),
+                notification: Vec::new(),
             }),
         );
         assert_eq!(
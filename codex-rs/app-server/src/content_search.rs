@@ -0,0 +1,190 @@
+use std::num::NonZero;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+
+use codex_app_server_protocol::ContentSearchMatchRange;
+use codex_app_server_protocol::ContentSearchResult;
+use codex_app_server_protocol::ContentSearchSessionCompletedNotification;
+use codex_app_server_protocol::ContentSearchSessionStartParams;
+use codex_app_server_protocol::ContentSearchSessionUpdatedNotification;
+use codex_app_server_protocol::ServerNotification;
+use codex_file_search as file_search;
+
+use crate::outgoing_message::OutgoingMessageSender;
+
+const MATCH_LIMIT: usize = 200;
+const MAX_THREADS: usize = 12;
+
+pub(crate) struct ContentSearchSession {
+    session: file_search::ContentSearchSession,
+    shared: Arc<SessionShared>,
+}
+
+impl ContentSearchSession {
+    pub(crate) fn update_query(&self, query: String) {
+        if self.shared.canceled.load(Ordering::Relaxed) {
+            return;
+        }
+        {
+            #[expect(clippy::unwrap_used)]
+            let mut latest_query = self.shared.latest_query.lock().unwrap();
+            *latest_query = query.clone();
+        }
+        self.session.update_query(&query);
+    }
+}
+
+impl Drop for ContentSearchSession {
+    fn drop(&mut self) {
+        self.shared.canceled.store(true, Ordering::Relaxed);
+    }
+}
+
+pub(crate) fn start_content_search_session(
+    params: ContentSearchSessionStartParams,
+    outgoing: Arc<OutgoingMessageSender>,
+) -> anyhow::Result<ContentSearchSession> {
+    let ContentSearchSessionStartParams {
+        session_id,
+        roots,
+        regex,
+        case_sensitive,
+        file_types,
+        globs,
+    } = params;
+    #[expect(clippy::expect_used)]
+    let limit = NonZero::new(MATCH_LIMIT).expect("MATCH_LIMIT should be a valid non-zero usize");
+    let cores = std::thread::available_parallelism()
+        .map(std::num::NonZero::get)
+        .unwrap_or(1);
+    let threads = cores.min(MAX_THREADS);
+    #[expect(clippy::expect_used)]
+    let threads = NonZero::new(threads.max(1)).expect("threads should be non-zero");
+    let search_dirs: Vec<PathBuf> = roots.iter().map(PathBuf::from).collect();
+    let canceled = Arc::new(AtomicBool::new(false));
+
+    let shared = Arc::new(SessionShared {
+        session_id,
+        latest_query: Mutex::new(String::new()),
+        outgoing,
+        runtime: tokio::runtime::Handle::current(),
+        canceled: canceled.clone(),
+    });
+
+    let reporter = Arc::new(SessionReporterImpl {
+        shared: shared.clone(),
+    });
+    let session = file_search::create_content_session(
+        search_dirs,
+        file_search::ContentSearchOptions {
+            limit,
+            threads,
+            regex,
+            case_sensitive,
+            file_types,
+            globs,
+            ..Default::default()
+        },
+        reporter,
+        Some(canceled),
+    )?;
+
+    Ok(ContentSearchSession { session, shared })
+}
+
+struct SessionShared {
+    session_id: String,
+    latest_query: Mutex<String>,
+    outgoing: Arc<OutgoingMessageSender>,
+    runtime: tokio::runtime::Handle,
+    canceled: Arc<AtomicBool>,
+}
+
+struct SessionReporterImpl {
+    shared: Arc<SessionShared>,
+}
+
+impl SessionReporterImpl {
+    fn send_snapshot(&self, snapshot: &file_search::ContentSearchSnapshot) {
+        if self.shared.canceled.load(Ordering::Relaxed) {
+            return;
+        }
+
+        let query = {
+            #[expect(clippy::unwrap_used)]
+            self.shared.latest_query.lock().unwrap().clone()
+        };
+        if snapshot.query != query {
+            return;
+        }
+
+        let notification = ServerNotification::ContentSearchSessionUpdated(
+            ContentSearchSessionUpdatedNotification {
+                session_id: self.shared.session_id.clone(),
+                query,
+                matches: snapshot
+                    .matches
+                    .iter()
+                    .map(to_content_search_result)
+                    .collect(),
+                total_match_count: saturating_u32(snapshot.total_match_count),
+                error: snapshot.error.clone(),
+            },
+        );
+        let outgoing = self.shared.outgoing.clone();
+        self.shared.runtime.spawn(async move {
+            outgoing.send_server_notification(notification).await;
+        });
+    }
+
+    fn send_complete(&self) {
+        if self.shared.canceled.load(Ordering::Relaxed) {
+            return;
+        }
+        let session_id = self.shared.session_id.clone();
+        let outgoing = self.shared.outgoing.clone();
+        self.shared.runtime.spawn(async move {
+            let notification = ServerNotification::ContentSearchSessionCompleted(
+                ContentSearchSessionCompletedNotification { session_id },
+            );
+            outgoing.send_server_notification(notification).await;
+        });
+    }
+}
+
+impl file_search::ContentSearchReporter for SessionReporterImpl {
+    fn on_update(&self, snapshot: &file_search::ContentSearchSnapshot) {
+        self.send_snapshot(snapshot);
+    }
+
+    fn on_complete(&self) {
+        self.send_complete();
+    }
+}
+
+fn to_content_search_result(content_match: &file_search::ContentMatch) -> ContentSearchResult {
+    let file_name = content_match.path.file_name().unwrap_or_default();
+    ContentSearchResult {
+        root: content_match.root.to_string_lossy().to_string(),
+        path: content_match.path.to_string_lossy().to_string(),
+        file_name: file_name.to_string_lossy().to_string(),
+        line_number: saturating_u32(content_match.line_number),
+        column: saturating_u32(content_match.column),
+        preview: content_match.preview.clone(),
+        ranges: content_match
+            .ranges
+            .iter()
+            .map(|range| ContentSearchMatchRange {
+                start: saturating_u32(range.start),
+                end: saturating_u32(range.end),
+            })
+            .collect(),
+    }
+}
+
+fn saturating_u32(value: usize) -> u32 {
+    u32::try_from(value).unwrap_or(u32::MAX)
+}
@@ -74,6 +74,7 @@ mod command_exec;
 mod config;
 mod config_api;
 mod config_manager;
+mod content_search;
 mod config_manager_service;
 mod device_key_api;
 mod dynamic_tools;
@@ -23,6 +23,7 @@ use codex_app_server_protocol::CommandExecWriteParams;
 use codex_app_server_protocol::ConfigBatchWriteParams;
 use codex_app_server_protocol::ConfigReadParams;
 use codex_app_server_protocol::ConfigValueWriteParams;
+use codex_app_server_protocol::ContentSearchSessionStartParams;
 use codex_app_server_protocol::ExperimentalFeatureListParams;
 use codex_app_server_protocol::FeedbackUploadParams;
 use codex_app_server_protocol::FsCopyParams;
@@ -1067,6 +1068,39 @@ impl McpProcess {
             .await
     }
 
+    pub async fn send_content_search_session_start_request(
+        &mut self,
+        params: ContentSearchSessionStartParams,
+    ) -> anyhow::Result<i64> {
+        let params = Some(serde_json::to_value(params)?);
+        self.send_request("contentSearch/sessionStart", params)
+            .await
+    }
+
+    pub async fn send_content_search_session_update_request(
+        &mut self,
+        session_id: &str,
+        query: &str,
+    ) -> anyhow::Result<i64> {
+        let params = serde_json::json!({
+            "sessionId": session_id,
+            "query": query,
+        });
+        self.send_request("contentSearch/sessionUpdate", Some(params))
+            .await
+    }
+
+    pub async fn send_content_search_session_stop_request(
+        &mut self,
+        session_id: &str,
+    ) -> anyhow::Result<i64> {
+        let params = serde_json::json!({
+            "sessionId": session_id,
+        });
+        self.send_request("contentSearch/sessionStop", Some(params))
+            .await
+    }
+
     async fn send_request(
         &mut self,
         method: &str,
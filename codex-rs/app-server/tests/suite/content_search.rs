@@ -0,0 +1,242 @@
+use anyhow::Result;
+use anyhow::anyhow;
+use app_test_support::McpProcess;
+use codex_app_server_protocol::ContentSearchMatchRange;
+use codex_app_server_protocol::ContentSearchSessionCompletedNotification;
+use codex_app_server_protocol::ContentSearchSessionStartParams;
+use codex_app_server_protocol::ContentSearchSessionUpdatedNotification;
+use codex_app_server_protocol::RequestId;
+use pretty_assertions::assert_eq;
+use std::path::Path;
+use tempfile::TempDir;
+use tokio::time::timeout;
+
+#[cfg(any(target_os = "macos", windows))]
+const DEFAULT_READ_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(60);
+#[cfg(not(any(target_os = "macos", windows)))]
+const DEFAULT_READ_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+const SESSION_UPDATED_METHOD: &str = "contentSearch/sessionUpdated";
+const SESSION_COMPLETED_METHOD: &str = "contentSearch/sessionCompleted";
+
+fn create_config_toml(codex_home: &Path) -> std::io::Result<()> {
+    std::fs::write(
+        codex_home.join("config.toml"),
+        r#"
+model = "mock-model"
+approval_policy = "never"
+sandbox_mode = "danger-full-access"
+
+[features]
+shell_snapshot = false
+"#,
+    )
+}
+
+async fn initialized_mcp(codex_home: &TempDir) -> Result<McpProcess> {
+    create_config_toml(codex_home.path())?;
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+    Ok(mcp)
+}
+
+fn start_params(session_id: &str, root: &Path) -> ContentSearchSessionStartParams {
+    ContentSearchSessionStartParams {
+        session_id: session_id.to_string(),
+        roots: vec![root.to_string_lossy().to_string()],
+        regex: false,
+        case_sensitive: false,
+        file_types: Vec::new(),
+        globs: Vec::new(),
+    }
+}
+
+async fn start_session(
+    mcp: &mut McpProcess,
+    params: ContentSearchSessionStartParams,
+) -> Result<()> {
+    let request_id = mcp
+        .send_content_search_session_start_request(params)
+        .await?;
+    timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    Ok(())
+}
+
+async fn update_session(mcp: &mut McpProcess, session_id: &str, query: &str) -> Result<()> {
+    let request_id = mcp
+        .send_content_search_session_update_request(session_id, query)
+        .await?;
+    timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    Ok(())
+}
+
+/// Waits for the first update for `query` that satisfies `predicate`.
+async fn wait_for_session_updated(
+    mcp: &mut McpProcess,
+    session_id: &str,
+    query: &str,
+    predicate: impl Fn(&ContentSearchSessionUpdatedNotification) -> bool,
+) -> Result<ContentSearchSessionUpdatedNotification> {
+    let description = format!("content search update for sessionId={session_id}, query={query}");
+    let notification = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_matching_notification(&description, |notification| {
+            if notification.method != SESSION_UPDATED_METHOD {
+                return false;
+            }
+            let Some(params) = notification.params.as_ref() else {
+                return false;
+            };
+            let Ok(payload) =
+                serde_json::from_value::<ContentSearchSessionUpdatedNotification>(params.clone())
+            else {
+                return false;
+            };
+            payload.session_id == session_id && payload.query == query && predicate(&payload)
+        }),
+    )
+    .await??;
+    let params = notification
+        .params
+        .ok_or_else(|| anyhow!("missing notification params"))?;
+    Ok(serde_json::from_value(params)?)
+}
+
+async fn wait_for_session_completed(mcp: &mut McpProcess, session_id: &str) -> Result<()> {
+    let notification = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_notification_message(SESSION_COMPLETED_METHOD),
+    )
+    .await??;
+    let params = notification
+        .params
+        .ok_or_else(|| anyhow!("missing notification params"))?;
+    let payload = serde_json::from_value::<ContentSearchSessionCompletedNotification>(params)?;
+    assert_eq!(payload.session_id, session_id);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_content_search_session_streams_line_matches() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let root = TempDir::new()?;
+    std::fs::create_dir_all(root.path().join("src"))?;
+    std::fs::write(
+        root.path().join("src/lib.rs"),
+        "fn main() {}\nlet needle = Needle::new();\n",
+    )?;
+    std::fs::write(root.path().join("notes.md"), "no needle here\n")?;
+    let mut mcp = initialized_mcp(&codex_home).await?;
+
+    let session_id = "content-session";
+    start_session(
+        &mut mcp,
+        ContentSearchSessionStartParams {
+            file_types: vec!["rust".to_string()],
+            ..start_params(session_id, root.path())
+        },
+    )
+    .await?;
+    update_session(&mut mcp, session_id, "needle").await?;
+
+    let payload = wait_for_session_updated(&mut mcp, session_id, "needle", |payload| {
+        !payload.matches.is_empty()
+    })
+    .await?;
+    wait_for_session_completed(&mut mcp, session_id).await?;
+
+    assert_eq!(payload.total_match_count, 1);
+    assert_eq!(payload.error, None);
+    let content_match = &payload.matches[0];
+    assert_eq!(
+        content_match.path,
+        Path::new("src").join("lib.rs").to_string_lossy()
+    );
+    assert_eq!(content_match.file_name, "lib.rs");
+    assert_eq!(content_match.line_number, 2);
+    assert_eq!(content_match.column, 5);
+    assert_eq!(content_match.preview, "let needle = Needle::new();");
+    assert_eq!(
+        content_match.ranges,
+        vec![
+            ContentSearchMatchRange { start: 4, end: 10 },
+            ContentSearchMatchRange { start: 13, end: 19 },
+        ]
+    );
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_content_search_session_reports_invalid_regex() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let root = TempDir::new()?;
+    std::fs::write(root.path().join("a.txt"), "alpha\n")?;
+    let mut mcp = initialized_mcp(&codex_home).await?;
+
+    let session_id = "regex-session";
+    start_session(
+        &mut mcp,
+        ContentSearchSessionStartParams {
+            regex: true,
+            ..start_params(session_id, root.path())
+        },
+    )
+    .await?;
+    update_session(&mut mcp, session_id, "(alpha").await?;
+
+    let payload = wait_for_session_updated(&mut mcp, session_id, "(alpha", |_| true).await?;
+    assert_eq!(payload.matches, Vec::new());
+    assert!(
+        payload
+            .error
+            .as_deref()
+            .is_some_and(|error| error.starts_with("invalid regex `(alpha`"))
+    );
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_content_search_session_rejects_unknown_file_types_and_sessions() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let root = TempDir::new()?;
+    let mut mcp = initialized_mcp(&codex_home).await?;
+
+    let request_id = mcp
+        .send_content_search_session_start_request(ContentSearchSessionStartParams {
+            file_types: vec!["not-a-type".to_string()],
+            ..start_params("bad-types", root.path())
+        })
+        .await?;
+    let err = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_error_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    assert_eq!(err.error.code, -32600);
+    assert!(
+        err.error
+            .message
+            .starts_with("failed to start content search session:")
+    );
+
+    let request_id = mcp
+        .send_content_search_session_update_request("missing", "alpha")
+        .await?;
+    let err = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_error_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    assert_eq!(
+        err.error.message,
+        "content search session not found: missing"
+    );
+    Ok(())
+}
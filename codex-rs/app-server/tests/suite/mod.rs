@@ -1,4 +1,5 @@
 mod auth;
+mod content_search;
 mod conversation_summary;
 mod fuzzy_file_search;
 mod v2;
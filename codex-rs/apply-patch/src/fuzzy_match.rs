@@ -0,0 +1,573 @@
+//! Scored fallback for locating chunk lines that [`seek_sequence`] could not
+//! find, plus the diagnostics reported when no acceptable location exists.
+//!
+//! Every candidate window is scored by the total edit distance between its
+//! lines and the pattern after normalisation (see [`normalise`]), relative to
+//! the total length of the compared lines. A window is only used when it is
+//! clearly similar ([`FUZZY_MATCH_THRESHOLD`]), every line in it resembles its
+//! counterpart, the lines the chunk removes match up to whitespace, and no
+//! other window scores nearly as well. Otherwise the best window is reported
+//! back to the caller as a [`ContextMismatch`] so the model can see how the
+//! file differs from what it expected.
+//!
+//! [`seek_sequence`]: crate::seek_sequence::seek_sequence
+
+use std::fmt;
+use std::path::PathBuf;
+
+use crate::seek_sequence::normalise;
+
+/// Minimum overall similarity for a fuzzy match to be applied.
+const FUZZY_MATCH_THRESHOLD: f64 = 0.9;
+
+/// Every line of a fuzzy match must be at least this similar to the pattern
+/// line it replaces.
+const FUZZY_MIN_LINE_SIMILARITY: f64 = 0.6;
+
+/// The best window must beat every other window by at least this much to be
+/// applied; otherwise the match is ambiguous.
+const FUZZY_AMBIGUITY_MARGIN: f64 = 0.05;
+
+/// Windows below this similarity are not worth showing as the closest match.
+const DIAGNOSTIC_MIN_SIMILARITY: f64 = 0.5;
+
+/// Line pairs whose cheap similarity upper bound falls below this value are
+/// not scored with a full edit distance.
+const EXACT_SCORING_FLOOR: f64 = 0.3;
+
+/// Lines are truncated to this many characters before scoring to bound the
+/// cost of the edit-distance computation.
+const MAX_SCORED_LINE_CHARS: usize = 160;
+
+/// Upper bound on the edit-distance cells computed for one search. Searches
+/// that run out of budget never apply a match, since a better or ambiguous
+/// window may have been left unscored.
+const MAX_SCORED_CELLS: usize = 20_000_000;
+
+/// Reported when the lines a chunk expects could not be located in a file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContextMismatch {
+    pub path: PathBuf,
+    /// The lines the chunk expected to find.
+    pub expected: Vec<String>,
+    /// Whether `expected` is the single `@@` context line of the chunk rather
+    /// than the lines it replaces.
+    pub is_change_context: bool,
+    /// The region of the file that most resembles `expected`, if any region
+    /// was similar enough to be worth reporting.
+    pub closest: Option<ClosestCandidate>,
+}
+
+/// The region of a file that most closely resembles the expected lines.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClosestCandidate {
+    /// 1-based line number of the first line of the region.
+    pub line_number: usize,
+    /// Similarity between the region and the expected lines, from 0 to 1.
+    pub similarity: f64,
+    /// Lines of the region that differ from the expected lines.
+    pub differing_lines: Vec<LineMismatch>,
+}
+
+/// One line of a [`ClosestCandidate`] that differs from the expected line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineMismatch {
+    /// 1-based line number in the file.
+    pub line_number: usize,
+    pub expected: String,
+    pub actual: String,
+}
+
+impl fmt::Display for ContextMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_change_context {
+            write!(
+                f,
+                "Failed to find context '{}' in {}",
+                self.expected.join("\n"),
+                self.path.display()
+            )?;
+        } else {
+            write!(
+                f,
+                "Failed to find expected lines in {}:\n{}",
+                self.path.display(),
+                self.expected.join("\n")
+            )?;
+        }
+        if let Some(closest) = &self.closest {
+            write!(
+                f,
+                "\nClosest match starts at line {} ({:.0}% similar):",
+                closest.line_number,
+                closest.similarity * 100.0
+            )?;
+            for mismatch in &closest.differing_lines {
+                write!(
+                    f,
+                    "\n  line {}:\n    expected: {}\n    actual:   {}",
+                    mismatch.line_number, mismatch.expected, mismatch.actual
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Result of scoring every candidate window for a pattern.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct FuzzySearch {
+    best: Option<ScoredWindow>,
+    runner_up_similarity: f64,
+    exhausted_budget: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct ScoredWindow {
+    start: usize,
+    similarity: f64,
+    min_line_similarity: f64,
+    removed_lines_match: bool,
+}
+
+impl FuzzySearch {
+    /// Returns the start of the best window if it is similar enough, and
+    /// unambiguous enough, to be applied.
+    pub(crate) fn accepted_start(&self) -> Option<usize> {
+        let best = self.best.as_ref()?;
+        let accepted = !self.exhausted_budget
+            && best.removed_lines_match
+            && best.similarity >= FUZZY_MATCH_THRESHOLD
+            && best.min_line_similarity >= FUZZY_MIN_LINE_SIMILARITY
+            && best.similarity - self.runner_up_similarity >= FUZZY_AMBIGUITY_MARGIN;
+        accepted.then_some(best.start)
+    }
+
+    /// Describes the best window for diagnostics, if it is similar enough to
+    /// be useful.
+    pub(crate) fn closest_candidate(
+        &self,
+        lines: &[String],
+        pattern: &[String],
+    ) -> Option<ClosestCandidate> {
+        let best = self
+            .best
+            .as_ref()
+            .filter(|best| best.similarity >= DIAGNOSTIC_MIN_SIMILARITY)?;
+        let differing_lines = pattern
+            .iter()
+            .enumerate()
+            .filter_map(|(offset, expected)| {
+                let actual = &lines[best.start + offset];
+                (actual != expected).then(|| LineMismatch {
+                    line_number: best.start + offset + 1,
+                    expected: expected.clone(),
+                    actual: actual.clone(),
+                })
+            })
+            .collect();
+        Some(ClosestCandidate {
+            line_number: best.start + 1,
+            similarity: best.similarity,
+            differing_lines,
+        })
+    }
+}
+
+/// Scores every window of `lines` at or after `start` against `pattern`. When
+/// `eof` is true only the window ending at the last line is considered.
+///
+/// `removed_lines[i]` marks pattern lines that the chunk deletes rather than
+/// keeps as context. Those must match the file up to whitespace for a window
+/// to be applied; only context lines are matched approximately.
+pub(crate) fn fuzzy_seek_sequence(
+    lines: &[String],
+    pattern: &[String],
+    removed_lines: &[bool],
+    start: usize,
+    eof: bool,
+) -> FuzzySearch {
+    let mut search = FuzzySearch {
+        best: None,
+        runner_up_similarity: 0.0,
+        exhausted_budget: false,
+    };
+    if pattern.is_empty() || pattern.len() > lines.len() {
+        return search;
+    }
+    let last_start = lines.len() - pattern.len();
+    let first_start = if eof { last_start } else { start };
+    if first_start > last_start {
+        return search;
+    }
+
+    let pattern: Vec<ScoredLine> = pattern.iter().map(|line| ScoredLine::new(line)).collect();
+    let lines: Vec<ScoredLine> = lines.iter().map(|line| ScoredLine::new(line)).collect();
+    let mut remaining_cells = MAX_SCORED_CELLS;
+    for window_start in first_start..=last_start {
+        let window = &lines[window_start..window_start + pattern.len()];
+        // A window that cannot reach the diagnostic floor or beat the current
+        // runner-up can change neither the outcome nor the report.
+        let upper_bound = window_similarity_upper_bound(window, &pattern);
+        if upper_bound < DIAGNOSTIC_MIN_SIMILARITY || upper_bound <= search.runner_up_similarity {
+            continue;
+        }
+        let Some(window) = score_window(window, &pattern, removed_lines, &mut remaining_cells)
+        else {
+            search.exhausted_budget = true;
+            break;
+        };
+        let window = ScoredWindow {
+            start: window_start,
+            ..window
+        };
+        match &search.best {
+            Some(best) if best.similarity >= window.similarity => {
+                search.runner_up_similarity = search.runner_up_similarity.max(window.similarity);
+            }
+            Some(best) => {
+                search.runner_up_similarity = best.similarity;
+                search.best = Some(window);
+            }
+            None => search.best = Some(window),
+        }
+    }
+    search
+}
+
+/// A normalised line along with a character histogram used to cheaply bound
+/// its edit distance to other lines.
+struct ScoredLine {
+    /// The whole line with runs of whitespace collapsed to a single space,
+    /// used to compare removed lines.
+    collapsed: String,
+    chars: Vec<char>,
+    histogram: [u16; 128],
+}
+
+impl ScoredLine {
+    fn new(line: &str) -> Self {
+        // Collapse runs of inner whitespace so that re-wrapped arguments or
+        // alignment changes only count once.
+        let collapsed = normalise(line)
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        let chars: Vec<char> = collapsed.chars().take(MAX_SCORED_LINE_CHARS).collect();
+        let mut histogram = [0u16; 128];
+        for ch in &chars {
+            histogram[histogram_bucket(*ch)] += 1;
+        }
+        Self {
+            collapsed,
+            chars,
+            histogram,
+        }
+    }
+}
+
+fn histogram_bucket(ch: char) -> usize {
+    // Non-ASCII characters share the last bucket, which keeps the bound valid
+    // (just looser) for them.
+    let code = ch as usize;
+    if code < 127 { code } else { 127 }
+}
+
+/// The best similarity `window` could score, from the histogram bounds alone.
+fn window_similarity_upper_bound(window: &[ScoredLine], pattern: &[ScoredLine]) -> f64 {
+    let (total_distance, total_length) = window.iter().zip(pattern).fold(
+        (0usize, 0usize),
+        |(total_distance, total_length), (actual, expected)| {
+            (
+                total_distance + distance_lower_bound(actual, expected),
+                total_length + actual.chars.len().max(expected.chars.len()),
+            )
+        },
+    );
+    if total_length == 0 {
+        1.0
+    } else {
+        1.0 - total_distance as f64 / total_length as f64
+    }
+}
+
+/// Scores `window` against `pattern`, or returns `None` once scoring it would
+/// exceed `remaining_cells`.
+fn score_window(
+    window: &[ScoredLine],
+    pattern: &[ScoredLine],
+    removed_lines: &[bool],
+    remaining_cells: &mut usize,
+) -> Option<ScoredWindow> {
+    let mut total_distance = 0.0;
+    let mut total_length = 0.0;
+    let mut min_line_similarity: f64 = 1.0;
+    let mut removed_lines_match = true;
+    for (index, (actual, expected)) in window.iter().zip(pattern).enumerate() {
+        if removed_lines.get(index).copied().unwrap_or(false)
+            && actual.collapsed != expected.collapsed
+        {
+            removed_lines_match = false;
+        }
+        let length = actual.chars.len().max(expected.chars.len());
+        if length == 0 {
+            continue;
+        }
+        let length = length as f64;
+        let upper_bound = 1.0 - distance_lower_bound(actual, expected) as f64 / length;
+        let similarity = if upper_bound < EXACT_SCORING_FLOOR {
+            upper_bound
+        } else {
+            let cells = actual.chars.len() * expected.chars.len();
+            *remaining_cells = remaining_cells.checked_sub(cells)?;
+            1.0 - levenshtein(&actual.chars, &expected.chars) as f64 / length
+        };
+        min_line_similarity = min_line_similarity.min(similarity);
+        total_distance += (1.0 - similarity) * length;
+        total_length += length;
+    }
+    let similarity = if total_length == 0.0 {
+        1.0
+    } else {
+        1.0 - total_distance / total_length
+    };
+    Some(ScoredWindow {
+        start: 0,
+        similarity,
+        min_line_similarity,
+        removed_lines_match,
+    })
+}
+
+/// A lower bound on the edit distance between two lines: every character
+/// whose count differs must be inserted, deleted or substituted.
+fn distance_lower_bound(lhs: &ScoredLine, rhs: &ScoredLine) -> usize {
+    let (surplus, deficit) = lhs.histogram.iter().zip(&rhs.histogram).fold(
+        (0usize, 0usize),
+        |(surplus, deficit), (lhs, rhs)| {
+            if lhs > rhs {
+                (surplus + usize::from(lhs - rhs), deficit)
+            } else {
+                (surplus, deficit + usize::from(rhs - lhs))
+            }
+        },
+    );
+    surplus.max(deficit)
+}
+
+fn levenshtein(lhs: &[char], rhs: &[char]) -> usize {
+    if lhs.is_empty() {
+        return rhs.len();
+    }
+    let mut previous: Vec<usize> = (0..=rhs.len()).collect();
+    let mut current = vec![0; rhs.len() + 1];
+    for (i, lhs_char) in lhs.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, rhs_char) in rhs.iter().enumerate() {
+            let substitution = previous[j] + usize::from(lhs_char != rhs_char);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[rhs.len()]
+}
+
+/// A consistent change in leading whitespace between the lines a chunk
+/// expected and the lines actually found in the file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum IndentShift {
+    /// The file is indented further than the patch by this prefix.
+    Add(String),
+    /// The patch is indented further than the file by this prefix.
+    Remove(String),
+}
+
+/// Detects whether every non-blank line of `actual` is `expected` with the same
+/// amount of leading whitespace added or removed.
+pub(crate) fn detect_indent_shift(actual: &[String], expected: &[String]) -> Option<IndentShift> {
+    let mut shift: Option<IndentShift> = None;
+    for (actual, expected) in actual.iter().zip(expected) {
+        if actual.trim().is_empty() || expected.trim().is_empty() {
+            continue;
+        }
+        let actual_indent = leading_whitespace(actual);
+        let expected_indent = leading_whitespace(expected);
+        let line_shift = if actual_indent == expected_indent {
+            return None;
+        } else if let Some(extra) = actual_indent.strip_prefix(expected_indent) {
+            IndentShift::Add(extra.to_string())
+        } else if let Some(extra) = expected_indent.strip_prefix(actual_indent) {
+            IndentShift::Remove(extra.to_string())
+        } else {
+            return None;
+        };
+        match &shift {
+            Some(existing) if *existing != line_shift => return None,
+            Some(_) => {}
+            None => shift = Some(line_shift),
+        }
+    }
+    shift
+}
+
+/// Re-indents `lines` by `shift`, leaving blank lines untouched.
+pub(crate) fn apply_indent_shift(lines: &[String], shift: &IndentShift) -> Vec<String> {
+    lines
+        .iter()
+        .map(|line| {
+            if line.trim().is_empty() {
+                return line.clone();
+            }
+            match shift {
+                IndentShift::Add(prefix) => format!("{prefix}{line}"),
+                IndentShift::Remove(prefix) => {
+                    let indent = leading_whitespace(line);
+                    match indent.strip_suffix(prefix.as_str()) {
+                        Some(kept) => format!("{kept}{}", &line[indent.len()..]),
+                        None => line.clone(),
+                    }
+                }
+            }
+        })
+        .collect()
+}
+
+fn leading_whitespace(line: &str) -> &str {
+    &line[..line.len() - line.trim_start().len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::string::ToString;
+
+    fn to_vec(strings: &[&str]) -> Vec<String> {
+        strings.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn fuzzy_match_accepts_small_edits() {
+        let lines = to_vec(&[
+            "fn main() {",
+            "    let value = compute(alpha, beta);",
+            "    println!(\"{value}\");",
+            "}",
+        ]);
+        let pattern = to_vec(&[
+            "    let value = compute(alpha,beta);",
+            "    println!(\"{value}\");",
+        ]);
+        let search =
+            fuzzy_seek_sequence(&lines, &pattern, &[], /*start*/ 0, /*eof*/ false);
+        assert_eq!(search.accepted_start(), Some(1));
+    }
+
+    #[test]
+    fn fuzzy_match_requires_removed_lines_to_match() {
+        let lines = to_vec(&[
+            "fn main() {",
+            "    let value = compute(alpha, beta);",
+            "    println!(\"{value}\");",
+            "}",
+        ]);
+        let pattern = to_vec(&[
+            "    let value = compute(alpha,beta);",
+            "    println!(\"{value}\");",
+        ]);
+        let search = fuzzy_seek_sequence(
+            &lines,
+            &pattern,
+            &[true, false],
+            /*start*/ 0,
+            /*eof*/ false,
+        );
+        assert_eq!(search.accepted_start(), None);
+
+        let pattern = to_vec(&[
+            "    let value =  compute(alpha, beta);",
+            "    println!(\"{value}\")",
+        ]);
+        let search = fuzzy_seek_sequence(
+            &lines,
+            &pattern,
+            &[true, false],
+            /*start*/ 0,
+            /*eof*/ false,
+        );
+        assert_eq!(search.accepted_start(), Some(1));
+    }
+
+    #[test]
+    fn fuzzy_match_rejects_dissimilar_lines() {
+        let lines = to_vec(&["alpha", "beta", "gamma"]);
+        let pattern = to_vec(&["delta", "epsilon"]);
+        let search =
+            fuzzy_seek_sequence(&lines, &pattern, &[], /*start*/ 0, /*eof*/ false);
+        assert_eq!(search.accepted_start(), None);
+        assert_eq!(search.closest_candidate(&lines, &pattern), None);
+    }
+
+    #[test]
+    fn fuzzy_match_rejects_ambiguous_windows() {
+        let lines = to_vec(&["let total = a + b;", "other", "let total = a + c;"]);
+        let pattern = to_vec(&["let total = a + d;"]);
+        let search =
+            fuzzy_seek_sequence(&lines, &pattern, &[], /*start*/ 0, /*eof*/ false);
+        assert_eq!(search.accepted_start(), None);
+    }
+
+    #[test]
+    fn closest_candidate_reports_differing_lines() {
+        let lines = to_vec(&["header", "let x = 1;", "let y = 2;", "footer"]);
+        let pattern = to_vec(&["let x = 1;", "let y = 3;"]);
+        let search =
+            fuzzy_seek_sequence(&lines, &pattern, &[], /*start*/ 0, /*eof*/ false);
+        let closest = search
+            .closest_candidate(&lines, &pattern)
+            .expect("closest candidate");
+        assert_eq!(closest.line_number, 2);
+        assert_eq!(
+            closest.differing_lines,
+            vec![LineMismatch {
+                line_number: 3,
+                expected: "let y = 3;".to_string(),
+                actual: "let y = 2;".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn detects_consistent_indent_shift() {
+        let actual = to_vec(&["        if ready {", "", "            go();"]);
+        let expected = to_vec(&["    if ready {", "", "        go();"]);
+        assert_eq!(
+            detect_indent_shift(&actual, &expected),
+            Some(IndentShift::Add("    ".to_string()))
+        );
+        assert_eq!(
+            detect_indent_shift(&expected, &actual),
+            Some(IndentShift::Remove("    ".to_string()))
+        );
+    }
+
+    #[test]
+    fn ignores_inconsistent_indent_shift() {
+        let actual = to_vec(&["        a", "  b"]);
+        let expected = to_vec(&["    a", "    b"]);
+        assert_eq!(detect_indent_shift(&actual, &expected), None);
+    }
+
+    #[test]
+    fn applies_indent_shift_to_new_lines() {
+        let lines = to_vec(&["    stop();", "", "  done();"]);
+        assert_eq!(
+            apply_indent_shift(&lines, &IndentShift::Add("  ".to_string())),
+            to_vec(&["      stop();", "", "    done();"])
+        );
+        assert_eq!(
+            apply_indent_shift(&lines, &IndentShift::Remove("  ".to_string())),
+            to_vec(&["  stop();", "", "done();"])
+        );
+    }
+}
@@ -208,6 +208,28 @@ pub async fn maybe_parse_apply_patch_verified(
                             },
                         );
                     }
+                    Hunk::AddBinaryFile { contents, .. } => {
+                        changes.insert(
+                            path.into_path_buf(),
+                            ApplyPatchFileChange::AddBinary { content: contents },
+                        );
+                    }
+                    Hunk::UpdateBinaryFile { contents, .. } => {
+                        changes.insert(
+                            path.into_path_buf(),
+                            ApplyPatchFileChange::UpdateBinary { content: contents },
+                        );
+                    }
+                    Hunk::AddSymlink { target, .. } => {
+                        changes.insert(
+                            path.into_path_buf(),
+                            ApplyPatchFileChange::AddSymlink { target },
+                        );
+                    }
+                    Hunk::SetMode { mode, .. } => {
+                        changes
+                            .insert(path.into_path_buf(), ApplyPatchFileChange::SetMode { mode });
+                    }
                 }
             }
             MaybeApplyPatchVerified::Body(ApplyPatchAction {
@@ -1,20 +1,22 @@
+mod fuzzy_match;
 mod invocation;
 mod parser;
 mod seek_sequence;
 mod standalone_executable;
+mod text_format;
+mod transaction;
 
 use std::collections::HashMap;
-use std::io;
 use std::path::Path;
 use std::path::PathBuf;
 
-use anyhow::Context;
 use anyhow::Result;
-use codex_exec_server::CreateDirectoryOptions;
 use codex_exec_server::ExecutorFileSystem;
 use codex_exec_server::FileSystemSandboxContext;
-use codex_exec_server::RemoveOptions;
 use codex_utils_absolute_path::AbsolutePathBuf;
+pub use fuzzy_match::ClosestCandidate;
+pub use fuzzy_match::ContextMismatch;
+pub use fuzzy_match::LineMismatch;
 pub use parser::Hunk;
 pub use parser::ParseError;
 use parser::ParseError::*;
@@ -22,10 +24,12 @@ pub use parser::UpdateFileChunk;
 pub use parser::parse_patch;
 pub use parser::parse_patch_streaming;
 use similar::TextDiff;
+use text_format::FileLines;
 use thiserror::Error;
 
 pub use invocation::maybe_parse_apply_patch_verified;
 pub use standalone_executable::main;
+pub use transaction::HunkFailure;
 
 use crate::invocation::ExtractHeredocError;
 
@@ -47,9 +51,13 @@ pub enum ApplyPatchError {
     ParseError(#[from] ParseError),
     #[error(transparent)]
     IoError(#[from] IoError),
-    /// Error that occurs while computing replacements when applying patch chunks
+    /// The lines a chunk expects could not be located in the target file.
     #[error("{0}")]
-    ComputeReplacements(String),
+    ContextNotFound(Box<ContextMismatch>),
+    /// A hunk could not be applied. Patches are applied atomically, so no
+    /// files were modified.
+    #[error(transparent)]
+    HunkFailed(#[from] HunkFailure),
     /// A raw patch body was provided without an explicit `apply_patch` invocation.
     #[error(
         "patch detected without explicit call to apply_patch. Rerun as [\"apply_patch\", \"<patch>\"]"
@@ -112,6 +120,22 @@ pub enum ApplyPatchFileChange {
         /// new_content that will result after the unified_diff is applied.
         new_content: String,
     },
+    /// Creates a file with binary contents.
+    AddBinary {
+        content: Vec<u8>,
+    },
+    /// Replaces the contents of an existing file with binary contents.
+    UpdateBinary {
+        content: Vec<u8>,
+    },
+    /// Creates a symbolic link pointing to `target`.
+    AddSymlink {
+        target: PathBuf,
+    },
+    /// Sets the permission bits of an existing file.
+    SetMode {
+        mode: u32,
+    },
 }
 
 #[derive(Debug, PartialEq)]
@@ -177,6 +201,32 @@ impl ApplyPatchAction {
             patch,
         }
     }
+
+    /// Should be used exclusively for testing.
+    pub fn new_add_symlink_for_test(path: &AbsolutePathBuf, target: PathBuf) -> Self {
+        #[expect(clippy::expect_used)]
+        let filename = path
+            .file_name()
+            .expect("path should not be empty")
+            .to_string_lossy();
+        let patch = format!(
+            r#"*** Begin Patch
+*** Add Symlink: {filename}
+*** Link to: {}
+*** End Patch"#,
+            target.display()
+        );
+        let changes = HashMap::from([(
+            path.to_path_buf(),
+            ApplyPatchFileChange::AddSymlink { target },
+        )]);
+        #[expect(clippy::expect_used)]
+        Self {
+            changes,
+            cwd: path.parent().expect("path should have parent"),
+            patch,
+        }
+    }
 }
 
 /// Applies the patch and prints the result to stdout/stderr.
@@ -215,7 +265,8 @@ pub async fn apply_patch(
     Ok(())
 }
 
-/// Applies hunks and continues to update stdout/stderr
+/// Applies hunks and continues to update stdout/stderr. The hunks are applied
+/// atomically: if any of them fails, every file is left as it was.
 pub async fn apply_hunks(
     hunks: &[Hunk],
     cwd: &AbsolutePathBuf,
@@ -224,29 +275,27 @@ pub async fn apply_hunks(
     fs: &dyn ExecutorFileSystem,
     sandbox: Option<&FileSystemSandboxContext>,
 ) -> Result<(), ApplyPatchError> {
-    // Delegate to a helper that applies each hunk to the filesystem.
-    match apply_hunks_to_files(hunks, cwd, fs, sandbox).await {
+    if hunks.is_empty() {
+        let msg = "No files were modified.";
+        writeln!(stderr, "{msg}").map_err(ApplyPatchError::from)?;
+        return Err(ApplyPatchError::IoError(IoError {
+            context: msg.to_string(),
+            source: std::io::Error::other(msg),
+        }));
+    }
+
+    match transaction::apply_hunks_atomically(hunks, cwd, fs, sandbox).await {
         Ok(affected) => {
             print_summary(&affected, stdout).map_err(ApplyPatchError::from)?;
             Ok(())
         }
-        Err(err) => {
-            let msg = err.to_string();
-            writeln!(stderr, "{msg}").map_err(ApplyPatchError::from)?;
-            if let Some(io) = err.downcast_ref::<std::io::Error>() {
-                Err(ApplyPatchError::from(io))
-            } else {
-                Err(ApplyPatchError::IoError(IoError {
-                    context: msg,
-                    source: std::io::Error::other(err),
-                }))
-            }
+        Err(failure) => {
+            writeln!(stderr, "{failure}").map_err(ApplyPatchError::from)?;
+            Err(ApplyPatchError::HunkFailed(failure))
         }
     }
 }
 
-/// Applies each parsed patch hunk to the filesystem.
-/// Returns an error if any of the changes could not be applied.
 /// Tracks file paths affected by applying a patch, preserving the path spelling
 /// from the patch for user-facing summaries.
 pub struct AffectedPaths {
@@ -255,145 +304,6 @@ pub struct AffectedPaths {
     pub deleted: Vec<PathBuf>,
 }
 
-/// Apply the hunks to the filesystem, returning which files were added, modified, or deleted.
-/// Returns an error if the patch could not be applied.
-async fn apply_hunks_to_files(
-    hunks: &[Hunk],
-    cwd: &AbsolutePathBuf,
-    fs: &dyn ExecutorFileSystem,
-    sandbox: Option<&FileSystemSandboxContext>,
-) -> anyhow::Result<AffectedPaths> {
-    if hunks.is_empty() {
-        anyhow::bail!("No files were modified.");
-    }
-
-    let mut added: Vec<PathBuf> = Vec::new();
-    let mut modified: Vec<PathBuf> = Vec::new();
-    let mut deleted: Vec<PathBuf> = Vec::new();
-    for hunk in hunks {
-        let affected_path = hunk.path().to_path_buf();
-        let path_abs = hunk.resolve_path(cwd);
-        match hunk {
-            Hunk::AddFile { contents, .. } => {
-                write_file_with_missing_parent_retry(
-                    fs,
-                    &path_abs,
-                    contents.clone().into_bytes(),
-                    sandbox,
-                )
-                .await?;
-                added.push(affected_path);
-            }
-            Hunk::DeleteFile { .. } => {
-                let result: io::Result<()> = async {
-                    let metadata = fs.get_metadata(&path_abs, sandbox).await?;
-                    if metadata.is_directory {
-                        return Err(io::Error::new(
-                            io::ErrorKind::InvalidInput,
-                            "path is a directory",
-                        ));
-                    }
-                    fs.remove(
-                        &path_abs,
-                        RemoveOptions {
-                            recursive: false,
-                            force: false,
-                        },
-                        sandbox,
-                    )
-                    .await
-                }
-                .await;
-                result.with_context(|| format!("Failed to delete file {}", path_abs.display()))?;
-                deleted.push(affected_path);
-            }
-            Hunk::UpdateFile {
-                move_path, chunks, ..
-            } => {
-                let AppliedPatch { new_contents, .. } =
-                    derive_new_contents_from_chunks(&path_abs, chunks, fs, sandbox).await?;
-                if let Some(dest) = move_path {
-                    let dest_abs = AbsolutePathBuf::resolve_path_against_base(dest, cwd);
-                    write_file_with_missing_parent_retry(
-                        fs,
-                        &dest_abs,
-                        new_contents.into_bytes(),
-                        sandbox,
-                    )
-                    .await?;
-                    let result: io::Result<()> = async {
-                        let metadata = fs.get_metadata(&path_abs, sandbox).await?;
-                        if metadata.is_directory {
-                            return Err(io::Error::new(
-                                io::ErrorKind::InvalidInput,
-                                "path is a directory",
-                            ));
-                        }
-                        fs.remove(
-                            &path_abs,
-                            RemoveOptions {
-                                recursive: false,
-                                force: false,
-                            },
-                            sandbox,
-                        )
-                        .await
-                    }
-                    .await;
-                    result.with_context(|| {
-                        format!("Failed to remove original {}", path_abs.display())
-                    })?;
-                    modified.push(affected_path);
-                } else {
-                    fs.write_file(&path_abs, new_contents.into_bytes(), sandbox)
-                        .await
-                        .with_context(|| format!("Failed to write file {}", path_abs.display()))?;
-                    modified.push(affected_path);
-                }
-            }
-        }
-    }
-    Ok(AffectedPaths {
-        added,
-        modified,
-        deleted,
-    })
-}
-
-async fn write_file_with_missing_parent_retry(
-    fs: &dyn ExecutorFileSystem,
-    path_abs: &AbsolutePathBuf,
-    contents: Vec<u8>,
-    sandbox: Option<&FileSystemSandboxContext>,
-) -> anyhow::Result<()> {
-    match fs.write_file(path_abs, contents.clone(), sandbox).await {
-        Ok(()) => Ok(()),
-        Err(err) if err.kind() == io::ErrorKind::NotFound => {
-            if let Some(parent_abs) = path_abs.parent() {
-                fs.create_directory(
-                    &parent_abs,
-                    CreateDirectoryOptions { recursive: true },
-                    sandbox,
-                )
-                .await
-                .with_context(|| {
-                    format!(
-                        "Failed to create parent directories for {}",
-                        path_abs.display()
-                    )
-                })?;
-            }
-            fs.write_file(path_abs, contents, sandbox)
-                .await
-                .with_context(|| format!("Failed to write file {}", path_abs.display()))?;
-            Ok(())
-        }
-        Err(err) => {
-            Err(err).with_context(|| format!("Failed to write file {}", path_abs.display()))
-        }
-    }
-}
-
 struct AppliedPatch {
     original_contents: String,
     new_contents: String,
@@ -413,22 +323,37 @@ async fn derive_new_contents_from_chunks(
             source: err,
         })
     })?;
+    derive_new_contents_from_text(original_contents, path_abs, chunks)
+}
 
-    let mut original_lines: Vec<String> = original_contents.split('\n').map(String::from).collect();
-
-    // Drop the trailing empty element that results from the final newline so
-    // that line counts match the behaviour of standard `diff`.
-    if original_lines.last().is_some_and(String::is_empty) {
-        original_lines.pop();
-    }
-
-    let replacements = compute_replacements(&original_lines, path_abs.as_path(), chunks)?;
-    let new_lines = apply_replacements(original_lines, &replacements);
-    let mut new_lines = new_lines;
-    if !new_lines.last().is_some_and(String::is_empty) {
-        new_lines.push(String::new());
-    }
-    let new_contents = new_lines.join("\n");
+/// Applies the chunks to `original_contents`, which holds the current contents
+/// of the file at `path_abs`.
+fn derive_new_contents_from_text(
+    original_contents: String,
+    path_abs: &AbsolutePathBuf,
+    chunks: &[UpdateFileChunk],
+) -> std::result::Result<AppliedPatch, ApplyPatchError> {
+    let FileLines {
+        format,
+        raw,
+        stripped,
+    } = FileLines::parse(&original_contents);
+
+    let replacements = compute_replacements(&stripped, path_abs.as_path(), chunks)?
+        .into_iter()
+        .map(|(start_idx, old_len, new_lines)| {
+            let end_idx = start_idx + old_len;
+            let new_lines = text_format::restore_line_endings(
+                &raw[start_idx..end_idx],
+                &stripped[start_idx..end_idx],
+                &new_lines,
+                format,
+            );
+            (start_idx, old_len, new_lines)
+        })
+        .collect::<Vec<_>>();
+    let new_lines = apply_replacements(raw, &replacements);
+    let new_contents = format.join(new_lines);
     Ok(AppliedPatch {
         original_contents,
         new_contents,
@@ -450,20 +375,39 @@ fn compute_replacements(
         // If a chunk has a `change_context`, we use seek_sequence to find it, then
         // adjust our `line_index` to continue from there.
         if let Some(ctx_line) = &chunk.change_context {
-            if let Some(idx) = seek_sequence::seek_sequence(
+            let ctx_pattern = std::slice::from_ref(ctx_line);
+            let found = seek_sequence::seek_sequence(
                 original_lines,
-                std::slice::from_ref(ctx_line),
+                ctx_pattern,
                 line_index,
                 /*eof*/ false,
-            ) {
-                line_index = idx + 1;
-            } else {
-                return Err(ApplyPatchError::ComputeReplacements(format!(
-                    "Failed to find context '{}' in {}",
-                    ctx_line,
-                    path.display()
-                )));
-            }
+            );
+            let found = match found {
+                Some(idx) => idx,
+                None => {
+                    let search = fuzzy_match::fuzzy_seek_sequence(
+                        original_lines,
+                        ctx_pattern,
+                        /*removed_lines*/ &[],
+                        line_index,
+                        /*eof*/ false,
+                    );
+                    match search.accepted_start() {
+                        Some(idx) => idx,
+                        None => {
+                            return Err(ApplyPatchError::ContextNotFound(Box::new(
+                                ContextMismatch {
+                                    path: path.to_path_buf(),
+                                    expected: ctx_pattern.to_vec(),
+                                    is_change_context: true,
+                                    closest: search.closest_candidate(original_lines, ctx_pattern),
+                                },
+                            )));
+                        }
+                    }
+                }
+            };
+            line_index = found + 1;
         }
 
         if chunk.old_lines.is_empty() {
@@ -511,15 +455,46 @@ fn compute_replacements(
             );
         }
 
+        if found.is_none() {
+            // Fall back to a scored match that tolerates small edits, and
+            // report the closest region if even that fails.
+            let search = fuzzy_match::fuzzy_seek_sequence(
+                original_lines,
+                pattern,
+                &removed_line_mask(pattern, new_slice),
+                line_index,
+                chunk.is_end_of_file,
+            );
+            found = search.accepted_start();
+            if found.is_none() {
+                return Err(ApplyPatchError::ContextNotFound(Box::new(
+                    ContextMismatch {
+                        path: path.to_path_buf(),
+                        expected: chunk.old_lines.clone(),
+                        is_change_context: false,
+                        closest: search.closest_candidate(original_lines, pattern),
+                    },
+                )));
+            }
+        }
+
         if let Some(start_idx) = found {
-            replacements.push((start_idx, pattern.len(), new_slice.to_vec()));
+            let matched = &original_lines[start_idx..start_idx + pattern.len()];
+            let new_lines = if matched == pattern {
+                new_slice.to_vec()
+            } else {
+                // The region only approximately matched. Indent the
+                // replacement the same way as the file if the patch assumed a
+                // different indentation level, and keep the file's own
+                // spelling of the context lines the patch left unchanged.
+                let new_lines = match fuzzy_match::detect_indent_shift(matched, pattern) {
+                    Some(shift) => fuzzy_match::apply_indent_shift(new_slice, &shift),
+                    None => new_slice.to_vec(),
+                };
+                preserve_matched_context(matched, pattern, new_slice, new_lines)
+            };
+            replacements.push((start_idx, pattern.len(), new_lines));
             line_index = start_idx + pattern.len();
-        } else {
-            return Err(ApplyPatchError::ComputeReplacements(format!(
-                "Failed to find expected lines in {}:\n{}",
-                path.display(),
-                chunk.old_lines.join("\n"),
-            )));
         }
     }
 
@@ -528,6 +503,41 @@ fn compute_replacements(
     Ok(replacements)
 }
 
+/// Marks the lines of `pattern` that the chunk removes rather than keeps as
+/// context in `new_slice`.
+fn removed_line_mask(pattern: &[String], new_slice: &[String]) -> Vec<bool> {
+    let mut removed = vec![true; pattern.len()];
+    for op in similar::capture_diff_slices(similar::Algorithm::Myers, pattern, new_slice) {
+        if let similar::DiffOp::Equal { old_index, len, .. } = op {
+            removed[old_index..old_index + len].fill(false);
+        }
+    }
+    removed
+}
+
+/// Replaces lines of `new_lines` that the chunk kept unchanged from `pattern`
+/// with the corresponding lines actually found in the file. `new_slice` is the
+/// chunk's original replacement, which `new_lines` is a re-indented copy of.
+fn preserve_matched_context(
+    matched: &[String],
+    pattern: &[String],
+    new_slice: &[String],
+    mut new_lines: Vec<String>,
+) -> Vec<String> {
+    for op in similar::capture_diff_slices(similar::Algorithm::Myers, pattern, new_slice) {
+        if let similar::DiffOp::Equal {
+            old_index,
+            new_index,
+            len,
+        } = op
+        {
+            new_lines[new_index..new_index + len]
+                .clone_from_slice(&matched[old_index..old_index + len]);
+        }
+    }
+    new_lines
+}
+
 /// Apply the `(start_index, old_len, new_lines)` replacements to `original_lines`,
 /// returning the modified file contents as a vector of lines.
 fn apply_replacements(
@@ -1038,6 +1048,498 @@ mod tests {
         assert_eq!(String::from_utf8(stderr).unwrap(), "");
     }
 
+    #[tokio::test]
+    async fn test_update_applies_fuzzy_match_for_small_edits() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("fuzzy.rs");
+        fs::write(
+            &path,
+            "fn main() {\n    let total = compute(alpha, beta, gamma);\n    report(total);\n}\n",
+        )
+        .unwrap();
+
+        // The patch misremembers the spacing of the first context line.
+        let patch = wrap_patch(
+            r#"*** Update File: fuzzy.rs
+@@
+     let total = compute(alpha,beta,gamma);
+-    report(total);
++    report_verbose(total);"#,
+        );
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        apply_patch(
+            &patch,
+            &dir.path().abs(),
+            &mut stdout,
+            &mut stderr,
+            LOCAL_FS.as_ref(),
+            /*sandbox*/ None,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "fn main() {\n    let total = compute(alpha, beta, gamma);\n    report_verbose(total);\n}\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_rejects_fuzzy_match_for_removed_lines() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("fuzzy.rs");
+        let original =
+            "fn main() {\n    let total = compute(alpha, beta, gamma);\n    report(total);\n}\n";
+        fs::write(&path, original).unwrap();
+
+        // The line being removed differs from the file by more than whitespace.
+        let patch = wrap_patch(
+            r#"*** Update File: fuzzy.rs
+@@
+-    let total = compute(alpha, beta, delta);
++    let total = compute(alpha, beta);
+     report(total);"#,
+        );
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let result = apply_patch(
+            &patch,
+            &dir.path().abs(),
+            &mut stdout,
+            &mut stderr,
+            LOCAL_FS.as_ref(),
+            /*sandbox*/ None,
+        )
+        .await;
+
+        let Err(ApplyPatchError::HunkFailed(failure)) = result else {
+            panic!("expected hunk failure, got {result:?}");
+        };
+        assert!(failure.context_mismatch.is_some());
+        assert_eq!(fs::read_to_string(&path).unwrap(), original);
+    }
+
+    #[tokio::test]
+    async fn test_update_reindents_replacement_on_indent_shift() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("indent.py");
+        fs::write(
+            &path,
+            "class Runner:\n    def run(self):\n        start()\n        stop()\n",
+        )
+        .unwrap();
+
+        // The patch was written as if `run` were a top-level function.
+        let patch = wrap_patch(
+            r#"*** Update File: indent.py
+@@
+ def run(self):
+     start()
+-    stop()
++    if ready():
++        stop()"#,
+        );
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        apply_patch(
+            &patch,
+            &dir.path().abs(),
+            &mut stdout,
+            &mut stderr,
+            LOCAL_FS.as_ref(),
+            /*sandbox*/ None,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "class Runner:\n    def run(self):\n        start()\n        if ready():\n            stop()\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_missing_context_reports_closest_candidate() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(
+            &path,
+            "[server]\nhost = \"localhost\"\nport = 8080\ntimeout = 30\n",
+        )
+        .unwrap();
+
+        let patch = wrap_patch(
+            r#"*** Update File: config.toml
+@@
+ host = "localhost"
+-port = 9090
+-retries = 3
++port = 9091"#,
+        );
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let result = apply_patch(
+            &patch,
+            &dir.path().abs(),
+            &mut stdout,
+            &mut stderr,
+            LOCAL_FS.as_ref(),
+            /*sandbox*/ None,
+        )
+        .await;
+
+        let Err(ApplyPatchError::HunkFailed(failure)) = result else {
+            panic!("expected hunk failure, got {result:?}");
+        };
+        let mismatch = failure.context_mismatch.expect("context mismatch");
+        let closest = mismatch.closest.expect("closest candidate");
+        assert_eq!(closest.line_number, 2);
+        assert_eq!(
+            closest.differing_lines,
+            vec![
+                LineMismatch {
+                    line_number: 3,
+                    expected: "port = 9090".to_string(),
+                    actual: "port = 8080".to_string(),
+                },
+                LineMismatch {
+                    line_number: 4,
+                    expected: "retries = 3".to_string(),
+                    actual: "timeout = 30".to_string(),
+                },
+            ]
+        );
+        let stderr = String::from_utf8(stderr).unwrap();
+        assert!(
+            stderr.contains("Closest match starts at line 2"),
+            "unexpected stderr: {stderr}"
+        );
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "[server]\nhost = \"localhost\"\nport = 8080\ntimeout = 30\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_preserves_crlf_bom_and_missing_trailing_newline() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("windows.txt");
+        fs::write(&path, "\u{FEFF}first\r\nsecond\r\nthird").unwrap();
+
+        let patch = wrap_patch(
+            r#"*** Update File: windows.txt
+@@
+ first
+-second
++second (edited)
++inserted"#,
+        );
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        apply_patch(
+            &patch,
+            &dir.path().abs(),
+            &mut stdout,
+            &mut stderr,
+            LOCAL_FS.as_ref(),
+            /*sandbox*/ None,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "\u{FEFF}first\r\nsecond (edited)\r\ninserted\r\nthird"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_keeps_line_endings_of_untouched_lines_in_mixed_files() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("mixed.txt");
+        fs::write(&path, "one\r\ntwo\nthree\r\nfour\r\n").unwrap();
+
+        let patch = wrap_patch(
+            r#"*** Update File: mixed.txt
+@@
+ two
+-three
++THREE"#,
+        );
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        apply_patch(
+            &patch,
+            &dir.path().abs(),
+            &mut stdout,
+            &mut stderr,
+            LOCAL_FS.as_ref(),
+            /*sandbox*/ None,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "one\r\ntwo\nTHREE\r\nfour\r\n"
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_move_preserves_file_mode() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempdir().unwrap();
+        let src = dir.path().join("script.sh");
+        let dest = dir.path().join("bin/script.sh");
+        fs::write(&src, "#!/bin/sh\necho old\n").unwrap();
+        fs::set_permissions(&src, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let patch = wrap_patch(
+            r#"*** Update File: script.sh
+*** Move to: bin/script.sh
+@@
+ #!/bin/sh
+-echo old
++echo new"#,
+        );
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        apply_patch(
+            &patch,
+            &dir.path().abs(),
+            &mut stdout,
+            &mut stderr,
+            LOCAL_FS.as_ref(),
+            /*sandbox*/ None,
+        )
+        .await
+        .unwrap();
+
+        assert!(!src.exists());
+        assert_eq!(fs::read_to_string(&dest).unwrap(), "#!/bin/sh\necho new\n");
+        assert_eq!(
+            fs::metadata(&dest).unwrap().permissions().mode() & 0o777,
+            0o755
+        );
+    }
+
+    #[tokio::test]
+    async fn test_add_and_update_binary_files() {
+        let dir = tempdir().unwrap();
+        let existing = dir.path().join("icon.bin");
+        fs::write(&existing, [0u8, 1, 2]).unwrap();
+
+        // "AP8QgA==" is [0x00, 0xff, 0x10, 0x80]; "/w==" is [0xff].
+        let patch = wrap_patch(
+            r#"*** Add Binary File: assets/new.bin
++AP8Q
++gA==
+*** Update Binary File: icon.bin
++/w=="#,
+        );
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        apply_patch(
+            &patch,
+            &dir.path().abs(),
+            &mut stdout,
+            &mut stderr,
+            LOCAL_FS.as_ref(),
+            /*sandbox*/ None,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            fs::read(dir.path().join("assets/new.bin")).unwrap(),
+            vec![0x00, 0xff, 0x10, 0x80]
+        );
+        assert_eq!(fs::read(&existing).unwrap(), vec![0xff]);
+        assert_eq!(
+            String::from_utf8(stdout).unwrap(),
+            "Success. Updated the following files:\nA assets/new.bin\nM icon.bin\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_binary_file_requires_existing_file() {
+        let dir = tempdir().unwrap();
+        let patch = wrap_patch(
+            r#"*** Update Binary File: missing.bin
++/w=="#,
+        );
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let result = apply_patch(
+            &patch,
+            &dir.path().abs(),
+            &mut stdout,
+            &mut stderr,
+            LOCAL_FS.as_ref(),
+            /*sandbox*/ None,
+        )
+        .await;
+
+        let Err(ApplyPatchError::HunkFailed(failure)) = result else {
+            panic!("expected hunk failure, got {result:?}");
+        };
+        assert_eq!(failure.path, PathBuf::from("missing.bin"));
+        assert!(!dir.path().join("missing.bin").exists());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_add_symlink_and_set_mode() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempdir().unwrap();
+        let script = dir.path().join("run.sh");
+        fs::write(&script, "#!/bin/sh\n").unwrap();
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o644)).unwrap();
+
+        let patch = wrap_patch(
+            r#"*** Set Mode: run.sh
+*** Mode: 755
+*** Add Symlink: bin/run
+*** Link to: ../run.sh"#,
+        );
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        apply_patch(
+            &patch,
+            &dir.path().abs(),
+            &mut stdout,
+            &mut stderr,
+            LOCAL_FS.as_ref(),
+            /*sandbox*/ None,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            fs::metadata(&script).unwrap().permissions().mode() & 0o777,
+            0o755
+        );
+        let link = dir.path().join("bin/run");
+        assert_eq!(fs::read_link(&link).unwrap(), PathBuf::from("../run.sh"));
+        assert_eq!(fs::read_to_string(&link).unwrap(), "#!/bin/sh\n");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_add_symlink_rejects_existing_path() {
+        let dir = tempdir().unwrap();
+        let existing = dir.path().join("taken.txt");
+        fs::write(&existing, "keep\n").unwrap();
+
+        let patch = wrap_patch(
+            r#"*** Add Symlink: taken.txt
+*** Link to: elsewhere.txt"#,
+        );
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let result = apply_patch(
+            &patch,
+            &dir.path().abs(),
+            &mut stdout,
+            &mut stderr,
+            LOCAL_FS.as_ref(),
+            /*sandbox*/ None,
+        )
+        .await;
+
+        let Err(ApplyPatchError::HunkFailed(failure)) = result else {
+            panic!("expected hunk failure, got {result:?}");
+        };
+        assert_eq!(failure.path, PathBuf::from("taken.txt"));
+        assert!(failure.message.starts_with("Failed to create symlink"));
+        assert_eq!(fs::read_to_string(&existing).unwrap(), "keep\n");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_commit_failure_restores_mode_and_removes_symlink() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempdir().unwrap();
+        let script = dir.path().join("run.sh");
+        fs::write(&script, "#!/bin/sh\n").unwrap();
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o644)).unwrap();
+        fs::create_dir(dir.path().join("blocked")).unwrap();
+
+        let patch = wrap_patch(
+            r#"*** Set Mode: run.sh
+*** Mode: 100755
+*** Add Symlink: link
+*** Link to: run.sh
+*** Add File: blocked
++cannot write over a directory"#,
+        );
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let result = apply_patch(
+            &patch,
+            &dir.path().abs(),
+            &mut stdout,
+            &mut stderr,
+            LOCAL_FS.as_ref(),
+            /*sandbox*/ None,
+        )
+        .await;
+
+        let Err(ApplyPatchError::HunkFailed(failure)) = result else {
+            panic!("expected hunk failure, got {result:?}");
+        };
+        assert_eq!(failure.hunk_index, 2);
+        assert_eq!(
+            fs::metadata(&script).unwrap().permissions().mode() & 0o777,
+            0o644
+        );
+        assert!(fs::symlink_metadata(dir.path().join("link")).is_err());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_commit_failure_recreates_deleted_symlink() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("target.txt"), "target\n").unwrap();
+        std::os::unix::fs::symlink("target.txt", dir.path().join("link")).unwrap();
+        fs::create_dir(dir.path().join("blocked")).unwrap();
+
+        let patch = wrap_patch(
+            r#"*** Delete File: link
+*** Add File: blocked
++cannot write over a directory"#,
+        );
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let result = apply_patch(
+            &patch,
+            &dir.path().abs(),
+            &mut stdout,
+            &mut stderr,
+            LOCAL_FS.as_ref(),
+            /*sandbox*/ None,
+        )
+        .await;
+
+        assert!(
+            matches!(result, Err(ApplyPatchError::HunkFailed(_))),
+            "expected hunk failure, got {result:?}"
+        );
+        let link = dir.path().join("link");
+        assert!(
+            fs::symlink_metadata(&link)
+                .unwrap()
+                .file_type()
+                .is_symlink()
+        );
+        assert_eq!(fs::read_link(&link).unwrap(), PathBuf::from("target.txt"));
+    }
+
     #[tokio::test]
     async fn test_unified_diff() {
         // Start with a file containing four lines.
@@ -1290,6 +1792,194 @@ g
         );
     }
 
+    #[tokio::test]
+    async fn test_failed_hunk_leaves_all_files_untouched() {
+        let dir = tempdir().unwrap();
+        let updated = dir.path().join("updated.txt");
+        let deleted = dir.path().join("deleted.txt");
+        fs::write(&updated, "before\n").unwrap();
+        fs::write(&deleted, "keep me\n").unwrap();
+
+        let patch = wrap_patch(
+            r#"*** Update File: updated.txt
+@@
+-before
++after
+*** Delete File: deleted.txt
+*** Add File: created.txt
++new
+*** Update File: updated.txt
+@@
+-missing
++never"#,
+        );
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let result = apply_patch(
+            &patch,
+            &dir.path().abs(),
+            &mut stdout,
+            &mut stderr,
+            LOCAL_FS.as_ref(),
+            /*sandbox*/ None,
+        )
+        .await;
+
+        let Err(ApplyPatchError::HunkFailed(failure)) = result else {
+            panic!("expected hunk failure, got {result:?}");
+        };
+        assert_eq!(failure.hunk_index, 3);
+        assert_eq!(failure.path, PathBuf::from("updated.txt"));
+        assert_eq!(String::from_utf8(stdout).unwrap(), "");
+        assert_eq!(fs::read_to_string(&updated).unwrap(), "before\n");
+        assert_eq!(fs::read_to_string(&deleted).unwrap(), "keep me\n");
+        assert!(!dir.path().join("created.txt").exists());
+    }
+
+    #[tokio::test]
+    async fn test_commit_failure_restores_written_files() {
+        let dir = tempdir().unwrap();
+        let updated = dir.path().join("updated.txt");
+        let deleted = dir.path().join("deleted.txt");
+        let blocked = dir.path().join("blocked");
+        fs::write(&updated, "before\n").unwrap();
+        fs::write(&deleted, "keep me\n").unwrap();
+        fs::create_dir(&blocked).unwrap();
+
+        // Adding a file over an existing directory passes validation but
+        // fails when written, after the earlier hunks have been committed.
+        let patch = wrap_patch(
+            r#"*** Update File: updated.txt
+@@
+-before
++after
+*** Delete File: deleted.txt
+*** Add File: nested/dir/created.txt
++new
+*** Add File: blocked
++cannot write over a directory"#,
+        );
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let result = apply_patch(
+            &patch,
+            &dir.path().abs(),
+            &mut stdout,
+            &mut stderr,
+            LOCAL_FS.as_ref(),
+            /*sandbox*/ None,
+        )
+        .await;
+
+        let Err(ApplyPatchError::HunkFailed(failure)) = result else {
+            panic!("expected hunk failure, got {result:?}");
+        };
+        assert_eq!(failure.hunk_index, 3);
+        assert_eq!(failure.path, PathBuf::from("blocked"));
+        assert_eq!(fs::read_to_string(&updated).unwrap(), "before\n");
+        assert_eq!(fs::read_to_string(&deleted).unwrap(), "keep me\n");
+        assert!(!dir.path().join("nested").exists());
+        assert!(blocked.is_dir());
+    }
+
+    #[tokio::test]
+    async fn test_deleted_file_can_be_replaced_by_directory() {
+        let dir = tempdir().unwrap();
+        let replaced = dir.path().join("a");
+        fs::write(&replaced, "old file\n").unwrap();
+
+        let patch = wrap_patch(
+            r#"*** Delete File: a
+*** Add File: a/b.txt
++nested"#,
+        );
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        apply_patch(
+            &patch,
+            &dir.path().abs(),
+            &mut stdout,
+            &mut stderr,
+            LOCAL_FS.as_ref(),
+            /*sandbox*/ None,
+        )
+        .await
+        .unwrap();
+
+        assert!(replaced.is_dir());
+        assert_eq!(
+            fs::read_to_string(replaced.join("b.txt")).unwrap(),
+            "nested\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_commit_failure_restores_file_replaced_by_directory() {
+        let dir = tempdir().unwrap();
+        let replaced = dir.path().join("a");
+        let blocked = dir.path().join("blocked");
+        fs::write(&replaced, "old file\n").unwrap();
+        fs::create_dir(&blocked).unwrap();
+
+        let patch = wrap_patch(
+            r#"*** Delete File: a
+*** Add File: a/b.txt
++nested
+*** Add File: blocked
++cannot write over a directory"#,
+        );
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let result = apply_patch(
+            &patch,
+            &dir.path().abs(),
+            &mut stdout,
+            &mut stderr,
+            LOCAL_FS.as_ref(),
+            /*sandbox*/ None,
+        )
+        .await;
+
+        let Err(ApplyPatchError::HunkFailed(failure)) = result else {
+            panic!("expected hunk failure, got {result:?}");
+        };
+        assert_eq!(failure.path, PathBuf::from("blocked"));
+        assert_eq!(fs::read_to_string(&replaced).unwrap(), "old file\n");
+        assert!(blocked.is_dir());
+    }
+
+    #[tokio::test]
+    async fn test_later_hunks_see_earlier_staged_changes() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("twice.txt");
+        fs::write(&path, "one\n").unwrap();
+
+        let patch = wrap_patch(
+            r#"*** Update File: twice.txt
+@@
+-one
++two
+*** Update File: twice.txt
+@@
+-two
++three"#,
+        );
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        apply_patch(
+            &patch,
+            &dir.path().abs(),
+            &mut stdout,
+            &mut stderr,
+            LOCAL_FS.as_ref(),
+            /*sandbox*/ None,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "three\n");
+    }
+
     #[tokio::test]
     async fn test_apply_patch_fails_on_write_error() {
         let dir = tempdir().unwrap();
@@ -7,12 +7,18 @@
 //! begin_patch: "*** Begin Patch" LF
 //! end_patch: "*** End Patch" LF?
 //!
-//! hunk: add_hunk | delete_hunk | update_hunk
+//! hunk: add_hunk | delete_hunk | update_hunk | add_binary_hunk | update_binary_hunk
+//!     | add_symlink_hunk | set_mode_hunk
 //! add_hunk: "*** Add File: " filename LF add_line+
 //! delete_hunk: "*** Delete File: " filename LF
 //! update_hunk: "*** Update File: " filename LF change_move? change?
+//! add_binary_hunk: "*** Add Binary File: " filename LF binary_line+
+//! update_binary_hunk: "*** Update Binary File: " filename LF binary_line+
+//! add_symlink_hunk: "*** Add Symlink: " filename LF "*** Link to: " filename LF
+//! set_mode_hunk: "*** Set Mode: " filename LF "*** Mode: " /([0-7]+)/ LF
 //! filename: /(.+)/
 //! add_line: "+" /(.+)/ LF -> line
+//! binary_line: "+" /([A-Za-z0-9+\/=]*)/ LF
 //!
 //! change_move: "*** Move to: " filename LF
 //! change: (change_context | change_line)+ eof_line?
@@ -23,6 +29,8 @@
 //! The parser below is a little more lenient than the explicit spec and allows for
 //! leading/trailing whitespace around patch markers.
 use crate::ApplyPatchArgs;
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
 use codex_utils_absolute_path::AbsolutePathBuf;
 #[cfg(test)]
 use codex_utils_absolute_path::test_support::PathBufExt;
@@ -36,6 +44,12 @@ const END_PATCH_MARKER: &str = "*** End Patch";
 const ADD_FILE_MARKER: &str = "*** Add File: ";
 const DELETE_FILE_MARKER: &str = "*** Delete File: ";
 const UPDATE_FILE_MARKER: &str = "*** Update File: ";
+const ADD_BINARY_FILE_MARKER: &str = "*** Add Binary File: ";
+const UPDATE_BINARY_FILE_MARKER: &str = "*** Update Binary File: ";
+const ADD_SYMLINK_MARKER: &str = "*** Add Symlink: ";
+const LINK_TO_MARKER: &str = "*** Link to: ";
+const SET_MODE_MARKER: &str = "*** Set Mode: ";
+const MODE_MARKER: &str = "*** Mode: ";
 const MOVE_TO_MARKER: &str = "*** Move to: ";
 const EOF_MARKER: &str = "*** End of File";
 const CHANGE_CONTEXT_MARKER: &str = "@@ ";
@@ -76,13 +90,33 @@ pub enum Hunk {
         /// should occur later in the file than the previous chunk.
         chunks: Vec<UpdateFileChunk>,
     },
+    /// Creates (or overwrites) a file with the given bytes.
+    AddBinaryFile {
+        path: PathBuf,
+        contents: Vec<u8>,
+    },
+    /// Replaces the contents of an existing file with the given bytes.
+    UpdateBinaryFile {
+        path: PathBuf,
+        contents: Vec<u8>,
+    },
+    /// Creates a symbolic link at `path` pointing to `target`.
+    AddSymlink {
+        path: PathBuf,
+        target: PathBuf,
+    },
+    /// Sets the permission bits of an existing file.
+    SetMode {
+        path: PathBuf,
+        mode: u32,
+    },
 }
 
 impl Hunk {
     pub fn resolve_path(&self, cwd: &AbsolutePathBuf) -> AbsolutePathBuf {
         let path = match self {
             Hunk::UpdateFile { path, .. } => path,
-            Hunk::AddFile { .. } | Hunk::DeleteFile { .. } => self.path(),
+            _ => self.path(),
         };
         AbsolutePathBuf::resolve_path_against_base(path, cwd)
     }
@@ -101,6 +135,10 @@ impl Hunk {
                 move_path: None,
                 ..
             } => path,
+            Hunk::AddBinaryFile { path, .. }
+            | Hunk::UpdateBinaryFile { path, .. }
+            | Hunk::AddSymlink { path, .. }
+            | Hunk::SetMode { path, .. } => path,
         }
     }
 }
@@ -399,14 +437,125 @@ fn parse_one_hunk(
         ));
     }
 
+    if let Some(path) = first_line.strip_prefix(ADD_BINARY_FILE_MARKER) {
+        let (contents, parsed_lines) =
+            parse_binary_lines(lines, path, line_number, allow_incomplete)?;
+        return Ok((
+            AddBinaryFile {
+                path: PathBuf::from(path),
+                contents,
+            },
+            parsed_lines,
+        ));
+    } else if let Some(path) = first_line.strip_prefix(UPDATE_BINARY_FILE_MARKER) {
+        let (contents, parsed_lines) =
+            parse_binary_lines(lines, path, line_number, allow_incomplete)?;
+        return Ok((
+            UpdateBinaryFile {
+                path: PathBuf::from(path),
+                contents,
+            },
+            parsed_lines,
+        ));
+    } else if let Some(path) = first_line.strip_prefix(ADD_SYMLINK_MARKER) {
+        let target = parse_required_line(lines, LINK_TO_MARKER, path, line_number)?;
+        return Ok((
+            AddSymlink {
+                path: PathBuf::from(path),
+                target: PathBuf::from(target),
+            },
+            2,
+        ));
+    } else if let Some(path) = first_line.strip_prefix(SET_MODE_MARKER) {
+        let mode = parse_required_line(lines, MODE_MARKER, path, line_number)?;
+        let mode = parse_mode(mode).ok_or_else(|| InvalidHunkError {
+            message: format!(
+                "Invalid mode '{mode}' for path '{path}'; expected octal permission bits such as 644 or 755"
+            ),
+            line_number: line_number + 1,
+        })?;
+        return Ok((
+            SetMode {
+                path: PathBuf::from(path),
+                mode,
+            },
+            2,
+        ));
+    }
+
     Err(InvalidHunkError {
         message: format!(
-            "'{first_line}' is not a valid hunk header. Valid hunk headers: '*** Add File: {{path}}', '*** Delete File: {{path}}', '*** Update File: {{path}}'"
+            "'{first_line}' is not a valid hunk header. Valid hunk headers: '*** Add File: {{path}}', '*** Delete File: {{path}}', '*** Update File: {{path}}', '*** Add Binary File: {{path}}', '*** Update Binary File: {{path}}', '*** Add Symlink: {{path}}', '*** Set Mode: {{path}}'"
         ),
         line_number,
     })
 }
 
+/// Parses the base64 `+` lines following a binary hunk header and decodes
+/// them. Returns the decoded bytes and the number of lines consumed, including
+/// the header.
+fn parse_binary_lines(
+    lines: &[&str],
+    path: &str,
+    line_number: usize,
+    allow_incomplete: bool,
+) -> Result<(Vec<u8>, usize), ParseError> {
+    let mut encoded = String::new();
+    let mut parsed_lines = 1;
+    for line in &lines[1..] {
+        let Some(data) = line.strip_prefix('+') else {
+            break;
+        };
+        encoded.push_str(data.trim());
+        parsed_lines += 1;
+    }
+    match BASE64_STANDARD.decode(&encoded) {
+        Ok(contents) => Ok((contents, parsed_lines)),
+        // A streamed binary hunk is usually cut off mid-line; report the path
+        // without its contents until the rest arrives.
+        Err(_) if allow_incomplete => Ok((Vec::new(), parsed_lines)),
+        Err(err) => Err(InvalidHunkError {
+            message: format!("Invalid base64 contents for binary file '{path}': {err}"),
+            line_number,
+        }),
+    }
+}
+
+/// Returns the value of the `marker` line that must directly follow the hunk
+/// header at `lines[0]`.
+fn parse_required_line<'a>(
+    lines: &[&'a str],
+    marker: &str,
+    path: &str,
+    line_number: usize,
+) -> Result<&'a str, ParseError> {
+    lines
+        .get(1)
+        .and_then(|line| line.trim().strip_prefix(marker))
+        .ok_or_else(|| InvalidHunkError {
+            message: format!(
+                "Expected '{marker}' line after the hunk header for path '{path}'",
+                marker = marker.trim_end()
+            ),
+            line_number: line_number + 1,
+        })
+}
+
+/// Parses octal permission bits, accepting both plain modes (`755`, `0644`)
+/// and git-style regular file modes (`100755`). The setuid, setgid and sticky
+/// bits are rejected.
+fn parse_mode(mode: &str) -> Option<u32> {
+    const GIT_REGULAR_FILE: u32 = 0o100000;
+    let value = u32::from_str_radix(mode.trim(), 8).ok()?;
+    if value <= 0o777 {
+        Some(value)
+    } else if value & !0o777 == GIT_REGULAR_FILE {
+        Some(value & 0o777)
+    } else {
+        None
+    }
+}
+
 fn parse_update_file_chunk(
     lines: &[&str],
     line_number: usize,
@@ -646,6 +795,12 @@ fn test_parse_patch_streaming_large_patch_by_character() {
                 UpdateFile {
                     move_path: None, ..
                 } => "update",
+                AddBinaryFile { .. }
+                | UpdateBinaryFile { .. }
+                | AddSymlink { .. }
+                | SetMode { .. } => {
+                    "other"
+                }
             })
             .collect::<Vec<_>>(),
         vec![
@@ -1004,13 +1159,91 @@ fn test_parse_one_hunk() {
         parse_one_hunk(&["bad"], /*line_number*/ 234, /*allow_incomplete*/ false),
         Err(InvalidHunkError {
             message: "'bad' is not a valid hunk header. \
-            Valid hunk headers: '*** Add File: {path}', '*** Delete File: {path}', '*** Update File: {path}'".to_string(),
+            Valid hunk headers: '*** Add File: {path}', '*** Delete File: {path}', '*** Update File: {path}', \
+            '*** Add Binary File: {path}', '*** Update Binary File: {path}', '*** Add Symlink: {path}', '*** Set Mode: {path}'".to_string(),
             line_number: 234
         })
     );
     // Other edge cases are already covered by tests above/below.
 }
 
+#[test]
+fn test_parse_binary_symlink_and_mode_hunks() {
+    assert_eq!(
+        parse_patch(
+            "*** Begin Patch\n\
+             *** Add Binary File: logo.png\n\
+             +AP8Q\n\
+             +gA==\n\
+             *** Update Binary File: data.bin\n\
+             +/w==\n\
+             *** Add Symlink: latest\n\
+             *** Link to: releases/v2\n\
+             *** Set Mode: run.sh\n\
+             *** Mode: 100755\n\
+             *** End Patch"
+        )
+        .map(|args| args.hunks),
+        Ok(vec![
+            AddBinaryFile {
+                path: PathBuf::from("logo.png"),
+                contents: vec![0x00, 0xff, 0x10, 0x80],
+            },
+            UpdateBinaryFile {
+                path: PathBuf::from("data.bin"),
+                contents: vec![0xff],
+            },
+            AddSymlink {
+                path: PathBuf::from("latest"),
+                target: PathBuf::from("releases/v2"),
+            },
+            SetMode {
+                path: PathBuf::from("run.sh"),
+                mode: 0o755,
+            },
+        ])
+    );
+
+    assert_eq!(
+        parse_patch("*** Begin Patch\n*** Add Binary File: bad.bin\n+not base64!\n*** End Patch"),
+        Err(InvalidHunkError {
+            message:
+                "Invalid base64 contents for binary file 'bad.bin': Invalid symbol 32, offset 3."
+                    .to_string(),
+            line_number: 2,
+        })
+    );
+    assert_eq!(
+        parse_patch("*** Begin Patch\n*** Add Symlink: link\n*** End Patch"),
+        Err(InvalidHunkError {
+            message: "Expected '*** Link to:' line after the hunk header for path 'link'"
+                .to_string(),
+            line_number: 3,
+        })
+    );
+    assert_eq!(
+        parse_patch("*** Begin Patch\n*** Set Mode: run.sh\n*** Mode: 999\n*** End Patch"),
+        Err(InvalidHunkError {
+            message: "Invalid mode '999' for path 'run.sh'; expected octal permission bits such as 644 or 755"
+                .to_string(),
+            line_number: 3,
+        })
+    );
+    for mode in ["4755", "104755", "1777"] {
+        assert_eq!(
+            parse_patch(&format!(
+                "*** Begin Patch\n*** Set Mode: run.sh\n*** Mode: {mode}\n*** End Patch"
+            )),
+            Err(InvalidHunkError {
+                message: format!(
+                    "Invalid mode '{mode}' for path 'run.sh'; expected octal permission bits such as 644 or 755"
+                ),
+                line_number: 3,
+            })
+        );
+    }
+}
+
 #[test]
 fn test_update_file_chunk() {
     assert_eq!(
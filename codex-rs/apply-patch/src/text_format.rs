@@ -0,0 +1,196 @@
+//! Preservation of a file's on-disk text conventions across an update.
+//!
+//! Patches are matched against lines with their line endings and byte-order
+//! mark removed. When the updated file is written back, untouched lines keep
+//! their original endings, new lines use the file's predominant line ending,
+//! and the BOM and trailing-newline state of the original are restored.
+
+const UTF8_BOM: char = '\u{FEFF}';
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum LineEnding {
+    #[default]
+    Lf,
+    CrLf,
+}
+
+/// Text conventions detected in the original contents of a file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct TextFormat {
+    pub(crate) has_bom: bool,
+    /// The line ending used by most lines, applied to lines the patch adds.
+    pub(crate) line_ending: LineEnding,
+    pub(crate) has_trailing_newline: bool,
+}
+
+/// The lines of a file, both as stored and with line endings removed.
+pub(crate) struct FileLines {
+    pub(crate) format: TextFormat,
+    /// Lines as stored, including a trailing `\r` for CRLF-terminated lines.
+    /// An unterminated final line is given the predominant line ending here so
+    /// that it stays correctly terminated if the patch appends after it;
+    /// [`TextFormat::join`] removes it again if the line remains last.
+    pub(crate) raw: Vec<String>,
+    /// Lines with their `\r` removed, used for matching patch chunks.
+    pub(crate) stripped: Vec<String>,
+}
+
+impl FileLines {
+    pub(crate) fn parse(contents: &str) -> Self {
+        let (has_bom, body) = match contents.strip_prefix(UTF8_BOM) {
+            Some(body) => (true, body),
+            None => (false, contents),
+        };
+        let mut raw: Vec<String> = body.split('\n').map(String::from).collect();
+        // Drop the trailing empty element that results from the final newline
+        // so that line counts match the behaviour of standard `diff`.
+        let has_trailing_newline = body.is_empty() || body.ends_with('\n');
+        if has_trailing_newline {
+            raw.pop();
+        }
+
+        let terminated_lines = if has_trailing_newline {
+            raw.len()
+        } else {
+            raw.len().saturating_sub(1)
+        };
+        let crlf_lines = raw[..terminated_lines]
+            .iter()
+            .filter(|line| line.ends_with('\r'))
+            .count();
+        let line_ending = if crlf_lines * 2 > terminated_lines {
+            LineEnding::CrLf
+        } else {
+            LineEnding::Lf
+        };
+
+        let stripped = raw
+            .iter()
+            .map(|line| line.strip_suffix('\r').unwrap_or(line).to_string())
+            .collect();
+        if !has_trailing_newline
+            && line_ending == LineEnding::CrLf
+            && let Some(last) = raw.last_mut()
+            && !last.ends_with('\r')
+        {
+            last.push('\r');
+        }
+        Self {
+            format: TextFormat {
+                has_bom,
+                line_ending,
+                has_trailing_newline,
+            },
+            raw,
+            stripped,
+        }
+    }
+}
+
+impl TextFormat {
+    /// Gives `line` (which has no line ending) the predominant line ending.
+    pub(crate) fn terminate(self, line: &str) -> String {
+        match self.line_ending {
+            LineEnding::Lf => line.to_string(),
+            LineEnding::CrLf => format!("{line}\r"),
+        }
+    }
+
+    /// Joins raw lines back into file contents.
+    pub(crate) fn join(self, mut lines: Vec<String>) -> String {
+        let mut contents = String::new();
+        if self.has_bom {
+            contents.push(UTF8_BOM);
+        }
+        if !self.has_trailing_newline
+            && let Some(last) = lines.last_mut()
+            && last.ends_with('\r')
+        {
+            // The final line has no newline, so it must not keep the `\r`
+            // half of a CRLF either.
+            last.pop();
+        }
+        contents.push_str(&lines.join("\n"));
+        if self.has_trailing_newline && !lines.is_empty() {
+            contents.push('\n');
+        }
+        contents
+    }
+}
+
+/// Maps replacement lines computed against stripped lines back to raw lines:
+/// lines the replacement keeps from the original region reuse the original
+/// raw line (and so its ending), and every other line gets the predominant
+/// line ending.
+pub(crate) fn restore_line_endings(
+    raw_region: &[String],
+    stripped_region: &[String],
+    new_lines: &[String],
+    format: TextFormat,
+) -> Vec<String> {
+    let mut restored: Vec<String> = new_lines
+        .iter()
+        .map(|line| format.terminate(line))
+        .collect();
+    for op in similar::capture_diff_slices(similar::Algorithm::Myers, stripped_region, new_lines) {
+        if let similar::DiffOp::Equal {
+            old_index,
+            new_index,
+            len,
+        } = op
+        {
+            restored[new_index..new_index + len]
+                .clone_from_slice(&raw_region[old_index..old_index + len]);
+        }
+    }
+    restored
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn detects_crlf_bom_and_missing_trailing_newline() {
+        let lines = FileLines::parse("\u{FEFF}one\r\ntwo\r\nthree");
+        assert_eq!(
+            lines.format,
+            TextFormat {
+                has_bom: true,
+                line_ending: LineEnding::CrLf,
+                has_trailing_newline: false,
+            }
+        );
+        assert_eq!(lines.stripped, vec!["one", "two", "three"]);
+        assert_eq!(lines.format.join(lines.raw), "\u{FEFF}one\r\ntwo\r\nthree");
+    }
+
+    #[test]
+    fn appending_after_unterminated_crlf_line_terminates_it() {
+        let lines = FileLines::parse("one\r\ntwo");
+        let mut raw = lines.raw;
+        raw.push(lines.format.terminate("three"));
+        assert_eq!(lines.format.join(raw), "one\r\ntwo\r\nthree");
+    }
+
+    #[test]
+    fn predominant_line_ending_wins_in_mixed_files() {
+        let lines = FileLines::parse("a\r\nb\nc\r\n");
+        assert_eq!(lines.format.line_ending, LineEnding::CrLf);
+        let lines = FileLines::parse("a\r\nb\nc\n");
+        assert_eq!(lines.format.line_ending, LineEnding::Lf);
+    }
+
+    #[test]
+    fn restores_original_endings_for_kept_lines() {
+        let lines = FileLines::parse("keep\nchange\r\n");
+        let new_lines = vec!["keep".to_string(), "changed".to_string()];
+        let restored = restore_line_endings(&lines.raw, &lines.stripped, &new_lines, lines.format);
+        assert_eq!(restored, vec!["keep", "changed"]);
+
+        let lines = FileLines::parse("keep\r\nchange\r\n");
+        let restored = restore_line_endings(&lines.raw, &lines.stripped, &new_lines, lines.format);
+        assert_eq!(restored, vec!["keep\r", "changed\r"]);
+    }
+}
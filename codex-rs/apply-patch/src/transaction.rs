@@ -0,0 +1,804 @@
+//! All-or-nothing application of parsed hunks.
+//!
+//! Applying a patch happens in two phases:
+//!
+//! 1. **Stage**: every hunk is validated and its resulting file contents are
+//!    derived in memory, in patch order. Later hunks observe the staged result
+//!    of earlier hunks, so a patch that touches the same file twice behaves as
+//!    if it had been applied sequentially. Nothing is written in this phase.
+//! 2. **Commit**: the original state of every touched path is captured and the
+//!    staged states are written out, deletions (including the sources of
+//!    moves) before files and symlinks, with any staged mode change applied
+//!    right after a path is written. If any step fails, every path that was
+//!    already written is restored to its original state (and any directories
+//!    created along the way are removed) before the failure is reported.
+//!
+//! Either way, a failure is reported as a [`HunkFailure`] naming the hunk that
+//! could not be applied.
+
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use codex_exec_server::CreateDirectoryOptions;
+use codex_exec_server::ExecutorFileSystem;
+use codex_exec_server::FileSystemSandboxContext;
+use codex_exec_server::RemoveOptions;
+use codex_utils_absolute_path::AbsolutePathBuf;
+use thiserror::Error;
+
+use crate::AffectedPaths;
+use crate::AppliedPatch;
+use crate::ApplyPatchError;
+use crate::ContextMismatch;
+use crate::Hunk;
+use crate::derive_new_contents_from_text;
+
+/// Describes the hunk that prevented a patch from being applied. When this is
+/// reported, no file on disk has been modified by the patch.
+#[derive(Debug, Clone, Error, PartialEq)]
+#[error("{message}")]
+pub struct HunkFailure {
+    /// Zero-based index of the failing hunk within the parsed patch.
+    pub hunk_index: usize,
+    /// Path of the failing hunk, spelled as it appears in the patch.
+    pub path: PathBuf,
+    pub message: String,
+    /// Set when the hunk failed because its lines could not be located in the
+    /// target file.
+    pub context_mismatch: Option<Box<ContextMismatch>>,
+}
+
+/// The state a path will be left in once the transaction commits.
+#[derive(Debug, Clone)]
+enum StagedState {
+    Write {
+        contents: Vec<u8>,
+        /// For move destinations, the source file whose original permissions
+        /// the destination should inherit.
+        permissions_from: Option<AbsolutePathBuf>,
+    },
+    Symlink {
+        target: PathBuf,
+    },
+    /// The path keeps its current contents; only its mode changes.
+    Unchanged,
+    Delete,
+}
+
+impl StagedState {
+    fn write(contents: Vec<u8>) -> Self {
+        Self::Write {
+            contents,
+            permissions_from: None,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct StagedPath {
+    path: AbsolutePathBuf,
+    state: StagedState,
+    /// Permission bits to apply once the state has been written.
+    mode: Option<u32>,
+    /// Index of the last hunk that touched this path, used to attribute commit
+    /// failures to a hunk.
+    hunk_index: usize,
+    /// Spelling of the path from that hunk.
+    hunk_path: PathBuf,
+}
+
+/// Original on-disk state of a path, captured right before committing.
+enum OriginalState {
+    Missing,
+    File {
+        contents: Vec<u8>,
+        mode: Option<u32>,
+    },
+    /// A directory occupies the path. Writing a file over it fails, so there
+    /// is never anything to restore.
+    Directory,
+    /// A symlink occupies the path. Deleting or replacing the link is undone
+    /// by recreating it; writes through it are undone from `resolved`.
+    Symlink {
+        target: PathBuf,
+        resolved: Box<OriginalState>,
+    },
+}
+
+/// Staged changes for every path touched by a patch, in first-touched order.
+#[derive(Default)]
+struct StagedChanges {
+    entries: Vec<StagedPath>,
+    index_by_path: HashMap<PathBuf, usize>,
+}
+
+impl StagedChanges {
+    fn get(&self, path: &AbsolutePathBuf) -> Option<&StagedState> {
+        self.index_by_path
+            .get(path.as_path())
+            .map(|idx| &self.entries[*idx].state)
+    }
+
+    fn stage(
+        &mut self,
+        path: AbsolutePathBuf,
+        state: StagedState,
+        hunk_index: usize,
+        hunk_path: PathBuf,
+    ) {
+        if let Some(idx) = self.index_by_path.get(path.as_path()) {
+            let entry = &mut self.entries[*idx];
+            // A mode set by an earlier hunk survives later content changes
+            // but not the file being replaced by a symlink or deleted.
+            if matches!(state, StagedState::Symlink { .. } | StagedState::Delete) {
+                entry.mode = None;
+            }
+            entry.state = state;
+            entry.hunk_index = hunk_index;
+            entry.hunk_path = hunk_path;
+            return;
+        }
+        self.push(path, state, /*mode*/ None, hunk_index, hunk_path);
+    }
+
+    fn set_mode(
+        &mut self,
+        path: AbsolutePathBuf,
+        mode: u32,
+        hunk_index: usize,
+        hunk_path: PathBuf,
+    ) {
+        if let Some(idx) = self.index_by_path.get(path.as_path()) {
+            let entry = &mut self.entries[*idx];
+            entry.mode = Some(mode);
+            entry.hunk_index = hunk_index;
+            entry.hunk_path = hunk_path;
+            return;
+        }
+        self.push(
+            path,
+            StagedState::Unchanged,
+            Some(mode),
+            hunk_index,
+            hunk_path,
+        );
+    }
+
+    fn push(
+        &mut self,
+        path: AbsolutePathBuf,
+        state: StagedState,
+        mode: Option<u32>,
+        hunk_index: usize,
+        hunk_path: PathBuf,
+    ) {
+        self.index_by_path
+            .insert(path.to_path_buf(), self.entries.len());
+        self.entries.push(StagedPath {
+            path,
+            state,
+            mode,
+            hunk_index,
+            hunk_path,
+        });
+    }
+}
+
+/// Applies `hunks` atomically: either every change lands or none does.
+pub(crate) async fn apply_hunks_atomically(
+    hunks: &[Hunk],
+    cwd: &AbsolutePathBuf,
+    fs: &dyn ExecutorFileSystem,
+    sandbox: Option<&FileSystemSandboxContext>,
+) -> Result<AffectedPaths, HunkFailure> {
+    let (staged, affected) = stage_hunks(hunks, cwd, fs, sandbox).await?;
+    commit(&staged, fs, sandbox).await?;
+    Ok(affected)
+}
+
+async fn stage_hunks(
+    hunks: &[Hunk],
+    cwd: &AbsolutePathBuf,
+    fs: &dyn ExecutorFileSystem,
+    sandbox: Option<&FileSystemSandboxContext>,
+) -> Result<(StagedChanges, AffectedPaths), HunkFailure> {
+    let mut staged = StagedChanges::default();
+    let mut added: Vec<PathBuf> = Vec::new();
+    let mut modified: Vec<PathBuf> = Vec::new();
+    let mut deleted: Vec<PathBuf> = Vec::new();
+
+    for (hunk_index, hunk) in hunks.iter().enumerate() {
+        let affected_path = hunk.path().to_path_buf();
+        let path_abs = hunk.resolve_path(cwd);
+        let failure = |err: anyhow::Error| HunkFailure {
+            hunk_index,
+            path: affected_path.clone(),
+            message: err.to_string(),
+            context_mismatch: None,
+        };
+        match hunk {
+            Hunk::AddFile { contents, .. } => {
+                staged.stage(
+                    path_abs,
+                    StagedState::write(contents.clone().into_bytes()),
+                    hunk_index,
+                    affected_path.clone(),
+                );
+                added.push(affected_path);
+            }
+            Hunk::DeleteFile { .. } => {
+                ensure_existing_file(&staged, &path_abs, fs, sandbox)
+                    .await
+                    .with_context(|| format!("Failed to delete file {}", path_abs.display()))
+                    .map_err(failure)?;
+                staged.stage(
+                    path_abs,
+                    StagedState::Delete,
+                    hunk_index,
+                    affected_path.clone(),
+                );
+                deleted.push(affected_path);
+            }
+            Hunk::UpdateFile {
+                move_path, chunks, ..
+            } => {
+                let original_contents = read_staged_text(&staged, &path_abs, fs, sandbox)
+                    .await
+                    .map_err(|err| failure(err.into()))?;
+                let AppliedPatch { new_contents, .. } =
+                    derive_new_contents_from_text(original_contents, &path_abs, chunks).map_err(
+                        |err| match err {
+                            ApplyPatchError::ContextNotFound(mismatch) => HunkFailure {
+                                hunk_index,
+                                path: affected_path.clone(),
+                                message: mismatch.to_string(),
+                                context_mismatch: Some(mismatch),
+                            },
+                            err => failure(err.into()),
+                        },
+                    )?;
+                if let Some(dest) = move_path {
+                    let dest_abs = AbsolutePathBuf::resolve_path_against_base(dest, cwd);
+                    staged.stage(
+                        dest_abs,
+                        StagedState::Write {
+                            contents: new_contents.into_bytes(),
+                            permissions_from: Some(path_abs.clone()),
+                        },
+                        hunk_index,
+                        affected_path.clone(),
+                    );
+                    ensure_existing_file(&staged, &path_abs, fs, sandbox)
+                        .await
+                        .with_context(|| {
+                            format!("Failed to remove original {}", path_abs.display())
+                        })
+                        .map_err(failure)?;
+                    staged.stage(
+                        path_abs,
+                        StagedState::Delete,
+                        hunk_index,
+                        affected_path.clone(),
+                    );
+                } else {
+                    staged.stage(
+                        path_abs,
+                        StagedState::write(new_contents.into_bytes()),
+                        hunk_index,
+                        affected_path.clone(),
+                    );
+                }
+                modified.push(affected_path);
+            }
+            Hunk::AddBinaryFile { contents, .. } => {
+                staged.stage(
+                    path_abs,
+                    StagedState::write(contents.clone()),
+                    hunk_index,
+                    affected_path.clone(),
+                );
+                added.push(affected_path);
+            }
+            Hunk::UpdateBinaryFile { contents, .. } => {
+                ensure_existing_file(&staged, &path_abs, fs, sandbox)
+                    .await
+                    .with_context(|| format!("Failed to update binary file {}", path_abs.display()))
+                    .map_err(failure)?;
+                staged.stage(
+                    path_abs,
+                    StagedState::write(contents.clone()),
+                    hunk_index,
+                    affected_path.clone(),
+                );
+                modified.push(affected_path);
+            }
+            Hunk::AddSymlink { target, .. } => {
+                ensure_vacant(&staged, &path_abs, fs, sandbox)
+                    .await
+                    .with_context(|| format!("Failed to create symlink {}", path_abs.display()))
+                    .map_err(failure)?;
+                staged.stage(
+                    path_abs,
+                    StagedState::Symlink {
+                        target: target.clone(),
+                    },
+                    hunk_index,
+                    affected_path.clone(),
+                );
+                added.push(affected_path);
+            }
+            Hunk::SetMode { mode, .. } => {
+                ensure_mode_settable(&staged, &path_abs, fs, sandbox)
+                    .await
+                    .with_context(|| format!("Failed to set mode of {}", path_abs.display()))
+                    .map_err(failure)?;
+                staged.set_mode(path_abs, *mode, hunk_index, affected_path.clone());
+                modified.push(affected_path);
+            }
+        }
+    }
+
+    Ok((
+        staged,
+        AffectedPaths {
+            added,
+            modified,
+            deleted,
+        },
+    ))
+}
+
+/// Reads the current text of `path`, preferring the staged state over disk.
+async fn read_staged_text(
+    staged: &StagedChanges,
+    path: &AbsolutePathBuf,
+    fs: &dyn ExecutorFileSystem,
+    sandbox: Option<&FileSystemSandboxContext>,
+) -> Result<String, ApplyPatchError> {
+    let context = || format!("Failed to read file to update {}", path.display());
+    let result = match staged.get(path) {
+        Some(StagedState::Write { contents, .. }) => String::from_utf8(contents.clone())
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err)),
+        Some(StagedState::Symlink { .. }) => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "path is a symlink created by an earlier hunk in this patch",
+        )),
+        Some(StagedState::Delete) => Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            "file is deleted by an earlier hunk in this patch",
+        )),
+        Some(StagedState::Unchanged) | None => fs.read_file_text(path, sandbox).await,
+    };
+    result.map_err(|source| {
+        ApplyPatchError::IoError(crate::IoError {
+            context: context(),
+            source,
+        })
+    })
+}
+
+/// Verifies that `path` currently refers to an existing file (or symlink)
+/// rather than a directory.
+async fn ensure_existing_file(
+    staged: &StagedChanges,
+    path: &AbsolutePathBuf,
+    fs: &dyn ExecutorFileSystem,
+    sandbox: Option<&FileSystemSandboxContext>,
+) -> io::Result<()> {
+    match staged.get(path) {
+        Some(StagedState::Write { .. } | StagedState::Symlink { .. }) => Ok(()),
+        Some(StagedState::Delete) => Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            "file is deleted by an earlier hunk in this patch",
+        )),
+        Some(StagedState::Unchanged) | None => {
+            let metadata = fs.get_metadata(path, sandbox).await?;
+            if metadata.is_directory {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "path is a directory",
+                ));
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Verifies that nothing occupies `path` once earlier hunks are applied, so
+/// that a symlink can be created there.
+async fn ensure_vacant(
+    staged: &StagedChanges,
+    path: &AbsolutePathBuf,
+    fs: &dyn ExecutorFileSystem,
+    sandbox: Option<&FileSystemSandboxContext>,
+) -> io::Result<()> {
+    match staged.get(path) {
+        Some(StagedState::Delete) => Ok(()),
+        Some(_) => Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            "path is created by an earlier hunk in this patch",
+        )),
+        None => match fs.get_metadata(path, sandbox).await {
+            Ok(_) => Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                "path already exists",
+            )),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(err),
+        },
+    }
+}
+
+/// Verifies that `path` is a regular file whose mode can be changed. Changing
+/// the mode of a symlink would change its target instead, so symlinks are
+/// rejected.
+async fn ensure_mode_settable(
+    staged: &StagedChanges,
+    path: &AbsolutePathBuf,
+    fs: &dyn ExecutorFileSystem,
+    sandbox: Option<&FileSystemSandboxContext>,
+) -> io::Result<()> {
+    let symlink_error = || {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "cannot set the mode of a symlink",
+        )
+    };
+    match staged.get(path) {
+        Some(StagedState::Symlink { .. }) => Err(symlink_error()),
+        Some(StagedState::Write { .. }) => Ok(()),
+        Some(StagedState::Delete) => Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            "file is deleted by an earlier hunk in this patch",
+        )),
+        Some(StagedState::Unchanged) | None => {
+            let metadata = fs.get_metadata(path, sandbox).await?;
+            if metadata.is_symlink {
+                return Err(symlink_error());
+            }
+            if metadata.is_directory {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "path is a directory",
+                ));
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Writes every staged path, restoring all originals if any write fails.
+async fn commit(
+    staged: &StagedChanges,
+    fs: &dyn ExecutorFileSystem,
+    sandbox: Option<&FileSystemSandboxContext>,
+) -> Result<(), HunkFailure> {
+    let mut originals = Vec::with_capacity(staged.entries.len());
+    for entry in &staged.entries {
+        let original = capture_original(&entry.path, fs, sandbox)
+            .await
+            .with_context(|| format!("Failed to read original {}", entry.path.display()))
+            .map_err(|err| commit_failure(entry, err))?;
+        originals.push(original);
+    }
+
+    // Delete before writing so that a new path can take the place of a
+    // deleted one, e.g. a file `a` replaced by `a/b.txt`. Each path has exactly
+    // one staged state, so the order does not otherwise affect the result.
+    let (deletes, writes): (Vec<usize>, Vec<usize>) = (0..staged.entries.len())
+        .partition(|idx| matches!(staged.entries[*idx].state, StagedState::Delete));
+    let order: Vec<usize> = deletes.into_iter().chain(writes).collect();
+
+    let mut created_dirs: Vec<AbsolutePathBuf> = Vec::new();
+    for (committed, idx) in order.iter().enumerate() {
+        let entry = &staged.entries[*idx];
+        let original = &originals[*idx];
+        let result = match &entry.state {
+            StagedState::Write {
+                contents,
+                permissions_from,
+            } => {
+                // Move sources are already deleted, so their mode comes from
+                // the state captured before committing.
+                let inherited_mode = permissions_from
+                    .as_ref()
+                    .and_then(|source| staged.index_by_path.get(source.as_path()))
+                    .and_then(|source_idx| match &originals[*source_idx] {
+                        OriginalState::File { mode, .. } => *mode,
+                        _ => None,
+                    });
+                write_creating_parents(
+                    fs,
+                    &entry.path,
+                    contents.clone(),
+                    inherited_mode,
+                    sandbox,
+                    &mut created_dirs,
+                )
+                .await
+            }
+            StagedState::Symlink { target } => {
+                create_symlink_creating_parents(
+                    fs,
+                    &entry.path,
+                    target,
+                    original,
+                    sandbox,
+                    &mut created_dirs,
+                )
+                .await
+            }
+            StagedState::Unchanged => Ok(()),
+            StagedState::Delete => fs
+                .remove(
+                    &entry.path,
+                    RemoveOptions {
+                        recursive: false,
+                        force: false,
+                    },
+                    sandbox,
+                )
+                .await
+                .with_context(|| format!("Failed to delete file {}", entry.path.display())),
+        };
+        let result = match (result, entry.mode) {
+            (Ok(()), Some(mode)) => fs
+                .set_permissions(&entry.path, mode, sandbox)
+                .await
+                .with_context(|| format!("Failed to set mode of {}", entry.path.display())),
+            (result, _) => result,
+        };
+        if let Err(err) = result {
+            let mut failure = commit_failure(entry, err);
+            let attempted = &order[..=committed];
+            let rollback_errors = rollback(
+                attempted
+                    .iter()
+                    .map(|idx| (&staged.entries[*idx], &originals[*idx])),
+                &created_dirs,
+                fs,
+                sandbox,
+            )
+            .await;
+            if !rollback_errors.is_empty() {
+                failure.message = format!(
+                    "{}\nFailed to restore original state: {}",
+                    failure.message,
+                    rollback_errors.join("; ")
+                );
+            }
+            return Err(failure);
+        }
+    }
+    Ok(())
+}
+
+fn commit_failure(entry: &StagedPath, err: anyhow::Error) -> HunkFailure {
+    HunkFailure {
+        hunk_index: entry.hunk_index,
+        path: entry.hunk_path.clone(),
+        message: err.to_string(),
+        context_mismatch: None,
+    }
+}
+
+async fn capture_original(
+    path: &AbsolutePathBuf,
+    fs: &dyn ExecutorFileSystem,
+    sandbox: Option<&FileSystemSandboxContext>,
+) -> io::Result<OriginalState> {
+    let metadata = match fs.get_metadata(path, sandbox).await {
+        Ok(metadata) => metadata,
+        // A path below an existing file, e.g. `a/b.txt` when `a` is a file
+        // deleted by this patch, cannot exist either.
+        Err(err)
+            if matches!(
+                err.kind(),
+                io::ErrorKind::NotFound | io::ErrorKind::NotADirectory
+            ) =>
+        {
+            return Ok(OriginalState::Missing);
+        }
+        Err(err) => return Err(err),
+    };
+    let resolved = if metadata.is_directory {
+        OriginalState::Directory
+    } else {
+        OriginalState::File {
+            contents: fs.read_file(path, sandbox).await?,
+            mode: metadata.mode,
+        }
+    };
+    Ok(match metadata.symlink_target {
+        Some(target) => OriginalState::Symlink {
+            target,
+            resolved: Box::new(resolved),
+        },
+        None => resolved,
+    })
+}
+
+/// Writes `contents` to `path`, creating missing parent directories. Any
+/// directory created here is recorded in `created_dirs` so that it can be
+/// removed on rollback. When `inherited_mode` is set, it is applied to `path`
+/// once written.
+async fn write_creating_parents(
+    fs: &dyn ExecutorFileSystem,
+    path: &AbsolutePathBuf,
+    contents: Vec<u8>,
+    inherited_mode: Option<u32>,
+    sandbox: Option<&FileSystemSandboxContext>,
+    created_dirs: &mut Vec<AbsolutePathBuf>,
+) -> anyhow::Result<()> {
+    create_parents(fs, path, sandbox, created_dirs).await?;
+    fs.write_file(path, contents, sandbox)
+        .await
+        .with_context(|| format!("Failed to write file {}", path.display()))?;
+    if let Some(mode) = inherited_mode {
+        fs.set_permissions(path, mode, sandbox)
+            .await
+            .with_context(|| format!("Failed to write file {}", path.display()))?;
+    }
+    Ok(())
+}
+
+/// Creates a symlink at `path` pointing to `target`, creating missing parent
+/// directories. A file or symlink that an earlier hunk deleted is still on disk
+/// at this point, so it is removed first.
+async fn create_symlink_creating_parents(
+    fs: &dyn ExecutorFileSystem,
+    path: &AbsolutePathBuf,
+    target: &Path,
+    original: &OriginalState,
+    sandbox: Option<&FileSystemSandboxContext>,
+    created_dirs: &mut Vec<AbsolutePathBuf>,
+) -> anyhow::Result<()> {
+    create_parents(fs, path, sandbox, created_dirs).await?;
+    if matches!(
+        original,
+        OriginalState::File { .. } | OriginalState::Symlink { .. }
+    ) {
+        fs.remove(
+            path,
+            RemoveOptions {
+                recursive: false,
+                force: true,
+            },
+            sandbox,
+        )
+        .await
+        .with_context(|| format!("Failed to delete file {}", path.display()))?;
+    }
+    fs.create_symlink(path, target, sandbox)
+        .await
+        .with_context(|| format!("Failed to create symlink {}", path.display()))
+}
+
+/// Creates the missing parent directories of `path`, recording the topmost
+/// one in `created_dirs`.
+async fn create_parents(
+    fs: &dyn ExecutorFileSystem,
+    path: &AbsolutePathBuf,
+    sandbox: Option<&FileSystemSandboxContext>,
+    created_dirs: &mut Vec<AbsolutePathBuf>,
+) -> anyhow::Result<()> {
+    let Some(parent) = path.parent() else {
+        return Ok(());
+    };
+    let topmost_missing = topmost_missing_ancestor(&parent, fs, sandbox)
+        .await
+        .with_context(|| format!("Failed to inspect parent directories of {}", path.display()))?;
+    if let Some(topmost_missing) = topmost_missing {
+        fs.create_directory(&parent, CreateDirectoryOptions { recursive: true }, sandbox)
+            .await
+            .with_context(|| {
+                format!("Failed to create parent directories for {}", path.display())
+            })?;
+        created_dirs.push(topmost_missing);
+    }
+    Ok(())
+}
+
+/// Returns the highest ancestor of `dir` (inclusive) that does not exist yet.
+/// Only `NotFound` counts as missing: rollback removes the returned directory
+/// recursively, so any other error must not be mistaken for absence.
+async fn topmost_missing_ancestor(
+    dir: &AbsolutePathBuf,
+    fs: &dyn ExecutorFileSystem,
+    sandbox: Option<&FileSystemSandboxContext>,
+) -> io::Result<Option<AbsolutePathBuf>> {
+    let mut missing = None;
+    let mut current = Some(dir.clone());
+    while let Some(candidate) = current {
+        match fs.get_metadata(&candidate, sandbox).await {
+            Ok(_) => break,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => return Err(err),
+        }
+        current = candidate.parent();
+        missing = Some(candidate);
+    }
+    Ok(missing)
+}
+
+/// Removes directories created during the commit, then restores each
+/// attempted path to its original state in reverse order. The directories go
+/// first because one may occupy the place of a deleted file; nothing inside
+/// them existed before the commit. Returns a description of every step that
+/// failed.
+async fn rollback<'a>(
+    attempted: impl DoubleEndedIterator<Item = (&'a StagedPath, &'a OriginalState)>,
+    created_dirs: &[AbsolutePathBuf],
+    fs: &dyn ExecutorFileSystem,
+    sandbox: Option<&FileSystemSandboxContext>,
+) -> Vec<String> {
+    let mut errors = Vec::new();
+    for dir in created_dirs.iter().rev() {
+        if let Err(err) = fs
+            .remove(
+                dir,
+                RemoveOptions {
+                    recursive: true,
+                    force: true,
+                },
+                sandbox,
+            )
+            .await
+        {
+            errors.push(format!("{}: {err}", dir.display()));
+        }
+    }
+    for (entry, original) in attempted.rev() {
+        if let Err(err) = restore_original(entry, original, fs, sandbox).await {
+            errors.push(format!("{}: {err}", entry.path.display()));
+        }
+    }
+    errors
+}
+
+async fn restore_original(
+    entry: &StagedPath,
+    original: &OriginalState,
+    fs: &dyn ExecutorFileSystem,
+    sandbox: Option<&FileSystemSandboxContext>,
+) -> io::Result<()> {
+    let remove_options = RemoveOptions {
+        recursive: false,
+        force: true,
+    };
+    // Writing the original contents through a newly created symlink would
+    // modify its target, so the link goes first.
+    if matches!(entry.state, StagedState::Symlink { .. }) {
+        fs.remove(&entry.path, remove_options, sandbox).await?;
+    }
+    let original = match original {
+        OriginalState::Symlink { target, .. }
+            if matches!(
+                entry.state,
+                StagedState::Delete | StagedState::Symlink { .. }
+            ) =>
+        {
+            fs.remove(&entry.path, remove_options, sandbox).await?;
+            return fs.create_symlink(&entry.path, target, sandbox).await;
+        }
+        OriginalState::Symlink { resolved, .. } => resolved.as_ref(),
+        original => original,
+    };
+    match original {
+        OriginalState::Directory | OriginalState::Symlink { .. } => Ok(()),
+        OriginalState::File { contents, mode } => {
+            if !matches!(entry.state, StagedState::Unchanged) {
+                fs.write_file(&entry.path, contents.clone(), sandbox)
+                    .await?;
+            }
+            match mode {
+                Some(mode) => fs.set_permissions(&entry.path, *mode, sandbox).await,
+                None => Ok(()),
+            }
+        }
+        OriginalState::Missing => fs.remove(&entry.path, remove_options, sandbox).await,
+    }
+}
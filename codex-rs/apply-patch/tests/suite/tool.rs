@@ -232,13 +232,13 @@ fn test_apply_patch_cli_rejects_invalid_hunk_header() -> anyhow::Result<()> {
         .arg("*** Begin Patch\n*** Frobnicate File: foo\n*** End Patch")
         .assert()
         .failure()
-        .stderr("Invalid patch hunk on line 2: '*** Frobnicate File: foo' is not a valid hunk header. Valid hunk headers: '*** Add File: {path}', '*** Delete File: {path}', '*** Update File: {path}'\n");
+        .stderr("Invalid patch hunk on line 2: '*** Frobnicate File: foo' is not a valid hunk header. Valid hunk headers: '*** Add File: {path}', '*** Delete File: {path}', '*** Update File: {path}', '*** Add Binary File: {path}', '*** Update Binary File: {path}', '*** Add Symlink: {path}', '*** Set Mode: {path}'\n");
 
     Ok(())
 }
 
 #[test]
-fn test_apply_patch_cli_updates_file_appends_trailing_newline() -> anyhow::Result<()> {
+fn test_apply_patch_cli_update_preserves_missing_trailing_newline() -> anyhow::Result<()> {
     let tmp = tempdir()?;
     let target_path = tmp.path().join("no_newline.txt");
     fs::write(&target_path, "no newline at end")?;
@@ -250,21 +250,21 @@ fn test_apply_patch_cli_updates_file_appends_trailing_newline() -> anyhow::Resul
     .success()
     .stdout("Success. Updated the following files:\nM no_newline.txt\n");
 
-    let contents = fs::read_to_string(&target_path)?;
-    assert!(contents.ends_with('\n'));
-    assert_eq!(contents, "first line\nsecond line\n");
+    assert_eq!(fs::read_to_string(&target_path)?, "first line\nsecond line");
 
     Ok(())
 }
 
 #[test]
-fn test_apply_patch_cli_failure_after_partial_success_leaves_changes() -> anyhow::Result<()> {
+fn test_apply_patch_cli_failure_after_partial_success_rolls_back() -> anyhow::Result<()> {
     let tmp = tempdir()?;
     let new_file = tmp.path().join("created.txt");
+    let existing_file = tmp.path().join("existing.txt");
     let missing_file = resolved_under(tmp.path(), "missing.txt")?;
+    fs::write(&existing_file, "original\n")?;
 
     apply_patch_command(tmp.path())?
-        .arg("*** Begin Patch\n*** Add File: created.txt\n+hello\n*** Update File: missing.txt\n@@\n-old\n+new\n*** End Patch")
+        .arg("*** Begin Patch\n*** Add File: created.txt\n+hello\n*** Update File: existing.txt\n@@\n-original\n+changed\n*** Update File: missing.txt\n@@\n-old\n+new\n*** End Patch")
         .assert()
         .failure()
         .stdout("")
@@ -273,7 +273,8 @@ fn test_apply_patch_cli_failure_after_partial_success_leaves_changes() -> anyhow
             missing_file.display()
         ));
 
-    assert_eq!(fs::read_to_string(&new_file)?, "hello\n");
+    assert!(!new_file.exists());
+    assert_eq!(fs::read_to_string(&existing_file)?, "original\n");
 
     Ok(())
 }
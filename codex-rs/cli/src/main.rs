@@ -21,6 +21,9 @@ use codex_exec::Cli as ExecCli;
 use codex_exec::Command as ExecCommand;
 use codex_exec::ReviewArgs;
 use codex_execpolicy::ExecPolicyCheckCommand;
+use codex_execpolicy::ExecPolicyExplainCommand;
+use codex_execpolicy::ExecPolicyLintCommand;
+use codex_execpolicy::ExecPolicyTestCommand;
 use codex_responses_api_proxy::Args as ResponsesApiProxyArgs;
 use codex_rollout_trace::REDUCED_STATE_FILE_NAME;
 use codex_rollout_trace::replay_bundle;
@@ -44,6 +47,7 @@ mod desktop_app;
 mod marketplace_cmd;
 mod mcp_cmd;
 mod responses_cmd;
+mod thread_store_cmd;
 #[cfg(not(windows))]
 mod wsl_paths;
 
@@ -51,6 +55,8 @@ use crate::marketplace_cmd::MarketplaceCli;
 use crate::mcp_cmd::McpCli;
 use crate::responses_cmd::ResponsesCommand;
 use crate::responses_cmd::run_responses_command;
+use crate::thread_store_cmd::ThreadStoreCli;
+use crate::thread_store_cmd::run_thread_store_command;
 
 use codex_core::build_models_manager;
 use codex_core::clear_memory_roots_contents;
@@ -174,6 +180,10 @@ enum Subcommand {
     /// [EXPERIMENTAL] Run the standalone exec-server service.
     ExecServer(ExecServerCommand),
 
+    /// [EXPERIMENTAL] Serve local thread storage to remote thread-store clients.
+    #[clap(hide = true)]
+    ThreadStore(ThreadStoreCli),
+
     /// Inspect feature flags.
     Features(FeaturesCli),
 }
@@ -353,6 +363,18 @@ enum ExecpolicySubcommand {
     /// Check execpolicy files against a command.
     #[clap(name = "check")]
     Check(ExecPolicyCheckCommand),
+
+    /// Report duplicate, conflicting, and shadowed execpolicy rules.
+    #[clap(name = "lint")]
+    Lint(ExecPolicyLintCommand),
+
+    /// Show which execpolicy rules match a command and which one decides it.
+    #[clap(name = "explain")]
+    Explain(ExecPolicyExplainCommand),
+
+    /// Run a file of expected decisions against execpolicy files.
+    #[clap(name = "test")]
+    Test(ExecPolicyTestCommand),
 }
 
 #[derive(Debug, Parser)]
@@ -443,13 +465,19 @@ struct AppServerCommand {
 
 #[derive(Debug, Parser)]
 struct ExecServerCommand {
-    /// Transport endpoint URL. Supported values: `ws://IP:PORT` (default).
+    /// Transport endpoint URL. Supported values: `ws://IP:PORT` (default),
+    /// `unix://PATH`, `stdio://`.
     #[arg(
         long = "listen",
         value_name = "URL",
         default_value = "ws://127.0.0.1:0"
     )]
     listen: String,
+
+    /// File containing a shared secret that clients must send as `authToken`
+    /// in `initialize`.
+    #[arg(long = "auth-token-file", value_name = "PATH")]
+    auth_token_file: Option<PathBuf>,
 }
 
 #[derive(Debug, clap::Subcommand)]
@@ -1108,6 +1136,30 @@ async fn cli_main(arg0_paths: Arg0DispatchPaths) -> anyhow::Result<()> {
                 )?;
                 run_execpolicycheck(cmd)?
             }
+            ExecpolicySubcommand::Lint(cmd) => {
+                reject_remote_mode_for_subcommand(
+                    root_remote.as_deref(),
+                    root_remote_auth_token_env.as_deref(),
+                    "execpolicy lint",
+                )?;
+                cmd.run()?
+            }
+            ExecpolicySubcommand::Explain(cmd) => {
+                reject_remote_mode_for_subcommand(
+                    root_remote.as_deref(),
+                    root_remote_auth_token_env.as_deref(),
+                    "execpolicy explain",
+                )?;
+                cmd.run()?
+            }
+            ExecpolicySubcommand::Test(cmd) => {
+                reject_remote_mode_for_subcommand(
+                    root_remote.as_deref(),
+                    root_remote_auth_token_env.as_deref(),
+                    "execpolicy test",
+                )?;
+                cmd.run()?
+            }
         },
         Some(Subcommand::Apply(mut apply_cli)) => {
             reject_remote_mode_for_subcommand(
@@ -1155,6 +1207,14 @@ async fn cli_main(arg0_paths: Arg0DispatchPaths) -> anyhow::Result<()> {
             )?;
             run_exec_server_command(cmd, &arg0_paths).await?;
         }
+        Some(Subcommand::ThreadStore(cli)) => {
+            reject_remote_mode_for_subcommand(
+                root_remote.as_deref(),
+                root_remote_auth_token_env.as_deref(),
+                "thread-store",
+            )?;
+            run_thread_store_command(cli, root_config_overrides).await?;
+        }
         Some(Subcommand::Features(FeaturesCli { sub })) => match sub {
             FeaturesSubcommand::List => {
                 reject_remote_mode_for_subcommand(
@@ -1237,7 +1297,11 @@ async fn run_exec_server_command(
         codex_self_exe,
         arg0_paths.codex_linux_sandbox_exe.clone(),
     )?;
-    codex_exec_server::run_main(&cmd.listen, runtime_paths)
+    let auth_token = match cmd.auth_token_file.as_deref() {
+        Some(path) => Some(codex_exec_server::ExecServerAuthToken::from_file(path).await?),
+        None => None,
+    };
+    codex_exec_server::run_main(&cmd.listen, runtime_paths, auth_token)
         .await
         .map_err(anyhow::Error::from_boxed)
 }
@@ -1845,6 +1909,25 @@ mod tests {
         assert!(matches!(cli.subcommand, Some(Subcommand::Responses(_))));
     }
 
+    #[test]
+    fn thread_store_serve_parses_listen_address() {
+        let cli = MultitoolCli::try_parse_from([
+            "codex",
+            "thread-store",
+            "serve",
+            "--listen",
+            "127.0.0.1:7777",
+        ])
+        .expect("parse");
+        let Some(Subcommand::ThreadStore(ThreadStoreCli {
+            subcommand: thread_store_cmd::ThreadStoreSubcommand::Serve(cmd),
+        })) = cli.subcommand
+        else {
+            panic!("expected thread-store serve subcommand");
+        };
+        assert_eq!(cmd.listen.to_string(), "127.0.0.1:7777");
+    }
+
     fn help_from_args(args: &[&str]) -> String {
         let err = MultitoolCli::try_parse_from(args).expect_err("help should short-circuit");
         assert_eq!(err.kind(), clap::error::ErrorKind::DisplayHelp);
@@ -0,0 +1,87 @@
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use clap::Parser;
+use codex_core::config::Config;
+use codex_rollout::RolloutConfig;
+use codex_thread_store::LocalThreadStore;
+use codex_thread_store::RemoteThreadStoreServer;
+use codex_utils_cli::CliConfigOverrides;
+
+#[derive(Debug, Parser)]
+pub(crate) struct ThreadStoreCli {
+    #[command(subcommand)]
+    pub(crate) subcommand: ThreadStoreSubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub(crate) enum ThreadStoreSubcommand {
+    /// Serve the local thread store over the `codex.thread_store.v1` gRPC protocol.
+    Serve(ThreadStoreServeCommand),
+}
+
+#[derive(Debug, Parser)]
+pub(crate) struct ThreadStoreServeCommand {
+    /// Loopback address to listen on. Use port 0 to pick a free port.
+    #[arg(long = "listen", value_name = "ADDR", default_value = "127.0.0.1:0")]
+    pub(crate) listen: SocketAddr,
+}
+
+pub(crate) async fn run_thread_store_command(
+    cli: ThreadStoreCli,
+    root_config_overrides: CliConfigOverrides,
+) -> anyhow::Result<()> {
+    match cli.subcommand {
+        ThreadStoreSubcommand::Serve(cmd) => serve(cmd, root_config_overrides).await,
+    }
+}
+
+async fn serve(
+    cmd: ThreadStoreServeCommand,
+    root_config_overrides: CliConfigOverrides,
+) -> anyhow::Result<()> {
+    ensure_loopback(cmd.listen)?;
+    let cli_overrides = root_config_overrides
+        .parse_overrides()
+        .map_err(anyhow::Error::msg)?;
+    let config = Config::load_with_cli_overrides(cli_overrides).await?;
+    let store = LocalThreadStore::new(RolloutConfig::from_view(&config));
+
+    let listener = tokio::net::TcpListener::bind(cmd.listen).await?;
+    // Print the resolved endpoint so callers using port 0 can point
+    // `experimental_thread_store_endpoint` at it.
+    println!("http://{}", listener.local_addr()?);
+
+    RemoteThreadStoreServer::new(Arc::new(store))
+        .serve_with_shutdown(listener, async {
+            let _ = tokio::signal::ctrl_c().await;
+        })
+        .await?;
+    Ok(())
+}
+
+/// The server does not authenticate clients, so it must not be reachable from
+/// other machines.
+fn ensure_loopback(addr: SocketAddr) -> anyhow::Result<()> {
+    if !addr.ip().is_loopback() {
+        anyhow::bail!(
+            "refusing to listen on {addr}: the thread store server does not authenticate clients, so it only listens on loopback addresses"
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_loopback_addresses_are_accepted() {
+        for addr in ["127.0.0.1:0", "[::1]:4000"] {
+            assert!(ensure_loopback(addr.parse().unwrap()).is_ok(), "{addr}");
+        }
+        for addr in ["0.0.0.0:0", "[::]:4000", "192.168.1.10:4000"] {
+            assert!(ensure_loopback(addr.parse().unwrap()).is_err(), "{addr}");
+        }
+    }
+}
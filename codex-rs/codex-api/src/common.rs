@@ -1,4 +1,5 @@
 use crate::error::ApiError;
+use codex_protocol::ToolName;
 use codex_protocol::config_types::ReasoningSummary as ReasoningSummaryConfig;
 use codex_protocol::config_types::Verbosity as VerbosityConfig;
 use codex_protocol::models::ResponseItem;
@@ -12,6 +13,7 @@ use serde::Deserialize;
 use serde::Serialize;
 use serde_json::Value;
 use std::collections::HashMap;
+use std::collections::HashSet;
 use std::pin::Pin;
 use std::task::Context;
 use std::task::Poll;
@@ -182,6 +184,65 @@ pub struct ResponsesApiRequest {
     pub client_metadata: Option<HashMap<String, String>>,
 }
 
+/// Canonical request for the Chat Completions API.
+///
+/// The Chat Completions body is derived from `input` when the request is sent,
+/// so callers keep working with the same `ResponseItem` history they use for
+/// the Responses API. Serializing this type yields that canonical form, not
+/// the wire body.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct ChatCompletionsApiRequest {
+    pub model: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub instructions: String,
+    pub input: Vec<ResponseItem>,
+    /// Tools in Chat Completions `{"type": "function", "function": ...}` form.
+    pub tools: Vec<Value>,
+    pub tool_choice: String,
+    pub parallel_tool_calls: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reasoning_effort: Option<ReasoningEffortConfig>,
+    /// Flattened function names that belong to a namespace, used to restore
+    /// the namespace on streamed tool calls.
+    #[serde(skip)]
+    pub namespaced_tools: HashMap<String, ToolName>,
+    /// Freeform tools whose function calls carry raw text in an `input`
+    /// argument and map to custom tool calls.
+    #[serde(skip)]
+    pub freeform_tools: HashSet<String>,
+}
+
+/// Canonical request for the Messages API.
+///
+/// As with [`ChatCompletionsApiRequest`], the wire body is derived from
+/// `input` when the request is sent and serializing this type yields the
+/// canonical form.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct MessagesApiRequest {
+    pub model: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub instructions: String,
+    pub input: Vec<ResponseItem>,
+    /// Tools in Messages `{"name", "description", "input_schema"}` form.
+    pub tools: Vec<Value>,
+    pub parallel_tool_calls: bool,
+    pub max_tokens: u64,
+    /// Enables extended thinking with this token budget.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thinking_budget_tokens: Option<u64>,
+    /// Marks the system prompt, last tool, and last message as prompt cache
+    /// breakpoints.
+    pub prompt_caching: bool,
+    /// Flattened function names that belong to a namespace, used to restore
+    /// the namespace on streamed tool calls.
+    #[serde(skip)]
+    pub namespaced_tools: HashMap<String, ToolName>,
+    /// Freeform tools whose `tool_use` blocks carry raw text in an `input`
+    /// property and map to custom tool calls.
+    #[serde(skip)]
+    pub freeform_tools: HashSet<String>,
+}
+
 impl From<&ResponsesApiRequest> for ResponseCreateWsRequest {
     fn from(request: &ResponsesApiRequest) -> Self {
         Self {
@@ -0,0 +1,123 @@
+use crate::auth::SharedAuthProvider;
+use crate::common::MessagesApiRequest;
+use crate::common::ResponseStream;
+use crate::endpoint::session::EndpointSession;
+use crate::error::ApiError;
+use crate::provider::Provider;
+use crate::requests::build_messages_body;
+use crate::requests::headers::build_conversation_headers;
+use crate::requests::headers::insert_header;
+use crate::requests::headers::subagent_header;
+use crate::sse::ToolCallNames;
+use crate::sse::spawn_messages_stream;
+use crate::telemetry::SseTelemetry;
+use codex_client::HttpTransport;
+use codex_client::RequestTelemetry;
+use codex_protocol::protocol::SessionSource;
+use http::HeaderMap;
+use http::HeaderValue;
+use http::Method;
+use std::sync::Arc;
+use tracing::instrument;
+
+/// Streams turns from providers that only expose the Messages API.
+pub struct MessagesClient<T: HttpTransport> {
+    session: EndpointSession<T>,
+    sse_telemetry: Option<Arc<dyn SseTelemetry>>,
+}
+
+#[derive(Default)]
+pub struct MessagesOptions {
+    pub conversation_id: Option<String>,
+    pub session_source: Option<SessionSource>,
+    pub extra_headers: HeaderMap,
+    /// Sent as the `anthropic-version` header when non-empty.
+    pub anthropic_version: String,
+    /// Joined into the `anthropic-beta` header when non-empty.
+    pub anthropic_beta: Vec<String>,
+}
+
+impl<T: HttpTransport> MessagesClient<T> {
+    pub fn new(transport: T, provider: Provider, auth: SharedAuthProvider) -> Self {
+        Self {
+            session: EndpointSession::new(transport, provider, auth),
+            sse_telemetry: None,
+        }
+    }
+
+    pub fn with_telemetry(
+        self,
+        request: Option<Arc<dyn RequestTelemetry>>,
+        sse: Option<Arc<dyn SseTelemetry>>,
+    ) -> Self {
+        Self {
+            session: self.session.with_request_telemetry(request),
+            sse_telemetry: sse,
+        }
+    }
+
+    fn path() -> &'static str {
+        "messages"
+    }
+
+    #[instrument(
+        name = "messages.stream_request",
+        level = "info",
+        skip_all,
+        fields(
+            transport = "messages_http",
+            http.method = "POST",
+            api.path = "messages"
+        )
+    )]
+    pub async fn stream_request(
+        &self,
+        request: MessagesApiRequest,
+        options: MessagesOptions,
+    ) -> Result<ResponseStream, ApiError> {
+        let MessagesOptions {
+            conversation_id,
+            session_source,
+            extra_headers,
+            anthropic_version,
+            anthropic_beta,
+        } = options;
+
+        let body = build_messages_body(&request);
+
+        let mut headers = extra_headers;
+        if !anthropic_version.is_empty() {
+            insert_header(&mut headers, "anthropic-version", &anthropic_version);
+        }
+        if !anthropic_beta.is_empty() {
+            insert_header(&mut headers, "anthropic-beta", &anthropic_beta.join(","));
+        }
+        if let Some(ref conv_id) = conversation_id {
+            insert_header(&mut headers, "x-client-request-id", conv_id);
+        }
+        headers.extend(build_conversation_headers(conversation_id));
+        if let Some(subagent) = subagent_header(&session_source) {
+            insert_header(&mut headers, "x-openai-subagent", &subagent);
+        }
+
+        let stream_response = self
+            .session
+            .stream_with(Method::POST, Self::path(), headers, Some(body), |req| {
+                req.headers.insert(
+                    http::header::ACCEPT,
+                    HeaderValue::from_static("text/event-stream"),
+                );
+            })
+            .await?;
+
+        Ok(spawn_messages_stream(
+            stream_response,
+            self.session.provider().stream_idle_timeout,
+            self.sse_telemetry.clone(),
+            ToolCallNames {
+                namespaced_tools: request.namespaced_tools,
+                freeform_tools: request.freeform_tools,
+            },
+        ))
+    }
+}
@@ -21,9 +21,11 @@ pub use crate::auth::AuthHeaderTelemetry;
 pub use crate::auth::AuthProvider;
 pub use crate::auth::SharedAuthProvider;
 pub use crate::auth::auth_header_telemetry;
+pub use crate::common::ChatCompletionsApiRequest;
 pub use crate::common::CompactionInput;
 pub use crate::common::MemorySummarizeInput;
 pub use crate::common::MemorySummarizeOutput;
+pub use crate::common::MessagesApiRequest;
 pub use crate::common::OpenAiVerbosity;
 pub use crate::common::RawMemory;
 pub use crate::common::RawMemoryMetadata;
@@ -38,8 +40,12 @@ pub use crate::common::WS_REQUEST_HEADER_TRACEPARENT_CLIENT_METADATA_KEY;
 pub use crate::common::WS_REQUEST_HEADER_TRACESTATE_CLIENT_METADATA_KEY;
 pub use crate::common::create_text_param_for_request;
 pub use crate::common::response_create_client_metadata;
+pub use crate::endpoint::ChatCompletionsClient;
+pub use crate::endpoint::ChatCompletionsOptions;
 pub use crate::endpoint::CompactClient;
 pub use crate::endpoint::MemoriesClient;
+pub use crate::endpoint::MessagesClient;
+pub use crate::endpoint::MessagesOptions;
 pub use crate::endpoint::ModelsClient;
 pub use crate::endpoint::RealtimeCallClient;
 pub use crate::endpoint::RealtimeCallResponse;
@@ -62,7 +68,9 @@ pub use crate::provider::Provider;
 pub use crate::provider::RetryConfig;
 pub use crate::provider::is_azure_responses_provider;
 pub use crate::requests::Compression;
+pub use crate::sse::stream_chat_completions_from_fixture;
 pub use crate::sse::stream_from_fixture;
+pub use crate::sse::stream_messages_from_fixture;
 pub use crate::telemetry::SseTelemetry;
 pub use crate::telemetry::WebsocketTelemetry;
 pub use codex_protocol::protocol::RealtimeAudioFrame;
@@ -0,0 +1,464 @@
+use crate::common::ChatCompletionsApiRequest;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::FunctionCallOutputPayload;
+use codex_protocol::models::ImageDetail;
+use codex_protocol::models::ReasoningItemContent;
+use codex_protocol::models::ReasoningItemReasoningSummary;
+use codex_protocol::models::ResponseItem;
+use serde_json::Map;
+use serde_json::Value;
+use serde_json::json;
+use std::collections::HashSet;
+
+/// Builds a streaming Chat Completions request body from Codex history.
+///
+/// Instructions become the leading system message. Reasoning items are
+/// attached as `reasoning_content` to the assistant message that follows them,
+/// and consecutive tool calls are grouped into a single assistant message.
+/// Items with no Chat Completions equivalent are dropped, along with tool
+/// outputs whose call was dropped, since servers reject a `tool` message that
+/// answers no preceding `tool_calls` entry.
+pub(crate) fn build_chat_completions_body(request: &ChatCompletionsApiRequest) -> Value {
+    let mut messages = Vec::new();
+    if !request.instructions.is_empty() {
+        messages.push(json!({"role": "system", "content": request.instructions}));
+    }
+
+    let mut pending_reasoning: Option<String> = None;
+    let mut emitted_call_ids = HashSet::new();
+    for item in &request.input {
+        match item {
+            ResponseItem::Message { role, content, .. } => {
+                let role = match role.as_str() {
+                    "developer" => "system",
+                    role => role,
+                };
+                let mut message = Map::new();
+                message.insert("role".to_string(), Value::String(role.to_string()));
+                message.insert("content".to_string(), message_content(content));
+                if role == "assistant"
+                    && let Some(reasoning) = pending_reasoning.take()
+                {
+                    message.insert("reasoning_content".to_string(), Value::String(reasoning));
+                }
+                messages.push(Value::Object(message));
+            }
+            ResponseItem::Reasoning {
+                summary, content, ..
+            } => {
+                if let Some(text) = reasoning_text(summary, content.as_deref()) {
+                    match &mut pending_reasoning {
+                        Some(pending) => {
+                            pending.push('\n');
+                            pending.push_str(&text);
+                        }
+                        None => pending_reasoning = Some(text),
+                    }
+                }
+            }
+            ResponseItem::FunctionCall {
+                name,
+                namespace,
+                arguments,
+                call_id,
+                ..
+            } => {
+                let name = match namespace {
+                    Some(namespace) => format!("{namespace}{name}"),
+                    None => name.clone(),
+                };
+                push_tool_call(
+                    &mut messages,
+                    &mut pending_reasoning,
+                    call_id,
+                    &name,
+                    arguments.clone(),
+                );
+                emitted_call_ids.insert(call_id.as_str());
+            }
+            ResponseItem::CustomToolCall {
+                call_id,
+                name,
+                input,
+                ..
+            } => {
+                push_tool_call(
+                    &mut messages,
+                    &mut pending_reasoning,
+                    call_id,
+                    name,
+                    json!({"input": input}).to_string(),
+                );
+                emitted_call_ids.insert(call_id.as_str());
+            }
+            ResponseItem::FunctionCallOutput { call_id, output }
+            | ResponseItem::CustomToolCallOutput {
+                call_id, output, ..
+            } => {
+                if !emitted_call_ids.contains(call_id.as_str()) {
+                    continue;
+                }
+                messages.push(json!({
+                    "role": "tool",
+                    "tool_call_id": call_id,
+                    "content": tool_output_text(output),
+                }));
+            }
+            ResponseItem::LocalShellCall { .. }
+            | ResponseItem::ToolSearchCall { .. }
+            | ResponseItem::ToolSearchOutput { .. }
+            | ResponseItem::WebSearchCall { .. }
+            | ResponseItem::ImageGenerationCall { .. }
+            | ResponseItem::GhostSnapshot { .. }
+            | ResponseItem::Compaction { .. }
+            | ResponseItem::Other => {}
+        }
+    }
+
+    let mut body = json!({
+        "model": request.model,
+        "messages": messages,
+        "stream": true,
+        "stream_options": {"include_usage": true},
+    });
+    if !request.tools.is_empty() {
+        body["tools"] = Value::Array(request.tools.clone());
+        body["tool_choice"] = Value::String(request.tool_choice.clone());
+        body["parallel_tool_calls"] = Value::Bool(request.parallel_tool_calls);
+    }
+    if let Some(effort) = request.reasoning_effort {
+        body["reasoning_effort"] = json!(effort);
+    }
+    body
+}
+
+/// Appends a tool call to the trailing assistant message, or starts a new one.
+fn push_tool_call(
+    messages: &mut Vec<Value>,
+    pending_reasoning: &mut Option<String>,
+    call_id: &str,
+    name: &str,
+    arguments: String,
+) {
+    let tool_call = json!({
+        "id": call_id,
+        "type": "function",
+        "function": {"name": name, "arguments": arguments},
+    });
+    if let Some(Value::Object(last)) = messages.last_mut()
+        && last.get("role").and_then(Value::as_str) == Some("assistant")
+    {
+        if let Some(reasoning) = pending_reasoning.take() {
+            last.entry("reasoning_content")
+                .or_insert(Value::String(reasoning));
+        }
+        if let Value::Array(tool_calls) = last
+            .entry("tool_calls")
+            .or_insert_with(|| Value::Array(Vec::new()))
+        {
+            tool_calls.push(tool_call);
+        }
+        return;
+    }
+
+    let mut message = Map::new();
+    message.insert("role".to_string(), Value::String("assistant".to_string()));
+    message.insert("content".to_string(), Value::Null);
+    message.insert("tool_calls".to_string(), Value::Array(vec![tool_call]));
+    if let Some(reasoning) = pending_reasoning.take() {
+        message.insert("reasoning_content".to_string(), Value::String(reasoning));
+    }
+    messages.push(Value::Object(message));
+}
+
+/// Uses plain string content when possible since some Chat Completions
+/// servers only accept content parts on user messages.
+fn message_content(content: &[ContentItem]) -> Value {
+    let has_image = content
+        .iter()
+        .any(|item| matches!(item, ContentItem::InputImage { .. }));
+    if !has_image {
+        let text = content
+            .iter()
+            .filter_map(|item| match item {
+                ContentItem::InputText { text } | ContentItem::OutputText { text } => {
+                    Some(text.as_str())
+                }
+                ContentItem::InputImage { .. } => None,
+            })
+            .collect::<String>();
+        return Value::String(text);
+    }
+
+    let parts = content
+        .iter()
+        .map(|item| match item {
+            ContentItem::InputText { text } | ContentItem::OutputText { text } => {
+                json!({"type": "text", "text": text})
+            }
+            ContentItem::InputImage { image_url, detail } => {
+                let mut image = json!({"url": image_url});
+                if let Some(detail) = detail {
+                    // Chat Completions has no `original` detail level.
+                    let detail = match detail {
+                        ImageDetail::Auto => "auto",
+                        ImageDetail::Low => "low",
+                        ImageDetail::High | ImageDetail::Original => "high",
+                    };
+                    image["detail"] = Value::String(detail.to_string());
+                }
+                json!({"type": "image_url", "image_url": image})
+            }
+        })
+        .collect();
+    Value::Array(parts)
+}
+
+fn reasoning_text(
+    summary: &[ReasoningItemReasoningSummary],
+    content: Option<&[ReasoningItemContent]>,
+) -> Option<String> {
+    // Prefer raw reasoning, which is what Chat Completions servers stream
+    // back, and fall back to the summary.
+    let text = match content {
+        Some(content) if !content.is_empty() => content
+            .iter()
+            .map(|item| match item {
+                ReasoningItemContent::ReasoningText { text }
+                | ReasoningItemContent::Text { text } => text.as_str(),
+            })
+            .collect::<String>(),
+        _ => summary
+            .iter()
+            .map(|item| match item {
+                ReasoningItemReasoningSummary::SummaryText { text } => text.as_str(),
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+    };
+    (!text.is_empty()).then_some(text)
+}
+
+/// Tool messages are text-only, so images in structured output are dropped.
+fn tool_output_text(output: &FunctionCallOutputPayload) -> String {
+    output.body.to_text().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::ToolName;
+    use codex_protocol::models::FunctionCallOutputContentItem;
+    use codex_protocol::models::LocalShellAction;
+    use codex_protocol::models::LocalShellExecAction;
+    use codex_protocol::models::LocalShellStatus;
+    use codex_protocol::openai_models::ReasoningEffort;
+    use pretty_assertions::assert_eq;
+    use std::collections::HashMap;
+
+    fn request(input: Vec<ResponseItem>, tools: Vec<Value>) -> ChatCompletionsApiRequest {
+        ChatCompletionsApiRequest {
+            model: "local-model".to_string(),
+            instructions: "be helpful".to_string(),
+            input,
+            tools,
+            tool_choice: "auto".to_string(),
+            parallel_tool_calls: true,
+            reasoning_effort: Some(ReasoningEffort::High),
+            namespaced_tools: HashMap::from([(
+                "mcp__docs__search".to_string(),
+                ToolName::namespaced("mcp__docs__", "search"),
+            )]),
+            freeform_tools: HashSet::new(),
+        }
+    }
+
+    #[test]
+    fn builds_messages_from_history() {
+        let input = vec![
+            ResponseItem::Message {
+                id: None,
+                role: "developer".to_string(),
+                content: vec![ContentItem::InputText {
+                    text: "sandbox notes".to_string(),
+                }],
+                end_turn: None,
+                phase: None,
+            },
+            ResponseItem::Message {
+                id: None,
+                role: "user".to_string(),
+                content: vec![
+                    ContentItem::InputText {
+                        text: "what is this?".to_string(),
+                    },
+                    ContentItem::InputImage {
+                        image_url: "data:image/png;base64,AAAA".to_string(),
+                        detail: Some(ImageDetail::Original),
+                    },
+                ],
+                end_turn: None,
+                phase: None,
+            },
+            ResponseItem::Reasoning {
+                id: "rs_1".to_string(),
+                summary: Vec::new(),
+                content: Some(vec![ReasoningItemContent::ReasoningText {
+                    text: "look it up".to_string(),
+                }]),
+                encrypted_content: None,
+            },
+            ResponseItem::FunctionCall {
+                id: None,
+                name: "shell".to_string(),
+                namespace: None,
+                arguments: "{\"command\":[\"ls\"]}".to_string(),
+                call_id: "call_1".to_string(),
+            },
+            ResponseItem::FunctionCall {
+                id: None,
+                name: "search".to_string(),
+                namespace: Some("mcp__docs__".to_string()),
+                arguments: "{}".to_string(),
+                call_id: "call_2".to_string(),
+            },
+            ResponseItem::FunctionCallOutput {
+                call_id: "call_1".to_string(),
+                output: FunctionCallOutputPayload::from_text("a.txt".to_string()),
+            },
+            ResponseItem::FunctionCallOutput {
+                call_id: "call_2".to_string(),
+                output: FunctionCallOutputPayload::from_content_items(vec![
+                    FunctionCallOutputContentItem::InputText {
+                        text: "found".to_string(),
+                    },
+                ]),
+            },
+            ResponseItem::Message {
+                id: None,
+                role: "assistant".to_string(),
+                content: vec![ContentItem::OutputText {
+                    text: "It is a.txt".to_string(),
+                }],
+                end_turn: None,
+                phase: None,
+            },
+        ];
+        let tools = vec![json!({
+            "type": "function",
+            "function": {"name": "shell", "parameters": {"type": "object"}},
+        })];
+
+        assert_eq!(
+            build_chat_completions_body(&request(input, tools.clone())),
+            json!({
+                "model": "local-model",
+                "messages": [
+                    {"role": "system", "content": "be helpful"},
+                    {"role": "system", "content": "sandbox notes"},
+                    {
+                        "role": "user",
+                        "content": [
+                            {"type": "text", "text": "what is this?"},
+                            {
+                                "type": "image_url",
+                                "image_url": {
+                                    "url": "data:image/png;base64,AAAA",
+                                    "detail": "high",
+                                },
+                            },
+                        ],
+                    },
+                    {
+                        "role": "assistant",
+                        "content": null,
+                        "reasoning_content": "look it up",
+                        "tool_calls": [
+                            {
+                                "id": "call_1",
+                                "type": "function",
+                                "function": {
+                                    "name": "shell",
+                                    "arguments": "{\"command\":[\"ls\"]}",
+                                },
+                            },
+                            {
+                                "id": "call_2",
+                                "type": "function",
+                                "function": {"name": "mcp__docs__search", "arguments": "{}"},
+                            },
+                        ],
+                    },
+                    {"role": "tool", "tool_call_id": "call_1", "content": "a.txt"},
+                    {"role": "tool", "tool_call_id": "call_2", "content": "found"},
+                    {"role": "assistant", "content": "It is a.txt"},
+                ],
+                "stream": true,
+                "stream_options": {"include_usage": true},
+                "tools": tools,
+                "tool_choice": "auto",
+                "parallel_tool_calls": true,
+                "reasoning_effort": "high",
+            })
+        );
+    }
+
+    #[test]
+    fn omits_tool_fields_without_tools() {
+        let mut request = request(Vec::new(), Vec::new());
+        request.instructions.clear();
+        request.reasoning_effort = None;
+
+        assert_eq!(
+            build_chat_completions_body(&request),
+            json!({
+                "model": "local-model",
+                "messages": [],
+                "stream": true,
+                "stream_options": {"include_usage": true},
+            })
+        );
+    }
+
+    #[test]
+    fn drops_outputs_of_dropped_tool_calls() {
+        let mut request = request(
+            vec![
+                ResponseItem::LocalShellCall {
+                    id: None,
+                    call_id: Some("call_shell".to_string()),
+                    status: LocalShellStatus::Completed,
+                    action: LocalShellAction::Exec(LocalShellExecAction {
+                        command: vec!["ls".to_string()],
+                        timeout_ms: None,
+                        working_directory: None,
+                        env: None,
+                        user: None,
+                    }),
+                },
+                ResponseItem::FunctionCallOutput {
+                    call_id: "call_shell".to_string(),
+                    output: FunctionCallOutputPayload::from_text("a.txt".to_string()),
+                },
+                ResponseItem::CustomToolCallOutput {
+                    call_id: "call_unknown".to_string(),
+                    name: None,
+                    output: FunctionCallOutputPayload::from_text("patched".to_string()),
+                },
+            ],
+            Vec::new(),
+        );
+        request.instructions.clear();
+        request.reasoning_effort = None;
+
+        assert_eq!(
+            build_chat_completions_body(&request),
+            json!({
+                "model": "local-model",
+                "messages": [],
+                "stream": true,
+                "stream_options": {"include_usage": true},
+            })
+        );
+    }
+}
@@ -0,0 +1,470 @@
+use crate::common::MessagesApiRequest;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::FunctionCallOutputBody;
+use codex_protocol::models::FunctionCallOutputContentItem;
+use codex_protocol::models::FunctionCallOutputPayload;
+use codex_protocol::models::ReasoningItemContent;
+use codex_protocol::models::ResponseItem;
+use serde_json::Map;
+use serde_json::Value;
+use serde_json::json;
+
+/// Builds a streaming Messages request body from Codex history.
+///
+/// The Messages API only has `user` and `assistant` turns, so developer
+/// messages are sent as user text and consecutive items with the same role
+/// are merged into one message. Reasoning items are replayed as `thinking`
+/// blocks only when they carry the signature the server issued for them;
+/// items with no Messages equivalent are dropped.
+pub(crate) fn build_messages_body(request: &MessagesApiRequest) -> Value {
+    let mut messages: Vec<(&'static str, Vec<Value>)> = Vec::new();
+    for item in &request.input {
+        match item {
+            ResponseItem::Message { role, content, .. } => {
+                let role = if role == "assistant" {
+                    "assistant"
+                } else {
+                    "user"
+                };
+                for block in content.iter().filter_map(content_block) {
+                    push_block(&mut messages, role, block);
+                }
+            }
+            ResponseItem::Reasoning {
+                content,
+                encrypted_content: Some(signature),
+                ..
+            } => {
+                let thinking = content
+                    .iter()
+                    .flatten()
+                    .map(|item| match item {
+                        ReasoningItemContent::ReasoningText { text }
+                        | ReasoningItemContent::Text { text } => text.as_str(),
+                    })
+                    .collect::<String>();
+                let block = if content.is_none() {
+                    json!({"type": "redacted_thinking", "data": signature})
+                } else {
+                    json!({"type": "thinking", "thinking": thinking, "signature": signature})
+                };
+                push_block(&mut messages, "assistant", block);
+            }
+            ResponseItem::FunctionCall {
+                name,
+                namespace,
+                arguments,
+                call_id,
+                ..
+            } => {
+                let name = match namespace {
+                    Some(namespace) => format!("{namespace}{name}"),
+                    None => name.clone(),
+                };
+                // Tool inputs must be objects; arguments the model produced
+                // that do not parse are replaced rather than failing the turn.
+                let input = serde_json::from_str::<Value>(arguments)
+                    .ok()
+                    .filter(Value::is_object)
+                    .unwrap_or_else(|| json!({}));
+                push_block(
+                    &mut messages,
+                    "assistant",
+                    json!({"type": "tool_use", "id": call_id, "name": name, "input": input}),
+                );
+            }
+            ResponseItem::CustomToolCall {
+                call_id,
+                name,
+                input,
+                ..
+            } => {
+                push_block(
+                    &mut messages,
+                    "assistant",
+                    json!({
+                        "type": "tool_use",
+                        "id": call_id,
+                        "name": name,
+                        "input": {"input": input},
+                    }),
+                );
+            }
+            ResponseItem::FunctionCallOutput { call_id, output }
+            | ResponseItem::CustomToolCallOutput {
+                call_id, output, ..
+            } => {
+                push_block(&mut messages, "user", tool_result_block(call_id, output));
+            }
+            ResponseItem::Reasoning {
+                encrypted_content: None,
+                ..
+            }
+            | ResponseItem::LocalShellCall { .. }
+            | ResponseItem::ToolSearchCall { .. }
+            | ResponseItem::ToolSearchOutput { .. }
+            | ResponseItem::WebSearchCall { .. }
+            | ResponseItem::ImageGenerationCall { .. }
+            | ResponseItem::GhostSnapshot { .. }
+            | ResponseItem::Compaction { .. }
+            | ResponseItem::Other => {}
+        }
+    }
+
+    if request.prompt_caching
+        && let Some((_, blocks)) = messages.last_mut()
+        && let Some(Value::Object(block)) = blocks.last_mut()
+        && !matches!(
+            block.get("type").and_then(Value::as_str),
+            Some("thinking" | "redacted_thinking")
+        )
+    {
+        block.insert("cache_control".to_string(), cache_control());
+    }
+    let messages = messages
+        .into_iter()
+        .map(|(role, content)| json!({"role": role, "content": content}))
+        .collect::<Vec<_>>();
+
+    let mut body = json!({
+        "model": request.model,
+        "max_tokens": request.max_tokens,
+        "messages": messages,
+        "stream": true,
+    });
+    if !request.instructions.is_empty() {
+        let mut system = json!({"type": "text", "text": request.instructions});
+        if request.prompt_caching {
+            system["cache_control"] = cache_control();
+        }
+        body["system"] = Value::Array(vec![system]);
+    }
+    if !request.tools.is_empty() {
+        let mut tools = request.tools.clone();
+        if request.prompt_caching
+            && let Some(Value::Object(tool)) = tools.last_mut()
+        {
+            tool.insert("cache_control".to_string(), cache_control());
+        }
+        body["tools"] = Value::Array(tools);
+        body["tool_choice"] = json!({
+            "type": "auto",
+            "disable_parallel_tool_use": !request.parallel_tool_calls,
+        });
+    }
+    if let Some(budget_tokens) = request.thinking_budget_tokens {
+        body["thinking"] = json!({"type": "enabled", "budget_tokens": budget_tokens});
+    }
+    body
+}
+
+/// Appends a content block to the trailing message when it has the same
+/// role, since the Messages API requires alternating turns.
+fn push_block(messages: &mut Vec<(&'static str, Vec<Value>)>, role: &'static str, block: Value) {
+    match messages.last_mut() {
+        Some((last_role, blocks)) if *last_role == role => blocks.push(block),
+        _ => messages.push((role, vec![block])),
+    }
+}
+
+fn cache_control() -> Value {
+    json!({"type": "ephemeral"})
+}
+
+/// Empty text blocks are rejected by the Messages API, so they are skipped.
+fn content_block(item: &ContentItem) -> Option<Value> {
+    match item {
+        ContentItem::InputText { text } | ContentItem::OutputText { text } => {
+            (!text.is_empty()).then(|| json!({"type": "text", "text": text}))
+        }
+        ContentItem::InputImage { image_url, .. } => Some(image_block(image_url)),
+    }
+}
+
+/// Data URLs are sent inline as base64 sources; anything else by URL.
+fn image_block(image_url: &str) -> Value {
+    let source = image_url
+        .strip_prefix("data:")
+        .and_then(|rest| rest.split_once(";base64,"))
+        .map(|(media_type, data)| json!({"type": "base64", "media_type": media_type, "data": data}))
+        .unwrap_or_else(|| json!({"type": "url", "url": image_url}));
+    json!({"type": "image", "source": source})
+}
+
+fn tool_result_block(call_id: &str, output: &FunctionCallOutputPayload) -> Value {
+    let content = match &output.body {
+        FunctionCallOutputBody::Text(text) => Value::String(text.clone()),
+        FunctionCallOutputBody::ContentItems(items) => Value::Array(
+            items
+                .iter()
+                .filter_map(|item| match item {
+                    FunctionCallOutputContentItem::InputText { text } => {
+                        (!text.is_empty()).then(|| json!({"type": "text", "text": text}))
+                    }
+                    FunctionCallOutputContentItem::InputImage { image_url, .. } => {
+                        Some(image_block(image_url))
+                    }
+                })
+                .collect(),
+        ),
+    };
+    let mut block = Map::new();
+    block.insert("type".to_string(), Value::String("tool_result".to_string()));
+    block.insert(
+        "tool_use_id".to_string(),
+        Value::String(call_id.to_string()),
+    );
+    block.insert("content".to_string(), content);
+    if output.success == Some(false) {
+        block.insert("is_error".to_string(), Value::Bool(true));
+    }
+    Value::Object(block)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::models::ImageDetail;
+    use pretty_assertions::assert_eq;
+    use std::collections::HashMap;
+    use std::collections::HashSet;
+
+    fn request(input: Vec<ResponseItem>, tools: Vec<Value>) -> MessagesApiRequest {
+        MessagesApiRequest {
+            model: "claude-test".to_string(),
+            instructions: "be helpful".to_string(),
+            input,
+            tools,
+            parallel_tool_calls: true,
+            max_tokens: 8192,
+            thinking_budget_tokens: Some(2048),
+            prompt_caching: true,
+            namespaced_tools: HashMap::new(),
+            freeform_tools: HashSet::from(["apply_patch".to_string()]),
+        }
+    }
+
+    #[test]
+    fn builds_messages_from_history() {
+        let input = vec![
+            ResponseItem::Message {
+                id: None,
+                role: "developer".to_string(),
+                content: vec![ContentItem::InputText {
+                    text: "sandbox notes".to_string(),
+                }],
+                end_turn: None,
+                phase: None,
+            },
+            ResponseItem::Message {
+                id: None,
+                role: "user".to_string(),
+                content: vec![
+                    ContentItem::InputText {
+                        text: "what is this?".to_string(),
+                    },
+                    ContentItem::InputImage {
+                        image_url: "data:image/png;base64,AAAA".to_string(),
+                        detail: Some(ImageDetail::High),
+                    },
+                ],
+                end_turn: None,
+                phase: None,
+            },
+            ResponseItem::Reasoning {
+                id: "rs_1".to_string(),
+                summary: Vec::new(),
+                content: Some(vec![ReasoningItemContent::ReasoningText {
+                    text: "look it up".to_string(),
+                }]),
+                encrypted_content: Some("sig_1".to_string()),
+            },
+            ResponseItem::Reasoning {
+                id: "rs_2".to_string(),
+                summary: Vec::new(),
+                content: Some(vec![ReasoningItemContent::ReasoningText {
+                    text: "unsigned reasoning from another provider".to_string(),
+                }]),
+                encrypted_content: None,
+            },
+            ResponseItem::FunctionCall {
+                id: None,
+                name: "search".to_string(),
+                namespace: Some("mcp__docs__".to_string()),
+                arguments: "{\"query\":\"a\"}".to_string(),
+                call_id: "toolu_1".to_string(),
+            },
+            ResponseItem::CustomToolCall {
+                id: None,
+                status: None,
+                call_id: "toolu_2".to_string(),
+                name: "apply_patch".to_string(),
+                input: "*** Begin Patch".to_string(),
+            },
+            ResponseItem::FunctionCallOutput {
+                call_id: "toolu_1".to_string(),
+                output: FunctionCallOutputPayload::from_content_items(vec![
+                    FunctionCallOutputContentItem::InputText {
+                        text: "found".to_string(),
+                    },
+                    FunctionCallOutputContentItem::InputImage {
+                        image_url: "https://example.com/a.png".to_string(),
+                        detail: None,
+                    },
+                ]),
+            },
+            ResponseItem::CustomToolCallOutput {
+                call_id: "toolu_2".to_string(),
+                name: None,
+                output: FunctionCallOutputPayload {
+                    body: FunctionCallOutputBody::Text("patch rejected".to_string()),
+                    success: Some(false),
+                },
+            },
+            ResponseItem::Message {
+                id: None,
+                role: "assistant".to_string(),
+                content: vec![ContentItem::OutputText {
+                    text: "Done".to_string(),
+                }],
+                end_turn: None,
+                phase: None,
+            },
+            ResponseItem::Message {
+                id: None,
+                role: "user".to_string(),
+                content: vec![ContentItem::InputText {
+                    text: "thanks".to_string(),
+                }],
+                end_turn: None,
+                phase: None,
+            },
+        ];
+        let tools = vec![
+            json!({"name": "mcp__docs__search", "input_schema": {"type": "object"}}),
+            json!({"name": "apply_patch", "input_schema": {"type": "object"}}),
+        ];
+
+        assert_eq!(
+            build_messages_body(&request(input, tools)),
+            json!({
+                "model": "claude-test",
+                "max_tokens": 8192,
+                "stream": true,
+                "system": [{
+                    "type": "text",
+                    "text": "be helpful",
+                    "cache_control": {"type": "ephemeral"},
+                }],
+                "messages": [
+                    {
+                        "role": "user",
+                        "content": [
+                            {"type": "text", "text": "sandbox notes"},
+                            {"type": "text", "text": "what is this?"},
+                            {
+                                "type": "image",
+                                "source": {
+                                    "type": "base64",
+                                    "media_type": "image/png",
+                                    "data": "AAAA",
+                                },
+                            },
+                        ],
+                    },
+                    {
+                        "role": "assistant",
+                        "content": [
+                            {"type": "thinking", "thinking": "look it up", "signature": "sig_1"},
+                            {
+                                "type": "tool_use",
+                                "id": "toolu_1",
+                                "name": "mcp__docs__search",
+                                "input": {"query": "a"},
+                            },
+                            {
+                                "type": "tool_use",
+                                "id": "toolu_2",
+                                "name": "apply_patch",
+                                "input": {"input": "*** Begin Patch"},
+                            },
+                        ],
+                    },
+                    {
+                        "role": "user",
+                        "content": [
+                            {
+                                "type": "tool_result",
+                                "tool_use_id": "toolu_1",
+                                "content": [
+                                    {"type": "text", "text": "found"},
+                                    {
+                                        "type": "image",
+                                        "source": {
+                                            "type": "url",
+                                            "url": "https://example.com/a.png",
+                                        },
+                                    },
+                                ],
+                            },
+                            {
+                                "type": "tool_result",
+                                "tool_use_id": "toolu_2",
+                                "content": "patch rejected",
+                                "is_error": true,
+                            },
+                        ],
+                    },
+                    {"role": "assistant", "content": [{"type": "text", "text": "Done"}]},
+                    {
+                        "role": "user",
+                        "content": [{
+                            "type": "text",
+                            "text": "thanks",
+                            "cache_control": {"type": "ephemeral"},
+                        }],
+                    },
+                ],
+                "tools": [
+                    {"name": "mcp__docs__search", "input_schema": {"type": "object"}},
+                    {
+                        "name": "apply_patch",
+                        "input_schema": {"type": "object"},
+                        "cache_control": {"type": "ephemeral"},
+                    },
+                ],
+                "tool_choice": {"type": "auto", "disable_parallel_tool_use": false},
+                "thinking": {"type": "enabled", "budget_tokens": 2048},
+            })
+        );
+    }
+
+    #[test]
+    fn omits_cache_control_when_prompt_caching_is_disabled() {
+        let mut request = request(
+            vec![ResponseItem::Message {
+                id: None,
+                role: "user".to_string(),
+                content: vec![ContentItem::InputText {
+                    text: "hi".to_string(),
+                }],
+                end_turn: None,
+                phase: None,
+            }],
+            Vec::new(),
+        );
+        request.prompt_caching = false;
+        request.thinking_budget_tokens = None;
+
+        assert_eq!(
+            build_messages_body(&request),
+            json!({
+                "model": "claude-test",
+                "max_tokens": 8192,
+                "stream": true,
+                "system": [{"type": "text", "text": "be helpful"}],
+                "messages": [{"role": "user", "content": [{"type": "text", "text": "hi"}]}],
+            })
+        );
+    }
+}
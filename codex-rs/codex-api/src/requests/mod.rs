@@ -1,5 +1,9 @@
+pub(crate) mod chat;
 pub(crate) mod headers;
+pub(crate) mod messages;
 pub(crate) mod responses;
 
+pub(crate) use chat::build_chat_completions_body;
+pub(crate) use messages::build_messages_body;
 pub use responses::Compression;
 pub(crate) use responses::attach_item_ids;
@@ -0,0 +1,768 @@
+//! Translates Chat Completions stream chunks into [`ResponseEvent`]s.
+//!
+//! Chat Completions has no output items, so text and reasoning deltas are
+//! wrapped in synthetic assistant message and reasoning items, and tool call
+//! fragments are accumulated by `index` and emitted as complete function or
+//! custom tool calls once the choice finishes.
+
+use crate::common::ResponseEvent;
+use crate::common::ResponseStream;
+use crate::error::ApiError;
+use crate::rate_limits::parse_all_rate_limits;
+use crate::sse::ToolCallNames;
+use crate::telemetry::SseTelemetry;
+use codex_client::ByteStream;
+use codex_client::StreamResponse;
+use codex_client::TransportError;
+use codex_protocol::ToolName;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ReasoningItemContent;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::TokenUsage;
+use eventsource_stream::Eventsource;
+use futures::StreamExt;
+use futures::TryStreamExt;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::io::BufRead;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+use tokio::time::timeout;
+use tokio_util::io::ReaderStream;
+use tracing::debug;
+use tracing::trace;
+
+const DONE_SENTINEL: &str = "[DONE]";
+
+/// Streams Chat Completions SSE chunks from an on-disk fixture for tests.
+pub fn stream_chat_completions_from_fixture(
+    path: impl AsRef<Path>,
+    idle_timeout: Duration,
+    namespaced_tools: HashMap<String, ToolName>,
+    freeform_tools: HashSet<String>,
+) -> Result<ResponseStream, ApiError> {
+    let file =
+        std::fs::File::open(path.as_ref()).map_err(|err| ApiError::Stream(err.to_string()))?;
+    let mut content = String::new();
+    for line in std::io::BufReader::new(file).lines() {
+        let line = line.map_err(|err| ApiError::Stream(err.to_string()))?;
+        content.push_str(&line);
+        content.push_str("\n\n");
+    }
+
+    let reader = std::io::Cursor::new(content);
+    let stream = ReaderStream::new(reader).map_err(|err| TransportError::Network(err.to_string()));
+    let (tx_event, rx_event) = mpsc::channel::<Result<ResponseEvent, ApiError>>(1600);
+    tokio::spawn(process_chat_sse(
+        Box::pin(stream),
+        tx_event,
+        idle_timeout,
+        /*telemetry*/ None,
+        ToolCallNames {
+            namespaced_tools,
+            freeform_tools,
+        },
+    ));
+    Ok(ResponseStream { rx_event })
+}
+
+pub(crate) fn spawn_chat_completions_stream(
+    stream_response: StreamResponse,
+    idle_timeout: Duration,
+    telemetry: Option<Arc<dyn SseTelemetry>>,
+    tool_names: ToolCallNames,
+) -> ResponseStream {
+    let rate_limit_snapshots = parse_all_rate_limits(&stream_response.headers);
+    let (tx_event, rx_event) = mpsc::channel::<Result<ResponseEvent, ApiError>>(1600);
+    tokio::spawn(async move {
+        for snapshot in rate_limit_snapshots {
+            let _ = tx_event.send(Ok(ResponseEvent::RateLimits(snapshot))).await;
+        }
+        process_chat_sse(
+            stream_response.bytes,
+            tx_event,
+            idle_timeout,
+            telemetry,
+            tool_names,
+        )
+        .await;
+    });
+
+    ResponseStream { rx_event }
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionChunk {
+    #[serde(default)]
+    id: Option<String>,
+    #[serde(default)]
+    choices: Vec<ChatCompletionChoice>,
+    #[serde(default)]
+    usage: Option<ChatCompletionUsage>,
+    #[serde(default)]
+    error: Option<ChatCompletionError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionChoice {
+    #[serde(default)]
+    delta: Option<ChatCompletionDelta>,
+    #[serde(default)]
+    finish_reason: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ChatCompletionDelta {
+    #[serde(default)]
+    content: Option<String>,
+    /// Raw reasoning as streamed by DeepSeek-style servers (vLLM, SGLang).
+    #[serde(default)]
+    reasoning_content: Option<String>,
+    /// Raw reasoning as streamed by OpenRouter- and Ollama-style servers.
+    #[serde(default)]
+    reasoning: Option<String>,
+    #[serde(default)]
+    tool_calls: Vec<ChatCompletionToolCallDelta>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionToolCallDelta {
+    #[serde(default)]
+    index: u64,
+    #[serde(default)]
+    id: Option<String>,
+    #[serde(default)]
+    function: Option<ChatCompletionFunctionDelta>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionFunctionDelta {
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    arguments: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionUsage {
+    #[serde(default)]
+    prompt_tokens: i64,
+    #[serde(default)]
+    completion_tokens: i64,
+    #[serde(default)]
+    total_tokens: i64,
+    #[serde(default)]
+    prompt_tokens_details: Option<ChatCompletionPromptTokensDetails>,
+    #[serde(default)]
+    completion_tokens_details: Option<ChatCompletionCompletionTokensDetails>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionPromptTokensDetails {
+    #[serde(default)]
+    cached_tokens: i64,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionCompletionTokensDetails {
+    #[serde(default)]
+    reasoning_tokens: i64,
+}
+
+impl From<ChatCompletionUsage> for TokenUsage {
+    fn from(val: ChatCompletionUsage) -> Self {
+        TokenUsage {
+            input_tokens: val.prompt_tokens,
+            cached_input_tokens: val
+                .prompt_tokens_details
+                .map(|d| d.cached_tokens)
+                .unwrap_or(0),
+            output_tokens: val.completion_tokens,
+            reasoning_output_tokens: val
+                .completion_tokens_details
+                .map(|d| d.reasoning_tokens)
+                .unwrap_or(0),
+            total_tokens: val.total_tokens,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionError {
+    #[serde(default)]
+    message: Option<String>,
+}
+
+#[derive(Debug, Default)]
+struct PendingToolCall {
+    call_id: String,
+    name: String,
+    arguments: String,
+}
+
+/// The synthetic item that text or reasoning deltas are currently streamed into.
+enum OpenItem {
+    Message { id: String, text: String },
+    Reasoning { id: String, text: String },
+}
+
+impl OpenItem {
+    /// The item announced by `OutputItemAdded` before any deltas arrive.
+    fn added_item(&self) -> ResponseItem {
+        match self {
+            OpenItem::Message { id, .. } => ResponseItem::Message {
+                id: Some(id.clone()),
+                role: "assistant".to_string(),
+                content: Vec::new(),
+                end_turn: None,
+                phase: None,
+            },
+            OpenItem::Reasoning { id, .. } => ResponseItem::Reasoning {
+                id: id.clone(),
+                summary: Vec::new(),
+                content: None,
+                encrypted_content: None,
+            },
+        }
+    }
+
+    fn into_done_item(self) -> ResponseItem {
+        match self {
+            OpenItem::Message { id, text } => ResponseItem::Message {
+                id: Some(id),
+                role: "assistant".to_string(),
+                content: vec![ContentItem::OutputText { text }],
+                end_turn: None,
+                phase: None,
+            },
+            OpenItem::Reasoning { id, text } => ResponseItem::Reasoning {
+                id,
+                summary: Vec::new(),
+                content: Some(vec![ReasoningItemContent::ReasoningText { text }]),
+                encrypted_content: None,
+            },
+        }
+    }
+}
+
+#[derive(Default)]
+struct ChatStreamState {
+    response_id: Option<String>,
+    created: bool,
+    open_item: Option<OpenItem>,
+    item_count: usize,
+    tool_calls: BTreeMap<u64, PendingToolCall>,
+    token_usage: Option<TokenUsage>,
+    finished: bool,
+}
+
+impl ChatStreamState {
+    fn next_item_id(&mut self) -> String {
+        self.item_count += 1;
+        let response_id = self.response_id.as_deref().unwrap_or("chatcmpl");
+        format!("{response_id}-{}", self.item_count)
+    }
+
+    fn close_open_item(&mut self, events: &mut Vec<ResponseEvent>) {
+        if let Some(item) = self.open_item.take() {
+            events.push(ResponseEvent::OutputItemDone(item.into_done_item()));
+        }
+    }
+
+    fn push_reasoning_delta(&mut self, delta: String, events: &mut Vec<ResponseEvent>) {
+        if !matches!(self.open_item, Some(OpenItem::Reasoning { .. })) {
+            self.close_open_item(events);
+            let item = OpenItem::Reasoning {
+                id: self.next_item_id(),
+                text: String::new(),
+            };
+            events.push(ResponseEvent::OutputItemAdded(item.added_item()));
+            self.open_item = Some(item);
+        }
+        if let Some(OpenItem::Reasoning { text, .. }) = &mut self.open_item {
+            text.push_str(&delta);
+        }
+        events.push(ResponseEvent::ReasoningContentDelta {
+            delta,
+            content_index: 0,
+        });
+    }
+
+    fn push_text_delta(&mut self, delta: String, events: &mut Vec<ResponseEvent>) {
+        if !matches!(self.open_item, Some(OpenItem::Message { .. })) {
+            self.close_open_item(events);
+            let item = OpenItem::Message {
+                id: self.next_item_id(),
+                text: String::new(),
+            };
+            events.push(ResponseEvent::OutputItemAdded(item.added_item()));
+            self.open_item = Some(item);
+        }
+        if let Some(OpenItem::Message { text, .. }) = &mut self.open_item {
+            text.push_str(&delta);
+        }
+        events.push(ResponseEvent::OutputTextDelta(delta));
+    }
+
+    fn push_tool_call_delta(
+        &mut self,
+        tool_call: ChatCompletionToolCallDelta,
+        events: &mut Vec<ResponseEvent>,
+    ) {
+        self.close_open_item(events);
+        let pending = self.tool_calls.entry(tool_call.index).or_default();
+        if let Some(id) = tool_call.id
+            && !id.is_empty()
+        {
+            pending.call_id = id;
+        }
+        if let Some(function) = tool_call.function {
+            if let Some(name) = function.name {
+                pending.name.push_str(&name);
+            }
+            if let Some(arguments) = function.arguments {
+                pending.arguments.push_str(&arguments);
+            }
+        }
+    }
+
+    fn handle_chunk(
+        &mut self,
+        chunk: ChatCompletionChunk,
+        tool_names: &ToolCallNames,
+    ) -> Result<Vec<ResponseEvent>, ApiError> {
+        if let Some(error) = chunk.error {
+            return Err(ApiError::Stream(error.message.unwrap_or_else(|| {
+                "chat completions stream returned an error".to_string()
+            })));
+        }
+
+        let mut events = Vec::new();
+        if self.response_id.is_none() {
+            self.response_id = chunk.id;
+        }
+        // `ServerModel` is not emitted: core treats a mismatch as a safety
+        // reroute, and Chat Completions servers commonly echo an alias or a
+        // fully qualified name rather than the requested model.
+        if !self.created {
+            self.created = true;
+            events.push(ResponseEvent::Created);
+        }
+        if let Some(usage) = chunk.usage {
+            self.token_usage = Some(usage.into());
+        }
+
+        // Codex never requests `n > 1`, so only the first choice is used.
+        if let Some(choice) = chunk.choices.into_iter().next() {
+            let delta = choice.delta.unwrap_or_default();
+            if let Some(reasoning) = delta.reasoning_content.or(delta.reasoning)
+                && !reasoning.is_empty()
+            {
+                self.push_reasoning_delta(reasoning, &mut events);
+            }
+            if let Some(content) = delta.content
+                && !content.is_empty()
+            {
+                self.push_text_delta(content, &mut events);
+            }
+            for tool_call in delta.tool_calls {
+                self.push_tool_call_delta(tool_call, &mut events);
+            }
+            if choice.finish_reason.is_some() {
+                self.finish(tool_names, &mut events);
+            }
+        }
+        Ok(events)
+    }
+
+    fn finish(&mut self, tool_names: &ToolCallNames, events: &mut Vec<ResponseEvent>) {
+        if self.finished {
+            return;
+        }
+        self.finished = true;
+        self.close_open_item(events);
+        for tool_call in std::mem::take(&mut self.tool_calls).into_values() {
+            let PendingToolCall {
+                call_id,
+                name,
+                arguments,
+            } = tool_call;
+            // Tool outputs are matched to calls by id across the whole
+            // conversation, so a missing id is derived from the response id.
+            let call_id = if call_id.is_empty() {
+                format!("call_{}", self.next_item_id())
+            } else {
+                call_id
+            };
+            let arguments = if arguments.is_empty() {
+                "{}".to_string()
+            } else {
+                arguments
+            };
+            events.push(ResponseEvent::OutputItemDone(
+                tool_names.tool_call_item(call_id, name, arguments),
+            ));
+        }
+    }
+
+    fn completed(&mut self) -> ResponseEvent {
+        ResponseEvent::Completed {
+            response_id: self.response_id.take().unwrap_or_default(),
+            token_usage: self.token_usage.take(),
+        }
+    }
+}
+
+pub(crate) async fn process_chat_sse(
+    stream: ByteStream,
+    tx_event: mpsc::Sender<Result<ResponseEvent, ApiError>>,
+    idle_timeout: Duration,
+    telemetry: Option<Arc<dyn SseTelemetry>>,
+    tool_names: ToolCallNames,
+) {
+    let mut stream = stream.eventsource();
+    let mut state = ChatStreamState::default();
+
+    loop {
+        let start = Instant::now();
+        let response = timeout(idle_timeout, stream.next()).await;
+        if let Some(t) = telemetry.as_ref() {
+            t.on_sse_poll(&response, start.elapsed());
+        }
+        let sse = match response {
+            Ok(Some(Ok(sse))) => sse,
+            Ok(Some(Err(e))) => {
+                debug!("SSE Error: {e:#}");
+                let _ = tx_event.send(Err(ApiError::Stream(e.to_string()))).await;
+                return;
+            }
+            Ok(None) => {
+                // Some servers close the stream without `[DONE]`; a finished
+                // choice is still a complete response.
+                let event = if state.finished {
+                    Ok(state.completed())
+                } else {
+                    Err(ApiError::Stream(
+                        "stream closed before chat completion finished".into(),
+                    ))
+                };
+                let _ = tx_event.send(event).await;
+                return;
+            }
+            Err(_) => {
+                let _ = tx_event
+                    .send(Err(ApiError::Stream("idle timeout waiting for SSE".into())))
+                    .await;
+                return;
+            }
+        };
+
+        trace!("SSE event: {}", &sse.data);
+
+        if sse.data.trim() == DONE_SENTINEL {
+            let mut events = Vec::new();
+            state.finish(&tool_names, &mut events);
+            events.push(state.completed());
+            for event in events {
+                if tx_event.send(Ok(event)).await.is_err() {
+                    return;
+                }
+            }
+            return;
+        }
+
+        let chunk: ChatCompletionChunk = match serde_json::from_str(&sse.data) {
+            Ok(chunk) => chunk,
+            Err(e) => {
+                debug!("Failed to parse SSE event: {e}, data: {}", &sse.data);
+                continue;
+            }
+        };
+
+        match state.handle_chunk(chunk, &tool_names) {
+            Ok(events) => {
+                for event in events {
+                    if tx_event.send(Ok(event)).await.is_err() {
+                        return;
+                    }
+                }
+            }
+            Err(error) => {
+                let _ = tx_event.send(Err(error)).await;
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::assert_matches;
+    use pretty_assertions::assert_eq;
+    use serde_json::Value;
+    use serde_json::json;
+
+    async fn run_chat_sse(
+        chunks: Vec<Value>,
+        tool_names: ToolCallNames,
+    ) -> Vec<Result<ResponseEvent, ApiError>> {
+        let mut body = String::new();
+        for chunk in chunks {
+            body.push_str(&format!("data: {chunk}\n\n"));
+        }
+        body.push_str("data: [DONE]\n\n");
+
+        let (tx, mut rx) = mpsc::channel::<Result<ResponseEvent, ApiError>>(16);
+        let stream = ReaderStream::new(std::io::Cursor::new(body))
+            .map_err(|err| TransportError::Network(err.to_string()));
+        tokio::spawn(process_chat_sse(
+            Box::pin(stream),
+            tx,
+            Duration::from_millis(1000),
+            /*telemetry*/ None,
+            tool_names,
+        ));
+
+        let mut events = Vec::new();
+        while let Some(event) = rx.recv().await {
+            events.push(event);
+        }
+        events
+    }
+
+    fn chunk(delta: Value, finish_reason: Option<&str>) -> Value {
+        json!({
+            "id": "chatcmpl-1",
+            "model": "local-model",
+            "choices": [{"index": 0, "delta": delta, "finish_reason": finish_reason}],
+        })
+    }
+
+    #[tokio::test]
+    async fn streams_reasoning_then_text() {
+        let events = run_chat_sse(
+            vec![
+                chunk(
+                    json!({"role": "assistant", "reasoning_content": "think"}),
+                    None,
+                ),
+                chunk(json!({"reasoning_content": "ing"}), None),
+                chunk(json!({"content": "Hel"}), None),
+                chunk(json!({"content": "lo"}), Some("stop")),
+                json!({
+                    "id": "chatcmpl-1",
+                    "choices": [],
+                    "usage": {
+                        "prompt_tokens": 10,
+                        "completion_tokens": 5,
+                        "total_tokens": 15,
+                        "prompt_tokens_details": {"cached_tokens": 4},
+                        "completion_tokens_details": {"reasoning_tokens": 2},
+                    },
+                }),
+            ],
+            ToolCallNames::default(),
+        )
+        .await;
+        let events = events
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()
+            .expect("stream should succeed");
+
+        assert_eq!(events.len(), 10);
+        assert_matches!(events[0], ResponseEvent::Created);
+        assert_matches!(
+            &events[1],
+            ResponseEvent::OutputItemAdded(ResponseItem::Reasoning { id, .. })
+                if id == "chatcmpl-1-1"
+        );
+        assert_matches!(
+            &events[2],
+            ResponseEvent::ReasoningContentDelta { delta, content_index: 0 } if delta == "think"
+        );
+        assert_matches!(
+            &events[3],
+            ResponseEvent::ReasoningContentDelta { delta, content_index: 0 } if delta == "ing"
+        );
+        assert_matches!(
+            &events[4],
+            ResponseEvent::OutputItemDone(ResponseItem::Reasoning { content: Some(content), .. })
+                if content == &vec![ReasoningItemContent::ReasoningText {
+                    text: "thinking".to_string(),
+                }]
+        );
+        assert_matches!(
+            &events[5],
+            ResponseEvent::OutputItemAdded(ResponseItem::Message { id: Some(id), .. })
+                if id == "chatcmpl-1-2"
+        );
+        assert_matches!(&events[6], ResponseEvent::OutputTextDelta(delta) if delta == "Hel");
+        assert_matches!(&events[7], ResponseEvent::OutputTextDelta(delta) if delta == "lo");
+        assert_matches!(
+            &events[8],
+            ResponseEvent::OutputItemDone(ResponseItem::Message { id: Some(id), content, .. })
+                if id == "chatcmpl-1-2"
+                    && content == &vec![ContentItem::OutputText { text: "Hello".to_string() }]
+        );
+        assert_matches!(
+            &events[9],
+            ResponseEvent::Completed { response_id, token_usage: Some(token_usage) }
+                if response_id == "chatcmpl-1"
+                    && token_usage == &TokenUsage {
+                        input_tokens: 10,
+                        cached_input_tokens: 4,
+                        output_tokens: 5,
+                        reasoning_output_tokens: 2,
+                        total_tokens: 15,
+                    }
+        );
+    }
+
+    #[tokio::test]
+    async fn aggregates_tool_calls_and_restores_namespaces_and_freeform_tools() {
+        let events = run_chat_sse(
+            vec![
+                chunk(
+                    json!({"tool_calls": [{
+                        "index": 0,
+                        "id": "call_a",
+                        "type": "function",
+                        "function": {"name": "shell", "arguments": ""},
+                    }]}),
+                    None,
+                ),
+                chunk(
+                    json!({"tool_calls": [{"index": 0, "function": {"arguments": "{\"command\":"}}]}),
+                    None,
+                ),
+                chunk(
+                    json!({"tool_calls": [{
+                        "index": 1,
+                        "id": "call_b",
+                        "type": "function",
+                        "function": {"name": "mcp__docs__search", "arguments": "{}"},
+                    }]}),
+                    None,
+                ),
+                chunk(
+                    json!({"tool_calls": [{"index": 0, "function": {"arguments": "[\"ls\"]}"}}]}),
+                    None,
+                ),
+                chunk(
+                    json!({"tool_calls": [{
+                        "index": 2,
+                        "id": "call_c",
+                        "type": "function",
+                        "function": {
+                            "name": "apply_patch",
+                            "arguments": "{\"input\":\"*** Begin Patch\"}",
+                        },
+                    }]}),
+                    None,
+                ),
+                chunk(json!({}), Some("tool_calls")),
+            ],
+            ToolCallNames {
+                namespaced_tools: HashMap::from([(
+                    "mcp__docs__search".to_string(),
+                    ToolName::namespaced("mcp__docs__", "search"),
+                )]),
+                freeform_tools: HashSet::from(["apply_patch".to_string()]),
+            },
+        )
+        .await;
+        let events = events
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()
+            .expect("stream should succeed");
+
+        let items = events
+            .into_iter()
+            .filter_map(|event| match event {
+                ResponseEvent::OutputItemDone(item) => Some(item),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            items,
+            vec![
+                ResponseItem::FunctionCall {
+                    id: None,
+                    name: "shell".to_string(),
+                    namespace: None,
+                    arguments: "{\"command\":[\"ls\"]}".to_string(),
+                    call_id: "call_a".to_string(),
+                },
+                ResponseItem::FunctionCall {
+                    id: None,
+                    name: "search".to_string(),
+                    namespace: Some("mcp__docs__".to_string()),
+                    arguments: "{}".to_string(),
+                    call_id: "call_b".to_string(),
+                },
+                ResponseItem::CustomToolCall {
+                    id: None,
+                    status: None,
+                    call_id: "call_c".to_string(),
+                    name: "apply_patch".to_string(),
+                    input: "*** Begin Patch".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn derives_missing_tool_call_ids_from_the_response_id() {
+        let events = run_chat_sse(
+            vec![
+                chunk(json!({"content": "checking"}), None),
+                chunk(
+                    json!({"tool_calls": [{
+                        "index": 0,
+                        "type": "function",
+                        "function": {"name": "shell", "arguments": "{}"},
+                    }]}),
+                    Some("tool_calls"),
+                ),
+            ],
+            ToolCallNames::default(),
+        )
+        .await;
+        let events = events
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()
+            .expect("stream should succeed");
+
+        let call_ids = events
+            .into_iter()
+            .filter_map(|event| match event {
+                ResponseEvent::OutputItemDone(ResponseItem::FunctionCall { call_id, .. }) => {
+                    Some(call_id)
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(call_ids, vec!["call_chatcmpl-1-2".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn reports_stream_errors() {
+        let events = run_chat_sse(
+            vec![json!({"error": {"message": "context length exceeded"}})],
+            ToolCallNames::default(),
+        )
+        .await;
+
+        assert_eq!(events.len(), 1);
+        assert_matches!(
+            &events[0],
+            Err(ApiError::Stream(message)) if message == "context length exceeded"
+        );
+    }
+}
@@ -0,0 +1,799 @@
+//! Translates Messages API stream events into [`ResponseEvent`]s.
+//!
+//! Each content block becomes one output item: `text` blocks become assistant
+//! messages, `thinking` blocks become reasoning items whose signature is kept
+//! in `encrypted_content` so they can be replayed, and `tool_use` blocks become
+//! function or custom tool calls once their streamed JSON input is complete.
+
+use crate::common::ResponseEvent;
+use crate::common::ResponseStream;
+use crate::error::ApiError;
+use crate::rate_limits::parse_all_rate_limits;
+use crate::sse::ToolCallNames;
+use crate::telemetry::SseTelemetry;
+use codex_client::ByteStream;
+use codex_client::StreamResponse;
+use codex_client::TransportError;
+use codex_protocol::ToolName;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ReasoningItemContent;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::TokenUsage;
+use eventsource_stream::Eventsource;
+use futures::StreamExt;
+use futures::TryStreamExt;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::io::BufRead;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+use tokio::time::timeout;
+use tokio_util::io::ReaderStream;
+use tracing::debug;
+use tracing::trace;
+
+/// Streams Messages API SSE events from an on-disk fixture for tests.
+pub fn stream_messages_from_fixture(
+    path: impl AsRef<Path>,
+    idle_timeout: Duration,
+    namespaced_tools: HashMap<String, ToolName>,
+    freeform_tools: HashSet<String>,
+) -> Result<ResponseStream, ApiError> {
+    let file =
+        std::fs::File::open(path.as_ref()).map_err(|err| ApiError::Stream(err.to_string()))?;
+    let mut content = String::new();
+    for line in std::io::BufReader::new(file).lines() {
+        let line = line.map_err(|err| ApiError::Stream(err.to_string()))?;
+        content.push_str(&line);
+        content.push_str("\n\n");
+    }
+
+    let reader = std::io::Cursor::new(content);
+    let stream = ReaderStream::new(reader).map_err(|err| TransportError::Network(err.to_string()));
+    let (tx_event, rx_event) = mpsc::channel::<Result<ResponseEvent, ApiError>>(1600);
+    tokio::spawn(process_messages_sse(
+        Box::pin(stream),
+        tx_event,
+        idle_timeout,
+        /*telemetry*/ None,
+        ToolCallNames {
+            namespaced_tools,
+            freeform_tools,
+        },
+    ));
+    Ok(ResponseStream { rx_event })
+}
+
+pub(crate) fn spawn_messages_stream(
+    stream_response: StreamResponse,
+    idle_timeout: Duration,
+    telemetry: Option<Arc<dyn SseTelemetry>>,
+    tool_names: ToolCallNames,
+) -> ResponseStream {
+    let rate_limit_snapshots = parse_all_rate_limits(&stream_response.headers);
+    let (tx_event, rx_event) = mpsc::channel::<Result<ResponseEvent, ApiError>>(1600);
+    tokio::spawn(async move {
+        for snapshot in rate_limit_snapshots {
+            let _ = tx_event.send(Ok(ResponseEvent::RateLimits(snapshot))).await;
+        }
+        process_messages_sse(
+            stream_response.bytes,
+            tx_event,
+            idle_timeout,
+            telemetry,
+            tool_names,
+        )
+        .await;
+    });
+
+    ResponseStream { rx_event }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum MessagesStreamEvent {
+    MessageStart {
+        message: MessagesStartMessage,
+    },
+    ContentBlockStart {
+        index: u64,
+        content_block: MessagesContentBlock,
+    },
+    ContentBlockDelta {
+        index: u64,
+        delta: MessagesContentDelta,
+    },
+    ContentBlockStop {
+        index: u64,
+    },
+    MessageDelta {
+        #[serde(default)]
+        usage: Option<MessagesUsage>,
+    },
+    MessageStop,
+    Error {
+        error: MessagesError,
+    },
+    /// `ping` and event types added after this adapter was written.
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Deserialize)]
+struct MessagesStartMessage {
+    #[serde(default)]
+    id: String,
+    #[serde(default)]
+    usage: Option<MessagesUsage>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum MessagesContentBlock {
+    Text {
+        #[serde(default)]
+        text: String,
+    },
+    Thinking {
+        #[serde(default)]
+        thinking: String,
+        #[serde(default)]
+        signature: String,
+    },
+    RedactedThinking {
+        #[serde(default)]
+        data: String,
+    },
+    ToolUse {
+        id: String,
+        name: String,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum MessagesContentDelta {
+    TextDelta {
+        text: String,
+    },
+    ThinkingDelta {
+        thinking: String,
+    },
+    SignatureDelta {
+        signature: String,
+    },
+    InputJsonDelta {
+        partial_json: String,
+    },
+    #[serde(other)]
+    Other,
+}
+
+/// Usage as reported by `message_start`, with `output_tokens` updated by
+/// later `message_delta` events.
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+struct MessagesUsage {
+    #[serde(default)]
+    input_tokens: Option<i64>,
+    #[serde(default)]
+    cache_creation_input_tokens: Option<i64>,
+    #[serde(default)]
+    cache_read_input_tokens: Option<i64>,
+    #[serde(default)]
+    output_tokens: Option<i64>,
+}
+
+impl MessagesUsage {
+    fn merge(&mut self, update: MessagesUsage) {
+        self.input_tokens = update.input_tokens.or(self.input_tokens);
+        self.cache_creation_input_tokens = update
+            .cache_creation_input_tokens
+            .or(self.cache_creation_input_tokens);
+        self.cache_read_input_tokens = update
+            .cache_read_input_tokens
+            .or(self.cache_read_input_tokens);
+        self.output_tokens = update.output_tokens.or(self.output_tokens);
+    }
+}
+
+impl From<MessagesUsage> for TokenUsage {
+    fn from(val: MessagesUsage) -> Self {
+        // `input_tokens` excludes cached tokens in the Messages API, while
+        // Codex counts cached tokens as a subset of input tokens.
+        let cached_input_tokens = val.cache_read_input_tokens.unwrap_or(0);
+        let input_tokens = val.input_tokens.unwrap_or(0)
+            + val.cache_creation_input_tokens.unwrap_or(0)
+            + cached_input_tokens;
+        let output_tokens = val.output_tokens.unwrap_or(0);
+        TokenUsage {
+            input_tokens,
+            cached_input_tokens,
+            output_tokens,
+            reasoning_output_tokens: 0,
+            total_tokens: input_tokens + output_tokens,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct MessagesError {
+    #[serde(default)]
+    r#type: Option<String>,
+    #[serde(default)]
+    message: Option<String>,
+}
+
+/// A content block that is still receiving deltas.
+enum OpenBlock {
+    Text {
+        id: String,
+        text: String,
+    },
+    Thinking {
+        id: String,
+        text: String,
+        signature: String,
+    },
+    ToolUse {
+        call_id: String,
+        name: String,
+        input_json: String,
+    },
+}
+
+#[derive(Default)]
+struct MessagesStreamState {
+    response_id: String,
+    created: bool,
+    blocks: BTreeMap<u64, OpenBlock>,
+    item_count: usize,
+    usage: MessagesUsage,
+    stopped: bool,
+}
+
+impl MessagesStreamState {
+    fn next_item_id(&mut self) -> String {
+        self.item_count += 1;
+        format!("{}-{}", self.response_id, self.item_count)
+    }
+
+    fn handle_event(
+        &mut self,
+        event: MessagesStreamEvent,
+        tool_names: &ToolCallNames,
+    ) -> Result<Vec<ResponseEvent>, ApiError> {
+        let mut events = Vec::new();
+        match event {
+            MessagesStreamEvent::MessageStart { message } => {
+                self.response_id = message.id;
+                if let Some(usage) = message.usage {
+                    self.usage.merge(usage);
+                }
+                if !self.created {
+                    self.created = true;
+                    events.push(ResponseEvent::Created);
+                }
+            }
+            MessagesStreamEvent::ContentBlockStart {
+                index,
+                content_block,
+            } => self.start_block(index, content_block, &mut events),
+            MessagesStreamEvent::ContentBlockDelta { index, delta } => {
+                self.push_delta(index, delta, &mut events);
+            }
+            MessagesStreamEvent::ContentBlockStop { index } => {
+                if let Some(block) = self.blocks.remove(&index) {
+                    events.push(ResponseEvent::OutputItemDone(finish_block(
+                        block, tool_names,
+                    )));
+                }
+            }
+            MessagesStreamEvent::MessageDelta { usage } => {
+                if let Some(usage) = usage {
+                    self.usage.merge(usage);
+                }
+            }
+            MessagesStreamEvent::MessageStop => {
+                self.stopped = true;
+                // Servers close every block before `message_stop`; flush any
+                // that were left open so no output is lost.
+                for (_, block) in std::mem::take(&mut self.blocks) {
+                    events.push(ResponseEvent::OutputItemDone(finish_block(
+                        block, tool_names,
+                    )));
+                }
+                events.push(ResponseEvent::Completed {
+                    response_id: self.response_id.clone(),
+                    token_usage: Some(self.usage.into()),
+                });
+            }
+            MessagesStreamEvent::Error { error } => {
+                let message = error
+                    .message
+                    .unwrap_or_else(|| "messages stream returned an error".to_string());
+                return Err(match error.r#type.as_deref() {
+                    Some("overloaded_error") => ApiError::ServerOverloaded,
+                    _ => ApiError::Stream(message),
+                });
+            }
+            MessagesStreamEvent::Other => {}
+        }
+        Ok(events)
+    }
+
+    fn start_block(
+        &mut self,
+        index: u64,
+        content_block: MessagesContentBlock,
+        events: &mut Vec<ResponseEvent>,
+    ) {
+        let block = match content_block {
+            MessagesContentBlock::Text { text } => {
+                let id = self.next_item_id();
+                events.push(ResponseEvent::OutputItemAdded(ResponseItem::Message {
+                    id: Some(id.clone()),
+                    role: "assistant".to_string(),
+                    content: Vec::new(),
+                    end_turn: None,
+                    phase: None,
+                }));
+                if !text.is_empty() {
+                    events.push(ResponseEvent::OutputTextDelta(text.clone()));
+                }
+                OpenBlock::Text { id, text }
+            }
+            MessagesContentBlock::Thinking {
+                thinking,
+                signature,
+            } => {
+                let id = self.next_item_id();
+                events.push(ResponseEvent::OutputItemAdded(ResponseItem::Reasoning {
+                    id: id.clone(),
+                    summary: Vec::new(),
+                    content: None,
+                    encrypted_content: None,
+                }));
+                if !thinking.is_empty() {
+                    events.push(ResponseEvent::ReasoningContentDelta {
+                        delta: thinking.clone(),
+                        content_index: 0,
+                    });
+                }
+                OpenBlock::Thinking {
+                    id,
+                    text: thinking,
+                    signature,
+                }
+            }
+            MessagesContentBlock::RedactedThinking { data } => {
+                let id = self.next_item_id();
+                // Redacted thinking has no deltas; it is complete on arrival.
+                events.push(ResponseEvent::OutputItemDone(ResponseItem::Reasoning {
+                    id,
+                    summary: Vec::new(),
+                    content: None,
+                    encrypted_content: Some(data),
+                }));
+                return;
+            }
+            MessagesContentBlock::ToolUse { id, name } => OpenBlock::ToolUse {
+                call_id: id,
+                name,
+                input_json: String::new(),
+            },
+            MessagesContentBlock::Other => return,
+        };
+        self.blocks.insert(index, block);
+    }
+
+    fn push_delta(
+        &mut self,
+        index: u64,
+        delta: MessagesContentDelta,
+        events: &mut Vec<ResponseEvent>,
+    ) {
+        match (self.blocks.get_mut(&index), delta) {
+            (
+                Some(OpenBlock::Text { text, .. }),
+                MessagesContentDelta::TextDelta { text: delta },
+            ) => {
+                text.push_str(&delta);
+                events.push(ResponseEvent::OutputTextDelta(delta));
+            }
+            (
+                Some(OpenBlock::Thinking { text, .. }),
+                MessagesContentDelta::ThinkingDelta { thinking },
+            ) => {
+                text.push_str(&thinking);
+                events.push(ResponseEvent::ReasoningContentDelta {
+                    delta: thinking,
+                    content_index: 0,
+                });
+            }
+            (
+                Some(OpenBlock::Thinking { signature, .. }),
+                MessagesContentDelta::SignatureDelta { signature: delta },
+            ) => signature.push_str(&delta),
+            (
+                Some(OpenBlock::ToolUse { input_json, .. }),
+                MessagesContentDelta::InputJsonDelta { partial_json },
+            ) => input_json.push_str(&partial_json),
+            (_, delta) => debug!("ignoring Messages delta for block {index}: {delta:?}"),
+        }
+    }
+}
+
+fn finish_block(block: OpenBlock, tool_names: &ToolCallNames) -> ResponseItem {
+    match block {
+        OpenBlock::Text { id, text } => ResponseItem::Message {
+            id: Some(id),
+            role: "assistant".to_string(),
+            content: vec![ContentItem::OutputText { text }],
+            end_turn: None,
+            phase: None,
+        },
+        OpenBlock::Thinking {
+            id,
+            text,
+            signature,
+        } => ResponseItem::Reasoning {
+            id,
+            summary: Vec::new(),
+            content: Some(vec![ReasoningItemContent::ReasoningText { text }]),
+            encrypted_content: (!signature.is_empty()).then_some(signature),
+        },
+        OpenBlock::ToolUse {
+            call_id,
+            name,
+            input_json,
+        } => {
+            // A tool with no parameters streams no input deltas at all.
+            let arguments = if input_json.trim().is_empty() {
+                "{}".to_string()
+            } else {
+                input_json
+            };
+            tool_names.tool_call_item(call_id, name, arguments)
+        }
+    }
+}
+
+pub(crate) async fn process_messages_sse(
+    stream: ByteStream,
+    tx_event: mpsc::Sender<Result<ResponseEvent, ApiError>>,
+    idle_timeout: Duration,
+    telemetry: Option<Arc<dyn SseTelemetry>>,
+    tool_names: ToolCallNames,
+) {
+    let mut stream = stream.eventsource();
+    let mut state = MessagesStreamState::default();
+
+    loop {
+        let start = Instant::now();
+        let response = timeout(idle_timeout, stream.next()).await;
+        if let Some(t) = telemetry.as_ref() {
+            t.on_sse_poll(&response, start.elapsed());
+        }
+        let sse = match response {
+            Ok(Some(Ok(sse))) => sse,
+            Ok(Some(Err(e))) => {
+                debug!("SSE Error: {e:#}");
+                let _ = tx_event.send(Err(ApiError::Stream(e.to_string()))).await;
+                return;
+            }
+            Ok(None) => {
+                if !state.stopped {
+                    let _ = tx_event
+                        .send(Err(ApiError::Stream(
+                            "stream closed before message_stop".into(),
+                        )))
+                        .await;
+                }
+                return;
+            }
+            Err(_) => {
+                let _ = tx_event
+                    .send(Err(ApiError::Stream("idle timeout waiting for SSE".into())))
+                    .await;
+                return;
+            }
+        };
+
+        trace!("SSE event: {}", &sse.data);
+
+        let event: MessagesStreamEvent = match serde_json::from_str(&sse.data) {
+            Ok(event) => event,
+            Err(e) => {
+                debug!("Failed to parse SSE event: {e}, data: {}", &sse.data);
+                continue;
+            }
+        };
+
+        match state.handle_event(event, &tool_names) {
+            Ok(events) => {
+                for event in events {
+                    if tx_event.send(Ok(event)).await.is_err() {
+                        return;
+                    }
+                }
+            }
+            Err(error) => {
+                let _ = tx_event.send(Err(error)).await;
+                return;
+            }
+        }
+        if state.stopped {
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::assert_matches;
+    use pretty_assertions::assert_eq;
+    use serde_json::Value;
+    use serde_json::json;
+
+    async fn run_messages_sse(
+        events: Vec<Value>,
+        tool_names: ToolCallNames,
+    ) -> Vec<Result<ResponseEvent, ApiError>> {
+        let mut body = String::new();
+        for event in events {
+            let kind = event["type"].as_str().unwrap_or_default().to_string();
+            body.push_str(&format!("event: {kind}\ndata: {event}\n\n"));
+        }
+
+        let (tx, mut rx) = mpsc::channel::<Result<ResponseEvent, ApiError>>(32);
+        let stream = ReaderStream::new(std::io::Cursor::new(body))
+            .map_err(|err| TransportError::Network(err.to_string()));
+        tokio::spawn(process_messages_sse(
+            Box::pin(stream),
+            tx,
+            Duration::from_millis(1000),
+            /*telemetry*/ None,
+            tool_names,
+        ));
+
+        let mut events = Vec::new();
+        while let Some(event) = rx.recv().await {
+            events.push(event);
+        }
+        events
+    }
+
+    fn message_start() -> Value {
+        json!({
+            "type": "message_start",
+            "message": {
+                "id": "msg_1",
+                "model": "claude-test",
+                "usage": {
+                    "input_tokens": 10,
+                    "cache_creation_input_tokens": 2,
+                    "cache_read_input_tokens": 4,
+                    "output_tokens": 1,
+                },
+            },
+        })
+    }
+
+    #[tokio::test]
+    async fn streams_thinking_then_text() {
+        let events = run_messages_sse(
+            vec![
+                message_start(),
+                json!({"type": "ping"}),
+                json!({
+                    "type": "content_block_start",
+                    "index": 0,
+                    "content_block": {"type": "thinking", "thinking": "", "signature": ""},
+                }),
+                json!({
+                    "type": "content_block_delta",
+                    "index": 0,
+                    "delta": {"type": "thinking_delta", "thinking": "think"},
+                }),
+                json!({
+                    "type": "content_block_delta",
+                    "index": 0,
+                    "delta": {"type": "signature_delta", "signature": "sig"},
+                }),
+                json!({"type": "content_block_stop", "index": 0}),
+                json!({
+                    "type": "content_block_start",
+                    "index": 1,
+                    "content_block": {"type": "text", "text": ""},
+                }),
+                json!({
+                    "type": "content_block_delta",
+                    "index": 1,
+                    "delta": {"type": "text_delta", "text": "Hello"},
+                }),
+                json!({"type": "content_block_stop", "index": 1}),
+                json!({
+                    "type": "message_delta",
+                    "delta": {"stop_reason": "end_turn"},
+                    "usage": {"output_tokens": 7},
+                }),
+                json!({"type": "message_stop"}),
+            ],
+            ToolCallNames::default(),
+        )
+        .await;
+        let events = events
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()
+            .expect("stream should succeed");
+
+        assert_eq!(events.len(), 8);
+        assert_matches!(events[0], ResponseEvent::Created);
+        assert_matches!(
+            &events[1],
+            ResponseEvent::OutputItemAdded(ResponseItem::Reasoning { id, .. }) if id == "msg_1-1"
+        );
+        assert_matches!(
+            &events[2],
+            ResponseEvent::ReasoningContentDelta { delta, content_index: 0 } if delta == "think"
+        );
+        assert_matches!(
+            &events[3],
+            ResponseEvent::OutputItemDone(ResponseItem::Reasoning {
+                content: Some(content),
+                encrypted_content: Some(signature),
+                ..
+            }) if content == &vec![ReasoningItemContent::ReasoningText {
+                text: "think".to_string(),
+            }] && signature == "sig"
+        );
+        assert_matches!(
+            &events[4],
+            ResponseEvent::OutputItemAdded(ResponseItem::Message { id: Some(id), .. })
+                if id == "msg_1-2"
+        );
+        assert_matches!(&events[5], ResponseEvent::OutputTextDelta(delta) if delta == "Hello");
+        assert_matches!(
+            &events[6],
+            ResponseEvent::OutputItemDone(ResponseItem::Message { content, .. })
+                if content == &vec![ContentItem::OutputText { text: "Hello".to_string() }]
+        );
+        assert_matches!(
+            &events[7],
+            ResponseEvent::Completed { response_id, token_usage: Some(token_usage) }
+                if response_id == "msg_1"
+                    && token_usage == &TokenUsage {
+                        input_tokens: 16,
+                        cached_input_tokens: 4,
+                        output_tokens: 7,
+                        reasoning_output_tokens: 0,
+                        total_tokens: 23,
+                    }
+        );
+    }
+
+    #[tokio::test]
+    async fn maps_tool_use_to_function_and_custom_calls() {
+        let events = run_messages_sse(
+            vec![
+                message_start(),
+                json!({
+                    "type": "content_block_start",
+                    "index": 0,
+                    "content_block": {
+                        "type": "tool_use",
+                        "id": "toolu_1",
+                        "name": "mcp__docs__search",
+                        "input": {},
+                    },
+                }),
+                json!({
+                    "type": "content_block_delta",
+                    "index": 0,
+                    "delta": {"type": "input_json_delta", "partial_json": "{\"query\":"},
+                }),
+                json!({
+                    "type": "content_block_delta",
+                    "index": 0,
+                    "delta": {"type": "input_json_delta", "partial_json": "\"a\"}"},
+                }),
+                json!({"type": "content_block_stop", "index": 0}),
+                json!({
+                    "type": "content_block_start",
+                    "index": 1,
+                    "content_block": {
+                        "type": "tool_use",
+                        "id": "toolu_2",
+                        "name": "apply_patch",
+                        "input": {},
+                    },
+                }),
+                json!({
+                    "type": "content_block_delta",
+                    "index": 1,
+                    "delta": {
+                        "type": "input_json_delta",
+                        "partial_json": "{\"input\":\"*** Begin Patch\"}",
+                    },
+                }),
+                json!({"type": "content_block_stop", "index": 1}),
+                json!({
+                    "type": "content_block_start",
+                    "index": 2,
+                    "content_block": {"type": "tool_use", "id": "toolu_3", "name": "list", "input": {}},
+                }),
+                json!({"type": "content_block_stop", "index": 2}),
+                json!({"type": "message_delta", "delta": {"stop_reason": "tool_use"}}),
+                json!({"type": "message_stop"}),
+            ],
+            ToolCallNames {
+                namespaced_tools: HashMap::from([(
+                    "mcp__docs__search".to_string(),
+                    ToolName::namespaced("mcp__docs__", "search"),
+                )]),
+                freeform_tools: HashSet::from(["apply_patch".to_string()]),
+            },
+        )
+        .await;
+        let items = events
+            .into_iter()
+            .filter_map(|event| match event {
+                Ok(ResponseEvent::OutputItemDone(item)) => Some(item),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            items,
+            vec![
+                ResponseItem::FunctionCall {
+                    id: None,
+                    name: "search".to_string(),
+                    namespace: Some("mcp__docs__".to_string()),
+                    arguments: "{\"query\":\"a\"}".to_string(),
+                    call_id: "toolu_1".to_string(),
+                },
+                ResponseItem::CustomToolCall {
+                    id: None,
+                    status: None,
+                    call_id: "toolu_2".to_string(),
+                    name: "apply_patch".to_string(),
+                    input: "*** Begin Patch".to_string(),
+                },
+                ResponseItem::FunctionCall {
+                    id: None,
+                    name: "list".to_string(),
+                    namespace: None,
+                    arguments: "{}".to_string(),
+                    call_id: "toolu_3".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn surfaces_stream_errors() {
+        let events = run_messages_sse(
+            vec![
+                message_start(),
+                json!({
+                    "type": "error",
+                    "error": {"type": "overloaded_error", "message": "Overloaded"},
+                }),
+            ],
+            ToolCallNames::default(),
+        )
+        .await;
+
+        assert_eq!(events.len(), 2);
+        assert_matches!(events[1], Err(ApiError::ServerOverloaded));
+    }
+}
@@ -1,6 +1,14 @@
+pub(crate) mod chat;
+pub(crate) mod messages;
 pub(crate) mod responses;
+pub(crate) mod tool_calls;
 
+pub(crate) use chat::spawn_chat_completions_stream;
+pub use chat::stream_chat_completions_from_fixture;
+pub(crate) use messages::spawn_messages_stream;
+pub use messages::stream_messages_from_fixture;
 pub(crate) use responses::ResponsesStreamEvent;
 pub(crate) use responses::process_responses_event;
 pub use responses::spawn_response_stream;
 pub use responses::stream_from_fixture;
+pub(crate) use tool_calls::ToolCallNames;
@@ -0,0 +1,50 @@
+use codex_protocol::ToolName;
+use codex_protocol::models::ResponseItem;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+/// Tool names used to map streamed tool calls back to Codex tool calls on
+/// wire APIs that only know about JSON functions.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ToolCallNames {
+    pub(crate) namespaced_tools: HashMap<String, ToolName>,
+    pub(crate) freeform_tools: HashSet<String>,
+}
+
+impl ToolCallNames {
+    /// Builds the output item for a completed tool call: freeform tools become
+    /// custom tool calls carrying their raw `input` text, and flattened
+    /// namespaced names get their namespace restored.
+    pub(crate) fn tool_call_item(
+        &self,
+        call_id: String,
+        name: String,
+        arguments: String,
+    ) -> ResponseItem {
+        if self.freeform_tools.contains(&name) {
+            let input = serde_json::from_str::<Value>(&arguments)
+                .ok()
+                .and_then(|value| value.get("input")?.as_str().map(str::to_string))
+                .unwrap_or(arguments);
+            return ResponseItem::CustomToolCall {
+                id: None,
+                status: None,
+                call_id,
+                name,
+                input,
+            };
+        }
+        let (namespace, name) = match self.namespaced_tools.get(&name) {
+            Some(tool_name) => (tool_name.namespace.clone(), tool_name.name.clone()),
+            None => (None, name),
+        };
+        ResponseItem::FunctionCall {
+            id: None,
+            name,
+            namespace,
+            arguments,
+            call_id,
+        }
+    }
+}
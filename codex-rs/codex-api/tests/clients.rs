@@ -9,6 +9,9 @@ use codex_api::ApiError;
 use codex_api::AuthError;
 use codex_api::AuthProvider;
 use codex_api::Compression;
+use codex_api::MessagesApiRequest;
+use codex_api::MessagesClient;
+use codex_api::MessagesOptions;
 use codex_api::Provider;
 use codex_api::ResponsesApiRequest;
 use codex_api::ResponsesClient;
@@ -272,6 +275,52 @@ async fn responses_client_uses_responses_path() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn messages_client_uses_messages_path_and_anthropic_headers() -> Result<()> {
+    let state = RecordingState::default();
+    let transport = RecordingTransport::new(state.clone());
+    let client = MessagesClient::new(transport, provider("gateway"), Arc::new(NoAuth));
+
+    let _stream = client
+        .stream_request(
+            MessagesApiRequest {
+                model: "claude-test".to_string(),
+                instructions: "be helpful".to_string(),
+                input: Vec::new(),
+                tools: Vec::new(),
+                parallel_tool_calls: true,
+                max_tokens: 1024,
+                thinking_budget_tokens: None,
+                prompt_caching: true,
+                namespaced_tools: Default::default(),
+                freeform_tools: Default::default(),
+            },
+            MessagesOptions {
+                anthropic_version: "2023-06-01".to_string(),
+                anthropic_beta: vec!["beta-a".to_string(), "beta-b".to_string()],
+                ..Default::default()
+            },
+        )
+        .await?;
+
+    let requests = state.take_stream_requests();
+    assert_path_ends_with(&requests, "/messages");
+    let headers = &requests[0].headers;
+    assert_eq!(
+        headers
+            .get("anthropic-version")
+            .and_then(|value| value.to_str().ok()),
+        Some("2023-06-01")
+    );
+    assert_eq!(
+        headers
+            .get("anthropic-beta")
+            .and_then(|value| value.to_str().ok()),
+        Some("beta-a,beta-b")
+    );
+    Ok(())
+}
+
 #[tokio::test]
 async fn streaming_client_adds_auth_headers() -> Result<()> {
     let state = RecordingState::default();
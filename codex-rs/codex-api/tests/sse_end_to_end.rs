@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -5,7 +7,13 @@ use anyhow::Result;
 use async_trait::async_trait;
 use bytes::Bytes;
 use codex_api::AuthProvider;
+use codex_api::ChatCompletionsApiRequest;
+use codex_api::ChatCompletionsClient;
+use codex_api::ChatCompletionsOptions;
 use codex_api::Compression;
+use codex_api::MessagesApiRequest;
+use codex_api::MessagesClient;
+use codex_api::MessagesOptions;
 use codex_api::Provider;
 use codex_api::ResponseEvent;
 use codex_api::ResponsesClient;
@@ -14,6 +22,9 @@ use codex_client::Request;
 use codex_client::Response;
 use codex_client::StreamResponse;
 use codex_client::TransportError;
+use codex_protocol::ToolName;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ReasoningItemContent;
 use codex_protocol::models::ResponseItem;
 use futures::StreamExt;
 use http::HeaderMap;
@@ -167,3 +178,198 @@ async fn responses_stream_parses_items_and_completed_end_to_end() -> Result<()>
 
     Ok(())
 }
+
+fn build_chat_completions_body(chunks: Vec<Value>) -> String {
+    let mut body = String::new();
+    for chunk in chunks {
+        body.push_str(&format!("data: {chunk}\n\n"));
+    }
+    body.push_str("data: [DONE]\n\n");
+    body
+}
+
+#[tokio::test]
+async fn chat_completions_stream_parses_text_and_tool_calls_end_to_end() -> Result<()> {
+    let chunks = vec![
+        serde_json::json!({
+            "id": "chatcmpl-1",
+            "model": "local-model",
+            "choices": [{"index": 0, "delta": {"role": "assistant", "content": "Checking"}}]
+        }),
+        serde_json::json!({
+            "id": "chatcmpl-1",
+            "choices": [{"index": 0, "delta": {"tool_calls": [{
+                "index": 0,
+                "id": "call_1",
+                "type": "function",
+                "function": {"name": "mcp__docs__search", "arguments": "{\"q\":"}
+            }]}}]
+        }),
+        serde_json::json!({
+            "id": "chatcmpl-1",
+            "choices": [{"index": 0, "delta": {"tool_calls": [{
+                "index": 0,
+                "function": {"arguments": "\"codex\"}"}
+            }]}, "finish_reason": "tool_calls"}]
+        }),
+        serde_json::json!({
+            "id": "chatcmpl-1",
+            "choices": [],
+            "usage": {"prompt_tokens": 7, "completion_tokens": 3, "total_tokens": 10}
+        }),
+    ];
+    let transport = FixtureSseTransport::new(build_chat_completions_body(chunks));
+    let client = ChatCompletionsClient::new(transport, provider("local"), Arc::new(NoAuth));
+
+    let mut stream = client
+        .stream_request(
+            ChatCompletionsApiRequest {
+                model: "local-model".to_string(),
+                instructions: String::new(),
+                input: Vec::new(),
+                tools: Vec::new(),
+                tool_choice: "auto".to_string(),
+                parallel_tool_calls: false,
+                reasoning_effort: None,
+                namespaced_tools: HashMap::from([(
+                    "mcp__docs__search".to_string(),
+                    ToolName::namespaced("mcp__docs__", "search"),
+                )]),
+                freeform_tools: HashSet::new(),
+            },
+            ChatCompletionsOptions::default(),
+        )
+        .await?;
+
+    let mut events = Vec::new();
+    while let Some(ev) = stream.next().await {
+        events.push(ev?);
+    }
+
+    let items: Vec<ResponseItem> = events
+        .iter()
+        .filter_map(|ev| match ev {
+            ResponseEvent::OutputItemDone(item) => Some(item.clone()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        items,
+        vec![
+            ResponseItem::Message {
+                id: Some("chatcmpl-1-1".to_string()),
+                role: "assistant".to_string(),
+                content: vec![ContentItem::OutputText {
+                    text: "Checking".to_string(),
+                }],
+                end_turn: None,
+                phase: None,
+            },
+            ResponseItem::FunctionCall {
+                id: None,
+                name: "search".to_string(),
+                namespace: Some("mcp__docs__".to_string()),
+                arguments: "{\"q\":\"codex\"}".to_string(),
+                call_id: "call_1".to_string(),
+            },
+        ]
+    );
+
+    match events.last() {
+        Some(ResponseEvent::Completed {
+            response_id,
+            token_usage: Some(token_usage),
+        }) => {
+            assert_eq!(response_id, "chatcmpl-1");
+            assert_eq!(token_usage.total_tokens, 10);
+        }
+        other => panic!("unexpected last event: {other:?}"),
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn messages_stream_parses_recorded_fixture_end_to_end() -> Result<()> {
+    let transport =
+        FixtureSseTransport::new(include_str!("fixtures/messages_tool_use.sse").to_string());
+    let client = MessagesClient::new(transport, provider("gateway"), Arc::new(NoAuth));
+
+    let mut stream = client
+        .stream_request(
+            MessagesApiRequest {
+                model: "claude-test".to_string(),
+                instructions: String::new(),
+                input: Vec::new(),
+                tools: Vec::new(),
+                parallel_tool_calls: true,
+                max_tokens: 1024,
+                thinking_budget_tokens: None,
+                prompt_caching: true,
+                namespaced_tools: HashMap::from([(
+                    "mcp__docs__search".to_string(),
+                    ToolName::namespaced("mcp__docs__", "search"),
+                )]),
+                freeform_tools: HashSet::new(),
+            },
+            MessagesOptions::default(),
+        )
+        .await?;
+
+    let mut events = Vec::new();
+    while let Some(ev) = stream.next().await {
+        events.push(ev?);
+    }
+
+    let items: Vec<ResponseItem> = events
+        .iter()
+        .filter_map(|ev| match ev {
+            ResponseEvent::OutputItemDone(item) => Some(item.clone()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        items,
+        vec![
+            ResponseItem::Reasoning {
+                id: "msg_01-1".to_string(),
+                summary: Vec::new(),
+                content: Some(vec![ReasoningItemContent::ReasoningText {
+                    text: "The user wants the docs searched.".to_string(),
+                }]),
+                encrypted_content: Some("EqQBCgIYAhIM1gbcDa9GJwZA2b3h".to_string()),
+            },
+            ResponseItem::Message {
+                id: Some("msg_01-2".to_string()),
+                role: "assistant".to_string(),
+                content: vec![ContentItem::OutputText {
+                    text: "Checking".to_string(),
+                }],
+                end_turn: None,
+                phase: None,
+            },
+            ResponseItem::FunctionCall {
+                id: None,
+                name: "search".to_string(),
+                namespace: Some("mcp__docs__".to_string()),
+                arguments: "{\"q\": \"codex\"}".to_string(),
+                call_id: "toolu_01".to_string(),
+            },
+        ]
+    );
+
+    match events.last() {
+        Some(ResponseEvent::Completed {
+            response_id,
+            token_usage: Some(token_usage),
+        }) => {
+            assert_eq!(response_id, "msg_01");
+            assert_eq!(token_usage.input_tokens, 2168);
+            assert_eq!(token_usage.cached_input_tokens, 2048);
+            assert_eq!(token_usage.output_tokens, 42);
+        }
+        other => panic!("unexpected last event: {other:?}"),
+    }
+
+    Ok(())
+}
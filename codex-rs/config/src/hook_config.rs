@@ -26,6 +26,16 @@ pub struct HookEventsToml {
     pub user_prompt_submit: Vec<MatcherGroup>,
     #[serde(rename = "Stop", default)]
     pub stop: Vec<MatcherGroup>,
+    #[serde(rename = "PreCompact", default)]
+    pub pre_compact: Vec<MatcherGroup>,
+    #[serde(rename = "SessionEnd", default)]
+    pub session_end: Vec<MatcherGroup>,
+    #[serde(rename = "SubagentStart", default)]
+    pub subagent_start: Vec<MatcherGroup>,
+    #[serde(rename = "SubagentStop", default)]
+    pub subagent_stop: Vec<MatcherGroup>,
+    #[serde(rename = "Notification", default)]
+    pub notification: Vec<MatcherGroup>,
 }
 
 impl HookEventsToml {
@@ -37,6 +47,11 @@ impl HookEventsToml {
             session_start,
             user_prompt_submit,
             stop,
+            pre_compact,
+            session_end,
+            subagent_start,
+            subagent_stop,
+            notification,
         } = self;
         pre_tool_use.is_empty()
             && permission_request.is_empty()
@@ -44,6 +59,11 @@ impl HookEventsToml {
             && session_start.is_empty()
             && user_prompt_submit.is_empty()
             && stop.is_empty()
+            && pre_compact.is_empty()
+            && session_end.is_empty()
+            && subagent_start.is_empty()
+            && subagent_stop.is_empty()
+            && notification.is_empty()
     }
 
     pub fn handler_count(&self) -> usize {
@@ -54,6 +74,11 @@ impl HookEventsToml {
             session_start,
             user_prompt_submit,
             stop,
+            pre_compact,
+            session_end,
+            subagent_start,
+            subagent_stop,
+            notification,
         } = self;
         [
             pre_tool_use,
@@ -62,6 +87,11 @@ impl HookEventsToml {
             session_start,
             user_prompt_submit,
             stop,
+            pre_compact,
+            session_end,
+            subagent_start,
+            subagent_stop,
+            notification,
         ]
         .into_iter()
         .flatten()
@@ -69,7 +99,7 @@ impl HookEventsToml {
         .sum()
     }
 
-    pub fn into_matcher_groups(self) -> [(HookEventName, Vec<MatcherGroup>); 6] {
+    pub fn into_matcher_groups(self) -> [(HookEventName, Vec<MatcherGroup>); 11] {
         [
             (HookEventName::PreToolUse, self.pre_tool_use),
             (HookEventName::PermissionRequest, self.permission_request),
@@ -77,6 +107,11 @@ impl HookEventsToml {
             (HookEventName::SessionStart, self.session_start),
             (HookEventName::UserPromptSubmit, self.user_prompt_submit),
             (HookEventName::Stop, self.stop),
+            (HookEventName::PreCompact, self.pre_compact),
+            (HookEventName::SessionEnd, self.session_end),
+            (HookEventName::SubagentStart, self.subagent_start),
+            (HookEventName::SubagentStop, self.subagent_stop),
+            (HookEventName::Notification, self.notification),
         ]
     }
 }
@@ -102,10 +137,29 @@ pub enum HookHandlerConfig {
         #[serde(default, rename = "statusMessage")]
         status_message: Option<String>,
     },
+    /// Asks a model to evaluate the hook input and answer with an allow/block decision.
     #[serde(rename = "prompt")]
-    Prompt {},
+    Prompt {
+        prompt: String,
+        #[serde(default)]
+        model: Option<String>,
+        #[serde(default, rename = "timeout")]
+        timeout_sec: Option<u64>,
+        #[serde(default, rename = "statusMessage")]
+        status_message: Option<String>,
+    },
+    /// Like `prompt`, but runs a short-lived read-only sub-agent that can inspect the
+    /// workspace before answering.
     #[serde(rename = "agent")]
-    Agent {},
+    Agent {
+        prompt: String,
+        #[serde(default)]
+        model: Option<String>,
+        #[serde(default, rename = "timeout")]
+        timeout_sec: Option<u64>,
+        #[serde(default, rename = "statusMessage")]
+        status_message: Option<String>,
+    },
 }
 
 #[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -117,3 +117,52 @@ command = "python3 /enterprise/place/pre.py"
         }
     );
 }
+
+#[test]
+fn hooks_file_deserializes_prompt_and_agent_handlers() {
+    let parsed: HooksFile = serde_json::from_str(
+        r#"{
+  "hooks": {
+    "Stop": [
+      {
+        "hooks": [
+          {
+            "type": "prompt",
+            "prompt": "Did the agent finish the task? $ARGUMENTS",
+            "timeout": 20
+          },
+          {
+            "type": "agent",
+            "prompt": "Check the diff against CONTRIBUTING.md",
+            "model": "gpt-5.1-codex-mini",
+            "statusMessage": "reviewing conventions"
+          }
+        ]
+      }
+    ]
+  }
+}"#,
+    )
+    .expect("hooks.json should deserialize");
+
+    assert_eq!(
+        parsed.hooks.stop,
+        vec![MatcherGroup {
+            matcher: None,
+            hooks: vec![
+                HookHandlerConfig::Prompt {
+                    prompt: "Did the agent finish the task? $ARGUMENTS".to_string(),
+                    model: None,
+                    timeout_sec: Some(20),
+                    status_message: None,
+                },
+                HookHandlerConfig::Agent {
+                    prompt: "Check the diff against CONTRIBUTING.md".to_string(),
+                    model: Some("gpt-5.1-codex-mini".to_string()),
+                    timeout_sec: None,
+                    status_message: Some("reviewing conventions".to_string()),
+                },
+            ],
+        }]
+    );
+}
@@ -1,11 +1,14 @@
 use std::collections::BTreeMap;
 
+use codex_network_proxy::NetworkCredentialInjection;
 use codex_network_proxy::NetworkDomainPermission as ProxyNetworkDomainPermission;
 use codex_network_proxy::NetworkMode;
 use codex_network_proxy::NetworkProxyConfig;
+use codex_network_proxy::NetworkRequestRule;
 use codex_network_proxy::NetworkUnixSocketPermission as ProxyNetworkUnixSocketPermission;
 use codex_network_proxy::normalize_host;
 use codex_protocol::permissions::FileSystemAccessMode;
+use codex_utils_absolute_path::AbsolutePathBuf;
 use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
@@ -27,6 +30,7 @@ impl PermissionsToml {
 pub struct PermissionProfileToml {
     pub filesystem: Option<FilesystemPermissionsToml>,
     pub network: Option<NetworkToml>,
+    pub resources: Option<ResourceLimitsToml>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, JsonSchema)]
@@ -45,6 +49,36 @@ impl FilesystemPermissionsToml {
     }
 }
 
+/// Per-command resource limits. Each command Codex spawns, including its
+/// descendants, gets its own budget; unset fields are unlimited.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct ResourceLimitsToml {
+    /// Maximum resident memory in bytes.
+    #[schemars(range(min = 1))]
+    pub max_rss_bytes: Option<u64>,
+    /// Maximum CPU time in seconds.
+    #[schemars(range(min = 1))]
+    pub cpu_seconds: Option<u64>,
+    /// Maximum number of processes and threads.
+    #[schemars(range(min = 1))]
+    pub max_pids: Option<u64>,
+    /// Maximum open file descriptors per process.
+    #[schemars(range(min = 1))]
+    pub max_open_files: Option<u64>,
+    /// Maximum combined stdout and stderr bytes.
+    #[schemars(range(min = 1))]
+    pub max_output_bytes: Option<u64>,
+    /// Maximum wall-clock time in seconds, regardless of the timeout the
+    /// model requests.
+    #[schemars(range(min = 1))]
+    pub wall_time_seconds: Option<u64>,
+    /// Delegated cgroup v2 directory (Linux only). When set and writable,
+    /// each command runs in its own leaf cgroup under it, which enforces
+    /// `max_rss_bytes` and `max_pids` exactly.
+    pub cgroup_parent: Option<AbsolutePathBuf>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
 #[serde(untagged)]
 pub enum FilesystemPermissionToml {
@@ -158,6 +192,16 @@ pub struct NetworkToml {
     pub domains: Option<NetworkDomainPermissionsToml>,
     pub unix_sockets: Option<NetworkUnixSocketPermissionsToml>,
     pub allow_local_binding: Option<bool>,
+    /// Terminate HTTPS `CONNECT` tunnels so request rules and credential injection apply.
+    pub mitm: Option<bool>,
+    /// Request-level rules keyed on scheme, host, path glob and HTTP method.
+    #[schemars(with = "Option<Vec<NetworkRequestRuleSchema>>")]
+    pub rules: Option<Vec<NetworkRequestRule>>,
+    /// Headers the MITM proxy injects per host, with values read from Codex secrets.
+    #[schemars(with = "Option<Vec<NetworkCredentialInjectionSchema>>")]
+    pub credentials: Option<Vec<NetworkCredentialInjection>>,
+    /// Record proxied requests to `$CODEX_HOME/network_logs/<thread_id>.jsonl`.
+    pub record_traffic: Option<bool>,
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
@@ -167,6 +211,39 @@ enum NetworkModeSchema {
     Full,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+struct NetworkRequestRuleSchema {
+    scheme: Option<NetworkRequestSchemeSchema>,
+    host: String,
+    path: Option<String>,
+    methods: Option<Vec<String>>,
+    decision: NetworkRequestRuleDecisionSchema,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+struct NetworkCredentialInjectionSchema {
+    host: String,
+    header: String,
+    secret: String,
+    prefix: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+enum NetworkRequestSchemeSchema {
+    Http,
+    Https,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+enum NetworkRequestRuleDecisionSchema {
+    Allow,
+    Deny,
+}
+
 impl NetworkToml {
     pub fn apply_to_network_proxy_config(&self, config: &mut NetworkProxyConfig) {
         if let Some(enabled) = self.enabled {
@@ -219,6 +296,22 @@ impl NetworkToml {
         if let Some(allow_local_binding) = self.allow_local_binding {
             config.network.allow_local_binding = allow_local_binding;
         }
+        if let Some(mitm) = self.mitm {
+            config.network.mitm = mitm;
+        }
+        if let Some(rules) = self.rules.as_ref() {
+            for rule in rules {
+                config.network.add_request_rule(rule.clone());
+            }
+        }
+        if let Some(credentials) = self.credentials.as_ref() {
+            for credential in credentials {
+                config.network.add_credential_injection(credential.clone());
+            }
+        }
+        if let Some(record_traffic) = self.record_traffic {
+            config.network.record_traffic = record_traffic;
+        }
     }
 
     pub fn to_network_proxy_config(&self) -> NetworkProxyConfig {
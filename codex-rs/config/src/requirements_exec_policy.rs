@@ -174,6 +174,7 @@ impl RequirementsExecPolicyToml {
                     },
                     decision,
                     justification: justification.clone(),
+                    location: None,
                 });
                 rules_by_program.insert(head.clone(), rule);
             }
@@ -304,6 +304,7 @@ mod tests {
             auth: None,
             aws: None,
             wire_api: WireApi::Responses,
+            messages: None,
             query_params: None,
             http_headers: None,
             env_http_headers: None,
@@ -281,6 +281,53 @@ impl From<MemoriesToml> for MemoriesConfig {
     }
 }
 
+/// Secret redaction settings loaded from config.toml.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct RedactionToml {
+    /// Additional regular expressions to redact. Every match is replaced with
+    /// `[REDACTED_SECRET]`, on top of the built-in patterns.
+    #[serde(default)]
+    pub patterns: Vec<String>,
+    /// When `true`, also redact long tokens that mix letters and digits and
+    /// look random. Defaults to `false`.
+    pub high_entropy_tokens: Option<bool>,
+    /// When `true`, scrub the exact values of stored secrets and of environment
+    /// variables whose names end in `_TOKEN` or `_KEY`. Defaults to `true`.
+    pub known_values: Option<bool>,
+}
+
+/// Effective redaction settings after defaults are applied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RedactionConfig {
+    pub patterns: Vec<String>,
+    pub high_entropy_tokens: bool,
+    pub known_values: bool,
+}
+
+impl Default for RedactionConfig {
+    fn default() -> Self {
+        Self {
+            patterns: Vec::new(),
+            high_entropy_tokens: false,
+            known_values: true,
+        }
+    }
+}
+
+impl From<RedactionToml> for RedactionConfig {
+    fn from(toml: RedactionToml) -> Self {
+        let defaults = Self::default();
+        Self {
+            patterns: toml.patterns,
+            high_entropy_tokens: toml
+                .high_entropy_tokens
+                .unwrap_or(defaults.high_entropy_tokens),
+            known_values: toml.known_values.unwrap_or(defaults.known_values),
+        }
+    }
+}
+
 /// Default settings that apply to all apps.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default, JsonSchema)]
 #[schemars(deny_unknown_fields)]
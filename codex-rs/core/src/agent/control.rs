@@ -7,6 +7,9 @@ use crate::agent::status::is_final;
 use crate::codex_thread::ThreadConfigSnapshot;
 use crate::find_archived_thread_path_by_id_str;
 use crate::find_thread_path_by_id_str;
+use crate::hook_runtime::SubagentHookTarget;
+use crate::hook_runtime::run_subagent_start_hooks;
+use crate::hook_runtime::run_subagent_stop_hooks;
 use crate::rollout::RolloutRecorder;
 use crate::session::emit_subagent_session_started;
 use crate::session_prefix::format_subagent_context_line;
@@ -314,6 +317,13 @@ impl AgentControl {
 
         self.send_input(new_thread.thread_id, initial_operation)
             .await?;
+        self.run_subagent_lifecycle_hooks(
+            &state,
+            new_thread.thread_id,
+            notification_source.as_ref(),
+            &agent_metadata,
+        )
+        .await;
         if !new_thread.thread.enabled(Feature::MultiAgentV2) {
             let child_reference = agent_metadata
                 .agent_path
@@ -906,6 +916,72 @@ impl AgentControl {
         Ok(agents)
     }
 
+    /// Waits until the agent reaches a final status, or returns its last known status if the
+    /// status channel closes first.
+    async fn wait_for_final_status(&self, agent_id: ThreadId) -> AgentStatus {
+        match self.subscribe_status(agent_id).await {
+            Ok(mut status_rx) => {
+                let mut status = status_rx.borrow().clone();
+                while !is_final(&status) {
+                    if status_rx.changed().await.is_err() {
+                        return self.get_status(agent_id).await;
+                    }
+                    status = status_rx.borrow().clone();
+                }
+                status
+            }
+            Err(_) => self.get_status(agent_id).await,
+        }
+    }
+
+    /// Runs the parent's `SubagentStart` hooks for a freshly spawned child and arranges for its
+    /// `SubagentStop` hooks to run once the child reaches a final status.
+    async fn run_subagent_lifecycle_hooks(
+        &self,
+        state: &Arc<ThreadManagerState>,
+        child_thread_id: ThreadId,
+        session_source: Option<&SessionSource>,
+        agent_metadata: &AgentMetadata,
+    ) {
+        let Some(SessionSource::SubAgent(SubAgentSource::ThreadSpawn {
+            parent_thread_id, ..
+        })) = session_source
+        else {
+            return;
+        };
+        let parent_thread_id = *parent_thread_id;
+        let Ok(parent_thread) = state.get_thread(parent_thread_id).await else {
+            return;
+        };
+        let target = SubagentHookTarget {
+            agent_id: child_thread_id,
+            agent_type: agent_metadata
+                .agent_role
+                .clone()
+                .unwrap_or_else(|| DEFAULT_ROLE_NAME.to_string()),
+            agent_path: agent_metadata.agent_path.as_ref().map(ToString::to_string),
+            agent_nickname: agent_metadata.agent_nickname.clone(),
+        };
+        run_subagent_start_hooks(&parent_thread.codex.session, &target).await;
+
+        let control = self.clone();
+        tokio::spawn(async move {
+            let status = control.wait_for_final_status(child_thread_id).await;
+            if !is_final(&status) {
+                return;
+            }
+            let token_usage = control.get_total_token_usage(child_thread_id).await;
+            let Ok(state) = control.upgrade() else {
+                return;
+            };
+            let Ok(parent_thread) = state.get_thread(parent_thread_id).await else {
+                return;
+            };
+            run_subagent_stop_hooks(&parent_thread.codex.session, target, &status, token_usage)
+                .await;
+        });
+    }
+
     /// Starts a detached watcher for sub-agents spawned from another thread.
     ///
     /// This is only enabled for `SubAgentSource::ThreadSpawn`, where a parent thread exists and
@@ -925,20 +1001,7 @@ impl AgentControl {
         };
         let control = self.clone();
         tokio::spawn(async move {
-            let status = match control.subscribe_status(child_thread_id).await {
-                Ok(mut status_rx) => {
-                    let mut status = status_rx.borrow().clone();
-                    while !is_final(&status) {
-                        if status_rx.changed().await.is_err() {
-                            status = control.get_status(child_thread_id).await;
-                            break;
-                        }
-                        status = status_rx.borrow().clone();
-                    }
-                    status
-                }
-                Err(_) => control.get_status(child_thread_id).await,
-            };
+            let status = control.wait_for_final_status(child_thread_id).await;
             if !is_final(&status) {
                 return;
             }
@@ -8,6 +8,7 @@ use codex_apply_patch::ApplyPatchFileChange;
 use codex_protocol::protocol::FileChange;
 use codex_protocol::protocol::FileSystemSandboxPolicy;
 use std::collections::HashMap;
+use std::path::Path;
 use std::path::PathBuf;
 
 pub(crate) enum InternalApplyPatchInvocation {
@@ -94,12 +95,44 @@ pub(crate) fn convert_apply_patch_to_protocol(
                 unified_diff: unified_diff.clone(),
                 move_path: move_path.clone(),
             },
+            ApplyPatchFileChange::AddBinary { content } => binary_add_change(content),
+            ApplyPatchFileChange::UpdateBinary { .. } => binary_update_change(),
+            ApplyPatchFileChange::AddSymlink { target } => symlink_add_change(target),
+            ApplyPatchFileChange::SetMode { mode } => set_mode_change(*mode),
         };
         result.insert(path.clone(), protocol_change);
     }
     result
 }
 
+/// Protocol clients only render text, so binary, symlink, and mode changes are
+/// reported as short descriptions in the shape of an add or update.
+pub(crate) fn binary_add_change(content: &[u8]) -> FileChange {
+    FileChange::Add {
+        content: format!("Binary file ({} bytes)\n", content.len()),
+    }
+}
+
+pub(crate) fn binary_update_change() -> FileChange {
+    FileChange::Update {
+        unified_diff: "Binary files differ\n".to_string(),
+        move_path: None,
+    }
+}
+
+pub(crate) fn symlink_add_change(target: &Path) -> FileChange {
+    FileChange::Add {
+        content: format!("Symbolic link to {}\n", target.display()),
+    }
+}
+
+pub(crate) fn set_mode_change(mode: u32) -> FileChange {
+    FileChange::Update {
+        unified_diff: format!("new mode {mode:o}\n"),
+        move_path: None,
+    }
+}
+
 #[cfg(test)]
 #[path = "apply_patch_tests.rs"]
 mod tests;
@@ -33,12 +33,18 @@ use std::sync::atomic::Ordering;
 
 use codex_api::ApiError;
 use codex_api::AuthProvider;
+use codex_api::ChatCompletionsApiRequest;
+use codex_api::ChatCompletionsClient as ApiChatCompletionsClient;
+use codex_api::ChatCompletionsOptions as ApiChatCompletionsOptions;
 use codex_api::CompactClient as ApiCompactClient;
 use codex_api::CompactionInput as ApiCompactionInput;
 use codex_api::Compression;
 use codex_api::MemoriesClient as ApiMemoriesClient;
 use codex_api::MemorySummarizeInput as ApiMemorySummarizeInput;
 use codex_api::MemorySummarizeOutput as ApiMemorySummarizeOutput;
+use codex_api::MessagesApiRequest;
+use codex_api::MessagesClient as ApiMessagesClient;
+use codex_api::MessagesOptions as ApiMessagesOptions;
 use codex_api::Provider as ApiProvider;
 use codex_api::RawMemory as ApiRawMemory;
 use codex_api::RealtimeCallClient as ApiRealtimeCallClient;
@@ -83,7 +89,11 @@ use codex_protocol::protocol::W3cTraceContext;
 use codex_rollout_trace::CompactionTraceContext;
 use codex_rollout_trace::InferenceTraceAttempt;
 use codex_rollout_trace::InferenceTraceContext;
+use codex_tools::create_tools_json_for_chat_completions_api;
+use codex_tools::create_tools_json_for_messages_api;
 use codex_tools::create_tools_json_for_responses_api;
+use codex_tools::flattened_namespaced_tool_names;
+use codex_tools::freeform_tool_names;
 use eventsource_stream::Event;
 use eventsource_stream::EventStreamError;
 use futures::StreamExt;
@@ -117,6 +127,7 @@ use codex_model_provider::create_model_provider;
 #[cfg(test)]
 use codex_model_provider_info::DEFAULT_WEBSOCKET_CONNECT_TIMEOUT_MS;
 use codex_model_provider_info::ModelProviderInfo;
+use codex_model_provider_info::ModelProviderMessagesInfo;
 use codex_model_provider_info::WireApi;
 use codex_protocol::error::CodexErr;
 use codex_protocol::error::Result;
@@ -138,6 +149,8 @@ pub const X_RESPONSESAPI_INCLUDE_TIMING_METRICS_HEADER: &str =
 const RESPONSES_WEBSOCKETS_V2_BETA_HEADER_VALUE: &str = "responses_websockets=2026-02-06";
 const RESPONSES_ENDPOINT: &str = "/responses";
 const RESPONSES_COMPACT_ENDPOINT: &str = "/responses/compact";
+const CHAT_COMPLETIONS_ENDPOINT: &str = "/chat/completions";
+const MESSAGES_ENDPOINT: &str = "/messages";
 const MEMORIES_SUMMARIZE_ENDPOINT: &str = "/memories/trace_summarize";
 #[cfg(test)]
 pub(crate) const WEBSOCKET_CONNECT_TIMEOUT: Duration =
@@ -662,6 +675,7 @@ impl ModelClient {
     /// WebSocket use is controlled by provider capability and session-scoped fallback state.
     pub fn responses_websocket_enabled(&self) -> bool {
         if !self.state.provider.info().supports_websockets
+            || self.state.provider.info().wire_api != WireApi::Responses
             || self.state.disable_websockets.load(Ordering::Relaxed)
             || (*CODEX_RS_SSE_FIXTURE).is_some()
         {
@@ -1252,6 +1266,292 @@ impl ModelClientSession {
         }
     }
 
+    fn build_chat_completions_request(
+        prompt: &Prompt,
+        model_info: &ModelInfo,
+        effort: Option<ReasoningEffortConfig>,
+    ) -> Result<ChatCompletionsApiRequest> {
+        let reasoning_effort = if model_info.supports_reasoning_summaries {
+            effort.or(model_info.default_reasoning_level)
+        } else {
+            None
+        };
+        Ok(ChatCompletionsApiRequest {
+            model: model_info.slug.clone(),
+            instructions: prompt.base_instructions.text.clone(),
+            input: prompt.get_formatted_input(),
+            tools: create_tools_json_for_chat_completions_api(&prompt.tools)?,
+            tool_choice: "auto".to_string(),
+            parallel_tool_calls: prompt.parallel_tool_calls,
+            reasoning_effort,
+            namespaced_tools: flattened_namespaced_tool_names(&prompt.tools),
+            freeform_tools: freeform_tool_names(&prompt.tools),
+        })
+    }
+
+    /// Streams a turn via the Chat Completions API.
+    ///
+    /// Used for providers configured with `wire_api = "chat"`. Tools without a
+    /// Chat Completions equivalent are omitted from the request, and output
+    /// schemas, verbosity, and reasoning summaries are not sent.
+    #[instrument(
+        name = "model_client.stream_chat_completions",
+        level = "info",
+        skip_all,
+        fields(
+            model = %model_info.slug,
+            wire_api = %self.client.state.provider.info().wire_api,
+            transport = "chat_completions_http",
+            http.method = "POST",
+            api.path = "chat/completions",
+            turn.has_metadata_header = turn_metadata_header.is_some()
+        )
+    )]
+    async fn stream_chat_completions(
+        &self,
+        prompt: &Prompt,
+        model_info: &ModelInfo,
+        session_telemetry: &SessionTelemetry,
+        effort: Option<ReasoningEffortConfig>,
+        turn_metadata_header: Option<&str>,
+        inference_trace: &InferenceTraceContext,
+    ) -> Result<ResponseStream> {
+        if let Some(path) = &*CODEX_RS_SSE_FIXTURE {
+            warn!(path, "Streaming from fixture");
+            let stream = codex_api::stream_chat_completions_from_fixture(
+                path,
+                self.client.state.provider.info().stream_idle_timeout(),
+                flattened_namespaced_tool_names(&prompt.tools),
+                freeform_tool_names(&prompt.tools),
+            )
+            .map_err(map_api_error)?;
+            let (stream, _last_request_rx) = map_response_stream(
+                stream,
+                session_telemetry.clone(),
+                InferenceTraceAttempt::disabled(),
+            );
+            return Ok(stream);
+        }
+
+        let auth_manager = self.client.state.provider.auth_manager();
+        let mut auth_recovery = auth_manager
+            .as_ref()
+            .map(AuthManager::unauthorized_recovery);
+        let mut pending_retry = PendingUnauthorizedRetry::default();
+        loop {
+            let client_setup = self.client.current_client_setup().await?;
+            let transport = ReqwestTransport::new(build_reqwest_client());
+            let request_auth_context = AuthRequestTelemetryContext::new(
+                client_setup.auth.as_ref().map(CodexAuth::auth_mode),
+                client_setup.api_auth.as_ref(),
+                pending_retry,
+            );
+            let (request_telemetry, sse_telemetry) = Self::build_streaming_telemetry(
+                session_telemetry,
+                request_auth_context,
+                RequestRouteTelemetry::for_endpoint(CHAT_COMPLETIONS_ENDPOINT),
+                self.client.state.auth_env_telemetry.clone(),
+            );
+            let ApiResponsesOptions {
+                conversation_id,
+                session_source,
+                extra_headers,
+                ..
+            } = self.build_responses_options(turn_metadata_header, Compression::None);
+            let options = ApiChatCompletionsOptions {
+                conversation_id,
+                session_source,
+                extra_headers,
+                compression: Compression::None,
+            };
+
+            let request = Self::build_chat_completions_request(prompt, model_info, effort)?;
+            let inference_trace_attempt = inference_trace.start_attempt();
+            inference_trace_attempt.record_started(&request);
+            let client = ApiChatCompletionsClient::new(
+                transport,
+                client_setup.api_provider,
+                client_setup.api_auth,
+            )
+            .with_telemetry(Some(request_telemetry), Some(sse_telemetry));
+            let stream_result = client.stream_request(request, options).await;
+
+            match stream_result {
+                Ok(stream) => {
+                    let (stream, _) = map_response_stream(
+                        stream,
+                        session_telemetry.clone(),
+                        inference_trace_attempt,
+                    );
+                    return Ok(stream);
+                }
+                Err(ApiError::Transport(
+                    unauthorized_transport @ TransportError::Http { status, .. },
+                )) if status == StatusCode::UNAUTHORIZED => {
+                    inference_trace_attempt.record_failed(&unauthorized_transport);
+                    pending_retry = PendingUnauthorizedRetry::from_recovery(
+                        handle_unauthorized(
+                            unauthorized_transport,
+                            &mut auth_recovery,
+                            session_telemetry,
+                        )
+                        .await?,
+                    );
+                    continue;
+                }
+                Err(err) => {
+                    let err = map_api_error(err);
+                    inference_trace_attempt.record_failed(&err);
+                    return Err(err);
+                }
+            }
+        }
+    }
+
+    fn build_messages_request(
+        prompt: &Prompt,
+        model_info: &ModelInfo,
+        messages_info: &ModelProviderMessagesInfo,
+    ) -> Result<MessagesApiRequest> {
+        Ok(MessagesApiRequest {
+            model: model_info.slug.clone(),
+            instructions: prompt.base_instructions.text.clone(),
+            input: prompt.get_formatted_input(),
+            tools: create_tools_json_for_messages_api(&prompt.tools)?,
+            parallel_tool_calls: prompt.parallel_tool_calls,
+            max_tokens: messages_info.max_tokens(),
+            thinking_budget_tokens: messages_info.thinking_budget_tokens,
+            prompt_caching: messages_info.prompt_caching(),
+            namespaced_tools: flattened_namespaced_tool_names(&prompt.tools),
+            freeform_tools: freeform_tool_names(&prompt.tools),
+        })
+    }
+
+    /// Streams a turn via the Messages API.
+    ///
+    /// Used for providers configured with `wire_api = "messages"`. Thinking is
+    /// controlled by the provider's `messages.thinking_budget_tokens` rather
+    /// than the reasoning effort, and output schemas and verbosity are not
+    /// sent.
+    #[instrument(
+        name = "model_client.stream_messages_api",
+        level = "info",
+        skip_all,
+        fields(
+            model = %model_info.slug,
+            wire_api = %self.client.state.provider.info().wire_api,
+            transport = "messages_http",
+            http.method = "POST",
+            api.path = "messages",
+            turn.has_metadata_header = turn_metadata_header.is_some()
+        )
+    )]
+    async fn stream_messages_api(
+        &self,
+        prompt: &Prompt,
+        model_info: &ModelInfo,
+        session_telemetry: &SessionTelemetry,
+        turn_metadata_header: Option<&str>,
+        inference_trace: &InferenceTraceContext,
+    ) -> Result<ResponseStream> {
+        if let Some(path) = &*CODEX_RS_SSE_FIXTURE {
+            warn!(path, "Streaming from fixture");
+            let stream = codex_api::stream_messages_from_fixture(
+                path,
+                self.client.state.provider.info().stream_idle_timeout(),
+                flattened_namespaced_tool_names(&prompt.tools),
+                freeform_tool_names(&prompt.tools),
+            )
+            .map_err(map_api_error)?;
+            let (stream, _last_request_rx) = map_response_stream(
+                stream,
+                session_telemetry.clone(),
+                InferenceTraceAttempt::disabled(),
+            );
+            return Ok(stream);
+        }
+
+        let messages_info = self
+            .client
+            .state
+            .provider
+            .info()
+            .messages
+            .clone()
+            .unwrap_or_default();
+        let auth_manager = self.client.state.provider.auth_manager();
+        let mut auth_recovery = auth_manager
+            .as_ref()
+            .map(AuthManager::unauthorized_recovery);
+        let mut pending_retry = PendingUnauthorizedRetry::default();
+        loop {
+            let client_setup = self.client.current_client_setup().await?;
+            let transport = ReqwestTransport::new(build_reqwest_client());
+            let request_auth_context = AuthRequestTelemetryContext::new(
+                client_setup.auth.as_ref().map(CodexAuth::auth_mode),
+                client_setup.api_auth.as_ref(),
+                pending_retry,
+            );
+            let (request_telemetry, sse_telemetry) = Self::build_streaming_telemetry(
+                session_telemetry,
+                request_auth_context,
+                RequestRouteTelemetry::for_endpoint(MESSAGES_ENDPOINT),
+                self.client.state.auth_env_telemetry.clone(),
+            );
+            let ApiResponsesOptions {
+                conversation_id,
+                session_source,
+                extra_headers,
+                ..
+            } = self.build_responses_options(turn_metadata_header, Compression::None);
+            let options = ApiMessagesOptions {
+                conversation_id,
+                session_source,
+                extra_headers,
+                anthropic_version: messages_info.anthropic_version().to_string(),
+                anthropic_beta: messages_info.anthropic_beta.clone(),
+            };
+
+            let request = Self::build_messages_request(prompt, model_info, &messages_info)?;
+            let inference_trace_attempt = inference_trace.start_attempt();
+            inference_trace_attempt.record_started(&request);
+            let client =
+                ApiMessagesClient::new(transport, client_setup.api_provider, client_setup.api_auth)
+                    .with_telemetry(Some(request_telemetry), Some(sse_telemetry));
+            let stream_result = client.stream_request(request, options).await;
+
+            match stream_result {
+                Ok(stream) => {
+                    let (stream, _) = map_response_stream(
+                        stream,
+                        session_telemetry.clone(),
+                        inference_trace_attempt,
+                    );
+                    return Ok(stream);
+                }
+                Err(ApiError::Transport(
+                    unauthorized_transport @ TransportError::Http { status, .. },
+                )) if status == StatusCode::UNAUTHORIZED => {
+                    inference_trace_attempt.record_failed(&unauthorized_transport);
+                    pending_retry = PendingUnauthorizedRetry::from_recovery(
+                        handle_unauthorized(
+                            unauthorized_transport,
+                            &mut auth_recovery,
+                            session_telemetry,
+                        )
+                        .await?,
+                    );
+                    continue;
+                }
+                Err(err) => {
+                    let err = map_api_error(err);
+                    inference_trace_attempt.record_failed(&err);
+                    return Err(err);
+                }
+            }
+        }
+    }
+
     /// Streams a turn via the Responses API over WebSocket transport.
     #[allow(clippy::too_many_arguments)]
     #[instrument(
@@ -1532,6 +1832,27 @@ impl ModelClientSession {
                 )
                 .await
             }
+            WireApi::Chat => {
+                self.stream_chat_completions(
+                    prompt,
+                    model_info,
+                    session_telemetry,
+                    effort,
+                    turn_metadata_header,
+                    inference_trace,
+                )
+                .await
+            }
+            WireApi::Messages => {
+                self.stream_messages_api(
+                    prompt,
+                    model_info,
+                    session_telemetry,
+                    turn_metadata_header,
+                    inference_trace,
+                )
+                .await
+            }
         }
     }
 
@@ -725,7 +725,12 @@ async fn maybe_auto_review_mcp_request_user_input(
         ReviewDecision::Approved
         | ReviewDecision::ApprovedExecpolicyAmendment { .. }
         | ReviewDecision::NetworkPolicyAmendment { .. } => MCP_TOOL_APPROVAL_ACCEPT.to_string(),
-        ReviewDecision::Denied | ReviewDecision::TimedOut | ReviewDecision::Abort => {
+        // Partial and edited approvals only apply to patches.
+        ReviewDecision::Denied
+        | ReviewDecision::TimedOut
+        | ReviewDecision::Abort
+        | ReviewDecision::ApprovedPartialPatch { .. }
+        | ReviewDecision::ApprovedEditedPatch { .. } => {
             MCP_TOOL_APPROVAL_DECLINE_SYNTHETIC.to_string()
         }
     };
@@ -4,6 +4,8 @@ use std::time::Instant;
 use crate::Prompt;
 use crate::client::ModelClientSession;
 use crate::client_common::ResponseEvent;
+use crate::hook_runtime::record_additional_contexts;
+use crate::hook_runtime::run_pre_compact_hooks;
 #[cfg(test)]
 use crate::session::PreviousTurnSettings;
 use crate::session::session::Session;
@@ -132,6 +134,7 @@ async fn run_compact_task_inner(
         phase,
     )
     .await;
+    let pre_compact_contexts = run_pre_compact_hooks(&sess, &turn_context, trigger).await;
     let result = run_compact_task_inner_impl(
         Arc::clone(&sess),
         Arc::clone(&turn_context),
@@ -139,6 +142,9 @@ async fn run_compact_task_inner(
         initial_context_injection,
     )
     .await;
+    if result.is_ok() {
+        record_additional_contexts(&sess, &turn_context, pre_compact_contexts).await;
+    }
     attempt
         .track(
             sess.as_ref(),
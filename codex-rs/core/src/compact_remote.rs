@@ -10,6 +10,8 @@ use crate::context_manager::ContextManager;
 use crate::context_manager::TotalTokenUsageBreakdown;
 use crate::context_manager::estimate_response_item_model_visible_bytes;
 use crate::context_manager::is_codex_generated_item;
+use crate::hook_runtime::record_additional_contexts;
+use crate::hook_runtime::run_pre_compact_hooks;
 use crate::session::session::Session;
 use crate::session::turn::built_tools;
 use crate::session::turn_context::TurnContext;
@@ -91,6 +93,7 @@ async fn run_remote_compact_task_inner(
         phase,
     )
     .await;
+    let pre_compact_contexts = run_pre_compact_hooks(sess, turn_context, trigger).await;
     let result =
         run_remote_compact_task_inner_impl(sess, turn_context, initial_context_injection).await;
     attempt
@@ -107,6 +110,7 @@ async fn run_remote_compact_task_inner(
         sess.send_event(turn_context, event).await;
         return Err(err);
     }
+    record_additional_contexts(sess, turn_context, pre_compact_contexts).await;
     Ok(())
 }
 
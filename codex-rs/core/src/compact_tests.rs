@@ -200,6 +200,7 @@ fn should_use_remote_compact_task_for_azure_provider() {
         auth: None,
         aws: None,
         wire_api: WireApi::Responses,
+        messages: None,
         query_params: None,
         http_headers: None,
         env_http_headers: None,
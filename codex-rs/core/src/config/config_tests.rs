@@ -43,6 +43,8 @@ use codex_config::types::Notice;
 use codex_config::types::NotificationCondition;
 use codex_config::types::NotificationMethod;
 use codex_config::types::Notifications;
+use codex_config::types::RedactionConfig;
+use codex_config::types::RedactionToml;
 use codex_config::types::SandboxWorkspaceWrite;
 use codex_config::types::SkillsConfig;
 use codex_config::types::ToolSuggestDiscoverableType;
@@ -65,6 +67,7 @@ use codex_protocol::permissions::NetworkSandboxPolicy;
 use codex_protocol::protocol::ReadOnlyAccess;
 use codex_protocol::protocol::RealtimeVoice;
 use codex_protocol::protocol::SandboxPolicy;
+use codex_secrets::CommandBackendConfig;
 use serde::Deserialize;
 use tempfile::tempdir;
 
@@ -292,6 +295,85 @@ consolidation_model = "gpt-5.2"
     );
 }
 
+#[tokio::test]
+async fn redaction_config_applies_defaults_and_rejects_invalid_patterns() {
+    let cfg = toml::from_str::<ConfigToml>(
+        r#"
+[redaction]
+patterns = ["acme_[0-9a-f]{16}"]
+high_entropy_tokens = true
+"#,
+    )
+    .expect("TOML deserialization should succeed");
+    assert_eq!(
+        Some(RedactionToml {
+            patterns: vec!["acme_[0-9a-f]{16}".to_string()],
+            high_entropy_tokens: Some(true),
+            known_values: None,
+        }),
+        cfg.redaction
+    );
+
+    let config = Config::load_from_base_config_with_overrides(
+        cfg,
+        ConfigOverrides::default(),
+        tempdir().expect("tempdir").abs(),
+    )
+    .await
+    .expect("load config from redaction settings");
+    assert_eq!(
+        config.redaction,
+        RedactionConfig {
+            patterns: vec!["acme_[0-9a-f]{16}".to_string()],
+            high_entropy_tokens: true,
+            known_values: true,
+        }
+    );
+
+    let invalid = toml::from_str::<ConfigToml>("[redaction]\npatterns = [\"(unclosed\"]\n")
+        .expect("TOML deserialization should succeed");
+    let err = Config::load_from_base_config_with_overrides(
+        invalid,
+        ConfigOverrides::default(),
+        tempdir().expect("tempdir").abs(),
+    )
+    .await
+    .expect_err("invalid redaction pattern should be rejected");
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    assert!(err.to_string().contains("(unclosed"));
+}
+
+#[tokio::test]
+async fn secrets_table_selects_command_backend() {
+    let cfg = toml::from_str::<ConfigToml>(
+        r#"
+[secrets]
+backend = "command"
+program = "vault-codex"
+args = ["--mount", "codex"]
+timeout_ms = 2000
+"#,
+    )
+    .expect("TOML deserialization should succeed");
+
+    let config = Config::load_from_base_config_with_overrides(
+        cfg,
+        ConfigOverrides::default(),
+        tempdir().expect("tempdir").abs(),
+    )
+    .await
+    .expect("load config from secrets settings");
+    assert_eq!(
+        config.secrets_backend,
+        SecretsBackendKind::Command(CommandBackendConfig {
+            program: PathBuf::from("vault-codex"),
+            args: vec!["--mount".to_string(), "codex".to_string()],
+            timeout_ms: Some(2000),
+            cache_ttl_secs: None,
+        })
+    );
+}
+
 #[test]
 fn parses_bundled_skills_config() {
     let cfg: ConfigToml = toml::from_str(
@@ -604,7 +686,12 @@ allow_upstream_proxy = false
                         }),
                         unix_sockets: None,
                         allow_local_binding: None,
+                        mitm: None,
+                        rules: None,
+                        credentials: None,
+                        record_traffic: None,
                     }),
+                    resources: None,
                 },
             )]),
         }
@@ -638,6 +725,7 @@ async fn permissions_profiles_network_populates_runtime_network_proxy_spec() ->
                             enable_socks5: Some(false),
                             ..Default::default()
                         }),
+                        resources: None,
                     },
                 )]),
             }),
@@ -691,6 +779,7 @@ async fn permissions_profiles_network_disabled_by_default_does_not_start_proxy()
                             }),
                             ..Default::default()
                         }),
+                        resources: None,
                     },
                 )]),
             }),
@@ -738,6 +827,7 @@ async fn default_permissions_profile_populates_runtime_sandbox_policy() -> std::
                         ]),
                     }),
                     network: None,
+                    resources: None,
                 },
             )]),
         }),
@@ -862,6 +952,7 @@ async fn permission_profile_override_preserves_configured_network_proxy() -> std
                             }),
                             ..Default::default()
                         }),
+                        resources: None,
                     },
                 )]),
             }),
@@ -911,6 +1002,7 @@ async fn project_root_glob_none_compiles_to_filesystem_pattern_entry() -> std::i
                             )]),
                         }),
                         network: None,
+                        resources: None,
                     },
                 )]),
             }),
@@ -983,6 +1075,7 @@ async fn permissions_profiles_require_default_permissions() -> std::io::Result<(
                             )]),
                         }),
                         network: None,
+                        resources: None,
                     },
                 )]),
             }),
@@ -1027,6 +1120,7 @@ async fn permissions_profiles_reject_writes_outside_workspace_root() -> std::io:
                             )]),
                         }),
                         network: None,
+                        resources: None,
                     },
                 )]),
             }),
@@ -1074,6 +1168,7 @@ async fn permissions_profiles_reject_nested_entries_for_non_project_roots() -> s
                             )]),
                         }),
                         network: None,
+                        resources: None,
                     },
                 )]),
             }),
@@ -1131,6 +1226,7 @@ async fn permissions_profiles_allow_unknown_special_paths() -> std::io::Result<(
             )]),
         }),
         network: None,
+        resources: None,
     })
     .await?;
 
@@ -1181,6 +1277,7 @@ async fn permissions_profiles_allow_unknown_special_paths_with_nested_entries()
             )]),
         }),
         network: None,
+        resources: None,
     })
     .await?;
 
@@ -1208,6 +1305,7 @@ async fn permissions_profiles_allow_missing_filesystem_with_warning() -> std::io
     let config = load_workspace_permission_profile(PermissionProfileToml {
         filesystem: None,
         network: None,
+        resources: None,
     })
     .await?;
 
@@ -1243,6 +1341,7 @@ async fn permissions_profiles_allow_empty_filesystem_with_warning() -> std::io::
             entries: BTreeMap::new(),
         }),
         network: None,
+        resources: None,
     })
     .await?;
 
@@ -1284,6 +1383,7 @@ async fn permissions_profiles_reject_project_root_parent_traversal() -> std::io:
                             )]),
                         }),
                         network: None,
+                        resources: None,
                     },
                 )]),
             }),
@@ -1330,6 +1430,7 @@ async fn permissions_profiles_allow_network_enablement() -> std::io::Result<()>
                             enabled: Some(true),
                             ..Default::default()
                         }),
+                        resources: None,
                     },
                 )]),
             }),
@@ -5155,6 +5256,7 @@ model_verbosity = "high"
         base_url: Some("https://api.openai.com/v1".to_string()),
         env_key: Some("OPENAI_API_KEY".to_string()),
         wire_api: WireApi::Responses,
+        messages: None,
         env_key_instructions: None,
         experimental_bearer_token: None,
         auth: None,
@@ -5235,6 +5337,7 @@ async fn test_precedence_fixture_with_o3_profile() -> std::io::Result<()> {
                 ),
                 network_sandbox_policy: NetworkSandboxPolicy::Restricted,
                 network: None,
+                resource_limits: ResourceLimits::default(),
                 allow_login_shell: true,
                 shell_environment_policy: ShellEnvironmentPolicy::default(),
                 windows_sandbox_mode: None,
@@ -5261,6 +5364,8 @@ async fn test_precedence_fixture_with_o3_profile() -> std::io::Result<()> {
             agent_max_depth: DEFAULT_AGENT_MAX_DEPTH,
             agent_roles: BTreeMap::new(),
             memories: MemoriesConfig::default(),
+            redaction: RedactionConfig::default(),
+            secrets_backend: SecretsBackendKind::default(),
             agent_job_max_runtime_seconds: DEFAULT_AGENT_JOB_MAX_RUNTIME_SECONDS,
             agent_interrupt_message_enabled: true,
             codex_home: fixture.codex_home(),
@@ -5433,6 +5538,7 @@ async fn test_precedence_fixture_with_gpt3_profile() -> std::io::Result<()> {
             ),
             network_sandbox_policy: NetworkSandboxPolicy::Restricted,
             network: None,
+            resource_limits: ResourceLimits::default(),
             allow_login_shell: true,
             shell_environment_policy: ShellEnvironmentPolicy::default(),
             windows_sandbox_mode: None,
@@ -5459,6 +5565,8 @@ async fn test_precedence_fixture_with_gpt3_profile() -> std::io::Result<()> {
         agent_max_depth: DEFAULT_AGENT_MAX_DEPTH,
         agent_roles: BTreeMap::new(),
         memories: MemoriesConfig::default(),
+        redaction: RedactionConfig::default(),
+        secrets_backend: SecretsBackendKind::default(),
         agent_job_max_runtime_seconds: DEFAULT_AGENT_JOB_MAX_RUNTIME_SECONDS,
         agent_interrupt_message_enabled: true,
         codex_home: fixture.codex_home(),
@@ -5585,6 +5693,7 @@ async fn test_precedence_fixture_with_zdr_profile() -> std::io::Result<()> {
             ),
             network_sandbox_policy: NetworkSandboxPolicy::Restricted,
             network: None,
+            resource_limits: ResourceLimits::default(),
             allow_login_shell: true,
             shell_environment_policy: ShellEnvironmentPolicy::default(),
             windows_sandbox_mode: None,
@@ -5611,6 +5720,8 @@ async fn test_precedence_fixture_with_zdr_profile() -> std::io::Result<()> {
         agent_max_depth: DEFAULT_AGENT_MAX_DEPTH,
         agent_roles: BTreeMap::new(),
         memories: MemoriesConfig::default(),
+        redaction: RedactionConfig::default(),
+        secrets_backend: SecretsBackendKind::default(),
         agent_job_max_runtime_seconds: DEFAULT_AGENT_JOB_MAX_RUNTIME_SECONDS,
         agent_interrupt_message_enabled: true,
         codex_home: fixture.codex_home(),
@@ -5722,6 +5833,7 @@ async fn test_precedence_fixture_with_gpt5_profile() -> std::io::Result<()> {
             ),
             network_sandbox_policy: NetworkSandboxPolicy::Restricted,
             network: None,
+            resource_limits: ResourceLimits::default(),
             allow_login_shell: true,
             shell_environment_policy: ShellEnvironmentPolicy::default(),
             windows_sandbox_mode: None,
@@ -5748,6 +5860,8 @@ async fn test_precedence_fixture_with_gpt5_profile() -> std::io::Result<()> {
         agent_max_depth: DEFAULT_AGENT_MAX_DEPTH,
         agent_roles: BTreeMap::new(),
         memories: MemoriesConfig::default(),
+        redaction: RedactionConfig::default(),
+        secrets_backend: SecretsBackendKind::default(),
         agent_job_max_runtime_seconds: DEFAULT_AGENT_JOB_MAX_RUNTIME_SECONDS,
         agent_interrupt_message_enabled: true,
         codex_home: fixture.codex_home(),
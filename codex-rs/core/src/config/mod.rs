@@ -43,6 +43,7 @@ use codex_config::types::OAuthCredentialsStoreMode;
 use codex_config::types::OtelConfig;
 use codex_config::types::OtelConfigToml;
 use codex_config::types::OtelExporterKind;
+use codex_config::types::RedactionConfig;
 use codex_config::types::ShellEnvironmentPolicy;
 use codex_config::types::ToolSuggestConfig;
 use codex_config::types::ToolSuggestDiscoverable;
@@ -86,8 +87,10 @@ use codex_protocol::permissions::FileSystemSandboxPolicy;
 use codex_protocol::permissions::NetworkSandboxPolicy;
 use codex_protocol::protocol::AskForApproval;
 use codex_protocol::protocol::SandboxPolicy;
+use codex_secrets::SecretsBackendKind;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use codex_utils_absolute_path::AbsolutePathBufGuard;
+use codex_utils_pty::resource_limits::ResourceLimits;
 use serde::Deserialize;
 use std::collections::BTreeMap;
 use std::collections::HashMap;
@@ -99,6 +102,7 @@ use std::sync::Arc;
 use crate::config::permissions::compile_permission_profile;
 use crate::config::permissions::get_readable_roots_required_for_codex_runtime;
 use crate::config::permissions::network_proxy_config_from_profile_network;
+use crate::config::permissions::resource_limits_from_profile;
 use codex_network_proxy::NetworkProxyConfig;
 use toml::Value as TomlValue;
 use toml_edit::DocumentMut;
@@ -200,6 +204,8 @@ pub struct Permissions {
     pub network_sandbox_policy: NetworkSandboxPolicy,
     /// Effective network configuration applied to all spawned processes.
     pub network: Option<NetworkProxySpec>,
+    /// Per-command resource limits from the active permissions profile.
+    pub resource_limits: ResourceLimits,
     /// Whether the model may request a login shell for shell-based tools.
     /// Default to `true`
     ///
@@ -444,6 +450,13 @@ pub struct Config {
     /// Memories subsystem settings.
     pub memories: MemoriesConfig,
 
+    /// Secret redaction applied to tool output before it reaches the model,
+    /// to rollout files, and to log sinks.
+    pub redaction: RedactionConfig,
+
+    /// Where stored secrets live, for `network.credentials` and redaction.
+    pub secrets_backend: SecretsBackendKind,
+
     /// Directory containing all Codex state (defaults to `~/.codex` but can be
     /// overridden by the `CODEX_HOME` environment variable).
     pub codex_home: AbsolutePathBuf,
@@ -1754,6 +1767,17 @@ impl Config {
             Some(PermissionConfigSyntax::Profiles)
         ) || (permission_config_syntax.is_none()
             && has_permission_profiles);
+        let resource_limits = match (
+            profiles_are_active,
+            cfg.permissions.as_ref(),
+            cfg.default_permissions.as_deref(),
+        ) {
+            (true, Some(permissions), Some(default_permissions)) => {
+                let profile = resolve_permission_profile(permissions, default_permissions)?;
+                resource_limits_from_profile(profile.resources.as_ref())?
+            }
+            _ => ResourceLimits::default(),
+        };
         let (
             configured_network_proxy_config,
             sandbox_policy,
@@ -1957,6 +1981,15 @@ impl Config {
 
         let history = cfg.history.unwrap_or_default();
 
+        let redaction: RedactionConfig = cfg.redaction.unwrap_or_default().into();
+        codex_secrets::Redactor::new(&redaction.patterns).map_err(|err| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("invalid [redaction] config: {err:#}"),
+            )
+        })?;
+        let secrets_backend = cfg.secrets.unwrap_or_default();
+
         let agent_max_threads_from_config = cfg.agents.as_ref().and_then(|agents| agents.max_threads);
         if features.enabled(Feature::MultiAgentV2) && agent_max_threads_from_config.is_some() {
             return Err(std::io::Error::new(
@@ -2265,6 +2298,12 @@ impl Config {
                 err
             }
         })?;
+        let network = network
+            .with_credential_secrets(
+                codex_home.to_path_buf(),
+                resolved_cwd.to_path_buf(),
+                secrets_backend.clone(),
+            );
         let network = if has_network_requirements {
             Some(network)
         } else {
@@ -2320,6 +2359,7 @@ impl Config {
                 file_system_sandbox_policy: effective_file_system_sandbox_policy,
                 network_sandbox_policy: effective_network_sandbox_policy,
                 network,
+                resource_limits,
                 allow_login_shell,
                 shell_environment_policy,
                 windows_sandbox_mode,
@@ -2373,6 +2413,8 @@ impl Config {
             agent_max_depth,
             agent_roles,
             memories: cfg.memories.unwrap_or_default().into(),
+            redaction,
+            secrets_backend,
             agent_job_max_runtime_seconds,
             agent_interrupt_message_enabled,
             codex_home,
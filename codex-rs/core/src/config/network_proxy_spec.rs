@@ -1,9 +1,11 @@
 use crate::config_loader::NetworkConstraints;
+use crate::network_proxy_loader::exec_policy_network_request_rules;
 use async_trait::async_trait;
 use codex_execpolicy::Policy;
 use codex_network_proxy::BlockedRequestObserver;
 use codex_network_proxy::ConfigReloader;
 use codex_network_proxy::ConfigState;
+use codex_network_proxy::NetworkCredentialSource;
 use codex_network_proxy::NetworkDecision;
 use codex_network_proxy::NetworkPolicyDecider;
 use codex_network_proxy::NetworkProxy;
@@ -12,12 +14,17 @@ use codex_network_proxy::NetworkProxyConfig;
 use codex_network_proxy::NetworkProxyConstraints;
 use codex_network_proxy::NetworkProxyHandle;
 use codex_network_proxy::NetworkProxyState;
+use codex_network_proxy::SecretsCredentialSource;
 use codex_network_proxy::build_config_state;
 use codex_network_proxy::host_and_port_from_network_addr;
 use codex_network_proxy::normalize_host;
 use codex_network_proxy::validate_policy_against_constraints;
 use codex_protocol::protocol::SandboxPolicy;
+use codex_secrets::SecretsBackendKind;
+use codex_secrets::SecretsManager;
+use codex_secrets::environment_id_from_cwd;
 use std::collections::HashSet;
+use std::path::PathBuf;
 use std::sync::Arc;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -27,6 +34,15 @@ pub struct NetworkProxySpec {
     config: NetworkProxyConfig,
     constraints: NetworkProxyConstraints,
     hard_deny_allowlist_misses: bool,
+    credential_secrets: Option<NetworkCredentialSecrets>,
+}
+
+/// Where the proxy reads `network.credentials` secrets from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct NetworkCredentialSecrets {
+    codex_home: PathBuf,
+    cwd: PathBuf,
+    backend: SecretsBackendKind,
 }
 
 pub struct StartedNetworkProxy {
@@ -117,9 +133,26 @@ impl NetworkProxySpec {
             config,
             constraints,
             hard_deny_allowlist_misses,
+            credential_secrets: None,
         })
     }
 
+    /// Lets the proxy inject `network.credentials` from the secrets stored in `backend`,
+    /// preferring secrets scoped to the environment of `cwd`.
+    pub(crate) fn with_credential_secrets(
+        mut self,
+        codex_home: PathBuf,
+        cwd: PathBuf,
+        backend: SecretsBackendKind,
+    ) -> Self {
+        self.credential_secrets = Some(NetworkCredentialSecrets {
+            codex_home,
+            cwd,
+            backend,
+        });
+        self
+    }
+
     pub async fn start_proxy(
         &self,
         sandbox_policy: &SandboxPolicy,
@@ -144,6 +177,9 @@ impl NetworkProxySpec {
         if let Some(blocked_request_observer) = blocked_request_observer {
             builder = builder.blocked_request_observer_arc(blocked_request_observer);
         }
+        if let Some(credential_source) = self.credential_source() {
+            builder = builder.credential_source_arc(credential_source);
+        }
         let proxy = builder.build().await.map_err(|err| {
             std::io::Error::other(format!("failed to build network proxy: {err}"))
         })?;
@@ -158,11 +194,13 @@ impl NetworkProxySpec {
         &self,
         sandbox_policy: &SandboxPolicy,
     ) -> std::io::Result<Self> {
-        Self::from_config_and_constraints(
+        let mut spec = Self::from_config_and_constraints(
             self.base_config.clone(),
             self.requirements.clone(),
             sandbox_policy,
-        )
+        )?;
+        spec.credential_secrets = self.credential_secrets.clone();
+        Ok(spec)
     }
 
     pub(crate) fn with_exec_policy_network_rules(
@@ -194,6 +232,21 @@ impl NetworkProxySpec {
             })
     }
 
+    fn credential_source(&self) -> Option<Arc<dyn NetworkCredentialSource>> {
+        let secrets = self.credential_secrets.as_ref()?;
+        if self.config.network.credentials.is_empty() {
+            return None;
+        }
+        let manager = SecretsManager::new(secrets.codex_home.clone(), secrets.backend.clone());
+        match SecretsCredentialSource::new(manager, Some(environment_id_from_cwd(&secrets.cwd))) {
+            Ok(source) => Some(Arc::new(source)),
+            Err(err) => {
+                tracing::warn!("failed to set up network credential injection: {err}");
+                None
+            }
+        }
+    }
+
     fn build_state_with_audit_metadata(
         &self,
         audit_metadata: NetworkProxyAuditMetadata,
@@ -359,6 +412,9 @@ fn apply_exec_policy_network_rules(config: &mut NetworkProxyConfig, exec_policy:
     let (allowed_domains, denied_domains) = exec_policy.compiled_network_domains();
     upsert_network_domains(config, allowed_domains, /*allow*/ true);
     upsert_network_domains(config, denied_domains, /*allow*/ false);
+    for rule in exec_policy_network_request_rules(exec_policy) {
+        config.network.add_request_rule(rule);
+    }
 }
 
 fn upsert_network_domains(config: &mut NetworkProxyConfig, hosts: Vec<String>, allow: bool) {
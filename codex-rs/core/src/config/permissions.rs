@@ -3,12 +3,14 @@ use std::io;
 use std::path::Component;
 use std::path::Path;
 use std::path::PathBuf;
+use std::time::Duration;
 
 use codex_config::permissions_toml::FilesystemPermissionToml;
 use codex_config::permissions_toml::FilesystemPermissionsToml;
 use codex_config::permissions_toml::NetworkToml;
 use codex_config::permissions_toml::PermissionProfileToml;
 use codex_config::permissions_toml::PermissionsToml;
+use codex_config::permissions_toml::ResourceLimitsToml;
 use codex_network_proxy::NetworkProxyConfig;
 #[cfg(test)]
 use codex_network_proxy::NetworkUnixSocketPermission as ProxyNetworkUnixSocketPermission;
@@ -19,6 +21,7 @@ use codex_protocol::permissions::FileSystemSandboxPolicy;
 use codex_protocol::permissions::FileSystemSpecialPath;
 use codex_protocol::permissions::NetworkSandboxPolicy;
 use codex_utils_absolute_path::AbsolutePathBuf;
+use codex_utils_pty::resource_limits::ResourceLimits;
 
 pub(crate) fn network_proxy_config_from_profile_network(
     network: Option<&NetworkToml>,
@@ -29,6 +32,27 @@ pub(crate) fn network_proxy_config_from_profile_network(
     )
 }
 
+pub(crate) fn resource_limits_from_profile(
+    resources: Option<&ResourceLimitsToml>,
+) -> io::Result<ResourceLimits> {
+    let Some(resources) = resources else {
+        return Ok(ResourceLimits::default());
+    };
+    Ok(ResourceLimits {
+        max_rss_bytes: validate_resource_limit("max_rss_bytes", resources.max_rss_bytes)?,
+        cpu_seconds: validate_resource_limit("cpu_seconds", resources.cpu_seconds)?,
+        max_pids: validate_resource_limit("max_pids", resources.max_pids)?,
+        max_open_files: validate_resource_limit("max_open_files", resources.max_open_files)?,
+        max_output_bytes: validate_resource_limit("max_output_bytes", resources.max_output_bytes)?,
+        wall_time: validate_resource_limit("wall_time_seconds", resources.wall_time_seconds)?
+            .map(Duration::from_secs),
+        cgroup_parent: resources
+            .cgroup_parent
+            .as_ref()
+            .map(AbsolutePathBuf::to_path_buf),
+    })
+}
+
 pub(crate) fn resolve_permission_profile<'a>(
     permissions: &'a PermissionsToml,
     profile_name: &str,
@@ -382,6 +406,16 @@ fn validate_glob_scan_max_depth(max_depth: Option<usize>) -> io::Result<Option<u
     }
 }
 
+fn validate_resource_limit(name: &str, limit: Option<u64>) -> io::Result<Option<u64>> {
+    match limit {
+        Some(0) => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("resources.{name} must be at least 1"),
+        )),
+        _ => Ok(limit),
+    }
+}
+
 fn contains_glob_chars(path: &str) -> bool {
     path.chars().any(|ch| matches!(ch, '*' | '?' | '[' | ']'))
 }
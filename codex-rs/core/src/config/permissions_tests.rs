@@ -59,6 +59,7 @@ async fn restricted_read_implicitly_allows_helper_executables() -> std::io::Resu
                             entries: BTreeMap::new(),
                         }),
                         network: None,
+                        resources: None,
                     },
                 )]),
             }),
@@ -224,6 +225,79 @@ fn network_toml_overlays_unix_socket_permissions_by_path() {
     );
 }
 
+#[test]
+fn network_toml_appends_request_rules_from_toml() {
+    let network: NetworkToml = toml::from_str(
+        r#"
+rules = [
+  { scheme = "https", host = "github.com", path = "/*/archive/*", methods = ["GET"], decision = "allow" },
+  { host = "api.github.com", methods = ["POST"], decision = "deny" },
+]
+"#,
+    )
+    .expect("network rules should deserialize");
+    let mut config = NetworkProxyConfig::default();
+
+    network.apply_to_network_proxy_config(&mut config);
+    network.apply_to_network_proxy_config(&mut config);
+
+    assert_eq!(
+        config.network.rules,
+        vec![
+            codex_network_proxy::NetworkRequestRule {
+                scheme: Some(codex_network_proxy::NetworkRequestScheme::Https),
+                host: "github.com".to_string(),
+                path: Some("/*/archive/*".to_string()),
+                methods: vec!["GET".to_string()],
+                decision: codex_network_proxy::NetworkRequestRuleDecision::Allow,
+            },
+            codex_network_proxy::NetworkRequestRule {
+                scheme: None,
+                host: "api.github.com".to_string(),
+                path: None,
+                methods: vec!["POST".to_string()],
+                decision: codex_network_proxy::NetworkRequestRuleDecision::Deny,
+            },
+        ]
+    );
+}
+
+#[test]
+fn network_toml_enables_mitm_credential_injection() {
+    let network: NetworkToml = toml::from_str(
+        r#"
+mitm = true
+credentials = [
+  { host = "registry.npmjs.org", header = "authorization", secret = "NPM_TOKEN", prefix = "Bearer " },
+]
+"#,
+    )
+    .expect("network credentials should deserialize");
+
+    let config = network.to_network_proxy_config();
+
+    assert!(config.network.mitm);
+    assert_eq!(
+        config.network.credentials,
+        vec![codex_network_proxy::NetworkCredentialInjection {
+            host: "registry.npmjs.org".to_string(),
+            header: "authorization".to_string(),
+            secret: "NPM_TOKEN".to_string(),
+            prefix: Some("Bearer ".to_string()),
+        }]
+    );
+}
+
+#[test]
+fn network_toml_enables_traffic_recording() {
+    let network: NetworkToml =
+        toml::from_str("record_traffic = true").expect("record_traffic should deserialize");
+
+    let config = network.to_network_proxy_config();
+
+    assert!(config.network.record_traffic);
+}
+
 #[test]
 fn read_write_glob_warnings_skip_supported_deny_read_globs_and_trailing_subpaths() {
     let filesystem = FilesystemPermissionsToml {
@@ -319,6 +393,7 @@ fn read_write_trailing_glob_suffix_compiles_as_subpath() -> std::io::Result<()>
                         )]),
                     }),
                     network: None,
+                    resources: None,
                 },
             )]),
         },
@@ -352,3 +427,49 @@ fn read_write_glob_patterns_still_reject_non_subpath_globs() {
         "{err}"
     );
 }
+
+#[test]
+fn resource_limits_compile_from_profile_toml() {
+    let cgroup_parent = if cfg!(windows) {
+        r"C:\cgroup\codex"
+    } else {
+        "/sys/fs/cgroup/codex"
+    };
+    let profile: PermissionProfileToml = toml::from_str(&format!(
+        r#"
+[resources]
+max_rss_bytes = 1073741824
+cpu_seconds = 600
+max_pids = 512
+max_open_files = 1024
+max_output_bytes = 10485760
+wall_time_seconds = 1800
+cgroup_parent = '{cgroup_parent}'
+"#
+    ))
+    .expect("resources should deserialize");
+
+    assert_eq!(
+        resource_limits_from_profile(profile.resources.as_ref()).expect("valid limits"),
+        ResourceLimits {
+            max_rss_bytes: Some(1_073_741_824),
+            cpu_seconds: Some(600),
+            max_pids: Some(512),
+            max_open_files: Some(1024),
+            max_output_bytes: Some(10_485_760),
+            wall_time: Some(Duration::from_secs(1800)),
+            cgroup_parent: Some(PathBuf::from(cgroup_parent)),
+        }
+    );
+    assert_eq!(
+        resource_limits_from_profile(/*resources*/ None).expect("no limits"),
+        ResourceLimits::default()
+    );
+
+    let err = resource_limits_from_profile(Some(&ResourceLimitsToml {
+        max_pids: Some(0),
+        ..Default::default()
+    }))
+    .expect_err("a zero process budget would make every command fail");
+    assert_eq!(err.to_string(), "resources.max_pids must be at least 1");
+}
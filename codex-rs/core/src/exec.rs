@@ -7,6 +7,9 @@ use std::io;
 use std::path::Path;
 use std::path::PathBuf;
 use std::process::ExitStatus;
+use std::sync::Arc;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
 use std::time::Duration;
 use std::time::Instant;
 
@@ -15,6 +18,7 @@ use tokio::io::AsyncRead;
 use tokio::io::AsyncReadExt;
 use tokio::io::BufReader;
 use tokio::process::Child;
+use tokio::sync::Notify;
 use tokio_util::sync::CancellationToken;
 
 use crate::sandboxing::ExecOptions;
@@ -26,6 +30,7 @@ use crate::spawn::spawn_child_async;
 use codex_network_proxy::NetworkProxy;
 use codex_protocol::config_types::WindowsSandboxLevel;
 use codex_protocol::error::CodexErr;
+use codex_protocol::error::ResourceLimitKind;
 use codex_protocol::error::Result;
 use codex_protocol::error::SandboxErr;
 use codex_protocol::exec_output::ExecToolCallOutput;
@@ -46,12 +51,16 @@ use codex_sandboxing::SandboxablePreference;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use codex_utils_pty::DEFAULT_OUTPUT_BYTES_CAP;
 use codex_utils_pty::process_group::kill_child_process_group;
+use codex_utils_pty::resource_limits::CgroupLimit;
+use codex_utils_pty::resource_limits::ResourceLimiter;
+use codex_utils_pty::resource_limits::ResourceLimits;
 
 pub const DEFAULT_EXEC_COMMAND_TIMEOUT_MS: u64 = 10_000;
 
 // Hardcode these since it does not seem worth including the libc crate just
 // for these.
 const SIGKILL_CODE: i32 = 9;
+const SIGXCPU_CODE: i32 = 24;
 const TIMEOUT_CODE: i32 = 64;
 const EXIT_CODE_SIGNAL_BASE: i32 = 128; // conventional shell: 128 + signal
 const EXEC_TIMEOUT_EXIT_CODE: i32 = 124; // conventional timeout exit code
@@ -298,6 +307,7 @@ pub fn build_exec_request(
     let options = ExecOptions {
         expiration,
         capture_policy,
+        resource_limits: ResourceLimits::default(),
     };
     let mut exec_req = manager
         .transform(SandboxTransformRequest {
@@ -311,6 +321,7 @@ pub fn build_exec_request(
             sandbox_policy_cwd: sandbox_cwd,
             codex_linux_sandbox_exe: codex_linux_sandbox_exe.as_deref(),
             use_legacy_landlock,
+            overlay_workspace: None,
             windows_sandbox_level,
             windows_sandbox_private_desktop,
         })
@@ -370,6 +381,7 @@ pub(crate) async fn execute_exec_request(
         network_sandbox_policy,
         windows_sandbox_filesystem_overrides,
         arg0,
+        resource_limits,
     } = exec_request;
 
     let params = ExecParams {
@@ -390,6 +402,7 @@ pub(crate) async fn execute_exec_request(
     let raw_output_result = get_raw_output_result(
         params,
         network_sandbox_policy,
+        &resource_limits,
         stdout_stream,
         after_spawn,
         sandbox,
@@ -401,9 +414,11 @@ pub(crate) async fn execute_exec_request(
     finalize_exec_result(raw_output_result, sandbox, duration)
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn get_raw_output_result(
     params: ExecParams,
     network_sandbox_policy: NetworkSandboxPolicy,
+    resource_limits: &ResourceLimits,
     stdout_stream: Option<StdoutStream>,
     after_spawn: Option<Box<dyn FnOnce() + Send>>,
     #[cfg_attr(not(windows), allow(unused_variables))] sandbox: SandboxType,
@@ -418,7 +433,14 @@ async fn get_raw_output_result(
             .await;
     }
 
-    exec(params, network_sandbox_policy, stdout_stream, after_spawn).await
+    exec(
+        params,
+        network_sandbox_policy,
+        resource_limits,
+        stdout_stream,
+        after_spawn,
+    )
+    .await
 }
 
 #[cfg(target_os = "windows")]
@@ -639,6 +661,7 @@ async fn exec_windows_sandbox(
         stderr,
         aggregated_output,
         timed_out: capture.timed_out,
+        resource_limit: None,
     })
 }
 
@@ -649,6 +672,17 @@ fn finalize_exec_result(
 ) -> Result<ExecToolCallOutput> {
     match raw_output_result {
         Ok(raw_output) => {
+            if let Some(limit) = raw_output.resource_limit {
+                let exit_code = signal_exit_code(raw_output.exit_status);
+                let exec_output = exec_tool_call_output(
+                    raw_output, exit_code, /*timed_out*/ false, duration,
+                );
+                return Err(CodexErr::Sandbox(SandboxErr::ResourceLimitExceeded {
+                    limit,
+                    output: Box::new(exec_output),
+                }));
+            }
+
             #[allow(unused_mut)]
             let mut timed_out = raw_output.timed_out;
 
@@ -668,17 +702,7 @@ fn finalize_exec_result(
                 exit_code = EXEC_TIMEOUT_EXIT_CODE;
             }
 
-            let stdout = raw_output.stdout.from_utf8_lossy();
-            let stderr = raw_output.stderr.from_utf8_lossy();
-            let aggregated_output = raw_output.aggregated_output.from_utf8_lossy();
-            let exec_output = ExecToolCallOutput {
-                exit_code,
-                stdout,
-                stderr,
-                aggregated_output,
-                duration,
-                timed_out,
-            };
+            let exec_output = exec_tool_call_output(raw_output, exit_code, timed_out, duration);
 
             if timed_out {
                 return Err(CodexErr::Sandbox(SandboxErr::Timeout {
@@ -702,6 +726,56 @@ fn finalize_exec_result(
     }
 }
 
+fn exec_tool_call_output(
+    raw_output: RawExecToolCallOutput,
+    exit_code: i32,
+    timed_out: bool,
+    duration: Duration,
+) -> ExecToolCallOutput {
+    ExecToolCallOutput {
+        exit_code,
+        stdout: raw_output.stdout.from_utf8_lossy(),
+        stderr: raw_output.stderr.from_utf8_lossy(),
+        aggregated_output: raw_output.aggregated_output.from_utf8_lossy(),
+        duration,
+        timed_out,
+    }
+}
+
+/// Exit code for a command that may have died from a signal, using the shell
+/// convention of 128 + signal.
+fn signal_exit_code(exit_status: ExitStatus) -> i32 {
+    #[cfg(unix)]
+    if let Some(signal) = exit_status.signal() {
+        return EXIT_CODE_SIGNAL_BASE + signal;
+    }
+    exit_status.code().unwrap_or(-1)
+}
+
+/// Works out which kernel-enforced limit, if any, killed a failed command.
+/// Wall time and output size are enforced in [`consume_output`] instead.
+fn exceeded_kernel_limit(
+    resource_limits: &ResourceLimits,
+    limiter: &ResourceLimiter,
+    exit_status: ExitStatus,
+) -> Option<ResourceLimitKind> {
+    if exit_status.success() {
+        return None;
+    }
+    if let Some(limit) = limiter.exceeded_limit() {
+        return Some(match limit {
+            CgroupLimit::Memory => ResourceLimitKind::Memory,
+            CgroupLimit::Pids => ResourceLimitKind::Processes,
+        });
+    }
+    if resource_limits.cpu_seconds.is_some()
+        && signal_exit_code(exit_status) == EXIT_CODE_SIGNAL_BASE + SIGXCPU_CODE
+    {
+        return Some(ResourceLimitKind::CpuTime);
+    }
+    None
+}
+
 /// We don't have a fully deterministic way to tell if our command failed
 /// because of the sandbox - a command in the user's zshrc file might hit an
 /// error, but the command itself might fail or succeed for other reasons.
@@ -771,6 +845,33 @@ struct RawExecToolCallOutput {
     pub stderr: StreamOutput<Vec<u8>>,
     pub aggregated_output: StreamOutput<Vec<u8>>,
     pub timed_out: bool,
+    pub resource_limit: Option<ResourceLimitKind>,
+}
+
+/// Shared byte budget for a command's combined stdout and stderr.
+#[derive(Debug)]
+struct OutputLimit {
+    max_bytes: u64,
+    total_bytes: AtomicU64,
+    exceeded: Notify,
+}
+
+impl OutputLimit {
+    fn new(max_bytes: u64) -> Self {
+        Self {
+            max_bytes,
+            total_bytes: AtomicU64::new(0),
+            exceeded: Notify::new(),
+        }
+    }
+
+    fn record(&self, bytes: usize) {
+        let bytes = bytes as u64;
+        let total = self.total_bytes.fetch_add(bytes, Ordering::Relaxed) + bytes;
+        if total > self.max_bytes {
+            self.exceeded.notify_one();
+        }
+    }
 }
 
 #[inline]
@@ -836,12 +937,16 @@ fn aggregate_output(
 /// CODEX_SANDBOX_NETWORK_DISABLED=1 is added to the environment of the spawned
 /// process.
 ///
+/// `resource_limits` are applied to the child before it execs and enforced
+/// while its output is consumed.
+///
 /// Note this command does not apply any sandboxing logic. The caller is
 /// responsible for constructing [ExecParams::command] to include any sandboxing
 /// wrapper args, as appropriate.
 async fn exec(
     params: ExecParams,
     network_sandbox_policy: NetworkSandboxPolicy,
+    resource_limits: &ResourceLimits,
     stdout_stream: Option<StdoutStream>,
     after_spawn: Option<Box<dyn FnOnce() + Send>>,
 ) -> Result<RawExecToolCallOutput> {
@@ -873,6 +978,10 @@ async fn exec(
         ))
     })?;
     let arg0_ref = arg0.as_deref();
+    let limiter = ResourceLimiter::new(resource_limits);
+    if resource_limits.cgroup_parent.is_some() && !limiter.has_cgroup() {
+        tracing::warn!("could not create a cgroup leaf; falling back to rlimits");
+    }
     let child = spawn_child_async(SpawnChildRequest {
         program: PathBuf::from(program),
         args: args.into(),
@@ -885,12 +994,25 @@ async fn exec(
         network: None,
         stdio_policy: StdioPolicy::RedirectForShellTool,
         env,
+        resource_limiter: Some(&limiter),
     })
     .await?;
     if let Some(after_spawn) = after_spawn {
         after_spawn();
     }
-    consume_output(child, expiration, capture_policy, stdout_stream).await
+    let mut raw_output = consume_output(
+        child,
+        expiration,
+        capture_policy,
+        resource_limits,
+        stdout_stream,
+    )
+    .await?;
+    if raw_output.resource_limit.is_none() {
+        raw_output.resource_limit =
+            exceeded_kernel_limit(resource_limits, &limiter, raw_output.exit_status);
+    }
+    Ok(raw_output)
 }
 
 #[cfg_attr(not(target_os = "windows"), allow(dead_code))]
@@ -1235,11 +1357,13 @@ fn has_reopened_writable_descendant(
 }
 
 /// Consumes the output of a child process according to the configured capture
-/// policy.
+/// policy, killing it if it runs past its wall-time limit or writes more than
+/// its output limit.
 async fn consume_output(
     mut child: Child,
     expiration: ExecExpiration,
     capture_policy: ExecCapturePolicy,
+    resource_limits: &ResourceLimits,
     stdout_stream: Option<StdoutStream>,
 ) -> Result<RawExecToolCallOutput> {
     // Both stdout and stderr were configured with `Stdio::piped()`
@@ -1258,17 +1382,22 @@ async fn consume_output(
     })?;
 
     let retained_bytes_cap = capture_policy.retained_bytes_cap();
+    let output_limit = resource_limits
+        .max_output_bytes
+        .map(|max_bytes| Arc::new(OutputLimit::new(max_bytes)));
     let stdout_handle = tokio::spawn(read_output(
         BufReader::new(stdout_reader),
         stdout_stream.clone(),
         /*is_stderr*/ false,
         retained_bytes_cap,
+        output_limit.clone(),
     ));
     let stderr_handle = tokio::spawn(read_output(
         BufReader::new(stderr_reader),
         stdout_stream.clone(),
         /*is_stderr*/ true,
         retained_bytes_cap,
+        output_limit.clone(),
     ));
 
     let expiration_wait = async {
@@ -1278,21 +1407,53 @@ async fn consume_output(
             std::future::pending::<()>().await;
         }
     };
+    let wall_time_wait = async {
+        match resource_limits.wall_time {
+            Some(wall_time) => tokio::time::sleep(wall_time).await,
+            None => std::future::pending::<()>().await,
+        }
+    };
+    let output_limit_wait = async {
+        match output_limit.as_ref() {
+            Some(output_limit) => output_limit.exceeded.notified().await,
+            None => std::future::pending::<()>().await,
+        }
+    };
     tokio::pin!(expiration_wait);
-    let (exit_status, timed_out) = tokio::select! {
+    tokio::pin!(wall_time_wait);
+    tokio::pin!(output_limit_wait);
+    let (exit_status, timed_out, resource_limit) = tokio::select! {
         status_result = child.wait() => {
             let exit_status = status_result?;
-            (exit_status, false)
+            (exit_status, false, None)
         }
         _ = &mut expiration_wait => {
             kill_child_process_group(&mut child)?;
             child.start_kill()?;
-            (synthetic_exit_status(EXIT_CODE_SIGNAL_BASE + TIMEOUT_CODE), true)
+            (synthetic_exit_status(EXIT_CODE_SIGNAL_BASE + TIMEOUT_CODE), true, None)
+        }
+        _ = &mut wall_time_wait => {
+            kill_child_process_group(&mut child)?;
+            child.start_kill()?;
+            (
+                synthetic_exit_status(EXIT_CODE_SIGNAL_BASE + SIGKILL_CODE),
+                false,
+                Some(ResourceLimitKind::WallTime),
+            )
+        }
+        _ = &mut output_limit_wait => {
+            kill_child_process_group(&mut child)?;
+            child.start_kill()?;
+            (
+                synthetic_exit_status(EXIT_CODE_SIGNAL_BASE + SIGKILL_CODE),
+                false,
+                Some(ResourceLimitKind::OutputBytes),
+            )
         }
         _ = tokio::signal::ctrl_c() => {
             kill_child_process_group(&mut child)?;
             child.start_kill()?;
-            (synthetic_exit_status(EXIT_CODE_SIGNAL_BASE + SIGKILL_CODE), false)
+            (synthetic_exit_status(EXIT_CODE_SIGNAL_BASE + SIGKILL_CODE), false, None)
         }
     };
 
@@ -1332,6 +1493,7 @@ async fn consume_output(
         stderr,
         aggregated_output,
         timed_out,
+        resource_limit,
     })
 }
 
@@ -1340,6 +1502,7 @@ async fn read_output<R: AsyncRead + Unpin + Send + 'static>(
     stream: Option<StdoutStream>,
     is_stderr: bool,
     max_bytes: Option<usize>,
+    output_limit: Option<Arc<OutputLimit>>,
 ) -> io::Result<StreamOutput<Vec<u8>>> {
     let mut buf = Vec::with_capacity(
         max_bytes.map_or(AGGREGATE_BUFFER_INITIAL_CAPACITY, |max_bytes| {
@@ -1354,6 +1517,9 @@ async fn read_output<R: AsyncRead + Unpin + Send + 'static>(
         if n == 0 {
             break;
         }
+        if let Some(output_limit) = &output_limit {
+            output_limit.record(n);
+        }
 
         if let Some(stream) = &stream
             && emitted_deltas < MAX_EXEC_OUTPUT_DELTAS_PER_CALL
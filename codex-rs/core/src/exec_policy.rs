@@ -203,6 +203,9 @@ pub(crate) struct ExecPolicyManager {
 
 pub(crate) struct ExecApprovalRequest<'a> {
     pub(crate) command: &'a [String],
+    /// Working directory the command will run in; `workspace_path()` rule
+    /// tokens resolve against it.
+    pub(crate) cwd: &'a AbsolutePathBuf,
     pub(crate) approval_policy: AskForApproval,
     pub(crate) sandbox_policy: &'a SandboxPolicy,
     pub(crate) file_system_sandbox_policy: &'a FileSystemSandboxPolicy,
@@ -237,6 +240,7 @@ impl ExecPolicyManager {
     ) -> ExecApprovalRequirement {
         let ExecApprovalRequest {
             command,
+            cwd,
             approval_policy,
             sandbox_policy,
             file_system_sandbox_policy,
@@ -261,6 +265,7 @@ impl ExecPolicyManager {
         };
         let match_options = MatchOptions {
             resolve_host_executables: true,
+            cwd: Some(cwd.clone()),
         };
         let evaluation = exec_policy.check_multiple_with_options(
             commands.iter(),
@@ -357,6 +362,7 @@ impl ExecPolicyManager {
         let current_policy = self.current();
         let match_options = MatchOptions {
             resolve_host_executables: true,
+            ..Default::default()
         };
         let existing_evaluation = current_policy.check_multiple_with_options(
             [&amendment.command],
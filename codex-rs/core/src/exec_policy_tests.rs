@@ -59,6 +59,10 @@ fn host_absolute_path(segments: &[&str]) -> String {
     path.to_string_lossy().into_owned()
 }
 
+fn test_cwd() -> AbsolutePathBuf {
+    AbsolutePathBuf::try_from(host_absolute_path(&["workspace"])).expect("absolute test cwd")
+}
+
 fn host_program_path(name: &str) -> String {
     let executable_name = if cfg!(windows) {
         format!("{name}.exe")
@@ -1051,6 +1055,7 @@ async fn mixed_rule_and_sandbox_prompt_prioritizes_rule_for_rejection_decision()
     let requirement = manager
         .create_exec_approval_requirement_for_command(ExecApprovalRequest {
             command: &command,
+            cwd: &test_cwd(),
             approval_policy: AskForApproval::Granular(GranularApprovalConfig {
                 sandbox_approval: true,
                 rules: true,
@@ -1088,6 +1093,7 @@ async fn mixed_rule_and_sandbox_prompt_rejects_when_granular_rules_are_disabled(
     let requirement = manager
         .create_exec_approval_requirement_for_command(ExecApprovalRequest {
             command: &command,
+            cwd: &test_cwd(),
             approval_policy: AskForApproval::Granular(GranularApprovalConfig {
                 sandbox_approval: true,
                 rules: false,
@@ -1118,6 +1124,7 @@ async fn exec_approval_requirement_falls_back_to_heuristics() {
     let requirement = manager
         .create_exec_approval_requirement_for_command(ExecApprovalRequest {
             command: &command,
+            cwd: &test_cwd(),
             approval_policy: AskForApproval::UnlessTrusted,
             sandbox_policy: &SandboxPolicy::new_read_only_policy(),
             file_system_sandbox_policy: &read_only_file_system_sandbox_policy(),
@@ -1143,6 +1150,7 @@ async fn empty_bash_lc_script_falls_back_to_original_command() {
     let requirement = manager
         .create_exec_approval_requirement_for_command(ExecApprovalRequest {
             command: &command,
+            cwd: &test_cwd(),
             approval_policy: AskForApproval::UnlessTrusted,
             sandbox_policy: &SandboxPolicy::new_read_only_policy(),
             file_system_sandbox_policy: &read_only_file_system_sandbox_policy(),
@@ -1172,6 +1180,7 @@ async fn whitespace_bash_lc_script_falls_back_to_original_command() {
     let requirement = manager
         .create_exec_approval_requirement_for_command(ExecApprovalRequest {
             command: &command,
+            cwd: &test_cwd(),
             approval_policy: AskForApproval::UnlessTrusted,
             sandbox_policy: &SandboxPolicy::new_read_only_policy(),
             file_system_sandbox_policy: &read_only_file_system_sandbox_policy(),
@@ -1201,6 +1210,7 @@ async fn request_rule_uses_prefix_rule() {
     let requirement = manager
         .create_exec_approval_requirement_for_command(ExecApprovalRequest {
             command: &command,
+            cwd: &test_cwd(),
             approval_policy: AskForApproval::OnRequest,
             sandbox_policy: &SandboxPolicy::new_read_only_policy(),
             file_system_sandbox_policy: &read_only_file_system_sandbox_policy(),
@@ -1233,6 +1243,7 @@ async fn request_rule_falls_back_when_prefix_rule_does_not_approve_all_commands(
     let requirement = manager
         .create_exec_approval_requirement_for_command(ExecApprovalRequest {
             command: &command,
+            cwd: &test_cwd(),
             approval_policy: AskForApproval::OnRequest,
             sandbox_policy: &SandboxPolicy::DangerFullAccess,
             file_system_sandbox_policy: &unrestricted_file_system_sandbox_policy(),
@@ -1272,6 +1283,7 @@ async fn heuristics_apply_when_other_commands_match_policy() {
         ExecPolicyManager::new(policy)
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 command: &command,
+                cwd: &test_cwd(),
                 approval_policy: AskForApproval::UnlessTrusted,
                 sandbox_policy: &SandboxPolicy::DangerFullAccess,
                 file_system_sandbox_policy: &unrestricted_file_system_sandbox_policy(),
@@ -1758,6 +1770,7 @@ async fn verify_approval_requirement_for_unsafe_powershell_command() {
         policy
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 command: &sneaky_command,
+                cwd: &test_cwd(),
                 approval_policy: AskForApproval::OnRequest,
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 file_system_sandbox_policy: &read_only_file_system_sandbox_policy(),
@@ -1782,6 +1795,7 @@ async fn verify_approval_requirement_for_unsafe_powershell_command() {
         policy
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 command: &dangerous_command,
+                cwd: &test_cwd(),
                 approval_policy: AskForApproval::OnRequest,
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 file_system_sandbox_policy: &read_only_file_system_sandbox_policy(),
@@ -1802,6 +1816,7 @@ async fn verify_approval_requirement_for_unsafe_powershell_command() {
         policy
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 command: &dangerous_command,
+                cwd: &test_cwd(),
                 approval_policy: AskForApproval::Never,
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 file_system_sandbox_policy: &read_only_file_system_sandbox_policy(),
@@ -1900,6 +1915,7 @@ async fn assert_exec_approval_requirement_for_command(
     let requirement = ExecPolicyManager::new(policy)
         .create_exec_approval_requirement_for_command(ExecApprovalRequest {
             command: &command,
+            cwd: &test_cwd(),
             approval_policy,
             sandbox_policy: &sandbox_policy,
             file_system_sandbox_policy: &file_system_sandbox_policy,
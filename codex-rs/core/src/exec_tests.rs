@@ -107,6 +107,7 @@ async fn read_output_limits_retained_bytes_for_shell_capture() {
         /*stream*/ None,
         /*is_stderr*/ false,
         Some(EXEC_OUTPUT_MAX_BYTES),
+        /*output_limit*/ None,
     )
     .await
     .expect("read");
@@ -205,6 +206,7 @@ async fn read_output_retains_all_bytes_for_full_buffer_capture() {
 
     let out = read_output(
         reader, /*stream*/ None, /*is_stderr*/ false, /*max_bytes*/ None,
+        /*output_limit*/ None,
     )
     .await
     .expect("read");
@@ -278,6 +280,7 @@ async fn exec_full_buffer_capture_ignores_expiration() -> Result<()> {
             arg0: None,
         },
         NetworkSandboxPolicy::Enabled,
+        &ResourceLimits::default(),
         /*stdout_stream*/ None,
         /*after_spawn*/ None,
     )
@@ -314,6 +317,7 @@ async fn exec_full_buffer_capture_keeps_io_drain_timeout_when_descendant_holds_p
                 arg0: None,
             },
             NetworkSandboxPolicy::Enabled,
+            &ResourceLimits::default(),
             /*stdout_stream*/ None,
             /*after_spawn*/ None,
         ),
@@ -971,6 +975,7 @@ async fn kill_child_process_group_kills_grandchildren_on_timeout() -> Result<()>
     let output = exec(
         params,
         NetworkSandboxPolicy::Restricted,
+        &ResourceLimits::default(),
         /*stdout_stream*/ None,
         /*after_spawn*/ None,
     )
@@ -1002,6 +1007,54 @@ async fn kill_child_process_group_kills_grandchildren_on_timeout() -> Result<()>
     Ok(())
 }
 
+#[cfg(unix)]
+#[tokio::test]
+async fn exec_kills_command_that_exceeds_output_limit() -> Result<()> {
+    let params = ExecParams {
+        command: vec![
+            "/bin/sh".to_string(),
+            "-c".to_string(),
+            "yes; sleep 60".to_string(),
+        ],
+        cwd: codex_utils_absolute_path::AbsolutePathBuf::current_dir()?,
+        expiration: 10_000.into(),
+        capture_policy: ExecCapturePolicy::ShellTool,
+        env: std::env::vars().collect(),
+        network: None,
+        sandbox_permissions: SandboxPermissions::UseDefault,
+        windows_sandbox_level: WindowsSandboxLevel::Disabled,
+        windows_sandbox_private_desktop: false,
+        justification: None,
+        arg0: None,
+    };
+    let resource_limits = ResourceLimits {
+        max_output_bytes: Some(64 * 1024),
+        ..Default::default()
+    };
+
+    let raw_output = exec(
+        params,
+        NetworkSandboxPolicy::Enabled,
+        &resource_limits,
+        /*stdout_stream*/ None,
+        /*after_spawn*/ None,
+    )
+    .await?;
+    assert_eq!(
+        raw_output.resource_limit,
+        Some(ResourceLimitKind::OutputBytes)
+    );
+
+    let err = finalize_exec_result(Ok(raw_output), SandboxType::None, Duration::ZERO)
+        .expect_err("output limit should fail the command");
+    let CodexErr::Sandbox(SandboxErr::ResourceLimitExceeded { limit, output }) = err else {
+        panic!("expected a resource limit error, got {err:?}");
+    };
+    assert_eq!(limit, ResourceLimitKind::OutputBytes);
+    assert_eq!(output.exit_code, EXIT_CODE_SIGNAL_BASE + SIGKILL_CODE);
+    Ok(())
+}
+
 #[tokio::test]
 async fn process_exec_tool_call_respects_cancellation_token() -> Result<()> {
     let command = long_running_command();
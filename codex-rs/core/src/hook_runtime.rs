@@ -2,23 +2,37 @@ use std::future::Future;
 use std::sync::Arc;
 use std::time::Duration;
 
+use codex_analytics::CompactionTrigger;
 use codex_analytics::HookRunFact;
 use codex_analytics::build_track_events_context;
+use codex_hooks::AsyncHookRuns;
+use codex_hooks::NotificationRequest;
+use codex_hooks::NotificationType;
 use codex_hooks::PermissionRequestDecision;
 use codex_hooks::PermissionRequestOutcome;
 use codex_hooks::PermissionRequestRequest;
 use codex_hooks::PostToolUseOutcome;
 use codex_hooks::PostToolUseRequest;
+use codex_hooks::PreCompactRequest;
+use codex_hooks::PreCompactTrigger;
 use codex_hooks::PreToolUseOutcome;
+use codex_hooks::PreToolUsePermissionDecision;
 use codex_hooks::PreToolUseRequest;
+use codex_hooks::SessionEndReason;
+use codex_hooks::SessionEndRequest;
 use codex_hooks::SessionStartOutcome;
+use codex_hooks::SubagentStartRequest;
+use codex_hooks::SubagentStopRequest;
+use codex_hooks::SubagentStopStatus;
 use codex_hooks::UserPromptSubmitOutcome;
 use codex_hooks::UserPromptSubmitRequest;
 use codex_otel::HOOK_RUN_DURATION_METRIC;
 use codex_otel::HOOK_RUN_METRIC;
+use codex_protocol::ThreadId;
 use codex_protocol::items::TurnItem;
 use codex_protocol::models::ResponseInputItem;
 use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::AgentStatus;
 use codex_protocol::protocol::AskForApproval;
 use codex_protocol::protocol::EventMsg;
 use codex_protocol::protocol::HookCompletedEvent;
@@ -27,6 +41,7 @@ use codex_protocol::protocol::HookRunStatus;
 use codex_protocol::protocol::HookRunSummary;
 use codex_protocol::protocol::HookSource;
 use codex_protocol::protocol::HookStartedEvent;
+use codex_protocol::protocol::TokenUsage;
 use codex_protocol::user_input::UserInput;
 use serde_json::Value;
 
@@ -61,6 +76,7 @@ pub(crate) enum PendingInputRecord {
 
 struct ContextInjectingHookOutcome {
     hook_events: Vec<HookCompletedEvent>,
+    async_hooks: AsyncHookRuns,
     outcome: HookRuntimeOutcome,
 }
 
@@ -68,12 +84,14 @@ impl From<SessionStartOutcome> for ContextInjectingHookOutcome {
     fn from(value: SessionStartOutcome) -> Self {
         let SessionStartOutcome {
             hook_events,
+            async_hooks,
             should_stop,
             stop_reason: _,
             additional_contexts,
         } = value;
         Self {
             hook_events,
+            async_hooks,
             outcome: HookRuntimeOutcome {
                 should_stop,
                 additional_contexts,
@@ -86,12 +104,14 @@ impl From<UserPromptSubmitOutcome> for ContextInjectingHookOutcome {
     fn from(value: UserPromptSubmitOutcome) -> Self {
         let UserPromptSubmitOutcome {
             hook_events,
+            async_hooks,
             should_stop,
             stop_reason: _,
             additional_contexts,
         } = value;
         Self {
             hook_events,
+            async_hooks,
             outcome: HookRuntimeOutcome {
                 should_stop,
                 additional_contexts,
@@ -129,6 +149,16 @@ pub(crate) async fn run_pending_session_start_hooks(
     .await
 }
 
+/// What tool dispatch should do once `PreToolUse` hooks have run.
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct PreToolUseHookResult {
+    /// Message returned to the model when a hook blocked the call.
+    pub(crate) block_message: Option<String>,
+    /// Hook-supplied replacement for the tool input, in `tool_input` shape.
+    pub(crate) updated_input: Option<Value>,
+    pub(crate) permission_decision: Option<PreToolUsePermissionDecision>,
+}
+
 /// Runs matching `PreToolUse` hooks before a tool executes.
 ///
 /// `tool_name` is the canonical name serialized to hook stdin. Matcher aliases
@@ -140,7 +170,7 @@ pub(crate) async fn run_pre_tool_use_hooks(
     tool_use_id: String,
     tool_name: &HookToolName,
     tool_input: &Value,
-) -> Option<String> {
+) -> PreToolUseHookResult {
     let request = PreToolUseRequest {
         session_id: sess.conversation_id,
         turn_id: turn_context.sub_id.clone(),
@@ -158,13 +188,24 @@ pub(crate) async fn run_pre_tool_use_hooks(
 
     let PreToolUseOutcome {
         hook_events,
+        async_hooks,
         should_block,
         block_reason,
+        permission_decision,
+        updated_input,
     } = sess.hooks().run_pre_tool_use(request).await;
     emit_hook_completed_events(sess, turn_context, hook_events).await;
+    spawn_async_hook_completions(sess, turn_context, async_hooks);
 
-    if should_block {
-        block_reason.map(|reason| {
+    if !should_block {
+        return PreToolUseHookResult {
+            block_message: None,
+            updated_input,
+            permission_decision,
+        };
+    }
+    PreToolUseHookResult {
+        block_message: block_reason.map(|reason| {
             if (tool_name.name() == "Bash" || tool_name.name() == "apply_patch")
                 && let Some(command) = tool_input.get("command").and_then(Value::as_str)
             {
@@ -175,9 +216,8 @@ pub(crate) async fn run_pre_tool_use_hooks(
                     tool_name.name()
                 )
             }
-        })
-    } else {
-        None
+        }),
+        ..PreToolUseHookResult::default()
     }
 }
 
@@ -207,9 +247,11 @@ pub(crate) async fn run_permission_request_hooks(
 
     let PermissionRequestOutcome {
         hook_events,
+        async_hooks,
         decision,
     } = sess.hooks().run_permission_request(request).await;
     emit_hook_completed_events(sess, turn_context, hook_events).await;
+    spawn_async_hook_completions(sess, turn_context, async_hooks);
 
     decision
 }
@@ -245,8 +287,9 @@ pub(crate) async fn run_post_tool_use_hooks(
     let preview_runs = sess.hooks().preview_post_tool_use(&request);
     emit_hook_started_events(sess, turn_context, preview_runs).await;
 
-    let outcome = sess.hooks().run_post_tool_use(request).await;
+    let mut outcome = sess.hooks().run_post_tool_use(request).await;
     emit_hook_completed_events(sess, turn_context, outcome.hook_events.clone()).await;
+    spawn_async_hook_completions(sess, turn_context, std::mem::take(&mut outcome.async_hooks));
     outcome
 }
 
@@ -274,6 +317,171 @@ pub(crate) async fn run_user_prompt_submit_hooks(
     .await
 }
 
+/// Runs matching `PreCompact` hooks before history is compacted.
+///
+/// Returns the context hooks asked to keep; callers record it once the compacted
+/// history is in place so it survives the rewrite.
+pub(crate) async fn run_pre_compact_hooks(
+    sess: &Arc<Session>,
+    turn_context: &Arc<TurnContext>,
+    trigger: CompactionTrigger,
+) -> Vec<String> {
+    let request = PreCompactRequest {
+        session_id: sess.conversation_id,
+        turn_id: turn_context.sub_id.clone(),
+        cwd: turn_context.cwd.clone(),
+        transcript_path: sess.hook_transcript_path().await,
+        model: turn_context.model_info.slug.clone(),
+        permission_mode: hook_permission_mode(turn_context),
+        trigger: match trigger {
+            CompactionTrigger::Manual => PreCompactTrigger::Manual,
+            CompactionTrigger::Auto => PreCompactTrigger::Auto,
+        },
+    };
+    let preview_runs = sess.hooks().preview_pre_compact(&request);
+    emit_hook_started_events(sess, turn_context, preview_runs).await;
+
+    let outcome = sess.hooks().run_pre_compact(request).await;
+    emit_hook_completed_events(sess, turn_context, outcome.hook_events).await;
+    spawn_async_hook_completions(sess, turn_context, outcome.async_hooks);
+    outcome.additional_contexts
+}
+
+/// Runs matching `SessionEnd` hooks while the session shuts down.
+///
+/// Tasks have already been aborted, so this runs against a fresh default turn.
+pub(crate) async fn run_session_end_hooks(sess: &Arc<Session>, sub_id: String) {
+    if !sess.hooks().has_handlers(HookEventName::SessionEnd) {
+        return;
+    }
+    let turn_context = sess.new_default_turn_with_sub_id(sub_id).await;
+    // Do not materialize a rollout for a session that never recorded anything.
+    let transcript_path = sess.current_rollout_path().await.ok().flatten();
+    let request = SessionEndRequest {
+        session_id: sess.conversation_id,
+        cwd: turn_context.cwd.clone(),
+        transcript_path,
+        model: turn_context.model_info.slug.clone(),
+        permission_mode: hook_permission_mode(&turn_context),
+        reason: SessionEndReason::Exit,
+    };
+    let preview_runs = sess.hooks().preview_session_end(&request);
+    emit_hook_started_events(sess, &turn_context, preview_runs).await;
+
+    let outcome = sess.hooks().run_session_end(request).await;
+    emit_hook_completed_events(sess, &turn_context, outcome.hook_events).await;
+    spawn_async_hook_completions(sess, &turn_context, outcome.async_hooks);
+}
+
+/// Identifies the sub-agent reported to `SubagentStart` and `SubagentStop` hooks.
+#[derive(Debug, Clone)]
+pub(crate) struct SubagentHookTarget {
+    pub(crate) agent_id: ThreadId,
+    pub(crate) agent_type: String,
+    pub(crate) agent_path: Option<String>,
+    pub(crate) agent_nickname: Option<String>,
+}
+
+/// Runs the parent session's `SubagentStart` hooks once a child agent has been spawned.
+pub(crate) async fn run_subagent_start_hooks(parent: &Arc<Session>, target: &SubagentHookTarget) {
+    if !parent.hooks().has_handlers(HookEventName::SubagentStart) {
+        return;
+    }
+    let turn_context = parent.hook_turn_context().await;
+    let request = SubagentStartRequest {
+        session_id: parent.conversation_id,
+        turn_id: turn_context.sub_id.clone(),
+        cwd: turn_context.cwd.clone(),
+        transcript_path: parent.hook_transcript_path().await,
+        model: turn_context.model_info.slug.clone(),
+        permission_mode: hook_permission_mode(&turn_context),
+        agent_id: target.agent_id,
+        agent_type: target.agent_type.clone(),
+        agent_path: target.agent_path.clone(),
+        agent_nickname: target.agent_nickname.clone(),
+    };
+    let preview_runs = parent.hooks().preview_subagent_start(&request);
+    emit_hook_started_events(parent, &turn_context, preview_runs).await;
+
+    let outcome = parent.hooks().run_subagent_start(request).await;
+    emit_hook_completed_events(parent, &turn_context, outcome.hook_events).await;
+    spawn_async_hook_completions(parent, &turn_context, outcome.async_hooks);
+}
+
+/// Runs the parent session's `SubagentStop` hooks once a child agent reaches a final status.
+pub(crate) async fn run_subagent_stop_hooks(
+    parent: &Arc<Session>,
+    target: SubagentHookTarget,
+    status: &AgentStatus,
+    token_usage: Option<TokenUsage>,
+) {
+    if !parent.hooks().has_handlers(HookEventName::SubagentStop) {
+        return;
+    }
+    let (status, last_assistant_message) = match status {
+        AgentStatus::Completed(message) => (SubagentStopStatus::Completed, message.clone()),
+        AgentStatus::Errored(message) => (SubagentStopStatus::Errored, Some(message.clone())),
+        AgentStatus::Shutdown => (SubagentStopStatus::Shutdown, None),
+        AgentStatus::NotFound => (SubagentStopStatus::NotFound, None),
+        AgentStatus::PendingInit | AgentStatus::Running | AgentStatus::Interrupted => return,
+    };
+    let turn_context = parent.hook_turn_context().await;
+    let request = SubagentStopRequest {
+        session_id: parent.conversation_id,
+        turn_id: turn_context.sub_id.clone(),
+        cwd: turn_context.cwd.clone(),
+        transcript_path: parent.hook_transcript_path().await,
+        model: turn_context.model_info.slug.clone(),
+        permission_mode: hook_permission_mode(&turn_context),
+        agent_id: target.agent_id,
+        agent_type: target.agent_type,
+        agent_path: target.agent_path,
+        agent_nickname: target.agent_nickname,
+        status,
+        last_assistant_message,
+        token_usage,
+    };
+    let preview_runs = parent.hooks().preview_subagent_stop(&request);
+    emit_hook_started_events(parent, &turn_context, preview_runs).await;
+
+    let outcome = parent.hooks().run_subagent_stop(request).await;
+    emit_hook_completed_events(parent, &turn_context, outcome.hook_events).await;
+    spawn_async_hook_completions(parent, &turn_context, outcome.async_hooks);
+}
+
+/// Starts matching `Notification` hooks in the background so they never delay the prompt
+/// the user is being notified about.
+pub(crate) fn spawn_notification_hooks(
+    sess: &Arc<Session>,
+    turn_context: &Arc<TurnContext>,
+    notification_type: NotificationType,
+    message: String,
+) {
+    if !sess.hooks().has_handlers(HookEventName::Notification) {
+        return;
+    }
+    let sess = Arc::clone(sess);
+    let turn_context = Arc::clone(turn_context);
+    tokio::spawn(async move {
+        let request = NotificationRequest {
+            session_id: sess.conversation_id,
+            turn_id: turn_context.sub_id.clone(),
+            cwd: turn_context.cwd.clone(),
+            transcript_path: sess.hook_transcript_path().await,
+            model: turn_context.model_info.slug.clone(),
+            permission_mode: hook_permission_mode(&turn_context),
+            notification_type,
+            message,
+        };
+        let preview_runs = sess.hooks().preview_notification(&request);
+        emit_hook_started_events(&sess, &turn_context, preview_runs).await;
+
+        let outcome = sess.hooks().run_notification(request).await;
+        emit_hook_completed_events(&sess, &turn_context, outcome.hook_events).await;
+        spawn_async_hook_completions(&sess, &turn_context, outcome.async_hooks);
+    });
+}
+
 pub(crate) async fn inspect_pending_input(
     sess: &Arc<Session>,
     turn_context: &Arc<TurnContext>,
@@ -341,6 +549,7 @@ where
 
     let outcome = outcome_future.await.into();
     emit_hook_completed_events(sess, turn_context, outcome.hook_events).await;
+    spawn_async_hook_completions(sess, turn_context, outcome.async_hooks);
     outcome.outcome
 }
 
@@ -408,6 +617,27 @@ pub(crate) async fn emit_hook_completed_events(
     }
 }
 
+/// Reports async hooks as they finish without holding up the caller.
+///
+/// Async hooks may outlive the turn that started them, so the task keeps the session and turn
+/// context alive until every started hook has reported or hit its timeout.
+pub(crate) fn spawn_async_hook_completions(
+    sess: &Arc<Session>,
+    turn_context: &Arc<TurnContext>,
+    mut async_hooks: AsyncHookRuns,
+) {
+    if async_hooks.is_empty() {
+        return;
+    }
+    let sess = Arc::clone(sess);
+    let turn_context = Arc::clone(turn_context);
+    tokio::spawn(async move {
+        while let Some(completed) = async_hooks.next_completed().await {
+            emit_hook_completed_events(&sess, &turn_context, vec![completed]).await;
+        }
+    });
+}
+
 fn emit_hook_completed_metrics(turn_context: &TurnContext, completed: &HookCompletedEvent) {
     let tags = hook_run_metric_tags(&completed.run);
     turn_context
@@ -466,6 +696,11 @@ fn hook_run_metric_tags(run: &HookRunSummary) -> [(&'static str, &'static str);
         HookEventName::SessionStart => "SessionStart",
         HookEventName::UserPromptSubmit => "UserPromptSubmit",
         HookEventName::Stop => "Stop",
+        HookEventName::PreCompact => "PreCompact",
+        HookEventName::SessionEnd => "SessionEnd",
+        HookEventName::SubagentStart => "SubagentStart",
+        HookEventName::SubagentStop => "SubagentStop",
+        HookEventName::Notification => "Notification",
     };
     let hook_source = match run.source {
         HookSource::System => "system",
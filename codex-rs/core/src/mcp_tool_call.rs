@@ -1104,7 +1104,10 @@ async fn mcp_tool_approval_decision_from_guardian(
         | ReviewDecision::ApprovedExecpolicyAmendment { .. }
         | ReviewDecision::NetworkPolicyAmendment { .. } => McpToolApprovalDecision::Accept,
         ReviewDecision::ApprovedForSession => McpToolApprovalDecision::AcceptForSession,
-        ReviewDecision::Denied => McpToolApprovalDecision::Decline {
+        // Partial and edited approvals only apply to patches.
+        ReviewDecision::Denied
+        | ReviewDecision::ApprovedPartialPatch { .. }
+        | ReviewDecision::ApprovedEditedPatch { .. } => McpToolApprovalDecision::Decline {
             message: Some(guardian_rejection_message(sess, review_id).await),
         },
         ReviewDecision::TimedOut => McpToolApprovalDecision::Decline {
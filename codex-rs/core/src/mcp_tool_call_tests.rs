@@ -1007,6 +1007,18 @@ async fn guardian_review_decision_maps_to_mcp_tool_decision() {
         .await,
         McpToolApprovalDecision::Decline { message: None }
     );
+    let partial_patch = mcp_tool_approval_decision_from_guardian(
+        session.as_ref(),
+        "review-id",
+        ReviewDecision::ApprovedPartialPatch {
+            accepted_files: Vec::new(),
+        },
+    )
+    .await;
+    assert!(
+        matches!(partial_patch, McpToolApprovalDecision::Decline { .. }),
+        "partial patch approval should not accept an MCP tool call: {partial_patch:?}"
+    );
 }
 
 #[test]
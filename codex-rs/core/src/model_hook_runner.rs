@@ -0,0 +1,208 @@
+//! Core's implementation of `prompt` and `agent` hooks.
+//!
+//! The hooks crate renders the request and interprets the answer; this module only decides how
+//! to reach a model. Prompt hooks make one structured-output call with no tools. Agent hooks run
+//! a one-shot read-only sub-agent so they can look around the workspace before answering.
+
+use std::sync::Arc;
+use std::sync::Weak;
+
+use codex_api::ResponseEvent;
+use codex_features::Feature;
+use codex_hooks::ModelHookRequest;
+use codex_hooks::ModelHookRunner;
+use codex_protocol::config_types::WebSearchMode;
+use codex_protocol::models::BaseInstructions;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::AskForApproval;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::HookHandlerType;
+use codex_protocol::protocol::SandboxPolicy;
+use codex_protocol::protocol::SubAgentSource;
+use codex_protocol::user_input::UserInput;
+use codex_rollout_trace::InferenceTraceContext;
+use futures::StreamExt;
+use futures::future::BoxFuture;
+use tokio::sync::RwLock;
+use tokio_util::sync::CancellationToken;
+
+use crate::Prompt;
+use crate::codex_delegate::run_codex_thread_one_shot;
+use crate::config::Constrained;
+use crate::session::session::Session;
+use crate::session::turn_context::TurnContext;
+
+const HOOK_SUB_AGENT_NAME: &str = "hook";
+
+/// Runs model-backed hooks against the session that owns the hook engine.
+///
+/// The engine is built before the session exists, so the session is filled in afterwards.
+pub(crate) struct SessionModelHookRunner {
+    session: Arc<RwLock<Weak<Session>>>,
+}
+
+impl SessionModelHookRunner {
+    pub(crate) fn new(session: Arc<RwLock<Weak<Session>>>) -> Self {
+        Self { session }
+    }
+}
+
+impl ModelHookRunner for SessionModelHookRunner {
+    fn run(&self, request: ModelHookRequest) -> BoxFuture<'static, Result<String, String>> {
+        let session = Arc::clone(&self.session);
+        Box::pin(async move {
+            let Some(session) = session.read().await.upgrade() else {
+                return Err("session is no longer running".to_string());
+            };
+            let turn_context = match request.turn_id.as_deref() {
+                Some(turn_id) => session.turn_context_for_sub_id(turn_id).await,
+                None => None,
+            };
+            let turn_context = match turn_context {
+                Some(turn_context) => turn_context,
+                None => session.new_default_turn().await,
+            };
+            match request.handler_type {
+                HookHandlerType::Agent => run_agent_hook(session, turn_context, request).await,
+                HookHandlerType::Prompt | HookHandlerType::Command => {
+                    run_prompt_hook(&session, &turn_context, request).await
+                }
+            }
+        })
+    }
+}
+
+async fn run_prompt_hook(
+    session: &Session,
+    turn_context: &TurnContext,
+    request: ModelHookRequest,
+) -> Result<String, String> {
+    let model_info = match request.model.as_deref() {
+        Some(model) => {
+            session
+                .services
+                .models_manager
+                .get_model_info(model, &turn_context.config.to_models_manager_config())
+                .await
+        }
+        None => turn_context.model_info.clone(),
+    };
+    let prompt = Prompt {
+        input: vec![ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText {
+                text: request.prompt,
+            }],
+            end_turn: None,
+            phase: None,
+        }],
+        tools: Vec::new(),
+        parallel_tool_calls: false,
+        base_instructions: BaseInstructions {
+            text: request.instructions,
+        },
+        personality: None,
+        output_schema: Some(request.output_schema),
+        output_schema_strict: true,
+    };
+
+    let mut client_session = session.services.model_client.new_session();
+    let mut stream = client_session
+        .stream(
+            &prompt,
+            &model_info,
+            &turn_context.session_telemetry,
+            turn_context.reasoning_effort,
+            turn_context.reasoning_summary,
+            turn_context.config.service_tier,
+            turn_context
+                .turn_metadata_state
+                .current_header_value()
+                .as_deref(),
+            &InferenceTraceContext::disabled(),
+        )
+        .await
+        .map_err(|err| err.to_string())?;
+
+    let mut result = String::new();
+    while let Some(event) = stream
+        .next()
+        .await
+        .transpose()
+        .map_err(|err| err.to_string())?
+    {
+        match event {
+            ResponseEvent::OutputTextDelta(delta) => result.push_str(&delta),
+            ResponseEvent::OutputItemDone(item) => {
+                if result.is_empty()
+                    && let ResponseItem::Message { content, .. } = item
+                    && let Some(text) = crate::compact::content_items_to_text(&content)
+                {
+                    result.push_str(&text);
+                }
+            }
+            ResponseEvent::Completed { .. } => break,
+            _ => {}
+        }
+    }
+    Ok(result)
+}
+
+async fn run_agent_hook(
+    session: Arc<Session>,
+    turn_context: Arc<TurnContext>,
+    request: ModelHookRequest,
+) -> Result<String, String> {
+    let mut config = turn_context.config.as_ref().clone();
+    if let Some(model) = request.model {
+        config.model = Some(model);
+    }
+    config.base_instructions = Some(request.instructions);
+    config.developer_instructions = None;
+    config.permissions.approval_policy = Constrained::allow_only(AskForApproval::Never);
+    config.permissions.sandbox_policy =
+        Constrained::allow_only(SandboxPolicy::new_read_only_policy());
+    if let Err(err) = config.web_search_mode.set(WebSearchMode::Disabled) {
+        return Err(format!("could not disable web search: {err}"));
+    }
+    // Hooks must not fire inside the sub-agent, or a hook on Stop would recurse forever.
+    for feature in [Feature::CodexHooks, Feature::Collab, Feature::SpawnCsv] {
+        let _ = config.features.disable(feature);
+    }
+
+    // Dropping this future (for example when the hook times out) stops the sub-agent.
+    let cancellation_token = CancellationToken::new();
+    let _cancel_on_drop = cancellation_token.clone().drop_guard();
+    let codex = run_codex_thread_one_shot(
+        config,
+        Arc::clone(&session.services.auth_manager),
+        Arc::clone(&session.services.models_manager),
+        vec![UserInput::Text {
+            text: request.prompt,
+            text_elements: Vec::new(),
+        }],
+        Arc::clone(&session),
+        turn_context,
+        cancellation_token,
+        SubAgentSource::Other(HOOK_SUB_AGENT_NAME.to_string()),
+        Some(request.output_schema),
+        /*initial_history*/ None,
+    )
+    .await
+    .map_err(|err| err.to_string())?;
+
+    while let Ok(event) = codex.rx_event.recv().await {
+        match event.msg {
+            EventMsg::TurnComplete(turn_complete) => {
+                return turn_complete
+                    .last_agent_message
+                    .ok_or_else(|| "agent finished without an answer".to_string());
+            }
+            EventMsg::TurnAborted(_) => return Err("agent was interrupted".to_string()),
+            _ => {}
+        }
+    }
+    Err("agent exited without an answer".to_string())
+}
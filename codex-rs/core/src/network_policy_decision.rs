@@ -62,6 +62,7 @@ pub(crate) fn denied_network_policy_message(blocked: &BlockedRequest) -> Option<
         "not_allowed" => "domain is not on the allowlist for the current sandbox mode",
         "not_allowed_local" => "local/private network addresses are blocked by the sandbox policy",
         "method_not_allowed" => "request method is blocked by the current network mode",
+        "request_denied" => "request is denied by a network request rule for this host",
         "proxy_disabled" => "network proxy is disabled",
         _ => "request is blocked by network policy",
     };
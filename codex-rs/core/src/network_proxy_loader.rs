@@ -17,12 +17,16 @@ use codex_config::permissions_toml::NetworkToml;
 use codex_config::permissions_toml::PermissionsToml;
 use codex_config::permissions_toml::overlay_network_domain_permissions;
 use codex_exec_server::LOCAL_FS;
+use codex_execpolicy::NetworkRuleProtocol;
 use codex_network_proxy::ConfigReloader;
 use codex_network_proxy::ConfigState;
 use codex_network_proxy::NetworkProxyConfig;
 use codex_network_proxy::NetworkProxyConstraintError;
 use codex_network_proxy::NetworkProxyConstraints;
 use codex_network_proxy::NetworkProxyState;
+use codex_network_proxy::NetworkRequestRule;
+use codex_network_proxy::NetworkRequestRuleDecision;
+use codex_network_proxy::NetworkRequestScheme;
 use codex_network_proxy::build_config_state;
 use codex_network_proxy::normalize_host;
 use codex_network_proxy::validate_policy_against_constraints;
@@ -241,6 +245,40 @@ fn apply_exec_policy_network_rules(
             codex_network_proxy::NetworkDomainPermission::Deny,
         );
     }
+    for rule in exec_policy_network_request_rules(exec_policy) {
+        config.network.add_request_rule(rule);
+    }
+}
+
+/// Convert execpolicy `network_rule`s restricted by path or method into proxy request rules.
+/// Prompt rules have no proxy equivalent and are skipped, as they are for domains.
+pub(crate) fn exec_policy_network_request_rules(
+    exec_policy: &codex_execpolicy::Policy,
+) -> Vec<NetworkRequestRule> {
+    exec_policy
+        .network_rules()
+        .iter()
+        .filter(|rule| rule.is_request_rule())
+        .filter_map(|rule| {
+            let decision = match rule.decision {
+                codex_execpolicy::Decision::Allow => NetworkRequestRuleDecision::Allow,
+                codex_execpolicy::Decision::Forbidden => NetworkRequestRuleDecision::Deny,
+                codex_execpolicy::Decision::Prompt => return None,
+            };
+            let scheme = match rule.protocol {
+                NetworkRuleProtocol::Http => NetworkRequestScheme::Http,
+                NetworkRuleProtocol::Https => NetworkRequestScheme::Https,
+                NetworkRuleProtocol::Socks5Tcp | NetworkRuleProtocol::Socks5Udp => return None,
+            };
+            Some(NetworkRequestRule {
+                scheme: Some(scheme),
+                host: rule.host.clone(),
+                path: rule.path.clone(),
+                methods: rule.methods.clone(),
+                decision,
+            })
+        })
+        .collect()
 }
 
 fn upsert_network_domain(
@@ -147,6 +147,47 @@ fn execpolicy_network_rules_overlay_network_lists() {
     );
 }
 
+#[test]
+fn execpolicy_network_request_rules_become_proxy_request_rules() {
+    let mut parser = codex_execpolicy::PolicyParser::new();
+    parser
+        .parse(
+            "network.rules",
+            r#"
+network_rule(host = "github.com", protocol = "https", decision = "allow", path = "/*/archive/*", methods = ["GET"])
+network_rule(host = "api.github.com", protocol = "https", decision = "deny", methods = ["POST"])
+network_rule(host = "uploads.github.com", protocol = "https", decision = "prompt", methods = ["PUT"])
+"#,
+        )
+        .expect("network rules should parse");
+    let exec_policy = parser.build();
+    let mut config = NetworkProxyConfig::default();
+
+    apply_exec_policy_network_rules(&mut config, &exec_policy);
+
+    assert_eq!(
+        config.network.rules,
+        vec![
+            NetworkRequestRule {
+                scheme: Some(NetworkRequestScheme::Https),
+                host: "github.com".to_string(),
+                path: Some("/*/archive/*".to_string()),
+                methods: vec!["GET".to_string()],
+                decision: NetworkRequestRuleDecision::Allow,
+            },
+            NetworkRequestRule {
+                scheme: Some(NetworkRequestScheme::Https),
+                host: "api.github.com".to_string(),
+                path: None,
+                methods: vec!["POST".to_string()],
+                decision: NetworkRequestRuleDecision::Deny,
+            },
+        ]
+    );
+    assert_eq!(config.network.allowed_domains(), None);
+    assert_eq!(config.network.denied_domains(), None);
+}
+
 #[test]
 fn apply_network_constraints_includes_allow_all_unix_sockets_flag() {
     let config: toml::Value = toml::from_str(
@@ -0,0 +1,151 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Weak;
+
+use codex_network_proxy::JsonlTrafficRecorder;
+use codex_network_proxy::NetworkTrafficBody;
+use codex_network_proxy::NetworkTrafficEntry;
+use codex_network_proxy::NetworkTrafficHeader;
+use codex_network_proxy::NetworkTrafficObserver;
+use codex_protocol::protocol::Event;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::NetworkTrafficBody as ProtocolNetworkTrafficBody;
+use codex_protocol::protocol::NetworkTrafficHeader as ProtocolNetworkTrafficHeader;
+use codex_protocol::protocol::NetworkTrafficRecordedEvent;
+
+use crate::session::session::Session;
+
+const NETWORK_LOGS_SUBDIR: &str = "network_logs";
+
+pub(crate) fn network_traffic_log_path(codex_home: &std::path::Path, thread_id: &str) -> PathBuf {
+    codex_home
+        .join(NETWORK_LOGS_SUBDIR)
+        .join(format!("{thread_id}.jsonl"))
+}
+
+/// Appends each proxied request to the thread's traffic log and forwards it to
+/// the session's event stream.
+pub(crate) fn build_network_traffic_observer(
+    session: Weak<Session>,
+    log_path: PathBuf,
+) -> Arc<dyn NetworkTrafficObserver> {
+    let recorder = Arc::new(JsonlTrafficRecorder::new(log_path));
+    Arc::new(move |entry: NetworkTrafficEntry| {
+        let recorder = Arc::clone(&recorder);
+        let session = session.clone();
+        async move {
+            recorder.on_network_traffic(entry.clone()).await;
+            let Some(session) = session.upgrade() else {
+                return;
+            };
+            session
+                .send_event_raw(Event {
+                    id: session.next_internal_sub_id(),
+                    msg: EventMsg::NetworkTrafficRecorded(traffic_event(entry)),
+                })
+                .await;
+        }
+    })
+}
+
+fn traffic_event(entry: NetworkTrafficEntry) -> NetworkTrafficRecordedEvent {
+    NetworkTrafficRecordedEvent {
+        started_at_ms: entry.started_at_ms,
+        duration_ms: entry.duration_ms,
+        protocol: entry.protocol,
+        method: entry.method,
+        url: entry.url,
+        status: entry.status,
+        request_headers: entry
+            .request_headers
+            .into_iter()
+            .map(traffic_header)
+            .collect(),
+        response_headers: entry
+            .response_headers
+            .into_iter()
+            .map(traffic_header)
+            .collect(),
+        request_body: entry.request_body.map(traffic_body),
+        response_body: entry.response_body.map(traffic_body),
+        decision: entry.decision,
+        source: entry.source,
+        reason: entry.reason,
+        client: entry.client,
+    }
+}
+
+fn traffic_header(header: NetworkTrafficHeader) -> ProtocolNetworkTrafficHeader {
+    ProtocolNetworkTrafficHeader {
+        name: header.name,
+        value: header.value,
+    }
+}
+
+fn traffic_body(body: NetworkTrafficBody) -> ProtocolNetworkTrafficBody {
+    ProtocolNetworkTrafficBody {
+        size: body.size,
+        text: body.text,
+        truncated: body.truncated,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn traffic_event_copies_entry_fields() {
+        let entry = NetworkTrafficEntry {
+            started_at_ms: 1_700_000_000_000,
+            duration_ms: 12,
+            protocol: "https".to_string(),
+            method: Some("GET".to_string()),
+            url: "https://example.com/".to_string(),
+            status: Some(200),
+            request_headers: vec![NetworkTrafficHeader {
+                name: "authorization".to_string(),
+                value: "[REDACTED]".to_string(),
+            }],
+            response_headers: Vec::new(),
+            request_body: None,
+            response_body: Some(NetworkTrafficBody {
+                size: 2,
+                text: "ok".to_string(),
+                truncated: false,
+            }),
+            decision: "allow".to_string(),
+            source: None,
+            reason: None,
+            client: Some("127.0.0.1:5000".to_string()),
+        };
+
+        assert_eq!(
+            traffic_event(entry),
+            NetworkTrafficRecordedEvent {
+                started_at_ms: 1_700_000_000_000,
+                duration_ms: 12,
+                protocol: "https".to_string(),
+                method: Some("GET".to_string()),
+                url: "https://example.com/".to_string(),
+                status: Some(200),
+                request_headers: vec![ProtocolNetworkTrafficHeader {
+                    name: "authorization".to_string(),
+                    value: "[REDACTED]".to_string(),
+                }],
+                response_headers: Vec::new(),
+                request_body: None,
+                response_body: Some(ProtocolNetworkTrafficBody {
+                    size: 2,
+                    text: "ok".to_string(),
+                    truncated: false,
+                }),
+                decision: "allow".to_string(),
+                source: None,
+                reason: None,
+                client: Some("127.0.0.1:5000".to_string()),
+            }
+        );
+    }
+}
@@ -0,0 +1,359 @@
+//! Per-turn copy-on-write overlay for the workspace root.
+//!
+//! With [`Feature::OverlayWorkspace`] enabled, a `WorkspaceWrite` turn in the
+//! Linux bubblewrap sandbox mounts its cwd through overlayfs, so sandboxed
+//! commands and patches write into an upper directory under `CODEX_HOME`
+//! instead of the real tree. When the task finishes, the aggregated diff is
+//! sent as a `TurnDiff` and the user approves or rejects it through the
+//! regular patch approval request; approved files and hunks are copied into
+//! the real tree and the overlay is removed either way. An interrupted turn
+//! cannot be reviewed, so its overlay is left on disk for the user to recover.
+//!
+//! Escalated (unsandboxed) retries and writable roots other than the cwd still
+//! write to the real filesystem.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_exec_server::Environment;
+use codex_features::Feature;
+use codex_protocol::ThreadId;
+use codex_protocol::protocol::AskForApproval;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::FileChange;
+use codex_protocol::protocol::ReviewDecision;
+use codex_protocol::protocol::SandboxPolicy;
+use codex_protocol::protocol::TurnDiffEvent;
+use codex_protocol::protocol::WarningEvent;
+use codex_sandboxing::overlay::OverlayChange;
+use codex_sandboxing::overlay::OverlayChangeKind;
+use codex_sandboxing::overlay::OverlayEntry;
+use codex_sandboxing::overlay::OverlayWorkspace;
+use codex_utils_absolute_path::AbsolutePathBuf;
+use tracing::warn;
+
+use crate::apply_patch::binary_add_change;
+use crate::apply_patch::binary_update_change;
+use crate::apply_patch::symlink_add_change;
+use crate::config::Config;
+use crate::session::session::Session;
+use crate::session::turn_context::TurnContext;
+use crate::turn_diff_tracker::TurnDiffTracker;
+
+/// Directory under `CODEX_HOME` that holds per-turn overlay state.
+const OVERLAYS_DIR: &str = "overlays";
+
+/// Context lines around each hunk of the overlay review diff. Hunk selections
+/// index into hunks built with the same radius.
+const OVERLAY_DIFF_CONTEXT_RADIUS: usize = 3;
+
+const OVERLAY_COMMIT_REASON: &str = "Commit this turn's overlay workspace changes to the working copy? Rejected changes are discarded.";
+
+/// Lays out the overlay for a new turn, or returns `None` when the turn should
+/// write to the real workspace. The overlay needs a local bubblewrap pipeline
+/// and someone to review the result, so it is skipped for remote environments,
+/// legacy Landlock and `AskForApproval::Never`. The Linux sandbox helper
+/// creates the directories the first time a command mounts the overlay.
+pub(crate) fn overlay_workspace_for_turn(
+    config: &Config,
+    environment: Option<&Environment>,
+    sandbox_policy: &SandboxPolicy,
+    approval_policy: AskForApproval,
+    cwd: &AbsolutePathBuf,
+    conversation_id: ThreadId,
+    sub_id: &str,
+) -> Option<OverlayWorkspace> {
+    if !cfg!(target_os = "linux")
+        || !config.features.enabled(Feature::OverlayWorkspace)
+        || config.features.use_legacy_landlock()
+        || environment.is_none_or(Environment::is_remote)
+        || matches!(approval_policy, AskForApproval::Never)
+        || !matches!(sandbox_policy, SandboxPolicy::WorkspaceWrite { .. })
+    {
+        return None;
+    }
+
+    let state_dir = config
+        .codex_home
+        .join(OVERLAYS_DIR)
+        .join(conversation_id.to_string())
+        .join(sub_id);
+    Some(OverlayWorkspace::new(cwd.clone(), &state_dir))
+}
+
+/// Shows the turn's overlay changes to the user, commits the approved ones,
+/// and removes the overlay. Does nothing for turns without an overlay.
+pub(crate) async fn finish_overlay_turn(sess: &Session, turn_context: &TurnContext) {
+    let Some(overlay) = turn_context.overlay_workspace.as_ref() else {
+        return;
+    };
+    let changes = match overlay.changes() {
+        Ok(changes) => changes,
+        Err(err) => {
+            // Keep the overlay on disk so the user can still recover the work.
+            let message = format!(
+                "Failed to read overlay workspace changes; they were left in {}: {err}",
+                overlay.upper_dir.display()
+            );
+            sess.send_event(turn_context, warning_event(message)).await;
+            return;
+        }
+    };
+
+    if !changes.is_empty() {
+        let mut tracker = TurnDiffTracker::with_overlay(overlay.clone());
+        tracker.on_overlay_changes(&changes);
+        if let Ok(Some(unified_diff)) = tracker.get_unified_diff() {
+            sess.send_event(
+                turn_context,
+                EventMsg::TurnDiff(TurnDiffEvent { unified_diff }),
+            )
+            .await;
+        }
+
+        let rx_approve = sess
+            .request_patch_approval(
+                turn_context,
+                format!("overlay-commit-{}", turn_context.sub_id),
+                overlay_file_changes(overlay, &changes),
+                Some(OVERLAY_COMMIT_REASON.to_string()),
+                /*grant_root*/ None,
+            )
+            .await;
+        let decision = rx_approve.await.unwrap_or_default();
+        let accepted = accepted_overlay_changes(overlay, &changes, &decision);
+        for message in accepted.warnings {
+            sess.send_event(turn_context, warning_event(message)).await;
+        }
+        if let Err(err) = overlay.commit(&accepted.changes) {
+            let message = format!(
+                "Failed to commit overlay workspace changes; they were left in {}: {err}",
+                overlay.upper_dir.display()
+            );
+            sess.send_event(turn_context, warning_event(message)).await;
+            return;
+        }
+    }
+
+    discard_overlay(overlay);
+}
+
+/// Keeps an interrupted turn's overlay on disk and tells the user where it is,
+/// since nobody is left to review its changes. An overlay without changes is
+/// removed. Does nothing for turns without an overlay.
+pub(crate) async fn keep_aborted_overlay(sess: &Session, turn_context: &TurnContext) {
+    let Some(overlay) = turn_context.overlay_workspace.as_ref() else {
+        return;
+    };
+    if let Some(message) = aborted_overlay_warning(overlay) {
+        sess.send_event(turn_context, warning_event(message)).await;
+    }
+}
+
+/// Returns the warning for an aborted turn whose overlay may hold changes, or
+/// removes the overlay and returns `None` when it holds none.
+fn aborted_overlay_warning(overlay: &OverlayWorkspace) -> Option<String> {
+    match overlay.changes() {
+        Ok(changes) if changes.is_empty() => {
+            discard_overlay(overlay);
+            None
+        }
+        Ok(_) => Some(format!(
+            "The turn was interrupted before its overlay workspace changes were reviewed; they were left in {}",
+            overlay.upper_dir.display()
+        )),
+        Err(err) => Some(format!(
+            "The turn was interrupted and its overlay workspace changes could not be read; they were left in {}: {err}",
+            overlay.upper_dir.display()
+        )),
+    }
+}
+
+/// Removes the overlay without committing anything else.
+fn discard_overlay(overlay: &OverlayWorkspace) {
+    if let Err(err) = overlay.discard() {
+        warn!(
+            "failed to remove overlay workspace {}: {err}",
+            overlay.upper_dir.display()
+        );
+    }
+}
+
+/// Describes each overlay change the way apply_patch approvals do, so clients
+/// can render the review with their existing patch UI.
+fn overlay_file_changes(
+    overlay: &OverlayWorkspace,
+    changes: &[OverlayChange],
+) -> HashMap<PathBuf, FileChange> {
+    changes
+        .iter()
+        .map(|change| {
+            let file_change = match change.kind {
+                OverlayChangeKind::Added => added_file_change(&overlay_source(overlay, change)),
+                OverlayChangeKind::Deleted => FileChange::Delete {
+                    content: text_or_binary_description(&change.path),
+                },
+                OverlayChangeKind::Modified => {
+                    let before = fs::read(&change.path).unwrap_or_default();
+                    let after = fs::read(overlay_source(overlay, change)).unwrap_or_default();
+                    match (std::str::from_utf8(&before), std::str::from_utf8(&after)) {
+                        (Ok(before), Ok(after)) => FileChange::Update {
+                            unified_diff: similar::TextDiff::from_lines(before, after)
+                                .unified_diff()
+                                .context_radius(OVERLAY_DIFF_CONTEXT_RADIUS)
+                                .to_string(),
+                            move_path: None,
+                        },
+                        _ => binary_update_change(),
+                    }
+                }
+            };
+            (change.path.clone(), file_change)
+        })
+        .collect()
+}
+
+/// The overlay changes to commit, plus a warning for each piece of approved
+/// work that could not be committed.
+#[derive(Debug, Default, PartialEq)]
+struct AcceptedOverlayChanges {
+    changes: Vec<OverlayChange>,
+    warnings: Vec<String>,
+}
+
+/// Picks the changes the user approved. A hunk selection on a modified text
+/// file is committed by rewriting the overlay copy to contain only the
+/// selected hunks. Edited patches cannot be mapped back onto the overlay, so
+/// they discard everything and say so.
+fn accepted_overlay_changes(
+    overlay: &OverlayWorkspace,
+    changes: &[OverlayChange],
+    decision: &ReviewDecision,
+) -> AcceptedOverlayChanges {
+    let mut accepted = AcceptedOverlayChanges::default();
+    match decision {
+        ReviewDecision::Approved | ReviewDecision::ApprovedForSession => {
+            accepted.changes = changes.to_vec();
+        }
+        ReviewDecision::ApprovedPartialPatch { accepted_files } => {
+            for change in changes {
+                let Some(file) = accepted_files.iter().find(|file| file.path == change.path) else {
+                    continue;
+                };
+                match file.hunks.as_deref() {
+                    None => accepted.changes.push(change.clone()),
+                    Some([]) => {}
+                    Some(selected) => match narrow_overlay_change(overlay, change, selected) {
+                        Ok(()) => accepted.changes.push(change.clone()),
+                        Err(err) => accepted.warnings.push(format!(
+                            "Could not commit the selected hunks of {}; it was left unchanged: {err}",
+                            change.path.display()
+                        )),
+                    },
+                }
+            }
+        }
+        ReviewDecision::ApprovedEditedPatch { .. } => {
+            accepted.warnings.push(
+                "Edited patches cannot be applied to overlay workspace changes; all of this turn's changes were discarded."
+                    .to_string(),
+            );
+        }
+        ReviewDecision::ApprovedExecpolicyAmendment { .. }
+        | ReviewDecision::NetworkPolicyAmendment { .. }
+        | ReviewDecision::Denied
+        | ReviewDecision::TimedOut
+        | ReviewDecision::Abort => {}
+    }
+    accepted
+}
+
+/// Rewrites the overlay copy of a modified text file so that it holds only
+/// the selected hunks of the review diff. Other changes have no hunks to
+/// select and are committed whole.
+fn narrow_overlay_change(
+    overlay: &OverlayWorkspace,
+    change: &OverlayChange,
+    selected: &[u32],
+) -> std::io::Result<()> {
+    if change.kind != OverlayChangeKind::Modified {
+        return Ok(());
+    }
+    let OverlayEntry::Upper(upper_path) = overlay.resolve(&change.path) else {
+        return Ok(());
+    };
+    let before = fs::read(&change.path)?;
+    let after = fs::read(&upper_path)?;
+    let (Ok(before), Ok(after)) = (std::str::from_utf8(&before), std::str::from_utf8(&after))
+    else {
+        return Ok(());
+    };
+    fs::write(&upper_path, select_diff_hunks(before, after, selected))
+}
+
+/// Builds the content of `before` with only the selected hunks of its diff to
+/// `after` applied.
+fn select_diff_hunks(before: &str, after: &str, selected: &[u32]) -> String {
+    let diff = similar::TextDiff::from_lines(before, after);
+    let old_lines = diff.old_slices();
+    let new_lines = diff.new_slices();
+    let mut merged = String::with_capacity(before.len().max(after.len()));
+    let mut old_index = 0;
+    for (index, group) in diff
+        .grouped_ops(OVERLAY_DIFF_CONTEXT_RADIUS)
+        .iter()
+        .enumerate()
+    {
+        let (Some(first), Some(last)) = (group.first(), group.last()) else {
+            continue;
+        };
+        let old_range = first.old_range().start..last.old_range().end;
+        merged.extend(old_lines[old_index..old_range.start].iter().copied());
+        if selected.contains(&(index as u32)) {
+            for op in group {
+                merged.extend(new_lines[op.new_range()].iter().copied());
+            }
+        } else {
+            merged.extend(old_lines[old_range.clone()].iter().copied());
+        }
+        old_index = old_range.end;
+    }
+    merged.extend(old_lines[old_index..].iter().copied());
+    merged
+}
+
+fn overlay_source(overlay: &OverlayWorkspace, change: &OverlayChange) -> PathBuf {
+    match overlay.resolve(&change.path) {
+        OverlayEntry::Upper(upper_path) => upper_path,
+        OverlayEntry::Lower | OverlayEntry::Removed => change.path.clone(),
+    }
+}
+
+fn added_file_change(source: &Path) -> FileChange {
+    if let Ok(target) = fs::read_link(source) {
+        return symlink_add_change(&target);
+    }
+    let content = fs::read(source).unwrap_or_default();
+    match String::from_utf8(content) {
+        Ok(content) => FileChange::Add { content },
+        Err(err) => binary_add_change(err.as_bytes()),
+    }
+}
+
+fn text_or_binary_description(path: &Path) -> String {
+    let content = fs::read(path).unwrap_or_default();
+    match String::from_utf8(content) {
+        Ok(content) => content,
+        Err(err) => format!("Binary file ({} bytes)\n", err.as_bytes().len()),
+    }
+}
+
+fn warning_event(message: String) -> EventMsg {
+    EventMsg::Warning(WarningEvent { message })
+}
+
+#[cfg(test)]
+#[path = "overlay_workspace_tests.rs"]
+mod tests;
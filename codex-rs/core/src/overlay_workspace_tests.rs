@@ -0,0 +1,182 @@
+use super::*;
+use codex_protocol::protocol::PatchFileApproval;
+use pretty_assertions::assert_eq;
+use tempfile::TempDir;
+
+fn overlay_in(root: &TempDir) -> OverlayWorkspace {
+    let lower_dir =
+        AbsolutePathBuf::from_absolute_path(root.path().join("workspace")).expect("absolute");
+    let state_dir =
+        AbsolutePathBuf::from_absolute_path(root.path().join("overlay")).expect("absolute");
+    fs::create_dir_all(&lower_dir).expect("create lower dir");
+    let overlay = OverlayWorkspace::new(lower_dir, &state_dir);
+    overlay.create_dirs().expect("create overlay dirs");
+    overlay
+}
+
+#[test]
+fn overlay_file_changes_describe_adds_updates_and_deletes() {
+    let root = TempDir::new().expect("tempdir");
+    let overlay = overlay_in(&root);
+    let edited = overlay.lower_dir.join("edited.txt").into_path_buf();
+    let added = overlay.lower_dir.join("added.txt").into_path_buf();
+    let deleted = overlay.lower_dir.join("deleted.txt").into_path_buf();
+    fs::write(&edited, "one\ntwo\n").expect("write lower");
+    fs::write(&deleted, "gone\n").expect("write lower");
+    fs::write(overlay.upper_dir.join("edited.txt"), "one\nthree\n").expect("write upper");
+    fs::write(overlay.upper_dir.join("added.txt"), "new\n").expect("write upper");
+
+    let changes = vec![
+        OverlayChange {
+            path: added.clone(),
+            kind: OverlayChangeKind::Added,
+        },
+        OverlayChange {
+            path: deleted.clone(),
+            kind: OverlayChangeKind::Deleted,
+        },
+        OverlayChange {
+            path: edited.clone(),
+            kind: OverlayChangeKind::Modified,
+        },
+    ];
+
+    assert_eq!(
+        overlay_file_changes(&overlay, &changes),
+        HashMap::from([
+            (
+                added,
+                FileChange::Add {
+                    content: "new\n".to_string(),
+                },
+            ),
+            (
+                deleted,
+                FileChange::Delete {
+                    content: "gone\n".to_string(),
+                },
+            ),
+            (
+                edited,
+                FileChange::Update {
+                    unified_diff: "@@ -1,2 +1,2 @@\n one\n-two\n+three\n".to_string(),
+                    move_path: None,
+                },
+            ),
+        ])
+    );
+}
+
+#[test]
+fn partial_approval_commits_whole_files_and_selected_hunks() {
+    let root = TempDir::new().expect("tempdir");
+    let overlay = overlay_in(&root);
+    let before = (1..=20)
+        .map(|line| format!("line {line}\n"))
+        .collect::<String>();
+    let after = before
+        .replace("line 2\n", "line two\n")
+        .replace("line 18\n", "line eighteen\n");
+    for name in ["whole.txt", "hunks.txt"] {
+        fs::write(overlay.lower_dir.join(name), &before).expect("write lower");
+        fs::write(overlay.upper_dir.join(name), &after).expect("write upper");
+    }
+    let whole = OverlayChange {
+        path: overlay.lower_dir.join("whole.txt").into_path_buf(),
+        kind: OverlayChangeKind::Modified,
+    };
+    let hunks = OverlayChange {
+        path: overlay.lower_dir.join("hunks.txt").into_path_buf(),
+        kind: OverlayChangeKind::Modified,
+    };
+    let unlisted = OverlayChange {
+        path: overlay.lower_dir.join("unlisted.txt").into_path_buf(),
+        kind: OverlayChangeKind::Added,
+    };
+    let changes = vec![whole.clone(), hunks.clone(), unlisted];
+    let decision = ReviewDecision::ApprovedPartialPatch {
+        accepted_files: vec![
+            PatchFileApproval {
+                path: whole.path.clone(),
+                hunks: None,
+            },
+            PatchFileApproval {
+                path: hunks.path.clone(),
+                hunks: Some(vec![1]),
+            },
+        ],
+    };
+
+    assert_eq!(
+        accepted_overlay_changes(&overlay, &changes, &decision),
+        AcceptedOverlayChanges {
+            changes: vec![whole, hunks],
+            warnings: Vec::new(),
+        }
+    );
+    assert_eq!(
+        fs::read_to_string(overlay.upper_dir.join("hunks.txt")).expect("read upper"),
+        before.replace("line 18\n", "line eighteen\n")
+    );
+    assert_eq!(
+        fs::read_to_string(overlay.upper_dir.join("whole.txt")).expect("read upper"),
+        after
+    );
+    assert_eq!(
+        accepted_overlay_changes(&overlay, &changes, &ReviewDecision::Approved).changes,
+        changes
+    );
+    assert_eq!(
+        accepted_overlay_changes(&overlay, &changes, &ReviewDecision::Denied),
+        AcceptedOverlayChanges::default()
+    );
+}
+
+#[test]
+fn edited_patch_approval_warns_that_overlay_changes_were_discarded() {
+    let root = TempDir::new().expect("tempdir");
+    let overlay = overlay_in(&root);
+    let changes = vec![OverlayChange {
+        path: overlay.lower_dir.join("added.txt").into_path_buf(),
+        kind: OverlayChangeKind::Added,
+    }];
+
+    let accepted = accepted_overlay_changes(
+        &overlay,
+        &changes,
+        &ReviewDecision::ApprovedEditedPatch {
+            patch: "*** Begin Patch\n*** End Patch".to_string(),
+        },
+    );
+
+    assert_eq!(accepted.changes, Vec::new());
+    assert_eq!(accepted.warnings.len(), 1);
+}
+
+#[test]
+fn aborted_overlay_with_changes_is_kept_and_reported() {
+    let root = TempDir::new().expect("tempdir");
+    let overlay = overlay_in(&root);
+    let added = overlay.upper_dir.join("added.txt");
+    fs::write(&added, "unreviewed work\n").expect("write upper");
+
+    let warning = aborted_overlay_warning(&overlay).expect("aborted overlay should warn");
+
+    assert!(
+        warning.contains(&overlay.upper_dir.display().to_string()),
+        "warning should name the overlay directory: {warning}"
+    );
+    assert_eq!(
+        fs::read_to_string(&added).expect("overlay work should be kept"),
+        "unreviewed work\n"
+    );
+}
+
+#[test]
+fn aborted_overlay_without_changes_is_removed() {
+    let root = TempDir::new().expect("tempdir");
+    let overlay = overlay_in(&root);
+
+    assert_eq!(aborted_overlay_warning(&overlay), None);
+    assert!(!overlay.upper_dir.exists());
+}
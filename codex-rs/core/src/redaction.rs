@@ -0,0 +1,167 @@
+//! Session-level secret redaction.
+//!
+//! The session builds one [`Redactor`] from `[redaction]` config, stored
+//! secrets, and secret-looking environment variables. It is applied to tool
+//! output before it enters the conversation history and to rollout items
+//! before they are written. Its rules are also merged into the process
+//! redactor so log sinks that call [`codex_secrets::redact_secrets`] pick up the
+//! rules of every session in the process.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use codex_config::types::RedactionConfig;
+use codex_protocol::models::FunctionCallOutputBody;
+use codex_protocol::models::FunctionCallOutputContentItem;
+use codex_protocol::models::FunctionCallOutputPayload;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::RolloutItem;
+use codex_secrets::Redactor;
+use codex_secrets::SecretsBackendKind;
+use codex_secrets::SecretsManager;
+use codex_secrets::secret_env_values;
+use tokio::sync::Mutex;
+use tracing::warn;
+
+/// Stored secret values already loaded by this process, keyed by where they
+/// were loaded from. Listing a secrets backend can mean one keyring or helper
+/// call per secret, so it happens once per process rather than per session.
+type StoredSecretValues = Vec<(PathBuf, SecretsBackendKind, Arc<[String]>)>;
+
+static STORED_SECRET_VALUES: Mutex<StoredSecretValues> = Mutex::const_new(Vec::new());
+
+/// Builds the session redactor and merges it into the process redactor.
+///
+/// Invalid patterns are rejected when the config is loaded, so a failure here
+/// only falls back to the built-in patterns.
+pub(crate) async fn build_session_redactor(
+    config: &RedactionConfig,
+    codex_home: PathBuf,
+    secrets_backend: SecretsBackendKind,
+) -> Arc<Redactor> {
+    let redactor = match Redactor::new(&config.patterns) {
+        Ok(redactor) => redactor,
+        Err(err) => {
+            warn!("ignoring [redaction] patterns: {err:#}");
+            Redactor::default()
+        }
+    }
+    .with_high_entropy_tokens(config.high_entropy_tokens);
+    let redactor = if config.known_values {
+        let mut values = secret_env_values(std::env::vars());
+        values.extend(
+            stored_secret_values(codex_home, secrets_backend)
+                .await
+                .iter()
+                .cloned(),
+        );
+        redactor.with_known_values(values)
+    } else {
+        redactor
+    };
+    codex_secrets::merge_process_redactor(&redactor);
+    Arc::new(redactor)
+}
+
+/// Loads every stored secret value on first use for a given home and backend.
+/// Failed loads are not cached, so a later session retries them.
+async fn stored_secret_values(codex_home: PathBuf, backend: SecretsBackendKind) -> Arc<[String]> {
+    // Held across the load so concurrent session starts share one listing.
+    let mut cache = STORED_SECRET_VALUES.lock().await;
+    if let Some((_, _, values)) = cache
+        .iter()
+        .find(|(home, kind, _)| *home == codex_home && *kind == backend)
+    {
+        return Arc::clone(values);
+    }
+
+    let result = tokio::task::spawn_blocking({
+        let codex_home = codex_home.clone();
+        let backend = backend.clone();
+        move || SecretsManager::new(codex_home, backend).values()
+    })
+    .await;
+    let values: Arc<[String]> = match result {
+        Ok(Ok(values)) => values.into(),
+        Ok(Err(err)) => {
+            warn!("failed to load stored secrets for redaction: {err:#}");
+            return Arc::from([]);
+        }
+        Err(err) => {
+            warn!("failed to load stored secrets for redaction: {err}");
+            return Arc::from([]);
+        }
+    };
+    cache.push((codex_home, backend, Arc::clone(&values)));
+    values
+}
+
+/// Redacts tool output carried by `items`. Other item kinds are returned
+/// unchanged.
+pub(crate) fn redact_response_items(
+    redactor: &Redactor,
+    items: &[ResponseItem],
+) -> Vec<ResponseItem> {
+    items
+        .iter()
+        .cloned()
+        .map(|mut item| {
+            redact_response_item(redactor, &mut item);
+            item
+        })
+        .collect()
+}
+
+/// Redacts tool output and command output carried by rollout items.
+pub(crate) fn redact_rollout_items(redactor: &Redactor, items: &[RolloutItem]) -> Vec<RolloutItem> {
+    items
+        .iter()
+        .cloned()
+        .map(|mut item| {
+            match &mut item {
+                RolloutItem::ResponseItem(item) => redact_response_item(redactor, item),
+                RolloutItem::EventMsg(EventMsg::ExecCommandEnd(event)) => {
+                    redact_in_place(redactor, &mut event.stdout);
+                    redact_in_place(redactor, &mut event.stderr);
+                    redact_in_place(redactor, &mut event.aggregated_output);
+                    redact_in_place(redactor, &mut event.formatted_output);
+                }
+                RolloutItem::SessionMeta(_)
+                | RolloutItem::Compacted(_)
+                | RolloutItem::TurnContext(_)
+                | RolloutItem::EventMsg(_) => {}
+            }
+            item
+        })
+        .collect()
+}
+
+fn redact_response_item(redactor: &Redactor, item: &mut ResponseItem) {
+    match item {
+        ResponseItem::FunctionCallOutput { output, .. }
+        | ResponseItem::CustomToolCallOutput { output, .. } => redact_payload(redactor, output),
+        _ => {}
+    }
+}
+
+fn redact_payload(redactor: &Redactor, payload: &mut FunctionCallOutputPayload) {
+    match &mut payload.body {
+        FunctionCallOutputBody::Text(text) => redact_in_place(redactor, text),
+        FunctionCallOutputBody::ContentItems(items) => {
+            for item in items {
+                if let FunctionCallOutputContentItem::InputText { text } = item {
+                    redact_in_place(redactor, text);
+                }
+            }
+        }
+    }
+}
+
+fn redact_in_place(redactor: &Redactor, text: &mut String) {
+    *text = redactor.redact(text);
+}
+
+#[cfg(test)]
+#[path = "redaction_tests.rs"]
+mod tests;
@@ -0,0 +1,84 @@
+use pretty_assertions::assert_eq;
+
+use super::*;
+use codex_protocol::models::ContentItem;
+
+const SECRET: &str = "acme-secret-value-1234";
+
+fn redactor() -> Redactor {
+    Redactor::default().with_known_values(vec![SECRET.to_string()])
+}
+
+fn function_output(body: FunctionCallOutputBody) -> ResponseItem {
+    ResponseItem::FunctionCallOutput {
+        call_id: "call-1".to_string(),
+        output: FunctionCallOutputPayload {
+            body,
+            success: Some(true),
+        },
+    }
+}
+
+#[test]
+fn tool_outputs_are_redacted_and_messages_are_kept() {
+    let message = ResponseItem::Message {
+        id: None,
+        role: "user".to_string(),
+        content: vec![ContentItem::InputText {
+            text: format!("use {SECRET}"),
+        }],
+        end_turn: None,
+        phase: None,
+    };
+    let items = vec![
+        function_output(FunctionCallOutputBody::Text(format!("token={SECRET}"))),
+        function_output(FunctionCallOutputBody::ContentItems(vec![
+            FunctionCallOutputContentItem::InputText {
+                text: format!("{SECRET}\n"),
+            },
+            FunctionCallOutputContentItem::InputImage {
+                image_url: "data:image/png;base64,AAAA".to_string(),
+                detail: None,
+            },
+        ])),
+        message.clone(),
+    ];
+
+    assert_eq!(
+        redact_response_items(&redactor(), &items),
+        vec![
+            function_output(FunctionCallOutputBody::Text(
+                "token=[REDACTED_SECRET]".to_string()
+            )),
+            function_output(FunctionCallOutputBody::ContentItems(vec![
+                FunctionCallOutputContentItem::InputText {
+                    text: "[REDACTED_SECRET]\n".to_string(),
+                },
+                FunctionCallOutputContentItem::InputImage {
+                    image_url: "data:image/png;base64,AAAA".to_string(),
+                    detail: None,
+                },
+            ])),
+            message,
+        ]
+    );
+}
+
+#[test]
+fn rollout_response_items_are_redacted() {
+    let items = vec![RolloutItem::ResponseItem(function_output(
+        FunctionCallOutputBody::Text(SECRET.to_string()),
+    ))];
+
+    let redacted = redact_rollout_items(&redactor(), &items);
+
+    let [RolloutItem::ResponseItem(item)] = redacted.as_slice() else {
+        panic!("expected a single response item, got {redacted:?}");
+    };
+    assert_eq!(
+        item,
+        &function_output(FunctionCallOutputBody::Text(
+            "[REDACTED_SECRET]".to_string()
+        ))
+    );
+}
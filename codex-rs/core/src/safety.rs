@@ -64,6 +64,19 @@ pub fn assess_patch_safety(
             AskForApproval::Granular(granular_config) if !granular_config.sandbox_approval
         );
 
+    // A symlink can be created inside the writable roots yet point outside
+    // them, which later writes through the link would escape, so the user has
+    // to approve it regardless of the sandbox.
+    if !are_symlink_targets_constrained_to_writable_paths(action, file_system_sandbox_policy, cwd) {
+        return if rejects_sandbox_approval {
+            SafetyCheck::Reject {
+                reason: patch_rejection_reason(sandbox_policy).to_string(),
+            }
+        } else {
+            SafetyCheck::AskUser
+        };
+    }
+
     // Even though the patch appears to be constrained to writable paths, it is
     // possible that paths in the patch are hard links to files outside the
     // writable roots, so we should still run `apply_patch` in a sandbox in that case.
@@ -117,53 +130,76 @@ fn patch_rejection_reason(sandbox_policy: &SandboxPolicy) -> &'static str {
     }
 }
 
-fn is_write_patch_constrained_to_writable_paths(
-    action: &ApplyPatchAction,
-    file_system_sandbox_policy: &FileSystemSandboxPolicy,
-    cwd: &AbsolutePathBuf,
-) -> bool {
-    // Normalize a path by removing `.` and resolving `..` without touching the
-    // filesystem (works even if the file does not exist).
-    fn normalize(path: &Path) -> Option<PathBuf> {
-        let mut out = PathBuf::new();
-        for comp in path.components() {
-            match comp {
-                Component::ParentDir => {
-                    out.pop();
-                }
-                Component::CurDir => { /* skip */ }
-                other => out.push(other.as_os_str()),
+// Normalize a path by removing `.` and resolving `..` without touching the
+// filesystem (works even if the file does not exist).
+fn normalize(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for comp in path.components() {
+        match comp {
+            Component::ParentDir => {
+                out.pop();
             }
+            Component::CurDir => { /* skip */ }
+            other => out.push(other.as_os_str()),
         }
-        Some(out)
     }
+    out
+}
 
-    // Determine whether `path` is inside **any** writable root. Both `path`
-    // and roots are converted to absolute, normalized forms before the
-    // prefix check.
-    let is_path_writable = |p: &PathBuf| {
-        let abs = resolve_path(cwd, p);
-        let abs = match normalize(&abs) {
-            Some(v) => v,
-            None => return false,
+// Determine whether `path` is inside **any** writable root. Both `path` and
+// roots are converted to absolute, normalized forms before the prefix check.
+fn is_path_writable(
+    path: &PathBuf,
+    file_system_sandbox_policy: &FileSystemSandboxPolicy,
+    cwd: &AbsolutePathBuf,
+) -> bool {
+    let abs = normalize(&resolve_path(cwd, path));
+    file_system_sandbox_policy.can_write_path_with_cwd(&abs, cwd)
+}
+
+/// Checks that every symlink the patch adds points inside the writable roots.
+/// Relative targets are resolved against the directory containing the link.
+fn are_symlink_targets_constrained_to_writable_paths(
+    action: &ApplyPatchAction,
+    file_system_sandbox_policy: &FileSystemSandboxPolicy,
+    cwd: &AbsolutePathBuf,
+) -> bool {
+    action.changes().iter().all(|(path, change)| {
+        let ApplyPatchFileChange::AddSymlink { target } = change else {
+            return true;
         };
+        let link = resolve_path(cwd, path);
+        let link_dir = link.parent().unwrap_or(&link);
+        let target = resolve_path(link_dir, target);
+        is_path_writable(&target, file_system_sandbox_policy, cwd)
+    })
+}
 
-        file_system_sandbox_policy.can_write_path_with_cwd(&abs, cwd)
-    };
+fn is_write_patch_constrained_to_writable_paths(
+    action: &ApplyPatchAction,
+    file_system_sandbox_policy: &FileSystemSandboxPolicy,
+    cwd: &AbsolutePathBuf,
+) -> bool {
+    let is_writable = |p: &PathBuf| is_path_writable(p, file_system_sandbox_policy, cwd);
 
     for (path, change) in action.changes() {
         match change {
-            ApplyPatchFileChange::Add { .. } | ApplyPatchFileChange::Delete { .. } => {
-                if !is_path_writable(path) {
+            ApplyPatchFileChange::Add { .. }
+            | ApplyPatchFileChange::AddBinary { .. }
+            | ApplyPatchFileChange::UpdateBinary { .. }
+            | ApplyPatchFileChange::AddSymlink { .. }
+            | ApplyPatchFileChange::SetMode { .. }
+            | ApplyPatchFileChange::Delete { .. } => {
+                if !is_writable(path) {
                     return false;
                 }
             }
             ApplyPatchFileChange::Update { move_path, .. } => {
-                if !is_path_writable(path) {
+                if !is_writable(path) {
                     return false;
                 }
                 if let Some(dest) = move_path
-                    && !is_path_writable(dest)
+                    && !is_writable(dest)
                 {
                     return false;
                 }
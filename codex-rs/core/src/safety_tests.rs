@@ -62,6 +62,51 @@ fn test_writable_roots_constraint() {
     ));
 }
 
+#[test]
+fn symlink_to_target_outside_writable_roots_requires_approval() {
+    let tmp = TempDir::new().unwrap();
+    let cwd = tmp.path().abs();
+    let link_path = cwd.join("link");
+    let policy = SandboxPolicy::WorkspaceWrite {
+        writable_roots: vec![],
+        read_only_access: Default::default(),
+        network_access: false,
+        exclude_tmpdir_env_var: true,
+        exclude_slash_tmp: true,
+    };
+    let assess_action = |action: &ApplyPatchAction| {
+        assess_patch_safety(
+            action,
+            AskForApproval::OnRequest,
+            &policy,
+            &FileSystemSandboxPolicy::from(&policy),
+            &cwd,
+            WindowsSandboxLevel::Disabled,
+        )
+    };
+
+    let assess_symlink = |target: PathBuf| {
+        assess_action(&ApplyPatchAction::new_add_symlink_for_test(
+            &link_path, target,
+        ))
+    };
+    let outside = cwd.parent().unwrap().join("outside.txt");
+
+    assert_eq!(
+        assess_symlink(PathBuf::from("../outside.txt")),
+        SafetyCheck::AskUser
+    );
+    assert_eq!(assess_symlink(outside.to_path_buf()), SafetyCheck::AskUser);
+    // A target inside the workspace is assessed like any other in-root write.
+    assert_eq!(
+        assess_symlink(PathBuf::from("nested/../inner.txt")),
+        assess_action(&ApplyPatchAction::new_add_for_test(
+            &cwd.join("inner.txt"),
+            String::new(),
+        ))
+    );
+}
+
 #[test]
 fn external_sandbox_auto_approves_in_on_request() {
     let tmp = TempDir::new().unwrap();
@@ -25,12 +25,14 @@ use codex_protocol::protocol::SandboxPolicy;
 use codex_sandboxing::SandboxExecRequest;
 use codex_sandboxing::SandboxType;
 use codex_utils_absolute_path::AbsolutePathBuf;
+use codex_utils_pty::resource_limits::ResourceLimits;
 use std::collections::HashMap;
 
 #[derive(Debug)]
 pub(crate) struct ExecOptions {
     pub(crate) expiration: ExecExpiration,
     pub(crate) capture_policy: ExecCapturePolicy,
+    pub(crate) resource_limits: ResourceLimits,
 }
 
 #[derive(Clone, Debug)]
@@ -48,6 +50,7 @@ pub struct ExecRequest {
     pub network: Option<NetworkProxy>,
     pub expiration: ExecExpiration,
     pub capture_policy: ExecCapturePolicy,
+    pub resource_limits: ResourceLimits,
     pub sandbox: SandboxType,
     pub windows_sandbox_policy_cwd: AbsolutePathBuf,
     pub windows_sandbox_level: WindowsSandboxLevel,
@@ -85,6 +88,7 @@ impl ExecRequest {
             network,
             expiration,
             capture_policy,
+            resource_limits: ResourceLimits::default(),
             sandbox,
             windows_sandbox_policy_cwd,
             windows_sandbox_level,
@@ -118,6 +122,7 @@ impl ExecRequest {
         let ExecOptions {
             expiration,
             capture_policy,
+            resource_limits,
         } = options;
         if !network_sandbox_policy.is_enabled() {
             env.insert(
@@ -137,6 +142,7 @@ impl ExecRequest {
             network,
             expiration,
             capture_policy,
+            resource_limits,
             sandbox,
             windows_sandbox_policy_cwd,
             windows_sandbox_level,
@@ -36,6 +36,9 @@ use crate::installation_id::resolve_installation_id;
 use crate::parse_turn_item;
 use crate::path_utils::normalize_for_native_workdir;
 use crate::realtime_conversation::RealtimeConversationManager;
+use crate::redaction::build_session_redactor;
+use crate::redaction::redact_response_items;
+use crate::redaction::redact_rollout_items;
 use crate::rollout::find_thread_name_by_id;
 use crate::session_prefix::format_subagent_notification_message;
 use crate::skills::SkillRenderSideEffects;
@@ -58,6 +61,7 @@ use codex_features::Feature;
 use codex_features::unstable_features_warning_event;
 use codex_hooks::Hooks;
 use codex_hooks::HooksConfig;
+use codex_hooks::PreToolUsePermissionDecision;
 use codex_login::AuthManager;
 use codex_login::CodexAuth;
 use codex_login::auth_env_telemetry::collect_auth_env_telemetry;
@@ -272,7 +276,10 @@ use crate::exec_policy::ExecPolicyUpdateError;
 use crate::guardian::GuardianReviewSessionManager;
 use crate::mcp::McpManager;
 use crate::memories;
+use crate::model_hook_runner::SessionModelHookRunner;
 use crate::network_policy_decision::execpolicy_network_rule_amendment;
+use crate::network_traffic::build_network_traffic_observer;
+use crate::network_traffic::network_traffic_log_path;
 use crate::plugins::PluginsManager;
 use crate::rollout::map_session_init_error;
 use crate::session_startup_prewarm::SessionStartupPrewarmHandle;
@@ -1069,7 +1076,7 @@ impl Session {
         }
     }
 
-    fn next_internal_sub_id(&self) -> String {
+    pub(crate) fn next_internal_sub_id(&self) -> String {
         let id = self
             .next_internal_sub_id
             .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
@@ -1681,6 +1688,16 @@ impl Session {
             .map(|task| Arc::clone(&task.turn_context))
     }
 
+    /// Turn context that hooks firing outside a tool call should report against.
+    ///
+    /// Uses the active turn when there is one so hook events land in that turn.
+    pub(crate) async fn hook_turn_context(&self) -> Arc<TurnContext> {
+        match self.active_turn_context_and_cancellation_token().await {
+            Some((turn_context, _)) => turn_context,
+            None => self.new_default_turn().await,
+        }
+    }
+
     async fn active_turn_context_and_cancellation_token(
         &self,
     ) -> Option<(Arc<TurnContext>, CancellationToken)> {
@@ -2054,13 +2071,16 @@ impl Session {
                         strict_auto_review: false,
                     },
                 },
-                ReviewDecision::Abort | ReviewDecision::Denied | ReviewDecision::TimedOut => {
-                    RequestPermissionsResponse {
-                        permissions: RequestPermissionProfile::default(),
-                        scope: PermissionGrantScope::Turn,
-                        strict_auto_review: false,
-                    }
-                }
+                // Partial and edited approvals only apply to patches.
+                ReviewDecision::Abort
+                | ReviewDecision::Denied
+                | ReviewDecision::TimedOut
+                | ReviewDecision::ApprovedPartialPatch { .. }
+                | ReviewDecision::ApprovedEditedPatch { .. } => RequestPermissionsResponse {
+                    permissions: RequestPermissionProfile::default(),
+                    scope: PermissionGrantScope::Turn,
+                    strict_auto_review: false,
+                },
             };
             let response = Self::normalize_request_permissions_response(
                 requested_permissions,
@@ -2305,6 +2325,30 @@ impl Session {
         ts.strict_auto_review_enabled()
     }
 
+    /// Holds a `PreToolUse` hook's approval verdict until the tool's approval step reads it.
+    pub(crate) async fn record_pre_tool_use_permission_decision(
+        &self,
+        call_id: &str,
+        decision: PreToolUsePermissionDecision,
+    ) {
+        let active = self.active_turn.lock().await;
+        let Some(active) = active.as_ref() else {
+            return;
+        };
+        let mut ts = active.turn_state.lock().await;
+        ts.record_pre_tool_use_permission_decision(call_id.to_string(), decision);
+    }
+
+    pub(crate) async fn take_pre_tool_use_permission_decision(
+        &self,
+        call_id: &str,
+    ) -> Option<PreToolUsePermissionDecision> {
+        let active = self.active_turn.lock().await;
+        let active = active.as_ref()?;
+        let mut ts = active.turn_state.lock().await;
+        ts.take_pre_tool_use_permission_decision(call_id)
+    }
+
     pub(crate) async fn granted_session_permissions(&self) -> Option<AdditionalPermissionProfile> {
         let state = self.state.lock().await;
         state.granted_permissions()
@@ -2367,9 +2411,10 @@ impl Session {
         turn_context: &TurnContext,
         items: &[ResponseItem],
     ) {
-        self.record_into_history(items, turn_context).await;
-        self.persist_rollout_response_items(items).await;
-        self.send_raw_response_items(turn_context, items).await;
+        let items = redact_response_items(&self.services.redactor, items);
+        self.record_into_history(&items, turn_context).await;
+        self.persist_rollout_response_items(&items).await;
+        self.send_raw_response_items(turn_context, &items).await;
     }
 
     /// Append ResponseItems to the in-memory conversation history only.
@@ -2719,8 +2764,9 @@ impl Session {
     }
 
     pub(crate) async fn persist_rollout_items(&self, items: &[RolloutItem]) {
+        let items = redact_rollout_items(&self.services.redactor, items);
         if let Some(live_thread) = self.live_thread()
-            && let Err(e) = live_thread.append_items(items).await
+            && let Err(e) = live_thread.append_items(&items).await
         {
             error!("failed to record rollout items: {e:#}");
         }
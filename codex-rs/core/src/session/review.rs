@@ -144,6 +144,7 @@ pub(super) async fn spawn_review_thread(
         turn_timing_state: Arc::new(TurnTimingState::default()),
         server_model_warning_emitted: AtomicBool::new(false),
         model_verification_emitted: AtomicBool::new(false),
+        overlay_workspace: None,
     };
 
     // Seed the child task with the review prompt as the initial user message.
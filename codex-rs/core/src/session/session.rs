@@ -689,12 +689,17 @@ impl Session {
                 default_shell.derive_exec_args("", /*use_login_shell*/ false);
             let hook_shell_program = hook_shell_argv.remove(0);
             let _ = hook_shell_argv.pop();
+            // Prompt and agent hooks call back into the session once it exists.
+            let model_hook_session = Arc::new(RwLock::new(std::sync::Weak::<Session>::new()));
             let hooks = Hooks::new(HooksConfig {
                 legacy_notify_argv: config.notify.clone(),
                 feature_enabled: config.features.enabled(Feature::CodexHooks),
                 config_layer_stack: Some(config.config_layer_stack.clone()),
                 shell_program: Some(hook_shell_program),
                 shell_args: hook_shell_argv,
+                model_hook_runner: Some(Arc::new(SessionModelHookRunner::new(Arc::clone(
+                    &model_hook_session,
+                )))),
             });
             for warning in hooks.startup_warnings() {
                 post_session_configured_events.push(Event {
@@ -713,6 +718,12 @@ impl Session {
                     config.analytics_enabled,
                 )
             });
+            let redactor = build_session_redactor(
+                &config.redaction,
+                config.codex_home.to_path_buf(),
+                config.secrets_backend.clone(),
+            )
+            .await;
             let services = SessionServices {
                 // Initialize the MCP connection manager with an uninitialized
                 // instance. It will be replaced with one created via
@@ -770,6 +781,7 @@ impl Session {
                     config.js_repl_node_path.clone(),
                 ),
                 environment_manager,
+                redactor,
             };
             services
                 .model_client
@@ -805,6 +817,20 @@ impl Session {
                 let mut guard = network_policy_decider_session.write().await;
                 *guard = Arc::downgrade(&sess);
             }
+            *model_hook_session.write().await = Arc::downgrade(&sess);
+            if let Some(started_proxy) = sess.services.network_proxy.as_ref() {
+                let observer = build_network_traffic_observer(
+                    Arc::downgrade(&sess),
+                    network_traffic_log_path(
+                        config.codex_home.as_path(),
+                        &conversation_id.to_string(),
+                    ),
+                );
+                started_proxy
+                    .proxy()
+                    .set_traffic_observer(Some(observer))
+                    .await;
+            }
             // Dispatch the SessionConfiguredEvent first and then report any errors.
             // If resuming, include converted initial messages in the payload so UIs can render them immediately.
             let initial_messages = initial_history.get_event_msgs();
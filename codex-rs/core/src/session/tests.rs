@@ -3328,6 +3328,7 @@ pub(crate) async fn make_session_and_context() -> (Session, TurnContext) {
             config.js_repl_node_path.clone(),
         ),
         environment_manager: Arc::new(codex_exec_server::EnvironmentManager::default_for_tests()),
+        redactor: Arc::new(codex_secrets::Redactor::default()),
     };
     let js_repl = Arc::new(JsReplHandle::with_node_path(
         config.js_repl_node_path.clone(),
@@ -4691,6 +4692,7 @@ pub(crate) async fn make_session_and_context_with_dynamic_tools_and_rx(
             config.js_repl_node_path.clone(),
         ),
         environment_manager: Arc::new(codex_exec_server::EnvironmentManager::default_for_tests()),
+        redactor: Arc::new(codex_secrets::Redactor::default()),
     };
     let js_repl = Arc::new(JsReplHandle::with_node_path(
         config.js_repl_node_path.clone(),
@@ -6985,6 +6987,7 @@ async fn rejects_escalated_permissions_when_policy_not_on_request() {
         .exec_policy
         .create_exec_approval_requirement_for_command(ExecApprovalRequest {
             command: &params.command,
+            cwd: &turn_context.cwd,
             approval_policy: turn_context.approval_policy.value(),
             sandbox_policy: turn_context.sandbox_policy.get(),
             file_system_sandbox_policy: &turn_context.file_system_sandbox_policy,
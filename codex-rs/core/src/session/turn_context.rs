@@ -1,9 +1,11 @@
 use super::*;
+use crate::overlay_workspace::overlay_workspace_for_turn;
 use codex_model_provider::SharedModelProvider;
 use codex_model_provider::create_model_provider;
 use codex_protocol::models::AdditionalPermissionProfile;
 use codex_protocol::models::SandboxEnforcement;
 use codex_protocol::protocol::TurnEnvironmentSelection;
+use codex_sandboxing::overlay::OverlayWorkspace;
 use codex_sandboxing::policy_transforms::effective_file_system_sandbox_policy;
 use codex_sandboxing::policy_transforms::effective_network_sandbox_policy;
 use std::sync::atomic::AtomicBool;
@@ -94,6 +96,9 @@ pub(crate) struct TurnContext {
     pub(crate) turn_timing_state: Arc<TurnTimingState>,
     pub(crate) server_model_warning_emitted: AtomicBool,
     pub(crate) model_verification_emitted: AtomicBool,
+    /// Copy-on-write overlay that sandboxed writes to `cwd` land in for this
+    /// turn; committed or discarded when the task finishes.
+    pub(crate) overlay_workspace: Option<OverlayWorkspace>,
 }
 impl TurnContext {
     pub(crate) fn permission_profile(&self) -> PermissionProfile {
@@ -238,6 +243,7 @@ impl TurnContext {
             model_verification_emitted: AtomicBool::new(
                 self.model_verification_emitted.load(Ordering::Relaxed),
             ),
+            overlay_workspace: self.overlay_workspace.clone(),
         }
     }
 
@@ -272,6 +278,7 @@ impl TurnContext {
                 .permissions
                 .windows_sandbox_private_desktop,
             use_legacy_landlock: self.features.use_legacy_landlock(),
+            overlay_workspace: self.overlay_workspace.clone(),
         }
     }
 
@@ -458,6 +465,15 @@ impl Session {
             session_configuration.windows_sandbox_level,
         ));
         let (current_date, timezone) = local_time_context();
+        let overlay_workspace = overlay_workspace_for_turn(
+            &per_turn_config,
+            environment.as_deref(),
+            session_configuration.sandbox_policy.get(),
+            session_configuration.approval_policy.value(),
+            &cwd,
+            conversation_id,
+            &sub_id,
+        );
         TurnContext {
             sub_id,
             trace_id: current_span_trace_id(),
@@ -503,6 +519,7 @@ impl Session {
             turn_timing_state: Arc::new(TurnTimingState::default()),
             server_model_warning_emitted: AtomicBool::new(false),
             model_verification_emitted: AtomicBool::new(false),
+            overlay_workspace,
         }
     }
 
@@ -1,5 +1,6 @@
 use codex_network_proxy::NetworkProxy;
 use codex_utils_absolute_path::AbsolutePathBuf;
+use codex_utils_pty::resource_limits::ResourceLimiter;
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::process::Stdio;
@@ -46,6 +47,8 @@ pub(crate) struct SpawnChildRequest<'a> {
     pub network: Option<&'a NetworkProxy>,
     pub stdio_policy: StdioPolicy,
     pub env: HashMap<String, String>,
+    /// Rlimits and cgroup placement applied in the child before `exec`.
+    pub resource_limiter: Option<&'a ResourceLimiter>,
 }
 
 pub(crate) async fn spawn_child_async(request: SpawnChildRequest<'_>) -> std::io::Result<Child> {
@@ -58,6 +61,7 @@ pub(crate) async fn spawn_child_async(request: SpawnChildRequest<'_>) -> std::io
         network,
         stdio_policy,
         mut env,
+        resource_limiter,
     } = request;
 
     trace!(
@@ -88,6 +92,7 @@ pub(crate) async fn spawn_child_async(request: SpawnChildRequest<'_>) -> std::io
         let detach_from_tty = matches!(stdio_policy, StdioPolicy::RedirectForShellTool);
         #[cfg(target_os = "linux")]
         let parent_pid = libc::getpid();
+        let pre_exec_limits = resource_limiter.map(ResourceLimiter::pre_exec);
         cmd.pre_exec(move || {
             if detach_from_tty {
                 codex_utils_pty::process_group::detach_from_tty()?;
@@ -100,10 +105,19 @@ pub(crate) async fn spawn_child_async(request: SpawnChildRequest<'_>) -> std::io
                 // current parent dies."
                 codex_utils_pty::process_group::set_parent_death_signal(parent_pid)?;
             }
+
+            // SAFETY: `apply` only makes async-signal-safe calls, which is
+            // what `pre_exec` requires between fork and exec.
+            if let Some(limits) = pre_exec_limits {
+                limits.apply()?;
+            }
             Ok(())
         });
     }
 
+    #[cfg(not(unix))]
+    let _ = resource_limiter;
+
     match stdio_policy {
         StdioPolicy::RedirectForShellTool => {
             // Do not create a file descriptor for stdin because otherwise some
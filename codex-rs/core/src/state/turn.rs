@@ -9,6 +9,7 @@ use tokio::sync::Notify;
 use tokio_util::sync::CancellationToken;
 use tokio_util::task::AbortOnDropHandle;
 
+use codex_hooks::PreToolUsePermissionDecision;
 use codex_protocol::dynamic_tools::DynamicToolResponse;
 use codex_protocol::models::ResponseInputItem;
 use codex_protocol::request_permissions::RequestPermissionProfile;
@@ -107,6 +108,7 @@ pub(crate) struct TurnState {
     mailbox_delivery_phase: MailboxDeliveryPhase,
     granted_permissions: Option<AdditionalPermissionProfile>,
     strict_auto_review_enabled: bool,
+    pre_tool_use_permission_decisions: HashMap<String, PreToolUsePermissionDecision>,
     pub(crate) tool_calls: u64,
     pub(crate) has_memory_citation: bool,
     pub(crate) token_usage_at_turn_start: TokenUsage,
@@ -263,6 +265,22 @@ impl TurnState {
     pub(crate) fn strict_auto_review_enabled(&self) -> bool {
         self.strict_auto_review_enabled
     }
+
+    pub(crate) fn record_pre_tool_use_permission_decision(
+        &mut self,
+        call_id: String,
+        decision: PreToolUsePermissionDecision,
+    ) {
+        self.pre_tool_use_permission_decisions
+            .insert(call_id, decision);
+    }
+
+    pub(crate) fn take_pre_tool_use_permission_decision(
+        &mut self,
+        call_id: &str,
+    ) -> Option<PreToolUsePermissionDecision> {
+        self.pre_tool_use_permission_decisions.remove(call_id)
+    }
 }
 
 impl ActiveTurn {
@@ -2,6 +2,8 @@ use std::sync::Arc;
 
 use tokio_util::sync::CancellationToken;
 
+use crate::overlay_workspace::finish_overlay_turn;
+use crate::overlay_workspace::keep_aborted_overlay;
 use crate::session::turn::run_turn;
 use crate::session::turn_context::TurnContext;
 use crate::session_startup_prewarm::SessionStartupPrewarmResolution;
@@ -75,9 +77,14 @@ impl SessionTask for RegularTask {
             .instrument(run_turn_span.clone())
             .await;
             if !sess.has_pending_input().await {
+                finish_overlay_turn(&sess, &ctx).await;
                 return last_agent_message;
             }
             next_input = Vec::new();
         }
     }
+
+    async fn abort(&self, session: Arc<SessionTaskContext>, ctx: Arc<TurnContext>) {
+        keep_aborted_overlay(&session.clone_session(), &ctx).await;
+    }
 }
@@ -29,6 +29,7 @@ use codex_protocol::protocol::SandboxPolicy;
 use codex_protocol::protocol::TurnStartedEvent;
 use codex_sandboxing::SandboxType;
 use codex_shell_command::parse_command::parse_command;
+use codex_utils_pty::resource_limits::ResourceLimits;
 
 use super::SessionTask;
 use super::SessionTaskContext;
@@ -182,6 +183,7 @@ pub(crate) async fn execute_user_shell_command(
         network_sandbox_policy: NetworkSandboxPolicy::from(&sandbox_policy),
         windows_sandbox_filesystem_overrides: None,
         arg0: None,
+        resource_limits: ResourceLimits::default(),
     };
 
     let stdout_stream = Some(StdoutStream {
@@ -323,6 +323,22 @@ impl ToolEmitter {
                 let result = Err(FunctionCallError::RespondToModel(response));
                 (event, result)
             }
+            Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::ResourceLimitExceeded {
+                limit,
+                output,
+            }))) => {
+                // Lead with the limit so the model knows the command was
+                // stopped on purpose rather than crashing on its own.
+                let mut model_output = (*output).clone();
+                model_output.aggregated_output.text = format!(
+                    "command was killed: it exceeded the {limit} limit set by the permissions profile\n{}",
+                    model_output.aggregated_output.text
+                );
+                let response = self.format_exec_output_for_model(&model_output, ctx);
+                let event = ToolEventStage::Failure(ToolEventFailure::Output(*output));
+                let result = Err(FunctionCallError::RespondToModel(response));
+                (event, result)
+            }
             Err(ToolError::Codex(err)) => {
                 let message = format!("execution error: {err:?}");
                 let event = ToolEventStage::Failure(ToolEventFailure::Message(message.clone()));
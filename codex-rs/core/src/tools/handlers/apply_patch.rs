@@ -29,6 +29,8 @@ use crate::tools::registry::PreToolUsePayload;
 use crate::tools::registry::ToolArgumentDiffConsumer;
 use crate::tools::registry::ToolHandler;
 use crate::tools::registry::ToolKind;
+use crate::tools::registry::pre_tool_use_updated_command;
+use crate::tools::registry::replace_function_argument;
 use crate::tools::runtimes::apply_patch::ApplyPatchRequest;
 use crate::tools::runtimes::apply_patch::ApplyPatchRuntime;
 use crate::tools::sandboxing::ToolCtx;
@@ -141,6 +143,10 @@ fn convert_apply_patch_hunks_to_protocol(hunks: &[Hunk]) -> HashMap<PathBuf, Fil
                     unified_diff: format_update_chunks_for_progress(chunks),
                     move_path: move_path.clone(),
                 },
+                Hunk::AddBinaryFile { contents, .. } => apply_patch::binary_add_change(contents),
+                Hunk::UpdateBinaryFile { .. } => apply_patch::binary_update_change(),
+                Hunk::AddSymlink { target, .. } => apply_patch::symlink_add_change(target),
+                Hunk::SetMode { mode, .. } => apply_patch::set_mode_change(*mode),
             };
             (path, change)
         })
@@ -149,9 +155,13 @@ fn convert_apply_patch_hunks_to_protocol(hunks: &[Hunk]) -> HashMap<PathBuf, Fil
 
 fn hunk_source_path(hunk: &Hunk) -> &Path {
     match hunk {
-        Hunk::AddFile { path, .. } | Hunk::DeleteFile { path } | Hunk::UpdateFile { path, .. } => {
-            path
-        }
+        Hunk::AddFile { path, .. }
+        | Hunk::DeleteFile { path }
+        | Hunk::UpdateFile { path, .. }
+        | Hunk::AddBinaryFile { path, .. }
+        | Hunk::UpdateBinaryFile { path, .. }
+        | Hunk::AddSymlink { path, .. }
+        | Hunk::SetMode { path, .. } => path,
     }
 }
 
@@ -320,6 +330,27 @@ impl ToolHandler for ApplyPatchHandler {
         })
     }
 
+    fn payload_with_pre_tool_use_input(
+        &self,
+        invocation: &ToolInvocation,
+        updated_input: serde_json::Value,
+    ) -> Result<ToolPayload, String> {
+        let command = pre_tool_use_updated_command(updated_input)?;
+        match &invocation.payload {
+            ToolPayload::Function { arguments } => {
+                let arguments = replace_function_argument(
+                    arguments,
+                    "input",
+                    serde_json::Value::String(command),
+                )?;
+                parse_arguments::<ApplyPatchToolArgs>(&arguments).map_err(|err| err.to_string())?;
+                Ok(ToolPayload::Function { arguments })
+            }
+            ToolPayload::Custom { .. } => Ok(ToolPayload::Custom { input: command }),
+            _ => Err("unsupported payload for apply_patch handler".to_string()),
+        }
+    }
+
     fn post_tool_use_payload(
         &self,
         invocation: &ToolInvocation,
@@ -416,7 +447,8 @@ impl ToolHandler for ApplyPatchHandler {
                         };
 
                         let mut orchestrator = ToolOrchestrator::new();
-                        let mut runtime = ApplyPatchRuntime::new();
+                        let mut runtime =
+                            ApplyPatchRuntime::new().with_turn_diff_tracker(Some(tracker.clone()));
                         let tool_ctx = ToolCtx {
                             session: session.clone(),
                             turn: turn.clone(),
@@ -433,6 +465,11 @@ impl ToolHandler for ApplyPatchHandler {
                             )
                             .await
                             .map(|result| result.output);
+                        // Report what was applied when approval narrowed or edited the patch.
+                        let emitter = match runtime.take_applied_changes() {
+                            Some(changes) => ToolEmitter::apply_patch(changes, apply.auto_approved),
+                            None => emitter,
+                        };
                         let event_ctx = ToolEventCtx::new(
                             session.as_ref(),
                             turn.as_ref(),
@@ -524,7 +561,8 @@ pub(crate) async fn intercept_apply_patch(
                     };
 
                     let mut orchestrator = ToolOrchestrator::new();
-                    let mut runtime = ApplyPatchRuntime::new();
+                    let mut runtime =
+                        ApplyPatchRuntime::new().with_turn_diff_tracker(tracker.cloned());
                     let tool_ctx = ToolCtx {
                         session: session.clone(),
                         turn: turn.clone(),
@@ -541,6 +579,11 @@ pub(crate) async fn intercept_apply_patch(
                         )
                         .await
                         .map(|result| result.output);
+                    // Report what was applied when approval narrowed or edited the patch.
+                    let emitter = match runtime.take_applied_changes() {
+                        Some(changes) => ToolEmitter::apply_patch(changes, apply.auto_approved),
+                        None => emitter,
+                    };
                     let event_ctx = ToolEventCtx::new(
                         session.as_ref(),
                         turn.as_ref(),
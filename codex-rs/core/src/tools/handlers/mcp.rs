@@ -34,6 +34,25 @@ impl ToolHandler for McpHandler {
         })
     }
 
+    fn payload_with_pre_tool_use_input(
+        &self,
+        invocation: &ToolInvocation,
+        updated_input: Value,
+    ) -> Result<ToolPayload, String> {
+        let ToolPayload::Mcp { server, tool, .. } = &invocation.payload else {
+            return Err("unsupported payload for mcp handler".to_string());
+        };
+        if !updated_input.is_object() {
+            return Err("MCP tool arguments must be a JSON object".to_string());
+        }
+
+        Ok(ToolPayload::Mcp {
+            server: server.clone(),
+            tool: tool.clone(),
+            raw_arguments: updated_input.to_string(),
+        })
+    }
+
     fn post_tool_use_payload(
         &self,
         invocation: &ToolInvocation,
@@ -157,6 +176,49 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn mcp_pre_tool_use_updated_input_replaces_raw_arguments() {
+        let (session, turn) = make_session_and_context().await;
+        let invocation = ToolInvocation {
+            session: session.into(),
+            turn: turn.into(),
+            cancellation_token: tokio_util::sync::CancellationToken::new(),
+            tracker: Arc::new(Mutex::new(TurnDiffTracker::new())),
+            call_id: "call-mcp-rewrite".to_string(),
+            tool_name: codex_tools::ToolName::namespaced("mcp__filesystem__", "read_file"),
+            source: ToolCallSource::Direct,
+            payload: ToolPayload::Mcp {
+                server: "filesystem".to_string(),
+                tool: "read_file".to_string(),
+                raw_arguments: json!({ "path": "/etc/shadow" }).to_string(),
+            },
+        };
+
+        let Ok(ToolPayload::Mcp {
+            server,
+            tool,
+            raw_arguments,
+        }) = McpHandler
+            .payload_with_pre_tool_use_input(&invocation, json!({ "path": "/tmp/notes.txt" }))
+        else {
+            panic!("expected rewritten MCP payload");
+        };
+        assert_eq!(
+            (server.as_str(), tool.as_str(), raw_arguments),
+            (
+                "filesystem",
+                "read_file",
+                json!({ "path": "/tmp/notes.txt" }).to_string()
+            )
+        );
+        assert_eq!(
+            McpHandler
+                .payload_with_pre_tool_use_input(&invocation, json!("/tmp/notes.txt"))
+                .err(),
+            Some("MCP tool arguments must be a JSON object".to_string())
+        );
+    }
+
     #[tokio::test]
     async fn mcp_post_tool_use_payload_uses_model_tool_name_args_and_result() {
         let payload = ToolPayload::Mcp {
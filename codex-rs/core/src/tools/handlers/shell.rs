@@ -31,6 +31,8 @@ use crate::tools::registry::PostToolUsePayload;
 use crate::tools::registry::PreToolUsePayload;
 use crate::tools::registry::ToolHandler;
 use crate::tools::registry::ToolKind;
+use crate::tools::registry::pre_tool_use_updated_command;
+use crate::tools::registry::replace_function_argument;
 use crate::tools::runtimes::shell::ShellRequest;
 use crate::tools::runtimes::shell::ShellRuntime;
 use crate::tools::runtimes::shell::ShellRuntimeBackend;
@@ -212,6 +214,32 @@ impl ToolHandler for ShellHandler {
         })
     }
 
+    fn payload_with_pre_tool_use_input(
+        &self,
+        invocation: &ToolInvocation,
+        updated_input: JsonValue,
+    ) -> Result<ToolPayload, String> {
+        let command = pre_tool_use_updated_command(updated_input)?;
+        let argv = shlex::split(&command)
+            .filter(|argv| !argv.is_empty())
+            .ok_or_else(|| format!("could not split command into arguments: {command}"))?;
+        match &invocation.payload {
+            ToolPayload::Function { arguments } => {
+                let arguments =
+                    replace_function_argument(arguments, "command", serde_json::json!(argv))?;
+                parse_arguments::<ShellToolCallParams>(&arguments)
+                    .map_err(|err| err.to_string())?;
+                Ok(ToolPayload::Function { arguments })
+            }
+            ToolPayload::LocalShell { params } => {
+                let mut params = params.clone();
+                params.command = argv;
+                Ok(ToolPayload::LocalShell { params })
+            }
+            _ => Err("unsupported payload for shell handler".to_string()),
+        }
+    }
+
     fn post_tool_use_payload(
         &self,
         invocation: &ToolInvocation,
@@ -326,6 +354,21 @@ impl ToolHandler for ShellCommandHandler {
         })
     }
 
+    fn payload_with_pre_tool_use_input(
+        &self,
+        invocation: &ToolInvocation,
+        updated_input: JsonValue,
+    ) -> Result<ToolPayload, String> {
+        let ToolPayload::Function { arguments } = &invocation.payload else {
+            return Err("unsupported payload for shell_command handler".to_string());
+        };
+        let command = pre_tool_use_updated_command(updated_input)?;
+        let arguments =
+            replace_function_argument(arguments, "command", JsonValue::String(command))?;
+        parse_arguments::<ShellCommandToolCallParams>(&arguments).map_err(|err| err.to_string())?;
+        Ok(ToolPayload::Function { arguments })
+    }
+
     fn post_tool_use_payload(
         &self,
         invocation: &ToolInvocation,
@@ -518,6 +561,7 @@ impl ShellHandler {
             .exec_policy
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 command: &exec_params.command,
+                cwd: &exec_params.cwd,
                 approval_policy: turn.approval_policy.value(),
                 sandbox_policy: turn.sandbox_policy.get(),
                 file_system_sandbox_policy: &turn.file_system_sandbox_policy,
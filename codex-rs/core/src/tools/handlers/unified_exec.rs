@@ -19,6 +19,8 @@ use crate::tools::registry::PostToolUsePayload;
 use crate::tools::registry::PreToolUsePayload;
 use crate::tools::registry::ToolHandler;
 use crate::tools::registry::ToolKind;
+use crate::tools::registry::pre_tool_use_updated_command;
+use crate::tools::registry::replace_function_argument;
 use crate::unified_exec::ExecCommandRequest;
 use crate::unified_exec::UnifiedExecContext;
 use crate::unified_exec::UnifiedExecError;
@@ -29,6 +31,7 @@ use crate::unified_exec::resolve_max_tokens;
 use codex_features::Feature;
 use codex_otel::SessionTelemetry;
 use codex_otel::TOOL_CALL_UNIFIED_EXEC_METRIC;
+use codex_protocol::error::ResourceLimitKind;
 use codex_protocol::models::AdditionalPermissionProfile;
 use codex_protocol::protocol::EventMsg;
 use codex_protocol::protocol::TerminalInteractionEvent;
@@ -152,6 +155,30 @@ impl ToolHandler for UnifiedExecHandler {
             })
     }
 
+    fn payload_with_pre_tool_use_input(
+        &self,
+        invocation: &ToolInvocation,
+        updated_input: serde_json::Value,
+    ) -> Result<ToolPayload, String> {
+        let ToolPayload::Function { arguments } = &invocation.payload else {
+            return Err("unsupported payload for unified exec handler".to_string());
+        };
+        if invocation.tool_name.namespace.is_some()
+            || invocation.tool_name.name.as_str() != "exec_command"
+        {
+            return Err(format!(
+                "{} does not accept rewritten input",
+                invocation.tool_name.display()
+            ));
+        }
+
+        let command = pre_tool_use_updated_command(updated_input)?;
+        let arguments =
+            replace_function_argument(arguments, "cmd", serde_json::Value::String(command))?;
+        parse_arguments::<ExecCommandArgs>(&arguments).map_err(|err| err.to_string())?;
+        Ok(ToolPayload::Function { arguments })
+    }
+
     fn post_tool_use_payload(
         &self,
         invocation: &ToolInvocation,
@@ -372,6 +399,22 @@ impl ToolHandler for UnifiedExecHandler {
                             hook_command: Some(hook_command),
                         }
                     }
+                    Err(UnifiedExecError::ResourceLimitExceeded { limit, output }) => {
+                        let output_text =
+                            resource_limit_exceeded_text(limit, &output.aggregated_output.text);
+                        let original_token_count = approx_token_count(&output_text);
+                        ExecCommandToolOutput {
+                            event_call_id: context.call_id.clone(),
+                            chunk_id: generate_chunk_id(),
+                            wall_time: output.duration,
+                            raw_output: output_text.into_bytes(),
+                            max_output_tokens: Some(max_output_tokens),
+                            process_id: None,
+                            exit_code: Some(output.exit_code),
+                            original_token_count: Some(original_token_count),
+                            hook_command: Some(hook_command),
+                        }
+                    }
                     Err(err) => {
                         return Err(FunctionCallError::RespondToModel(format!(
                             "exec_command failed for `{command_for_display}`: {err:?}"
@@ -391,8 +434,16 @@ impl ToolHandler for UnifiedExecHandler {
                         max_output_tokens: Some(max_output_tokens),
                     })
                     .await
-                    .map_err(|err| {
-                        FunctionCallError::RespondToModel(format!("write_stdin failed: {err}"))
+                    .map_err(|err| match err {
+                        UnifiedExecError::ResourceLimitExceeded { limit, output } => {
+                            FunctionCallError::RespondToModel(resource_limit_exceeded_text(
+                                limit,
+                                &output.aggregated_output.text,
+                            ))
+                        }
+                        err => {
+                            FunctionCallError::RespondToModel(format!("write_stdin failed: {err}"))
+                        }
                     })?;
 
                 let interaction = TerminalInteractionEvent {
@@ -417,6 +468,14 @@ impl ToolHandler for UnifiedExecHandler {
     }
 }
 
+/// Leads with the limit so the model knows the command was stopped on purpose
+/// rather than crashing on its own.
+fn resource_limit_exceeded_text(limit: ResourceLimitKind, output: &str) -> String {
+    format!(
+        "command was killed: it exceeded the {limit} limit set by the permissions profile\n{output}"
+    )
+}
+
 fn emit_unified_exec_tty_metric(session_telemetry: &SessionTelemetry, tty: bool) {
     session_telemetry.counter(
         TOOL_CALL_UNIFIED_EXEC_METRIC,
@@ -11,6 +11,7 @@ use crate::guardian::guardian_timeout_message;
 use crate::guardian::new_guardian_review_id;
 use crate::guardian::routes_approval_to_guardian;
 use crate::hook_runtime::run_permission_request_hooks;
+use crate::hook_runtime::spawn_notification_hooks;
 use crate::network_policy_decision::network_approval_context_from_payload;
 use crate::tools::network_approval::DeferredNetworkApproval;
 use crate::tools::network_approval::NetworkApprovalMode;
@@ -25,7 +26,9 @@ use crate::tools::sandboxing::ToolCtx;
 use crate::tools::sandboxing::ToolError;
 use crate::tools::sandboxing::ToolRuntime;
 use crate::tools::sandboxing::default_exec_approval_requirement;
+use codex_hooks::NotificationType;
 use codex_hooks::PermissionRequestDecision;
+use codex_hooks::PreToolUsePermissionDecision;
 use codex_otel::ToolDecisionSource;
 use codex_protocol::error::CodexErr;
 use codex_protocol::error::SandboxErr;
@@ -125,6 +128,47 @@ impl ToolOrchestrator {
         let requirement = tool.exec_approval_requirement(req).unwrap_or_else(|| {
             default_exec_approval_requirement(approval_policy, &turn_ctx.file_system_sandbox_policy)
         });
+        // A PreToolUse hook may pre-approve the call or insist on a prompt. Like
+        // PermissionRequest hooks it is ignored under strict auto-review, and it can never
+        // lift a policy that forbids the call.
+        let hook_permission_decision = tool_ctx
+            .session
+            .take_pre_tool_use_permission_decision(&tool_ctx.call_id)
+            .await
+            .filter(|_| !strict_auto_review);
+        let requirement = match (hook_permission_decision, requirement) {
+            (
+                Some(PreToolUsePermissionDecision::Allow),
+                ExecApprovalRequirement::NeedsApproval { .. },
+            ) => {
+                already_approved = true;
+                ExecApprovalRequirement::Skip {
+                    bypass_sandbox: false,
+                    proposed_execpolicy_amendment: None,
+                }
+            }
+            (
+                Some(PreToolUsePermissionDecision::Ask { reason }),
+                ExecApprovalRequirement::Skip {
+                    proposed_execpolicy_amendment,
+                    ..
+                },
+            ) => {
+                if matches!(approval_policy, AskForApproval::Never) {
+                    return Err(ToolError::Rejected(
+                        "PreToolUse hook requested approval, but approvals are disabled"
+                            .to_string(),
+                    ));
+                }
+                ExecApprovalRequirement::NeedsApproval {
+                    reason: Some(
+                        reason.unwrap_or_else(|| "PreToolUse hook requested approval".to_string()),
+                    ),
+                    proposed_execpolicy_amendment,
+                }
+            }
+            (_, requirement) => requirement,
+        };
         match requirement {
             ExecApprovalRequirement::Skip { .. } => {
                 if strict_auto_review {
@@ -214,6 +258,7 @@ impl ToolOrchestrator {
             sandbox_cwd: &turn_ctx.cwd,
             codex_linux_sandbox_exe: turn_ctx.codex_linux_sandbox_exe.as_ref(),
             use_legacy_landlock,
+            overlay_workspace: turn_ctx.overlay_workspace.as_ref(),
             windows_sandbox_level: turn_ctx.windows_sandbox_level,
             windows_sandbox_private_desktop: turn_ctx
                 .config
@@ -333,6 +378,7 @@ impl ToolOrchestrator {
                     sandbox_cwd: &turn_ctx.cwd,
                     codex_linux_sandbox_exe: None,
                     use_legacy_landlock,
+                    overlay_workspace: None,
                     windows_sandbox_level: turn_ctx.windows_sandbox_level,
                     windows_sandbox_private_desktop: turn_ctx
                         .config
@@ -413,7 +459,23 @@ impl ToolOrchestrator {
         } else {
             ToolDecisionSource::User
         };
-        let decision = tool.start_approval_async(req, approval_ctx).await;
+        if approval_ctx.guardian_review_id.is_none() {
+            spawn_notification_hooks(
+                approval_ctx.session,
+                approval_ctx.turn,
+                NotificationType::PermissionPrompt,
+                format!("Codex needs your permission to use {}", tool_ctx.tool_name),
+            );
+        }
+        let decision = match tool.start_approval_async(req, approval_ctx).await {
+            ReviewDecision::ApprovedPartialPatch { .. }
+            | ReviewDecision::ApprovedEditedPatch { .. }
+                if !tool.accepts_patch_approval() =>
+            {
+                ReviewDecision::Denied
+            }
+            decision => decision,
+        };
         otel.tool_decision(
             &tool_ctx.tool_name,
             &tool_ctx.call_id,
@@ -440,7 +502,9 @@ impl ToolOrchestrator {
             ReviewDecision::TimedOut => Err(ToolError::Rejected(guardian_timeout_message())),
             ReviewDecision::Approved
             | ReviewDecision::ApprovedExecpolicyAmendment { .. }
-            | ReviewDecision::ApprovedForSession => Ok(()),
+            | ReviewDecision::ApprovedForSession
+            | ReviewDecision::ApprovedPartialPatch { .. }
+            | ReviewDecision::ApprovedEditedPatch { .. } => Ok(()),
             ReviewDecision::NetworkPolicyAmendment {
                 network_policy_amendment,
             } => match network_policy_amendment.action {
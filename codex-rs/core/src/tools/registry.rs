@@ -4,6 +4,7 @@ use std::time::Duration;
 use std::time::Instant;
 
 use crate::function_tool::FunctionCallError;
+use crate::hook_runtime::PreToolUseHookResult;
 use crate::hook_runtime::record_additional_contexts;
 use crate::hook_runtime::run_post_tool_use_hooks;
 use crate::hook_runtime::run_pre_tool_use_hooks;
@@ -31,6 +32,7 @@ use codex_tools::ToolName;
 use codex_tools::ToolSpec;
 use codex_utils_readiness::Readiness;
 use futures::future::BoxFuture;
+use serde::Deserialize;
 use serde_json::Value;
 use tracing::warn;
 
@@ -69,6 +71,20 @@ pub trait ToolHandler: Send + Sync {
         None
     }
 
+    /// Builds the payload for a `PreToolUse` hook's `updatedInput`, which has the same shape as
+    /// [`PreToolUsePayload::tool_input`]. Returns why the input was rejected if it does not fit
+    /// the tool's arguments.
+    fn payload_with_pre_tool_use_input(
+        &self,
+        invocation: &ToolInvocation,
+        _updated_input: Value,
+    ) -> Result<ToolPayload, String> {
+        Err(format!(
+            "{} does not accept rewritten input",
+            invocation.tool_name.display()
+        ))
+    }
+
     fn post_tool_use_payload(
         &self,
         _invocation: &ToolInvocation,
@@ -143,6 +159,32 @@ pub(crate) struct PreToolUsePayload {
     pub(crate) tool_input: Value,
 }
 
+/// Reads `{ "command": ... }` from a `PreToolUse` hook's `updatedInput` for the shell-like
+/// tools that expose that shape.
+pub(crate) fn pre_tool_use_updated_command(updated_input: Value) -> Result<String, String> {
+    #[derive(Deserialize)]
+    #[serde(deny_unknown_fields)]
+    struct CommandInput {
+        command: String,
+    }
+
+    serde_json::from_value::<CommandInput>(updated_input)
+        .map(|input| input.command)
+        .map_err(|err| err.to_string())
+}
+
+/// Replaces one field of a function tool's JSON arguments, keeping the rest intact.
+pub(crate) fn replace_function_argument(
+    arguments: &str,
+    key: &str,
+    value: Value,
+) -> Result<String, String> {
+    let mut arguments = serde_json::from_str::<serde_json::Map<String, Value>>(arguments)
+        .map_err(|err| err.to_string())?;
+    arguments.insert(key.to_string(), value);
+    serde_json::to_string(&arguments).map_err(|err| err.to_string())
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub(crate) struct PostToolUsePayload {
     /// Hook-facing tool name model.
@@ -165,6 +207,12 @@ trait AnyToolHandler: Send + Sync {
 
     fn pre_tool_use_payload(&self, invocation: &ToolInvocation) -> Option<PreToolUsePayload>;
 
+    fn payload_with_pre_tool_use_input(
+        &self,
+        invocation: &ToolInvocation,
+        updated_input: Value,
+    ) -> Result<ToolPayload, String>;
+
     fn create_diff_consumer(&self) -> Option<Box<dyn ToolArgumentDiffConsumer>>;
     fn handle_any<'a>(
         &'a self,
@@ -188,6 +236,14 @@ where
         ToolHandler::pre_tool_use_payload(self, invocation)
     }
 
+    fn payload_with_pre_tool_use_input(
+        &self,
+        invocation: &ToolInvocation,
+        updated_input: Value,
+    ) -> Result<ToolPayload, String> {
+        ToolHandler::payload_with_pre_tool_use_input(self, invocation, updated_input)
+    }
+
     fn create_diff_consumer(&self) -> Option<Box<dyn ToolArgumentDiffConsumer>> {
         ToolHandler::create_diff_consumer(self)
     }
@@ -263,7 +319,7 @@ impl ToolRegistry {
     )]
     pub(crate) async fn dispatch_any(
         &self,
-        invocation: ToolInvocation,
+        mut invocation: ToolInvocation,
     ) -> Result<AnyToolResult, FunctionCallError> {
         let tool_name = invocation.tool_name.clone();
         let display_name = tool_name.display();
@@ -349,19 +405,47 @@ impl ToolRegistry {
             return Err(err);
         }
 
-        if let Some(pre_tool_use_payload) = handler.pre_tool_use_payload(&invocation)
-            && let Some(message) = run_pre_tool_use_hooks(
+        if let Some(pre_tool_use_payload) = handler.pre_tool_use_payload(&invocation) {
+            let PreToolUseHookResult {
+                block_message,
+                updated_input,
+                permission_decision,
+            } = run_pre_tool_use_hooks(
                 &invocation.session,
                 &invocation.turn,
                 invocation.call_id.clone(),
                 &pre_tool_use_payload.tool_name,
                 &pre_tool_use_payload.tool_input,
             )
-            .await
-        {
-            let err = FunctionCallError::RespondToModel(message);
-            dispatch_trace.record_failed(&err);
-            return Err(err);
+            .await;
+            if let Some(message) = block_message {
+                let err = FunctionCallError::RespondToModel(message);
+                dispatch_trace.record_failed(&err);
+                return Err(err);
+            }
+            if let Some(updated_input) = updated_input {
+                match handler.payload_with_pre_tool_use_input(&invocation, updated_input) {
+                    Ok(payload) => invocation.payload = payload,
+                    Err(reason) => {
+                        let err = FunctionCallError::RespondToModel(format!(
+                            "Tool call blocked: PreToolUse hook returned invalid updatedInput for {display_name}: {reason}"
+                        ));
+                        dispatch_trace.record_failed(&err);
+                        return Err(err);
+                    }
+                }
+            }
+            // The approval step runs inside the tool handler, so hand the verdict over through
+            // turn state keyed by call id.
+            if let Some(permission_decision) = permission_decision {
+                invocation
+                    .session
+                    .record_pre_tool_use_permission_decision(
+                        &invocation.call_id,
+                        permission_decision,
+                    )
+                    .await;
+            }
         }
 
         let is_mutating = handler.is_mutating(&invocation).await;
@@ -400,6 +484,12 @@ impl ToolRegistry {
                 },
             )
             .await;
+        // Handlers that never reach the orchestrator (MCP tools, for example) leave the verdict
+        // unread; drop it so it cannot outlive the call.
+        invocation
+            .session
+            .take_pre_tool_use_permission_decision(&invocation.call_id)
+            .await;
         let duration = started.elapsed();
         let (output_preview, success) = match &result {
             Ok((preview, success)) => (preview.clone(), *success),
@@ -2,10 +2,14 @@
 //!
 //! Assumes `apply_patch` verification/approval happened upstream. Reuses the
 //! selected turn environment filesystem for both local and remote turns, with
-//! sandboxing enforced by the explicit filesystem sandbox context.
+//! sandboxing enforced by the explicit filesystem sandbox context. When the
+//! user approves only part of a patch, or an edited replacement, only the
+//! accepted changes are applied and the rejected ones are reported back.
+use crate::apply_patch::convert_apply_patch_to_protocol;
 use crate::exec::is_likely_sandbox_denied;
 use crate::guardian::GuardianApprovalRequest;
 use crate::guardian::review_approval_request;
+use crate::tools::context::SharedTurnDiffTracker;
 use crate::tools::hook_names::HookToolName;
 use crate::tools::sandboxing::Approvable;
 use crate::tools::sandboxing::ApprovalCtx;
@@ -18,6 +22,8 @@ use crate::tools::sandboxing::ToolError;
 use crate::tools::sandboxing::ToolRuntime;
 use crate::tools::sandboxing::with_cached_approval;
 use codex_apply_patch::ApplyPatchAction;
+use codex_apply_patch::Hunk;
+use codex_apply_patch::UpdateFileChunk;
 use codex_exec_server::FileSystemSandboxContext;
 use codex_protocol::error::CodexErr;
 use codex_protocol::error::SandboxErr;
@@ -32,6 +38,7 @@ use codex_protocol::protocol::EventMsg;
 use codex_protocol::protocol::ExecCommandOutputDeltaEvent;
 use codex_protocol::protocol::ExecOutputStream;
 use codex_protocol::protocol::FileChange;
+use codex_protocol::protocol::PatchFileApproval;
 use codex_protocol::protocol::ReviewDecision;
 use codex_sandboxing::SandboxType;
 use codex_sandboxing::SandboxablePreference;
@@ -39,6 +46,7 @@ use codex_sandboxing::policy_transforms::effective_file_system_sandbox_policy;
 use codex_sandboxing::policy_transforms::effective_network_sandbox_policy;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use futures::future::BoxFuture;
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::time::Instant;
 
@@ -46,18 +54,72 @@ use std::time::Instant;
 pub struct ApplyPatchRequest {
     pub action: ApplyPatchAction,
     pub file_paths: Vec<AbsolutePathBuf>,
-    pub changes: std::collections::HashMap<PathBuf, FileChange>,
+    pub changes: HashMap<PathBuf, FileChange>,
     pub exec_approval_requirement: ExecApprovalRequirement,
     pub additional_permissions: Option<AdditionalPermissionProfile>,
     pub permissions_preapproved: bool,
 }
 
+/// The part of a proposed patch that the user accepted during approval.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum AcceptedPatch {
+    Files(Vec<PatchFileApproval>),
+    Edited(String),
+}
+
+impl AcceptedPatch {
+    fn from_decision(decision: &ReviewDecision) -> Option<Self> {
+        match decision {
+            ReviewDecision::ApprovedPartialPatch { accepted_files } => {
+                Some(Self::Files(accepted_files.clone()))
+            }
+            ReviewDecision::ApprovedEditedPatch { patch } => Some(Self::Edited(patch.clone())),
+            _ => None,
+        }
+    }
+}
+
+/// Hunks left after dropping everything the user rejected, the file changes
+/// they amount to, and a description of each rejected change for the model.
+#[derive(Debug)]
+struct NarrowedPatch {
+    hunks: Vec<Hunk>,
+    changes: HashMap<PathBuf, FileChange>,
+    rejected: Vec<String>,
+}
+
+impl NarrowedPatch {
+    fn accept_whole(&mut self, hunk: Hunk, path: &AbsolutePathBuf, req: &ApplyPatchRequest) {
+        self.hunks.push(hunk);
+        if let Some(change) = req.changes.get(path.as_path()) {
+            self.changes.insert(path.to_path_buf(), change.clone());
+        }
+    }
+}
+
 #[derive(Default)]
-pub struct ApplyPatchRuntime;
+pub struct ApplyPatchRuntime {
+    accepted: Option<AcceptedPatch>,
+    turn_diff_tracker: Option<SharedTurnDiffTracker>,
+    applied_changes: Option<HashMap<PathBuf, FileChange>>,
+}
 
 impl ApplyPatchRuntime {
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    /// Lets the runtime baseline files that only an approved edit or partial
+    /// selection touches before it applies them.
+    pub fn with_turn_diff_tracker(mut self, tracker: Option<SharedTurnDiffTracker>) -> Self {
+        self.turn_diff_tracker = tracker;
+        self
+    }
+
+    /// The changes actually applied when the user narrowed or edited the
+    /// patch during approval, or `None` when the proposed patch ran as is.
+    pub fn take_applied_changes(&mut self) -> Option<HashMap<PathBuf, FileChange>> {
+        self.applied_changes.take()
     }
 
     fn build_guardian_review_request(
@@ -99,9 +161,16 @@ impl ApplyPatchRuntime {
             windows_sandbox_level: attempt.windows_sandbox_level,
             windows_sandbox_private_desktop: attempt.windows_sandbox_private_desktop,
             use_legacy_landlock: attempt.use_legacy_landlock,
+            overlay_workspace: attempt.overlay_workspace.cloned(),
         })
     }
 
+    async fn track_patch_begin(&self, changes: &HashMap<PathBuf, FileChange>) {
+        if let Some(tracker) = &self.turn_diff_tracker {
+            tracker.lock().await.on_patch_begin(changes);
+        }
+    }
+
     async fn emit_output_delta(ctx: &ToolCtx, stream: ExecOutputStream, chunk: &[u8]) {
         if chunk.is_empty() {
             return;
@@ -117,6 +186,146 @@ impl ApplyPatchRuntime {
         };
         let _ = ctx.session.get_tx_event().send(event).await;
     }
+
+    /// Drops the files and hunks the user did not accept. Hunk indices refer
+    /// to the `@@` hunks of the `unified_diff` shown in the approval request.
+    fn narrow_patch(
+        req: &ApplyPatchRequest,
+        accepted_files: &[PatchFileApproval],
+    ) -> Result<NarrowedPatch, String> {
+        let parsed = codex_apply_patch::parse_patch(&req.action.patch)
+            .map_err(|err| format!("failed to parse approved patch: {err}"))?;
+        let mut narrowed = NarrowedPatch {
+            hunks: Vec::new(),
+            changes: HashMap::new(),
+            rejected: Vec::new(),
+        };
+        for hunk in parsed.hunks {
+            let path = hunk.resolve_path(&req.action.cwd);
+            let approval = accepted_files.iter().find(|file| {
+                AbsolutePathBuf::resolve_path_against_base(&file.path, &req.action.cwd) == path
+            });
+            let selected = match approval {
+                None => {
+                    narrowed.rejected.push(format!("- {}", path.display()));
+                    continue;
+                }
+                Some(PatchFileApproval { hunks: None, .. }) => {
+                    narrowed.accept_whole(hunk, &path, req);
+                    continue;
+                }
+                Some(PatchFileApproval {
+                    hunks: Some(selected),
+                    ..
+                }) => selected,
+            };
+            if selected.is_empty() {
+                narrowed.rejected.push(format!("- {}", path.display()));
+                continue;
+            }
+            let Hunk::UpdateFile {
+                path: source_path,
+                move_path,
+                chunks,
+            } = hunk
+            else {
+                narrowed.accept_whole(hunk, &path, req);
+                continue;
+            };
+            let Some(change) = req.changes.get(path.as_path()) else {
+                return Err(format!(
+                    "no change to select hunks from for {}",
+                    path.display()
+                ));
+            };
+            let diff_hunks = change.unified_diff_hunks();
+            if let Some(index) = selected
+                .iter()
+                .find(|index| **index as usize >= diff_hunks.len())
+            {
+                return Err(format!(
+                    "hunk {index} is out of range for {}, which has {} hunks",
+                    path.display(),
+                    diff_hunks.len()
+                ));
+            }
+            if (0..diff_hunks.len() as u32).all(|index| selected.contains(&index)) {
+                let hunk = Hunk::UpdateFile {
+                    path: source_path,
+                    move_path,
+                    chunks,
+                };
+                narrowed.accept_whole(hunk, &path, req);
+                continue;
+            }
+            let mut accepted_chunks = Vec::new();
+            let mut accepted_diff = unified_diff_header(change, &diff_hunks).to_string();
+            for (index, diff_hunk) in diff_hunks.iter().enumerate() {
+                if selected.contains(&(index as u32)) {
+                    accepted_chunks.push(update_chunk_from_diff_hunk(diff_hunk));
+                    accepted_diff.push_str(diff_hunk);
+                } else {
+                    narrowed.rejected.push(format!(
+                        "- {}, hunk:\n{}",
+                        path.display(),
+                        diff_hunk.trim_end()
+                    ));
+                }
+            }
+            let accepted_move_path = match change {
+                FileChange::Update { move_path, .. } => move_path.clone(),
+                _ => None,
+            };
+            narrowed.changes.insert(
+                path.to_path_buf(),
+                FileChange::Update {
+                    unified_diff: accepted_diff,
+                    move_path: accepted_move_path,
+                },
+            );
+            narrowed.hunks.push(Hunk::UpdateFile {
+                path: source_path,
+                move_path,
+                chunks: accepted_chunks,
+            });
+        }
+        Ok(narrowed)
+    }
+}
+
+/// The file header lines that precede the first `@@` hunk of an update diff.
+fn unified_diff_header<'a>(change: &'a FileChange, diff_hunks: &[&str]) -> &'a str {
+    let FileChange::Update { unified_diff, .. } = change else {
+        return "";
+    };
+    let hunks_len = diff_hunks.iter().map(|hunk| hunk.len()).sum::<usize>();
+    unified_diff
+        .get(..unified_diff.len().saturating_sub(hunks_len))
+        .unwrap_or_default()
+}
+
+/// Turns one unified diff hunk into an apply_patch chunk that is located by
+/// the hunk's context and removed lines.
+fn update_chunk_from_diff_hunk(diff_hunk: &str) -> UpdateFileChunk {
+    let mut old_lines = Vec::new();
+    let mut new_lines = Vec::new();
+    for line in diff_hunk.lines().skip(1) {
+        if let Some(removed) = line.strip_prefix('-') {
+            old_lines.push(removed.to_string());
+        } else if let Some(added) = line.strip_prefix('+') {
+            new_lines.push(added.to_string());
+        } else if !line.starts_with('\\') {
+            let context = line.strip_prefix(' ').unwrap_or(line);
+            old_lines.push(context.to_string());
+            new_lines.push(context.to_string());
+        }
+    }
+    UpdateFileChunk {
+        change_context: None,
+        old_lines,
+        new_lines,
+        is_end_of_file: false,
+    }
 }
 
 impl Sandboxable for ApplyPatchRuntime {
@@ -148,44 +357,54 @@ impl Approvable<ApplyPatchRequest> for ApplyPatchRuntime {
         let changes = req.changes.clone();
         let guardian_review_id = ctx.guardian_review_id.clone();
         Box::pin(async move {
-            if let Some(review_id) = guardian_review_id {
-                let action = ApplyPatchRuntime::build_guardian_review_request(req, ctx.call_id);
-                return review_approval_request(session, turn, review_id, action, retry_reason)
-                    .await;
-            }
-            if req.permissions_preapproved && retry_reason.is_none() {
-                return ReviewDecision::Approved;
-            }
-            if let Some(reason) = retry_reason {
-                let rx_approve = session
-                    .request_patch_approval(
-                        turn,
-                        call_id,
-                        changes.clone(),
-                        Some(reason),
-                        /*grant_root*/ None,
-                    )
-                    .await;
-                return rx_approve.await.unwrap_or_default();
-            }
-
-            with_cached_approval(
-                &session.services,
-                "apply_patch",
-                approval_keys,
-                || async move {
+            let decision = async move {
+                if let Some(review_id) = guardian_review_id {
+                    let action = ApplyPatchRuntime::build_guardian_review_request(req, ctx.call_id);
+                    return review_approval_request(session, turn, review_id, action, retry_reason)
+                        .await;
+                }
+                if req.permissions_preapproved && retry_reason.is_none() {
+                    return ReviewDecision::Approved;
+                }
+                if let Some(reason) = retry_reason {
                     let rx_approve = session
                         .request_patch_approval(
-                            turn, call_id, changes, /*reason*/ None, /*grant_root*/ None,
+                            turn,
+                            call_id,
+                            changes.clone(),
+                            Some(reason),
+                            /*grant_root*/ None,
                         )
                         .await;
-                    rx_approve.await.unwrap_or_default()
-                },
-            )
-            .await
+                    return rx_approve.await.unwrap_or_default();
+                }
+
+                with_cached_approval(
+                    &session.services,
+                    "apply_patch",
+                    approval_keys,
+                    || async move {
+                        let rx_approve = session
+                            .request_patch_approval(
+                                turn, call_id, changes, /*reason*/ None,
+                                /*grant_root*/ None,
+                            )
+                            .await;
+                        rx_approve.await.unwrap_or_default()
+                    },
+                )
+                .await
+            }
+            .await;
+            self.accepted = AcceptedPatch::from_decision(&decision);
+            decision
         })
     }
 
+    fn accepts_patch_approval(&self) -> bool {
+        true
+    }
+
     fn wants_no_sandbox_approval(&self, policy: AskForApproval) -> bool {
         match policy {
             AskForApproval::Never => false,
@@ -233,15 +452,79 @@ impl ToolRuntime<ApplyPatchRequest, ExecToolCallOutput> for ApplyPatchRuntime {
         let sandbox = Self::file_system_sandbox_context_for_attempt(req, attempt);
         let mut stdout = Vec::new();
         let mut stderr = Vec::new();
-        let result = codex_apply_patch::apply_patch(
-            &req.action.patch,
-            &req.action.cwd,
-            &mut stdout,
-            &mut stderr,
-            fs.as_ref(),
-            sandbox.as_ref(),
-        )
-        .await;
+        let mut note = None;
+        let mut applied_changes = None;
+        let result = match &self.accepted {
+            None => {
+                codex_apply_patch::apply_patch(
+                    &req.action.patch,
+                    &req.action.cwd,
+                    &mut stdout,
+                    &mut stderr,
+                    fs.as_ref(),
+                    sandbox.as_ref(),
+                )
+                .await
+            }
+            Some(AcceptedPatch::Edited(patch)) => {
+                note = Some(format!(
+                    "The user edited the patch before approving it. This patch was applied instead:\n{patch}"
+                ));
+                let command = vec!["apply_patch".to_string(), patch.clone()];
+                // An edit that fails verification is still applied below so the
+                // model sees the same error apply_patch reports.
+                if let codex_apply_patch::MaybeApplyPatchVerified::Body(action) =
+                    codex_apply_patch::maybe_parse_apply_patch_verified(
+                        &command,
+                        &req.action.cwd,
+                        fs.as_ref(),
+                        sandbox.as_ref(),
+                    )
+                    .await
+                {
+                    let changes = convert_apply_patch_to_protocol(&action);
+                    self.track_patch_begin(&changes).await;
+                    applied_changes = Some(changes);
+                }
+                codex_apply_patch::apply_patch(
+                    patch,
+                    &req.action.cwd,
+                    &mut stdout,
+                    &mut stderr,
+                    fs.as_ref(),
+                    sandbox.as_ref(),
+                )
+                .await
+            }
+            Some(AcceptedPatch::Files(accepted_files)) => {
+                let narrowed =
+                    Self::narrow_patch(req, accepted_files).map_err(ToolError::Rejected)?;
+                if narrowed.hunks.is_empty() {
+                    return Err(ToolError::Rejected("rejected by user".to_string()));
+                }
+                if !narrowed.rejected.is_empty() {
+                    note = Some(format!(
+                        "The user rejected these changes, which were not applied:\n{}",
+                        narrowed.rejected.join("\n")
+                    ));
+                }
+                self.track_patch_begin(&narrowed.changes).await;
+                applied_changes = Some(narrowed.changes);
+                codex_apply_patch::apply_hunks(
+                    &narrowed.hunks,
+                    &req.action.cwd,
+                    &mut stdout,
+                    &mut stderr,
+                    fs.as_ref(),
+                    sandbox.as_ref(),
+                )
+                .await
+            }
+        };
+        self.applied_changes = applied_changes;
+        if let Some(note) = note {
+            stdout.extend_from_slice(format!("{note}\n").as_bytes());
+        }
         let stdout = String::from_utf8_lossy(&stdout).into_owned();
         let stderr = String::from_utf8_lossy(&stderr).into_owned();
         Self::emit_output_delta(ctx, ExecOutputStream::Stdout, stdout.as_bytes()).await;
@@ -149,6 +149,7 @@ fn file_system_sandbox_context_uses_active_attempt() {
         sandbox_cwd: &path,
         codex_linux_sandbox_exe: None,
         use_legacy_landlock: true,
+        overlay_workspace: None,
         windows_sandbox_level: WindowsSandboxLevel::RestrictedToken,
         windows_sandbox_private_desktop: true,
     };
@@ -172,6 +173,7 @@ fn file_system_sandbox_context_uses_active_attempt() {
     );
     assert_eq!(sandbox.windows_sandbox_private_desktop, true);
     assert_eq!(sandbox.use_legacy_landlock, true);
+    assert_eq!(sandbox.overlay_workspace, None);
 }
 
 #[test]
@@ -203,6 +205,7 @@ fn no_sandbox_attempt_has_no_file_system_context() {
         sandbox_cwd: &path,
         codex_linux_sandbox_exe: None,
         use_legacy_landlock: false,
+        overlay_workspace: None,
         windows_sandbox_level: WindowsSandboxLevel::Disabled,
         windows_sandbox_private_desktop: false,
     };
@@ -212,3 +215,127 @@ fn no_sandbox_attempt_has_no_file_system_context() {
         None
     );
 }
+
+#[test]
+fn narrow_patch_keeps_only_accepted_files_and_hunks() {
+    let cwd = std::env::temp_dir().join("apply-patch-narrow").abs();
+    let mut action = ApplyPatchAction::new_add_for_test(&cwd.join("unused.txt"), String::new());
+    action.patch = "*** Begin Patch
+*** Update File: src/lib.rs
+@@
+-x
++y
+@@
+-z
++w
+*** Add File: Cargo.lock
++lock
+*** End Patch"
+        .to_string();
+    let lib_path = cwd.join("src/lib.rs");
+    let lock_path = cwd.join("Cargo.lock");
+    let req = ApplyPatchRequest {
+        action,
+        file_paths: vec![lib_path.clone(), lock_path.clone()],
+        changes: HashMap::from([
+            (
+                lib_path.to_path_buf(),
+                FileChange::Update {
+                    unified_diff: "@@ -1,2 +1,2 @@\n-x\n+y\n a\n@@ -8,2 +8,2 @@\n b\n-z\n+w\n"
+                        .to_string(),
+                    move_path: None,
+                },
+            ),
+            (
+                lock_path.to_path_buf(),
+                FileChange::Add {
+                    content: "lock\n".to_string(),
+                },
+            ),
+        ]),
+        exec_approval_requirement: ExecApprovalRequirement::NeedsApproval {
+            reason: None,
+            proposed_execpolicy_amendment: None,
+        },
+        additional_permissions: None,
+        permissions_preapproved: false,
+    };
+
+    let narrowed = ApplyPatchRuntime::narrow_patch(
+        &req,
+        &[PatchFileApproval {
+            path: PathBuf::from("src/lib.rs"),
+            hunks: Some(vec![1]),
+        }],
+    )
+    .expect("narrow patch");
+
+    assert_eq!(
+        narrowed.hunks,
+        vec![Hunk::UpdateFile {
+            path: PathBuf::from("src/lib.rs"),
+            move_path: None,
+            chunks: vec![UpdateFileChunk {
+                change_context: None,
+                old_lines: vec!["b".to_string(), "z".to_string()],
+                new_lines: vec!["b".to_string(), "w".to_string()],
+                is_end_of_file: false,
+            }],
+        }]
+    );
+    assert_eq!(
+        narrowed.rejected,
+        vec![
+            format!(
+                "- {}, hunk:\n@@ -1,2 +1,2 @@\n-x\n+y\n a",
+                lib_path.display()
+            ),
+            format!("- {}", lock_path.display()),
+        ]
+    );
+    assert_eq!(
+        narrowed.changes,
+        HashMap::from([(
+            lib_path.to_path_buf(),
+            FileChange::Update {
+                unified_diff: "@@ -8,2 +8,2 @@\n b\n-z\n+w\n".to_string(),
+                move_path: None,
+            },
+        )])
+    );
+}
+
+#[test]
+fn narrow_patch_applies_whole_non_update_files() {
+    let path = std::env::temp_dir()
+        .join("apply-patch-narrow-range.txt")
+        .abs();
+    let req = ApplyPatchRequest {
+        action: ApplyPatchAction::new_add_for_test(&path, "hello".to_string()),
+        file_paths: vec![path.clone()],
+        changes: HashMap::from([(
+            path.to_path_buf(),
+            FileChange::Add {
+                content: "hello".to_string(),
+            },
+        )]),
+        exec_approval_requirement: ExecApprovalRequirement::NeedsApproval {
+            reason: None,
+            proposed_execpolicy_amendment: None,
+        },
+        additional_permissions: None,
+        permissions_preapproved: false,
+    };
+
+    let narrowed = ApplyPatchRuntime::narrow_patch(
+        &req,
+        &[PatchFileApproval {
+            path: path.to_path_buf(),
+            hunks: Some(vec![0]),
+        }],
+    )
+    .expect("narrow patch");
+    assert_eq!(narrowed.hunks.len(), 1);
+    assert_eq!(narrowed.changes, req.changes);
+    assert_eq!(narrowed.rejected, Vec::<String>::new());
+}
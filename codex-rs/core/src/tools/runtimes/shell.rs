@@ -276,6 +276,7 @@ impl ToolRuntime<ShellRequest, ExecToolCallOutput> for ShellRuntime {
         let options = ExecOptions {
             expiration: req.timeout_ms.into(),
             capture_policy: ExecCapturePolicy::ShellTool,
+            resource_limits: ctx.turn.config.permissions.resource_limits.clone(),
         };
         let env = attempt
             .env_for(command, options, req.network.as_ref())
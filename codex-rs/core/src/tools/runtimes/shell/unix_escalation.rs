@@ -45,6 +45,7 @@ use codex_sandboxing::SandboxManager;
 use codex_sandboxing::SandboxTransformRequest;
 use codex_sandboxing::SandboxType;
 use codex_sandboxing::SandboxablePreference;
+use codex_sandboxing::overlay::OverlayWorkspace;
 use codex_shell_command::bash::parse_shell_lc_plain_commands;
 use codex_shell_command::bash::parse_shell_lc_single_command_prefix;
 use codex_shell_escalation::EscalateServer;
@@ -60,6 +61,7 @@ use codex_shell_escalation::ResolvedPermissionProfile;
 use codex_shell_escalation::ShellCommandExecutor;
 use codex_shell_escalation::Stopwatch;
 use codex_utils_absolute_path::AbsolutePathBuf;
+use codex_utils_pty::resource_limits::ResourceLimits;
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -123,6 +125,7 @@ pub(super) async fn try_run_zsh_fork(
     let options = ExecOptions {
         expiration: req.timeout_ms.into(),
         capture_policy: ExecCapturePolicy::ShellTool,
+        resource_limits: ctx.turn.config.permissions.resource_limits.clone(),
     };
     let sandbox_exec_request = attempt
         .env_for(command, options, req.network.as_ref())
@@ -144,6 +147,7 @@ pub(super) async fn try_run_zsh_fork(
         network_sandbox_policy,
         windows_sandbox_filesystem_overrides: _windows_sandbox_filesystem_overrides,
         arg0,
+        resource_limits,
     } = sandbox_exec_request;
     let ParsedShellCommand { script, login, .. } = extract_shell_script(&command)?;
     let effective_timeout = Duration::from_millis(
@@ -167,6 +171,8 @@ pub(super) async fn try_run_zsh_fork(
         sandbox_policy_cwd,
         codex_linux_sandbox_exe: ctx.turn.codex_linux_sandbox_exe.clone(),
         use_legacy_landlock: ctx.turn.features.use_legacy_landlock(),
+        overlay_workspace: ctx.turn.overlay_workspace.clone(),
+        resource_limits,
     };
     let main_execve_wrapper_exe = ctx
         .session
@@ -265,6 +271,8 @@ pub(crate) async fn prepare_unified_exec_zsh_fork(
         sandbox_policy_cwd: ctx.turn.cwd.clone(),
         codex_linux_sandbox_exe: ctx.turn.codex_linux_sandbox_exe.clone(),
         use_legacy_landlock: ctx.turn.features.use_legacy_landlock(),
+        overlay_workspace: ctx.turn.overlay_workspace.clone(),
+        resource_limits: exec_request.resource_limits.clone(),
     };
     let escalation_policy = CoreShellActionProvider {
         policy: Arc::clone(&exec_policy),
@@ -538,7 +546,10 @@ impl CoreShellActionProvider {
                                 EscalationDecision::deny(Some("User denied execution".to_string()))
                             }
                         },
-                        ReviewDecision::Denied => {
+                        // Partial and edited approvals only apply to patches.
+                        ReviewDecision::Denied
+                        | ReviewDecision::ApprovedPartialPatch { .. }
+                        | ReviewDecision::ApprovedEditedPatch { .. } => {
                             let message = if let Some(message) =
                                 prompt_decision.rejection_message.clone()
                             {
@@ -601,6 +612,7 @@ impl EscalationPolicy for CoreShellActionProvider {
                 program,
                 argv,
                 InterceptedExecPolicyContext {
+                    cwd: workdir,
                     approval_policy: self.approval_policy,
                     sandbox_policy: &self.sandbox_policy,
                     file_system_sandbox_policy: &self.file_system_sandbox_policy,
@@ -653,6 +665,7 @@ fn evaluate_intercepted_exec_policy(
     context: InterceptedExecPolicyContext<'_>,
 ) -> Evaluation {
     let InterceptedExecPolicyContext {
+        cwd,
         approval_policy,
         sandbox_policy,
         file_system_sandbox_policy,
@@ -692,12 +705,14 @@ fn evaluate_intercepted_exec_policy(
         &fallback,
         &MatchOptions {
             resolve_host_executables: true,
+            cwd: Some(cwd.clone()),
         },
     )
 }
 
 #[derive(Clone, Copy)]
 struct InterceptedExecPolicyContext<'a> {
+    cwd: &'a AbsolutePathBuf,
     approval_policy: AskForApproval,
     sandbox_policy: &'a SandboxPolicy,
     file_system_sandbox_policy: &'a FileSystemSandboxPolicy,
@@ -754,6 +769,8 @@ struct CoreShellCommandExecutor {
     sandbox_policy_cwd: AbsolutePathBuf,
     codex_linux_sandbox_exe: Option<PathBuf>,
     use_legacy_landlock: bool,
+    overlay_workspace: Option<OverlayWorkspace>,
+    resource_limits: ResourceLimits,
 }
 
 struct PrepareSandboxedExecParams<'a> {
@@ -803,6 +820,7 @@ impl ShellCommandExecutor for CoreShellCommandExecutor {
                 network_sandbox_policy: self.network_sandbox_policy,
                 windows_sandbox_filesystem_overrides: None,
                 arg0: self.arg0.clone(),
+                resource_limits: self.resource_limits.clone(),
             },
             /*stdout_stream*/ None,
             after_spawn,
@@ -924,6 +942,7 @@ impl CoreShellCommandExecutor {
         let options = ExecOptions {
             expiration: ExecExpiration::DefaultTimeout,
             capture_policy: ExecCapturePolicy::ShellTool,
+            resource_limits: ResourceLimits::default(),
         };
         let exec_request = sandbox_manager.transform(SandboxTransformRequest {
             command,
@@ -936,6 +955,7 @@ impl CoreShellCommandExecutor {
             sandbox_policy_cwd: &self.sandbox_policy_cwd,
             codex_linux_sandbox_exe: self.codex_linux_sandbox_exe.as_deref(),
             use_legacy_landlock: self.use_legacy_landlock,
+            overlay_workspace: self.overlay_workspace.as_ref(),
             windows_sandbox_level: self.windows_sandbox_level,
             windows_sandbox_private_desktop: false,
         })?;
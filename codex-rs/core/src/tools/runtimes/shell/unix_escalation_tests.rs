@@ -54,6 +54,10 @@ fn host_absolute_path(segments: &[&str]) -> String {
     path.to_string_lossy().into_owned()
 }
 
+fn test_cwd() -> AbsolutePathBuf {
+    AbsolutePathBuf::try_from(host_absolute_path(&["workspace"])).expect("absolute test cwd")
+}
+
 fn starlark_string(value: &str) -> String {
     value.replace('\\', "\\\\").replace('"', "\\\"")
 }
@@ -476,6 +480,7 @@ fn evaluate_intercepted_exec_policy_uses_wrapper_command_when_shell_wrapper_pars
             "npm publish".to_string(),
         ],
         InterceptedExecPolicyContext {
+            cwd: &test_cwd(),
             approval_policy: AskForApproval::OnRequest,
             sandbox_policy: &SandboxPolicy::new_read_only_policy(),
             file_system_sandbox_policy: &read_only_file_system_sandbox_policy(),
@@ -527,6 +532,7 @@ fn evaluate_intercepted_exec_policy_matches_inner_shell_commands_when_enabled()
             "npm publish".to_string(),
         ],
         InterceptedExecPolicyContext {
+            cwd: &test_cwd(),
             approval_policy: AskForApproval::OnRequest,
             sandbox_policy: &SandboxPolicy::new_read_only_policy(),
             file_system_sandbox_policy: &read_only_file_system_sandbox_policy(),
@@ -569,6 +575,7 @@ host_executable(name = "git", paths = ["{git_path_literal}"])
         &program,
         &["git".to_string(), "status".to_string()],
         InterceptedExecPolicyContext {
+            cwd: &test_cwd(),
             approval_policy: AskForApproval::OnRequest,
             sandbox_policy: &SandboxPolicy::new_read_only_policy(),
             file_system_sandbox_policy: &read_only_file_system_sandbox_policy(),
@@ -609,6 +616,7 @@ fn intercepted_exec_policy_treats_preapproved_additional_permissions_as_default(
         &program,
         &argv,
         InterceptedExecPolicyContext {
+            cwd: &test_cwd(),
             approval_policy,
             sandbox_policy: &sandbox_policy,
             file_system_sandbox_policy: &file_system_sandbox_policy,
@@ -624,6 +632,7 @@ fn intercepted_exec_policy_treats_preapproved_additional_permissions_as_default(
         &program,
         &argv,
         InterceptedExecPolicyContext {
+            cwd: &test_cwd(),
             approval_policy,
             sandbox_policy: &sandbox_policy,
             file_system_sandbox_policy: &file_system_sandbox_policy,
@@ -657,6 +666,7 @@ host_executable(name = "git", paths = ["{allowed_git_literal}"])
         &program,
         &["git".to_string(), "status".to_string()],
         InterceptedExecPolicyContext {
+            cwd: &test_cwd(),
             approval_policy: AskForApproval::OnRequest,
             sandbox_policy: &SandboxPolicy::new_read_only_policy(),
             file_system_sandbox_policy: &read_only_file_system_sandbox_policy(),
@@ -262,6 +262,7 @@ impl<'a> ToolRuntime<UnifiedExecRequest, UnifiedExecProcess> for UnifiedExecRunt
             let options = ExecOptions {
                 expiration: ExecExpiration::DefaultTimeout,
                 capture_policy: ExecCapturePolicy::ShellTool,
+                resource_limits: ctx.turn.config.permissions.resource_limits.clone(),
             };
             let mut exec_env = attempt
                 .env_for(command, options, req.network.as_ref())
@@ -297,15 +298,7 @@ impl<'a> ToolRuntime<UnifiedExecRequest, UnifiedExecProcess> for UnifiedExecRunt
                             environment.as_ref(),
                         )
                         .await
-                        .map_err(|err| match err {
-                            UnifiedExecError::SandboxDenied { output, .. } => {
-                                ToolError::Codex(CodexErr::Sandbox(SandboxErr::Denied {
-                                    output: Box::new(output),
-                                    network_policy_decision: None,
-                                }))
-                            }
-                            other => ToolError::Rejected(other.to_string()),
-                        });
+                        .map_err(unified_exec_tool_error);
                 }
                 None => {
                     tracing::warn!(
@@ -320,6 +313,7 @@ impl<'a> ToolRuntime<UnifiedExecRequest, UnifiedExecProcess> for UnifiedExecRunt
         let options = ExecOptions {
             expiration: ExecExpiration::DefaultTimeout,
             capture_policy: ExecCapturePolicy::ShellTool,
+            resource_limits: ctx.turn.config.permissions.resource_limits.clone(),
         };
         let mut exec_env = attempt
             .env_for(command, options, req.network.as_ref())
@@ -339,14 +333,24 @@ impl<'a> ToolRuntime<UnifiedExecRequest, UnifiedExecProcess> for UnifiedExecRunt
                 environment.as_ref(),
             )
             .await
-            .map_err(|err| match err {
-                UnifiedExecError::SandboxDenied { output, .. } => {
-                    ToolError::Codex(CodexErr::Sandbox(SandboxErr::Denied {
-                        output: Box::new(output),
-                        network_policy_decision: None,
-                    }))
-                }
-                other => ToolError::Rejected(other.to_string()),
-            })
+            .map_err(unified_exec_tool_error)
+    }
+}
+
+fn unified_exec_tool_error(err: UnifiedExecError) -> ToolError {
+    match err {
+        UnifiedExecError::SandboxDenied { output, .. } => {
+            ToolError::Codex(CodexErr::Sandbox(SandboxErr::Denied {
+                output: Box::new(output),
+                network_policy_decision: None,
+            }))
+        }
+        UnifiedExecError::ResourceLimitExceeded { limit, output } => {
+            ToolError::Codex(CodexErr::Sandbox(SandboxErr::ResourceLimitExceeded {
+                limit,
+                output: Box::new(output),
+            }))
+        }
+        other => ToolError::Rejected(other.to_string()),
     }
 }
@@ -28,6 +28,7 @@ use codex_sandboxing::SandboxTransformError;
 use codex_sandboxing::SandboxTransformRequest;
 use codex_sandboxing::SandboxType;
 use codex_sandboxing::SandboxablePreference;
+use codex_sandboxing::overlay::OverlayWorkspace;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use futures::Future;
 use futures::future::BoxFuture;
@@ -304,6 +305,12 @@ pub(crate) trait Approvable<Req> {
         None
     }
 
+    /// Whether this tool can honour a partial or edited patch approval. Every
+    /// other tool treats such a decision as a denial.
+    fn accepts_patch_approval(&self) -> bool {
+        false
+    }
+
     /// Decide we can request an approval for no-sandbox execution.
     fn wants_no_sandbox_approval(&self, policy: AskForApproval) -> bool {
         match policy {
@@ -365,6 +372,9 @@ pub(crate) struct SandboxAttempt<'a> {
     pub(crate) sandbox_cwd: &'a AbsolutePathBuf,
     pub codex_linux_sandbox_exe: Option<&'a std::path::PathBuf>,
     pub use_legacy_landlock: bool,
+    /// Per-turn workspace overlay; `None` for escalated attempts, which write
+    /// to the real tree.
+    pub overlay_workspace: Option<&'a OverlayWorkspace>,
     pub windows_sandbox_level: codex_protocol::config_types::WindowsSandboxLevel,
     pub windows_sandbox_private_desktop: bool,
 }
@@ -390,6 +400,7 @@ impl<'a> SandboxAttempt<'a> {
                     .codex_linux_sandbox_exe
                     .map(std::path::PathBuf::as_path),
                 use_legacy_landlock: self.use_legacy_landlock,
+                overlay_workspace: self.overlay_workspace,
                 windows_sandbox_level: self.windows_sandbox_level,
                 windows_sandbox_private_desktop: self.windows_sandbox_private_desktop,
             })
@@ -11,6 +11,9 @@ use sha1::digest::Output;
 use uuid::Uuid;
 
 use codex_protocol::protocol::FileChange;
+use codex_sandboxing::overlay::OverlayChange;
+use codex_sandboxing::overlay::OverlayEntry;
+use codex_sandboxing::overlay::OverlayWorkspace;
 
 const ZERO_OID: &str = "0000000000000000000000000000000000000000";
 const DEV_NULL: &str = "/dev/null";
@@ -29,6 +32,9 @@ struct BaselineFileInfo {
 /// 2. Keep a stable internal filename (uuid) per external path for rename tracking.
 /// 3. To compute the aggregated unified diff, compare each baseline snapshot to the current file on disk entirely in-memory
 ///    using the `similar` crate and emit unified diffs with rewritten external paths.
+///
+/// When the turn runs in an overlay workspace, baselines still come from the real tree while the
+/// current side is read through the overlay, so the diff shows what committing the overlay would do.
 #[derive(Default)]
 pub struct TurnDiffTracker {
     /// Map external path -> internal filename (uuid).
@@ -40,6 +46,8 @@ pub struct TurnDiffTracker {
     temp_name_to_current_path: HashMap<String, PathBuf>,
     /// Cache of known git worktree roots to avoid repeated filesystem walks.
     git_root_cache: Vec<PathBuf>,
+    /// Overlay that holds this turn's writes, if the turn is sandboxed with one.
+    overlay: Option<OverlayWorkspace>,
 }
 
 impl TurnDiffTracker {
@@ -47,51 +55,28 @@ impl TurnDiffTracker {
         Self::default()
     }
 
+    pub fn with_overlay(overlay: OverlayWorkspace) -> Self {
+        Self {
+            overlay: Some(overlay),
+            ..Self::default()
+        }
+    }
+
+    /// Track files that commands changed inside the overlay, so they show up in the aggregated
+    /// diff alongside apply_patch edits.
+    pub fn on_overlay_changes(&mut self, changes: &[OverlayChange]) {
+        for change in changes {
+            self.track_path(&change.path);
+        }
+    }
+
     /// Front-run apply patch calls to track the starting contents of any modified files.
     /// - Creates an in-memory baseline snapshot for files that already exist on disk when first seen.
     /// - For additions, we intentionally do not create a baseline snapshot so that diffs are proper additions.
     /// - Also updates internal mappings for move/rename events.
     pub fn on_patch_begin(&mut self, changes: &HashMap<PathBuf, FileChange>) {
         for (path, change) in changes.iter() {
-            // Ensure a stable internal filename exists for this external path.
-            if !self.external_to_temp_name.contains_key(path) {
-                let internal = Uuid::new_v4().to_string();
-                self.external_to_temp_name
-                    .insert(path.clone(), internal.clone());
-                self.temp_name_to_current_path
-                    .insert(internal.clone(), path.clone());
-
-                // If the file exists on disk now, snapshot as baseline; else leave missing to represent /dev/null.
-                let baseline_file_info = if path.exists() {
-                    let mode = file_mode_for_path(path);
-                    let mode_val = mode.unwrap_or(FileMode::Regular);
-                    let content = blob_bytes(path, mode_val).unwrap_or_default();
-                    let oid = if mode == Some(FileMode::Symlink) {
-                        format!("{:x}", git_blob_sha1_hex_bytes(&content))
-                    } else {
-                        self.git_blob_oid_for_path(path)
-                            .unwrap_or_else(|| format!("{:x}", git_blob_sha1_hex_bytes(&content)))
-                    };
-                    Some(BaselineFileInfo {
-                        path: path.clone(),
-                        content,
-                        mode: mode_val,
-                        oid,
-                    })
-                } else {
-                    Some(BaselineFileInfo {
-                        path: path.clone(),
-                        content: vec![],
-                        mode: FileMode::Regular,
-                        oid: ZERO_OID.to_string(),
-                    })
-                };
-
-                if let Some(baseline_file_info) = baseline_file_info {
-                    self.baseline_file_info
-                        .insert(internal.clone(), baseline_file_info);
-                }
-            }
+            self.track_path(path);
 
             // Track rename/move in current mapping if provided in an Update.
             if let FileChange::Update {
@@ -127,6 +112,58 @@ impl TurnDiffTracker {
         }
     }
 
+    /// Ensure a stable internal filename and baseline snapshot exist for this external path.
+    fn track_path(&mut self, path: &Path) {
+        if self.external_to_temp_name.contains_key(path) {
+            return;
+        }
+        let internal = Uuid::new_v4().to_string();
+        self.external_to_temp_name
+            .insert(path.to_path_buf(), internal.clone());
+        self.temp_name_to_current_path
+            .insert(internal.clone(), path.to_path_buf());
+
+        // If the file exists on disk now, snapshot as baseline; else leave missing to represent /dev/null.
+        let baseline_file_info = if path.exists() {
+            let mode = file_mode_for_path(path);
+            let mode_val = mode.unwrap_or(FileMode::Regular);
+            let content = blob_bytes(path, mode_val).unwrap_or_default();
+            let oid = if mode == Some(FileMode::Symlink) {
+                format!("{:x}", git_blob_sha1_hex_bytes(&content))
+            } else {
+                self.git_blob_oid_for_path(path, path)
+                    .unwrap_or_else(|| format!("{:x}", git_blob_sha1_hex_bytes(&content)))
+            };
+            BaselineFileInfo {
+                path: path.to_path_buf(),
+                content,
+                mode: mode_val,
+                oid,
+            }
+        } else {
+            BaselineFileInfo {
+                path: path.to_path_buf(),
+                content: vec![],
+                mode: FileMode::Regular,
+                oid: ZERO_OID.to_string(),
+            }
+        };
+        self.baseline_file_info.insert(internal, baseline_file_info);
+    }
+
+    /// Where the current contents of `path` live: the overlay's upper layer when the turn wrote
+    /// it there, `None` when the overlay removed it, and the path itself otherwise.
+    fn current_source_path(&self, path: &Path) -> Option<PathBuf> {
+        let Some(overlay) = &self.overlay else {
+            return Some(path.to_path_buf());
+        };
+        match overlay.resolve(path) {
+            OverlayEntry::Lower => Some(path.to_path_buf()),
+            OverlayEntry::Upper(upper_path) => Some(upper_path),
+            OverlayEntry::Removed => None,
+        }
+    }
+
     fn get_path_for_internal(&self, internal: &str) -> Option<PathBuf> {
         self.temp_name_to_current_path
             .get(internal)
@@ -198,20 +235,22 @@ impl TurnDiffTracker {
         s.replace('\\', "/")
     }
 
-    /// Ask git to compute the blob SHA-1 for the file at `path` within its repository.
+    /// Ask git to compute the blob SHA-1 for the file at `path` within its repository, reading
+    /// the contents from `source` (which differs from `path` when the file lives in an overlay).
     /// Returns None if no repository is found or git invocation fails.
-    fn git_blob_oid_for_path(&mut self, path: &Path) -> Option<String> {
+    fn git_blob_oid_for_path(&mut self, path: &Path, source: &Path) -> Option<String> {
         let root = self.find_git_root_cached(path)?;
         // Compute a path relative to the repo root for better portability across platforms.
         let rel = path.strip_prefix(&root).unwrap_or(path);
-        let output = Command::new("git")
-            .arg("-C")
-            .arg(&root)
-            .arg("hash-object")
-            .arg("--")
-            .arg(rel)
-            .output()
-            .ok()?;
+        let mut command = Command::new("git");
+        command.arg("-C").arg(&root).arg("hash-object");
+        if source == path {
+            command.arg("--").arg(rel);
+        } else {
+            // `--path` keeps git's attribute-based filters keyed to the workspace path.
+            command.arg("--path").arg(rel).arg("--").arg(source);
+        }
+        let output = command.output().ok()?;
         if !output.status.success() {
             return None;
         }
@@ -265,23 +304,28 @@ impl TurnDiffTracker {
             None => return aggregated,
         };
 
-        let current_mode = file_mode_for_path(&current_external_path).unwrap_or(FileMode::Regular);
-        let right_bytes = blob_bytes(&current_external_path, current_mode);
+        let current_source = self.current_source_path(&current_external_path);
+        let current_mode = current_source
+            .as_deref()
+            .and_then(file_mode_for_path)
+            .unwrap_or(FileMode::Regular);
+        let right_bytes = current_source
+            .as_deref()
+            .and_then(|source| blob_bytes(source, current_mode));
 
         // Compute displays with &mut self before borrowing any baseline content.
         let left_display = self.relative_to_git_root_str(&baseline_external_path);
         let right_display = self.relative_to_git_root_str(&current_external_path);
 
         // Compute right oid before borrowing baseline content.
-        let right_oid = if let Some(b) = right_bytes.as_ref() {
-            if current_mode == FileMode::Symlink {
+        let right_oid = match (right_bytes.as_ref(), current_source.as_deref()) {
+            (Some(b), _) if current_mode == FileMode::Symlink => {
                 format!("{:x}", git_blob_sha1_hex_bytes(b))
-            } else {
-                self.git_blob_oid_for_path(&current_external_path)
-                    .unwrap_or_else(|| format!("{:x}", git_blob_sha1_hex_bytes(b)))
             }
-        } else {
-            ZERO_OID.to_string()
+            (Some(b), Some(source)) => self
+                .git_blob_oid_for_path(&current_external_path, source)
+                .unwrap_or_else(|| format!("{:x}", git_blob_sha1_hex_bytes(b))),
+            _ => ZERO_OID.to_string(),
         };
 
         // Borrow baseline content only after all &mut self uses are done.
@@ -294,8 +338,9 @@ impl TurnDiffTracker {
             None
         };
 
-        // Fast path: identical bytes or both missing.
-        if left_bytes == right_bytes.as_deref() {
+        // Fast path: identical bytes and mode, or both missing.
+        let same_bytes = left_bytes == right_bytes.as_deref();
+        if same_bytes && baseline_mode == current_mode {
             return aggregated;
         }
 
@@ -313,6 +358,11 @@ impl TurnDiffTracker {
             aggregated.push_str(&format!("new mode {current_mode}\n"));
         }
 
+        // A pure mode change has no content hunks, matching `git diff`.
+        if same_bytes {
+            return aggregated;
+        }
+
         let left_text = left_bytes.and_then(|b| std::str::from_utf8(b).ok());
         let right_text = right_bytes
             .as_deref()
@@ -428,7 +478,8 @@ fn file_mode_for_path(_path: &Path) -> Option<FileMode> {
 }
 
 fn blob_bytes(path: &Path, mode: FileMode) -> Option<Vec<u8>> {
-    if path.exists() {
+    // `symlink_metadata` so that a dangling symlink still counts as present.
+    if fs::symlink_metadata(path).is_ok() {
         let contents = if mode == FileMode::Symlink {
             symlink_blob_bytes(path)
                 .ok_or_else(|| anyhow!("failed to read symlink target for {}", path.display()))
@@ -179,6 +179,71 @@ index {left_oid}..{right_oid}
     assert_eq!(out, expected);
 }
 
+#[cfg(unix)]
+#[test]
+fn mode_only_change_yields_mode_header() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = tempdir().unwrap();
+    let file = dir.path().join("run.sh");
+    fs::write(&file, "echo hi\n").unwrap();
+    fs::set_permissions(&file, fs::Permissions::from_mode(0o644)).unwrap();
+
+    let mut acc = TurnDiffTracker::new();
+    let changes = HashMap::from([(
+        file.clone(),
+        FileChange::Update {
+            unified_diff: "new mode 755\n".to_owned(),
+            move_path: None,
+        },
+    )]);
+    acc.on_patch_begin(&changes);
+
+    fs::set_permissions(&file, fs::Permissions::from_mode(0o755)).unwrap();
+
+    let out = acc.get_unified_diff().unwrap().unwrap();
+    let out = normalize_diff_for_test(&out, dir.path());
+    let expected = r#"diff --git a/<TMP>/run.sh b/<TMP>/run.sh
+old mode 100644
+new mode 100755
+"#;
+    assert_eq!(out, expected);
+}
+
+#[cfg(unix)]
+#[test]
+fn dangling_symlink_add_is_reported() {
+    let dir = tempdir().unwrap();
+    let link = dir.path().join("latest");
+
+    let mut acc = TurnDiffTracker::new();
+    let changes = HashMap::from([(
+        link.clone(),
+        FileChange::Add {
+            content: "Symbolic link to missing\n".to_owned(),
+        },
+    )]);
+    acc.on_patch_begin(&changes);
+
+    std::os::unix::fs::symlink("missing", &link).unwrap();
+
+    let out = acc.get_unified_diff().unwrap().unwrap();
+    let out = normalize_diff_for_test(&out, dir.path());
+    let right_oid = git_blob_sha1_hex("missing");
+    let expected = format!(
+        r#"diff --git a/<TMP>/latest b/<TMP>/latest
+new file mode 120000
+index {ZERO_OID}..{right_oid}
+--- {DEV_NULL}
++++ b/<TMP>/latest
+@@ -0,0 +1 @@
++missing
+\ No newline at end of file
+"#
+    );
+    assert_eq!(out, expected);
+}
+
 #[test]
 fn move_without_1change_yields_no_diff() {
     let dir = tempdir().unwrap();
@@ -425,3 +490,52 @@ index {ZERO_OID}..{right_oid}
     };
     assert_eq!(combined, expected_combined);
 }
+
+#[test]
+fn overlay_changes_diff_upper_layer_against_real_tree() {
+    let dir = tempdir().unwrap();
+    let workspace = dir.path().join("workspace");
+    fs::create_dir_all(&workspace).unwrap();
+    fs::write(workspace.join("a.txt"), "old\n").unwrap();
+    let overlay = OverlayWorkspace::new(
+        codex_utils_absolute_path::AbsolutePathBuf::from_absolute_path(&workspace).unwrap(),
+        &codex_utils_absolute_path::AbsolutePathBuf::from_absolute_path(dir.path().join("overlay"))
+            .unwrap(),
+    );
+    overlay.create_dirs().unwrap();
+    fs::write(overlay.upper_dir.join("a.txt"), "new\n").unwrap();
+    fs::write(overlay.upper_dir.join("b.txt"), "added\n").unwrap();
+
+    let mut acc = TurnDiffTracker::with_overlay(overlay.clone());
+    acc.on_overlay_changes(&overlay.changes().unwrap());
+    let diff = acc.get_unified_diff().unwrap().unwrap();
+    let diff = normalize_diff_for_test(&diff, dir.path());
+
+    // The real tree is untouched; only the overlay holds the edits.
+    assert_eq!(
+        fs::read_to_string(workspace.join("a.txt")).unwrap(),
+        "old\n"
+    );
+    let mode = FileMode::Regular;
+    let old_oid = git_blob_sha1_hex("old\n");
+    let new_oid = git_blob_sha1_hex("new\n");
+    let added_oid = git_blob_sha1_hex("added\n");
+    let expected = format!(
+        r#"diff --git a/<TMP>/workspace/a.txt b/<TMP>/workspace/a.txt
+index {old_oid}..{new_oid}
+--- a/<TMP>/workspace/a.txt
++++ b/<TMP>/workspace/a.txt
+@@ -1 +1 @@
+-old
++new
+diff --git a/<TMP>/workspace/b.txt b/<TMP>/workspace/b.txt
+new file mode {mode}
+index {ZERO_OID}..{added_oid}
+--- {DEV_NULL}
++++ b/<TMP>/workspace/b.txt
+@@ -0,0 +1 @@
++added
+"#,
+    );
+    assert_eq!(diff, expected);
+}
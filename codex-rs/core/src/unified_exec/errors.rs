@@ -1,3 +1,4 @@
+use codex_protocol::error::ResourceLimitKind;
 use codex_protocol::exec_output::ExecToolCallOutput;
 use thiserror::Error;
 
@@ -23,6 +24,11 @@ pub(crate) enum UnifiedExecError {
         message: String,
         output: ExecToolCallOutput,
     },
+    #[error("Command was killed for exceeding its {limit} limit")]
+    ResourceLimitExceeded {
+        limit: ResourceLimitKind,
+        output: ExecToolCallOutput,
+    },
 }
 
 impl UnifiedExecError {
@@ -37,4 +43,11 @@ impl UnifiedExecError {
     pub(crate) fn sandbox_denied(message: String, output: ExecToolCallOutput) -> Self {
         Self::SandboxDenied { message, output }
     }
+
+    pub(crate) fn resource_limit_exceeded(
+        limit: ResourceLimitKind,
+        output: ExecToolCallOutput,
+    ) -> Self {
+        Self::ResourceLimitExceeded { limit, output }
+    }
 }
@@ -17,6 +17,7 @@ use codex_exec_server::ExecProcess;
 use codex_exec_server::ReadResponse as ExecReadResponse;
 use codex_exec_server::StartedExecProcess;
 use codex_exec_server::WriteStatus;
+use codex_protocol::error::ResourceLimitKind;
 use codex_protocol::exec_output::ExecToolCallOutput;
 use codex_protocol::exec_output::StreamOutput;
 use codex_protocol::protocol::TruncationPolicy;
@@ -24,6 +25,7 @@ use codex_sandboxing::SandboxType;
 use codex_utils_output_truncation::formatted_truncate_text;
 use codex_utils_pty::ExecCommandSession;
 use codex_utils_pty::SpawnedPty;
+use codex_utils_pty::resource_limits::ExceededResourceLimit;
 
 use super::UNIFIED_EXEC_OUTPUT_MAX_TOKENS;
 use super::UnifiedExecError;
@@ -245,6 +247,8 @@ impl UnifiedExecProcess {
         &self,
         text: &str,
     ) -> Result<(), UnifiedExecError> {
+        self.check_for_resource_limit_with_text(text)?;
+
         let sandbox_type = self.sandbox_type();
         if sandbox_type == SandboxType::None || !self.has_exited() {
             return Ok(());
@@ -272,6 +276,34 @@ impl UnifiedExecProcess {
         Ok(())
     }
 
+    /// Fails with the resource limit that ended an exited local process, so
+    /// the limit is reported instead of a bare signal exit.
+    pub(super) fn check_for_resource_limit_with_text(
+        &self,
+        text: &str,
+    ) -> Result<(), UnifiedExecError> {
+        let ProcessHandle::Local(process_handle) = &self.process_handle else {
+            return Ok(());
+        };
+        if !self.has_exited() {
+            return Ok(());
+        }
+        let Some(limit) = process_handle.exceeded_resource_limit() else {
+            return Ok(());
+        };
+
+        let exec_output = ExecToolCallOutput {
+            exit_code: self.exit_code().unwrap_or(-1),
+            stderr: StreamOutput::new(text.to_string()),
+            aggregated_output: StreamOutput::new(text.to_string()),
+            ..Default::default()
+        };
+        Err(UnifiedExecError::resource_limit_exceeded(
+            resource_limit_kind(limit),
+            exec_output,
+        ))
+    }
+
     pub(super) async fn from_spawned(
         spawned: SpawnedPty,
         sandbox_type: SandboxType,
@@ -497,3 +529,13 @@ impl Drop for UnifiedExecProcess {
         self.terminate();
     }
 }
+
+fn resource_limit_kind(limit: ExceededResourceLimit) -> ResourceLimitKind {
+    match limit {
+        ExceededResourceLimit::Memory => ResourceLimitKind::Memory,
+        ExceededResourceLimit::CpuTime => ResourceLimitKind::CpuTime,
+        ExceededResourceLimit::Processes => ResourceLimitKind::Processes,
+        ExceededResourceLimit::OutputBytes => ResourceLimitKind::OutputBytes,
+        ExceededResourceLimit::WallTime => ResourceLimitKind::WallTime,
+    }
+}
@@ -499,6 +499,7 @@ impl UnifiedExecProcessManager {
                 process_id,
             } => (Some(process_id), exit_code, call_id),
             ProcessStatus::Exited { exit_code, entry } => {
+                entry.process.check_for_resource_limit_with_text(&text)?;
                 let call_id = entry.call_id.clone();
                 (None, exit_code, call_id)
             }
@@ -744,6 +745,7 @@ impl UnifiedExecProcessManager {
                 &request.arg0,
                 codex_utils_pty::TerminalSize::default(),
                 &inherited_fds,
+                &request.resource_limits,
             )
             .await
         } else {
@@ -754,6 +756,7 @@ impl UnifiedExecProcessManager {
                 &request.env,
                 &request.arg0,
                 &inherited_fds,
+                &request.resource_limits,
             )
             .await
         };
@@ -794,6 +797,7 @@ impl UnifiedExecProcessManager {
             .exec_policy
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 command: &request.command,
+                cwd: &cwd,
                 approval_policy: context.turn.approval_policy.value(),
                 sandbox_policy: context.turn.sandbox_policy.get(),
                 file_system_sandbox_policy: &context.turn.file_system_sandbox_policy,
@@ -849,6 +853,10 @@ impl UnifiedExecProcessManager {
                     };
                     UnifiedExecError::sandbox_denied(message, output)
                 }
+                ToolError::Codex(CodexErr::Sandbox(SandboxErr::ResourceLimitExceeded {
+                    limit,
+                    output,
+                })) => UnifiedExecError::resource_limit_exceeded(limit, *output),
                 other => UnifiedExecError::create_process(format!("{other:?}")),
             })
     }
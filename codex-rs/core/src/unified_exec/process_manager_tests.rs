@@ -106,6 +106,7 @@ fn exec_server_params_use_env_policy_overlay_contract() {
         network_sandbox_policy: codex_protocol::permissions::NetworkSandboxPolicy::Restricted,
         windows_sandbox_filesystem_overrides: None,
         arg0: None,
+        resource_limits: codex_utils_pty::resource_limits::ResourceLimits::default(),
     };
 
     let params =
@@ -59,6 +59,7 @@ async fn responses_stream_includes_subagent_header_on_review() {
         auth: None,
         aws: None,
         wire_api: WireApi::Responses,
+        messages: None,
         query_params: None,
         http_headers: None,
         env_http_headers: None,
@@ -185,6 +186,7 @@ async fn responses_stream_includes_subagent_header_on_other() {
         auth: None,
         aws: None,
         wire_api: WireApi::Responses,
+        messages: None,
         query_params: None,
         http_headers: None,
         env_http_headers: None,
@@ -296,6 +298,7 @@ async fn responses_respects_model_info_overrides_from_config() {
         auth: None,
         aws: None,
         wire_api: WireApi::Responses,
+        messages: None,
         query_params: None,
         http_headers: None,
         env_http_headers: None,
@@ -845,6 +845,7 @@ async fn send_provider_auth_request(server: &MockServer, auth: ModelProviderAuth
         auth: Some(auth),
         aws: None,
         wire_api: WireApi::Responses,
+        messages: None,
         query_params: None,
         http_headers: None,
         env_http_headers: None,
@@ -2159,6 +2160,7 @@ async fn azure_responses_request_includes_store_and_reasoning_ids() {
         auth: None,
         aws: None,
         wire_api: WireApi::Responses,
+        messages: None,
         query_params: None,
         http_headers: None,
         env_http_headers: None,
@@ -2789,6 +2791,7 @@ async fn azure_overrides_assign_properties_used_for_responses_url() {
         )])),
         env_key_instructions: None,
         wire_api: WireApi::Responses,
+        messages: None,
         http_headers: Some(std::collections::HashMap::from([(
             "Custom-Header".to_string(),
             "Value".to_string(),
@@ -2877,6 +2880,7 @@ async fn env_var_overrides_loaded_auth() {
         auth: None,
         aws: None,
         wire_api: WireApi::Responses,
+        messages: None,
         http_headers: Some(std::collections::HashMap::from([(
             "Custom-Header".to_string(),
             "Value".to_string(),
@@ -1746,6 +1746,7 @@ fn websocket_provider_with_connect_timeout(
         auth: None,
         aws: None,
         wire_api: WireApi::Responses,
+        messages: None,
         query_params: None,
         http_headers: None,
         env_http_headers: None,
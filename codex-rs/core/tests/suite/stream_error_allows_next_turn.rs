@@ -72,6 +72,7 @@ async fn continue_after_stream_error() {
         auth: None,
         aws: None,
         wire_api: WireApi::Responses,
+        messages: None,
         query_params: None,
         http_headers: None,
         env_http_headers: None,
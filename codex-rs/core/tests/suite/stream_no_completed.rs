@@ -56,6 +56,7 @@ async fn retries_on_early_close() {
         auth: None,
         aws: None,
         wire_api: WireApi::Responses,
+        messages: None,
         query_params: None,
         http_headers: None,
         env_http_headers: None,
@@ -17,7 +17,9 @@ use tokio::sync::mpsc;
 use tokio::sync::watch;
 
 use tokio::time::timeout;
+use tokio_tungstenite::WebSocketStream;
 use tokio_tungstenite::connect_async;
+use tokio_tungstenite::tungstenite::protocol::Role;
 use tracing::debug;
 
 use crate::ProcessId;
@@ -40,25 +42,48 @@ use crate::protocol::ExecExitedNotification;
 use crate::protocol::ExecOutputDeltaNotification;
 use crate::protocol::ExecParams;
 use crate::protocol::ExecResponse;
+use crate::protocol::FS_CHANGED_METHOD;
 use crate::protocol::FS_COPY_METHOD;
 use crate::protocol::FS_CREATE_DIRECTORY_METHOD;
+use crate::protocol::FS_CREATE_SYMLINK_METHOD;
 use crate::protocol::FS_GET_METADATA_METHOD;
+use crate::protocol::FS_GLOB_METHOD;
+use crate::protocol::FS_GREP_METHOD;
 use crate::protocol::FS_READ_DIRECTORY_METHOD;
 use crate::protocol::FS_READ_FILE_METHOD;
 use crate::protocol::FS_REMOVE_METHOD;
+use crate::protocol::FS_RENAME_METHOD;
+use crate::protocol::FS_SET_PERMISSIONS_METHOD;
+use crate::protocol::FS_UNWATCH_METHOD;
+use crate::protocol::FS_WATCH_METHOD;
 use crate::protocol::FS_WRITE_FILE_METHOD;
+use crate::protocol::FsChangedNotification;
 use crate::protocol::FsCopyParams;
 use crate::protocol::FsCopyResponse;
 use crate::protocol::FsCreateDirectoryParams;
 use crate::protocol::FsCreateDirectoryResponse;
+use crate::protocol::FsCreateSymlinkParams;
+use crate::protocol::FsCreateSymlinkResponse;
 use crate::protocol::FsGetMetadataParams;
 use crate::protocol::FsGetMetadataResponse;
+use crate::protocol::FsGlobParams;
+use crate::protocol::FsGlobResponse;
+use crate::protocol::FsGrepParams;
+use crate::protocol::FsGrepResponse;
 use crate::protocol::FsReadDirectoryParams;
 use crate::protocol::FsReadDirectoryResponse;
 use crate::protocol::FsReadFileParams;
 use crate::protocol::FsReadFileResponse;
 use crate::protocol::FsRemoveParams;
 use crate::protocol::FsRemoveResponse;
+use crate::protocol::FsRenameParams;
+use crate::protocol::FsRenameResponse;
+use crate::protocol::FsSetPermissionsParams;
+use crate::protocol::FsSetPermissionsResponse;
+use crate::protocol::FsUnwatchParams;
+use crate::protocol::FsUnwatchResponse;
+use crate::protocol::FsWatchParams;
+use crate::protocol::FsWatchResponse;
 use crate::protocol::FsWriteFileParams;
 use crate::protocol::FsWriteFileResponse;
 use crate::protocol::HTTP_REQUEST_BODY_DELTA_METHOD;
@@ -91,6 +116,7 @@ impl Default for ExecServerClientConnectOptions {
             client_name: "codex-core".to_string(),
             initialize_timeout: INITIALIZE_TIMEOUT,
             resume_session_id: None,
+            auth_token: None,
         }
     }
 }
@@ -101,6 +127,7 @@ impl From<RemoteExecServerConnectArgs> for ExecServerClientConnectOptions {
             client_name: value.client_name,
             initialize_timeout: value.initialize_timeout,
             resume_session_id: value.resume_session_id,
+            auth_token: value.auth_token,
         }
     }
 }
@@ -113,6 +140,7 @@ impl RemoteExecServerConnectArgs {
             connect_timeout: CONNECT_TIMEOUT,
             initialize_timeout: INITIALIZE_TIMEOUT,
             resume_session_id: None,
+            auth_token: None,
         }
     }
 }
@@ -163,6 +191,8 @@ struct Inner {
     http_body_stream_failures: ArcSwap<HashMap<String, String>>,
     http_body_streams_write_lock: Mutex<()>,
     http_body_stream_next_id: AtomicU64,
+    // `fs/changed` notifications are routed by the client-chosen watch id.
+    fs_watches: StdMutex<HashMap<String, mpsc::UnboundedSender<FsChangedNotification>>>,
     session_id: std::sync::RwLock<Option<String>>,
     reader_task: tokio::task::JoinHandle<()>,
 }
@@ -181,13 +211,15 @@ pub struct ExecServerClient {
 #[derive(Clone)]
 pub(crate) struct LazyRemoteExecServerClient {
     websocket_url: String,
+    auth_token: Option<String>,
     client: Arc<OnceCell<ExecServerClient>>,
 }
 
 impl LazyRemoteExecServerClient {
-    pub(crate) fn new(websocket_url: String) -> Self {
+    pub(crate) fn new(websocket_url: String, auth_token: Option<String>) -> Self {
         Self {
             websocket_url,
+            auth_token,
             client: Arc::new(OnceCell::new()),
         }
     }
@@ -201,6 +233,7 @@ impl LazyRemoteExecServerClient {
                     connect_timeout: Duration::from_secs(5),
                     initialize_timeout: Duration::from_secs(5),
                     resume_session_id: None,
+                    auth_token: self.auth_token.clone(),
                 })
                 .await
             })
@@ -240,6 +273,12 @@ pub enum ExecServerError {
         #[source]
         source: tokio_tungstenite::tungstenite::Error,
     },
+    #[error("failed to connect to exec-server unix socket `{url}`: {source}")]
+    UnixSocketConnect {
+        url: String,
+        #[source]
+        source: std::io::Error,
+    },
     #[error("timed out waiting for exec-server initialize handshake after {timeout:?}")]
     InitializeTimedOut { timeout: Duration },
     #[error("exec-server transport closed")]
@@ -262,6 +301,27 @@ impl ExecServerClient {
     ) -> Result<Self, ExecServerError> {
         let websocket_url = args.websocket_url.clone();
         let connect_timeout = args.connect_timeout;
+        if let Some(socket_path) = websocket_url.strip_prefix("unix://") {
+            let stream = timeout(connect_timeout, codex_uds::UnixStream::connect(socket_path))
+                .await
+                .map_err(|_| ExecServerError::WebSocketConnectTimeout {
+                    url: websocket_url.clone(),
+                    timeout: connect_timeout,
+                })?
+                .map_err(|source| ExecServerError::UnixSocketConnect {
+                    url: websocket_url.clone(),
+                    source,
+                })?;
+            let stream = WebSocketStream::from_raw_socket(stream, Role::Client, None).await;
+            return Self::connect(
+                JsonRpcConnection::from_websocket(
+                    stream,
+                    format!("exec-server unix socket {websocket_url}"),
+                ),
+                args.into(),
+            )
+            .await;
+        }
         let (stream, _) = timeout(connect_timeout, connect_async(websocket_url.as_str()))
             .await
             .map_err(|_| ExecServerError::WebSocketConnectTimeout {
@@ -291,6 +351,7 @@ impl ExecServerClient {
             client_name,
             initialize_timeout,
             resume_session_id,
+            auth_token,
         } = options;
 
         timeout(initialize_timeout, async {
@@ -302,6 +363,7 @@ impl ExecServerClient {
                     &InitializeParams {
                         client_name,
                         resume_session_id,
+                        auth_token,
                     },
                 )
                 .await?;
@@ -404,6 +466,71 @@ impl ExecServerClient {
         self.call(FS_COPY_METHOD, &params).await
     }
 
+    pub async fn fs_create_symlink(
+        &self,
+        params: FsCreateSymlinkParams,
+    ) -> Result<FsCreateSymlinkResponse, ExecServerError> {
+        self.call(FS_CREATE_SYMLINK_METHOD, &params).await
+    }
+
+    pub async fn fs_set_permissions(
+        &self,
+        params: FsSetPermissionsParams,
+    ) -> Result<FsSetPermissionsResponse, ExecServerError> {
+        self.call(FS_SET_PERMISSIONS_METHOD, &params).await
+    }
+
+    pub async fn fs_rename(
+        &self,
+        params: FsRenameParams,
+    ) -> Result<FsRenameResponse, ExecServerError> {
+        self.call(FS_RENAME_METHOD, &params).await
+    }
+
+    pub async fn fs_glob(&self, params: FsGlobParams) -> Result<FsGlobResponse, ExecServerError> {
+        self.call(FS_GLOB_METHOD, &params).await
+    }
+
+    pub async fn fs_grep(&self, params: FsGrepParams) -> Result<FsGrepResponse, ExecServerError> {
+        self.call(FS_GREP_METHOD, &params).await
+    }
+
+    /// Starts a watch and returns the receiver for its `fs/changed`
+    /// notifications. The receiver closes after `fs_unwatch` or when the
+    /// transport disconnects.
+    pub async fn fs_watch(
+        &self,
+        params: FsWatchParams,
+    ) -> Result<
+        (
+            FsWatchResponse,
+            mpsc::UnboundedReceiver<FsChangedNotification>,
+        ),
+        ExecServerError,
+    > {
+        let watch_id = params.watch_id.clone();
+        let (events_tx, events_rx) = mpsc::unbounded_channel();
+        // Register the route before the request so no notification sent right
+        // after the response is dropped.
+        self.inner.insert_fs_watch(&watch_id, events_tx)?;
+        match self.call(FS_WATCH_METHOD, &params).await {
+            Ok(response) => Ok((response, events_rx)),
+            Err(error) => {
+                self.inner.remove_fs_watch(&watch_id);
+                Err(error)
+            }
+        }
+    }
+
+    pub async fn fs_unwatch(
+        &self,
+        params: FsUnwatchParams,
+    ) -> Result<FsUnwatchResponse, ExecServerError> {
+        let result = self.call(FS_UNWATCH_METHOD, &params).await;
+        self.inner.remove_fs_watch(&params.watch_id);
+        result
+    }
+
     pub(crate) async fn register_session(
         &self,
         process_id: &ProcessId,
@@ -477,6 +604,7 @@ impl ExecServerClient {
                 http_body_stream_failures: ArcSwap::from_pointee(HashMap::new()),
                 http_body_streams_write_lock: Mutex::new(()),
                 http_body_stream_next_id: AtomicU64::new(1),
+                fs_watches: StdMutex::new(HashMap::new()),
                 session_id: std::sync::RwLock::new(None),
                 reader_task,
             }
@@ -763,6 +891,51 @@ impl Inner {
         self.sessions.store(Arc::new(HashMap::new()));
         drained_sessions
     }
+
+    fn insert_fs_watch(
+        &self,
+        watch_id: &str,
+        events_tx: mpsc::UnboundedSender<FsChangedNotification>,
+    ) -> Result<(), ExecServerError> {
+        if let Some(error) = self.disconnected_error() {
+            return Err(error);
+        }
+        let mut fs_watches = self
+            .fs_watches
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if fs_watches.contains_key(watch_id) {
+            return Err(ExecServerError::Protocol(format!(
+                "fs watch already registered for watch id {watch_id}"
+            )));
+        }
+        fs_watches.insert(watch_id.to_string(), events_tx);
+        Ok(())
+    }
+
+    fn remove_fs_watch(&self, watch_id: &str) {
+        self.fs_watches
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .remove(watch_id);
+    }
+
+    fn publish_fs_change(&self, notification: FsChangedNotification) {
+        let fs_watches = self
+            .fs_watches
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if let Some(events_tx) = fs_watches.get(&notification.watch_id) {
+            let _ = events_tx.send(notification);
+        }
+    }
+
+    fn clear_fs_watches(&self) {
+        self.fs_watches
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .clear();
+    }
 }
 
 fn disconnected_message(reason: Option<&str>) -> String {
@@ -811,6 +984,7 @@ async fn fail_all_sessions(inner: &Arc<Inner>, message: String) {
 async fn fail_all_in_flight_work(inner: &Arc<Inner>, message: String) {
     fail_all_sessions(inner, message.clone()).await;
     inner.fail_all_http_body_streams(message).await;
+    inner.clear_fs_watches();
 }
 
 async fn handle_server_notification(
@@ -868,6 +1042,11 @@ async fn handle_server_notification(
                 .handle_http_body_delta_notification(notification.params)
                 .await?;
         }
+        FS_CHANGED_METHOD => {
+            let params: FsChangedNotification =
+                serde_json::from_value(notification.params.unwrap_or(Value::Null))?;
+            inner.publish_fs_change(params);
+        }
         other => {
             debug!("ignoring unknown exec-server notification: {other}");
         }
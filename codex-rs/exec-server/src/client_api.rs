@@ -13,9 +13,14 @@ pub struct ExecServerClientConnectOptions {
     pub client_name: String,
     pub initialize_timeout: Duration,
     pub resume_session_id: Option<String>,
+    /// Sent as `authToken` in `initialize` for servers started with a token.
+    pub auth_token: Option<String>,
 }
 
 /// WebSocket connection arguments for a remote exec-server.
+///
+/// `websocket_url` is either `ws://HOST:PORT` or `unix://PATH`; the latter
+/// speaks the same websocket framing over a Unix socket.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RemoteExecServerConnectArgs {
     pub websocket_url: String,
@@ -23,6 +28,7 @@ pub struct RemoteExecServerConnectArgs {
     pub connect_timeout: Duration,
     pub initialize_timeout: Duration,
     pub resume_session_id: Option<String>,
+    pub auth_token: Option<String>,
 }
 
 /// Sends HTTP requests through a runtime-selected transport.
@@ -1,22 +1,17 @@
 use codex_app_server_protocol::JSONRPCMessage;
 use futures::SinkExt;
 use futures::StreamExt;
+use tokio::io::AsyncBufReadExt;
 use tokio::io::AsyncRead;
 use tokio::io::AsyncWrite;
+use tokio::io::AsyncWriteExt;
+use tokio::io::BufReader;
+use tokio::io::BufWriter;
 use tokio::sync::mpsc;
 use tokio::sync::watch;
 use tokio_tungstenite::WebSocketStream;
 use tokio_tungstenite::tungstenite::Message;
 
-#[cfg(test)]
-use tokio::io::AsyncBufReadExt;
-#[cfg(test)]
-use tokio::io::AsyncWriteExt;
-#[cfg(test)]
-use tokio::io::BufReader;
-#[cfg(test)]
-use tokio::io::BufWriter;
-
 pub(crate) const CHANNEL_CAPACITY: usize = 128;
 
 #[derive(Debug)]
@@ -34,7 +29,8 @@ pub(crate) struct JsonRpcConnection {
 }
 
 impl JsonRpcConnection {
-    #[cfg(test)]
+    /// Newline-delimited JSON-RPC over a byte stream, used by the `stdio://`
+    /// transport.
     pub(crate) fn from_stdio<R, W>(reader: R, writer: W, connection_label: String) -> Self
     where
         R: AsyncRead + Unpin + Send + 'static,
@@ -298,7 +294,6 @@ async fn send_malformed_message(
         .await;
 }
 
-#[cfg(test)]
 async fn write_jsonrpc_line_message<W>(
     writer: &mut BufWriter<W>,
     message: &JSONRPCMessage,
@@ -6,9 +6,14 @@ use codex_protocol::permissions::FileSystemSandboxKind;
 use codex_protocol::permissions::FileSystemSandboxPolicy;
 use codex_protocol::permissions::FileSystemSpecialPath;
 use codex_protocol::protocol::SandboxPolicy;
+use codex_sandboxing::overlay::OverlayWorkspace;
 use codex_utils_absolute_path::AbsolutePathBuf;
+use futures::StreamExt;
+use futures::stream::BoxStream;
 use std::path::Path;
+use std::path::PathBuf;
 use tokio::io;
+use tokio::sync::mpsc;
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub struct CreateDirectoryOptions {
@@ -33,6 +38,10 @@ pub struct FileMetadata {
     pub is_symlink: bool,
     pub created_at_ms: i64,
     pub modified_at_ms: i64,
+    /// Unix permission bits of the resolved path, when the platform has them.
+    pub mode: Option<u32>,
+    /// Target of the symlink at the path itself, when it is one.
+    pub symlink_target: Option<PathBuf>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -42,6 +51,110 @@ pub struct ReadDirectoryEntry {
     pub is_file: bool,
 }
 
+/// Byte range for [`ExecutorFileSystem::read_file_range`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ReadFileRange {
+    pub offset: u64,
+    /// Maximum number of bytes to return. `None` reads to the end of the file.
+    pub length: Option<u64>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FileChunk {
+    pub data: Vec<u8>,
+    /// Size of the whole file when the chunk was read.
+    pub total_size: u64,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct WatchOptions {
+    pub recursive: bool,
+}
+
+/// Batch of paths that changed below a watched path.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FileChangeEvent {
+    pub changed_paths: Vec<AbsolutePathBuf>,
+}
+
+/// Live registration returned by [`ExecutorFileSystem::watch`]. Dropping it
+/// stops the watch.
+pub struct FileWatch {
+    path: AbsolutePathBuf,
+    events: mpsc::UnboundedReceiver<FileChangeEvent>,
+    _registration: Box<dyn Send>,
+}
+
+impl FileWatch {
+    pub(crate) fn new(
+        path: AbsolutePathBuf,
+        events: mpsc::UnboundedReceiver<FileChangeEvent>,
+        registration: impl Send + 'static,
+    ) -> Self {
+        Self {
+            path,
+            events,
+            _registration: Box::new(registration),
+        }
+    }
+
+    /// Path the watch was registered for.
+    pub fn path(&self) -> &AbsolutePathBuf {
+        &self.path
+    }
+
+    /// Waits for the next batch of changes, or returns `None` once the watch
+    /// can no longer deliver events.
+    pub async fn recv(&mut self) -> Option<FileChangeEvent> {
+        self.events.recv().await
+    }
+}
+
+impl std::fmt::Debug for FileWatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FileWatch")
+            .field("path", &self.path)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GlobOptions {
+    /// Patterns matched against paths relative to the search root.
+    pub patterns: Vec<String>,
+    pub max_results: usize,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GlobResult {
+    pub paths: Vec<AbsolutePathBuf>,
+    pub truncated: bool,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GrepOptions {
+    /// Regular expression matched against each line.
+    pub pattern: String,
+    /// Glob patterns restricting which files are searched. Empty searches all
+    /// files.
+    pub include: Vec<String>,
+    pub case_insensitive: bool,
+    pub max_results: usize,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GrepMatch {
+    pub path: AbsolutePathBuf,
+    pub line_number: u64,
+    pub line: String,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GrepResult {
+    pub matches: Vec<GrepMatch>,
+    pub truncated: bool,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct FileSystemSandboxContext {
@@ -53,6 +166,10 @@ pub struct FileSystemSandboxContext {
     pub windows_sandbox_private_desktop: bool,
     #[serde(default)]
     pub use_legacy_landlock: bool,
+    /// When set, sandboxed writes under the workspace root land in this
+    /// overlay instead of the real tree.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub overlay_workspace: Option<OverlayWorkspace>,
 }
 
 impl FileSystemSandboxContext {
@@ -83,6 +200,7 @@ impl FileSystemSandboxContext {
             windows_sandbox_level: WindowsSandboxLevel::Disabled,
             windows_sandbox_private_desktop: false,
             use_legacy_landlock: false,
+            overlay_workspace: None,
         }
     }
 
@@ -138,6 +256,47 @@ pub trait ExecutorFileSystem: Send + Sync {
         String::from_utf8(bytes).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
     }
 
+    /// Reads at most `range.length` bytes starting at `range.offset`. Reading
+    /// past the end of the file returns an empty chunk.
+    async fn read_file_range(
+        &self,
+        path: &AbsolutePathBuf,
+        range: ReadFileRange,
+        sandbox: Option<&FileSystemSandboxContext>,
+    ) -> FileSystemResult<FileChunk>;
+
+    /// Streams a file as a sequence of ranged reads of at most `chunk_size`
+    /// bytes, so large files never travel as one message.
+    fn read_file_stream<'a>(
+        &'a self,
+        path: &'a AbsolutePathBuf,
+        chunk_size: u64,
+        sandbox: Option<&'a FileSystemSandboxContext>,
+    ) -> BoxStream<'a, FileSystemResult<Vec<u8>>> {
+        futures::stream::try_unfold(Some(0), move |offset| async move {
+            let Some(offset) = offset else {
+                return Ok::<_, io::Error>(None);
+            };
+            let chunk = self
+                .read_file_range(
+                    path,
+                    ReadFileRange {
+                        offset,
+                        length: Some(chunk_size),
+                    },
+                    sandbox,
+                )
+                .await?;
+            if chunk.data.is_empty() {
+                return Ok(None);
+            }
+            let next_offset = offset.saturating_add(chunk.data.len() as u64);
+            let next_offset = (next_offset < chunk.total_size).then_some(next_offset);
+            Ok(Some((chunk.data, next_offset)))
+        })
+        .boxed()
+    }
+
     async fn write_file(
         &self,
         path: &AbsolutePathBuf,
@@ -178,4 +337,59 @@ pub trait ExecutorFileSystem: Send + Sync {
         copy_options: CopyOptions,
         sandbox: Option<&FileSystemSandboxContext>,
     ) -> FileSystemResult<()>;
+
+    /// Creates a symbolic link at `path` pointing to `target`. `target` is
+    /// stored verbatim, so a relative target resolves against the link's
+    /// parent directory.
+    async fn create_symlink(
+        &self,
+        path: &AbsolutePathBuf,
+        target: &Path,
+        sandbox: Option<&FileSystemSandboxContext>,
+    ) -> FileSystemResult<()>;
+
+    /// Sets the Unix permission bits of `path`.
+    async fn set_permissions(
+        &self,
+        path: &AbsolutePathBuf,
+        mode: u32,
+        sandbox: Option<&FileSystemSandboxContext>,
+    ) -> FileSystemResult<()>;
+
+    /// Atomically renames `source_path` to `destination_path`, replacing an
+    /// existing destination file. Fails rather than copying when the paths are
+    /// on different filesystems.
+    async fn rename(
+        &self,
+        source_path: &AbsolutePathBuf,
+        destination_path: &AbsolutePathBuf,
+        sandbox: Option<&FileSystemSandboxContext>,
+    ) -> FileSystemResult<()>;
+
+    /// Reports changes to `path`, or to entries below it when it is a
+    /// directory, until the returned [`FileWatch`] is dropped.
+    async fn watch(
+        &self,
+        path: &AbsolutePathBuf,
+        options: WatchOptions,
+        sandbox: Option<&FileSystemSandboxContext>,
+    ) -> FileSystemResult<FileWatch>;
+
+    /// Lists paths below `path` matching any of `options.patterns`, honoring
+    /// `.gitignore` files.
+    async fn glob(
+        &self,
+        path: &AbsolutePathBuf,
+        options: GlobOptions,
+        sandbox: Option<&FileSystemSandboxContext>,
+    ) -> FileSystemResult<GlobResult>;
+
+    /// Searches text files below `path` for lines matching `options.pattern`,
+    /// honoring `.gitignore` files and skipping binary files.
+    async fn grep(
+        &self,
+        path: &AbsolutePathBuf,
+        options: GrepOptions,
+        sandbox: Option<&FileSystemSandboxContext>,
+    ) -> FileSystemResult<GrepResult>;
 }
@@ -8,21 +8,37 @@ use tokio::io;
 use crate::CopyOptions;
 use crate::CreateDirectoryOptions;
 use crate::ExecutorFileSystem;
+use crate::GlobOptions;
+use crate::GrepOptions;
+use crate::ReadFileRange;
 use crate::RemoveOptions;
+use crate::fs_search::DEFAULT_SEARCH_MAX_RESULTS;
 use crate::local_file_system::DirectFileSystem;
 use crate::protocol::FS_COPY_METHOD;
 use crate::protocol::FS_CREATE_DIRECTORY_METHOD;
+use crate::protocol::FS_CREATE_SYMLINK_METHOD;
 use crate::protocol::FS_GET_METADATA_METHOD;
+use crate::protocol::FS_GLOB_METHOD;
+use crate::protocol::FS_GREP_METHOD;
 use crate::protocol::FS_READ_DIRECTORY_METHOD;
 use crate::protocol::FS_READ_FILE_METHOD;
 use crate::protocol::FS_REMOVE_METHOD;
+use crate::protocol::FS_RENAME_METHOD;
+use crate::protocol::FS_SET_PERMISSIONS_METHOD;
 use crate::protocol::FS_WRITE_FILE_METHOD;
 use crate::protocol::FsCopyParams;
 use crate::protocol::FsCopyResponse;
 use crate::protocol::FsCreateDirectoryParams;
 use crate::protocol::FsCreateDirectoryResponse;
+use crate::protocol::FsCreateSymlinkParams;
+use crate::protocol::FsCreateSymlinkResponse;
 use crate::protocol::FsGetMetadataParams;
 use crate::protocol::FsGetMetadataResponse;
+use crate::protocol::FsGlobParams;
+use crate::protocol::FsGlobResponse;
+use crate::protocol::FsGrepMatch;
+use crate::protocol::FsGrepParams;
+use crate::protocol::FsGrepResponse;
 use crate::protocol::FsReadDirectoryEntry;
 use crate::protocol::FsReadDirectoryParams;
 use crate::protocol::FsReadDirectoryResponse;
@@ -30,6 +46,10 @@ use crate::protocol::FsReadFileParams;
 use crate::protocol::FsReadFileResponse;
 use crate::protocol::FsRemoveParams;
 use crate::protocol::FsRemoveResponse;
+use crate::protocol::FsRenameParams;
+use crate::protocol::FsRenameResponse;
+use crate::protocol::FsSetPermissionsParams;
+use crate::protocol::FsSetPermissionsResponse;
 use crate::protocol::FsWriteFileParams;
 use crate::protocol::FsWriteFileResponse;
 use crate::rpc::internal_error;
@@ -55,6 +75,16 @@ pub(crate) enum FsHelperRequest {
     Remove(FsRemoveParams),
     #[serde(rename = "fs/copy")]
     Copy(FsCopyParams),
+    #[serde(rename = "fs/createSymlink")]
+    CreateSymlink(FsCreateSymlinkParams),
+    #[serde(rename = "fs/setPermissions")]
+    SetPermissions(FsSetPermissionsParams),
+    #[serde(rename = "fs/rename")]
+    Rename(FsRenameParams),
+    #[serde(rename = "fs/glob")]
+    Glob(FsGlobParams),
+    #[serde(rename = "fs/grep")]
+    Grep(FsGrepParams),
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -81,6 +111,16 @@ pub(crate) enum FsHelperPayload {
     Remove(FsRemoveResponse),
     #[serde(rename = "fs/copy")]
     Copy(FsCopyResponse),
+    #[serde(rename = "fs/createSymlink")]
+    CreateSymlink(FsCreateSymlinkResponse),
+    #[serde(rename = "fs/setPermissions")]
+    SetPermissions(FsSetPermissionsResponse),
+    #[serde(rename = "fs/rename")]
+    Rename(FsRenameResponse),
+    #[serde(rename = "fs/glob")]
+    Glob(FsGlobResponse),
+    #[serde(rename = "fs/grep")]
+    Grep(FsGrepResponse),
 }
 
 impl FsHelperPayload {
@@ -93,6 +133,11 @@ impl FsHelperPayload {
             Self::ReadDirectory(_) => FS_READ_DIRECTORY_METHOD,
             Self::Remove(_) => FS_REMOVE_METHOD,
             Self::Copy(_) => FS_COPY_METHOD,
+            Self::CreateSymlink(_) => FS_CREATE_SYMLINK_METHOD,
+            Self::SetPermissions(_) => FS_SET_PERMISSIONS_METHOD,
+            Self::Rename(_) => FS_RENAME_METHOD,
+            Self::Glob(_) => FS_GLOB_METHOD,
+            Self::Grep(_) => FS_GREP_METHOD,
         }
     }
 
@@ -157,6 +202,51 @@ impl FsHelperPayload {
             other => Err(unexpected_response(FS_COPY_METHOD, other.operation())),
         }
     }
+
+    pub(crate) fn expect_create_symlink(
+        self,
+    ) -> Result<FsCreateSymlinkResponse, JSONRPCErrorError> {
+        match self {
+            Self::CreateSymlink(response) => Ok(response),
+            other => Err(unexpected_response(
+                FS_CREATE_SYMLINK_METHOD,
+                other.operation(),
+            )),
+        }
+    }
+
+    pub(crate) fn expect_set_permissions(
+        self,
+    ) -> Result<FsSetPermissionsResponse, JSONRPCErrorError> {
+        match self {
+            Self::SetPermissions(response) => Ok(response),
+            other => Err(unexpected_response(
+                FS_SET_PERMISSIONS_METHOD,
+                other.operation(),
+            )),
+        }
+    }
+
+    pub(crate) fn expect_rename(self) -> Result<FsRenameResponse, JSONRPCErrorError> {
+        match self {
+            Self::Rename(response) => Ok(response),
+            other => Err(unexpected_response(FS_RENAME_METHOD, other.operation())),
+        }
+    }
+
+    pub(crate) fn expect_glob(self) -> Result<FsGlobResponse, JSONRPCErrorError> {
+        match self {
+            Self::Glob(response) => Ok(response),
+            other => Err(unexpected_response(FS_GLOB_METHOD, other.operation())),
+        }
+    }
+
+    pub(crate) fn expect_grep(self) -> Result<FsGrepResponse, JSONRPCErrorError> {
+        match self {
+            Self::Grep(response) => Ok(response),
+            other => Err(unexpected_response(FS_GREP_METHOD, other.operation())),
+        }
+    }
 }
 
 fn unexpected_response(expected: &str, actual: &str) -> JSONRPCErrorError {
@@ -171,12 +261,30 @@ pub(crate) async fn run_direct_request(
     let file_system = DirectFileSystem;
     match request {
         FsHelperRequest::ReadFile(params) => {
-            let data = file_system
-                .read_file(&params.path, /*sandbox*/ None)
+            if params.offset.is_none() && params.length.is_none() {
+                let data = file_system
+                    .read_file(&params.path, /*sandbox*/ None)
+                    .await
+                    .map_err(map_fs_error)?;
+                return Ok(FsHelperPayload::ReadFile(FsReadFileResponse {
+                    data_base64: STANDARD.encode(data),
+                    total_size: None,
+                }));
+            }
+            let chunk = file_system
+                .read_file_range(
+                    &params.path,
+                    ReadFileRange {
+                        offset: params.offset.unwrap_or(0),
+                        length: params.length,
+                    },
+                    /*sandbox*/ None,
+                )
                 .await
                 .map_err(map_fs_error)?;
             Ok(FsHelperPayload::ReadFile(FsReadFileResponse {
-                data_base64: STANDARD.encode(data),
+                data_base64: STANDARD.encode(chunk.data),
+                total_size: Some(chunk.total_size),
             }))
         }
         FsHelperRequest::WriteFile(params) => {
@@ -217,6 +325,8 @@ pub(crate) async fn run_direct_request(
                 is_symlink: metadata.is_symlink,
                 created_at_ms: metadata.created_at_ms,
                 modified_at_ms: metadata.modified_at_ms,
+                mode: metadata.mode,
+                symlink_target: metadata.symlink_target,
             }))
         }
         FsHelperRequest::ReadDirectory(params) => {
@@ -263,6 +373,75 @@ pub(crate) async fn run_direct_request(
                 .map_err(map_fs_error)?;
             Ok(FsHelperPayload::Copy(FsCopyResponse {}))
         }
+        FsHelperRequest::CreateSymlink(params) => {
+            file_system
+                .create_symlink(&params.path, &params.target, /*sandbox*/ None)
+                .await
+                .map_err(map_fs_error)?;
+            Ok(FsHelperPayload::CreateSymlink(FsCreateSymlinkResponse {}))
+        }
+        FsHelperRequest::SetPermissions(params) => {
+            file_system
+                .set_permissions(&params.path, params.mode, /*sandbox*/ None)
+                .await
+                .map_err(map_fs_error)?;
+            Ok(FsHelperPayload::SetPermissions(FsSetPermissionsResponse {}))
+        }
+        FsHelperRequest::Rename(params) => {
+            file_system
+                .rename(
+                    &params.source_path,
+                    &params.destination_path,
+                    /*sandbox*/ None,
+                )
+                .await
+                .map_err(map_fs_error)?;
+            Ok(FsHelperPayload::Rename(FsRenameResponse {}))
+        }
+        FsHelperRequest::Glob(params) => {
+            let result = file_system
+                .glob(
+                    &params.path,
+                    GlobOptions {
+                        patterns: params.patterns,
+                        max_results: params.max_results.unwrap_or(DEFAULT_SEARCH_MAX_RESULTS),
+                    },
+                    /*sandbox*/ None,
+                )
+                .await
+                .map_err(map_fs_error)?;
+            Ok(FsHelperPayload::Glob(FsGlobResponse {
+                paths: result.paths,
+                truncated: result.truncated,
+            }))
+        }
+        FsHelperRequest::Grep(params) => {
+            let result = file_system
+                .grep(
+                    &params.path,
+                    GrepOptions {
+                        pattern: params.pattern,
+                        include: params.include,
+                        case_insensitive: params.case_insensitive,
+                        max_results: params.max_results.unwrap_or(DEFAULT_SEARCH_MAX_RESULTS),
+                    },
+                    /*sandbox*/ None,
+                )
+                .await
+                .map_err(map_fs_error)?;
+            Ok(FsHelperPayload::Grep(FsGrepResponse {
+                matches: result
+                    .matches
+                    .into_iter()
+                    .map(|grep_match| FsGrepMatch {
+                        path: grep_match.path,
+                        line_number: grep_match.line_number,
+                        line: grep_match.line,
+                    })
+                    .collect(),
+                truncated: result.truncated,
+            }))
+        }
     }
 }
 
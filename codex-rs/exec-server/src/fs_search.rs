@@ -0,0 +1,306 @@
+//! Server-side `fs/glob` and `fs/grep` so remote clients can search a
+//! workspace without transferring every file.
+
+use std::io;
+use std::path::Path;
+
+use codex_utils_absolute_path::AbsolutePathBuf;
+use globset::GlobBuilder;
+use globset::GlobSet;
+use globset::GlobSetBuilder;
+use ignore::WalkBuilder;
+use regex::bytes::RegexBuilder;
+
+use crate::GlobOptions;
+use crate::GlobResult;
+use crate::GrepMatch;
+use crate::GrepOptions;
+use crate::GrepResult;
+
+/// Default `maxResults` for `fs/glob` and `fs/grep`.
+pub(crate) const DEFAULT_SEARCH_MAX_RESULTS: usize = 1000;
+
+/// Files larger than this are skipped by `fs/grep`.
+const MAX_GREP_FILE_BYTES: u64 = 16 * 1024 * 1024;
+/// Matching lines longer than this are truncated in `fs/grep` results.
+const MAX_GREP_LINE_BYTES: usize = 2000;
+/// A NUL byte in this prefix marks a file as binary.
+const BINARY_SNIFF_BYTES: usize = 8 * 1024;
+
+pub(crate) fn glob(root: &Path, options: &GlobOptions) -> io::Result<GlobResult> {
+    let patterns = build_glob_set(&options.patterns)?;
+    std::fs::metadata(root)?;
+    let mut paths = Vec::new();
+    let mut truncated = false;
+    // Unreadable entries are skipped rather than failing the whole search.
+    for entry in walk(root).flatten() {
+        if entry.depth() == 0 {
+            continue;
+        }
+        let Ok(relative_path) = entry.path().strip_prefix(root) else {
+            continue;
+        };
+        if !patterns.is_match(relative_path) {
+            continue;
+        }
+        if paths.len() == options.max_results {
+            truncated = true;
+            break;
+        }
+        paths.push(AbsolutePathBuf::from_absolute_path(entry.path())?);
+    }
+    Ok(GlobResult { paths, truncated })
+}
+
+pub(crate) fn grep(root: &Path, options: &GrepOptions) -> io::Result<GrepResult> {
+    let regex = RegexBuilder::new(&options.pattern)
+        .case_insensitive(options.case_insensitive)
+        .build()
+        .map_err(|err| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("fs/grep pattern is not a valid regular expression: {err}"),
+            )
+        })?;
+    let include = if options.include.is_empty() {
+        None
+    } else {
+        Some(build_glob_set(&options.include)?)
+    };
+
+    std::fs::metadata(root)?;
+
+    let mut matches = Vec::new();
+    for entry in walk(root).flatten() {
+        if !entry
+            .file_type()
+            .is_some_and(|file_type| file_type.is_file())
+        {
+            continue;
+        }
+        if let Some(include) = &include {
+            let relative_path = match entry.path().strip_prefix(root) {
+                Ok(relative_path) if !relative_path.as_os_str().is_empty() => relative_path,
+                // `root` is itself a file; match its name instead.
+                _ => Path::new(entry.file_name()),
+            };
+            if !include.is_match(relative_path) {
+                continue;
+            }
+        }
+        if entry
+            .metadata()
+            .is_ok_and(|metadata| metadata.len() > MAX_GREP_FILE_BYTES)
+        {
+            continue;
+        }
+        // Files can disappear or become unreadable mid-walk; skip them like
+        // the walker does for unreadable directories.
+        let Ok(contents) = std::fs::read(entry.path()) else {
+            continue;
+        };
+        if contents[..contents.len().min(BINARY_SNIFF_BYTES)].contains(&0) {
+            continue;
+        }
+        let path = AbsolutePathBuf::from_absolute_path(entry.path())?;
+        for (index, line) in contents.split(|byte| *byte == b'\n').enumerate() {
+            let line = line.strip_suffix(b"\r").unwrap_or(line);
+            if !regex.is_match(line) {
+                continue;
+            }
+            if matches.len() == options.max_results {
+                return Ok(GrepResult {
+                    matches,
+                    truncated: true,
+                });
+            }
+            matches.push(GrepMatch {
+                path: path.clone(),
+                line_number: index as u64 + 1,
+                line: truncate_line(line),
+            });
+        }
+    }
+    Ok(GrepResult {
+        matches,
+        truncated: false,
+    })
+}
+
+fn walk(root: &Path) -> ignore::Walk {
+    WalkBuilder::new(root)
+        .hidden(false)
+        .require_git(false)
+        .filter_entry(|entry| entry.file_name() != ".git")
+        .sort_by_file_name(|left, right| left.cmp(right))
+        .build()
+}
+
+fn build_glob_set(patterns: &[String]) -> io::Result<GlobSet> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let glob = GlobBuilder::new(pattern)
+            .literal_separator(true)
+            .build()
+            .map_err(|err| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("invalid glob pattern `{pattern}`: {err}"),
+                )
+            })?;
+        builder.add(glob);
+    }
+    builder
+        .build()
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err.to_string()))
+}
+
+fn truncate_line(line: &[u8]) -> String {
+    if line.len() <= MAX_GREP_LINE_BYTES {
+        return String::from_utf8_lossy(line).into_owned();
+    }
+    // Back up to the start of a UTF-8 sequence so the cut does not split a
+    // character.
+    let mut end = MAX_GREP_LINE_BYTES;
+    while end > 0 && line[end] & 0b1100_0000 == 0b1000_0000 {
+        end -= 1;
+    }
+    let mut line = String::from_utf8_lossy(&line[..end]).into_owned();
+    line.push_str("...");
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    use super::*;
+
+    fn write(root: &Path, relative_path: &str, contents: &[u8]) {
+        let path = root.join(relative_path);
+        std::fs::create_dir_all(path.parent().expect("parent")).expect("create parent");
+        std::fs::write(path, contents).expect("write file");
+    }
+
+    fn absolute(path: std::path::PathBuf) -> AbsolutePathBuf {
+        AbsolutePathBuf::from_absolute_path(path).expect("absolute path")
+    }
+
+    #[test]
+    fn glob_matches_relative_paths_and_honors_gitignore() -> io::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path();
+        write(root, ".gitignore", b"generated/\n");
+        write(root, "lib.rs", b"");
+        write(root, "src/main.rs", b"");
+        write(root, "src/nested/mod.rs", b"");
+        write(root, "generated/out.rs", b"");
+        write(root, "README.md", b"");
+
+        let result = glob(
+            root,
+            &GlobOptions {
+                patterns: vec!["src/**/*.rs".to_string()],
+                max_results: DEFAULT_SEARCH_MAX_RESULTS,
+            },
+        )?;
+        assert_eq!(
+            result,
+            GlobResult {
+                paths: vec![
+                    absolute(root.join("src/main.rs")),
+                    absolute(root.join("src/nested/mod.rs")),
+                ],
+                truncated: false,
+            }
+        );
+
+        let result = glob(
+            root,
+            &GlobOptions {
+                patterns: vec!["*.rs".to_string(), "**/*.md".to_string()],
+                max_results: 1,
+            },
+        )?;
+        assert_eq!(
+            result,
+            GlobResult {
+                paths: vec![absolute(root.join("README.md"))],
+                truncated: true,
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn grep_reports_matching_lines_and_skips_binary_files() -> io::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path();
+        write(root, "a.txt", b"alpha\nneedle one\r\nbeta\n");
+        write(root, "b/c.rs", b"let NEEDLE = 2;\n");
+        write(root, "binary.bin", b"needle\0needle\n");
+
+        let result = grep(
+            root,
+            &GrepOptions {
+                pattern: "needle".to_string(),
+                include: Vec::new(),
+                case_insensitive: true,
+                max_results: DEFAULT_SEARCH_MAX_RESULTS,
+            },
+        )?;
+        assert_eq!(
+            result,
+            GrepResult {
+                matches: vec![
+                    GrepMatch {
+                        path: absolute(root.join("a.txt")),
+                        line_number: 2,
+                        line: "needle one".to_string(),
+                    },
+                    GrepMatch {
+                        path: absolute(root.join("b/c.rs")),
+                        line_number: 1,
+                        line: "let NEEDLE = 2;".to_string(),
+                    },
+                ],
+                truncated: false,
+            }
+        );
+
+        let result = grep(
+            root,
+            &GrepOptions {
+                pattern: "needle".to_string(),
+                include: vec!["**/*.rs".to_string()],
+                case_insensitive: false,
+                max_results: DEFAULT_SEARCH_MAX_RESULTS,
+            },
+        )?;
+        assert_eq!(
+            result,
+            GrepResult {
+                matches: Vec::new(),
+                truncated: false,
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn grep_rejects_invalid_pattern() {
+        let temp_dir = TempDir::new().expect("tempdir");
+        let err = grep(
+            temp_dir.path(),
+            &GrepOptions {
+                pattern: "(".to_string(),
+                include: Vec::new(),
+                case_insensitive: false,
+                max_results: DEFAULT_SEARCH_MAX_RESULTS,
+            },
+        )
+        .expect_err("invalid regex should fail");
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+}
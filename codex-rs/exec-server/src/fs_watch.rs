@@ -0,0 +1,141 @@
+//! `notify`-backed watches for local [`crate::ExecutorFileSystem::watch`].
+
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use codex_utils_absolute_path::AbsolutePathBuf;
+use notify::Event;
+use notify::EventKind;
+use notify::RecursiveMode;
+use notify::Watcher;
+use tokio::sync::mpsc;
+use tracing::warn;
+
+use crate::FileChangeEvent;
+use crate::FileWatch;
+use crate::WatchOptions;
+
+/// Decides whether a changed path may be reported to the watcher's owner.
+pub(crate) type WatchPathFilter = Arc<dyn Fn(&Path) -> bool + Send + Sync>;
+
+pub(crate) fn watch_path(
+    path: &AbsolutePathBuf,
+    options: WatchOptions,
+    path_filter: Option<WatchPathFilter>,
+) -> io::Result<FileWatch> {
+    // Some backends report canonical paths such as `/private/var/...` for a
+    // watch registered through `/var/...`; rebase them onto the requested path.
+    let canonical_root = std::fs::canonicalize(path.as_path())?;
+    let requested_root = path.to_path_buf();
+    let (events_tx, events_rx) = mpsc::unbounded_channel();
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<Event>| {
+        let event = match event {
+            Ok(event) => event,
+            Err(err) => {
+                warn!("exec-server file watch error: {err}");
+                return;
+            }
+        };
+        if matches!(event.kind, EventKind::Access(_)) {
+            return;
+        }
+        let mut changed_paths = event
+            .paths
+            .into_iter()
+            .map(|changed_path| rebase_path(changed_path, &canonical_root, &requested_root))
+            .filter(|changed_path| {
+                path_filter
+                    .as_ref()
+                    .is_none_or(|path_filter| path_filter(changed_path))
+            })
+            .collect::<Vec<_>>();
+        changed_paths.sort();
+        changed_paths.dedup();
+        let changed_paths = changed_paths
+            .into_iter()
+            .filter_map(|changed_path| AbsolutePathBuf::from_absolute_path(changed_path).ok())
+            .collect::<Vec<_>>();
+        if !changed_paths.is_empty() {
+            let _ = events_tx.send(FileChangeEvent { changed_paths });
+        }
+    })
+    .map_err(notify_error)?;
+    let recursive_mode = if options.recursive {
+        RecursiveMode::Recursive
+    } else {
+        RecursiveMode::NonRecursive
+    };
+    watcher
+        .watch(path.as_path(), recursive_mode)
+        .map_err(notify_error)?;
+    Ok(FileWatch::new(path.clone(), events_rx, watcher))
+}
+
+fn rebase_path(changed_path: PathBuf, canonical_root: &Path, requested_root: &Path) -> PathBuf {
+    match changed_path.strip_prefix(canonical_root) {
+        Ok(relative_path) if relative_path.as_os_str().is_empty() => requested_root.to_path_buf(),
+        Ok(relative_path) => requested_root.join(relative_path),
+        Err(_) => changed_path,
+    }
+}
+
+fn notify_error(err: notify::Error) -> io::Error {
+    match err.kind {
+        notify::ErrorKind::Io(err) => err,
+        notify::ErrorKind::PathNotFound => {
+            io::Error::new(io::ErrorKind::NotFound, "watched path does not exist")
+        }
+        kind => io::Error::other(format!("failed to watch path: {kind:?}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+    use tokio::time::timeout;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn reports_changes_below_watched_directory() -> io::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let root = AbsolutePathBuf::from_absolute_path(temp_dir.path())?;
+        let mut watch = watch_path(
+            &root,
+            WatchOptions { recursive: false },
+            /*path_filter*/ None,
+        )?;
+
+        std::fs::write(temp_dir.path().join("created.txt"), "hello")?;
+
+        let event = timeout(Duration::from_secs(10), watch.recv())
+            .await
+            .map_err(|_| io::Error::other("timed out waiting for file change"))?
+            .expect("watch should stay open");
+        assert_eq!(event.changed_paths, vec![root.join("created.txt")]);
+        Ok(())
+    }
+
+    #[test]
+    fn rebase_path_maps_canonical_paths_onto_requested_root() {
+        let canonical_root = Path::new("/private/var/folders/x");
+        let requested_root = Path::new("/var/folders/x");
+        assert_eq!(
+            [
+                rebase_path(canonical_root.join("a.txt"), canonical_root, requested_root),
+                rebase_path(canonical_root.to_path_buf(), canonical_root, requested_root),
+                rebase_path(PathBuf::from("/elsewhere"), canonical_root, requested_root),
+            ],
+            [
+                requested_root.join("a.txt"),
+                requested_root.to_path_buf(),
+                PathBuf::from("/elsewhere"),
+            ]
+        );
+    }
+}
@@ -6,6 +6,8 @@ mod file_system;
 mod fs_helper;
 mod fs_helper_main;
 mod fs_sandbox;
+mod fs_search;
+mod fs_watch;
 mod local_file_system;
 mod local_process;
 mod process;
@@ -25,6 +27,7 @@ pub use client::http_client::ReqwestHttpClient;
 pub use client_api::ExecServerClientConnectOptions;
 pub use client_api::HttpClient;
 pub use client_api::RemoteExecServerConnectArgs;
+pub use environment::CODEX_EXEC_SERVER_AUTH_TOKEN_ENV_VAR;
 pub use environment::CODEX_EXEC_SERVER_URL_ENV_VAR;
 pub use environment::Environment;
 pub use environment::EnvironmentManager;
@@ -34,11 +37,21 @@ pub use environment::REMOTE_ENVIRONMENT_ID;
 pub use file_system::CopyOptions;
 pub use file_system::CreateDirectoryOptions;
 pub use file_system::ExecutorFileSystem;
+pub use file_system::FileChangeEvent;
+pub use file_system::FileChunk;
 pub use file_system::FileMetadata;
 pub use file_system::FileSystemResult;
 pub use file_system::FileSystemSandboxContext;
+pub use file_system::FileWatch;
+pub use file_system::GlobOptions;
+pub use file_system::GlobResult;
+pub use file_system::GrepMatch;
+pub use file_system::GrepOptions;
+pub use file_system::GrepResult;
 pub use file_system::ReadDirectoryEntry;
+pub use file_system::ReadFileRange;
 pub use file_system::RemoveOptions;
+pub use file_system::WatchOptions;
 pub use fs_helper::CODEX_FS_HELPER_ARG1;
 pub use fs_helper_main::main as run_fs_helper_main;
 pub use local_file_system::LOCAL_FS;
@@ -56,12 +69,20 @@ pub use protocol::ExecOutputDeltaNotification;
 pub use protocol::ExecOutputStream;
 pub use protocol::ExecParams;
 pub use protocol::ExecResponse;
+pub use protocol::FsChangedNotification;
 pub use protocol::FsCopyParams;
 pub use protocol::FsCopyResponse;
 pub use protocol::FsCreateDirectoryParams;
 pub use protocol::FsCreateDirectoryResponse;
+pub use protocol::FsCreateSymlinkParams;
+pub use protocol::FsCreateSymlinkResponse;
 pub use protocol::FsGetMetadataParams;
 pub use protocol::FsGetMetadataResponse;
+pub use protocol::FsGlobParams;
+pub use protocol::FsGlobResponse;
+pub use protocol::FsGrepMatch;
+pub use protocol::FsGrepParams;
+pub use protocol::FsGrepResponse;
 pub use protocol::FsReadDirectoryEntry;
 pub use protocol::FsReadDirectoryParams;
 pub use protocol::FsReadDirectoryResponse;
@@ -69,6 +90,14 @@ pub use protocol::FsReadFileParams;
 pub use protocol::FsReadFileResponse;
 pub use protocol::FsRemoveParams;
 pub use protocol::FsRemoveResponse;
+pub use protocol::FsRenameParams;
+pub use protocol::FsRenameResponse;
+pub use protocol::FsSetPermissionsParams;
+pub use protocol::FsSetPermissionsResponse;
+pub use protocol::FsUnwatchParams;
+pub use protocol::FsUnwatchResponse;
+pub use protocol::FsWatchParams;
+pub use protocol::FsWatchResponse;
 pub use protocol::FsWriteFileParams;
 pub use protocol::FsWriteFileResponse;
 pub use protocol::HttpHeader;
@@ -87,5 +116,6 @@ pub use protocol::WriteResponse;
 pub use protocol::WriteStatus;
 pub use runtime_paths::ExecServerRuntimePaths;
 pub use server::DEFAULT_LISTEN_URL;
+pub use server::ExecServerAuthToken;
 pub use server::ExecServerListenUrlParseError;
 pub use server::run_main;
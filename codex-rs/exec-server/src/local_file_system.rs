@@ -7,19 +7,31 @@ use std::sync::LazyLock;
 use std::time::SystemTime;
 use std::time::UNIX_EPOCH;
 use tokio::io;
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncSeekExt;
 
 use crate::CopyOptions;
 use crate::CreateDirectoryOptions;
 use crate::ExecServerRuntimePaths;
 use crate::ExecutorFileSystem;
+use crate::FileChunk;
 use crate::FileMetadata;
 use crate::FileSystemResult;
 use crate::FileSystemSandboxContext;
+use crate::FileWatch;
+use crate::GlobOptions;
+use crate::GlobResult;
+use crate::GrepOptions;
+use crate::GrepResult;
 use crate::ReadDirectoryEntry;
+use crate::ReadFileRange;
 use crate::RemoveOptions;
+use crate::WatchOptions;
+use crate::fs_search;
+use crate::fs_watch;
 use crate::sandboxed_file_system::SandboxedFileSystem;
 
-const MAX_READ_FILE_BYTES: u64 = 512 * 1024 * 1024;
+pub(crate) const MAX_READ_FILE_BYTES: u64 = 512 * 1024 * 1024;
 
 pub static LOCAL_FS: LazyLock<Arc<dyn ExecutorFileSystem>> =
     LazyLock::new(|| -> Arc<dyn ExecutorFileSystem> { Arc::new(LocalFileSystem::unsandboxed()) });
@@ -88,6 +100,16 @@ impl ExecutorFileSystem for LocalFileSystem {
         file_system.read_file(path, sandbox).await
     }
 
+    async fn read_file_range(
+        &self,
+        path: &AbsolutePathBuf,
+        range: ReadFileRange,
+        sandbox: Option<&FileSystemSandboxContext>,
+    ) -> FileSystemResult<FileChunk> {
+        let (file_system, sandbox) = self.file_system_for(sandbox)?;
+        file_system.read_file_range(path, range, sandbox).await
+    }
+
     async fn write_file(
         &self,
         path: &AbsolutePathBuf,
@@ -148,6 +170,68 @@ impl ExecutorFileSystem for LocalFileSystem {
             .copy(source_path, destination_path, options, sandbox)
             .await
     }
+
+    async fn create_symlink(
+        &self,
+        path: &AbsolutePathBuf,
+        target: &Path,
+        sandbox: Option<&FileSystemSandboxContext>,
+    ) -> FileSystemResult<()> {
+        let (file_system, sandbox) = self.file_system_for(sandbox)?;
+        file_system.create_symlink(path, target, sandbox).await
+    }
+
+    async fn set_permissions(
+        &self,
+        path: &AbsolutePathBuf,
+        mode: u32,
+        sandbox: Option<&FileSystemSandboxContext>,
+    ) -> FileSystemResult<()> {
+        let (file_system, sandbox) = self.file_system_for(sandbox)?;
+        file_system.set_permissions(path, mode, sandbox).await
+    }
+
+    async fn rename(
+        &self,
+        source_path: &AbsolutePathBuf,
+        destination_path: &AbsolutePathBuf,
+        sandbox: Option<&FileSystemSandboxContext>,
+    ) -> FileSystemResult<()> {
+        let (file_system, sandbox) = self.file_system_for(sandbox)?;
+        file_system
+            .rename(source_path, destination_path, sandbox)
+            .await
+    }
+
+    async fn watch(
+        &self,
+        path: &AbsolutePathBuf,
+        options: WatchOptions,
+        sandbox: Option<&FileSystemSandboxContext>,
+    ) -> FileSystemResult<FileWatch> {
+        let (file_system, sandbox) = self.file_system_for(sandbox)?;
+        file_system.watch(path, options, sandbox).await
+    }
+
+    async fn glob(
+        &self,
+        path: &AbsolutePathBuf,
+        options: GlobOptions,
+        sandbox: Option<&FileSystemSandboxContext>,
+    ) -> FileSystemResult<GlobResult> {
+        let (file_system, sandbox) = self.file_system_for(sandbox)?;
+        file_system.glob(path, options, sandbox).await
+    }
+
+    async fn grep(
+        &self,
+        path: &AbsolutePathBuf,
+        options: GrepOptions,
+        sandbox: Option<&FileSystemSandboxContext>,
+    ) -> FileSystemResult<GrepResult> {
+        let (file_system, sandbox) = self.file_system_for(sandbox)?;
+        file_system.grep(path, options, sandbox).await
+    }
 }
 
 #[async_trait]
@@ -161,6 +245,18 @@ impl ExecutorFileSystem for UnsandboxedFileSystem {
         self.file_system.read_file(path, /*sandbox*/ None).await
     }
 
+    async fn read_file_range(
+        &self,
+        path: &AbsolutePathBuf,
+        range: ReadFileRange,
+        sandbox: Option<&FileSystemSandboxContext>,
+    ) -> FileSystemResult<FileChunk> {
+        reject_platform_sandbox_context(sandbox)?;
+        self.file_system
+            .read_file_range(path, range, /*sandbox*/ None)
+            .await
+    }
+
     async fn write_file(
         &self,
         path: &AbsolutePathBuf,
@@ -234,6 +330,74 @@ impl ExecutorFileSystem for UnsandboxedFileSystem {
             )
             .await
     }
+
+    async fn create_symlink(
+        &self,
+        path: &AbsolutePathBuf,
+        target: &Path,
+        sandbox: Option<&FileSystemSandboxContext>,
+    ) -> FileSystemResult<()> {
+        reject_platform_sandbox_context(sandbox)?;
+        self.file_system
+            .create_symlink(path, target, /*sandbox*/ None)
+            .await
+    }
+
+    async fn set_permissions(
+        &self,
+        path: &AbsolutePathBuf,
+        mode: u32,
+        sandbox: Option<&FileSystemSandboxContext>,
+    ) -> FileSystemResult<()> {
+        reject_platform_sandbox_context(sandbox)?;
+        self.file_system
+            .set_permissions(path, mode, /*sandbox*/ None)
+            .await
+    }
+
+    async fn rename(
+        &self,
+        source_path: &AbsolutePathBuf,
+        destination_path: &AbsolutePathBuf,
+        sandbox: Option<&FileSystemSandboxContext>,
+    ) -> FileSystemResult<()> {
+        reject_platform_sandbox_context(sandbox)?;
+        self.file_system
+            .rename(source_path, destination_path, /*sandbox*/ None)
+            .await
+    }
+
+    async fn watch(
+        &self,
+        path: &AbsolutePathBuf,
+        options: WatchOptions,
+        sandbox: Option<&FileSystemSandboxContext>,
+    ) -> FileSystemResult<FileWatch> {
+        reject_platform_sandbox_context(sandbox)?;
+        self.file_system
+            .watch(path, options, /*sandbox*/ None)
+            .await
+    }
+
+    async fn glob(
+        &self,
+        path: &AbsolutePathBuf,
+        options: GlobOptions,
+        sandbox: Option<&FileSystemSandboxContext>,
+    ) -> FileSystemResult<GlobResult> {
+        reject_platform_sandbox_context(sandbox)?;
+        self.file_system.glob(path, options, /*sandbox*/ None).await
+    }
+
+    async fn grep(
+        &self,
+        path: &AbsolutePathBuf,
+        options: GrepOptions,
+        sandbox: Option<&FileSystemSandboxContext>,
+    ) -> FileSystemResult<GrepResult> {
+        reject_platform_sandbox_context(sandbox)?;
+        self.file_system.grep(path, options, /*sandbox*/ None).await
+    }
 }
 
 #[async_trait]
@@ -254,6 +418,31 @@ impl ExecutorFileSystem for DirectFileSystem {
         tokio::fs::read(path.as_path()).await
     }
 
+    async fn read_file_range(
+        &self,
+        path: &AbsolutePathBuf,
+        range: ReadFileRange,
+        sandbox: Option<&FileSystemSandboxContext>,
+    ) -> FileSystemResult<FileChunk> {
+        reject_sandbox_context(sandbox)?;
+        let mut file = tokio::fs::File::open(path.as_path()).await?;
+        let total_size = file.metadata().await?.len();
+        let remaining = total_size.saturating_sub(range.offset);
+        let length = range.length.unwrap_or(remaining);
+        if length.min(remaining) > MAX_READ_FILE_BYTES {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("file range is too large to read: limit is {MAX_READ_FILE_BYTES} bytes"),
+            ));
+        }
+        file.seek(io::SeekFrom::Start(range.offset)).await?;
+        let mut data = Vec::new();
+        file.take(length.min(MAX_READ_FILE_BYTES))
+            .read_to_end(&mut data)
+            .await?;
+        Ok(FileChunk { data, total_size })
+    }
+
     async fn write_file(
         &self,
         path: &AbsolutePathBuf,
@@ -287,12 +476,20 @@ impl ExecutorFileSystem for DirectFileSystem {
         reject_sandbox_context(sandbox)?;
         let metadata = tokio::fs::metadata(path.as_path()).await?;
         let symlink_metadata = tokio::fs::symlink_metadata(path.as_path()).await?;
+        let is_symlink = symlink_metadata.file_type().is_symlink();
+        let symlink_target = if is_symlink {
+            Some(tokio::fs::read_link(path.as_path()).await?)
+        } else {
+            None
+        };
         Ok(FileMetadata {
             is_directory: metadata.is_dir(),
             is_file: metadata.is_file(),
-            is_symlink: symlink_metadata.file_type().is_symlink(),
+            is_symlink,
             created_at_ms: metadata.created().ok().map_or(0, system_time_to_unix_ms),
             modified_at_ms: metadata.modified().ok().map_or(0, system_time_to_unix_ms),
+            mode: permission_mode(&metadata),
+            symlink_target,
         })
     }
 
@@ -395,6 +592,88 @@ impl ExecutorFileSystem for DirectFileSystem {
         .await
         .map_err(|err| io::Error::other(format!("filesystem task failed: {err}")))?
     }
+
+    async fn create_symlink(
+        &self,
+        path: &AbsolutePathBuf,
+        target: &Path,
+        sandbox: Option<&FileSystemSandboxContext>,
+    ) -> FileSystemResult<()> {
+        reject_sandbox_context(sandbox)?;
+        let path = path.to_path_buf();
+        let target = target.to_path_buf();
+        tokio::task::spawn_blocking(move || create_symlink(target.as_path(), path.as_path()))
+            .await
+            .map_err(|err| io::Error::other(format!("filesystem task failed: {err}")))?
+    }
+
+    async fn set_permissions(
+        &self,
+        path: &AbsolutePathBuf,
+        mode: u32,
+        sandbox: Option<&FileSystemSandboxContext>,
+    ) -> FileSystemResult<()> {
+        reject_sandbox_context(sandbox)?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            tokio::fs::set_permissions(path.as_path(), std::fs::Permissions::from_mode(mode)).await
+        }
+        #[cfg(not(unix))]
+        {
+            let _ = (path, mode);
+            Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "setting permission bits is unsupported on this platform",
+            ))
+        }
+    }
+
+    async fn rename(
+        &self,
+        source_path: &AbsolutePathBuf,
+        destination_path: &AbsolutePathBuf,
+        sandbox: Option<&FileSystemSandboxContext>,
+    ) -> FileSystemResult<()> {
+        reject_sandbox_context(sandbox)?;
+        tokio::fs::rename(source_path.as_path(), destination_path.as_path()).await
+    }
+
+    async fn watch(
+        &self,
+        path: &AbsolutePathBuf,
+        options: WatchOptions,
+        sandbox: Option<&FileSystemSandboxContext>,
+    ) -> FileSystemResult<FileWatch> {
+        reject_sandbox_context(sandbox)?;
+        fs_watch::watch_path(path, options, /*path_filter*/ None)
+    }
+
+    async fn glob(
+        &self,
+        path: &AbsolutePathBuf,
+        options: GlobOptions,
+        sandbox: Option<&FileSystemSandboxContext>,
+    ) -> FileSystemResult<GlobResult> {
+        reject_sandbox_context(sandbox)?;
+        let root = path.to_path_buf();
+        tokio::task::spawn_blocking(move || fs_search::glob(root.as_path(), &options))
+            .await
+            .map_err(|err| io::Error::other(format!("filesystem task failed: {err}")))?
+    }
+
+    async fn grep(
+        &self,
+        path: &AbsolutePathBuf,
+        options: GrepOptions,
+        sandbox: Option<&FileSystemSandboxContext>,
+    ) -> FileSystemResult<GrepResult> {
+        reject_sandbox_context(sandbox)?;
+        let root = path.to_path_buf();
+        tokio::task::spawn_blocking(move || fs_search::grep(root.as_path(), &options))
+            .await
+            .map_err(|err| io::Error::other(format!("filesystem task failed: {err}")))?
+    }
 }
 
 fn reject_sandbox_context(sandbox: Option<&FileSystemSandboxContext>) -> io::Result<()> {
@@ -474,10 +753,6 @@ pub(crate) fn current_sandbox_cwd() -> io::Result<PathBuf> {
 
 fn copy_symlink(source: &Path, target: &Path) -> io::Result<()> {
     let link_target = std::fs::read_link(source)?;
-    #[cfg(unix)]
-    {
-        std::os::unix::fs::symlink(&link_target, target)
-    }
     #[cfg(windows)]
     {
         if symlink_points_to_directory(source)? {
@@ -486,17 +761,54 @@ fn copy_symlink(source: &Path, target: &Path) -> io::Result<()> {
             std::os::windows::fs::symlink_file(&link_target, target)
         }
     }
+    #[cfg(not(windows))]
+    {
+        create_symlink(&link_target, target)
+    }
+}
+
+/// Creates a symlink at `link_path` pointing to `link_target`. On Windows the
+/// link is created as a directory link when the target resolves to a
+/// directory.
+fn create_symlink(link_target: &Path, link_path: &Path) -> io::Result<()> {
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink(link_target, link_path)
+    }
+    #[cfg(windows)]
+    {
+        let resolved_target = match link_path.parent() {
+            Some(parent) => parent.join(link_target),
+            None => link_target.to_path_buf(),
+        };
+        if resolved_target.is_dir() {
+            std::os::windows::fs::symlink_dir(link_target, link_path)
+        } else {
+            std::os::windows::fs::symlink_file(link_target, link_path)
+        }
+    }
     #[cfg(not(any(unix, windows)))]
     {
         let _ = link_target;
-        let _ = target;
+        let _ = link_path;
         Err(io::Error::new(
             io::ErrorKind::Unsupported,
-            "copying symlinks is unsupported on this platform",
+            "creating symlinks is unsupported on this platform",
         ))
     }
 }
 
+#[cfg(unix)]
+fn permission_mode(metadata: &std::fs::Metadata) -> Option<u32> {
+    use std::os::unix::fs::PermissionsExt;
+    Some(metadata.permissions().mode() & 0o7777)
+}
+
+#[cfg(not(unix))]
+fn permission_mode(_metadata: &std::fs::Metadata) -> Option<u32> {
+    None
+}
+
 #[cfg(windows)]
 fn symlink_points_to_directory(source: &Path) -> io::Result<bool> {
     use std::os::windows::fs::FileTypeExt;
@@ -2,29 +2,56 @@ use async_trait::async_trait;
 use base64::Engine as _;
 use base64::engine::general_purpose::STANDARD;
 use codex_utils_absolute_path::AbsolutePathBuf;
+use futures::TryStreamExt;
+use std::path::Path;
 use tokio::io;
+use tokio::sync::mpsc;
 use tracing::trace;
+use tracing::warn;
+use uuid::Uuid;
 
 use crate::CopyOptions;
 use crate::CreateDirectoryOptions;
+use crate::ExecServerClient;
 use crate::ExecServerError;
 use crate::ExecutorFileSystem;
+use crate::FileChangeEvent;
+use crate::FileChunk;
 use crate::FileMetadata;
 use crate::FileSystemResult;
 use crate::FileSystemSandboxContext;
+use crate::FileWatch;
+use crate::GlobOptions;
+use crate::GlobResult;
+use crate::GrepMatch;
+use crate::GrepOptions;
+use crate::GrepResult;
 use crate::ReadDirectoryEntry;
+use crate::ReadFileRange;
 use crate::RemoveOptions;
+use crate::WatchOptions;
 use crate::client::LazyRemoteExecServerClient;
+use crate::local_file_system::MAX_READ_FILE_BYTES;
 use crate::protocol::FsCopyParams;
 use crate::protocol::FsCreateDirectoryParams;
+use crate::protocol::FsCreateSymlinkParams;
 use crate::protocol::FsGetMetadataParams;
+use crate::protocol::FsGlobParams;
+use crate::protocol::FsGrepParams;
 use crate::protocol::FsReadDirectoryParams;
 use crate::protocol::FsReadFileParams;
 use crate::protocol::FsRemoveParams;
+use crate::protocol::FsRenameParams;
+use crate::protocol::FsSetPermissionsParams;
+use crate::protocol::FsUnwatchParams;
+use crate::protocol::FsWatchParams;
 use crate::protocol::FsWriteFileParams;
 
 const INVALID_REQUEST_ERROR_CODE: i64 = -32600;
 const NOT_FOUND_ERROR_CODE: i64 = -32004;
+/// Whole-file reads are fetched in chunks of this size so a large file never
+/// has to fit in a single JSON-RPC message.
+const READ_FILE_CHUNK_BYTES: u64 = 4 * 1024 * 1024;
 
 #[derive(Clone)]
 pub(crate) struct RemoteFileSystem {
@@ -46,20 +73,56 @@ impl ExecutorFileSystem for RemoteFileSystem {
         sandbox: Option<&FileSystemSandboxContext>,
     ) -> FileSystemResult<Vec<u8>> {
         trace!("remote fs read_file");
+        let mut chunks = self.read_file_stream(path, READ_FILE_CHUNK_BYTES, sandbox);
+        let mut contents = Vec::new();
+        while let Some(chunk) = chunks.try_next().await? {
+            if contents.len() as u64 + chunk.len() as u64 > MAX_READ_FILE_BYTES {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("file is too large to read: limit is {MAX_READ_FILE_BYTES} bytes"),
+                ));
+            }
+            contents.extend_from_slice(&chunk);
+        }
+        Ok(contents)
+    }
+
+    async fn read_file_range(
+        &self,
+        path: &AbsolutePathBuf,
+        range: ReadFileRange,
+        sandbox: Option<&FileSystemSandboxContext>,
+    ) -> FileSystemResult<FileChunk> {
+        trace!("remote fs read_file_range");
         let client = self.client.get().await.map_err(map_remote_error)?;
         let response = client
             .fs_read_file(FsReadFileParams {
                 path: path.clone(),
+                offset: Some(range.offset),
+                length: range.length,
                 sandbox: remote_sandbox_context(sandbox),
             })
             .await
             .map_err(map_remote_error)?;
-        STANDARD.decode(response.data_base64).map_err(|err| {
+        let data = STANDARD.decode(response.data_base64).map_err(|err| {
             io::Error::new(
                 io::ErrorKind::InvalidData,
                 format!("remote fs/readFile returned invalid base64 dataBase64: {err}"),
             )
-        })
+        })?;
+        // Servers that predate ranged reads ignore `offset`/`length` and
+        // return the whole file without `totalSize`.
+        let total_size = match response.total_size {
+            Some(total_size) => total_size,
+            None if range.offset == 0 => data.len() as u64,
+            None => {
+                return Err(io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    "remote exec-server does not support ranged fs/readFile",
+                ));
+            }
+        };
+        Ok(FileChunk { data, total_size })
     }
 
     async fn write_file(
@@ -120,6 +183,8 @@ impl ExecutorFileSystem for RemoteFileSystem {
             is_symlink: response.is_symlink,
             created_at_ms: response.created_at_ms,
             modified_at_ms: response.modified_at_ms,
+            mode: response.mode,
+            symlink_target: response.symlink_target,
         })
     }
 
@@ -188,6 +253,182 @@ impl ExecutorFileSystem for RemoteFileSystem {
             .map_err(map_remote_error)?;
         Ok(())
     }
+
+    async fn create_symlink(
+        &self,
+        path: &AbsolutePathBuf,
+        target: &Path,
+        sandbox: Option<&FileSystemSandboxContext>,
+    ) -> FileSystemResult<()> {
+        trace!("remote fs create_symlink");
+        let client = self.client.get().await.map_err(map_remote_error)?;
+        client
+            .fs_create_symlink(FsCreateSymlinkParams {
+                path: path.clone(),
+                target: target.to_path_buf(),
+                sandbox: remote_sandbox_context(sandbox),
+            })
+            .await
+            .map_err(map_remote_error)?;
+        Ok(())
+    }
+
+    async fn set_permissions(
+        &self,
+        path: &AbsolutePathBuf,
+        mode: u32,
+        sandbox: Option<&FileSystemSandboxContext>,
+    ) -> FileSystemResult<()> {
+        trace!("remote fs set_permissions");
+        let client = self.client.get().await.map_err(map_remote_error)?;
+        client
+            .fs_set_permissions(FsSetPermissionsParams {
+                path: path.clone(),
+                mode,
+                sandbox: remote_sandbox_context(sandbox),
+            })
+            .await
+            .map_err(map_remote_error)?;
+        Ok(())
+    }
+
+    async fn rename(
+        &self,
+        source_path: &AbsolutePathBuf,
+        destination_path: &AbsolutePathBuf,
+        sandbox: Option<&FileSystemSandboxContext>,
+    ) -> FileSystemResult<()> {
+        trace!("remote fs rename");
+        let client = self.client.get().await.map_err(map_remote_error)?;
+        client
+            .fs_rename(FsRenameParams {
+                source_path: source_path.clone(),
+                destination_path: destination_path.clone(),
+                sandbox: remote_sandbox_context(sandbox),
+            })
+            .await
+            .map_err(map_remote_error)?;
+        Ok(())
+    }
+
+    async fn watch(
+        &self,
+        path: &AbsolutePathBuf,
+        options: WatchOptions,
+        sandbox: Option<&FileSystemSandboxContext>,
+    ) -> FileSystemResult<FileWatch> {
+        trace!("remote fs watch");
+        let client = self.client.get().await.map_err(map_remote_error)?;
+        let watch_id = Uuid::new_v4().to_string();
+        let (response, mut notifications) = client
+            .fs_watch(FsWatchParams {
+                watch_id: watch_id.clone(),
+                path: path.clone(),
+                recursive: options.recursive,
+                sandbox: remote_sandbox_context(sandbox),
+            })
+            .await
+            .map_err(map_remote_error)?;
+        let (events_tx, events_rx) = mpsc::unbounded_channel();
+        let forward_task = tokio::spawn(async move {
+            while let Some(notification) = notifications.recv().await {
+                let event = FileChangeEvent {
+                    changed_paths: notification.changed_paths,
+                };
+                if events_tx.send(event).is_err() {
+                    break;
+                }
+            }
+        });
+        Ok(FileWatch::new(
+            response.path,
+            events_rx,
+            RemoteWatchRegistration {
+                client,
+                watch_id,
+                forward_task,
+            },
+        ))
+    }
+
+    async fn glob(
+        &self,
+        path: &AbsolutePathBuf,
+        options: GlobOptions,
+        sandbox: Option<&FileSystemSandboxContext>,
+    ) -> FileSystemResult<GlobResult> {
+        trace!("remote fs glob");
+        let client = self.client.get().await.map_err(map_remote_error)?;
+        let response = client
+            .fs_glob(FsGlobParams {
+                path: path.clone(),
+                patterns: options.patterns,
+                max_results: Some(options.max_results),
+                sandbox: remote_sandbox_context(sandbox),
+            })
+            .await
+            .map_err(map_remote_error)?;
+        Ok(GlobResult {
+            paths: response.paths,
+            truncated: response.truncated,
+        })
+    }
+
+    async fn grep(
+        &self,
+        path: &AbsolutePathBuf,
+        options: GrepOptions,
+        sandbox: Option<&FileSystemSandboxContext>,
+    ) -> FileSystemResult<GrepResult> {
+        trace!("remote fs grep");
+        let client = self.client.get().await.map_err(map_remote_error)?;
+        let response = client
+            .fs_grep(FsGrepParams {
+                path: path.clone(),
+                pattern: options.pattern,
+                include: options.include,
+                case_insensitive: options.case_insensitive,
+                max_results: Some(options.max_results),
+                sandbox: remote_sandbox_context(sandbox),
+            })
+            .await
+            .map_err(map_remote_error)?;
+        Ok(GrepResult {
+            matches: response
+                .matches
+                .into_iter()
+                .map(|grep_match| GrepMatch {
+                    path: grep_match.path,
+                    line_number: grep_match.line_number,
+                    line: grep_match.line,
+                })
+                .collect(),
+            truncated: response.truncated,
+        })
+    }
+}
+
+/// Keeps a remote watch alive; dropping it sends `fs/unwatch`.
+struct RemoteWatchRegistration {
+    client: ExecServerClient,
+    watch_id: String,
+    forward_task: tokio::task::JoinHandle<()>,
+}
+
+impl Drop for RemoteWatchRegistration {
+    fn drop(&mut self) {
+        self.forward_task.abort();
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        let client = self.client.clone();
+        let watch_id = std::mem::take(&mut self.watch_id);
+        runtime.spawn(async move {
+            if let Err(err) = client.fs_unwatch(FsUnwatchParams { watch_id }).await {
+                warn!("failed to stop remote exec-server file watch: {err}");
+            }
+        });
+    }
 }
 
 fn remote_sandbox_context(
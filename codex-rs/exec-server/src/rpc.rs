@@ -426,6 +426,14 @@ pub(crate) fn not_found(message: String) -> JSONRPCErrorError {
     }
 }
 
+pub(crate) fn unauthorized(message: String) -> JSONRPCErrorError {
+    JSONRPCErrorError {
+        code: -32001,
+        data: None,
+        message,
+    }
+}
+
 pub(crate) fn internal_error(message: String) -> JSONRPCErrorError {
     JSONRPCErrorError {
         code: -32603,
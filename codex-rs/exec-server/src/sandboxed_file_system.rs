@@ -3,26 +3,44 @@ use base64::Engine as _;
 use base64::engine::general_purpose::STANDARD;
 use codex_app_server_protocol::JSONRPCErrorError;
 use codex_utils_absolute_path::AbsolutePathBuf;
+use std::path::Path;
+use std::sync::Arc;
 use tokio::io;
 
 use crate::CopyOptions;
 use crate::CreateDirectoryOptions;
 use crate::ExecServerRuntimePaths;
 use crate::ExecutorFileSystem;
+use crate::FileChunk;
 use crate::FileMetadata;
 use crate::FileSystemResult;
 use crate::FileSystemSandboxContext;
+use crate::FileWatch;
+use crate::GlobOptions;
+use crate::GlobResult;
+use crate::GrepMatch;
+use crate::GrepOptions;
+use crate::GrepResult;
 use crate::ReadDirectoryEntry;
+use crate::ReadFileRange;
 use crate::RemoveOptions;
+use crate::WatchOptions;
 use crate::fs_helper::FsHelperPayload;
 use crate::fs_helper::FsHelperRequest;
 use crate::fs_sandbox::FileSystemSandboxRunner;
+use crate::fs_watch;
+use crate::local_file_system::current_sandbox_cwd;
 use crate::protocol::FsCopyParams;
 use crate::protocol::FsCreateDirectoryParams;
+use crate::protocol::FsCreateSymlinkParams;
 use crate::protocol::FsGetMetadataParams;
+use crate::protocol::FsGlobParams;
+use crate::protocol::FsGrepParams;
 use crate::protocol::FsReadDirectoryParams;
 use crate::protocol::FsReadFileParams;
 use crate::protocol::FsRemoveParams;
+use crate::protocol::FsRenameParams;
+use crate::protocol::FsSetPermissionsParams;
 use crate::protocol::FsWriteFileParams;
 
 #[derive(Clone)]
@@ -62,6 +80,8 @@ impl ExecutorFileSystem for SandboxedFileSystem {
                 sandbox,
                 FsHelperRequest::ReadFile(FsReadFileParams {
                     path: path.clone(),
+                    offset: None,
+                    length: None,
                     sandbox: None,
                 }),
             )
@@ -76,6 +96,38 @@ impl ExecutorFileSystem for SandboxedFileSystem {
         })
     }
 
+    async fn read_file_range(
+        &self,
+        path: &AbsolutePathBuf,
+        range: ReadFileRange,
+        sandbox: Option<&FileSystemSandboxContext>,
+    ) -> FileSystemResult<FileChunk> {
+        let sandbox = require_platform_sandbox(sandbox)?;
+        let response = self
+            .run_sandboxed(
+                sandbox,
+                FsHelperRequest::ReadFile(FsReadFileParams {
+                    path: path.clone(),
+                    offset: Some(range.offset),
+                    length: range.length,
+                    sandbox: None,
+                }),
+            )
+            .await?
+            .expect_read_file()
+            .map_err(map_sandbox_error)?;
+        let data = STANDARD.decode(response.data_base64).map_err(|err| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("fs/readFile returned invalid base64 dataBase64: {err}"),
+            )
+        })?;
+        let total_size = response
+            .total_size
+            .unwrap_or(range.offset + data.len() as u64);
+        Ok(FileChunk { data, total_size })
+    }
+
     async fn write_file(
         &self,
         path: &AbsolutePathBuf,
@@ -141,6 +193,8 @@ impl ExecutorFileSystem for SandboxedFileSystem {
             is_symlink: response.is_symlink,
             created_at_ms: response.created_at_ms,
             modified_at_ms: response.modified_at_ms,
+            mode: response.mode,
+            symlink_target: response.symlink_target,
         })
     }
 
@@ -216,6 +270,164 @@ impl ExecutorFileSystem for SandboxedFileSystem {
         .map_err(map_sandbox_error)?;
         Ok(())
     }
+
+    async fn create_symlink(
+        &self,
+        path: &AbsolutePathBuf,
+        target: &Path,
+        sandbox: Option<&FileSystemSandboxContext>,
+    ) -> FileSystemResult<()> {
+        let sandbox = require_platform_sandbox(sandbox)?;
+        self.run_sandboxed(
+            sandbox,
+            FsHelperRequest::CreateSymlink(FsCreateSymlinkParams {
+                path: path.clone(),
+                target: target.to_path_buf(),
+                sandbox: None,
+            }),
+        )
+        .await?
+        .expect_create_symlink()
+        .map_err(map_sandbox_error)?;
+        Ok(())
+    }
+
+    async fn set_permissions(
+        &self,
+        path: &AbsolutePathBuf,
+        mode: u32,
+        sandbox: Option<&FileSystemSandboxContext>,
+    ) -> FileSystemResult<()> {
+        let sandbox = require_platform_sandbox(sandbox)?;
+        self.run_sandboxed(
+            sandbox,
+            FsHelperRequest::SetPermissions(FsSetPermissionsParams {
+                path: path.clone(),
+                mode,
+                sandbox: None,
+            }),
+        )
+        .await?
+        .expect_set_permissions()
+        .map_err(map_sandbox_error)?;
+        Ok(())
+    }
+
+    async fn rename(
+        &self,
+        source_path: &AbsolutePathBuf,
+        destination_path: &AbsolutePathBuf,
+        sandbox: Option<&FileSystemSandboxContext>,
+    ) -> FileSystemResult<()> {
+        let sandbox = require_platform_sandbox(sandbox)?;
+        self.run_sandboxed(
+            sandbox,
+            FsHelperRequest::Rename(FsRenameParams {
+                source_path: source_path.clone(),
+                destination_path: destination_path.clone(),
+                sandbox: None,
+            }),
+        )
+        .await?
+        .expect_rename()
+        .map_err(map_sandbox_error)?;
+        Ok(())
+    }
+
+    async fn watch(
+        &self,
+        path: &AbsolutePathBuf,
+        options: WatchOptions,
+        sandbox: Option<&FileSystemSandboxContext>,
+    ) -> FileSystemResult<FileWatch> {
+        // Watches are long-lived, so they cannot run in the one-shot helper.
+        // Check the sandbox policy here instead and filter every reported path
+        // through it.
+        let sandbox = require_platform_sandbox(sandbox)?;
+        let policy = sandbox.permissions.file_system_sandbox_policy();
+        let cwd = match &sandbox.cwd {
+            Some(cwd) => cwd.to_path_buf(),
+            None => current_sandbox_cwd()?,
+        };
+        if !policy.can_read_path_with_cwd(path.as_path(), cwd.as_path()) {
+            return Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                format!(
+                    "fs/watch is not permitted by the sandbox policy: {}",
+                    path.display()
+                ),
+            ));
+        }
+        fs_watch::watch_path(
+            path,
+            options,
+            Some(Arc::new(move |changed_path: &Path| {
+                policy.can_read_path_with_cwd(changed_path, cwd.as_path())
+            })),
+        )
+    }
+
+    async fn glob(
+        &self,
+        path: &AbsolutePathBuf,
+        options: GlobOptions,
+        sandbox: Option<&FileSystemSandboxContext>,
+    ) -> FileSystemResult<GlobResult> {
+        let sandbox = require_platform_sandbox(sandbox)?;
+        let response = self
+            .run_sandboxed(
+                sandbox,
+                FsHelperRequest::Glob(FsGlobParams {
+                    path: path.clone(),
+                    patterns: options.patterns,
+                    max_results: Some(options.max_results),
+                    sandbox: None,
+                }),
+            )
+            .await?
+            .expect_glob()
+            .map_err(map_sandbox_error)?;
+        Ok(GlobResult {
+            paths: response.paths,
+            truncated: response.truncated,
+        })
+    }
+
+    async fn grep(
+        &self,
+        path: &AbsolutePathBuf,
+        options: GrepOptions,
+        sandbox: Option<&FileSystemSandboxContext>,
+    ) -> FileSystemResult<GrepResult> {
+        let sandbox = require_platform_sandbox(sandbox)?;
+        let response = self
+            .run_sandboxed(
+                sandbox,
+                FsHelperRequest::Grep(FsGrepParams {
+                    path: path.clone(),
+                    pattern: options.pattern,
+                    include: options.include,
+                    case_insensitive: options.case_insensitive,
+                    max_results: Some(options.max_results),
+                    sandbox: None,
+                }),
+            )
+            .await?
+            .expect_grep()
+            .map_err(map_sandbox_error)?;
+        Ok(GrepResult {
+            matches: response
+                .matches
+                .into_iter()
+                .map(|grep_match| GrepMatch {
+                    path: grep_match.path,
+                    line_number: grep_match.line_number,
+                    line: grep_match.line,
+                })
+                .collect(),
+            truncated: response.truncated,
+        })
+    }
 }
 
 fn require_platform_sandbox(
@@ -1,3 +1,4 @@
+mod auth;
 mod file_system_handler;
 mod handler;
 mod process_handler;
@@ -6,6 +7,7 @@ mod registry;
 mod session_registry;
 mod transport;
 
+pub use auth::ExecServerAuthToken;
 pub(crate) use handler::ExecServerHandler;
 pub use transport::DEFAULT_LISTEN_URL;
 pub use transport::ExecServerListenUrlParseError;
@@ -15,6 +17,7 @@ use crate::ExecServerRuntimePaths;
 pub async fn run_main(
     listen_url: &str,
     runtime_paths: ExecServerRuntimePaths,
+    auth_token: Option<ExecServerAuthToken>,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    transport::run_transport(listen_url, runtime_paths).await
+    transport::run_transport(listen_url, runtime_paths, auth_token).await
 }
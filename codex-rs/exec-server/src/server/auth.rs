@@ -0,0 +1,102 @@
+use std::io;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Shared secret that clients must present as `authToken` in `initialize`.
+///
+/// When a server is started with a token, connections that omit it or send a
+/// different value are rejected before a session is attached.
+#[derive(Clone)]
+pub struct ExecServerAuthToken {
+    token: Arc<str>,
+}
+
+impl ExecServerAuthToken {
+    pub fn new(token: impl Into<String>) -> io::Result<Self> {
+        let token = token.into();
+        let token = token.trim();
+        if token.is_empty() {
+            return Err(io::Error::new(
+                ErrorKind::InvalidInput,
+                "exec-server auth token must not be empty",
+            ));
+        }
+        Ok(Self {
+            token: Arc::from(token),
+        })
+    }
+
+    /// Reads the token from `path`, ignoring surrounding whitespace.
+    pub async fn from_file(path: &Path) -> io::Result<Self> {
+        let token = tokio::fs::read_to_string(path).await.map_err(|err| {
+            io::Error::new(
+                err.kind(),
+                format!(
+                    "failed to read exec-server auth token file {}: {err}",
+                    path.display()
+                ),
+            )
+        })?;
+        Self::new(token)
+    }
+
+    pub(crate) fn matches(&self, presented: Option<&str>) -> bool {
+        let Some(presented) = presented else {
+            return false;
+        };
+        constant_time_eq(self.token.as_bytes(), presented.as_bytes())
+    }
+}
+
+impl std::fmt::Debug for ExecServerAuthToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ExecServerAuthToken(<redacted>)")
+    }
+}
+
+fn constant_time_eq(expected: &[u8], presented: &[u8]) -> bool {
+    // Compare every byte of the expected token so the comparison time does not
+    // reveal how long a matching prefix the client guessed.
+    let mut diff = expected.len() ^ presented.len();
+    for (index, expected_byte) in expected.iter().enumerate() {
+        let presented_byte = presented.get(index).copied().unwrap_or(0);
+        diff |= usize::from(expected_byte ^ presented_byte);
+    }
+    diff == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::ExecServerAuthToken;
+
+    #[test]
+    fn matches_only_the_exact_token() {
+        let token = ExecServerAuthToken::new("secret-token\n").expect("token");
+
+        assert_eq!(
+            [
+                token.matches(Some("secret-token")),
+                token.matches(Some("secret-token-2")),
+                token.matches(Some("secret")),
+                token.matches(Some("")),
+                token.matches(/*presented*/ None),
+            ],
+            [true, false, false, false, false]
+        );
+    }
+
+    #[test]
+    fn rejects_empty_token() {
+        let err = ExecServerAuthToken::new("  \n").expect_err("empty token should fail");
+        assert_eq!(err.to_string(), "exec-server auth token must not be empty");
+    }
+
+    #[test]
+    fn debug_output_redacts_token() {
+        let token = ExecServerAuthToken::new("secret-token").expect("token");
+        assert_eq!(format!("{token:?}"), "ExecServerAuthToken(<redacted>)");
+    }
+}
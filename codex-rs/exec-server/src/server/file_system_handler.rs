@@ -8,15 +8,28 @@ use crate::CopyOptions;
 use crate::CreateDirectoryOptions;
 use crate::ExecServerRuntimePaths;
 use crate::ExecutorFileSystem;
+use crate::FileWatch;
+use crate::GlobOptions;
+use crate::GrepOptions;
+use crate::ReadFileRange;
 use crate::RemoveOptions;
+use crate::WatchOptions;
+use crate::fs_search::DEFAULT_SEARCH_MAX_RESULTS;
 use crate::local_file_system::LocalFileSystem;
 use crate::protocol::FS_WRITE_FILE_METHOD;
 use crate::protocol::FsCopyParams;
 use crate::protocol::FsCopyResponse;
 use crate::protocol::FsCreateDirectoryParams;
 use crate::protocol::FsCreateDirectoryResponse;
+use crate::protocol::FsCreateSymlinkParams;
+use crate::protocol::FsCreateSymlinkResponse;
 use crate::protocol::FsGetMetadataParams;
 use crate::protocol::FsGetMetadataResponse;
+use crate::protocol::FsGlobParams;
+use crate::protocol::FsGlobResponse;
+use crate::protocol::FsGrepMatch;
+use crate::protocol::FsGrepParams;
+use crate::protocol::FsGrepResponse;
 use crate::protocol::FsReadDirectoryEntry;
 use crate::protocol::FsReadDirectoryParams;
 use crate::protocol::FsReadDirectoryResponse;
@@ -24,6 +37,12 @@ use crate::protocol::FsReadFileParams;
 use crate::protocol::FsReadFileResponse;
 use crate::protocol::FsRemoveParams;
 use crate::protocol::FsRemoveResponse;
+use crate::protocol::FsRenameParams;
+use crate::protocol::FsRenameResponse;
+use crate::protocol::FsSetPermissionsParams;
+use crate::protocol::FsSetPermissionsResponse;
+use crate::protocol::FsWatchParams;
+use crate::protocol::FsWatchResponse;
 use crate::protocol::FsWriteFileParams;
 use crate::protocol::FsWriteFileResponse;
 use crate::rpc::internal_error;
@@ -46,13 +65,32 @@ impl FileSystemHandler {
         &self,
         params: FsReadFileParams,
     ) -> Result<FsReadFileResponse, JSONRPCErrorError> {
-        let bytes = self
+        if params.offset.is_none() && params.length.is_none() {
+            let bytes = self
+                .file_system
+                .read_file(&params.path, params.sandbox.as_ref())
+                .await
+                .map_err(map_fs_error)?;
+            return Ok(FsReadFileResponse {
+                data_base64: STANDARD.encode(bytes),
+                total_size: None,
+            });
+        }
+        let chunk = self
             .file_system
-            .read_file(&params.path, params.sandbox.as_ref())
+            .read_file_range(
+                &params.path,
+                ReadFileRange {
+                    offset: params.offset.unwrap_or(0),
+                    length: params.length,
+                },
+                params.sandbox.as_ref(),
+            )
             .await
             .map_err(map_fs_error)?;
         Ok(FsReadFileResponse {
-            data_base64: STANDARD.encode(bytes),
+            data_base64: STANDARD.encode(chunk.data),
+            total_size: Some(chunk.total_size),
         })
     }
 
@@ -103,6 +141,8 @@ impl FileSystemHandler {
             is_symlink: metadata.is_symlink,
             created_at_ms: metadata.created_at_ms,
             modified_at_ms: metadata.modified_at_ms,
+            mode: metadata.mode,
+            symlink_target: metadata.symlink_target,
         })
     }
 
@@ -159,6 +199,120 @@ impl FileSystemHandler {
             .map_err(map_fs_error)?;
         Ok(FsCopyResponse {})
     }
+
+    pub(crate) async fn create_symlink(
+        &self,
+        params: FsCreateSymlinkParams,
+    ) -> Result<FsCreateSymlinkResponse, JSONRPCErrorError> {
+        self.file_system
+            .create_symlink(&params.path, &params.target, params.sandbox.as_ref())
+            .await
+            .map_err(map_fs_error)?;
+        Ok(FsCreateSymlinkResponse {})
+    }
+
+    pub(crate) async fn set_permissions(
+        &self,
+        params: FsSetPermissionsParams,
+    ) -> Result<FsSetPermissionsResponse, JSONRPCErrorError> {
+        self.file_system
+            .set_permissions(&params.path, params.mode, params.sandbox.as_ref())
+            .await
+            .map_err(map_fs_error)?;
+        Ok(FsSetPermissionsResponse {})
+    }
+
+    pub(crate) async fn rename(
+        &self,
+        params: FsRenameParams,
+    ) -> Result<FsRenameResponse, JSONRPCErrorError> {
+        self.file_system
+            .rename(
+                &params.source_path,
+                &params.destination_path,
+                params.sandbox.as_ref(),
+            )
+            .await
+            .map_err(map_fs_error)?;
+        Ok(FsRenameResponse {})
+    }
+
+    pub(crate) async fn watch(
+        &self,
+        params: &FsWatchParams,
+    ) -> Result<(FsWatchResponse, FileWatch), JSONRPCErrorError> {
+        let watch = self
+            .file_system
+            .watch(
+                &params.path,
+                WatchOptions {
+                    recursive: params.recursive,
+                },
+                params.sandbox.as_ref(),
+            )
+            .await
+            .map_err(map_fs_error)?;
+        Ok((
+            FsWatchResponse {
+                path: watch.path().clone(),
+            },
+            watch,
+        ))
+    }
+
+    pub(crate) async fn glob(
+        &self,
+        params: FsGlobParams,
+    ) -> Result<FsGlobResponse, JSONRPCErrorError> {
+        let result = self
+            .file_system
+            .glob(
+                &params.path,
+                GlobOptions {
+                    patterns: params.patterns,
+                    max_results: params.max_results.unwrap_or(DEFAULT_SEARCH_MAX_RESULTS),
+                },
+                params.sandbox.as_ref(),
+            )
+            .await
+            .map_err(map_fs_error)?;
+        Ok(FsGlobResponse {
+            paths: result.paths,
+            truncated: result.truncated,
+        })
+    }
+
+    pub(crate) async fn grep(
+        &self,
+        params: FsGrepParams,
+    ) -> Result<FsGrepResponse, JSONRPCErrorError> {
+        let result = self
+            .file_system
+            .grep(
+                &params.path,
+                GrepOptions {
+                    pattern: params.pattern,
+                    include: params.include,
+                    case_insensitive: params.case_insensitive,
+                    max_results: params.max_results.unwrap_or(DEFAULT_SEARCH_MAX_RESULTS),
+                },
+                params.sandbox.as_ref(),
+            )
+            .await
+            .map_err(map_fs_error)?;
+        Ok(FsGrepResponse {
+            matches: result
+                .matches
+                .into_iter()
+                .map(|grep_match| FsGrepMatch {
+                    path: grep_match.path,
+                    line_number: grep_match.line_number,
+                    line: grep_match.line,
+                })
+                .collect(),
+            truncated: result.truncated,
+        })
+    }
 }
 
 fn map_fs_error(err: io::Error) -> JSONRPCErrorError {
@@ -223,6 +377,8 @@ mod tests {
             let response = handler
                 .read_file(FsReadFileParams {
                     path,
+                    offset: None,
+                    length: None,
                     sandbox: Some(FileSystemSandboxContext::from_legacy_sandbox_policy(
                         sandbox_policy,
                         sandbox_cwd.clone(),
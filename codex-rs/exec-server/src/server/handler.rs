@@ -6,8 +6,10 @@ use std::sync::atomic::Ordering;
 use codex_app_server_protocol::JSONRPCErrorError;
 use codex_app_server_protocol::RequestId;
 use serde_json::to_value;
+use std::collections::HashMap;
 use std::collections::HashSet;
 use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
 use tokio_util::sync::CancellationToken;
 use tokio_util::task::TaskTracker;
 
@@ -16,18 +18,34 @@ use crate::client::http_client::PendingReqwestHttpBodyStream;
 use crate::client::http_client::ReqwestHttpRequestRunner;
 use crate::protocol::ExecParams;
 use crate::protocol::ExecResponse;
+use crate::protocol::FS_CHANGED_METHOD;
+use crate::protocol::FsChangedNotification;
 use crate::protocol::FsCopyParams;
 use crate::protocol::FsCopyResponse;
 use crate::protocol::FsCreateDirectoryParams;
 use crate::protocol::FsCreateDirectoryResponse;
+use crate::protocol::FsCreateSymlinkParams;
+use crate::protocol::FsCreateSymlinkResponse;
 use crate::protocol::FsGetMetadataParams;
 use crate::protocol::FsGetMetadataResponse;
+use crate::protocol::FsGlobParams;
+use crate::protocol::FsGlobResponse;
+use crate::protocol::FsGrepParams;
+use crate::protocol::FsGrepResponse;
 use crate::protocol::FsReadDirectoryParams;
 use crate::protocol::FsReadDirectoryResponse;
 use crate::protocol::FsReadFileParams;
 use crate::protocol::FsReadFileResponse;
 use crate::protocol::FsRemoveParams;
 use crate::protocol::FsRemoveResponse;
+use crate::protocol::FsRenameParams;
+use crate::protocol::FsRenameResponse;
+use crate::protocol::FsSetPermissionsParams;
+use crate::protocol::FsSetPermissionsResponse;
+use crate::protocol::FsUnwatchParams;
+use crate::protocol::FsUnwatchResponse;
+use crate::protocol::FsWatchParams;
+use crate::protocol::FsWatchResponse;
 use crate::protocol::FsWriteFileParams;
 use crate::protocol::FsWriteFileResponse;
 use crate::protocol::HttpRequestParams;
@@ -43,20 +61,29 @@ use crate::rpc::RpcNotificationSender;
 use crate::rpc::internal_error;
 use crate::rpc::invalid_params;
 use crate::rpc::invalid_request;
+use crate::rpc::unauthorized;
+use crate::server::auth::ExecServerAuthToken;
 use crate::server::file_system_handler::FileSystemHandler;
 use crate::server::session_registry::SessionHandle;
 use crate::server::session_registry::SessionRegistry;
 
+struct FsWatchTask {
+    cancel: CancellationToken,
+    task: JoinHandle<()>,
+}
+
 pub(crate) struct ExecServerHandler {
     session_registry: Arc<SessionRegistry>,
     notifications: RpcNotificationSender,
     session: StdMutex<Option<SessionHandle>>,
     active_body_stream_ids: Mutex<HashSet<String>>,
+    fs_watches: Mutex<HashMap<String, FsWatchTask>>,
     background_task_shutdown: CancellationToken,
     background_tasks: TaskTracker,
     file_system: FileSystemHandler,
     initialize_requested: AtomicBool,
     initialized: AtomicBool,
+    auth_token: Option<ExecServerAuthToken>,
 }
 
 impl ExecServerHandler {
@@ -64,17 +91,20 @@ impl ExecServerHandler {
         session_registry: Arc<SessionRegistry>,
         notifications: RpcNotificationSender,
         runtime_paths: ExecServerRuntimePaths,
+        auth_token: Option<ExecServerAuthToken>,
     ) -> Self {
         Self {
             session_registry,
             notifications,
             session: StdMutex::new(None),
             active_body_stream_ids: Mutex::new(HashSet::new()),
+            fs_watches: Mutex::new(HashMap::new()),
             background_task_shutdown: CancellationToken::new(),
             background_tasks: TaskTracker::new(),
             file_system: FileSystemHandler::new(runtime_paths),
             initialize_requested: AtomicBool::new(false),
             initialized: AtomicBool::new(false),
+            auth_token,
         }
     }
 
@@ -101,6 +131,19 @@ impl ExecServerHandler {
                 "initialize may only be sent once per connection".to_string(),
             ));
         }
+        // A rejected connection keeps `initialize_requested` set, so it can
+        // never attach a session and every later request fails.
+        if let Some(auth_token) = &self.auth_token
+            && !auth_token.matches(params.auth_token.as_deref())
+        {
+            tracing::warn!(
+                client_name = %params.client_name,
+                "rejected exec-server connection with missing or invalid auth token"
+            );
+            return Err(unauthorized(
+                "exec-server rejected initialize: missing or invalid auth token".to_string(),
+            ));
+        }
 
         let session = match self
             .session_registry
@@ -264,6 +307,112 @@ impl ExecServerHandler {
         self.file_system.copy(params).await
     }
 
+    pub(crate) async fn fs_create_symlink(
+        &self,
+        params: FsCreateSymlinkParams,
+    ) -> Result<FsCreateSymlinkResponse, JSONRPCErrorError> {
+        self.require_initialized_for("filesystem")?;
+        self.file_system.create_symlink(params).await
+    }
+
+    pub(crate) async fn fs_set_permissions(
+        &self,
+        params: FsSetPermissionsParams,
+    ) -> Result<FsSetPermissionsResponse, JSONRPCErrorError> {
+        self.require_initialized_for("filesystem")?;
+        self.file_system.set_permissions(params).await
+    }
+
+    pub(crate) async fn fs_rename(
+        &self,
+        params: FsRenameParams,
+    ) -> Result<FsRenameResponse, JSONRPCErrorError> {
+        self.require_initialized_for("filesystem")?;
+        self.file_system.rename(params).await
+    }
+
+    pub(crate) async fn fs_glob(
+        &self,
+        params: FsGlobParams,
+    ) -> Result<FsGlobResponse, JSONRPCErrorError> {
+        self.require_initialized_for("filesystem")?;
+        self.file_system.glob(params).await
+    }
+
+    pub(crate) async fn fs_grep(
+        &self,
+        params: FsGrepParams,
+    ) -> Result<FsGrepResponse, JSONRPCErrorError> {
+        self.require_initialized_for("filesystem")?;
+        self.file_system.grep(params).await
+    }
+
+    pub(crate) async fn fs_watch(
+        &self,
+        params: FsWatchParams,
+    ) -> Result<FsWatchResponse, JSONRPCErrorError> {
+        self.require_initialized_for("filesystem")?;
+        // Hold the table lock across registration so a concurrent request
+        // with the same watch id cannot start a second watcher.
+        let mut fs_watches = self.fs_watches.lock().await;
+        if fs_watches.contains_key(&params.watch_id) {
+            return Err(invalid_params(format!(
+                "fs/watch watchId `{}` is already active",
+                params.watch_id
+            )));
+        }
+        if self.background_task_shutdown.is_cancelled() {
+            return Err(invalid_request(
+                "exec-server connection is shutting down".to_string(),
+            ));
+        }
+        let (response, mut watch) = self.file_system.watch(&params).await?;
+        let watch_id = params.watch_id;
+        let cancel = self.background_task_shutdown.child_token();
+        let task_cancel = cancel.clone();
+        let notifications = self.notifications.clone();
+        let task_watch_id = watch_id.clone();
+        let task = self.background_tasks.spawn(async move {
+            loop {
+                let event = tokio::select! {
+                    _ = task_cancel.cancelled() => break,
+                    event = watch.recv() => event,
+                };
+                let Some(event) = event else {
+                    break;
+                };
+                let notification = FsChangedNotification {
+                    watch_id: task_watch_id.clone(),
+                    changed_paths: event.changed_paths,
+                };
+                if notifications
+                    .notify(FS_CHANGED_METHOD, &notification)
+                    .await
+                    .is_err()
+                {
+                    break;
+                }
+            }
+        });
+        fs_watches.insert(watch_id, FsWatchTask { cancel, task });
+        Ok(response)
+    }
+
+    pub(crate) async fn fs_unwatch(
+        &self,
+        params: FsUnwatchParams,
+    ) -> Result<FsUnwatchResponse, JSONRPCErrorError> {
+        self.require_initialized_for("filesystem")?;
+        let watch = self.fs_watches.lock().await.remove(&params.watch_id);
+        if let Some(FsWatchTask { cancel, task }) = watch {
+            cancel.cancel();
+            // Wait for the forwarding task so no `fs/changed` for this watch
+            // follows the unwatch response.
+            let _ = task.await;
+        }
+        Ok(FsUnwatchResponse {})
+    }
+
     fn require_initialized_for(
         &self,
         method_family: &str,
@@ -16,6 +16,7 @@ use crate::protocol::ReadResponse;
 use crate::protocol::TerminateParams;
 use crate::protocol::TerminateResponse;
 use crate::rpc::RpcNotificationSender;
+use crate::server::auth::ExecServerAuthToken;
 use crate::server::session_registry::SessionRegistry;
 
 fn exec_params(process_id: &str) -> ExecParams {
@@ -82,11 +83,13 @@ async fn initialized_handler() -> Arc<ExecServerHandler> {
         registry,
         RpcNotificationSender::new(outgoing_tx),
         test_runtime_paths(),
+        /*auth_token*/ None,
     ));
     let initialize_response = handler
         .initialize(InitializeParams {
             client_name: "exec-server-test".to_string(),
             resume_session_id: None,
+            auth_token: None,
         })
         .await
         .expect("initialize");
@@ -160,11 +163,13 @@ async fn long_poll_read_fails_after_session_resume() {
         Arc::clone(&registry),
         RpcNotificationSender::new(first_tx),
         test_runtime_paths(),
+        /*auth_token*/ None,
     ));
     let initialize_response = first_handler
         .initialize(InitializeParams {
             client_name: "exec-server-test".to_string(),
             resume_session_id: None,
+            auth_token: None,
         })
         .await
         .expect("initialize");
@@ -200,11 +205,13 @@ async fn long_poll_read_fails_after_session_resume() {
         registry,
         RpcNotificationSender::new(second_tx),
         test_runtime_paths(),
+        /*auth_token*/ None,
     ));
     second_handler
         .initialize(InitializeParams {
             client_name: "exec-server-test".to_string(),
             resume_session_id: Some(initialize_response.session_id),
+            auth_token: None,
         })
         .await
         .expect("initialize second connection");
@@ -233,11 +240,13 @@ async fn active_session_resume_is_rejected() {
         Arc::clone(&registry),
         RpcNotificationSender::new(first_tx),
         test_runtime_paths(),
+        /*auth_token*/ None,
     ));
     let initialize_response = first_handler
         .initialize(InitializeParams {
             client_name: "exec-server-test".to_string(),
             resume_session_id: None,
+            auth_token: None,
         })
         .await
         .expect("initialize");
@@ -247,11 +256,13 @@ async fn active_session_resume_is_rejected() {
         registry,
         RpcNotificationSender::new(second_tx),
         test_runtime_paths(),
+        /*auth_token*/ None,
     ));
     let err = second_handler
         .initialize(InitializeParams {
             client_name: "exec-server-test".to_string(),
             resume_session_id: Some(initialize_response.session_id.clone()),
+            auth_token: None,
         })
         .await
         .expect_err("active session resume should fail");
@@ -268,6 +279,75 @@ async fn active_session_resume_is_rejected() {
     first_handler.shutdown().await;
 }
 
+#[tokio::test]
+async fn initialize_rejects_missing_or_invalid_auth_token() {
+    for presented in [None, Some("wrong-token")] {
+        let (outgoing_tx, _outgoing_rx) = mpsc::channel(16);
+        let handler = Arc::new(ExecServerHandler::new(
+            SessionRegistry::new(),
+            RpcNotificationSender::new(outgoing_tx),
+            test_runtime_paths(),
+            Some(ExecServerAuthToken::new("expected-token").expect("auth token")),
+        ));
+        let err = handler
+            .initialize(InitializeParams {
+                client_name: "exec-server-test".to_string(),
+                resume_session_id: None,
+                auth_token: presented.map(str::to_string),
+            })
+            .await
+            .expect_err("initialize without the right token should fail");
+        assert_eq!(err.code, -32001);
+        assert_eq!(
+            err.message,
+            "exec-server rejected initialize: missing or invalid auth token"
+        );
+
+        let retry = handler
+            .initialize(InitializeParams {
+                client_name: "exec-server-test".to_string(),
+                resume_session_id: None,
+                auth_token: Some("expected-token".to_string()),
+            })
+            .await
+            .expect_err("a rejected connection cannot retry initialize");
+        assert_eq!(
+            retry.message,
+            "initialize may only be sent once per connection"
+        );
+        let exec_err = handler
+            .exec(exec_params("proc-unauthenticated"))
+            .await
+            .expect_err("a rejected connection cannot start processes");
+        assert_eq!(exec_err.code, -32600);
+
+        handler.shutdown().await;
+    }
+}
+
+#[tokio::test]
+async fn initialize_accepts_matching_auth_token() {
+    let (outgoing_tx, _outgoing_rx) = mpsc::channel(16);
+    let handler = Arc::new(ExecServerHandler::new(
+        SessionRegistry::new(),
+        RpcNotificationSender::new(outgoing_tx),
+        test_runtime_paths(),
+        Some(ExecServerAuthToken::new("expected-token").expect("auth token")),
+    ));
+    let response = handler
+        .initialize(InitializeParams {
+            client_name: "exec-server-test".to_string(),
+            resume_session_id: None,
+            auth_token: Some("expected-token".to_string()),
+        })
+        .await
+        .expect("initialize with the right token");
+    Uuid::parse_str(&response.session_id).expect("session id should be a UUID");
+    handler.initialized().expect("initialized");
+
+    handler.shutdown().await;
+}
+
 #[tokio::test]
 async fn output_and_exit_are_retained_after_notification_receiver_closes() {
     let (outgoing_tx, outgoing_rx) = mpsc::channel(16);
@@ -275,11 +355,13 @@ async fn output_and_exit_are_retained_after_notification_receiver_closes() {
         SessionRegistry::new(),
         RpcNotificationSender::new(outgoing_tx),
         test_runtime_paths(),
+        /*auth_token*/ None,
     ));
     handler
         .initialize(InitializeParams {
             client_name: "exec-server-test".to_string(),
             resume_session_id: None,
+            auth_token: None,
         })
         .await
         .expect("initialize");
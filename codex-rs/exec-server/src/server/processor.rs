@@ -14,6 +14,7 @@ use crate::rpc::encode_server_message;
 use crate::rpc::invalid_request;
 use crate::rpc::method_not_found;
 use crate::server::ExecServerHandler;
+use crate::server::auth::ExecServerAuthToken;
 use crate::server::registry::build_router;
 use crate::server::session_registry::SessionRegistry;
 
@@ -21,13 +22,18 @@ use crate::server::session_registry::SessionRegistry;
 pub(crate) struct ConnectionProcessor {
     session_registry: Arc<SessionRegistry>,
     runtime_paths: ExecServerRuntimePaths,
+    auth_token: Option<ExecServerAuthToken>,
 }
 
 impl ConnectionProcessor {
-    pub(crate) fn new(runtime_paths: ExecServerRuntimePaths) -> Self {
+    pub(crate) fn new(
+        runtime_paths: ExecServerRuntimePaths,
+        auth_token: Option<ExecServerAuthToken>,
+    ) -> Self {
         Self {
             session_registry: SessionRegistry::new(),
             runtime_paths,
+            auth_token,
         }
     }
 
@@ -36,6 +42,7 @@ impl ConnectionProcessor {
             connection,
             Arc::clone(&self.session_registry),
             self.runtime_paths.clone(),
+            self.auth_token.clone(),
         )
         .await;
     }
@@ -45,6 +52,7 @@ async fn run_connection(
     connection: JsonRpcConnection,
     session_registry: Arc<SessionRegistry>,
     runtime_paths: ExecServerRuntimePaths,
+    auth_token: Option<ExecServerAuthToken>,
 ) {
     let router = Arc::new(build_router());
     let (json_outgoing_tx, mut incoming_rx, mut disconnected_rx, connection_tasks) =
@@ -56,6 +64,7 @@ async fn run_connection(
         session_registry,
         notifications,
         runtime_paths,
+        auth_token,
     ));
 
     let outbound_task = tokio::spawn(async move {
@@ -232,6 +241,7 @@ mod tests {
             &InitializeParams {
                 client_name: "exec-server-test".to_string(),
                 resume_session_id: None,
+                auth_token: None,
             },
         )
         .await;
@@ -273,6 +283,7 @@ mod tests {
             &InitializeParams {
                 client_name: "exec-server-test".to_string(),
                 resume_session_id: Some(initialize_response.session_id.clone()),
+                auth_token: None,
             },
         )
         .await;
@@ -317,7 +328,12 @@ mod tests {
         let (server_writer, client_reader) = duplex(1 << 20);
         let connection =
             JsonRpcConnection::from_stdio(server_reader, server_writer, label.to_string());
-        let task = tokio::spawn(run_connection(connection, registry, test_runtime_paths()));
+        let task = tokio::spawn(run_connection(
+            connection,
+            registry,
+            test_runtime_paths(),
+            /*auth_token*/ None,
+        ));
         (client_writer, BufReader::new(client_reader).lines(), task)
     }
 
@@ -7,17 +7,31 @@ use crate::protocol::EXEC_WRITE_METHOD;
 use crate::protocol::ExecParams;
 use crate::protocol::FS_COPY_METHOD;
 use crate::protocol::FS_CREATE_DIRECTORY_METHOD;
+use crate::protocol::FS_CREATE_SYMLINK_METHOD;
 use crate::protocol::FS_GET_METADATA_METHOD;
+use crate::protocol::FS_GLOB_METHOD;
+use crate::protocol::FS_GREP_METHOD;
 use crate::protocol::FS_READ_DIRECTORY_METHOD;
 use crate::protocol::FS_READ_FILE_METHOD;
 use crate::protocol::FS_REMOVE_METHOD;
+use crate::protocol::FS_RENAME_METHOD;
+use crate::protocol::FS_SET_PERMISSIONS_METHOD;
+use crate::protocol::FS_UNWATCH_METHOD;
+use crate::protocol::FS_WATCH_METHOD;
 use crate::protocol::FS_WRITE_FILE_METHOD;
 use crate::protocol::FsCopyParams;
 use crate::protocol::FsCreateDirectoryParams;
+use crate::protocol::FsCreateSymlinkParams;
 use crate::protocol::FsGetMetadataParams;
+use crate::protocol::FsGlobParams;
+use crate::protocol::FsGrepParams;
 use crate::protocol::FsReadDirectoryParams;
 use crate::protocol::FsReadFileParams;
 use crate::protocol::FsRemoveParams;
+use crate::protocol::FsRenameParams;
+use crate::protocol::FsSetPermissionsParams;
+use crate::protocol::FsUnwatchParams;
+use crate::protocol::FsWatchParams;
 use crate::protocol::FsWriteFileParams;
 use crate::protocol::HTTP_REQUEST_METHOD;
 use crate::protocol::HttpRequestParams;
@@ -114,5 +128,47 @@ pub(crate) fn build_router() -> RpcRouter<ExecServerHandler> {
             handler.fs_copy(params).await
         },
     );
+    router.request(
+        FS_CREATE_SYMLINK_METHOD,
+        |handler: Arc<ExecServerHandler>, params: FsCreateSymlinkParams| async move {
+            handler.fs_create_symlink(params).await
+        },
+    );
+    router.request(
+        FS_SET_PERMISSIONS_METHOD,
+        |handler: Arc<ExecServerHandler>, params: FsSetPermissionsParams| async move {
+            handler.fs_set_permissions(params).await
+        },
+    );
+    router.request(
+        FS_RENAME_METHOD,
+        |handler: Arc<ExecServerHandler>, params: FsRenameParams| async move {
+            handler.fs_rename(params).await
+        },
+    );
+    router.request(
+        FS_WATCH_METHOD,
+        |handler: Arc<ExecServerHandler>, params: FsWatchParams| async move {
+            handler.fs_watch(params).await
+        },
+    );
+    router.request(
+        FS_UNWATCH_METHOD,
+        |handler: Arc<ExecServerHandler>, params: FsUnwatchParams| async move {
+            handler.fs_unwatch(params).await
+        },
+    );
+    router.request(
+        FS_GLOB_METHOD,
+        |handler: Arc<ExecServerHandler>, params: FsGlobParams| async move {
+            handler.fs_glob(params).await
+        },
+    );
+    router.request(
+        FS_GREP_METHOD,
+        |handler: Arc<ExecServerHandler>, params: FsGrepParams| async move {
+            handler.fs_grep(params).await
+        },
+    );
     router
 }
@@ -1,19 +1,43 @@
+use std::io::ErrorKind;
 use std::io::Write as _;
 use std::net::SocketAddr;
+use std::path::Path;
+
+use codex_uds::UnixListener;
+use codex_uds::UnixStream;
+use codex_utils_absolute_path::AbsolutePathBuf;
 use tokio::net::TcpListener;
+use tokio_tungstenite::WebSocketStream;
 use tokio_tungstenite::accept_async;
+use tokio_tungstenite::tungstenite::protocol::Role;
 use tracing::warn;
 
 use crate::ExecServerRuntimePaths;
 use crate::connection::JsonRpcConnection;
+use crate::server::auth::ExecServerAuthToken;
 use crate::server::processor::ConnectionProcessor;
 
 pub const DEFAULT_LISTEN_URL: &str = "ws://127.0.0.1:0";
+const STDIO_LISTEN_URL: &str = "stdio://";
+const UNIX_SOCKET_URL_PREFIX: &str = "unix://";
+
+#[cfg(unix)]
+const UNIX_SOCKET_MODE: u32 = 0o600;
+#[cfg(unix)]
+const UNIX_SOCKET_STAGING_DIR_MODE: u32 = 0o700;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) enum ExecServerListenTarget {
+    WebSocket(SocketAddr),
+    UnixSocket(AbsolutePathBuf),
+    Stdio,
+}
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum ExecServerListenUrlParseError {
     UnsupportedListenUrl(String),
     InvalidWebSocketListenUrl(String),
+    InvalidUnixSocketListenUrl { listen_url: String, message: String },
 }
 
 impl std::fmt::Display for ExecServerListenUrlParseError {
@@ -21,12 +45,19 @@ impl std::fmt::Display for ExecServerListenUrlParseError {
         match self {
             ExecServerListenUrlParseError::UnsupportedListenUrl(listen_url) => write!(
                 f,
-                "unsupported --listen URL `{listen_url}`; expected `ws://IP:PORT`"
+                "unsupported --listen URL `{listen_url}`; expected `ws://IP:PORT`, `unix://PATH`, or `stdio://`"
             ),
             ExecServerListenUrlParseError::InvalidWebSocketListenUrl(listen_url) => write!(
                 f,
                 "invalid websocket --listen URL `{listen_url}`; expected `ws://IP:PORT`"
             ),
+            ExecServerListenUrlParseError::InvalidUnixSocketListenUrl {
+                listen_url,
+                message,
+            } => write!(
+                f,
+                "invalid unix socket --listen URL `{listen_url}`; {message}"
+            ),
         }
     }
 }
@@ -35,11 +66,33 @@ impl std::error::Error for ExecServerListenUrlParseError {}
 
 pub(crate) fn parse_listen_url(
     listen_url: &str,
-) -> Result<SocketAddr, ExecServerListenUrlParseError> {
+) -> Result<ExecServerListenTarget, ExecServerListenUrlParseError> {
+    if listen_url == STDIO_LISTEN_URL {
+        return Ok(ExecServerListenTarget::Stdio);
+    }
+
+    if let Some(raw_socket_path) = listen_url.strip_prefix(UNIX_SOCKET_URL_PREFIX) {
+        if raw_socket_path.is_empty() {
+            return Err(ExecServerListenUrlParseError::InvalidUnixSocketListenUrl {
+                listen_url: listen_url.to_string(),
+                message: "expected `unix://PATH`".to_string(),
+            });
+        }
+        let socket_path =
+            AbsolutePathBuf::relative_to_current_dir(raw_socket_path).map_err(|err| {
+                ExecServerListenUrlParseError::InvalidUnixSocketListenUrl {
+                    listen_url: listen_url.to_string(),
+                    message: format!("failed to resolve socket path: {err}"),
+                }
+            })?;
+        return Ok(ExecServerListenTarget::UnixSocket(socket_path));
+    }
+
     if let Some(socket_addr) = listen_url.strip_prefix("ws://") {
-        return socket_addr.parse::<SocketAddr>().map_err(|_| {
+        let bind_address = socket_addr.parse::<SocketAddr>().map_err(|_| {
             ExecServerListenUrlParseError::InvalidWebSocketListenUrl(listen_url.to_string())
-        });
+        })?;
+        return Ok(ExecServerListenTarget::WebSocket(bind_address));
     }
 
     Err(ExecServerListenUrlParseError::UnsupportedListenUrl(
@@ -50,18 +103,30 @@ pub(crate) fn parse_listen_url(
 pub(crate) async fn run_transport(
     listen_url: &str,
     runtime_paths: ExecServerRuntimePaths,
+    auth_token: Option<ExecServerAuthToken>,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let bind_address = parse_listen_url(listen_url)?;
-    run_websocket_listener(bind_address, runtime_paths).await
+    let target = parse_listen_url(listen_url)?;
+    let processor = ConnectionProcessor::new(runtime_paths, auth_token);
+    match target {
+        ExecServerListenTarget::WebSocket(bind_address) => {
+            run_websocket_listener(bind_address, processor).await
+        }
+        ExecServerListenTarget::UnixSocket(socket_path) => {
+            run_unix_socket_listener(socket_path, processor).await
+        }
+        ExecServerListenTarget::Stdio => {
+            run_stdio_connection(processor).await;
+            Ok(())
+        }
+    }
 }
 
 async fn run_websocket_listener(
     bind_address: SocketAddr,
-    runtime_paths: ExecServerRuntimePaths,
+    processor: ConnectionProcessor,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let listener = TcpListener::bind(bind_address).await?;
     let local_addr = listener.local_addr()?;
-    let processor = ConnectionProcessor::new(runtime_paths);
     tracing::info!("codex-exec-server listening on ws://{local_addr}");
     println!("ws://{local_addr}");
     std::io::stdout().flush()?;
@@ -89,6 +154,142 @@ async fn run_websocket_listener(
     }
 }
 
+/// Serves websocket-framed JSON-RPC over a Unix socket, the same framing the
+/// app-server control socket uses. The socket file is owner-only.
+async fn run_unix_socket_listener(
+    socket_path: AbsolutePathBuf,
+    processor: ConnectionProcessor,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    prepare_unix_socket_path(socket_path.as_path()).await?;
+    let mut listener = bind_unix_socket(socket_path.as_path()).await?;
+    let listen_url = format!("{UNIX_SOCKET_URL_PREFIX}{}", socket_path.display());
+    tracing::info!("codex-exec-server listening on {listen_url}");
+    println!("{listen_url}");
+    std::io::stdout().flush()?;
+
+    loop {
+        let stream = match listener.accept().await {
+            Ok(stream) => stream,
+            Err(err)
+                if matches!(
+                    err.kind(),
+                    ErrorKind::ConnectionAborted
+                        | ErrorKind::ConnectionReset
+                        | ErrorKind::Interrupted
+                ) =>
+            {
+                warn!("recoverable exec-server unix socket accept error: {err}");
+                continue;
+            }
+            Err(err) => return Err(err.into()),
+        };
+        let processor = processor.clone();
+        let connection_label = format!("exec-server unix socket {}", socket_path.display());
+        tokio::spawn(async move {
+            let websocket = WebSocketStream::from_raw_socket(stream, Role::Server, None).await;
+            processor
+                .run_connection(JsonRpcConnection::from_websocket(
+                    websocket,
+                    connection_label,
+                ))
+                .await;
+        });
+    }
+}
+
+/// Serves a single newline-delimited JSON-RPC connection on stdin/stdout and
+/// returns once the client closes stdin.
+async fn run_stdio_connection(processor: ConnectionProcessor) {
+    tracing::info!("codex-exec-server serving {STDIO_LISTEN_URL}");
+    processor
+        .run_connection(JsonRpcConnection::from_stdio(
+            tokio::io::stdin(),
+            tokio::io::stdout(),
+            "exec-server stdio".to_string(),
+        ))
+        .await;
+}
+
+async fn prepare_unix_socket_path(socket_path: &Path) -> std::io::Result<()> {
+    match UnixStream::connect(socket_path).await {
+        Ok(_stream) => {
+            return Err(std::io::Error::new(
+                ErrorKind::AddrInUse,
+                format!(
+                    "exec-server socket is already in use at {}",
+                    socket_path.display()
+                ),
+            ));
+        }
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(()),
+        Err(err) if err.kind() == ErrorKind::ConnectionRefused => {}
+        Err(err) => {
+            if !socket_path.exists() {
+                return Ok(());
+            }
+            return Err(err);
+        }
+    }
+
+    if !codex_uds::is_stale_socket_path(socket_path).await? {
+        return Err(std::io::Error::new(
+            ErrorKind::AlreadyExists,
+            format!(
+                "exec-server socket path exists and is not a socket: {}",
+                socket_path.display()
+            ),
+        ));
+    }
+    tokio::fs::remove_file(socket_path).await
+}
+
+/// Binds an owner-only listener at `socket_path`.
+///
+/// `bind` creates the socket with the process umask, so it is bound inside a
+/// fresh owner-only directory next to `socket_path`, restricted there, and only
+/// then renamed into place. Other users can never connect through a window in
+/// which the socket is still group- or world-accessible.
+#[cfg(unix)]
+async fn bind_unix_socket(socket_path: &Path) -> std::io::Result<UnixListener> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let parent = socket_path.parent().unwrap_or_else(|| Path::new("/"));
+    let staging_dir = parent.join(format!(
+        ".codex-exec-server-{}",
+        &uuid::Uuid::new_v4().simple().to_string()[..12]
+    ));
+    // `create` (not `create_all`) fails if the directory already exists, so
+    // the staging directory is always one this process made.
+    tokio::fs::DirBuilder::new()
+        .mode(UNIX_SOCKET_STAGING_DIR_MODE)
+        .create(&staging_dir)
+        .await?;
+    let staged_socket = staging_dir.join("s");
+    let result = async {
+        let listener = UnixListener::bind(&staged_socket).await?;
+        tokio::fs::set_permissions(
+            &staged_socket,
+            std::fs::Permissions::from_mode(UNIX_SOCKET_MODE),
+        )
+        .await?;
+        tokio::fs::rename(&staged_socket, socket_path).await?;
+        Ok(listener)
+    }
+    .await;
+    if let Err(err) = tokio::fs::remove_dir_all(&staging_dir).await {
+        warn!(
+            "failed to remove exec-server socket staging directory {}: {err}",
+            staging_dir.display()
+        );
+    }
+    result
+}
+
+#[cfg(not(unix))]
+async fn bind_unix_socket(socket_path: &Path) -> std::io::Result<UnixListener> {
+    UnixListener::bind(socket_path).await
+}
+
 #[cfg(test)]
 #[path = "transport_tests.rs"]
 mod transport_tests;
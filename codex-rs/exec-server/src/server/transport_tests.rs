@@ -1,31 +1,79 @@
 use std::net::SocketAddr;
 
+use codex_utils_absolute_path::AbsolutePathBuf;
 use pretty_assertions::assert_eq;
 
 use super::DEFAULT_LISTEN_URL;
+use super::ExecServerListenTarget;
+#[cfg(unix)]
+use super::bind_unix_socket;
 use super::parse_listen_url;
 
 #[test]
 fn parse_listen_url_accepts_default_websocket_url() {
-    let bind_address =
-        parse_listen_url(DEFAULT_LISTEN_URL).expect("default listen URL should parse");
+    let target = parse_listen_url(DEFAULT_LISTEN_URL).expect("default listen URL should parse");
     assert_eq!(
-        bind_address,
-        "127.0.0.1:0"
-            .parse::<SocketAddr>()
-            .expect("valid socket address")
+        target,
+        ExecServerListenTarget::WebSocket(
+            "127.0.0.1:0"
+                .parse::<SocketAddr>()
+                .expect("valid socket address")
+        )
     );
 }
 
 #[test]
 fn parse_listen_url_accepts_websocket_url() {
-    let bind_address =
+    let target =
         parse_listen_url("ws://127.0.0.1:1234").expect("websocket listen URL should parse");
     assert_eq!(
-        bind_address,
-        "127.0.0.1:1234"
-            .parse::<SocketAddr>()
-            .expect("valid socket address")
+        target,
+        ExecServerListenTarget::WebSocket(
+            "127.0.0.1:1234"
+                .parse::<SocketAddr>()
+                .expect("valid socket address")
+        )
+    );
+}
+
+#[test]
+fn parse_listen_url_accepts_stdio_url() {
+    let target = parse_listen_url("stdio://").expect("stdio listen URL should parse");
+    assert_eq!(target, ExecServerListenTarget::Stdio);
+}
+
+#[test]
+fn parse_listen_url_accepts_absolute_unix_socket_url() {
+    let socket_path = std::env::temp_dir().join("codex-exec-server.sock");
+    let target = parse_listen_url(&format!("unix://{}", socket_path.display()))
+        .expect("unix socket listen URL should parse");
+    assert_eq!(
+        target,
+        ExecServerListenTarget::UnixSocket(
+            AbsolutePathBuf::from_absolute_path(&socket_path).expect("absolute socket path")
+        )
+    );
+}
+
+#[test]
+fn parse_listen_url_resolves_relative_unix_socket_path() {
+    let target =
+        parse_listen_url("unix://exec-server.sock").expect("relative socket path should parse");
+    assert_eq!(
+        target,
+        ExecServerListenTarget::UnixSocket(
+            AbsolutePathBuf::relative_to_current_dir("exec-server.sock")
+                .expect("relative socket path")
+        )
+    );
+}
+
+#[test]
+fn parse_listen_url_rejects_empty_unix_socket_path() {
+    let err = parse_listen_url("unix://").expect_err("empty socket path should be rejected");
+    assert_eq!(
+        err.to_string(),
+        "invalid unix socket --listen URL `unix://`; expected `unix://PATH`"
     );
 }
 
@@ -45,6 +93,33 @@ fn parse_listen_url_rejects_unsupported_url() {
         parse_listen_url("http://127.0.0.1:1234").expect_err("unsupported scheme should fail");
     assert_eq!(
         err.to_string(),
-        "unsupported --listen URL `http://127.0.0.1:1234`; expected `ws://IP:PORT`"
+        "unsupported --listen URL `http://127.0.0.1:1234`; expected `ws://IP:PORT`, `unix://PATH`, or `stdio://`"
     );
 }
+
+#[cfg(unix)]
+#[tokio::test]
+async fn bind_unix_socket_is_owner_only_from_the_start() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = tempfile::tempdir().expect("tempdir");
+    let socket_path = dir.path().join("exec-server.sock");
+
+    let _listener = bind_unix_socket(&socket_path)
+        .await
+        .expect("bind unix socket");
+
+    let mode = std::fs::metadata(&socket_path)
+        .expect("socket metadata")
+        .permissions()
+        .mode();
+    assert_eq!(mode & 0o777, 0o600);
+    let entries = std::fs::read_dir(dir.path())
+        .expect("read socket dir")
+        .map(|entry| entry.expect("dir entry").file_name())
+        .collect::<Vec<_>>();
+    assert_eq!(entries, vec![std::ffi::OsString::from("exec-server.sock")]);
+    codex_uds::UnixStream::connect(&socket_path)
+        .await
+        .expect("renamed socket accepts connections");
+}
@@ -68,13 +68,13 @@ where
 {
     let helper_paths = test_codex_helper_paths()?;
     let codex_home = TempDir::new()?;
-    let mut child = Command::new(&helper_paths.codex_exe);
-    child.args(["exec-server", "--listen", "ws://127.0.0.1:0"]);
+    let mut child = exec_server_command(
+        &helper_paths,
+        &codex_home,
+        "ws://127.0.0.1:0",
+        /*auth_token_file*/ None,
+    );
     child.stdin(Stdio::null());
-    child.stdout(Stdio::piped());
-    child.stderr(Stdio::inherit());
-    child.kill_on_drop(true);
-    child.env("CODEX_HOME", codex_home.path());
     child.envs(env);
     let mut child = child.spawn()?;
 
@@ -90,6 +90,70 @@ where
     })
 }
 
+/// A spawned `exec-server` process whose transport the test drives directly.
+pub(crate) struct ExecServerProcess {
+    pub(crate) codex_home: TempDir,
+    pub(crate) child: Child,
+    _helper_paths: TestCodexHelperPaths,
+}
+
+/// Spawns `exec-server --listen <listen_url>`, optionally requiring `auth_token`.
+///
+/// Stdin and stdout are piped so `stdio://` tests can speak JSON-RPC over them
+/// and socket tests can read the announced listen URL.
+pub(crate) async fn spawn_exec_server_process(
+    listen_url: &str,
+    auth_token: Option<&str>,
+) -> anyhow::Result<ExecServerProcess> {
+    let helper_paths = test_codex_helper_paths()?;
+    let codex_home = TempDir::new()?;
+    let auth_token_file = match auth_token {
+        Some(auth_token) => {
+            let path = codex_home.path().join("exec-server-auth-token");
+            std::fs::write(&path, auth_token)?;
+            Some(path)
+        }
+        None => None,
+    };
+    let mut child = exec_server_command(
+        &helper_paths,
+        &codex_home,
+        listen_url,
+        auth_token_file.as_deref(),
+    );
+    child.stdin(Stdio::piped());
+    let child = child.spawn()?;
+    Ok(ExecServerProcess {
+        codex_home,
+        child,
+        _helper_paths: helper_paths,
+    })
+}
+
+impl ExecServerProcess {
+    pub(crate) async fn read_listen_url(&mut self) -> anyhow::Result<String> {
+        read_listen_url_from_stdout(&mut self.child).await
+    }
+}
+
+fn exec_server_command(
+    helper_paths: &TestCodexHelperPaths,
+    codex_home: &TempDir,
+    listen_url: &str,
+    auth_token_file: Option<&std::path::Path>,
+) -> Command {
+    let mut child = Command::new(&helper_paths.codex_exe);
+    child.args(["exec-server", "--listen", listen_url]);
+    if let Some(auth_token_file) = auth_token_file {
+        child.arg("--auth-token-file").arg(auth_token_file);
+    }
+    child.stdout(Stdio::piped());
+    child.stderr(Stdio::inherit());
+    child.kill_on_drop(true);
+    child.env("CODEX_HOME", codex_home.path());
+    child
+}
+
 impl ExecServerHarness {
     pub(crate) fn websocket_url(&self) -> &str {
         &self.websocket_url
@@ -254,7 +318,7 @@ async fn read_listen_url_from_stdout(child: &mut Child) -> anyhow::Result<String
             .map_err(|_| anyhow!("timed out waiting for exec-server stdout"))??
             .ok_or_else(|| anyhow!("exec-server stdout closed before emitting listen URL"))?;
         let listen_url = line.trim();
-        if listen_url.starts_with("ws://") {
+        if listen_url.starts_with("ws://") || listen_url.starts_with("unix://") {
             return Ok(listen_url.to_string());
         }
     }
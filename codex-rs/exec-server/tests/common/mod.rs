@@ -7,6 +7,7 @@ use std::process::Stdio;
 use std::time::Duration;
 
 use codex_exec_server::CODEX_FS_HELPER_ARG1;
+use codex_exec_server::ExecServerAuthToken;
 use codex_exec_server::ExecServerRuntimePaths;
 use codex_sandboxing::landlock::CODEX_LINUX_SANDBOX_ARG0;
 use codex_test_binary_support::TestBinaryDispatchGuard;
@@ -148,6 +149,20 @@ fn maybe_run_exec_server_from_test_binary(guard: Option<&TestBinaryDispatchGuard
         eprintln!("expected listen URL");
         std::process::exit(1);
     };
+    let auth_token_file = match args.next() {
+        None => None,
+        Some(flag) if flag == "--auth-token-file" => {
+            let Some(path) = args.next() else {
+                eprintln!("expected auth token file");
+                std::process::exit(1);
+            };
+            Some(PathBuf::from(path))
+        }
+        Some(_) => {
+            eprintln!("unexpected extra arguments");
+            std::process::exit(1);
+        }
+    };
     if args.next().is_some() {
         eprintln!("unexpected extra arguments");
         std::process::exit(1);
@@ -180,8 +195,13 @@ fn maybe_run_exec_server_from_test_binary(guard: Option<&TestBinaryDispatchGuard
             std::process::exit(1);
         }
     };
-    let exit_code = match runtime.block_on(codex_exec_server::run_main(&listen_url, runtime_paths))
-    {
+    let exit_code = match runtime.block_on(async {
+        let auth_token = match auth_token_file {
+            Some(path) => Some(ExecServerAuthToken::from_file(&path).await?),
+            None => None,
+        };
+        codex_exec_server::run_main(&listen_url, runtime_paths, auth_token).await
+    }) {
         Ok(()) => 0,
         Err(error) => {
             eprintln!("exec-server failed: {error}");
@@ -9,6 +9,7 @@ use std::path::Path;
 use std::path::PathBuf;
 use std::process::Command;
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::Context;
 use anyhow::Result;
@@ -17,10 +18,18 @@ use codex_exec_server::CreateDirectoryOptions;
 use codex_exec_server::Environment;
 use codex_exec_server::ExecServerRuntimePaths;
 use codex_exec_server::ExecutorFileSystem;
+use codex_exec_server::FileChunk;
 use codex_exec_server::FileSystemSandboxContext;
+use codex_exec_server::GlobOptions;
+use codex_exec_server::GlobResult;
+use codex_exec_server::GrepMatch;
+use codex_exec_server::GrepOptions;
+use codex_exec_server::GrepResult;
 use codex_exec_server::LocalFileSystem;
 use codex_exec_server::ReadDirectoryEntry;
+use codex_exec_server::ReadFileRange;
 use codex_exec_server::RemoveOptions;
+use codex_exec_server::WatchOptions;
 use codex_protocol::models::AdditionalPermissionProfile;
 use codex_protocol::models::FileSystemPermissions;
 use codex_protocol::models::PermissionProfile;
@@ -32,9 +41,11 @@ use codex_protocol::permissions::NetworkSandboxPolicy;
 use codex_sandboxing::policy_transforms::effective_file_system_sandbox_policy;
 use codex_sandboxing::policy_transforms::effective_network_sandbox_policy;
 use codex_utils_absolute_path::AbsolutePathBuf;
+use futures::TryStreamExt;
 use pretty_assertions::assert_eq;
 use tempfile::TempDir;
 use test_case::test_case;
+use tokio::time::timeout;
 
 use common::exec_server::ExecServerHarness;
 use common::exec_server::TestCodexHelperPaths;
@@ -474,6 +485,64 @@ async fn file_system_write_file_reports_missing_parent(use_remote: bool) -> Resu
     Ok(())
 }
 
+#[test_case(false ; "local")]
+#[test_case(true ; "remote")]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn file_system_create_symlink_and_set_permissions(use_remote: bool) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt as _;
+
+    let context = create_file_system_context(use_remote).await?;
+    let file_system = context.file_system;
+
+    let tmp = TempDir::new()?;
+    let file_path = tmp.path().join("run.sh");
+    std::fs::write(&file_path, "#!/bin/sh\n")?;
+    std::fs::set_permissions(&file_path, std::fs::Permissions::from_mode(0o644))?;
+
+    file_system
+        .set_permissions(
+            &absolute_path(file_path.clone()),
+            0o755,
+            /*sandbox*/ None,
+        )
+        .await
+        .with_context(|| format!("mode={use_remote}"))?;
+    let metadata = file_system
+        .get_metadata(&absolute_path(file_path.clone()), /*sandbox*/ None)
+        .await
+        .with_context(|| format!("mode={use_remote}"))?;
+    assert_eq!(metadata.mode, Some(0o755), "mode={use_remote}");
+
+    let link_path = tmp.path().join("dangling-link");
+    file_system
+        .create_symlink(
+            &absolute_path(link_path.clone()),
+            Path::new("missing.txt"),
+            /*sandbox*/ None,
+        )
+        .await
+        .with_context(|| format!("mode={use_remote}"))?;
+    assert_eq!(
+        std::fs::read_link(&link_path)?,
+        PathBuf::from("missing.txt")
+    );
+
+    let result = file_system
+        .create_symlink(
+            &absolute_path(link_path.clone()),
+            Path::new("other.txt"),
+            /*sandbox*/ None,
+        )
+        .await;
+    assert!(result.is_err(), "mode={use_remote}");
+    assert_eq!(
+        std::fs::read_link(&link_path)?,
+        PathBuf::from("missing.txt")
+    );
+
+    Ok(())
+}
+
 #[test_case(false ; "local")]
 #[test_case(true ; "remote")]
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
@@ -1128,3 +1197,223 @@ async fn file_system_copy_rejects_standalone_fifo_source(use_remote: bool) -> Re
 
     Ok(())
 }
+
+#[test_case(false ; "local")]
+#[test_case(true ; "remote")]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn file_system_ranged_read_and_rename(use_remote: bool) -> Result<()> {
+    let context = create_file_system_context(use_remote).await?;
+    let file_system = context.file_system;
+
+    let tmp = TempDir::new()?;
+    let source_path = absolute_path(tmp.path().join("source.txt"));
+    let renamed_path = absolute_path(tmp.path().join("renamed.txt"));
+    std::fs::write(source_path.as_path(), "0123456789")?;
+
+    let chunk = file_system
+        .read_file_range(
+            &source_path,
+            ReadFileRange {
+                offset: 3,
+                length: Some(4),
+            },
+            /*sandbox*/ None,
+        )
+        .await
+        .with_context(|| format!("mode={use_remote}"))?;
+    assert_eq!(
+        chunk,
+        FileChunk {
+            data: b"3456".to_vec(),
+            total_size: 10,
+        }
+    );
+
+    let past_end = file_system
+        .read_file_range(
+            &source_path,
+            ReadFileRange {
+                offset: 20,
+                length: None,
+            },
+            /*sandbox*/ None,
+        )
+        .await
+        .with_context(|| format!("mode={use_remote}"))?;
+    assert_eq!(
+        past_end,
+        FileChunk {
+            data: Vec::new(),
+            total_size: 10,
+        }
+    );
+
+    let chunks = file_system
+        .read_file_stream(&source_path, /*chunk_size*/ 4, /*sandbox*/ None)
+        .try_collect::<Vec<_>>()
+        .await
+        .with_context(|| format!("mode={use_remote}"))?;
+    assert_eq!(
+        chunks,
+        vec![b"0123".to_vec(), b"4567".to_vec(), b"89".to_vec()]
+    );
+
+    file_system
+        .rename(&source_path, &renamed_path, /*sandbox*/ None)
+        .await
+        .with_context(|| format!("mode={use_remote}"))?;
+    assert!(!source_path.as_path().exists());
+    assert_eq!(
+        std::fs::read_to_string(renamed_path.as_path())?,
+        "0123456789"
+    );
+
+    Ok(())
+}
+
+#[test_case(false ; "local")]
+#[test_case(true ; "remote")]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn file_system_glob_and_grep_search_workspace(use_remote: bool) -> Result<()> {
+    let context = create_file_system_context(use_remote).await?;
+    let file_system = context.file_system;
+
+    let tmp = TempDir::new()?;
+    let workspace = tmp.path().join("workspace");
+    std::fs::create_dir_all(workspace.join("src"))?;
+    std::fs::create_dir_all(workspace.join("target"))?;
+    std::fs::write(workspace.join(".gitignore"), "target/\n")?;
+    std::fs::write(workspace.join("src/lib.rs"), "pub fn needle() {}\n")?;
+    std::fs::write(workspace.join("src/main.rs"), "fn main() {}\n")?;
+    std::fs::write(workspace.join("target/out.rs"), "pub fn needle() {}\n")?;
+
+    let globbed = file_system
+        .glob(
+            &absolute_path(workspace.clone()),
+            GlobOptions {
+                patterns: vec!["**/*.rs".to_string()],
+                max_results: 10,
+            },
+            /*sandbox*/ None,
+        )
+        .await
+        .with_context(|| format!("mode={use_remote}"))?;
+    assert_eq!(
+        globbed,
+        GlobResult {
+            paths: vec![
+                absolute_path(workspace.join("src/lib.rs")),
+                absolute_path(workspace.join("src/main.rs")),
+            ],
+            truncated: false,
+        }
+    );
+
+    let grepped = file_system
+        .grep(
+            &absolute_path(workspace.clone()),
+            GrepOptions {
+                pattern: r"fn \w+\(".to_string(),
+                include: vec!["src/*.rs".to_string()],
+                case_insensitive: false,
+                max_results: 1,
+            },
+            /*sandbox*/ None,
+        )
+        .await
+        .with_context(|| format!("mode={use_remote}"))?;
+    assert_eq!(
+        grepped,
+        GrepResult {
+            matches: vec![GrepMatch {
+                path: absolute_path(workspace.join("src/lib.rs")),
+                line_number: 1,
+                line: "pub fn needle() {}".to_string(),
+            }],
+            truncated: true,
+        }
+    );
+
+    Ok(())
+}
+
+#[test_case(false ; "local")]
+#[test_case(true ; "remote")]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn file_system_sandboxed_grep_allows_readable_root(use_remote: bool) -> Result<()> {
+    let context = create_file_system_context(use_remote).await?;
+    let file_system = context.file_system;
+
+    let tmp = TempDir::new()?;
+    let allowed_dir = tmp.path().join("allowed");
+    std::fs::create_dir_all(&allowed_dir)?;
+    std::fs::write(allowed_dir.join("note.txt"), "sandboxed needle\n")?;
+    let sandbox = read_only_sandbox(allowed_dir.clone());
+
+    let grepped = file_system
+        .grep(
+            &absolute_path(allowed_dir.clone()),
+            GrepOptions {
+                pattern: "needle".to_string(),
+                include: Vec::new(),
+                case_insensitive: false,
+                max_results: 10,
+            },
+            Some(&sandbox),
+        )
+        .await
+        .with_context(|| format!("mode={use_remote}"))?;
+    assert_eq!(
+        grepped,
+        GrepResult {
+            matches: vec![GrepMatch {
+                path: absolute_path(allowed_dir.join("note.txt")),
+                line_number: 1,
+                line: "sandboxed needle".to_string(),
+            }],
+            truncated: false,
+        }
+    );
+
+    Ok(())
+}
+
+#[test_case(false ; "local")]
+#[test_case(true ; "remote")]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn file_system_watch_reports_created_file(use_remote: bool) -> Result<()> {
+    let context = create_file_system_context(use_remote).await?;
+    let file_system = context.file_system;
+
+    let tmp = TempDir::new()?;
+    let watched_dir = tmp.path().join("watched");
+    std::fs::create_dir_all(&watched_dir)?;
+    let created_path = absolute_path(watched_dir.join("created.txt"));
+
+    let mut watch = file_system
+        .watch(
+            &absolute_path(watched_dir.clone()),
+            WatchOptions { recursive: true },
+            /*sandbox*/ None,
+        )
+        .await
+        .with_context(|| format!("mode={use_remote}"))?;
+    assert_eq!(watch.path(), &absolute_path(watched_dir));
+
+    std::fs::write(created_path.as_path(), "hello")?;
+
+    timeout(Duration::from_secs(10), async {
+        loop {
+            let Some(event) = watch.recv().await else {
+                anyhow::bail!("watch closed before reporting the created file");
+            };
+            if event.changed_paths.contains(&created_path) {
+                return Ok(());
+            }
+        }
+    })
+    .await
+    .with_context(|| format!("mode={use_remote}: timed out waiting for fs change"))??;
+
+    Ok(())
+}
@@ -1007,6 +1007,7 @@ impl JsonRpcPeer {
             InitializeParams {
                 client_name: CLIENT_NAME.to_string(),
                 resume_session_id: None,
+                auth_token: None,
             }
         );
         self.write_response(
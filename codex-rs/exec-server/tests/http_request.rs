@@ -356,6 +356,7 @@ async fn initialize_exec_server(server: &mut ExecServerHarness) -> anyhow::Resul
             serde_json::to_value(InitializeParams {
                 client_name: "exec-server-http-test".to_string(),
                 resume_session_id: None,
+                auth_token: None,
             })?,
         )
         .await?;
@@ -4,9 +4,13 @@ mod common;
 
 use codex_app_server_protocol::JSONRPCMessage;
 use codex_app_server_protocol::JSONRPCResponse;
+use codex_exec_server::ExecServerClient;
+use codex_exec_server::ExecServerError;
 use codex_exec_server::InitializeParams;
 use codex_exec_server::InitializeResponse;
+use codex_exec_server::RemoteExecServerConnectArgs;
 use common::exec_server::exec_server;
+use common::exec_server::spawn_exec_server_process;
 use pretty_assertions::assert_eq;
 use uuid::Uuid;
 
@@ -19,6 +23,7 @@ async fn exec_server_accepts_initialize() -> anyhow::Result<()> {
             serde_json::to_value(InitializeParams {
                 client_name: "exec-server-test".to_string(),
                 resume_session_id: None,
+                auth_token: None,
             })?,
         )
         .await?;
@@ -34,3 +39,39 @@ async fn exec_server_accepts_initialize() -> anyhow::Result<()> {
     server.shutdown().await?;
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn exec_server_rejects_initialize_without_valid_auth_token() -> anyhow::Result<()> {
+    let mut server = spawn_exec_server_process("ws://127.0.0.1:0", Some("expected-token")).await?;
+    let websocket_url = server.read_listen_url().await?;
+
+    for auth_token in [None, Some("wrong-token".to_string())] {
+        let mut args =
+            RemoteExecServerConnectArgs::new(websocket_url.clone(), "exec-server-test".to_string());
+        args.auth_token = auth_token;
+        let Err(err) = ExecServerClient::connect_websocket(args).await else {
+            panic!("initialize without the expected auth token should be rejected");
+        };
+        match err {
+            ExecServerError::Server { code, message } => assert_eq!(
+                (code, message.as_str()),
+                (
+                    -32001,
+                    "exec-server rejected initialize: missing or invalid auth token"
+                )
+            ),
+            other => panic!("expected exec-server rejection, got {other}"),
+        }
+    }
+
+    let mut args =
+        RemoteExecServerConnectArgs::new(websocket_url.clone(), "exec-server-test".to_string());
+    args.auth_token = Some("expected-token".to_string());
+    let client = ExecServerClient::connect_websocket(args).await?;
+    let session_id = client
+        .session_id()
+        .expect("initialize should record the session id");
+    Uuid::parse_str(&session_id)?;
+
+    Ok(())
+}
@@ -24,6 +24,7 @@ async fn exec_server_starts_process_over_websocket() -> anyhow::Result<()> {
             serde_json::to_value(InitializeParams {
                 client_name: "exec-server-test".to_string(),
                 resume_session_id: None,
+                auth_token: None,
             })?,
         )
         .await?;
@@ -87,6 +88,7 @@ async fn exec_server_defaults_omitted_pipe_stdin_to_closed_stdin() -> anyhow::Re
             serde_json::to_value(InitializeParams {
                 client_name: "exec-server-test".to_string(),
                 resume_session_id: None,
+                auth_token: None,
             })?,
         )
         .await?;
@@ -180,6 +182,7 @@ async fn exec_server_resumes_detached_session_without_killing_processes() -> any
             serde_json::to_value(InitializeParams {
                 client_name: "exec-server-test".to_string(),
                 resume_session_id: None,
+                auth_token: None,
             })?,
         )
         .await?;
@@ -232,6 +235,7 @@ async fn exec_server_resumes_detached_session_without_killing_processes() -> any
             serde_json::to_value(InitializeParams {
                 client_name: "exec-server-test".to_string(),
                 resume_session_id: Some(initialize_response.session_id.clone()),
+                auth_token: None,
             })?,
         )
         .await?;
@@ -0,0 +1,86 @@
+#![cfg(unix)]
+
+mod common;
+
+use std::time::Duration;
+
+use codex_app_server_protocol::JSONRPCError;
+use codex_app_server_protocol::JSONRPCMessage;
+use codex_app_server_protocol::JSONRPCRequest;
+use codex_app_server_protocol::JSONRPCResponse;
+use codex_app_server_protocol::RequestId;
+use codex_exec_server::InitializeParams;
+use codex_exec_server::InitializeResponse;
+use common::exec_server::spawn_exec_server_process;
+use pretty_assertions::assert_eq;
+use tokio::io::AsyncBufReadExt;
+use tokio::io::AsyncWriteExt;
+use tokio::io::BufReader;
+use tokio::time::timeout;
+use uuid::Uuid;
+
+const RESPONSE_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn exec_server_speaks_jsonrpc_over_stdio() -> anyhow::Result<()> {
+    let mut server = spawn_exec_server_process("stdio://", Some("expected-token")).await?;
+    let mut stdin = server.child.stdin.take().expect("piped stdin");
+    let mut stdout = BufReader::new(server.child.stdout.take().expect("piped stdout")).lines();
+
+    write_initialize(&mut stdin, /*id*/ 1, Some("wrong-token")).await?;
+    let line = timeout(RESPONSE_TIMEOUT, stdout.next_line())
+        .await??
+        .expect("initialize error line");
+    let JSONRPCMessage::Error(JSONRPCError { id, error }) = serde_json::from_str(&line)? else {
+        panic!("expected initialize error, got {line}");
+    };
+    assert_eq!(id, RequestId::Integer(1));
+    assert_eq!(error.code, -32001);
+
+    drop(stdin);
+    let status = timeout(RESPONSE_TIMEOUT, server.child.wait()).await??;
+    assert!(
+        status.success(),
+        "stdio server should exit once stdin closes"
+    );
+
+    let mut server = spawn_exec_server_process("stdio://", Some("expected-token")).await?;
+    let mut stdin = server.child.stdin.take().expect("piped stdin");
+    let mut stdout = BufReader::new(server.child.stdout.take().expect("piped stdout")).lines();
+
+    write_initialize(&mut stdin, /*id*/ 1, Some("expected-token")).await?;
+    let line = timeout(RESPONSE_TIMEOUT, stdout.next_line())
+        .await??
+        .expect("initialize response line");
+    let JSONRPCMessage::Response(JSONRPCResponse { id, result }) = serde_json::from_str(&line)?
+    else {
+        panic!("expected initialize response, got {line}");
+    };
+    assert_eq!(id, RequestId::Integer(1));
+    let initialize_response: InitializeResponse = serde_json::from_value(result)?;
+    Uuid::parse_str(&initialize_response.session_id)?;
+
+    Ok(())
+}
+
+async fn write_initialize(
+    stdin: &mut tokio::process::ChildStdin,
+    id: i64,
+    auth_token: Option<&str>,
+) -> anyhow::Result<()> {
+    let request = JSONRPCMessage::Request(JSONRPCRequest {
+        id: RequestId::Integer(id),
+        method: "initialize".to_string(),
+        params: Some(serde_json::to_value(InitializeParams {
+            client_name: "exec-server-test".to_string(),
+            resume_session_id: None,
+            auth_token: auth_token.map(str::to_string),
+        })?),
+        trace: None,
+    });
+    let mut encoded = serde_json::to_vec(&request)?;
+    encoded.push(b'\n');
+    stdin.write_all(&encoded).await?;
+    stdin.flush().await?;
+    Ok(())
+}
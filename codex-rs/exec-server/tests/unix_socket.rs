@@ -0,0 +1,67 @@
+#![cfg(unix)]
+
+mod common;
+
+use std::os::unix::fs::PermissionsExt;
+
+use codex_exec_server::ExecServerClient;
+use codex_exec_server::ExecServerError;
+use codex_exec_server::RemoteExecServerConnectArgs;
+use common::exec_server::spawn_exec_server_process;
+use pretty_assertions::assert_eq;
+use tempfile::TempDir;
+use uuid::Uuid;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn exec_server_accepts_authenticated_clients_over_unix_socket() -> anyhow::Result<()> {
+    let socket_dir = TempDir::new()?;
+    let socket_path = socket_dir.path().join("exec-server.sock");
+    let listen_url = format!("unix://{}", socket_path.display());
+    let mut server = spawn_exec_server_process(&listen_url, Some("expected-token")).await?;
+    assert_eq!(server.read_listen_url().await?, listen_url);
+    assert_eq!(
+        std::fs::metadata(&socket_path)?.permissions().mode() & 0o777,
+        0o600
+    );
+
+    let mut args = RemoteExecServerConnectArgs::new(listen_url, "exec-server-test".to_string());
+    let Err(err) = ExecServerClient::connect_websocket(args.clone()).await else {
+        panic!("unix socket client without an auth token should be rejected");
+    };
+    assert!(
+        matches!(err, ExecServerError::Server { code: -32001, .. }),
+        "expected exec-server rejection, got {err}"
+    );
+
+    args.auth_token = Some("expected-token".to_string());
+    let client = ExecServerClient::connect_websocket(args).await?;
+    let session_id = client
+        .session_id()
+        .expect("initialize should record the session id");
+    Uuid::parse_str(&session_id)?;
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn exec_server_refuses_to_replace_live_unix_socket() -> anyhow::Result<()> {
+    let socket_dir = TempDir::new()?;
+    let socket_path = socket_dir.path().join("exec-server.sock");
+    let listen_url = format!("unix://{}", socket_path.display());
+    let mut first = spawn_exec_server_process(&listen_url, /*auth_token*/ None).await?;
+    first.read_listen_url().await?;
+
+    let mut second = spawn_exec_server_process(&listen_url, /*auth_token*/ None).await?;
+    let status =
+        tokio::time::timeout(std::time::Duration::from_secs(10), second.child.wait()).await??;
+    assert!(!status.success(), "second listener should fail to bind");
+
+    let client = ExecServerClient::connect_websocket(RemoteExecServerConnectArgs::new(
+        listen_url,
+        "exec-server-test".to_string(),
+    ))
+    .await?;
+    assert!(client.session_id().is_some());
+
+    Ok(())
+}
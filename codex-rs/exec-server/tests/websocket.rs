@@ -38,6 +38,7 @@ async fn exec_server_reports_malformed_websocket_json_and_keeps_running() -> any
             serde_json::to_value(InitializeParams {
                 client_name: "exec-server-test".to_string(),
                 resume_session_id: None,
+                auth_token: None,
             })?,
         )
         .await?;
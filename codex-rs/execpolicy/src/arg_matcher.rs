@@ -0,0 +1,207 @@
+use std::fmt;
+use std::path::Path;
+
+use codex_utils_absolute_path::AbsolutePathBuf;
+use globset::GlobBuilder;
+use globset::GlobMatcher;
+use regex::Regex;
+
+use crate::error::Error;
+use crate::error::Result;
+
+/// Matches a single command argument by something other than exact equality.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ArgMatcher {
+    Literal(String),
+    /// Shell-style glob over the whole argument. `*` also matches `/`.
+    Glob(GlobPattern),
+    /// Regular expression that must match the whole argument.
+    Regex(RegexPattern),
+    /// A path that stays inside the command's working directory once `.` and
+    /// `..` components are resolved lexically. Flags never match.
+    WorkspacePath,
+}
+
+impl ArgMatcher {
+    pub fn glob(pattern: &str) -> Result<Self> {
+        let matcher = GlobBuilder::new(pattern)
+            .backslash_escape(true)
+            .build()
+            .map_err(|err| Error::InvalidPattern(format!("invalid glob `{pattern}`: {err}")))?
+            .compile_matcher();
+        Ok(Self::Glob(GlobPattern {
+            source: pattern.to_string(),
+            matcher,
+        }))
+    }
+
+    pub fn regex(pattern: &str) -> Result<Self> {
+        let regex = Regex::new(&format!("^(?:{pattern})$"))
+            .map_err(|err| Error::InvalidPattern(format!("invalid regex `{pattern}`: {err}")))?;
+        Ok(Self::Regex(RegexPattern {
+            source: pattern.to_string(),
+            regex,
+        }))
+    }
+
+    pub fn matches(&self, arg: &str, cwd: Option<&AbsolutePathBuf>) -> bool {
+        match self {
+            Self::Literal(expected) => expected == arg,
+            Self::Glob(glob) => glob.matcher.is_match(arg),
+            Self::Regex(regex) => regex.regex.is_match(arg),
+            Self::WorkspacePath => cwd.is_some_and(|cwd| is_workspace_path(arg, cwd)),
+        }
+    }
+}
+
+impl fmt::Display for ArgMatcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Literal(value) => f.write_str(value),
+            Self::Glob(glob) => write!(f, "glob({:?})", glob.source),
+            Self::Regex(regex) => write!(f, "regex({:?})", regex.source),
+            Self::WorkspacePath => f.write_str("workspace_path()"),
+        }
+    }
+}
+
+/// A compiled glob that compares equal to another glob with the same source.
+#[derive(Clone, Debug)]
+pub struct GlobPattern {
+    source: String,
+    matcher: GlobMatcher,
+}
+
+impl GlobPattern {
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+}
+
+impl PartialEq for GlobPattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source
+    }
+}
+
+impl Eq for GlobPattern {}
+
+/// A compiled, fully anchored regex that compares equal to another regex with
+/// the same source.
+#[derive(Clone, Debug)]
+pub struct RegexPattern {
+    source: String,
+    regex: Regex,
+}
+
+impl RegexPattern {
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+}
+
+impl PartialEq for RegexPattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source
+    }
+}
+
+impl Eq for RegexPattern {}
+
+/// Returns whether `arg` sets `flag`, either exactly, as `--flag=value`, or as
+/// part of a bundle of short flags such as `-fu` for `-f`.
+pub(crate) fn arg_sets_flag(arg: &str, flag: &str) -> bool {
+    if arg == flag {
+        return true;
+    }
+
+    if flag.starts_with("--") {
+        return arg
+            .strip_prefix(flag)
+            .is_some_and(|rest| rest.starts_with('='));
+    }
+
+    let Some(short) = flag.strip_prefix('-') else {
+        return false;
+    };
+    let mut short_chars = short.chars();
+    let (Some(short), None) = (short_chars.next(), short_chars.next()) else {
+        return false;
+    };
+    arg.strip_prefix('-')
+        .is_some_and(|bundle| !bundle.starts_with('-') && bundle.contains(short))
+}
+
+fn is_workspace_path(arg: &str, cwd: &AbsolutePathBuf) -> bool {
+    if arg.is_empty() || arg.starts_with('-') {
+        return false;
+    }
+
+    let resolved = AbsolutePathBuf::resolve_path_against_base(Path::new(arg), cwd.as_path());
+    resolved.as_path().starts_with(cwd.as_path())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn glob_and_regex_match_whole_argument() {
+        let glob = ArgMatcher::glob("*.rs").expect("valid glob");
+        assert_eq!(glob.matches("src/lib.rs", /*cwd*/ None), true);
+        assert_eq!(glob.matches("lib.rs.bak", /*cwd*/ None), false);
+
+        let regex = ArgMatcher::regex("-[0-9]+").expect("valid regex");
+        assert_eq!(regex.matches("-20", /*cwd*/ None), true);
+        assert_eq!(regex.matches("x-20", /*cwd*/ None), false);
+        assert_eq!(regex.matches("-20x", /*cwd*/ None), false);
+    }
+
+    #[test]
+    fn invalid_glob_and_regex_are_rejected() {
+        assert!(matches!(
+            ArgMatcher::glob("[a-"),
+            Err(Error::InvalidPattern(_))
+        ));
+        assert!(matches!(
+            ArgMatcher::regex("(unclosed"),
+            Err(Error::InvalidPattern(_))
+        ));
+    }
+
+    #[test]
+    fn workspace_path_resolves_lexically_against_cwd() {
+        let cwd = AbsolutePathBuf::current_dir().expect("current dir");
+        let outside = cwd
+            .parent()
+            .expect("current dir has a parent")
+            .join("elsewhere");
+        let matcher = ArgMatcher::WorkspacePath;
+
+        assert_eq!(matcher.matches("src/lib.rs", Some(&cwd)), true);
+        assert_eq!(matcher.matches("./a/../b", Some(&cwd)), true);
+        assert_eq!(
+            matcher.matches(&cwd.join("nested").to_string_lossy(), Some(&cwd)),
+            true
+        );
+        assert_eq!(matcher.matches("../elsewhere", Some(&cwd)), false);
+        assert_eq!(
+            matcher.matches(&outside.to_string_lossy(), Some(&cwd)),
+            false
+        );
+        assert_eq!(matcher.matches("-rf", Some(&cwd)), false);
+        assert_eq!(matcher.matches("src", /*cwd*/ None), false);
+    }
+
+    #[test]
+    fn arg_sets_flag_handles_values_and_bundles() {
+        assert_eq!(arg_sets_flag("--force", "--force"), true);
+        assert_eq!(arg_sets_flag("--force=yes", "--force"), true);
+        assert_eq!(arg_sets_flag("--force-with-lease", "--force"), false);
+        assert_eq!(arg_sets_flag("-f", "-f"), true);
+        assert_eq!(arg_sets_flag("-uf", "-f"), true);
+        assert_eq!(arg_sets_flag("--follow-tags", "-f"), false);
+        assert_eq!(arg_sets_flag("origin", "-f"), false);
+    }
+}
@@ -1,4 +1,5 @@
 use starlark::Error as StarlarkError;
+use std::fmt;
 use thiserror::Error;
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -21,6 +22,13 @@ pub struct ErrorLocation {
     pub range: TextRange,
 }
 
+impl fmt::Display for ErrorLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let start = self.range.start;
+        write!(f, "{}:{}:{}", self.path, start.line, start.column)
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum Error {
     #[error("invalid decision: {0}")]
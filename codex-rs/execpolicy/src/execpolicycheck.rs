@@ -3,7 +3,9 @@ use std::path::PathBuf;
 
 use anyhow::Context;
 use anyhow::Result;
+use clap::Args;
 use clap::Parser;
+use codex_utils_absolute_path::AbsolutePathBuf;
 use serde::Serialize;
 
 use crate::Decision;
@@ -23,10 +25,8 @@ pub struct ExecPolicyCheckCommand {
     #[arg(long)]
     pub pretty: bool,
 
-    /// Resolve absolute program paths against basename rules, gated by any
-    /// `host_executable()` definitions in the loaded policy files.
-    #[arg(long)]
-    pub resolve_host_executables: bool,
+    #[command(flatten)]
+    pub match_args: MatchArgs,
 
     /// Command tokens to check against the policy.
     #[arg(
@@ -45,9 +45,7 @@ impl ExecPolicyCheckCommand {
         let matched_rules = policy.matches_for_command_with_options(
             &self.command,
             /*heuristics_fallback*/ None,
-            &MatchOptions {
-                resolve_host_executables: self.resolve_host_executables,
-            },
+            &self.match_args.match_options()?,
         );
 
         let json = format_matches_json(&matched_rules, self.pretty)?;
@@ -70,6 +68,36 @@ pub fn format_matches_json(matched_rules: &[RuleMatch], pretty: bool) -> Result<
     }
 }
 
+/// Matching options shared by the subcommands that evaluate commands.
+#[derive(Debug, Args, Clone)]
+pub struct MatchArgs {
+    /// Resolve absolute program paths against basename rules, gated by any
+    /// `host_executable()` definitions in the loaded policy files.
+    #[arg(long)]
+    pub resolve_host_executables: bool,
+
+    /// Working directory that `workspace_path()` tokens resolve against.
+    /// Defaults to the current directory.
+    #[arg(long, value_name = "DIR")]
+    pub cwd: Option<PathBuf>,
+}
+
+impl MatchArgs {
+    /// Resolves `--cwd` against the current directory and builds the options
+    /// used to match commands.
+    pub fn match_options(&self) -> Result<MatchOptions> {
+        let cwd = match &self.cwd {
+            Some(cwd) => AbsolutePathBuf::relative_to_current_dir(cwd),
+            None => AbsolutePathBuf::current_dir(),
+        }
+        .context("failed to resolve working directory")?;
+        Ok(MatchOptions {
+            resolve_host_executables: self.resolve_host_executables,
+            cwd: Some(cwd),
+        })
+    }
+}
+
 pub fn load_policies(policy_paths: &[PathBuf]) -> Result<Policy> {
     let mut parser = PolicyParser::new();
 
@@ -0,0 +1,184 @@
+use std::fmt::Write as _;
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::Parser;
+
+use crate::MatchOptions;
+use crate::Policy;
+use crate::PrefixRule;
+use crate::RuleMatch;
+use crate::RuleRef;
+use crate::execpolicycheck::MatchArgs;
+use crate::execpolicycheck::load_policies;
+use crate::execpolicylint::display_location;
+
+/// Arguments for explaining how a command is evaluated against execpolicy files.
+#[derive(Debug, Parser, Clone)]
+pub struct ExecPolicyExplainCommand {
+    /// Paths to execpolicy rule files to evaluate (repeatable).
+    #[arg(short = 'r', long = "rules", value_name = "PATH", required = true)]
+    pub rules: Vec<PathBuf>,
+
+    #[command(flatten)]
+    pub match_args: MatchArgs,
+
+    /// Command tokens to explain.
+    #[arg(
+        value_name = "COMMAND",
+        required = true,
+        trailing_var_arg = true,
+        allow_hyphen_values = true
+    )]
+    pub command: Vec<String>,
+}
+
+impl ExecPolicyExplainCommand {
+    /// Load the policies and print every matching rule, marking the ones that
+    /// decide the outcome.
+    pub fn run(&self) -> Result<()> {
+        let policy = load_policies(&self.rules)?;
+        let options = self.match_args.match_options()?;
+        print!("{}", explain_command(&policy, &self.command, &options));
+        Ok(())
+    }
+}
+
+/// Renders a human-readable account of which rules match `command` and why
+/// the resulting decision wins.
+pub fn explain_command(policy: &Policy, command: &[String], options: &MatchOptions) -> String {
+    let matching_rules = policy.matching_rules_with_options(command, options);
+    let rendered_command =
+        shlex::try_join(command.iter().map(String::as_str)).unwrap_or_else(|_| command.join(" "));
+
+    let mut out = String::new();
+    let _ = writeln!(out, "command: {rendered_command}");
+    let Some(decision) = matching_rules
+        .iter()
+        .map(|(_rule, rule_match)| rule_match.decision())
+        .max()
+    else {
+        let _ = writeln!(
+            out,
+            "decision: none (no rule matched; Codex falls back to its built-in safety checks)"
+        );
+        return out;
+    };
+
+    let _ = writeln!(out, "decision: {}", decision.as_str());
+    let deciding = matching_rules
+        .iter()
+        .filter(|(_rule, rule_match)| rule_match.decision() == decision)
+        .count();
+    let reason = if matching_rules.len() == 1 {
+        "it is the only matching rule".to_string()
+    } else if deciding == matching_rules.len() {
+        format!("all {} matching rules agree", matching_rules.len())
+    } else {
+        format!(
+            "the strictest decision across {} matching rules wins (forbidden > prompt > allow)",
+            matching_rules.len()
+        )
+    };
+    let _ = writeln!(out, "reason: {reason}");
+    let _ = writeln!(out, "matched rules:");
+    for (rule, rule_match) in &matching_rules {
+        write_rule_match(
+            &mut out,
+            rule,
+            rule_match,
+            rule_match.decision() == decision,
+        );
+    }
+    out
+}
+
+fn write_rule_match(out: &mut String, rule: &RuleRef, rule_match: &RuleMatch, decides: bool) {
+    let marker = if decides { "*" } else { "-" };
+    let pattern = rule
+        .as_any()
+        .downcast_ref::<PrefixRule>()
+        .map_or_else(|| format!("{rule:?}"), |rule| rule.pattern.to_string());
+    let _ = writeln!(
+        out,
+        "  {marker} {:<9} {pattern}  ({})",
+        rule_match.decision().as_str(),
+        display_location(rule.location())
+    );
+    if let RuleMatch::PrefixRuleMatch {
+        matched_prefix,
+        resolved_program,
+        justification,
+        ..
+    } = rule_match
+    {
+        let matched_prefix = shlex::try_join(matched_prefix.iter().map(String::as_str))
+            .unwrap_or_else(|_| matched_prefix.join(" "));
+        let _ = writeln!(out, "      matched: {matched_prefix}");
+        if let Some(resolved_program) = resolved_program {
+            let _ = writeln!(
+                out,
+                "      resolved program: {}",
+                resolved_program.display()
+            );
+        }
+        if let Some(justification) = justification {
+            let _ = writeln!(out, "      justification: {justification}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PolicyParser;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn explains_which_rule_wins() {
+        let mut parser = PolicyParser::new();
+        parser
+            .parse(
+                "team.rules",
+                r#"
+prefix_rule(pattern = ["git"], decision = "prompt")
+prefix_rule(
+    pattern = ["git", "push", any_remaining()],
+    decision = "forbidden",
+    justification = "Open a pull request instead.",
+)
+prefix_rule(pattern = ["git", "status"])
+"#,
+            )
+            .expect("policy should parse");
+        let policy = parser.build();
+        let command = ["git", "push", "origin", "main"].map(str::to_string);
+
+        assert_eq!(
+            explain_command(&policy, &command, &MatchOptions::default()),
+            "command: git push origin main
+decision: forbidden
+reason: the strictest decision across 2 matching rules wins (forbidden > prompt > allow)
+matched rules:
+  - prompt    git  (team.rules:2:1)
+      matched: git
+  * forbidden git push any_remaining()  (team.rules:3:1)
+      matched: git push origin main
+      justification: Open a pull request instead.
+"
+        );
+    }
+
+    #[test]
+    fn explains_unmatched_commands() {
+        let policy = Policy::empty();
+        let command = ["ls", "-l"].map(str::to_string);
+
+        assert_eq!(
+            explain_command(&policy, &command, &MatchOptions::default()),
+            "command: ls -l
+decision: none (no rule matched; Codex falls back to its built-in safety checks)
+"
+        );
+    }
+}
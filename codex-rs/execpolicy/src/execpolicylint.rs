@@ -0,0 +1,337 @@
+use std::fmt;
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::Parser;
+
+use crate::ArgMatcher;
+use crate::ErrorLocation;
+use crate::PatternToken;
+use crate::Policy;
+use crate::PrefixPattern;
+use crate::PrefixRule;
+use crate::execpolicycheck::load_policies;
+
+/// Arguments for linting one or more execpolicy files.
+#[derive(Debug, Parser, Clone)]
+pub struct ExecPolicyLintCommand {
+    /// Paths to execpolicy rule files to lint together (repeatable).
+    #[arg(short = 'r', long = "rules", value_name = "PATH", required = true)]
+    pub rules: Vec<PathBuf>,
+}
+
+impl ExecPolicyLintCommand {
+    /// Load the policies, print every finding, and fail if there were any.
+    pub fn run(&self) -> Result<()> {
+        let policy = load_policies(&self.rules)?;
+        let findings = lint_policy(&policy);
+        if findings.is_empty() {
+            println!("No problems found.");
+            return Ok(());
+        }
+
+        for finding in &findings {
+            println!("{finding}");
+        }
+        anyhow::bail!("found {} problem(s)", findings.len())
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LintFindingKind {
+    /// Another rule has the same pattern and decision.
+    Duplicate,
+    /// Another rule has the same pattern but a different decision.
+    Conflict,
+    /// A broader rule matches every command this rule matches, so this rule
+    /// never changes the outcome.
+    Shadowed,
+}
+
+impl LintFindingKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Duplicate => "duplicate",
+            Self::Conflict => "conflict",
+            Self::Shadowed => "shadowed",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LintFinding {
+    pub kind: LintFindingKind,
+    /// Location of the rule the finding is about.
+    pub location: Option<ErrorLocation>,
+    pub message: String,
+}
+
+impl fmt::Display for LintFinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {}: {}",
+            display_location(self.location.as_ref()),
+            self.kind.as_str(),
+            self.message
+        )
+    }
+}
+
+/// Reports duplicate, conflicting, and shadowed prefix rules. Each rule is
+/// reported at most once, against the first rule that explains the finding.
+pub fn lint_policy(policy: &Policy) -> Vec<LintFinding> {
+    let mut programs: Vec<_> = policy.rules().iter_all().collect();
+    programs.sort_by(|(left, _), (right, _)| left.cmp(right));
+
+    let mut findings = Vec::new();
+    for (_program, rules) in programs {
+        let prefix_rules: Vec<&PrefixRule> = rules
+            .iter()
+            .filter_map(|rule| rule.as_any().downcast_ref::<PrefixRule>())
+            .collect();
+        for (index, rule) in prefix_rules.iter().enumerate() {
+            if let Some(finding) = lint_rule(rule, index, &prefix_rules) {
+                findings.push(finding);
+            }
+        }
+    }
+    findings
+}
+
+fn lint_rule(rule: &PrefixRule, index: usize, rules: &[&PrefixRule]) -> Option<LintFinding> {
+    if let Some(earlier) = rules[..index]
+        .iter()
+        .find(|earlier| earlier.pattern == rule.pattern)
+    {
+        let (kind, message) = if earlier.decision == rule.decision {
+            (
+                LintFindingKind::Duplicate,
+                format!(
+                    "`{}` repeats the rule at {}",
+                    rule.pattern,
+                    display_location(earlier.location.as_ref())
+                ),
+            )
+        } else {
+            (
+                LintFindingKind::Conflict,
+                format!(
+                    "`{}` is `{}` here but `{}` at {}; `{}` always wins",
+                    rule.pattern,
+                    rule.decision.as_str(),
+                    earlier.decision.as_str(),
+                    display_location(earlier.location.as_ref()),
+                    rule.decision.max(earlier.decision).as_str()
+                ),
+            )
+        };
+        return Some(LintFinding {
+            kind,
+            location: rule.location.clone(),
+            message,
+        });
+    }
+
+    let broader = rules.iter().enumerate().find(|(other_index, other)| {
+        *other_index != index
+            && other.pattern != rule.pattern
+            && other.decision >= rule.decision
+            && pattern_covers(&other.pattern, &rule.pattern)
+    });
+    let (_, broader) = broader?;
+    let message = if broader.decision > rule.decision {
+        format!(
+            "every command matching `{}` also matches `{}` at {}, so this `{}` rule is always overridden by `{}`",
+            rule.pattern,
+            broader.pattern,
+            display_location(broader.location.as_ref()),
+            rule.decision.as_str(),
+            broader.decision.as_str()
+        )
+    } else {
+        format!(
+            "every command matching `{}` also matches `{}` at {} with the same decision",
+            rule.pattern,
+            broader.pattern,
+            display_location(broader.location.as_ref())
+        )
+    };
+    Some(LintFinding {
+        kind: LintFindingKind::Shadowed,
+        location: rule.location.clone(),
+        message,
+    })
+}
+
+/// Returns whether `broad` matches every command that `narrow` matches. This is
+/// conservative: it may return `false` for patterns that do overlap, but never
+/// `true` for patterns that do not.
+fn pattern_covers(broad: &PrefixPattern, narrow: &PrefixPattern) -> bool {
+    if broad.first != narrow.first {
+        return false;
+    }
+
+    for (index, broad_token) in broad.rest.iter().enumerate() {
+        let narrow_rest = narrow.rest.get(index..).unwrap_or_default();
+        match broad_token {
+            PatternToken::AnyRemaining(matchers) => {
+                return narrow_rest
+                    .iter()
+                    .all(|narrow_token| remaining_token_covered(matchers, narrow_token));
+            }
+            PatternToken::WithoutFlags(_) => {
+                if narrow_rest.first() != Some(broad_token) {
+                    return false;
+                }
+            }
+            PatternToken::Single(_) | PatternToken::Alts(_) | PatternToken::Matchers(_) => {
+                let Some(narrow_token) = narrow_rest.first() else {
+                    return false;
+                };
+                if !token_covers(broad_token, narrow_token) {
+                    return false;
+                }
+            }
+        }
+    }
+    true
+}
+
+fn remaining_token_covered(matchers: &[ArgMatcher], narrow_token: &PatternToken) -> bool {
+    if matchers.is_empty() {
+        return true;
+    }
+    match narrow_token {
+        PatternToken::AnyRemaining(narrow_matchers) => {
+            !narrow_matchers.is_empty()
+                && narrow_matchers
+                    .iter()
+                    .all(|narrow| matcher_covered(matchers, narrow))
+        }
+        PatternToken::WithoutFlags(_) => true,
+        _ => token_covers(&PatternToken::Matchers(matchers.to_vec()), narrow_token),
+    }
+}
+
+/// Returns whether the single-token `broad` accepts every token `narrow` accepts.
+fn token_covers(broad: &PatternToken, narrow: &PatternToken) -> bool {
+    let broad_matchers = single_token_matchers(broad);
+    match narrow {
+        PatternToken::Single(_) | PatternToken::Alts(_) | PatternToken::Matchers(_) => {
+            single_token_matchers(narrow)
+                .iter()
+                .all(|narrow| matcher_covered(&broad_matchers, narrow))
+        }
+        PatternToken::AnyRemaining(_) | PatternToken::WithoutFlags(_) => false,
+    }
+}
+
+fn matcher_covered(broad: &[ArgMatcher], narrow: &ArgMatcher) -> bool {
+    broad.iter().any(|matcher| match narrow {
+        ArgMatcher::Literal(value) => {
+            matches!(
+                matcher,
+                ArgMatcher::Literal(_) | ArgMatcher::Glob(_) | ArgMatcher::Regex(_)
+            ) && matcher.matches(value, /*cwd*/ None)
+        }
+        _ => matcher == narrow,
+    })
+}
+
+fn single_token_matchers(token: &PatternToken) -> Vec<ArgMatcher> {
+    match token {
+        PatternToken::Single(value) => vec![ArgMatcher::Literal(value.clone())],
+        PatternToken::Alts(values) => values.iter().cloned().map(ArgMatcher::Literal).collect(),
+        PatternToken::Matchers(matchers) => matchers.clone(),
+        PatternToken::AnyRemaining(_) | PatternToken::WithoutFlags(_) => Vec::new(),
+    }
+}
+
+pub(crate) fn display_location(location: Option<&ErrorLocation>) -> String {
+    location.map_or_else(|| "<unknown location>".to_string(), ToString::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Decision;
+    use crate::PolicyParser;
+    use pretty_assertions::assert_eq;
+
+    fn lint(policy_src: &str) -> Vec<(LintFindingKind, usize)> {
+        let mut parser = PolicyParser::new();
+        parser
+            .parse("test.rules", policy_src)
+            .expect("policy should parse");
+        lint_policy(&parser.build())
+            .into_iter()
+            .map(|finding| {
+                let line = finding
+                    .location
+                    .expect("parsed rules have locations")
+                    .range
+                    .start
+                    .line;
+                (finding.kind, line)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn reports_duplicates_and_conflicts() {
+        assert_eq!(
+            lint(
+                r#"
+prefix_rule(pattern = ["git", "status"])
+prefix_rule(pattern = ["git", "status"])
+prefix_rule(pattern = ["git", "status"], decision = "prompt")
+"#
+            ),
+            vec![
+                (LintFindingKind::Duplicate, 3),
+                (LintFindingKind::Conflict, 4),
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_rules_shadowed_by_broader_rules() {
+        assert_eq!(
+            lint(
+                r#"
+prefix_rule(pattern = ["git", "status"])
+prefix_rule(pattern = ["git"], decision = "prompt")
+prefix_rule(pattern = ["git", "push", any_remaining()], decision = "forbidden")
+prefix_rule(pattern = ["rm", glob("-*")], decision = "prompt")
+prefix_rule(pattern = ["rm", ["-r", "-f"]], decision = "prompt")
+"#
+            ),
+            vec![
+                (LintFindingKind::Shadowed, 2),
+                (LintFindingKind::Shadowed, 6),
+            ]
+        );
+    }
+
+    #[test]
+    fn stricter_narrow_rules_are_not_shadowed() {
+        for decision in [Decision::Allow, Decision::Prompt, Decision::Forbidden] {
+            let policy_src = format!(
+                r#"
+prefix_rule(pattern = ["git"], decision = "{}")
+prefix_rule(pattern = ["git", "push", without_flags(["-f"])], decision = "forbidden")
+prefix_rule(pattern = ["ls", workspace_path()])
+prefix_rule(pattern = ["ls", glob("*")], decision = "prompt")
+"#,
+                decision.as_str()
+            );
+            let expected = if decision == Decision::Forbidden {
+                vec![(LintFindingKind::Shadowed, 3)]
+            } else {
+                Vec::new()
+            };
+            assert_eq!(lint(&policy_src), expected, "decision={decision:?}");
+        }
+    }
+}
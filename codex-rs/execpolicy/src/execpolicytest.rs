@@ -0,0 +1,217 @@
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+use clap::Parser;
+
+use crate::Decision;
+use crate::MatchOptions;
+use crate::Policy;
+use crate::execpolicycheck::MatchArgs;
+use crate::execpolicycheck::load_policies;
+
+/// Arguments for running a corpus of expected decisions against execpolicy files.
+#[derive(Debug, Parser, Clone)]
+pub struct ExecPolicyTestCommand {
+    /// Paths to execpolicy rule files to evaluate (repeatable).
+    #[arg(short = 'r', long = "rules", value_name = "PATH", required = true)]
+    pub rules: Vec<PathBuf>,
+
+    /// Case files to run (repeatable). Each non-empty line that does not start
+    /// with `#` is `<expected> <command>`, where `<expected>` is `allow`,
+    /// `prompt`, `forbidden`, or `none` (no rule matches) and `<command>` is
+    /// split like a shell would.
+    #[arg(short = 'c', long = "cases", value_name = "PATH", required = true)]
+    pub cases: Vec<PathBuf>,
+
+    #[command(flatten)]
+    pub match_args: MatchArgs,
+}
+
+impl ExecPolicyTestCommand {
+    /// Run every case, print failures, and fail if any case did not pass.
+    pub fn run(&self) -> Result<()> {
+        let policy = load_policies(&self.rules)?;
+        let options = self.match_args.match_options()?;
+
+        let mut cases = Vec::new();
+        for path in &self.cases {
+            cases.extend(load_cases(path)?);
+        }
+        let failures = run_cases(&policy, &cases, &options);
+        for failure in &failures {
+            println!("{failure}");
+        }
+        println!(
+            "{} passed; {} failed",
+            cases.len() - failures.len(),
+            failures.len()
+        );
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            anyhow::bail!("{} of {} case(s) failed", failures.len(), cases.len())
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PolicyTestCase {
+    /// `path:line` of the case in its case file.
+    pub source: String,
+    pub command: Vec<String>,
+    /// `None` when no rule should match.
+    pub expected: Option<Decision>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PolicyTestFailure {
+    pub case: PolicyTestCase,
+    pub actual: Option<Decision>,
+}
+
+impl fmt::Display for PolicyTestFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let command = shlex::try_join(self.case.command.iter().map(String::as_str))
+            .unwrap_or_else(|_| self.case.command.join(" "));
+        write!(
+            f,
+            "{}: `{command}`: expected {}, got {}",
+            self.case.source,
+            render_expected(self.case.expected),
+            render_expected(self.actual)
+        )
+    }
+}
+
+pub fn load_cases(path: &Path) -> Result<Vec<PolicyTestCase>> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("failed to read cases at {}", path.display()))?;
+    parse_cases(&path.to_string_lossy(), &contents)
+}
+
+pub fn parse_cases(identifier: &str, contents: &str) -> Result<Vec<PolicyTestCase>> {
+    let mut cases = Vec::new();
+    for (index, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let source = format!("{identifier}:{}", index + 1);
+        let (expected, command) = line
+            .split_once(char::is_whitespace)
+            .with_context(|| format!("{source}: expected `<decision> <command>`"))?;
+        let expected = match expected {
+            "none" => None,
+            other => Some(Decision::parse(other).with_context(|| {
+                format!("{source}: expected decision to be allow, prompt, forbidden, or none")
+            })?),
+        };
+        let command = shlex::split(command.trim())
+            .filter(|command| !command.is_empty())
+            .with_context(|| format!("{source}: command has invalid shell syntax"))?;
+        cases.push(PolicyTestCase {
+            source,
+            command,
+            expected,
+        });
+    }
+    Ok(cases)
+}
+
+/// Evaluates each case and returns the ones whose decision differs from the
+/// expectation.
+pub fn run_cases(
+    policy: &Policy,
+    cases: &[PolicyTestCase],
+    options: &MatchOptions,
+) -> Vec<PolicyTestFailure> {
+    cases
+        .iter()
+        .filter_map(|case| {
+            let actual = policy
+                .matches_for_command_with_options(
+                    &case.command,
+                    /*heuristics_fallback*/ None,
+                    options,
+                )
+                .iter()
+                .map(crate::RuleMatch::decision)
+                .max();
+            (actual != case.expected).then(|| PolicyTestFailure {
+                case: case.clone(),
+                actual,
+            })
+        })
+        .collect()
+}
+
+fn render_expected(decision: Option<Decision>) -> &'static str {
+    decision.map_or("none", Decision::as_str)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PolicyParser;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn reports_cases_with_unexpected_decisions() {
+        let mut parser = PolicyParser::new();
+        parser
+            .parse(
+                "team.rules",
+                r#"
+prefix_rule(pattern = ["git", "status"])
+prefix_rule(pattern = ["git", "push"], decision = "forbidden")
+"#,
+            )
+            .expect("policy should parse");
+        let policy = parser.build();
+        let cases = parse_cases(
+            "cases.txt",
+            "# expected  command
+allow      git status --short
+forbidden  git push origin main
+
+prompt     git push --force
+none       'git' commit -m 'a message'
+none       git status
+",
+        )
+        .expect("cases should parse");
+
+        let failures = run_cases(&policy, &cases, &MatchOptions::default());
+        assert_eq!(
+            failures.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            vec![
+                "cases.txt:5: `git push --force`: expected prompt, got forbidden".to_string(),
+                "cases.txt:7: `git status`: expected none, got allow".to_string(),
+            ]
+        );
+        assert_eq!(cases[3].command, ["git", "commit", "-m", "a message"]);
+    }
+
+    #[test]
+    fn rejects_malformed_cases() {
+        for (contents, expected) in [
+            ("allow", "cases.txt:1: expected `<decision> <command>`"),
+            (
+                "maybe git status",
+                "cases.txt:1: expected decision to be allow, prompt, forbidden, or none",
+            ),
+            (
+                "allow git 'unterminated",
+                "cases.txt:1: command has invalid shell syntax",
+            ),
+        ] {
+            let err = parse_cases("cases.txt", contents).expect_err("case should be rejected");
+            assert_eq!(err.to_string(), expected);
+        }
+    }
+}
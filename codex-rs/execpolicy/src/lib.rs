@@ -1,7 +1,11 @@
 pub(crate) mod amend;
+pub mod arg_matcher;
 pub(crate) mod decision;
 pub(crate) mod error;
 pub(crate) mod execpolicycheck;
+pub(crate) mod execpolicyexplain;
+pub(crate) mod execpolicylint;
+pub(crate) mod execpolicytest;
 mod executable_name;
 pub(crate) mod parser;
 pub(crate) mod policy;
@@ -10,6 +14,7 @@ pub mod rule;
 pub use amend::AmendError;
 pub use amend::blocking_append_allow_prefix_rule;
 pub use amend::blocking_append_network_rule;
+pub use arg_matcher::ArgMatcher;
 pub use decision::Decision;
 pub use error::Error;
 pub use error::ErrorLocation;
@@ -17,6 +22,14 @@ pub use error::Result;
 pub use error::TextPosition;
 pub use error::TextRange;
 pub use execpolicycheck::ExecPolicyCheckCommand;
+pub use execpolicycheck::MatchArgs;
+pub use execpolicyexplain::ExecPolicyExplainCommand;
+pub use execpolicyexplain::explain_command;
+pub use execpolicylint::ExecPolicyLintCommand;
+pub use execpolicylint::LintFinding;
+pub use execpolicylint::LintFindingKind;
+pub use execpolicylint::lint_policy;
+pub use execpolicytest::ExecPolicyTestCommand;
 pub use parser::PolicyParser;
 pub use policy::Evaluation;
 pub use policy::MatchOptions;
@@ -1,6 +1,9 @@
 use anyhow::Result;
 use clap::Parser;
 use codex_execpolicy::ExecPolicyCheckCommand;
+use codex_execpolicy::ExecPolicyExplainCommand;
+use codex_execpolicy::ExecPolicyLintCommand;
+use codex_execpolicy::ExecPolicyTestCommand;
 
 /// CLI for evaluating exec policies
 #[derive(Parser)]
@@ -8,11 +11,20 @@ use codex_execpolicy::ExecPolicyCheckCommand;
 enum Cli {
     /// Evaluate a command against a policy.
     Check(ExecPolicyCheckCommand),
+    /// Report duplicate, conflicting, and shadowed rules.
+    Lint(ExecPolicyLintCommand),
+    /// Show which rules match a command and which one decides it.
+    Explain(ExecPolicyExplainCommand),
+    /// Run a file of expected decisions against a policy.
+    Test(ExecPolicyTestCommand),
 }
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
     match cli {
         Cli::Check(cmd) => cmd.run(),
+        Cli::Lint(cmd) => cmd.run(),
+        Cli::Explain(cmd) => cmd.run(),
+        Cli::Test(cmd) => cmd.run(),
     }
 }
@@ -1,3 +1,4 @@
+use allocative::Allocative;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use multimap::MultiMap;
 use starlark::any::ProvidesStaticType;
@@ -8,16 +9,24 @@ use starlark::eval::Evaluator;
 use starlark::starlark_module;
 use starlark::syntax::AstModule;
 use starlark::syntax::Dialect;
+use starlark::values::AllocValue;
+use starlark::values::Heap;
+use starlark::values::NoSerialize;
+use starlark::values::StarlarkValue;
 use starlark::values::Value;
+use starlark::values::ValueLike;
 use starlark::values::list::ListRef;
 use starlark::values::list::UnpackList;
 use starlark::values::none::NoneType;
+use starlark::values::starlark_value;
 use std::cell::RefCell;
 use std::cell::RefMut;
 use std::collections::HashMap;
+use std::fmt;
 use std::path::Path;
 use std::sync::Arc;
 
+use crate::arg_matcher::ArgMatcher;
 use crate::decision::Decision;
 use crate::error::Error;
 use crate::error::ErrorLocation;
@@ -167,46 +176,73 @@ struct PendingExampleValidation {
     location: Option<ErrorLocation>,
 }
 
+/// Starlark value produced by the `glob()`, `regex()`, `workspace_path()`,
+/// `any_remaining()`, and `without_flags()` builtins.
+#[derive(Debug, NoSerialize, ProvidesStaticType, Allocative)]
+struct StarlarkPatternToken(#[allocative(skip)] PatternToken);
+
+impl fmt::Display for StarlarkPatternToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[starlark_value(type = "PatternToken")]
+impl<'v> StarlarkValue<'v> for StarlarkPatternToken {}
+
+impl<'v> AllocValue<'v> for StarlarkPatternToken {
+    fn alloc_value(self, heap: &'v Heap) -> Value<'v> {
+        heap.alloc_simple(self)
+    }
+}
+
 fn parse_pattern<'v>(pattern: UnpackList<Value<'v>>) -> Result<Vec<PatternToken>> {
     let tokens: Vec<PatternToken> = pattern
         .items
         .into_iter()
         .map(parse_pattern_token)
         .collect::<Result<_>>()?;
-    if tokens.is_empty() {
-        Err(Error::InvalidPattern("pattern cannot be empty".to_string()))
-    } else {
-        Ok(tokens)
+    match tokens.split_first() {
+        None => Err(Error::InvalidPattern("pattern cannot be empty".to_string())),
+        Some((first, _)) if first.alternatives().is_empty() => Err(Error::InvalidPattern(
+            "first pattern element must be a string or list of strings".to_string(),
+        )),
+        Some((_, rest))
+            if rest
+                .iter()
+                .rev()
+                .skip(1)
+                .any(|token| matches!(token, PatternToken::AnyRemaining(_))) =>
+        {
+            Err(Error::InvalidPattern(
+                "any_remaining() must be the last pattern element".to_string(),
+            ))
+        }
+        Some(_) => Ok(tokens),
     }
 }
 
 fn parse_pattern_token<'v>(value: Value<'v>) -> Result<PatternToken> {
     if let Some(s) = value.unpack_str() {
         Ok(PatternToken::Single(s.to_string()))
+    } else if let Some(token) = value.downcast_ref::<StarlarkPatternToken>() {
+        Ok(token.0.clone())
     } else if let Some(list) = ListRef::from_value(value) {
-        let tokens: Vec<String> = list
-            .content()
+        let matchers = parse_arg_matchers(list)?;
+        if let Some(tokens) = matchers
             .iter()
-            .map(|value| {
-                value
-                    .unpack_str()
-                    .ok_or_else(|| {
-                        Error::InvalidPattern(format!(
-                            "pattern alternative must be a string (got {})",
-                            value.get_type()
-                        ))
-                    })
-                    .map(str::to_string)
+            .map(|matcher| match matcher {
+                ArgMatcher::Literal(value) => Some(value.clone()),
+                _ => None,
             })
-            .collect::<Result<_>>()?;
-
-        match tokens.as_slice() {
-            [] => Err(Error::InvalidPattern(
-                "pattern alternatives cannot be empty".to_string(),
-            )),
-            [single] => Ok(PatternToken::Single(single.clone())),
-            _ => Ok(PatternToken::Alts(tokens)),
+            .collect::<Option<Vec<String>>>()
+        {
+            return match tokens.as_slice() {
+                [single] => Ok(PatternToken::Single(single.clone())),
+                _ => Ok(PatternToken::Alts(tokens)),
+            };
         }
+        Ok(PatternToken::Matchers(matchers))
     } else {
         Err(Error::InvalidPattern(format!(
             "pattern element must be a string or list of strings (got {})",
@@ -215,6 +251,60 @@ fn parse_pattern_token<'v>(value: Value<'v>) -> Result<PatternToken> {
     }
 }
 
+/// Parses a non-empty list of alternatives, each a string or a single-token
+/// matcher such as `glob(...)`.
+fn parse_arg_matchers(list: &ListRef) -> Result<Vec<ArgMatcher>> {
+    let matchers: Vec<ArgMatcher> = list
+        .content()
+        .iter()
+        .map(|value| parse_arg_matcher(*value))
+        .collect::<Result<Vec<_>>>()?
+        .into_iter()
+        .flatten()
+        .collect();
+    if matchers.is_empty() {
+        Err(Error::InvalidPattern(
+            "pattern alternatives cannot be empty".to_string(),
+        ))
+    } else {
+        Ok(matchers)
+    }
+}
+
+fn parse_arg_matcher<'v>(value: Value<'v>) -> Result<Vec<ArgMatcher>> {
+    if let Some(s) = value.unpack_str() {
+        return Ok(vec![ArgMatcher::Literal(s.to_string())]);
+    }
+    match value.downcast_ref::<StarlarkPatternToken>() {
+        Some(StarlarkPatternToken(PatternToken::Matchers(matchers))) => Ok(matchers.clone()),
+        Some(token) => Err(Error::InvalidPattern(format!(
+            "{token} cannot be used as a single-token alternative"
+        ))),
+        None => Err(Error::InvalidPattern(format!(
+            "pattern alternative must be a string or matcher (got {})",
+            value.get_type()
+        ))),
+    }
+}
+
+fn parse_flags(flags: UnpackList<&str>) -> Result<Vec<String>> {
+    let flags: Vec<String> = flags.items.into_iter().map(str::to_string).collect();
+    if flags.is_empty() {
+        return Err(Error::InvalidPattern(
+            "without_flags() requires at least one flag".to_string(),
+        ));
+    }
+    if let Some(flag) = flags
+        .iter()
+        .find(|flag| !flag.starts_with('-') || flag.trim_start_matches('-').is_empty())
+    {
+        return Err(Error::InvalidPattern(format!(
+            "without_flags() entries must be flags such as `-f` or `--force` (got `{flag}`)"
+        )));
+    }
+    Ok(flags)
+}
+
 fn parse_examples<'v>(examples: UnpackList<Value<'v>>) -> Result<Vec<Vec<String>>> {
     examples.items.into_iter().map(parse_example).collect()
 }
@@ -397,6 +487,7 @@ fn policy_builtins(builder: &mut GlobalsBuilder) {
                     },
                     decision,
                     justification: justification.clone(),
+                    location: location.clone(),
                 }) as RuleRef
             })
             .collect();
@@ -406,11 +497,61 @@ fn policy_builtins(builder: &mut GlobalsBuilder) {
         Ok(NoneType)
     }
 
+    fn glob<'v>(pattern: &'v str, eval: &mut Evaluator<'v, '_, '_>) -> anyhow::Result<Value<'v>> {
+        let matcher = ArgMatcher::glob(pattern)?;
+        Ok(eval
+            .heap()
+            .alloc(StarlarkPatternToken(PatternToken::Matchers(vec![matcher]))))
+    }
+
+    fn regex<'v>(pattern: &'v str, eval: &mut Evaluator<'v, '_, '_>) -> anyhow::Result<Value<'v>> {
+        let matcher = ArgMatcher::regex(pattern)?;
+        Ok(eval
+            .heap()
+            .alloc(StarlarkPatternToken(PatternToken::Matchers(vec![matcher]))))
+    }
+
+    fn workspace_path<'v>(eval: &mut Evaluator<'v, '_, '_>) -> anyhow::Result<Value<'v>> {
+        Ok(eval
+            .heap()
+            .alloc(StarlarkPatternToken(PatternToken::Matchers(vec![
+                ArgMatcher::WorkspacePath,
+            ]))))
+    }
+
+    fn any_remaining<'v>(
+        matchers: Option<Value<'v>>,
+        eval: &mut Evaluator<'v, '_, '_>,
+    ) -> anyhow::Result<Value<'v>> {
+        let matchers = match matchers {
+            Some(value) => match ListRef::from_value(value) {
+                Some(list) => parse_arg_matchers(list)?,
+                None => parse_arg_matcher(value)?,
+            },
+            None => Vec::new(),
+        };
+        Ok(eval
+            .heap()
+            .alloc(StarlarkPatternToken(PatternToken::AnyRemaining(matchers))))
+    }
+
+    fn without_flags<'v>(
+        flags: UnpackList<&'v str>,
+        eval: &mut Evaluator<'v, '_, '_>,
+    ) -> anyhow::Result<Value<'v>> {
+        let flags = parse_flags(flags)?;
+        Ok(eval
+            .heap()
+            .alloc(StarlarkPatternToken(PatternToken::WithoutFlags(flags))))
+    }
+
     fn network_rule<'v>(
         host: &'v str,
         protocol: &'v str,
         decision: &'v str,
         justification: Option<&'v str>,
+        path: Option<&'v str>,
+        methods: Option<UnpackList<&'v str>>,
         eval: &mut Evaluator<'v, '_, '_>,
     ) -> anyhow::Result<NoneType> {
         let protocol = NetworkRuleProtocol::parse(protocol)?;
@@ -422,6 +563,23 @@ fn policy_builtins(builder: &mut GlobalsBuilder) {
             Some(raw) => Some(raw.to_string()),
             None => None,
         };
+        let path = path
+            .map(crate::rule::normalize_network_rule_path)
+            .transpose()?;
+        let methods = crate::rule::normalize_network_rule_methods(
+            methods.into_iter().flat_map(|methods| methods.items),
+        )?;
+        if (path.is_some() || !methods.is_empty())
+            && !matches!(
+                protocol,
+                NetworkRuleProtocol::Http | NetworkRuleProtocol::Https
+            )
+        {
+            return Err(Error::InvalidRule(
+                "network_rule path and methods require protocol http or https".to_string(),
+            )
+            .into());
+        }
 
         let mut builder = policy_builder(eval);
         builder.add_network_rule(NetworkRule {
@@ -429,6 +587,8 @@ fn policy_builtins(builder: &mut GlobalsBuilder) {
             protocol,
             decision,
             justification,
+            path,
+            methods,
         });
         Ok(NoneType)
     }
@@ -22,6 +22,9 @@ type HeuristicsFallback<'a> = Option<&'a dyn Fn(&[String]) -> Decision>;
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct MatchOptions {
     pub resolve_host_executables: bool,
+    /// Working directory of the command. `workspace_path()` pattern tokens
+    /// only match when this is set.
+    pub cwd: Option<AbsolutePathBuf>,
 }
 
 #[derive(Clone, Debug)]
@@ -78,7 +81,7 @@ impl Policy {
 
                 let mut prefix = Vec::with_capacity(prefix_rule.pattern.rest.len() + 1);
                 prefix.push(prefix_rule.pattern.first.as_ref().to_string());
-                prefix.extend(prefix_rule.pattern.rest.iter().map(render_pattern_token));
+                prefix.extend(prefix_rule.pattern.rest.iter().map(ToString::to_string));
                 prefixes.push(prefix);
             }
         }
@@ -104,6 +107,7 @@ impl Policy {
             },
             decision,
             justification: None,
+            location: None,
         });
 
         self.rules_by_program.insert(first_token.clone(), rule);
@@ -130,6 +134,8 @@ impl Policy {
             protocol,
             decision,
             justification,
+            path: None,
+            methods: Vec::new(),
         });
         Ok(())
     }
@@ -168,7 +174,11 @@ impl Policy {
         let mut allowed = Vec::new();
         let mut denied = Vec::new();
 
-        for rule in &self.network_rules {
+        for rule in self
+            .network_rules
+            .iter()
+            .filter(|rule| !rule.is_request_rule())
+        {
             match rule.decision {
                 Decision::Allow => {
                     denied.retain(|entry| entry != &rule.host);
@@ -271,16 +281,11 @@ impl Policy {
         heuristics_fallback: HeuristicsFallback<'_>,
         options: &MatchOptions,
     ) -> Vec<RuleMatch> {
-        let matched_rules = self
-            .match_exact_rules(cmd)
-            .filter(|matched_rules| !matched_rules.is_empty())
-            .or_else(|| {
-                options
-                    .resolve_host_executables
-                    .then(|| self.match_host_executable_rules(cmd))
-                    .filter(|matched_rules| !matched_rules.is_empty())
-            })
-            .unwrap_or_default();
+        let matched_rules: Vec<RuleMatch> = self
+            .matching_rules_with_options(cmd, options)
+            .into_iter()
+            .map(|(_rule, rule_match)| rule_match)
+            .collect();
 
         if matched_rules.is_empty()
             && let Some(heuristics_fallback) = heuristics_fallback
@@ -294,17 +299,46 @@ impl Policy {
         }
     }
 
-    fn match_exact_rules(&self, cmd: &[String]) -> Option<Vec<RuleMatch>> {
-        let first = cmd.first()?;
-        Some(
-            self.rules_by_program
-                .get_vec(first)
-                .map(|rules| rules.iter().filter_map(|rule| rule.matches(cmd)).collect())
-                .unwrap_or_default(),
-        )
+    /// Returns each rule that matches `cmd` alongside its match, without a
+    /// heuristics fallback.
+    pub fn matching_rules_with_options(
+        &self,
+        cmd: &[String],
+        options: &MatchOptions,
+    ) -> Vec<(RuleRef, RuleMatch)> {
+        let matched_rules = self.match_exact_rules(cmd, options);
+        if matched_rules.is_empty() && options.resolve_host_executables {
+            self.match_host_executable_rules(cmd, options)
+        } else {
+            matched_rules
+        }
+    }
+
+    fn match_exact_rules(
+        &self,
+        cmd: &[String],
+        options: &MatchOptions,
+    ) -> Vec<(RuleRef, RuleMatch)> {
+        let Some(rules) = cmd
+            .first()
+            .and_then(|first| self.rules_by_program.get_vec(first))
+        else {
+            return Vec::new();
+        };
+        rules
+            .iter()
+            .filter_map(|rule| {
+                rule.matches(cmd, options)
+                    .map(|rule_match| (rule.clone(), rule_match))
+            })
+            .collect()
     }
 
-    fn match_host_executable_rules(&self, cmd: &[String]) -> Vec<RuleMatch> {
+    fn match_host_executable_rules(
+        &self,
+        cmd: &[String],
+        options: &MatchOptions,
+    ) -> Vec<(RuleRef, RuleMatch)> {
         let Some(first) = cmd.first() else {
             return Vec::new();
         };
@@ -328,8 +362,10 @@ impl Policy {
             .collect::<Vec<_>>();
         rules
             .iter()
-            .filter_map(|rule| rule.matches(&basename_command))
-            .map(|rule_match| rule_match.with_resolved_program(&program))
+            .filter_map(|rule| {
+                rule.matches(&basename_command, options)
+                    .map(|rule_match| (rule.clone(), rule_match.with_resolved_program(&program)))
+            })
             .collect()
     }
 }
@@ -339,13 +375,6 @@ fn upsert_domain(entries: &mut Vec<String>, host: &str) {
     entries.push(host.to_string());
 }
 
-fn render_pattern_token(token: &PatternToken) -> String {
-    match token {
-        PatternToken::Single(value) => value.clone(),
-        PatternToken::Alts(alternatives) => format!("[{}]", alternatives.join("|")),
-    }
-}
-
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Evaluation {
@@ -1,5 +1,8 @@
+use crate::arg_matcher::ArgMatcher;
+use crate::arg_matcher::arg_sets_flag;
 use crate::decision::Decision;
 use crate::error::Error;
+use crate::error::ErrorLocation;
 use crate::error::Result;
 use crate::policy::MatchOptions;
 use crate::policy::Policy;
@@ -8,6 +11,7 @@ use serde::Deserialize;
 use serde::Serialize;
 use shlex::try_join;
 use std::any::Any;
+use std::fmt;
 use std::fmt::Debug;
 use std::sync::Arc;
 
@@ -16,24 +20,54 @@ use std::sync::Arc;
 pub enum PatternToken {
     Single(String),
     Alts(Vec<String>),
+    /// Matches one token that satisfies any of the argument matchers.
+    Matchers(Vec<ArgMatcher>),
+    /// Consumes every remaining token. When `matchers` is non-empty, each
+    /// remaining token must satisfy one of them.
+    AnyRemaining(Vec<ArgMatcher>),
+    /// Consumes no tokens; fails if any later token (up to a `--` separator)
+    /// sets one of these flags.
+    WithoutFlags(Vec<String>),
 }
 
 impl PatternToken {
-    fn matches(&self, token: &str) -> bool {
+    /// Literal alternatives for this token. Empty for tokens that are not
+    /// plain strings.
+    pub fn alternatives(&self) -> &[String] {
         match self {
-            Self::Single(expected) => expected == token,
-            Self::Alts(alternatives) => alternatives.iter().any(|alt| alt == token),
+            Self::Single(expected) => std::slice::from_ref(expected),
+            Self::Alts(alternatives) => alternatives,
+            Self::Matchers(_) | Self::AnyRemaining(_) | Self::WithoutFlags(_) => &[],
         }
     }
+}
 
-    pub fn alternatives(&self) -> &[String] {
+impl fmt::Display for PatternToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::Single(expected) => std::slice::from_ref(expected),
-            Self::Alts(alternatives) => alternatives,
+            Self::Single(value) => f.write_str(value),
+            Self::Alts(alternatives) => write!(f, "[{}]", alternatives.join("|")),
+            Self::Matchers(matchers) => match matchers.as_slice() {
+                [single] => write!(f, "{single}"),
+                _ => write!(f, "[{}]", join_matchers(matchers)),
+            },
+            Self::AnyRemaining(matchers) if matchers.is_empty() => f.write_str("any_remaining()"),
+            Self::AnyRemaining(matchers) => {
+                write!(f, "any_remaining([{}])", join_matchers(matchers))
+            }
+            Self::WithoutFlags(flags) => write!(f, "without_flags([{}])", flags.join("|")),
         }
     }
 }
 
+fn join_matchers(matchers: &[ArgMatcher]) -> String {
+    matchers
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("|")
+}
+
 /// Prefix matcher for commands with support for alternative match tokens.
 /// First token is fixed since we key by the first token in policy.
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -42,20 +76,70 @@ pub struct PrefixPattern {
     pub rest: Arc<[PatternToken]>,
 }
 
+impl fmt::Display for PrefixPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.first)?;
+        for token in self.rest.iter() {
+            write!(f, " {token}")?;
+        }
+        Ok(())
+    }
+}
+
 impl PrefixPattern {
-    pub fn matches_prefix(&self, cmd: &[String]) -> Option<Vec<String>> {
-        let pattern_length = self.rest.len() + 1;
-        if cmd.len() < pattern_length || cmd[0] != self.first.as_ref() {
+    pub fn matches_prefix(&self, cmd: &[String], options: &MatchOptions) -> Option<Vec<String>> {
+        if cmd.first().map(String::as_str) != Some(self.first.as_ref()) {
             return None;
         }
 
-        for (pattern_token, cmd_token) in self.rest.iter().zip(&cmd[1..pattern_length]) {
-            if !pattern_token.matches(cmd_token) {
-                return None;
+        let cwd = options.cwd.as_ref();
+        let mut matched_length = 1;
+        for pattern_token in self.rest.iter() {
+            let remaining = &cmd[matched_length..];
+            match pattern_token {
+                PatternToken::Single(expected) => {
+                    if remaining.first() != Some(expected) {
+                        return None;
+                    }
+                    matched_length += 1;
+                }
+                PatternToken::Alts(alternatives) => {
+                    let token = remaining.first()?;
+                    if !alternatives.contains(token) {
+                        return None;
+                    }
+                    matched_length += 1;
+                }
+                PatternToken::Matchers(matchers) => {
+                    let token = remaining.first()?;
+                    if !matchers.iter().any(|matcher| matcher.matches(token, cwd)) {
+                        return None;
+                    }
+                    matched_length += 1;
+                }
+                PatternToken::AnyRemaining(matchers) => {
+                    if !matchers.is_empty()
+                        && !remaining
+                            .iter()
+                            .all(|token| matchers.iter().any(|matcher| matcher.matches(token, cwd)))
+                    {
+                        return None;
+                    }
+                    matched_length = cmd.len();
+                }
+                PatternToken::WithoutFlags(flags) => {
+                    if remaining
+                        .iter()
+                        .take_while(|token| token.as_str() != "--")
+                        .any(|token| flags.iter().any(|flag| arg_sets_flag(token, flag)))
+                    {
+                        return None;
+                    }
+                }
             }
         }
 
-        Some(cmd[..pattern_length].to_vec())
+        Some(cmd[..matched_length].to_vec())
     }
 }
 
@@ -107,13 +191,27 @@ impl RuleMatch {
     }
 }
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug)]
 pub struct PrefixRule {
     pub pattern: PrefixPattern,
     pub decision: Decision,
     pub justification: Option<String>,
+    /// Where the rule was declared, when it was parsed from a policy file.
+    pub location: Option<ErrorLocation>,
 }
 
+/// Rules compare equal when they match the same commands with the same
+/// outcome, wherever they were declared.
+impl PartialEq for PrefixRule {
+    fn eq(&self, other: &Self) -> bool {
+        self.pattern == other.pattern
+            && self.decision == other.decision
+            && self.justification == other.justification
+    }
+}
+
+impl Eq for PrefixRule {}
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum NetworkRuleProtocol {
     Http,
@@ -151,6 +249,51 @@ pub struct NetworkRule {
     pub protocol: NetworkRuleProtocol,
     pub decision: Decision,
     pub justification: Option<String>,
+    /// Path glob the rule is restricted to, e.g. `/*/archive/*`.
+    pub path: Option<String>,
+    /// Upper-case HTTP methods the rule is restricted to; empty means every method.
+    pub methods: Vec<String>,
+}
+
+impl NetworkRule {
+    /// Whether the rule targets individual HTTP requests rather than the whole host.
+    pub fn is_request_rule(&self) -> bool {
+        self.path.is_some() || !self.methods.is_empty()
+    }
+}
+
+pub(crate) fn normalize_network_rule_path(raw: &str) -> Result<String> {
+    let path = raw.trim();
+    if !path.starts_with('/') {
+        return Err(Error::InvalidRule(format!(
+            "network_rule path must start with `/` (got {raw})"
+        )));
+    }
+    if path.contains('?') || path.contains('#') || path.chars().any(char::is_whitespace) {
+        return Err(Error::InvalidRule(format!(
+            "network_rule path must be a URL path without query, fragment or whitespace (got {raw})"
+        )));
+    }
+    Ok(path.to_string())
+}
+
+pub(crate) fn normalize_network_rule_methods<'a>(
+    raw: impl IntoIterator<Item = &'a str>,
+) -> Result<Vec<String>> {
+    let mut methods: Vec<String> = Vec::new();
+    for method in raw {
+        let method = method.trim();
+        if method.is_empty() || !method.bytes().all(|b| b.is_ascii_alphabetic()) {
+            return Err(Error::InvalidRule(format!(
+                "network_rule methods must be HTTP method names (got {method:?})"
+            )));
+        }
+        let method = method.to_ascii_uppercase();
+        if !methods.contains(&method) {
+            methods.push(method);
+        }
+    }
+    Ok(methods)
 }
 
 pub(crate) fn normalize_network_rule_host(raw: &str) -> Result<String> {
@@ -214,7 +357,12 @@ pub(crate) fn normalize_network_rule_host(raw: &str) -> Result<String> {
 pub trait Rule: Any + Debug + Send + Sync {
     fn program(&self) -> &str;
 
-    fn matches(&self, cmd: &[String]) -> Option<RuleMatch>;
+    fn matches(&self, cmd: &[String], options: &MatchOptions) -> Option<RuleMatch>;
+
+    /// Where the rule was declared, if known.
+    fn location(&self) -> Option<&ErrorLocation> {
+        None
+    }
 
     fn as_any(&self) -> &dyn Any;
 }
@@ -226,9 +374,9 @@ impl Rule for PrefixRule {
         self.pattern.first.as_ref()
     }
 
-    fn matches(&self, cmd: &[String]) -> Option<RuleMatch> {
+    fn matches(&self, cmd: &[String], options: &MatchOptions) -> Option<RuleMatch> {
         self.pattern
-            .matches_prefix(cmd)
+            .matches_prefix(cmd, options)
             .map(|matched_prefix| RuleMatch::PrefixRuleMatch {
                 matched_prefix,
                 decision: self.decision,
@@ -237,11 +385,29 @@ impl Rule for PrefixRule {
             })
     }
 
+    fn location(&self) -> Option<&ErrorLocation> {
+        self.location.as_ref()
+    }
+
     fn as_any(&self) -> &dyn Any {
         self
     }
 }
 
+#[cfg(windows)]
+const EXAMPLE_CWD: &str = r"C:\workspace";
+#[cfg(not(windows))]
+const EXAMPLE_CWD: &str = "/workspace";
+
+/// Examples are evaluated as if they ran from `EXAMPLE_CWD`, so relative paths
+/// in examples are inside the workspace and absolute paths elsewhere are not.
+fn example_match_options() -> MatchOptions {
+    MatchOptions {
+        resolve_host_executables: true,
+        cwd: AbsolutePathBuf::from_absolute_path(EXAMPLE_CWD).ok(),
+    }
+}
+
 /// Count how many rules match each provided example and error if any example is unmatched.
 pub(crate) fn validate_match_examples(
     policy: &Policy,
@@ -249,9 +415,7 @@ pub(crate) fn validate_match_examples(
     matches: &[Vec<String>],
 ) -> Result<()> {
     let mut unmatched_examples = Vec::new();
-    let options = MatchOptions {
-        resolve_host_executables: true,
-    };
+    let options = example_match_options();
 
     for example in matches {
         if !policy
@@ -284,9 +448,7 @@ pub(crate) fn validate_not_match_examples(
     _rules: &[RuleRef],
     not_matches: &[Vec<String>],
 ) -> Result<()> {
-    let options = MatchOptions {
-        resolve_host_executables: true,
-    };
+    let options = example_match_options();
 
     for example in not_matches {
         if let Some(rule) = policy
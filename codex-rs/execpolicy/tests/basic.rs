@@ -127,6 +127,54 @@ network_rule(host = "prompt-only.example.com", protocol = "https", decision = "p
     Ok(())
 }
 
+#[test]
+fn network_request_rules_keep_path_and_methods_out_of_domain_lists() -> Result<()> {
+    let policy_src = r#"
+network_rule(host = "github.com", protocol = "https", decision = "allow", path = "/*/archive/*", methods = ["get", "HEAD"])
+network_rule(host = "api.github.com", protocol = "https", decision = "deny", methods = ["POST"])
+    "#;
+    let mut parser = PolicyParser::new();
+    parser.parse("network.rules", policy_src)?;
+    let policy = parser.build();
+
+    let request_rules: Vec<(&str, Option<&str>, Vec<&str>)> = policy
+        .network_rules()
+        .iter()
+        .filter(|rule| rule.is_request_rule())
+        .map(|rule| {
+            (
+                rule.host.as_str(),
+                rule.path.as_deref(),
+                rule.methods.iter().map(String::as_str).collect(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        request_rules,
+        vec![
+            ("github.com", Some("/*/archive/*"), vec!["GET", "HEAD"]),
+            ("api.github.com", None, vec!["POST"]),
+        ]
+    );
+    assert_eq!(
+        policy.compiled_network_domains(),
+        (Vec::<String>::new(), Vec::<String>::new())
+    );
+    Ok(())
+}
+
+#[test]
+fn network_rule_rejects_path_for_socks_protocols() {
+    let mut parser = PolicyParser::new();
+    let err = parser
+        .parse(
+            "network.rules",
+            r#"network_rule(host="github.com", protocol="socks5_tcp", decision="allow", path="/x")"#,
+        )
+        .expect_err("socks network_rule with a path should fail");
+    assert!(err.to_string().contains("require protocol http or https"));
+}
+
 #[test]
 fn network_rule_rejects_wildcard_hosts() {
     let mut parser = PolicyParser::new();
@@ -260,6 +308,7 @@ fn add_prefix_rule_extends_policy() -> Result<()> {
             },
             decision: Decision::Prompt,
             justification: None,
+            location: None,
         })],
         rules
     );
@@ -321,6 +370,7 @@ prefix_rule(
                 },
                 decision: Decision::Prompt,
                 justification: None,
+                location: None,
             }),
             RuleSnapshot::Prefix(PrefixRule {
                 pattern: PrefixPattern {
@@ -329,6 +379,7 @@ prefix_rule(
                 },
                 decision: Decision::Forbidden,
                 justification: None,
+                location: None,
             }),
         ],
         git_rules
@@ -398,6 +449,7 @@ prefix_rule(
             },
             decision: Decision::Allow,
             justification: None,
+            location: None,
         })],
         bash_rules
     );
@@ -409,6 +461,7 @@ prefix_rule(
             },
             decision: Decision::Allow,
             justification: None,
+            location: None,
         })],
         sh_rules
     );
@@ -470,6 +523,7 @@ prefix_rule(
             },
             decision: Decision::Allow,
             justification: None,
+            location: None,
         })],
         rules
     );
@@ -553,6 +607,159 @@ prefix_rule(
     Ok(())
 }
 
+#[test]
+fn without_flags_rejects_commands_that_set_the_flag() -> Result<()> {
+    let policy_src = r#"
+prefix_rule(
+    pattern = ["git", "push", without_flags(["--force", "-f"])],
+    match = ["git push origin main", "git push origin -- -f"],
+    not_match = ["git push -f origin", "git push --force=yes", "git push -uf origin main"],
+)
+    "#;
+    let mut parser = PolicyParser::new();
+    parser.parse("test.rules", policy_src)?;
+    let policy = parser.build();
+
+    assert_eq!(
+        Evaluation {
+            decision: Decision::Allow,
+            matched_rules: vec![RuleMatch::PrefixRuleMatch {
+                matched_prefix: tokens(&["git", "push"]),
+                decision: Decision::Allow,
+                resolved_program: None,
+                justification: None,
+            }],
+        },
+        policy.check(&tokens(&["git", "push", "origin", "main"]), &prompt_all)
+    );
+    assert_eq!(
+        Evaluation {
+            decision: Decision::Prompt,
+            matched_rules: vec![RuleMatch::HeuristicsRuleMatch {
+                command: tokens(&["git", "push", "--force", "origin"]),
+                decision: Decision::Prompt,
+            }],
+        },
+        policy.check(&tokens(&["git", "push", "--force", "origin"]), &prompt_all)
+    );
+    Ok(())
+}
+
+#[test]
+fn argument_matchers_match_globs_regexes_and_remaining_args() -> Result<()> {
+    let policy_src = r#"
+prefix_rule(
+    pattern = ["head", "-n", regex("[0-9]+"), any_remaining(glob("*.log"))],
+    match = ["head -n 20 app.log other.log", "head -n 5"],
+    not_match = ["head -n x app.log", "head -n 20 notes.txt"],
+)
+    "#;
+    let mut parser = PolicyParser::new();
+    parser.parse("test.rules", policy_src)?;
+    let policy = parser.build();
+
+    let cmd = tokens(&["head", "-n", "20", "logs/app.log"]);
+    assert_eq!(
+        Evaluation {
+            decision: Decision::Allow,
+            matched_rules: vec![RuleMatch::PrefixRuleMatch {
+                matched_prefix: cmd.clone(),
+                decision: Decision::Allow,
+                resolved_program: None,
+                justification: None,
+            }],
+        },
+        policy.check(&cmd, &prompt_all)
+    );
+    assert_eq!(
+        vec![vec![
+            "head".to_string(),
+            "-n".to_string(),
+            r#"regex("[0-9]+")"#.to_string(),
+            r#"any_remaining([glob("*.log")])"#.to_string(),
+        ]],
+        policy.get_allowed_prefixes()
+    );
+    Ok(())
+}
+
+#[test]
+fn workspace_path_matches_paths_inside_cwd() -> Result<()> {
+    let policy_src = r#"
+prefix_rule(
+    pattern = ["rm", any_remaining([glob("-*"), workspace_path()])],
+    match = ["rm -rf build", "rm src/old.rs ./tmp/../cache"],
+    not_match = ["rm -rf ../sibling", "rm /etc/passwd"],
+)
+    "#;
+    let mut parser = PolicyParser::new();
+    parser.parse("test.rules", policy_src)?;
+    let policy = parser.build();
+
+    let cwd = absolute_path(&host_absolute_path(&["repo"]));
+    let inside = host_absolute_path(&["repo", "target"]);
+    let outside = host_absolute_path(&["other", "target"]);
+    let options = MatchOptions {
+        cwd: Some(cwd),
+        ..Default::default()
+    };
+    let check = |cmd: &[String], options: &MatchOptions| {
+        policy
+            .check_with_options(cmd, &prompt_all, options)
+            .decision
+    };
+
+    assert_eq!(
+        Decision::Allow,
+        check(&tokens(&["rm", "-r", inside.as_str()]), &options)
+    );
+    assert_eq!(
+        Decision::Prompt,
+        check(&tokens(&["rm", "-r", outside.as_str()]), &options)
+    );
+    // Without a working directory no path is considered inside the workspace.
+    assert_eq!(
+        Decision::Prompt,
+        check(&tokens(&["rm", "build"]), &MatchOptions::default())
+    );
+    Ok(())
+}
+
+#[test]
+fn argument_matcher_placement_is_validated() {
+    for (policy_src, expected) in [
+        (
+            r#"prefix_rule(pattern = [glob("g*"), "status"])"#,
+            "first pattern element must be a string or list of strings",
+        ),
+        (
+            r#"prefix_rule(pattern = ["ls", any_remaining(), "-l"])"#,
+            "any_remaining() must be the last pattern element",
+        ),
+        (
+            r#"prefix_rule(pattern = ["ls", [any_remaining(), "-l"]])"#,
+            "cannot be used as a single-token alternative",
+        ),
+        (
+            r#"prefix_rule(pattern = ["git", "push", without_flags(["force"])])"#,
+            "without_flags() entries must be flags",
+        ),
+        (
+            r#"prefix_rule(pattern = ["ls", regex("(")])"#,
+            "invalid regex `(`",
+        ),
+    ] {
+        let mut parser = PolicyParser::new();
+        let err = parser
+            .parse("test.rules", policy_src)
+            .expect_err("invalid pattern should fail to parse");
+        assert!(
+            err.to_string().contains(expected),
+            "expected `{expected}` in `{err}`"
+        );
+    }
+}
+
 #[test]
 fn strictest_decision_wins_across_matches() -> Result<()> {
     let policy_src = r#"
@@ -786,6 +993,7 @@ host_executable(name = "git", paths = ["{git_path_literal}"])
         &allow_all,
         &MatchOptions {
             resolve_host_executables: true,
+            ..Default::default()
         },
     );
     assert_eq!(
@@ -843,6 +1051,7 @@ host_executable(name = "git", paths = [])
         &allow_all,
         &MatchOptions {
             resolve_host_executables: true,
+            ..Default::default()
         },
     );
     assert_eq!(
@@ -878,6 +1087,7 @@ host_executable(name = "git", paths = ["{allowed_git_literal}"])
         &allow_all,
         &MatchOptions {
             resolve_host_executables: true,
+            ..Default::default()
         },
     );
     assert_eq!(
@@ -908,6 +1118,7 @@ prefix_rule(pattern = ["git"], decision = "prompt")
         &allow_all,
         &MatchOptions {
             resolve_host_executables: true,
+            ..Default::default()
         },
     );
     assert_eq!(
@@ -945,6 +1156,7 @@ host_executable(name = "git", paths = ["{git_path_literal}"])
         &allow_all,
         &MatchOptions {
             resolve_host_executables: true,
+            ..Default::default()
         },
     );
     assert_eq!(
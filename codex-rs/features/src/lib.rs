@@ -112,6 +112,9 @@ pub enum Feature {
     /// Use the legacy Landlock Linux sandbox fallback instead of the default
     /// bubblewrap pipeline.
     UseLegacyLandlock,
+    /// Mount the workspace through a per-turn overlay in the Linux sandbox and
+    /// ask the user to commit or discard the result at turn end.
+    OverlayWorkspace,
     /// Allow the model to request approval and propose exec rules.
     RequestRule,
     /// Enable Windows sandbox (restricted token) on Windows.
@@ -776,6 +779,12 @@ pub const FEATURES: &[FeatureSpec] = &[
         stage: Stage::Deprecated,
         default_enabled: false,
     },
+    FeatureSpec {
+        id: Feature::OverlayWorkspace,
+        key: "overlay_workspace",
+        stage: Stage::UnderDevelopment,
+        default_enabled: false,
+    },
     FeatureSpec {
         id: Feature::RequestRule,
         key: "request_rule",
@@ -4,7 +4,8 @@ use std::path::PathBuf;
 use clap::ArgAction;
 use clap::Parser;
 
-/// Fuzzy matches filenames under a directory.
+/// Fuzzy matches filenames under a directory, or searches file contents with
+/// `--content`.
 #[derive(Parser)]
 #[command(version)]
 pub struct Cli {
@@ -37,6 +38,28 @@ pub struct Cli {
     #[arg(short, long, action = ArgAction::Append)]
     pub exclude: Vec<String>,
 
+    /// Search file contents for lines matching the pattern instead of fuzzy
+    /// matching paths.
+    #[arg(long, default_value = "false")]
+    pub content: bool,
+
+    /// Treat the pattern as a regular expression (content search only).
+    #[arg(long, default_value = "false", requires = "content")]
+    pub regex: bool,
+
+    /// Match case exactly (content search only).
+    #[arg(long, short = 's', default_value = "false", requires = "content")]
+    pub case_sensitive: bool,
+
+    /// Only search files of this type, such as `rust` or `py` (repeatable;
+    /// content search only).
+    #[arg(long = "type", short = 't', action = ArgAction::Append, requires = "content")]
+    pub file_types: Vec<String>,
+
+    /// Only search files matching this glob (repeatable; content search only).
+    #[arg(long = "glob", short = 'g', action = ArgAction::Append, requires = "content")]
+    pub globs: Vec<String>,
+
     /// Search pattern.
     pub pattern: Option<String>,
 }
@@ -0,0 +1,898 @@
+//! Content (grep) search over the same ignore-aware walk used for fuzzy path
+//! matching.
+
+use std::fs;
+use std::io;
+use std::num::NonZero;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::thread;
+use std::time::Duration;
+use std::time::Instant;
+
+use crossbeam_channel::Receiver;
+use crossbeam_channel::Sender;
+use crossbeam_channel::unbounded;
+use ignore::overrides::Override;
+use ignore::types::Types;
+use ignore::types::TypesBuilder;
+use regex::Regex;
+use regex::RegexBuilder;
+use serde::Serialize;
+
+use crate::RunReporter;
+use crate::build_override_matcher;
+use crate::build_walker;
+use crate::get_file_path;
+
+/// Longest line preview, in bytes, returned for a match.
+const MAX_PREVIEW_BYTES: usize = 512;
+/// Files larger than this are skipped rather than searched.
+const MAX_FILE_BYTES: u64 = 8 * 1024 * 1024;
+/// Files with a NUL byte in this many leading bytes are treated as binary.
+const BINARY_SNIFF_BYTES: usize = 8 * 1024;
+/// Minimum delay between streamed updates while a walk is in progress.
+const UPDATE_INTERVAL: Duration = Duration::from_millis(50);
+
+/// A single matching line returned from a content search.
+///
+/// `column` is a byte offset into the line as stored in the file, while
+/// `ranges` are byte offsets into `preview`, so callers that need character
+/// offsets must convert them using `preview`. The two differ only for lines
+/// that are not valid UTF-8, whose invalid bytes appear in `preview` as
+/// U+FFFD.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct ContentMatch {
+    /// Path to the file, relative to `root`.
+    pub path: PathBuf,
+    pub root: PathBuf,
+    /// 1-based line number.
+    pub line_number: usize,
+    /// 1-based byte column of the first match on the line as stored.
+    pub column: usize,
+    /// The matching line without its terminator, truncated to
+    /// [`MAX_PREVIEW_BYTES`] on a character boundary.
+    pub preview: String,
+    /// Byte ranges of every match within `preview`, in ascending order.
+    pub ranges: Vec<ContentMatchRange>,
+}
+
+impl ContentMatch {
+    pub fn full_path(&self) -> PathBuf {
+        self.root.join(&self.path)
+    }
+}
+
+/// Half-open byte range `[start, end)` of a match within a line preview.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+pub struct ContentMatchRange {
+    pub start: usize,
+    pub end: usize,
+}
+
+#[derive(Debug)]
+pub struct ContentSearchResults {
+    pub matches: Vec<ContentMatch>,
+    pub total_match_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq, Default)]
+pub struct ContentSearchSnapshot {
+    pub query: String,
+    /// The first `limit` matching lines found so far, ordered by root, path
+    /// and line number. Once the walk is complete these are the first `limit`
+    /// matches overall in that order.
+    pub matches: Vec<ContentMatch>,
+    /// Number of matching lines found so far, including those beyond `limit`.
+    pub total_match_count: usize,
+    pub searched_file_count: usize,
+    pub walk_complete: bool,
+    /// Set when the query could not be compiled; no files are searched.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ContentSearchOptions {
+    pub limit: NonZero<usize>,
+    pub exclude: Vec<String>,
+    pub threads: NonZero<usize>,
+    /// See [`crate::FileSearchOptions::respect_gitignore`].
+    pub respect_gitignore: bool,
+    /// Treat the query as a regular expression instead of a literal string.
+    pub regex: bool,
+    pub case_sensitive: bool,
+    /// Names of `ignore` file types (e.g. `rust`, `py`) to restrict the search
+    /// to. Empty searches every file.
+    pub file_types: Vec<String>,
+    /// Globs a file must match to be searched. Empty searches every file.
+    pub globs: Vec<String>,
+}
+
+impl Default for ContentSearchOptions {
+    fn default() -> Self {
+        Self {
+            #[expect(clippy::unwrap_used)]
+            limit: NonZero::new(200).unwrap(),
+            exclude: Vec::new(),
+            #[expect(clippy::unwrap_used)]
+            threads: NonZero::new(2).unwrap(),
+            respect_gitignore: true,
+            regex: false,
+            case_sensitive: false,
+            file_types: Vec::new(),
+            globs: Vec::new(),
+        }
+    }
+}
+
+pub trait ContentSearchReporter: Send + Sync + 'static {
+    /// Called periodically while a query is being searched, and once more when
+    /// its walk completes.
+    fn on_update(&self, snapshot: &ContentSearchSnapshot);
+
+    /// Called when the session becomes idle or is cancelled.
+    fn on_complete(&self);
+}
+
+/// An incremental content search. Each query update abandons the in-flight
+/// walk and searches the roots again for the new query.
+pub struct ContentSearchSession {
+    inner: Arc<ContentSessionInner>,
+}
+
+impl ContentSearchSession {
+    pub fn update_query(&self, query: &str) {
+        // Bump the generation before queueing the query so the in-flight walk
+        // stops as soon as possible.
+        self.inner.generation.fetch_add(1, Ordering::SeqCst);
+        let _ = self
+            .inner
+            .work_tx
+            .send(ContentWorkSignal::QueryUpdated(query.to_string()));
+    }
+}
+
+impl Drop for ContentSearchSession {
+    fn drop(&mut self) {
+        self.inner.shutdown.store(true, Ordering::Relaxed);
+        let _ = self.inner.work_tx.send(ContentWorkSignal::Shutdown);
+    }
+}
+
+pub fn create_content_session(
+    search_directories: Vec<PathBuf>,
+    options: ContentSearchOptions,
+    reporter: Arc<dyn ContentSearchReporter>,
+    cancel_flag: Option<Arc<AtomicBool>>,
+) -> anyhow::Result<ContentSearchSession> {
+    let ContentSearchOptions {
+        limit,
+        exclude,
+        threads,
+        respect_gitignore,
+        regex,
+        case_sensitive,
+        file_types,
+        globs,
+    } = options;
+
+    let Some(primary_search_directory) = search_directories.first() else {
+        anyhow::bail!("at least one search directory is required");
+    };
+    let override_matcher = build_override_matcher(primary_search_directory, &globs, &exclude)?;
+    let types = build_types(&file_types)?;
+    let (work_tx, work_rx) = unbounded();
+
+    let inner = Arc::new(ContentSessionInner {
+        search_directories,
+        limit: limit.get(),
+        threads: threads.get(),
+        respect_gitignore,
+        regex,
+        case_sensitive,
+        override_matcher,
+        types,
+        cancelled: cancel_flag.unwrap_or_else(|| Arc::new(AtomicBool::new(false))),
+        shutdown: AtomicBool::new(false),
+        generation: AtomicU64::new(0),
+        reporter,
+        work_tx,
+    });
+
+    let worker_inner = inner.clone();
+    thread::spawn(move || content_worker(worker_inner, work_rx));
+
+    Ok(ContentSearchSession { inner })
+}
+
+/// Searches `roots` for lines matching `query` and returns once the walk has
+/// finished. Unlike a session, an invalid query is reported as an error.
+pub fn run_content_search(
+    query: &str,
+    roots: Vec<PathBuf>,
+    options: ContentSearchOptions,
+    cancel_flag: Option<Arc<AtomicBool>>,
+) -> anyhow::Result<ContentSearchResults> {
+    build_query_regex(query, options.regex, options.case_sensitive)?;
+    let reporter = Arc::new(RunReporter::<ContentSearchSnapshot>::default());
+    let session = create_content_session(roots, options, reporter.clone(), cancel_flag)?;
+
+    session.update_query(query);
+
+    let snapshot = reporter.wait_for_complete();
+    Ok(ContentSearchResults {
+        matches: snapshot.matches,
+        total_match_count: snapshot.total_match_count,
+    })
+}
+
+struct ContentSessionInner {
+    search_directories: Vec<PathBuf>,
+    limit: usize,
+    threads: usize,
+    respect_gitignore: bool,
+    regex: bool,
+    case_sensitive: bool,
+    override_matcher: Option<Override>,
+    types: Option<Types>,
+    cancelled: Arc<AtomicBool>,
+    shutdown: AtomicBool,
+    /// Incremented on every query update; a walk stops once it no longer
+    /// matches the generation it started with.
+    generation: AtomicU64,
+    reporter: Arc<dyn ContentSearchReporter>,
+    work_tx: Sender<ContentWorkSignal>,
+}
+
+impl ContentSessionInner {
+    fn stop_requested(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed) || self.shutdown.load(Ordering::Relaxed)
+    }
+
+    fn is_stale(&self, generation: u64) -> bool {
+        self.stop_requested() || self.generation.load(Ordering::SeqCst) != generation
+    }
+}
+
+enum ContentWorkSignal {
+    QueryUpdated(String),
+    Shutdown,
+}
+
+#[derive(Default)]
+struct ContentSearchState {
+    matches: Vec<ContentMatch>,
+    total_match_count: usize,
+    searched_file_count: usize,
+}
+
+impl ContentSearchState {
+    /// Adds the matches of one file, keeping only the first `limit` matches in
+    /// result order so that the outcome does not depend on walk order.
+    fn add_matches(&mut self, matches: impl IntoIterator<Item = ContentMatch>, limit: usize) {
+        self.matches.extend(matches);
+        if self.matches.len() > limit {
+            self.matches.select_nth_unstable_by(limit, compare_matches);
+            self.matches.truncate(limit);
+        }
+    }
+
+    fn snapshot(&self, query: &str, walk_complete: bool) -> ContentSearchSnapshot {
+        let mut matches = self.matches.clone();
+        matches.sort_by(compare_matches);
+        ContentSearchSnapshot {
+            query: query.to_string(),
+            matches,
+            total_match_count: self.total_match_count,
+            searched_file_count: self.searched_file_count,
+            walk_complete,
+            error: None,
+        }
+    }
+}
+
+fn compare_matches(a: &ContentMatch, b: &ContentMatch) -> std::cmp::Ordering {
+    (&a.root, &a.path, a.line_number).cmp(&(&b.root, &b.path, b.line_number))
+}
+
+fn content_worker(inner: Arc<ContentSessionInner>, work_rx: Receiver<ContentWorkSignal>) {
+    'signals: while let Ok(signal) = work_rx.recv() {
+        let mut query = match signal {
+            ContentWorkSignal::QueryUpdated(query) => query,
+            ContentWorkSignal::Shutdown => break,
+        };
+        // Only the most recent query is worth searching.
+        for signal in work_rx.try_iter() {
+            match signal {
+                ContentWorkSignal::QueryUpdated(latest) => query = latest,
+                ContentWorkSignal::Shutdown => break 'signals,
+            }
+        }
+        if inner.stop_requested() {
+            break;
+        }
+
+        let generation = inner.generation.load(Ordering::SeqCst);
+        search_query(&inner, &query, generation);
+        if inner.stop_requested() {
+            break;
+        }
+    }
+
+    // If we cancelled or otherwise exited the loop, make sure the reporter is notified.
+    inner.reporter.on_complete();
+}
+
+fn search_query(inner: &ContentSessionInner, query: &str, generation: u64) {
+    let complete = |snapshot: ContentSearchSnapshot| {
+        inner.reporter.on_update(&snapshot);
+        inner.reporter.on_complete();
+    };
+    if query.is_empty() {
+        complete(ContentSearchState::default().snapshot(query, /*walk_complete*/ true));
+        return;
+    }
+    let regex = match build_query_regex(query, inner.regex, inner.case_sensitive) {
+        Ok(regex) => regex,
+        Err(err) => {
+            complete(ContentSearchSnapshot {
+                error: Some(err.to_string()),
+                ..ContentSearchState::default().snapshot(query, /*walk_complete*/ true)
+            });
+            return;
+        }
+    };
+    let Some(mut walk_builder) = build_walker(
+        &inner.search_directories,
+        inner.threads,
+        inner.respect_gitignore,
+        inner.override_matcher.clone(),
+    ) else {
+        complete(ContentSearchState::default().snapshot(query, /*walk_complete*/ true));
+        return;
+    };
+    if let Some(types) = &inner.types {
+        walk_builder.types(types.clone());
+    }
+
+    let state = Mutex::new(ContentSearchState::default());
+    let last_update = Mutex::new(Instant::now());
+    walk_builder.build_parallel().run(|| {
+        let regex = &regex;
+        let state = &state;
+        let last_update = &last_update;
+        Box::new(move |entry| {
+            if inner.is_stale(generation) {
+                return ignore::WalkState::Quit;
+            }
+            let Ok(entry) = entry else {
+                return ignore::WalkState::Continue;
+            };
+            if !entry
+                .file_type()
+                .is_some_and(|file_type| file_type.is_file())
+            {
+                return ignore::WalkState::Continue;
+            }
+            let path = entry.path();
+            let Some((root_idx, relative_path)) = get_file_path(path, &inner.search_directories)
+            else {
+                return ignore::WalkState::Continue;
+            };
+            let line_matches = search_file(path, regex).unwrap_or_default();
+
+            #[expect(clippy::unwrap_used)]
+            let mut state = state.lock().unwrap();
+            state.searched_file_count += 1;
+            state.total_match_count += line_matches.len();
+            // Lines of one file are already in order, so only its first
+            // `limit` matches can make the cut.
+            let file_matches = line_matches
+                .into_iter()
+                .take(inner.limit)
+                .map(|line_match| ContentMatch {
+                    path: PathBuf::from(relative_path),
+                    root: inner.search_directories[root_idx].clone(),
+                    line_number: line_match.line_number,
+                    column: line_match.column,
+                    preview: line_match.preview,
+                    ranges: line_match.ranges,
+                });
+            state.add_matches(file_matches, inner.limit);
+
+            #[expect(clippy::unwrap_used)]
+            let mut last_update = last_update.lock().unwrap();
+            if last_update.elapsed() >= UPDATE_INTERVAL {
+                *last_update = Instant::now();
+                inner
+                    .reporter
+                    .on_update(&state.snapshot(query, /*walk_complete*/ false));
+            }
+            ignore::WalkState::Continue
+        })
+    });
+
+    if inner.is_stale(generation) {
+        return;
+    }
+    #[expect(clippy::unwrap_used)]
+    let state = state.into_inner().unwrap();
+    complete(state.snapshot(query, /*walk_complete*/ true));
+}
+
+fn build_query_regex(query: &str, regex: bool, case_sensitive: bool) -> anyhow::Result<Regex> {
+    let pattern = if regex {
+        query.to_string()
+    } else {
+        regex::escape(query)
+    };
+    RegexBuilder::new(&pattern)
+        .case_insensitive(!case_sensitive)
+        .build()
+        .map_err(|err| anyhow::anyhow!("invalid regex `{query}`: {err}"))
+}
+
+fn build_types(file_types: &[String]) -> anyhow::Result<Option<Types>> {
+    if file_types.is_empty() {
+        return Ok(None);
+    }
+    let mut builder = TypesBuilder::new();
+    builder.add_defaults();
+    for file_type in file_types {
+        builder.select(file_type);
+    }
+    Ok(Some(builder.build()?))
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct LineMatch {
+    line_number: usize,
+    column: usize,
+    preview: String,
+    ranges: Vec<ContentMatchRange>,
+}
+
+/// Returns every line of `path` that `regex` matches. Binary and oversized
+/// files yield no matches.
+fn search_file(path: &Path, regex: &Regex) -> io::Result<Vec<LineMatch>> {
+    if fs::metadata(path)?.len() > MAX_FILE_BYTES {
+        return Ok(Vec::new());
+    }
+    let bytes = fs::read(path)?;
+    if bytes[..bytes.len().min(BINARY_SNIFF_BYTES)].contains(&0) {
+        return Ok(Vec::new());
+    }
+    Ok(search_bytes(&bytes, regex))
+}
+
+/// Searches file contents that may not be valid UTF-8. Lines are matched with
+/// invalid bytes replaced by U+FFFD, and columns are mapped back to byte
+/// offsets in the original line.
+fn search_bytes(bytes: &[u8], regex: &Regex) -> Vec<LineMatch> {
+    if let Ok(text) = std::str::from_utf8(bytes) {
+        return search_text(text, regex);
+    }
+    let mut lines: Vec<&[u8]> = bytes.split(|byte| *byte == b'\n').collect();
+    if bytes.ends_with(b"\n") {
+        lines.pop();
+    }
+    lines
+        .into_iter()
+        .enumerate()
+        .filter_map(|(index, line)| {
+            let line = line.strip_suffix(b"\r").unwrap_or(line);
+            let mut line_match = match_line(index, &String::from_utf8_lossy(line), regex)?;
+            line_match.column = raw_byte_offset(line, line_match.column - 1) + 1;
+            Some(line_match)
+        })
+        .collect()
+}
+
+/// Maps a byte offset in the lossy UTF-8 conversion of `raw` back to the
+/// offset of the corresponding byte in `raw`.
+fn raw_byte_offset(raw: &[u8], lossy_offset: usize) -> usize {
+    let replacement_len = char::REPLACEMENT_CHARACTER.len_utf8();
+    let mut raw_pos = 0;
+    let mut lossy_pos = 0;
+    for chunk in raw.utf8_chunks() {
+        let valid_len = chunk.valid().len();
+        if lossy_offset < lossy_pos + valid_len {
+            return raw_pos + (lossy_offset - lossy_pos);
+        }
+        raw_pos += valid_len;
+        lossy_pos += valid_len;
+        if chunk.invalid().is_empty() {
+            continue;
+        }
+        if lossy_offset < lossy_pos + replacement_len {
+            return raw_pos;
+        }
+        raw_pos += chunk.invalid().len();
+        lossy_pos += replacement_len;
+    }
+    raw_pos
+}
+
+fn search_text(text: &str, regex: &Regex) -> Vec<LineMatch> {
+    text.lines()
+        .enumerate()
+        .filter_map(|(index, line)| match_line(index, line, regex))
+        .collect()
+}
+
+fn match_line(index: usize, line: &str, regex: &Regex) -> Option<LineMatch> {
+    let ranges: Vec<ContentMatchRange> = regex
+        .find_iter(line)
+        .filter(|found| !found.is_empty())
+        .map(|found| ContentMatchRange {
+            start: found.start(),
+            end: found.end(),
+        })
+        .collect();
+    let first = ranges.first()?;
+    let preview = truncate_preview(line);
+    let column = first.start + 1;
+    let ranges = ranges
+        .into_iter()
+        .filter(|range| range.start < preview.len())
+        .map(|range| ContentMatchRange {
+            start: range.start,
+            end: range.end.min(preview.len()),
+        })
+        .collect();
+    Some(LineMatch {
+        line_number: index + 1,
+        column,
+        preview: preview.to_string(),
+        ranges,
+    })
+}
+
+fn truncate_preview(line: &str) -> &str {
+    if line.len() <= MAX_PREVIEW_BYTES {
+        return line;
+    }
+    let mut end = MAX_PREVIEW_BYTES;
+    while !line.is_char_boundary(end) {
+        end -= 1;
+    }
+    &line[..end]
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::sync::Condvar;
+    use tempfile::TempDir;
+
+    fn search(query: &str, options: ContentSearchOptions) -> Vec<(String, usize, String)> {
+        let dir = create_fixture_tree();
+        run_content_search(query, vec![dir.path().to_path_buf()], options, None)
+            .expect("search ok")
+            .matches
+            .into_iter()
+            .map(|m| {
+                (
+                    m.path.to_string_lossy().replace('\\', "/"),
+                    m.line_number,
+                    m.preview,
+                )
+            })
+            .collect()
+    }
+
+    fn create_fixture_tree() -> TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("src")).unwrap();
+        fs::write(
+            dir.path().join("src/lib.rs"),
+            "fn alpha() {}\n// TODO: Alpha beta\nfn gamma() {}\n",
+        )
+        .unwrap();
+        fs::write(dir.path().join("notes.md"), "alpha notes\r\nnothing\r\n").unwrap();
+        fs::write(dir.path().join("data.bin"), b"alpha\0binary").unwrap();
+        dir
+    }
+
+    #[test]
+    fn literal_search_is_case_insensitive_by_default() {
+        assert_eq!(
+            search("alpha", ContentSearchOptions::default()),
+            vec![
+                ("notes.md".to_string(), 1, "alpha notes".to_string()),
+                ("src/lib.rs".to_string(), 1, "fn alpha() {}".to_string()),
+                (
+                    "src/lib.rs".to_string(),
+                    2,
+                    "// TODO: Alpha beta".to_string()
+                ),
+            ]
+        );
+        assert_eq!(
+            search(
+                "Alpha",
+                ContentSearchOptions {
+                    case_sensitive: true,
+                    ..Default::default()
+                }
+            ),
+            vec![(
+                "src/lib.rs".to_string(),
+                2,
+                "// TODO: Alpha beta".to_string()
+            )]
+        );
+        assert_eq!(
+            search("a()", ContentSearchOptions::default())
+                .into_iter()
+                .map(|(_, line_number, _)| line_number)
+                .collect::<Vec<_>>(),
+            vec![1, 3]
+        );
+    }
+
+    #[test]
+    fn regex_search_honors_file_types_and_globs() {
+        assert_eq!(
+            search(
+                r"^fn \w+\(\)",
+                ContentSearchOptions {
+                    regex: true,
+                    ..Default::default()
+                }
+            ),
+            vec![
+                ("src/lib.rs".to_string(), 1, "fn alpha() {}".to_string()),
+                ("src/lib.rs".to_string(), 3, "fn gamma() {}".to_string()),
+            ]
+        );
+        assert_eq!(
+            search(
+                "alpha",
+                ContentSearchOptions {
+                    file_types: vec!["markdown".to_string()],
+                    ..Default::default()
+                }
+            ),
+            vec![("notes.md".to_string(), 1, "alpha notes".to_string())]
+        );
+        assert_eq!(
+            search(
+                "alpha",
+                ContentSearchOptions {
+                    globs: vec!["src/**".to_string()],
+                    exclude: vec!["*.md".to_string()],
+                    ..Default::default()
+                }
+            ),
+            vec![
+                ("src/lib.rs".to_string(), 1, "fn alpha() {}".to_string()),
+                (
+                    "src/lib.rs".to_string(),
+                    2,
+                    "// TODO: Alpha beta".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_queries_and_file_types_are_rejected() {
+        let dir = create_fixture_tree();
+        let err = run_content_search(
+            "fn (",
+            vec![dir.path().to_path_buf()],
+            ContentSearchOptions {
+                regex: true,
+                ..Default::default()
+            },
+            None,
+        )
+        .expect_err("invalid regex");
+        assert!(err.to_string().starts_with("invalid regex `fn (`"));
+
+        let err = run_content_search(
+            "alpha",
+            vec![dir.path().to_path_buf()],
+            ContentSearchOptions {
+                file_types: vec!["not-a-type".to_string()],
+                ..Default::default()
+            },
+            None,
+        )
+        .expect_err("unknown file type");
+        assert!(err.to_string().contains("not-a-type"));
+    }
+
+    #[test]
+    fn search_text_reports_columns_and_clipped_ranges() {
+        let regex = build_query_regex("ab", /*regex*/ false, /*case_sensitive*/ true).unwrap();
+        let straddling = format!("{}xéab", "é".repeat(255));
+        let clipped = format!("{}ab", "x".repeat(511));
+        assert_eq!(
+            search_text(&format!("xxab ab\nnone\n{straddling}\n{clipped}"), &regex),
+            vec![
+                LineMatch {
+                    line_number: 1,
+                    column: 3,
+                    preview: "xxab ab".to_string(),
+                    ranges: vec![
+                        ContentMatchRange { start: 2, end: 4 },
+                        ContentMatchRange { start: 5, end: 7 },
+                    ],
+                },
+                LineMatch {
+                    line_number: 3,
+                    column: 514,
+                    preview: format!("{}x", "é".repeat(255)),
+                    ranges: Vec::new(),
+                },
+                LineMatch {
+                    line_number: 4,
+                    column: 512,
+                    preview: format!("{}a", "x".repeat(511)),
+                    ranges: vec![ContentMatchRange {
+                        start: 511,
+                        end: 512
+                    }],
+                },
+            ]
+        );
+
+        let empty_matches =
+            build_query_regex("z*", /*regex*/ true, /*case_sensitive*/ true).unwrap();
+        assert_eq!(search_text("abc", &empty_matches), Vec::new());
+    }
+
+    #[test]
+    fn search_bytes_reports_columns_in_the_original_line() {
+        let regex = build_query_regex("ab", /*regex*/ false, /*case_sensitive*/ true).unwrap();
+        assert_eq!(
+            search_bytes(b"none\r\n\xff\xfex ab\r\n", &regex),
+            vec![LineMatch {
+                line_number: 2,
+                column: 5,
+                preview: "\u{FFFD}\u{FFFD}x ab".to_string(),
+                ranges: vec![ContentMatchRange { start: 8, end: 10 }],
+            }]
+        );
+    }
+
+    #[derive(Default)]
+    struct RecordingReporter {
+        updates: Mutex<Vec<ContentSearchSnapshot>>,
+        completed: (Condvar, Mutex<usize>),
+    }
+
+    impl RecordingReporter {
+        fn wait_for_completions(&self, count: usize) {
+            let (cv, mutex) = &self.completed;
+            let guard = mutex.lock().unwrap();
+            let (_guard, timeout) = cv
+                .wait_timeout_while(guard, Duration::from_secs(5), |completed| {
+                    *completed < count
+                })
+                .unwrap();
+            assert!(!timeout.timed_out(), "session did not complete");
+        }
+
+        fn last_update(&self) -> ContentSearchSnapshot {
+            self.updates.lock().unwrap().last().cloned().unwrap()
+        }
+    }
+
+    impl ContentSearchReporter for RecordingReporter {
+        fn on_update(&self, snapshot: &ContentSearchSnapshot) {
+            self.updates.lock().unwrap().push(snapshot.clone());
+        }
+
+        fn on_complete(&self) {
+            let (cv, mutex) = &self.completed;
+            *mutex.lock().unwrap() += 1;
+            cv.notify_all();
+        }
+    }
+
+    #[test]
+    fn session_searches_each_query_and_reports_errors() {
+        let dir = create_fixture_tree();
+        let reporter = Arc::new(RecordingReporter::default());
+        let session = create_content_session(
+            vec![dir.path().to_path_buf()],
+            ContentSearchOptions {
+                regex: true,
+                ..Default::default()
+            },
+            reporter.clone(),
+            None,
+        )
+        .expect("session");
+
+        session.update_query("gamma");
+        reporter.wait_for_completions(1);
+        let snapshot = reporter.last_update();
+        assert_eq!(snapshot.query, "gamma");
+        assert_eq!(snapshot.total_match_count, 1);
+        assert_eq!(snapshot.searched_file_count, 3);
+        assert!(snapshot.walk_complete);
+        assert_eq!(
+            snapshot.matches[0].full_path(),
+            dir.path().join("src/lib.rs")
+        );
+
+        session.update_query("(");
+        reporter.wait_for_completions(2);
+        let snapshot = reporter.last_update();
+        assert_eq!(snapshot.query, "(");
+        assert_eq!(snapshot.matches, Vec::new());
+        assert!(snapshot.error.is_some());
+    }
+
+    #[test]
+    fn limit_caps_matches_but_not_total_count() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("many.txt"), "hit\n".repeat(10)).unwrap();
+        let results = run_content_search(
+            "hit",
+            vec![dir.path().to_path_buf()],
+            ContentSearchOptions {
+                limit: NonZero::new(3).unwrap(),
+                ..Default::default()
+            },
+            None,
+        )
+        .expect("search ok");
+
+        assert_eq!(
+            results
+                .matches
+                .iter()
+                .map(|m| m.line_number)
+                .collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+        assert_eq!(results.total_match_count, 10);
+    }
+
+    #[test]
+    fn limit_keeps_the_first_matches_in_path_order() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["a.txt", "b.txt", "c.txt", "d.txt"] {
+            fs::write(dir.path().join(name), "hit\nhit\n").unwrap();
+        }
+        let results = run_content_search(
+            "hit",
+            vec![dir.path().to_path_buf()],
+            ContentSearchOptions {
+                limit: NonZero::new(3).unwrap(),
+                threads: NonZero::new(4).unwrap(),
+                ..Default::default()
+            },
+            None,
+        )
+        .expect("search ok");
+
+        assert_eq!(
+            results
+                .matches
+                .iter()
+                .map(|m| (m.path.to_string_lossy().into_owned(), m.line_number))
+                .collect::<Vec<_>>(),
+            vec![
+                ("a.txt".to_string(), 1),
+                ("a.txt".to_string(), 2),
+                ("b.txt".to_string(), 1),
+            ]
+        );
+        assert_eq!(results.total_match_count, 8);
+    }
+}
@@ -35,8 +35,18 @@ use nucleo::pattern::AtomKind;
 use nucleo::pattern::Pattern;
 
 mod cli;
+mod content;
 
 pub use cli::Cli;
+pub use content::ContentMatch;
+pub use content::ContentMatchRange;
+pub use content::ContentSearchOptions;
+pub use content::ContentSearchReporter;
+pub use content::ContentSearchResults;
+pub use content::ContentSearchSession;
+pub use content::ContentSearchSnapshot;
+pub use content::create_content_session;
+pub use content::run_content_search;
 
 /// A single match result returned from the search.
 ///
@@ -172,7 +182,8 @@ pub fn create_session(
     let Some(primary_search_directory) = search_directories.first() else {
         anyhow::bail!("at least one search directory is required");
     };
-    let override_matcher = build_override_matcher(primary_search_directory, &exclude)?;
+    let override_matcher =
+        build_override_matcher(primary_search_directory, /*include*/ &[], &exclude)?;
     let (work_tx, work_rx) = unbounded();
 
     let notify_tx = work_tx.clone();
@@ -212,6 +223,7 @@ pub fn create_session(
 
 pub trait Reporter {
     fn report_match(&self, file_match: &FileMatch);
+    fn report_content_match(&self, content_match: &ContentMatch);
     fn warn_matches_truncated(&self, total_match_count: usize, shown_match_count: usize);
     fn warn_no_search_pattern(&self, search_directory: &Path);
 }
@@ -225,6 +237,11 @@ pub async fn run_main<T: Reporter>(
         json: _,
         exclude,
         threads,
+        content,
+        regex,
+        case_sensitive,
+        file_types,
+        globs,
     }: Cli,
     reporter: T,
 ) -> anyhow::Result<()> {
@@ -258,6 +275,34 @@ pub async fn run_main<T: Reporter>(
         }
     };
 
+    if content {
+        let ContentSearchResults {
+            total_match_count,
+            matches,
+        } = run_content_search(
+            &pattern_text,
+            vec![search_directory],
+            ContentSearchOptions {
+                limit,
+                exclude,
+                threads,
+                respect_gitignore: true,
+                regex,
+                case_sensitive,
+                file_types,
+                globs,
+            },
+            /*cancel_flag*/ None,
+        )?;
+        for content_match in &matches {
+            reporter.report_content_match(content_match);
+        }
+        if total_match_count > matches.len() {
+            reporter.warn_matches_truncated(total_match_count, matches.len());
+        }
+        return Ok(());
+    }
+
     let FileSearchResults {
         total_match_count,
         matches,
@@ -294,7 +339,7 @@ pub fn run(
     options: FileSearchOptions,
     cancel_flag: Option<Arc<AtomicBool>>,
 ) -> anyhow::Result<FileSearchResults> {
-    let reporter = Arc::new(RunReporter::default());
+    let reporter = Arc::new(RunReporter::<FileSearchSnapshot>::default());
     let session = create_session(roots, options, reporter.clone(), cancel_flag)?;
 
     session.update_query(pattern_text);
@@ -361,14 +406,21 @@ enum WorkSignal {
     Shutdown,
 }
 
+/// Builds the walker override matcher. `include` globs whitelist files (any
+/// file that matches none of them is skipped); `exclude` globs skip matching
+/// entries.
 fn build_override_matcher(
     search_directory: &Path,
+    include: &[String],
     exclude: &[String],
 ) -> anyhow::Result<Option<ignore::overrides::Override>> {
-    if exclude.is_empty() {
+    if include.is_empty() && exclude.is_empty() {
         return Ok(None);
     }
     let mut override_builder = OverrideBuilder::new(search_directory);
+    for include in include {
+        override_builder.add(include)?;
+    }
     for exclude in exclude {
         let exclude_pattern = format!("!{exclude}");
         override_builder.add(&exclude_pattern)?;
@@ -396,8 +448,8 @@ fn get_file_path<'a>(path: &'a Path, search_directories: &[PathBuf]) -> Option<(
     rel_path.to_str().map(|p| (root_idx, p))
 }
 
-/// Walks the search directories and feeds discovered paths into `nucleo`
-/// via the injector.
+/// Configures a walker over `search_directories`; `None` when there are no
+/// directories to walk.
 ///
 /// The walker uses `require_git(true)` to match git's own ignore semantics:
 /// git never reads `.gitignore` files from directories above the repository
@@ -408,22 +460,20 @@ fn get_file_path<'a>(path: &'a Path, search_directories: &[PathBuf]) -> Option<(
 ///
 /// When `respect_gitignore` is `false`, all git-related ignore processing is
 /// disabled regardless of this flag.
-fn walker_worker(
-    inner: Arc<SessionInner>,
+fn build_walker(
+    search_directories: &[PathBuf],
+    threads: usize,
+    respect_gitignore: bool,
     override_matcher: Option<ignore::overrides::Override>,
-    injector: Injector<Arc<str>>,
-) {
-    let Some(first_root) = inner.search_directories.first() else {
-        let _ = inner.work_tx.send(WorkSignal::WalkComplete);
-        return;
-    };
+) -> Option<WalkBuilder> {
+    let first_root = search_directories.first()?;
 
     let mut walk_builder = WalkBuilder::new(first_root);
-    for root in inner.search_directories.iter().skip(1) {
+    for root in search_directories.iter().skip(1) {
         walk_builder.add(root);
     }
     walk_builder
-        .threads(inner.threads)
+        .threads(threads)
         // Allow hidden entries.
         .hidden(false)
         // Follow symlinks to search their contents.
@@ -431,7 +481,7 @@ fn walker_worker(
         // Keep ignore behavior aligned with git repositories: only apply
         // gitignore rules when a git context exists.
         .require_git(true);
-    if !inner.respect_gitignore {
+    if !respect_gitignore {
         walk_builder
             .git_ignore(false)
             .git_global(false)
@@ -442,6 +492,25 @@ fn walker_worker(
     if let Some(override_matcher) = override_matcher {
         walk_builder.overrides(override_matcher);
     }
+    Some(walk_builder)
+}
+
+/// Walks the search directories and feeds discovered paths into `nucleo`
+/// via the injector.
+fn walker_worker(
+    inner: Arc<SessionInner>,
+    override_matcher: Option<ignore::overrides::Override>,
+    injector: Injector<Arc<str>>,
+) {
+    let Some(walk_builder) = build_walker(
+        &inner.search_directories,
+        inner.threads,
+        inner.respect_gitignore,
+        override_matcher,
+    ) else {
+        let _ = inner.work_tx.send(WorkSignal::WalkComplete);
+        return;
+    };
 
     let walker = walk_builder.build_parallel();
 
@@ -603,30 +672,30 @@ fn matcher_worker(
     Ok(())
 }
 
+/// Collects the latest snapshot from a session so one-shot searches can block
+/// until it completes.
 #[derive(Default)]
-struct RunReporter {
-    snapshot: RwLock<FileSearchSnapshot>,
+struct RunReporter<S> {
+    snapshot: RwLock<S>,
     completed: (Condvar, Mutex<bool>),
 }
 
-impl SessionReporter for RunReporter {
-    fn on_update(&self, snapshot: &FileSearchSnapshot) {
+impl<S: Clone> RunReporter<S> {
+    fn record_update(&self, snapshot: &S) {
         #[allow(clippy::unwrap_used)]
         let mut guard = self.snapshot.write().unwrap();
         *guard = snapshot.clone();
     }
 
-    fn on_complete(&self) {
+    fn record_complete(&self) {
         let (cv, mutex) = &self.completed;
         #[allow(clippy::unwrap_used)]
         let mut completed = mutex.lock().unwrap();
         *completed = true;
         cv.notify_all();
     }
-}
 
-impl RunReporter {
-    fn wait_for_complete(&self) -> FileSearchSnapshot {
+    fn wait_for_complete(&self) -> S {
         let (cv, mutex) = &self.completed;
         #[allow(clippy::unwrap_used)]
         let mut completed = mutex.lock().unwrap();
@@ -641,6 +710,26 @@ impl RunReporter {
     }
 }
 
+impl SessionReporter for RunReporter<FileSearchSnapshot> {
+    fn on_update(&self, snapshot: &FileSearchSnapshot) {
+        self.record_update(snapshot);
+    }
+
+    fn on_complete(&self) {
+        self.record_complete();
+    }
+}
+
+impl ContentSearchReporter for RunReporter<ContentSearchSnapshot> {
+    fn on_update(&self, snapshot: &ContentSearchSnapshot) {
+        self.record_update(snapshot);
+    }
+
+    fn on_complete(&self) {
+        self.record_complete();
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(clippy::unwrap_used)]
@@ -3,6 +3,7 @@ use std::path::Path;
 
 use clap::Parser;
 use codex_file_search::Cli;
+use codex_file_search::ContentMatch;
 use codex_file_search::FileMatch;
 use codex_file_search::Reporter;
 use codex_file_search::run_main;
@@ -61,6 +62,22 @@ impl Reporter for StdioReporter {
         }
     }
 
+    fn report_content_match(&self, content_match: &ContentMatch) {
+        if self.write_output_as_json {
+            #[allow(clippy::unwrap_used)]
+            let json = serde_json::to_string(content_match).unwrap();
+            println!("{json}");
+        } else {
+            println!(
+                "{}:{}:{}:{}",
+                content_match.path.to_string_lossy(),
+                content_match.line_number,
+                content_match.column,
+                content_match.preview
+            );
+        }
+    }
+
     fn warn_matches_truncated(&self, total_match_count: usize, shown_match_count: usize) {
         if self.write_output_as_json {
             let value = json!({"matches_truncated": true});
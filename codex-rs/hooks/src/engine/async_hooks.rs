@@ -0,0 +1,135 @@
+//! Fire-and-forget execution for command hooks configured with `async: true`.
+//!
+//! Async hooks receive the same stdin as sync hooks and are still killed after their
+//! `timeout`, but the event that started them does not wait for them. Because the turn has
+//! already moved on by the time they exit, their output is informational only: an async hook
+//! cannot block a tool call, stop a turn, inject additional context, or answer a permission
+//! request. Each run is reported as a `HookCompletedEvent` once its command exits.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use codex_protocol::protocol::HookCompletedEvent;
+use codex_protocol::protocol::HookOutputEntry;
+use codex_protocol::protocol::HookOutputEntryKind;
+use codex_protocol::protocol::HookRunStatus;
+use tokio::sync::Semaphore;
+use tokio::sync::mpsc;
+
+use super::ConfiguredHandler;
+use super::HookRuntime;
+use super::command_runner::CommandRunResult;
+use super::dispatcher::ParsedHandler;
+use super::dispatcher::run_handler;
+use crate::events::common;
+
+/// Upper bound on async hook commands running at once across every clone of an engine.
+pub(crate) const MAX_CONCURRENT_ASYNC_HOOKS: usize = 8;
+
+const IGNORED_DECISION_WARNING: &str =
+    "async hooks finish after the event is handled, so this block/stop decision was ignored";
+
+/// Spawns async hooks while capping how many of their commands run concurrently.
+#[derive(Clone, Debug)]
+pub(crate) struct AsyncHookLimiter {
+    permits: Arc<Semaphore>,
+}
+
+impl Default for AsyncHookLimiter {
+    fn default() -> Self {
+        Self::new(MAX_CONCURRENT_ASYNC_HOOKS)
+    }
+}
+
+impl AsyncHookLimiter {
+    pub(crate) fn new(max_concurrent: usize) -> Self {
+        Self {
+            permits: Arc::new(Semaphore::new(max_concurrent.max(1))),
+        }
+    }
+
+    /// Starts every handler in the background and returns a handle that yields their
+    /// completion events. Handlers past the concurrency cap wait for a free slot before their
+    /// command (and its timeout) starts.
+    pub(crate) fn spawn<T: 'static>(
+        &self,
+        runtime: &HookRuntime,
+        handlers: Vec<ConfiguredHandler>,
+        input_json: &str,
+        cwd: PathBuf,
+        turn_id: Option<String>,
+        parse: fn(&ConfiguredHandler, CommandRunResult, Option<String>) -> ParsedHandler<T>,
+    ) -> AsyncHookRuns {
+        if handlers.is_empty() {
+            return AsyncHookRuns::default();
+        }
+
+        let input_json: Arc<str> = Arc::from(input_json);
+        let (completed_tx, completed_rx) = mpsc::unbounded_channel();
+        for handler in handlers {
+            let permits = Arc::clone(&self.permits);
+            let runtime = runtime.clone();
+            let input_json = Arc::clone(&input_json);
+            let cwd = cwd.clone();
+            let turn_id = turn_id.clone();
+            let completed_tx = completed_tx.clone();
+            tokio::spawn(async move {
+                // The semaphore is never closed, so this only fails if the runtime is shutting
+                // down; dropping the sender then reports nothing for this run.
+                let Ok(_permit) = permits.acquire_owned().await else {
+                    return;
+                };
+                let run_result =
+                    run_handler(&runtime, &handler, &input_json, &cwd, turn_id.as_deref()).await;
+                let mut completed = parse(&handler, run_result, turn_id).completed;
+                if matches!(
+                    completed.run.status,
+                    HookRunStatus::Blocked | HookRunStatus::Stopped
+                ) {
+                    completed.run.entries.push(HookOutputEntry {
+                        kind: HookOutputEntryKind::Warning,
+                        text: IGNORED_DECISION_WARNING.to_string(),
+                    });
+                }
+                let _ = completed_tx.send(completed);
+            });
+        }
+
+        AsyncHookRuns {
+            completed_rx: Some(completed_rx),
+            run_id_suffix: None,
+        }
+    }
+}
+
+/// Async hooks started by one hook event that may still be running.
+///
+/// The hooks keep running if this handle is dropped; only their completion events are lost.
+#[derive(Debug, Default)]
+pub struct AsyncHookRuns {
+    completed_rx: Option<mpsc::UnboundedReceiver<HookCompletedEvent>>,
+    run_id_suffix: Option<String>,
+}
+
+impl AsyncHookRuns {
+    /// Returns true when the event did not start any async hooks.
+    pub fn is_empty(&self) -> bool {
+        self.completed_rx.is_none()
+    }
+
+    /// Waits for the next async hook to finish, in completion order. Returns `None` once every
+    /// started hook has reported.
+    pub async fn next_completed(&mut self) -> Option<HookCompletedEvent> {
+        let completed = self.completed_rx.as_mut()?.recv().await?;
+        Some(match self.run_id_suffix.as_deref() {
+            Some(tool_use_id) => common::hook_completed_for_tool_use(completed, tool_use_id),
+            None => completed,
+        })
+    }
+
+    /// Scopes completion run ids to a tool call so they match the ids used in previews.
+    pub(crate) fn for_tool_use(mut self, tool_use_id: &str) -> Self {
+        self.run_id_suffix = Some(tool_use_id.to_string());
+        self
+    }
+}
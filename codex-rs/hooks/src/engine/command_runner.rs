@@ -8,7 +8,6 @@ use tokio::process::Command;
 use tokio::time::timeout;
 
 use super::CommandShell;
-use super::ConfiguredHandler;
 
 #[derive(Debug)]
 pub(crate) struct CommandRunResult {
@@ -23,14 +22,15 @@ pub(crate) struct CommandRunResult {
 
 pub(crate) async fn run_command(
     shell: &CommandShell,
-    handler: &ConfiguredHandler,
+    command_line: &str,
+    timeout_sec: u64,
     input_json: &str,
     cwd: &Path,
 ) -> CommandRunResult {
     let started_at = chrono::Utc::now().timestamp();
     let started = Instant::now();
 
-    let mut command = build_command(shell, handler);
+    let mut command = build_command(shell, command_line);
     command
         .current_dir(cwd)
         .stdin(Stdio::piped())
@@ -68,7 +68,7 @@ pub(crate) async fn run_command(
         };
     }
 
-    let timeout_duration = Duration::from_secs(handler.timeout_sec);
+    let timeout_duration = Duration::from_secs(timeout_sec);
     match timeout(timeout_duration, child.wait_with_output()).await {
         Ok(Ok(output)) => CommandRunResult {
             started_at,
@@ -95,23 +95,23 @@ pub(crate) async fn run_command(
             exit_code: None,
             stdout: String::new(),
             stderr: String::new(),
-            error: Some(format!("hook timed out after {}s", handler.timeout_sec)),
+            error: Some(format!("hook timed out after {timeout_sec}s")),
         },
     }
 }
 
-fn build_command(shell: &CommandShell, handler: &ConfiguredHandler) -> Command {
+fn build_command(shell: &CommandShell, command_line: &str) -> Command {
     let mut command = if shell.program.is_empty() {
         default_shell_command()
     } else {
         Command::new(&shell.program)
     };
     if shell.program.is_empty() {
-        command.arg(&handler.command);
+        command.arg(command_line);
         command
     } else {
         command.args(&shell.args);
-        command.arg(&handler.command);
+        command.arg(command_line);
         command
     }
 }
@@ -16,10 +16,16 @@ use codex_utils_absolute_path::AbsolutePathBuf;
 use serde::Deserialize;
 
 use super::ConfiguredHandler;
+use super::HandlerKind;
 use crate::events::common::matcher_pattern_for_event;
 use crate::events::common::validate_matcher_pattern;
+use codex_protocol::protocol::HookEventName;
+use codex_protocol::protocol::HookExecutionMode;
 use codex_protocol::protocol::HookSource;
 
+const DEFAULT_PROMPT_HOOK_TIMEOUT_SEC: u64 = 30;
+const DEFAULT_AGENT_HOOK_TIMEOUT_SEC: u64 = 120;
+
 pub(crate) struct DiscoveryResult {
     pub handlers: Vec<ConfiguredHandler>,
     pub warnings: Vec<String>,
@@ -326,20 +332,13 @@ fn append_group_handlers(
     }
 
     for handler in group_handlers {
-        match handler {
+        let (kind, timeout_sec, execution_mode, status_message) = match handler {
             HookHandlerConfig::Command {
                 command,
                 timeout_sec,
                 r#async,
                 status_message,
             } => {
-                if r#async {
-                    warnings.push(format!(
-                        "skipping async hook in {}: async hooks are not supported yet",
-                        source.path.display()
-                    ));
-                    continue;
-                }
                 if command.trim().is_empty() {
                     warnings.push(format!(
                         "skipping empty hook command in {}",
@@ -347,32 +346,96 @@ fn append_group_handlers(
                     ));
                     continue;
                 }
-                let timeout_sec = timeout_sec.unwrap_or(600).max(1);
-                handlers.push(ConfiguredHandler {
-                    event_name,
-                    is_managed: source.is_managed,
-                    matcher: matcher.map(ToOwned::to_owned),
-                    command,
-                    timeout_sec,
+                (
+                    HandlerKind::Command { command },
+                    timeout_sec.unwrap_or(600),
+                    if r#async {
+                        HookExecutionMode::Async
+                    } else {
+                        HookExecutionMode::Sync
+                    },
                     status_message,
-                    source_path: source.path.clone(),
-                    source: source.source,
-                    display_order: *display_order,
-                });
-                *display_order += 1;
+                )
             }
-            HookHandlerConfig::Prompt {} => warnings.push(format!(
-                "skipping prompt hook in {}: prompt hooks are not supported yet",
-                source.path.display()
-            )),
-            HookHandlerConfig::Agent {} => warnings.push(format!(
-                "skipping agent hook in {}: agent hooks are not supported yet",
-                source.path.display()
-            )),
-        }
+            // Prompt and agent hooks exist to return a decision, so they always run
+            // synchronously.
+            HookHandlerConfig::Prompt {
+                prompt,
+                model,
+                timeout_sec,
+                status_message,
+            } => {
+                if !model_hook_is_usable(warnings, source, event_name, "prompt", &prompt) {
+                    continue;
+                }
+                (
+                    HandlerKind::Prompt { prompt, model },
+                    timeout_sec.unwrap_or(DEFAULT_PROMPT_HOOK_TIMEOUT_SEC),
+                    HookExecutionMode::Sync,
+                    status_message,
+                )
+            }
+            HookHandlerConfig::Agent {
+                prompt,
+                model,
+                timeout_sec,
+                status_message,
+            } => {
+                if !model_hook_is_usable(warnings, source, event_name, "agent", &prompt) {
+                    continue;
+                }
+                (
+                    HandlerKind::Agent { prompt, model },
+                    timeout_sec.unwrap_or(DEFAULT_AGENT_HOOK_TIMEOUT_SEC),
+                    HookExecutionMode::Sync,
+                    status_message,
+                )
+            }
+        };
+        handlers.push(ConfiguredHandler {
+            event_name,
+            is_managed: source.is_managed,
+            matcher: matcher.map(ToOwned::to_owned),
+            kind,
+            timeout_sec: timeout_sec.max(1),
+            execution_mode,
+            status_message,
+            source_path: source.path.clone(),
+            source: source.source,
+            display_order: *display_order,
+        });
+        *display_order += 1;
     }
 }
 
+/// Prompt and agent hooks can only answer events whose outcome is a simple allow/block.
+fn model_hook_is_usable(
+    warnings: &mut Vec<String>,
+    source: HookHandlerSource<'_>,
+    event_name: HookEventName,
+    label: &str,
+    prompt: &str,
+) -> bool {
+    if !matches!(
+        event_name,
+        HookEventName::PreToolUse | HookEventName::UserPromptSubmit | HookEventName::Stop
+    ) {
+        warnings.push(format!(
+            "skipping {label} hook in {}: {label} hooks are only supported for PreToolUse, UserPromptSubmit, and Stop",
+            source.path.display()
+        ));
+        return false;
+    }
+    if prompt.trim().is_empty() {
+        warnings.push(format!(
+            "skipping {label} hook with an empty prompt in {}",
+            source.path.display()
+        ));
+        return false;
+    }
+    true
+}
+
 fn hook_source_for_config_layer_source(source: &ConfigLayerSource) -> HookSource {
     match source {
         ConfigLayerSource::System { .. } => HookSource::System,
@@ -407,6 +470,7 @@ fn hook_source_for_requirement_source(source: Option<&RequirementSource>) -> Hoo
 mod tests {
     use codex_config::ConfigLayerSource;
     use codex_protocol::protocol::HookEventName;
+    use codex_protocol::protocol::HookExecutionMode;
     use codex_protocol::protocol::HookSource;
     use codex_utils_absolute_path::AbsolutePathBuf;
     use codex_utils_absolute_path::test_support::PathBufExt;
@@ -414,6 +478,7 @@ mod tests {
     use pretty_assertions::assert_eq;
 
     use super::ConfiguredHandler;
+    use super::HandlerKind;
     use super::append_matcher_groups;
     use codex_config::HookHandlerConfig;
     use codex_config::MatcherGroup;
@@ -469,8 +534,11 @@ mod tests {
                 event_name: HookEventName::UserPromptSubmit,
                 is_managed: false,
                 matcher: None,
-                command: "echo hello".to_string(),
+                kind: HandlerKind::Command {
+                    command: "echo hello".to_string(),
+                },
                 timeout_sec: 600,
+                execution_mode: HookExecutionMode::Sync,
                 status_message: None,
                 source_path: source_path.clone(),
                 source: hook_source(),
@@ -502,8 +570,11 @@ mod tests {
                 event_name: HookEventName::PreToolUse,
                 is_managed: false,
                 matcher: Some("^Bash$".to_string()),
-                command: "echo hello".to_string(),
+                kind: HandlerKind::Command {
+                    command: "echo hello".to_string(),
+                },
                 timeout_sec: 600,
+                execution_mode: HookExecutionMode::Sync,
                 status_message: None,
                 source_path: source_path.clone(),
                 source: hook_source(),
@@ -555,6 +626,115 @@ mod tests {
         assert_eq!(handlers[0].matcher.as_deref(), Some("Edit|Write"));
     }
 
+    #[test]
+    fn async_command_hooks_are_discovered_as_async() {
+        let mut handlers = Vec::new();
+        let mut warnings = Vec::new();
+        let mut display_order = 0;
+        let source_path = source_path();
+
+        append_matcher_groups(
+            &mut handlers,
+            &mut warnings,
+            &mut display_order,
+            hook_handler_source(&source_path),
+            HookEventName::PostToolUse,
+            vec![MatcherGroup {
+                matcher: None,
+                hooks: vec![HookHandlerConfig::Command {
+                    command: "audit-log".to_string(),
+                    timeout_sec: Some(30),
+                    r#async: true,
+                    status_message: None,
+                }],
+            }],
+        );
+
+        assert_eq!(warnings, Vec::<String>::new());
+        assert_eq!(handlers.len(), 1);
+        assert_eq!(handlers[0].execution_mode, HookExecutionMode::Async);
+        assert_eq!(handlers[0].timeout_sec, 30);
+    }
+
+    #[test]
+    fn prompt_hooks_are_discovered_as_sync_with_default_timeout() {
+        let mut handlers = Vec::new();
+        let mut warnings = Vec::new();
+        let mut display_order = 0;
+        let source_path = source_path();
+
+        append_matcher_groups(
+            &mut handlers,
+            &mut warnings,
+            &mut display_order,
+            hook_handler_source(&source_path),
+            HookEventName::PreToolUse,
+            vec![MatcherGroup {
+                matcher: Some("^Bash$".to_string()),
+                hooks: vec![HookHandlerConfig::Prompt {
+                    prompt: "Does this command follow our conventions? $ARGUMENTS".to_string(),
+                    model: Some("gpt-5.1-codex-mini".to_string()),
+                    timeout_sec: None,
+                    status_message: None,
+                }],
+            }],
+        );
+
+        assert_eq!(warnings, Vec::<String>::new());
+        assert_eq!(
+            handlers,
+            vec![ConfiguredHandler {
+                event_name: HookEventName::PreToolUse,
+                is_managed: false,
+                matcher: Some("^Bash$".to_string()),
+                kind: HandlerKind::Prompt {
+                    prompt: "Does this command follow our conventions? $ARGUMENTS".to_string(),
+                    model: Some("gpt-5.1-codex-mini".to_string()),
+                },
+                timeout_sec: 30,
+                execution_mode: HookExecutionMode::Sync,
+                status_message: None,
+                source_path: source_path.clone(),
+                source: hook_source(),
+                display_order: 0,
+            }]
+        );
+    }
+
+    #[test]
+    fn agent_hooks_are_skipped_for_unsupported_events() {
+        let mut handlers = Vec::new();
+        let mut warnings = Vec::new();
+        let mut display_order = 0;
+        let source_path = source_path();
+
+        append_matcher_groups(
+            &mut handlers,
+            &mut warnings,
+            &mut display_order,
+            hook_handler_source(&source_path),
+            HookEventName::PostToolUse,
+            vec![MatcherGroup {
+                matcher: None,
+                hooks: vec![HookHandlerConfig::Agent {
+                    prompt: "Review the edit.".to_string(),
+                    model: None,
+                    timeout_sec: None,
+                    status_message: None,
+                }],
+            }],
+        );
+
+        assert_eq!(handlers, Vec::<ConfiguredHandler>::new());
+        assert_eq!(
+            warnings,
+            vec![format!(
+                "skipping agent hook in {}: agent hooks are only supported for PreToolUse, UserPromptSubmit, and Stop",
+                source_path.display()
+            )]
+        );
+    }
+
     #[test]
     fn hook_source_for_config_layer_source_discards_source_details() {
         let config_file = test_path_buf("/tmp/.codex/config.toml").abs();
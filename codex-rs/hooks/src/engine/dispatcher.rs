@@ -5,15 +5,17 @@ use futures::future::join_all;
 use codex_protocol::protocol::HookCompletedEvent;
 use codex_protocol::protocol::HookEventName;
 use codex_protocol::protocol::HookExecutionMode;
-use codex_protocol::protocol::HookHandlerType;
 use codex_protocol::protocol::HookRunStatus;
 use codex_protocol::protocol::HookRunSummary;
 use codex_protocol::protocol::HookScope;
 
-use super::CommandShell;
 use super::ConfiguredHandler;
+use super::HandlerKind;
+use super::HookRuntime;
+use super::async_hooks::AsyncHookRuns;
 use super::command_runner::CommandRunResult;
 use super::command_runner::run_command;
+use super::model_hooks::run_model_hook;
 use crate::events::common::matches_matcher;
 
 #[derive(Debug)]
@@ -46,7 +48,12 @@ pub(crate) fn select_handlers_for_matcher_inputs(
             HookEventName::PreToolUse
             | HookEventName::PermissionRequest
             | HookEventName::PostToolUse
-            | HookEventName::SessionStart => {
+            | HookEventName::SessionStart
+            | HookEventName::PreCompact
+            | HookEventName::SessionEnd
+            | HookEventName::SubagentStart
+            | HookEventName::SubagentStop
+            | HookEventName::Notification => {
                 if matcher_inputs.is_empty() {
                     matches_matcher(handler.matcher.as_deref(), /*input*/ None)
                 } else {
@@ -65,8 +72,8 @@ pub(crate) fn running_summary(handler: &ConfiguredHandler) -> HookRunSummary {
     HookRunSummary {
         id: handler.run_id(),
         event_name: handler.event_name,
-        handler_type: HookHandlerType::Command,
-        execution_mode: HookExecutionMode::Sync,
+        handler_type: handler.kind.handler_type(),
+        execution_mode: handler.execution_mode,
         scope: scope_for_event(handler.event_name),
         source_path: handler.source_path.clone(),
         source: handler.source,
@@ -80,26 +87,75 @@ pub(crate) fn running_summary(handler: &ConfiguredHandler) -> HookRunSummary {
     }
 }
 
-pub(crate) async fn execute_handlers<T>(
-    shell: &CommandShell,
+/// Runs sync handlers to completion and starts async handlers in the background.
+///
+/// Only sync results are returned for the caller to fold into its outcome; async handlers
+/// report through the returned [`AsyncHookRuns`] and never influence the outcome.
+pub(crate) async fn execute_handlers<T: 'static>(
+    runtime: &HookRuntime,
     handlers: Vec<ConfiguredHandler>,
     input_json: String,
     cwd: &Path,
     turn_id: Option<String>,
     parse: fn(&ConfiguredHandler, CommandRunResult, Option<String>) -> ParsedHandler<T>,
-) -> Vec<ParsedHandler<T>> {
+) -> (Vec<ParsedHandler<T>>, AsyncHookRuns) {
+    let (async_handlers, handlers): (Vec<_>, Vec<_>) = handlers
+        .into_iter()
+        .partition(|handler| handler.execution_mode == HookExecutionMode::Async);
+    let async_runs = runtime.async_hooks.spawn(
+        runtime,
+        async_handlers,
+        &input_json,
+        cwd.to_path_buf(),
+        turn_id.clone(),
+        parse,
+    );
+
     let results = join_all(
         handlers
             .iter()
-            .map(|handler| run_command(shell, handler, &input_json, cwd)),
+            .map(|handler| run_handler(runtime, handler, &input_json, cwd, turn_id.as_deref())),
     )
     .await;
 
-    handlers
+    let results = handlers
         .into_iter()
         .zip(results)
         .map(|(handler, result)| parse(&handler, result, turn_id.clone()))
-        .collect()
+        .collect();
+    (results, async_runs)
+}
+
+/// Runs one handler to completion, whatever its kind.
+pub(crate) async fn run_handler(
+    runtime: &HookRuntime,
+    handler: &ConfiguredHandler,
+    input_json: &str,
+    cwd: &Path,
+    turn_id: Option<&str>,
+) -> CommandRunResult {
+    match &handler.kind {
+        HandlerKind::Command { command } => {
+            run_command(
+                &runtime.shell,
+                command,
+                handler.timeout_sec,
+                input_json,
+                cwd,
+            )
+            .await
+        }
+        HandlerKind::Prompt { .. } | HandlerKind::Agent { .. } => {
+            run_model_hook(
+                runtime.model_hooks.as_deref(),
+                handler,
+                input_json,
+                cwd,
+                turn_id,
+            )
+            .await
+        }
+    }
 }
 
 pub(crate) fn completed_summary(
@@ -111,8 +167,8 @@ pub(crate) fn completed_summary(
     HookRunSummary {
         id: handler.run_id(),
         event_name: handler.event_name,
-        handler_type: HookHandlerType::Command,
-        execution_mode: HookExecutionMode::Sync,
+        handler_type: handler.kind.handler_type(),
+        execution_mode: handler.execution_mode,
         scope: scope_for_event(handler.event_name),
         source_path: handler.source_path.clone(),
         source: handler.source,
@@ -128,23 +184,29 @@ pub(crate) fn completed_summary(
 
 fn scope_for_event(event_name: HookEventName) -> HookScope {
     match event_name {
-        HookEventName::SessionStart => HookScope::Thread,
+        HookEventName::SessionStart | HookEventName::SessionEnd => HookScope::Thread,
         HookEventName::PreToolUse
         | HookEventName::PermissionRequest
         | HookEventName::PostToolUse
         | HookEventName::UserPromptSubmit
-        | HookEventName::Stop => HookScope::Turn,
+        | HookEventName::Stop
+        | HookEventName::PreCompact
+        | HookEventName::SubagentStart
+        | HookEventName::SubagentStop
+        | HookEventName::Notification => HookScope::Turn,
     }
 }
 
 #[cfg(test)]
 mod tests {
     use codex_protocol::protocol::HookEventName;
+    use codex_protocol::protocol::HookExecutionMode;
     use codex_protocol::protocol::HookSource;
     use codex_utils_absolute_path::test_support::PathBufExt;
     use codex_utils_absolute_path::test_support::test_path_buf;
 
     use super::ConfiguredHandler;
+    use super::HandlerKind;
     use super::select_handlers;
     use super::select_handlers_for_matcher_inputs;
 
@@ -158,8 +220,11 @@ mod tests {
             event_name,
             is_managed: false,
             matcher: matcher.map(str::to_owned),
-            command: command.to_string(),
+            kind: HandlerKind::Command {
+                command: command.to_string(),
+            },
             timeout_sec: 5,
+            execution_mode: HookExecutionMode::Sync,
             status_message: None,
             source_path: test_path_buf("/tmp/hooks.json").abs(),
             source: HookSource::User,
@@ -401,8 +466,23 @@ mod tests {
         let selected = select_handlers(&handlers, HookEventName::Stop, /*matcher_input*/ None);
 
         assert_eq!(selected.len(), 3);
-        assert_eq!(selected[0].command, "first");
-        assert_eq!(selected[1].command, "second");
-        assert_eq!(selected[2].command, "third");
+        assert_eq!(
+            selected[0].kind,
+            HandlerKind::Command {
+                command: "first".to_string(),
+            }
+        );
+        assert_eq!(
+            selected[1].kind,
+            HandlerKind::Command {
+                command: "second".to_string(),
+            }
+        );
+        assert_eq!(
+            selected[2].kind,
+            HandlerKind::Command {
+                command: "third".to_string(),
+            }
+        );
     }
 }
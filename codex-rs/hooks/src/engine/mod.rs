@@ -1,24 +1,42 @@
+pub(crate) mod async_hooks;
 pub(crate) mod command_runner;
 pub(crate) mod discovery;
 pub(crate) mod dispatcher;
+pub(crate) mod model_hooks;
 pub(crate) mod output_parser;
 pub(crate) mod schema_loader;
 
+use std::sync::Arc;
+
 use codex_config::ConfigLayerStack;
+use codex_protocol::protocol::HookExecutionMode;
+use codex_protocol::protocol::HookHandlerType;
 use codex_protocol::protocol::HookRunSummary;
 use codex_protocol::protocol::HookSource;
 use codex_utils_absolute_path::AbsolutePathBuf;
 
+use crate::engine::async_hooks::AsyncHookLimiter;
+use crate::engine::model_hooks::ModelHookRunner;
+use crate::events::notification::NotificationOutcome;
+use crate::events::notification::NotificationRequest;
 use crate::events::permission_request::PermissionRequestOutcome;
 use crate::events::permission_request::PermissionRequestRequest;
 use crate::events::post_tool_use::PostToolUseOutcome;
 use crate::events::post_tool_use::PostToolUseRequest;
+use crate::events::pre_compact::PreCompactOutcome;
+use crate::events::pre_compact::PreCompactRequest;
 use crate::events::pre_tool_use::PreToolUseOutcome;
 use crate::events::pre_tool_use::PreToolUseRequest;
+use crate::events::session_end::SessionEndOutcome;
+use crate::events::session_end::SessionEndRequest;
 use crate::events::session_start::SessionStartOutcome;
 use crate::events::session_start::SessionStartRequest;
 use crate::events::stop::StopOutcome;
 use crate::events::stop::StopRequest;
+use crate::events::subagent_start::SubagentStartOutcome;
+use crate::events::subagent_start::SubagentStartRequest;
+use crate::events::subagent_stop::SubagentStopOutcome;
+use crate::events::subagent_stop::SubagentStopRequest;
 use crate::events::user_prompt_submit::UserPromptSubmitOutcome;
 use crate::events::user_prompt_submit::UserPromptSubmitRequest;
 
@@ -28,13 +46,50 @@ pub(crate) struct CommandShell {
     pub args: Vec<String>,
 }
 
+/// What a configured handler runs when its event fires.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum HandlerKind {
+    Command {
+        command: String,
+    },
+    /// A single model call that evaluates the hook input and returns a decision.
+    Prompt {
+        prompt: String,
+        model: Option<String>,
+    },
+    /// A read-only sub-agent that can inspect the workspace before returning a decision.
+    Agent {
+        prompt: String,
+        model: Option<String>,
+    },
+}
+
+impl HandlerKind {
+    pub fn handler_type(&self) -> HookHandlerType {
+        match self {
+            Self::Command { .. } => HookHandlerType::Command,
+            Self::Prompt { .. } => HookHandlerType::Prompt,
+            Self::Agent { .. } => HookHandlerType::Agent,
+        }
+    }
+}
+
+/// Everything needed to execute a selected handler.
+#[derive(Clone)]
+pub(crate) struct HookRuntime {
+    pub shell: CommandShell,
+    pub async_hooks: AsyncHookLimiter,
+    pub model_hooks: Option<Arc<dyn ModelHookRunner>>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) struct ConfiguredHandler {
     pub event_name: codex_protocol::protocol::HookEventName,
     pub is_managed: bool,
     pub matcher: Option<String>,
-    pub command: String,
+    pub kind: HandlerKind,
     pub timeout_sec: u64,
+    pub execution_mode: HookExecutionMode,
     pub status_message: Option<String>,
     pub source_path: AbsolutePathBuf,
     pub source: HookSource,
@@ -59,6 +114,11 @@ impl ConfiguredHandler {
             codex_protocol::protocol::HookEventName::SessionStart => "session-start",
             codex_protocol::protocol::HookEventName::UserPromptSubmit => "user-prompt-submit",
             codex_protocol::protocol::HookEventName::Stop => "stop",
+            codex_protocol::protocol::HookEventName::PreCompact => "pre-compact",
+            codex_protocol::protocol::HookEventName::SessionEnd => "session-end",
+            codex_protocol::protocol::HookEventName::SubagentStart => "subagent-start",
+            codex_protocol::protocol::HookEventName::SubagentStop => "subagent-stop",
+            codex_protocol::protocol::HookEventName::Notification => "notification",
         }
     }
 }
@@ -67,7 +127,7 @@ impl ConfiguredHandler {
 pub(crate) struct ClaudeHooksEngine {
     handlers: Vec<ConfiguredHandler>,
     warnings: Vec<String>,
-    shell: CommandShell,
+    runtime: HookRuntime,
 }
 
 impl ClaudeHooksEngine {
@@ -75,12 +135,18 @@ impl ClaudeHooksEngine {
         enabled: bool,
         config_layer_stack: Option<&ConfigLayerStack>,
         shell: CommandShell,
+        model_hooks: Option<Arc<dyn ModelHookRunner>>,
     ) -> Self {
+        let runtime = HookRuntime {
+            shell,
+            async_hooks: AsyncHookLimiter::default(),
+            model_hooks,
+        };
         if !enabled {
             return Self {
                 handlers: Vec::new(),
                 warnings: Vec::new(),
-                shell,
+                runtime,
             };
         }
 
@@ -89,7 +155,7 @@ impl ClaudeHooksEngine {
         Self {
             handlers: discovered.handlers,
             warnings: discovered.warnings,
-            shell,
+            runtime,
         }
     }
 
@@ -97,6 +163,12 @@ impl ClaudeHooksEngine {
         &self.warnings
     }
 
+    pub(crate) fn has_handlers(&self, event_name: codex_protocol::protocol::HookEventName) -> bool {
+        self.handlers
+            .iter()
+            .any(|handler| handler.event_name == event_name)
+    }
+
     pub(crate) fn preview_session_start(
         &self,
         request: &SessionStartRequest,
@@ -127,25 +199,25 @@ impl ClaudeHooksEngine {
         request: SessionStartRequest,
         turn_id: Option<String>,
     ) -> SessionStartOutcome {
-        crate::events::session_start::run(&self.handlers, &self.shell, request, turn_id).await
+        crate::events::session_start::run(&self.handlers, &self.runtime, request, turn_id).await
     }
 
     pub(crate) async fn run_pre_tool_use(&self, request: PreToolUseRequest) -> PreToolUseOutcome {
-        crate::events::pre_tool_use::run(&self.handlers, &self.shell, request).await
+        crate::events::pre_tool_use::run(&self.handlers, &self.runtime, request).await
     }
 
     pub(crate) async fn run_permission_request(
         &self,
         request: PermissionRequestRequest,
     ) -> PermissionRequestOutcome {
-        crate::events::permission_request::run(&self.handlers, &self.shell, request).await
+        crate::events::permission_request::run(&self.handlers, &self.runtime, request).await
     }
 
     pub(crate) async fn run_post_tool_use(
         &self,
         request: PostToolUseRequest,
     ) -> PostToolUseOutcome {
-        crate::events::post_tool_use::run(&self.handlers, &self.shell, request).await
+        crate::events::post_tool_use::run(&self.handlers, &self.runtime, request).await
     }
 
     pub(crate) fn preview_user_prompt_submit(
@@ -159,7 +231,7 @@ impl ClaudeHooksEngine {
         &self,
         request: UserPromptSubmitRequest,
     ) -> UserPromptSubmitOutcome {
-        crate::events::user_prompt_submit::run(&self.handlers, &self.shell, request).await
+        crate::events::user_prompt_submit::run(&self.handlers, &self.runtime, request).await
     }
 
     pub(crate) fn preview_stop(&self, request: &StopRequest) -> Vec<HookRunSummary> {
@@ -167,7 +239,65 @@ impl ClaudeHooksEngine {
     }
 
     pub(crate) async fn run_stop(&self, request: StopRequest) -> StopOutcome {
-        crate::events::stop::run(&self.handlers, &self.shell, request).await
+        crate::events::stop::run(&self.handlers, &self.runtime, request).await
+    }
+
+    pub(crate) fn preview_pre_compact(&self, request: &PreCompactRequest) -> Vec<HookRunSummary> {
+        crate::events::pre_compact::preview(&self.handlers, request)
+    }
+
+    pub(crate) async fn run_pre_compact(&self, request: PreCompactRequest) -> PreCompactOutcome {
+        crate::events::pre_compact::run(&self.handlers, &self.runtime, request).await
+    }
+
+    pub(crate) fn preview_session_end(&self, request: &SessionEndRequest) -> Vec<HookRunSummary> {
+        crate::events::session_end::preview(&self.handlers, request)
+    }
+
+    pub(crate) async fn run_session_end(&self, request: SessionEndRequest) -> SessionEndOutcome {
+        crate::events::session_end::run(&self.handlers, &self.runtime, request).await
+    }
+
+    pub(crate) fn preview_subagent_start(
+        &self,
+        request: &SubagentStartRequest,
+    ) -> Vec<HookRunSummary> {
+        crate::events::subagent_start::preview(&self.handlers, request)
+    }
+
+    pub(crate) async fn run_subagent_start(
+        &self,
+        request: SubagentStartRequest,
+    ) -> SubagentStartOutcome {
+        crate::events::subagent_start::run(&self.handlers, &self.runtime, request).await
+    }
+
+    pub(crate) fn preview_subagent_stop(
+        &self,
+        request: &SubagentStopRequest,
+    ) -> Vec<HookRunSummary> {
+        crate::events::subagent_stop::preview(&self.handlers, request)
+    }
+
+    pub(crate) async fn run_subagent_stop(
+        &self,
+        request: SubagentStopRequest,
+    ) -> SubagentStopOutcome {
+        crate::events::subagent_stop::run(&self.handlers, &self.runtime, request).await
+    }
+
+    pub(crate) fn preview_notification(
+        &self,
+        request: &NotificationRequest,
+    ) -> Vec<HookRunSummary> {
+        crate::events::notification::preview(&self.handlers, request)
+    }
+
+    pub(crate) async fn run_notification(
+        &self,
+        request: NotificationRequest,
+    ) -> NotificationOutcome {
+        crate::events::notification::run(&self.handlers, &self.runtime, request).await
     }
 }
 
@@ -1,5 +1,8 @@
 use std::fs;
 use std::path::Path;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
 
 use codex_config::AbsolutePathBuf;
 use codex_config::ConfigLayerEntry;
@@ -16,11 +19,17 @@ use codex_config::MatcherGroup;
 use codex_config::RequirementSource;
 use codex_config::TomlValue;
 use codex_protocol::ThreadId;
+use codex_protocol::protocol::HookExecutionMode;
+use codex_protocol::protocol::HookHandlerType;
+use codex_protocol::protocol::HookOutputEntryKind;
+use codex_protocol::protocol::HookRunStatus;
 use pretty_assertions::assert_eq;
 use tempfile::tempdir;
 
 use super::ClaudeHooksEngine;
 use super::CommandShell;
+use super::model_hooks::ModelHookRequest;
+use super::model_hooks::ModelHookRunner;
 use crate::events::pre_tool_use::PreToolUseRequest;
 
 fn cwd() -> AbsolutePathBuf {
@@ -109,6 +118,7 @@ with Path(r"{log_path}").open("a", encoding="utf-8") as handle:
             program: String::new(),
             args: Vec::new(),
         },
+        /*model_hooks*/ None,
     );
 
     assert!(engine.warnings().is_empty());
@@ -192,6 +202,7 @@ fn requirements_managed_hooks_warn_when_managed_dir_is_missing() {
             program: String::new(),
             args: Vec::new(),
         },
+        /*model_hooks*/ None,
     );
 
     assert!(engine.warnings().iter().any(|warning| {
@@ -299,6 +310,7 @@ fn discovers_hooks_from_json_and_toml_in_the_same_layer() {
             program: String::new(),
             args: Vec::new(),
         },
+        /*model_hooks*/ None,
     );
 
     assert!(engine.warnings().iter().any(|warning| {
@@ -325,3 +337,216 @@ fn discovers_hooks_from_json_and_toml_in_the_same_layer() {
     assert_eq!(preview[0].source_path, hooks_json_path);
     assert_eq!(preview[1].source_path, config_path);
 }
+
+#[tokio::test]
+async fn async_hooks_report_completion_without_blocking_the_event() {
+    let temp = tempdir().expect("create temp dir");
+    let config_path =
+        AbsolutePathBuf::try_from(temp.path().join("config.toml")).expect("absolute config path");
+    let script_path = temp.path().join("async_hook.py");
+    let release_path = temp.path().join("release");
+    fs::write(
+        &script_path,
+        format!(
+            r#"import sys
+import time
+from pathlib import Path
+
+while not Path(r"{release_path}").exists():
+    time.sleep(0.01)
+sys.stderr.write("async hooks cannot block")
+sys.exit(2)
+"#,
+            release_path = release_path.display(),
+        ),
+    )
+    .expect("write async hook script");
+    fs::write(
+        temp.path().join("hooks.json"),
+        serde_json::json!({
+            "hooks": {
+                "PreToolUse": [{
+                    "matcher": "^Bash$",
+                    "hooks": [{
+                        "type": "command",
+                        "command": format!("python3 {}", script_path.display()),
+                        "timeout": 10,
+                        "async": true,
+                    }],
+                }],
+            },
+        })
+        .to_string(),
+    )
+    .expect("write hooks.json");
+    let config_layer_stack = ConfigLayerStack::new(
+        vec![ConfigLayerEntry::new(
+            ConfigLayerSource::User { file: config_path },
+            TomlValue::Table(Default::default()),
+        )],
+        ConfigRequirements::default(),
+        ConfigRequirementsToml::default(),
+    )
+    .expect("config layer stack");
+    let engine = ClaudeHooksEngine::new(
+        /*enabled*/ true,
+        Some(&config_layer_stack),
+        CommandShell {
+            program: String::new(),
+            args: Vec::new(),
+        },
+        /*model_hooks*/ None,
+    );
+    assert_eq!(engine.warnings(), &[] as &[String]);
+
+    let request = PreToolUseRequest {
+        session_id: ThreadId::new(),
+        turn_id: "turn-1".to_string(),
+        cwd: cwd(),
+        transcript_path: None,
+        model: "gpt-test".to_string(),
+        permission_mode: "default".to_string(),
+        tool_name: "Bash".to_string(),
+        matcher_aliases: Vec::new(),
+        tool_use_id: "tool-1".to_string(),
+        tool_input: serde_json::json!({ "command": "echo hello" }),
+    };
+    let preview = engine.preview_pre_tool_use(&request);
+    assert_eq!(preview.len(), 1);
+    assert_eq!(preview[0].execution_mode, HookExecutionMode::Async);
+
+    // The hook is still waiting for the release file, so the event must not wait for it.
+    let mut outcome = engine.run_pre_tool_use(request).await;
+    assert!(!outcome.should_block);
+    assert!(outcome.hook_events.is_empty());
+    assert!(!outcome.async_hooks.is_empty());
+
+    fs::write(&release_path, "").expect("release async hook");
+    let completed = tokio::time::timeout(
+        Duration::from_secs(10),
+        outcome.async_hooks.next_completed(),
+    )
+    .await
+    .expect("async hook should finish")
+    .expect("async hook completion");
+    assert_eq!(completed.turn_id.as_deref(), Some("turn-1"));
+    assert_eq!(completed.run.id, preview[0].id);
+    assert_eq!(completed.run.execution_mode, HookExecutionMode::Async);
+    assert_eq!(completed.run.status, HookRunStatus::Blocked);
+    assert!(
+        completed
+            .run
+            .entries
+            .iter()
+            .any(|entry| entry.kind == HookOutputEntryKind::Warning)
+    );
+    assert!(outcome.async_hooks.next_completed().await.is_none());
+}
+
+struct FakeModelHookRunner {
+    answer: String,
+    requests: Mutex<Vec<ModelHookRequest>>,
+}
+
+impl ModelHookRunner for FakeModelHookRunner {
+    fn run(
+        &self,
+        request: ModelHookRequest,
+    ) -> futures::future::BoxFuture<'static, Result<String, String>> {
+        self.requests.lock().expect("requests lock").push(request);
+        let answer = self.answer.clone();
+        Box::pin(async move { Ok(answer) })
+    }
+}
+
+#[tokio::test]
+async fn prompt_hooks_block_pre_tool_use_with_model_reason() {
+    let temp = tempdir().expect("create temp dir");
+    let managed_dir =
+        AbsolutePathBuf::try_from(temp.path().join("managed-hooks")).expect("absolute path");
+    fs::create_dir_all(managed_dir.as_path()).expect("create managed hooks dir");
+    let managed_hooks = managed_hooks_for_current_platform(
+        managed_dir,
+        HookEventsToml {
+            pre_tool_use: vec![MatcherGroup {
+                matcher: Some("^Bash$".to_string()),
+                hooks: vec![HookHandlerConfig::Prompt {
+                    prompt: "Does this follow our conventions? $ARGUMENTS".to_string(),
+                    model: Some("gpt-review".to_string()),
+                    timeout_sec: Some(10),
+                    status_message: None,
+                }],
+            }],
+            ..Default::default()
+        },
+    );
+    let config_layer_stack = ConfigLayerStack::new(
+        Vec::new(),
+        ConfigRequirements {
+            managed_hooks: Some(ConstrainedWithSource::new(
+                Constrained::allow_any(managed_hooks.clone()),
+                Some(RequirementSource::CloudRequirements),
+            )),
+            ..ConfigRequirements::default()
+        },
+        ConfigRequirementsToml {
+            hooks: Some(managed_hooks),
+            ..ConfigRequirementsToml::default()
+        },
+    )
+    .expect("config layer stack");
+    let runner = Arc::new(FakeModelHookRunner {
+        answer: r#"{"ok": false, "reason": "use rg instead of grep"}"#.to_string(),
+        requests: Mutex::new(Vec::new()),
+    });
+
+    let engine = ClaudeHooksEngine::new(
+        /*enabled*/ true,
+        Some(&config_layer_stack),
+        CommandShell {
+            program: String::new(),
+            args: Vec::new(),
+        },
+        Some(Arc::clone(&runner) as Arc<dyn ModelHookRunner>),
+    );
+    assert_eq!(engine.warnings(), &[] as &[String]);
+
+    let outcome = engine
+        .run_pre_tool_use(PreToolUseRequest {
+            session_id: ThreadId::new(),
+            turn_id: "turn-1".to_string(),
+            cwd: cwd(),
+            transcript_path: None,
+            model: "gpt-test".to_string(),
+            permission_mode: "default".to_string(),
+            tool_name: "Bash".to_string(),
+            matcher_aliases: Vec::new(),
+            tool_use_id: "tool-1".to_string(),
+            tool_input: serde_json::json!({ "command": "grep -r foo" }),
+        })
+        .await;
+
+    assert!(outcome.should_block);
+    assert_eq!(
+        outcome.block_reason.as_deref(),
+        Some("use rg instead of grep")
+    );
+    assert_eq!(outcome.hook_events.len(), 1);
+    assert_eq!(
+        outcome.hook_events[0].run.handler_type,
+        HookHandlerType::Prompt
+    );
+    assert_eq!(outcome.hook_events[0].run.status, HookRunStatus::Blocked);
+
+    let requests = runner.requests.lock().expect("requests lock");
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].handler_type, HookHandlerType::Prompt);
+    assert_eq!(requests[0].model.as_deref(), Some("gpt-review"));
+    assert_eq!(requests[0].turn_id.as_deref(), Some("turn-1"));
+    assert!(
+        requests[0]
+            .prompt
+            .starts_with("Does this follow our conventions? {")
+    );
+    assert!(requests[0].prompt.contains("grep -r foo"));
+}
@@ -0,0 +1,213 @@
+//! Prompt and agent hook execution.
+//!
+//! Both handler types hand the hook input to a model instead of a shell command. A `prompt`
+//! hook is a single model call; an `agent` hook runs a short-lived read-only sub-agent that can
+//! inspect the workspace first. The hooks crate cannot talk to a model itself, so the host
+//! supplies a [`ModelHookRunner`] and this module only renders the request and maps the answer
+//! back onto a [`CommandRunResult`]. That keeps every event parser unchanged: an allow is an exit
+//! code 0 (with the reason surfaced as a `systemMessage`), and a block is exit code 2 with the
+//! reason on stderr, exactly like a command hook.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::time::Instant;
+
+use codex_protocol::protocol::HookEventName;
+use codex_protocol::protocol::HookHandlerType;
+use futures::future::BoxFuture;
+use serde::Deserialize;
+use serde_json::Value;
+use serde_json::json;
+use tokio::time::timeout;
+
+use super::ConfiguredHandler;
+use super::HandlerKind;
+use super::command_runner::CommandRunResult;
+
+/// Placeholder in a hook prompt that is replaced with the hook input JSON.
+const ARGUMENTS_PLACEHOLDER: &str = "$ARGUMENTS";
+
+const MODEL_HOOK_INSTRUCTIONS: &str = "You are a policy check running inside a coding agent's \
+hook system. You receive a hook event as JSON together with the reviewer's question. Decide \
+whether the action described by the event should go ahead. Answer with `ok: true` to allow it, \
+or `ok: false` to block it. Always explain the decision in `reason`; when blocking, write the \
+reason as feedback the agent can act on.";
+
+/// Executes prompt and agent hooks on behalf of the hook engine.
+pub trait ModelHookRunner: Send + Sync {
+    /// Runs the request and returns the model's final message, which should be JSON matching
+    /// [`ModelHookRequest::output_schema`].
+    fn run(&self, request: ModelHookRequest) -> BoxFuture<'static, Result<String, String>>;
+}
+
+/// One prompt or agent hook invocation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelHookRequest {
+    /// Either [`HookHandlerType::Prompt`] or [`HookHandlerType::Agent`].
+    pub handler_type: HookHandlerType,
+    pub event_name: HookEventName,
+    pub turn_id: Option<String>,
+    pub cwd: PathBuf,
+    /// Model override from the hook config; `None` uses the session model.
+    pub model: Option<String>,
+    /// System-level instructions describing the expected answer.
+    pub instructions: String,
+    /// The configured prompt with the hook input JSON substituted in.
+    pub prompt: String,
+    /// JSON schema the final answer must follow.
+    pub output_schema: Value,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ModelHookAnswer {
+    ok: bool,
+    reason: Option<String>,
+}
+
+pub(crate) fn output_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "ok": { "type": "boolean" },
+            "reason": { "type": ["string", "null"] },
+        },
+        "required": ["ok", "reason"],
+        "additionalProperties": false,
+    })
+}
+
+pub(crate) fn render_prompt(prompt: &str, input_json: &str) -> String {
+    if prompt.contains(ARGUMENTS_PLACEHOLDER) {
+        prompt.replace(ARGUMENTS_PLACEHOLDER, input_json)
+    } else {
+        format!("{prompt}\n\nHook input:\n{input_json}")
+    }
+}
+
+pub(crate) async fn run_model_hook(
+    runner: Option<&dyn ModelHookRunner>,
+    handler: &ConfiguredHandler,
+    input_json: &str,
+    cwd: &Path,
+    turn_id: Option<&str>,
+) -> CommandRunResult {
+    let started_at = chrono::Utc::now().timestamp();
+    let started = Instant::now();
+    let finish = |exit_code: Option<i32>, stdout: String, stderr: String, error: Option<String>| {
+        CommandRunResult {
+            started_at,
+            completed_at: chrono::Utc::now().timestamp(),
+            duration_ms: started.elapsed().as_millis().try_into().unwrap_or(i64::MAX),
+            exit_code,
+            stdout,
+            stderr,
+            error,
+        }
+    };
+
+    let (label, prompt, model) = match &handler.kind {
+        HandlerKind::Prompt { prompt, model } => ("prompt", prompt, model),
+        HandlerKind::Agent { prompt, model } => ("agent", prompt, model),
+        HandlerKind::Command { .. } => {
+            return finish(
+                None,
+                String::new(),
+                String::new(),
+                Some("command hooks cannot run as model hooks".to_string()),
+            );
+        }
+    };
+    let Some(runner) = runner else {
+        return finish(
+            None,
+            String::new(),
+            String::new(),
+            Some(format!("{label} hooks are not available in this session")),
+        );
+    };
+
+    let request = ModelHookRequest {
+        handler_type: handler.kind.handler_type(),
+        event_name: handler.event_name,
+        turn_id: turn_id.map(ToOwned::to_owned),
+        cwd: cwd.to_path_buf(),
+        model: model.clone(),
+        instructions: MODEL_HOOK_INSTRUCTIONS.to_string(),
+        prompt: render_prompt(prompt, input_json),
+        output_schema: output_schema(),
+    };
+    let answer = match timeout(
+        Duration::from_secs(handler.timeout_sec),
+        runner.run(request),
+    )
+    .await
+    {
+        Ok(Ok(answer)) => answer,
+        Ok(Err(err)) => {
+            return finish(
+                None,
+                String::new(),
+                String::new(),
+                Some(format!("{label} hook failed: {err}")),
+            );
+        }
+        Err(_) => {
+            return finish(
+                None,
+                String::new(),
+                String::new(),
+                Some(format!(
+                    "{label} hook timed out after {}s",
+                    handler.timeout_sec
+                )),
+            );
+        }
+    };
+
+    match serde_json::from_str::<ModelHookAnswer>(answer.trim()) {
+        Ok(ModelHookAnswer { ok: true, reason }) => {
+            let stdout = reason
+                .filter(|reason| !reason.trim().is_empty())
+                .map(|reason| json!({ "systemMessage": reason }).to_string())
+                .unwrap_or_default();
+            finish(Some(0), stdout, String::new(), None)
+        }
+        Ok(ModelHookAnswer { ok: false, reason }) => {
+            let reason = reason
+                .filter(|reason| !reason.trim().is_empty())
+                .unwrap_or_else(|| format!("blocked by {label} hook"));
+            finish(Some(2), String::new(), reason, None)
+        }
+        Err(err) => finish(
+            None,
+            String::new(),
+            String::new(),
+            Some(format!("{label} hook returned an invalid answer: {err}")),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::render_prompt;
+
+    #[test]
+    fn render_prompt_substitutes_arguments_placeholder() {
+        assert_eq!(
+            render_prompt("Check this: $ARGUMENTS", r#"{"a":1}"#),
+            r#"Check this: {"a":1}"#
+        );
+    }
+
+    #[test]
+    fn render_prompt_appends_input_without_placeholder() {
+        assert_eq!(
+            render_prompt("Check this.", r#"{"a":1}"#),
+            "Check this.\n\nHook input:\n{\"a\":1}"
+        );
+    }
+}
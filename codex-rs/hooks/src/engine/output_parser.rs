@@ -12,10 +12,18 @@ pub(crate) struct SessionStartOutput {
     pub additional_context: Option<String>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum PreToolUsePermissionDecision {
+    Allow,
+    Ask { reason: Option<String> },
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct PreToolUseOutput {
     pub universal: UniversalOutput,
     pub block_reason: Option<String>,
+    pub permission_decision: Option<PreToolUsePermissionDecision>,
+    pub updated_input: Option<serde_json::Value>,
     pub invalid_reason: Option<String>,
 }
 
@@ -59,17 +67,36 @@ pub(crate) struct StopOutput {
     pub invalid_block_reason: Option<String>,
 }
 
+#[derive(Debug, Clone)]
+pub(crate) struct PreCompactOutput {
+    pub universal: UniversalOutput,
+    pub additional_context: Option<String>,
+    pub invalid_reason: Option<String>,
+}
+
+/// Output of events that only observe Codex and cannot change what happens next.
+#[derive(Debug, Clone)]
+pub(crate) struct ObserverOutput {
+    pub universal: UniversalOutput,
+    pub invalid_reason: Option<String>,
+}
+
 use crate::schema::BlockDecisionWire;
 use crate::schema::HookUniversalOutputWire;
+use crate::schema::NotificationCommandOutputWire;
 use crate::schema::PermissionRequestBehaviorWire;
 use crate::schema::PermissionRequestCommandOutputWire;
 use crate::schema::PermissionRequestDecisionWire;
 use crate::schema::PostToolUseCommandOutputWire;
+use crate::schema::PreCompactCommandOutputWire;
 use crate::schema::PreToolUseCommandOutputWire;
 use crate::schema::PreToolUseDecisionWire;
 use crate::schema::PreToolUsePermissionDecisionWire;
+use crate::schema::SessionEndCommandOutputWire;
 use crate::schema::SessionStartCommandOutputWire;
 use crate::schema::StopCommandOutputWire;
+use crate::schema::SubagentStartCommandOutputWire;
+use crate::schema::SubagentStopCommandOutputWire;
 use crate::schema::UserPromptSubmitCommandOutputWire;
 
 pub(crate) fn parse_session_start(stdout: &str) -> Option<SessionStartOutput> {
@@ -105,6 +132,26 @@ pub(crate) fn parse_pre_tool_use(stdout: &str) -> Option<PreToolUseOutput> {
             unsupported_pre_tool_use_legacy_decision(decision.as_ref(), reason.as_deref())
         }
     });
+    let valid_hook_specific_output =
+        hook_specific_output.filter(|_| invalid_reason.is_none() && use_hook_specific_decision);
+    let permission_decision =
+        valid_hook_specific_output.and_then(|output| match output.permission_decision {
+            Some(PreToolUsePermissionDecisionWire::Allow) => {
+                Some(PreToolUsePermissionDecision::Allow)
+            }
+            Some(PreToolUsePermissionDecisionWire::Ask) => {
+                Some(PreToolUsePermissionDecision::Ask {
+                    reason: output
+                        .permission_decision_reason
+                        .as_deref()
+                        .and_then(trimmed_reason),
+                })
+            }
+            Some(PreToolUsePermissionDecisionWire::Deny) | None => None,
+        });
+    let updated_input = valid_hook_specific_output
+        .filter(|output| output.permission_decision != Some(PreToolUsePermissionDecisionWire::Deny))
+        .and_then(|output| output.updated_input.clone());
     let block_reason = if invalid_reason.is_none() {
         if use_hook_specific_decision {
             hook_specific_output.and_then(|output| match output.permission_decision {
@@ -127,6 +174,8 @@ pub(crate) fn parse_pre_tool_use(stdout: &str) -> Option<PreToolUseOutput> {
     Some(PreToolUseOutput {
         universal,
         block_reason,
+        permission_decision,
+        updated_input,
         invalid_reason,
     })
 }
@@ -232,6 +281,49 @@ pub(crate) fn parse_stop(stdout: &str) -> Option<StopOutput> {
     })
 }
 
+pub(crate) fn parse_pre_compact(stdout: &str) -> Option<PreCompactOutput> {
+    let wire: PreCompactCommandOutputWire = parse_json(stdout)?;
+    let universal = UniversalOutput::from(wire.universal);
+    let invalid_reason = unsupported_observer_universal("PreCompact", &universal);
+    let additional_context = wire
+        .hook_specific_output
+        .and_then(|output| output.additional_context);
+    Some(PreCompactOutput {
+        universal,
+        additional_context,
+        invalid_reason,
+    })
+}
+
+pub(crate) fn parse_session_end(stdout: &str) -> Option<ObserverOutput> {
+    let wire: SessionEndCommandOutputWire = parse_json(stdout)?;
+    Some(observer_output("SessionEnd", wire.universal))
+}
+
+pub(crate) fn parse_subagent_start(stdout: &str) -> Option<ObserverOutput> {
+    let wire: SubagentStartCommandOutputWire = parse_json(stdout)?;
+    Some(observer_output("SubagentStart", wire.universal))
+}
+
+pub(crate) fn parse_subagent_stop(stdout: &str) -> Option<ObserverOutput> {
+    let wire: SubagentStopCommandOutputWire = parse_json(stdout)?;
+    Some(observer_output("SubagentStop", wire.universal))
+}
+
+pub(crate) fn parse_notification(stdout: &str) -> Option<ObserverOutput> {
+    let wire: NotificationCommandOutputWire = parse_json(stdout)?;
+    Some(observer_output("Notification", wire.universal))
+}
+
+fn observer_output(event_name: &str, universal: HookUniversalOutputWire) -> ObserverOutput {
+    let universal = UniversalOutput::from(universal);
+    let invalid_reason = unsupported_observer_universal(event_name, &universal);
+    ObserverOutput {
+        universal,
+        invalid_reason,
+    }
+}
+
 impl From<HookUniversalOutputWire> for UniversalOutput {
     fn from(value: HookUniversalOutputWire) -> Self {
         Self {
@@ -274,6 +366,22 @@ fn unsupported_pre_tool_use_universal(universal: &UniversalOutput) -> Option<Str
     }
 }
 
+fn unsupported_observer_universal(event_name: &str, universal: &UniversalOutput) -> Option<String> {
+    if !universal.continue_processing {
+        Some(format!(
+            "{event_name} hook returned unsupported continue:false"
+        ))
+    } else if universal.stop_reason.is_some() {
+        Some(format!("{event_name} hook returned unsupported stopReason"))
+    } else if universal.suppress_output {
+        Some(format!(
+            "{event_name} hook returned unsupported suppressOutput"
+        ))
+    } else {
+        None
+    }
+}
+
 fn unsupported_permission_request_universal(universal: &UniversalOutput) -> Option<String> {
     if !universal.continue_processing {
         Some("PermissionRequest hook returned unsupported continue:false".to_string())
@@ -337,8 +445,12 @@ fn unsupported_post_tool_use_hook_specific_output(
 fn unsupported_pre_tool_use_hook_specific_output(
     output: &crate::schema::PreToolUseHookSpecificOutputWire,
 ) -> Option<String> {
-    if output.updated_input.is_some() {
-        Some("PreToolUse hook returned unsupported updatedInput".to_string())
+    if output
+        .updated_input
+        .as_ref()
+        .is_some_and(|updated_input| !updated_input.is_object())
+    {
+        Some("PreToolUse hook returned updatedInput that is not a JSON object".to_string())
     } else if output
         .additional_context
         .as_deref()
@@ -348,12 +460,8 @@ fn unsupported_pre_tool_use_hook_specific_output(
         Some("PreToolUse hook returned unsupported additionalContext".to_string())
     } else {
         match output.permission_decision {
-            Some(PreToolUsePermissionDecisionWire::Allow) => {
-                Some("PreToolUse hook returned unsupported permissionDecision:allow".to_string())
-            }
-            Some(PreToolUsePermissionDecisionWire::Ask) => {
-                Some("PreToolUse hook returned unsupported permissionDecision:ask".to_string())
-            }
+            Some(PreToolUsePermissionDecisionWire::Allow)
+            | Some(PreToolUsePermissionDecisionWire::Ask) => None,
             Some(PreToolUsePermissionDecisionWire::Deny) => {
                 if output
                     .permission_decision_reason
@@ -422,6 +530,8 @@ mod tests {
     use serde_json::json;
 
     use super::parse_permission_request;
+    use super::parse_pre_compact;
+    use super::parse_subagent_stop;
 
     #[test]
     fn permission_request_rejects_reserved_updated_input_field() {
@@ -491,4 +601,35 @@ mod tests {
             Some("PermissionRequest hook returned unsupported interrupt:true".to_string())
         );
     }
+
+    #[test]
+    fn pre_compact_keeps_additional_context() {
+        let parsed = parse_pre_compact(
+            &json!({
+                "hookSpecificOutput": {
+                    "hookEventName": "PreCompact",
+                    "additionalContext": "remember the migration plan"
+                }
+            })
+            .to_string(),
+        )
+        .expect("pre compact hook output should parse");
+
+        assert_eq!(parsed.invalid_reason, None);
+        assert_eq!(
+            parsed.additional_context,
+            Some("remember the migration plan".to_string())
+        );
+    }
+
+    #[test]
+    fn observer_events_reject_continue_false() {
+        let parsed = parse_subagent_stop(&json!({ "continue": false }).to_string())
+            .expect("subagent stop hook output should parse");
+
+        assert_eq!(
+            parsed.invalid_reason,
+            Some("SubagentStop hook returned unsupported continue:false".to_string())
+        );
+    }
 }
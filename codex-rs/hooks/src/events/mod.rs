@@ -1,7 +1,12 @@
 pub(crate) mod common;
+pub mod notification;
 pub mod permission_request;
 pub mod post_tool_use;
+pub mod pre_compact;
 pub mod pre_tool_use;
+pub mod session_end;
 pub mod session_start;
 pub mod stop;
+pub mod subagent_start;
+pub mod subagent_stop;
 pub mod user_prompt_submit;
@@ -0,0 +1,134 @@
+use std::path::PathBuf;
+
+use codex_protocol::ThreadId;
+use codex_protocol::protocol::HookCompletedEvent;
+use codex_protocol::protocol::HookEventName;
+use codex_protocol::protocol::HookRunSummary;
+use codex_utils_absolute_path::AbsolutePathBuf;
+
+use super::common;
+use crate::engine::ConfiguredHandler;
+use crate::engine::HookRuntime;
+use crate::engine::async_hooks::AsyncHookRuns;
+use crate::engine::command_runner::CommandRunResult;
+use crate::engine::dispatcher;
+use crate::engine::output_parser;
+use crate::schema::NotificationCommandInput;
+use crate::schema::NullableString;
+
+#[derive(Debug, Clone, Copy)]
+pub enum NotificationType {
+    /// The agent is blocked until the user answers an approval prompt.
+    PermissionPrompt,
+}
+
+impl NotificationType {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::PermissionPrompt => "permission_prompt",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct NotificationRequest {
+    pub session_id: ThreadId,
+    pub turn_id: String,
+    pub cwd: AbsolutePathBuf,
+    pub transcript_path: Option<PathBuf>,
+    pub model: String,
+    pub permission_mode: String,
+    pub notification_type: NotificationType,
+    pub message: String,
+}
+
+#[derive(Debug)]
+pub struct NotificationOutcome {
+    pub hook_events: Vec<HookCompletedEvent>,
+    pub async_hooks: AsyncHookRuns,
+}
+
+pub(crate) fn preview(
+    handlers: &[ConfiguredHandler],
+    request: &NotificationRequest,
+) -> Vec<HookRunSummary> {
+    dispatcher::select_handlers(
+        handlers,
+        HookEventName::Notification,
+        Some(request.notification_type.as_str()),
+    )
+    .into_iter()
+    .map(|handler| dispatcher::running_summary(&handler))
+    .collect()
+}
+
+pub(crate) async fn run(
+    handlers: &[ConfiguredHandler],
+    runtime: &HookRuntime,
+    request: NotificationRequest,
+) -> NotificationOutcome {
+    let matched = dispatcher::select_handlers(
+        handlers,
+        HookEventName::Notification,
+        Some(request.notification_type.as_str()),
+    );
+    if matched.is_empty() {
+        return NotificationOutcome {
+            hook_events: Vec::new(),
+            async_hooks: AsyncHookRuns::default(),
+        };
+    }
+
+    let input_json = match serde_json::to_string(&NotificationCommandInput {
+        session_id: request.session_id.to_string(),
+        turn_id: request.turn_id.clone(),
+        transcript_path: NullableString::from_path(request.transcript_path.clone()),
+        cwd: request.cwd.display().to_string(),
+        hook_event_name: "Notification".to_string(),
+        model: request.model.clone(),
+        permission_mode: request.permission_mode.clone(),
+        notification_type: request.notification_type.as_str().to_string(),
+        message: request.message.clone(),
+    }) {
+        Ok(input_json) => input_json,
+        Err(error) => {
+            return NotificationOutcome {
+                hook_events: common::serialization_failure_hook_events(
+                    matched,
+                    Some(request.turn_id),
+                    format!("failed to serialize notification hook input: {error}"),
+                ),
+                async_hooks: AsyncHookRuns::default(),
+            };
+        }
+    };
+
+    let (results, async_runs) = dispatcher::execute_handlers(
+        runtime,
+        matched,
+        input_json,
+        request.cwd.as_path(),
+        Some(request.turn_id),
+        parse_completed,
+    )
+    .await;
+
+    NotificationOutcome {
+        hook_events: results.into_iter().map(|result| result.completed).collect(),
+        async_hooks: async_runs,
+    }
+}
+
+fn parse_completed(
+    handler: &ConfiguredHandler,
+    run_result: CommandRunResult,
+    turn_id: Option<String>,
+) -> dispatcher::ParsedHandler<()> {
+    common::parse_observer_completed(
+        handler,
+        run_result,
+        turn_id,
+        "notification",
+        output_parser::parse_notification,
+    )
+}
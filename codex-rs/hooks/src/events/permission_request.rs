@@ -16,8 +16,9 @@
 use std::path::PathBuf;
 
 use super::common;
-use crate::engine::CommandShell;
 use crate::engine::ConfiguredHandler;
+use crate::engine::HookRuntime;
+use crate::engine::async_hooks::AsyncHookRuns;
 use crate::engine::command_runner::CommandRunResult;
 use crate::engine::dispatcher;
 use crate::engine::output_parser;
@@ -54,6 +55,7 @@ pub enum PermissionRequestDecision {
 #[derive(Debug)]
 pub struct PermissionRequestOutcome {
     pub hook_events: Vec<HookCompletedEvent>,
+    pub async_hooks: AsyncHookRuns,
     pub decision: Option<PermissionRequestDecision>,
 }
 
@@ -84,7 +86,7 @@ pub(crate) fn preview(
 
 pub(crate) async fn run(
     handlers: &[ConfiguredHandler],
-    shell: &CommandShell,
+    runtime: &HookRuntime,
     request: PermissionRequestRequest,
 ) -> PermissionRequestOutcome {
     let matcher_inputs = common::matcher_inputs(&request.tool_name, &request.matcher_aliases);
@@ -96,6 +98,7 @@ pub(crate) async fn run(
     if matched.is_empty() {
         return PermissionRequestOutcome {
             hook_events: Vec::new(),
+            async_hooks: AsyncHookRuns::default(),
             decision: None,
         };
     }
@@ -111,13 +114,14 @@ pub(crate) async fn run(
             );
             return PermissionRequestOutcome {
                 hook_events,
+                async_hooks: AsyncHookRuns::default(),
                 decision: None,
             };
         }
     };
 
-    let results = dispatcher::execute_handlers(
-        shell,
+    let (results, async_runs) = dispatcher::execute_handlers(
+        runtime,
         matched,
         input_json,
         request.cwd.as_path(),
@@ -141,6 +145,7 @@ pub(crate) async fn run(
                 common::hook_completed_for_tool_use(result.completed, &request.run_id_suffix)
             })
             .collect(),
+        async_hooks: async_runs.for_tool_use(&request.run_id_suffix),
         decision,
     }
 }
@@ -11,8 +11,9 @@ use codex_utils_absolute_path::AbsolutePathBuf;
 use serde_json::Value;
 
 use super::common;
-use crate::engine::CommandShell;
 use crate::engine::ConfiguredHandler;
+use crate::engine::HookRuntime;
+use crate::engine::async_hooks::AsyncHookRuns;
 use crate::engine::command_runner::CommandRunResult;
 use crate::engine::dispatcher;
 use crate::engine::output_parser;
@@ -36,6 +37,7 @@ pub struct PostToolUseRequest {
 #[derive(Debug)]
 pub struct PostToolUseOutcome {
     pub hook_events: Vec<HookCompletedEvent>,
+    pub async_hooks: AsyncHookRuns,
     pub should_stop: bool,
     pub stop_reason: Option<String>,
     pub additional_contexts: Vec<String>,
@@ -69,7 +71,7 @@ pub(crate) fn preview(
 
 pub(crate) async fn run(
     handlers: &[ConfiguredHandler],
-    shell: &CommandShell,
+    runtime: &HookRuntime,
     request: PostToolUseRequest,
 ) -> PostToolUseOutcome {
     let matcher_inputs = common::matcher_inputs(&request.tool_name, &request.matcher_aliases);
@@ -81,6 +83,7 @@ pub(crate) async fn run(
     if matched.is_empty() {
         return PostToolUseOutcome {
             hook_events: Vec::new(),
+            async_hooks: AsyncHookRuns::default(),
             should_stop: false,
             stop_reason: None,
             additional_contexts: Vec::new(),
@@ -101,8 +104,8 @@ pub(crate) async fn run(
         }
     };
 
-    let results = dispatcher::execute_handlers(
-        shell,
+    let (results, async_runs) = dispatcher::execute_handlers(
+        runtime,
         matched,
         input_json,
         request.cwd.as_path(),
@@ -134,6 +137,7 @@ pub(crate) async fn run(
                 common::hook_completed_for_tool_use(result.completed, &request.tool_use_id)
             })
             .collect(),
+        async_hooks: async_runs.for_tool_use(&request.tool_use_id),
         should_stop,
         stop_reason,
         additional_contexts,
@@ -304,6 +308,7 @@ fn parse_completed(
 fn serialization_failure_outcome(hook_events: Vec<HookCompletedEvent>) -> PostToolUseOutcome {
     PostToolUseOutcome {
         hook_events,
+        async_hooks: AsyncHookRuns::default(),
         should_stop: false,
         stop_reason: None,
         additional_contexts: Vec::new(),
@@ -315,6 +320,7 @@ fn serialization_failure_outcome(hook_events: Vec<HookCompletedEvent>) -> PostTo
 mod tests {
     use codex_protocol::ThreadId;
     use codex_protocol::protocol::HookEventName;
+    use codex_protocol::protocol::HookExecutionMode;
     use codex_protocol::protocol::HookOutputEntry;
     use codex_protocol::protocol::HookOutputEntryKind;
     use codex_protocol::protocol::HookRunStatus;
@@ -328,6 +334,7 @@ mod tests {
     use super::parse_completed;
     use super::preview;
     use crate::engine::ConfiguredHandler;
+    use crate::engine::HandlerKind;
     use crate::engine::command_runner::CommandRunResult;
     use crate::events::common;
 
@@ -545,8 +552,11 @@ mod tests {
             event_name: HookEventName::PostToolUse,
             is_managed: false,
             matcher: Some("^Bash$".to_string()),
-            command: "python3 post_tool_use_hook.py".to_string(),
+            kind: HandlerKind::Command {
+                command: "python3 post_tool_use_hook.py".to_string(),
+            },
             timeout_sec: 5,
+            execution_mode: HookExecutionMode::Sync,
             status_message: Some("running post tool use hook".to_string()),
             source_path: test_path_buf("/tmp/hooks.json").abs(),
             source: codex_protocol::protocol::HookSource::User,
@@ -11,8 +11,9 @@ use codex_utils_absolute_path::AbsolutePathBuf;
 use serde_json::Value;
 
 use super::common;
-use crate::engine::CommandShell;
 use crate::engine::ConfiguredHandler;
+use crate::engine::HookRuntime;
+use crate::engine::async_hooks::AsyncHookRuns;
 use crate::engine::command_runner::CommandRunResult;
 use crate::engine::dispatcher;
 use crate::engine::output_parser;
@@ -32,17 +33,34 @@ pub struct PreToolUseRequest {
     pub tool_input: Value,
 }
 
+/// Approval verdict a `PreToolUse` hook can return for a call it does not block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PreToolUsePermissionDecision {
+    /// Run the call without asking for approval.
+    Allow,
+    /// Ask for approval even when policy would have run the call unprompted.
+    Ask { reason: Option<String> },
+}
+
 #[derive(Debug)]
 pub struct PreToolUseOutcome {
     pub hook_events: Vec<HookCompletedEvent>,
+    pub async_hooks: AsyncHookRuns,
     pub should_block: bool,
     pub block_reason: Option<String>,
+    /// Only set when no hook blocked the call.
+    pub permission_decision: Option<PreToolUsePermissionDecision>,
+    /// Replacement `tool_input`. Only set when no hook blocked the call; the caller is
+    /// responsible for validating it against the tool's own input schema.
+    pub updated_input: Option<Value>,
 }
 
 #[derive(Debug, Default, PartialEq, Eq)]
 struct PreToolUseHandlerData {
     should_block: bool,
     block_reason: Option<String>,
+    permission_decision: Option<PreToolUsePermissionDecision>,
+    updated_input: Option<Value>,
 }
 
 pub(crate) fn preview(
@@ -64,7 +82,7 @@ pub(crate) fn preview(
 
 pub(crate) async fn run(
     handlers: &[ConfiguredHandler],
-    shell: &CommandShell,
+    runtime: &HookRuntime,
     request: PreToolUseRequest,
 ) -> PreToolUseOutcome {
     let matcher_inputs = common::matcher_inputs(&request.tool_name, &request.matcher_aliases);
@@ -76,8 +94,11 @@ pub(crate) async fn run(
     if matched.is_empty() {
         return PreToolUseOutcome {
             hook_events: Vec::new(),
+            async_hooks: AsyncHookRuns::default(),
             should_block: false,
             block_reason: None,
+            permission_decision: None,
+            updated_input: None,
         };
     }
 
@@ -94,8 +115,8 @@ pub(crate) async fn run(
         }
     };
 
-    let results = dispatcher::execute_handlers(
-        shell,
+    let (results, async_runs) = dispatcher::execute_handlers(
+        runtime,
         matched,
         input_json,
         request.cwd.as_path(),
@@ -108,6 +129,15 @@ pub(crate) async fn run(
     let block_reason = results
         .iter()
         .find_map(|result| result.data.block_reason.clone());
+    let (permission_decision, updated_input) = if should_block {
+        (None, None)
+    } else {
+        let handler_data = results
+            .iter()
+            .map(|result| &result.data)
+            .collect::<Vec<_>>();
+        resolve_unblocked_outcome(&handler_data)
+    };
 
     PreToolUseOutcome {
         hook_events: results
@@ -116,9 +146,55 @@ pub(crate) async fn run(
                 common::hook_completed_for_tool_use(result.completed, &request.tool_use_id)
             })
             .collect(),
+        async_hooks: async_runs.for_tool_use(&request.tool_use_id),
         should_block,
         block_reason,
+        permission_decision,
+        updated_input,
+    }
+}
+
+/// Handlers all see the original input, so rewrites do not compose; the highest-precedence
+/// rewrite wins. An `allow` only vouches for the input its own hook saw, so once a rewrite wins
+/// the other hooks' `allow` verdicts are dropped and the call goes through normal approval.
+fn resolve_unblocked_outcome(
+    handler_data: &[&PreToolUseHandlerData],
+) -> (Option<PreToolUsePermissionDecision>, Option<Value>) {
+    let rewrite_index = handler_data
+        .iter()
+        .rposition(|data| data.updated_input.is_some());
+    let decisions = handler_data.iter().enumerate().filter_map(|(index, data)| {
+        match data.permission_decision.as_ref()? {
+            PreToolUsePermissionDecision::Allow
+                if rewrite_index.is_some_and(|rewrite_index| rewrite_index != index) =>
+            {
+                None
+            }
+            decision => Some(decision),
+        }
+    });
+    let permission_decision = resolve_permission_decision(decisions);
+    let updated_input = rewrite_index
+        .and_then(|index| handler_data.get(index))
+        .and_then(|data| data.updated_input.clone());
+    (permission_decision, updated_input)
+}
+
+/// Any `ask` wins so a broad auto-approval cannot silence a hook that wants a human to look;
+/// otherwise a single `allow` is enough to skip the approval prompt.
+fn resolve_permission_decision<'a>(
+    decisions: impl IntoIterator<Item = &'a PreToolUsePermissionDecision>,
+) -> Option<PreToolUsePermissionDecision> {
+    let mut resolved_allow = None;
+    for decision in decisions {
+        match decision {
+            PreToolUsePermissionDecision::Allow => {
+                resolved_allow = Some(PreToolUsePermissionDecision::Allow);
+            }
+            PreToolUsePermissionDecision::Ask { .. } => return Some(decision.clone()),
+        }
     }
+    resolved_allow
 }
 
 /// Serializes command stdin for a selected `PreToolUse` hook.
@@ -151,6 +227,8 @@ fn parse_completed(
     let mut status = HookRunStatus::Completed;
     let mut should_block = false;
     let mut block_reason = None;
+    let mut permission_decision = None;
+    let mut updated_input = None;
 
     match run_result.error.as_deref() {
         Some(error) => {
@@ -185,6 +263,17 @@ fn parse_completed(
                             kind: HookOutputEntryKind::Feedback,
                             text: reason,
                         });
+                    } else {
+                        permission_decision =
+                            parsed.permission_decision.map(|decision| match decision {
+                                output_parser::PreToolUsePermissionDecision::Allow => {
+                                    PreToolUsePermissionDecision::Allow
+                                }
+                                output_parser::PreToolUsePermissionDecision::Ask { reason } => {
+                                    PreToolUsePermissionDecision::Ask { reason }
+                                }
+                            });
+                        updated_input = parsed.updated_input;
                     }
                 } else if trimmed_stdout.starts_with('{') || trimmed_stdout.starts_with('[') {
                     status = HookRunStatus::Failed;
@@ -238,6 +327,8 @@ fn parse_completed(
         data: PreToolUseHandlerData {
             should_block,
             block_reason,
+            permission_decision,
+            updated_input,
         },
     }
 }
@@ -245,8 +336,11 @@ fn parse_completed(
 fn serialization_failure_outcome(hook_events: Vec<HookCompletedEvent>) -> PreToolUseOutcome {
     PreToolUseOutcome {
         hook_events,
+        async_hooks: AsyncHookRuns::default(),
         should_block: false,
         block_reason: None,
+        permission_decision: None,
+        updated_input: None,
     }
 }
 
@@ -254,6 +348,7 @@ fn serialization_failure_outcome(hook_events: Vec<HookCompletedEvent>) -> PreToo
 mod tests {
     use codex_protocol::ThreadId;
     use codex_protocol::protocol::HookEventName;
+    use codex_protocol::protocol::HookExecutionMode;
     use codex_protocol::protocol::HookOutputEntry;
     use codex_protocol::protocol::HookOutputEntryKind;
     use codex_protocol::protocol::HookRunStatus;
@@ -262,10 +357,13 @@ mod tests {
     use pretty_assertions::assert_eq;
 
     use super::PreToolUseHandlerData;
+    use super::PreToolUsePermissionDecision;
     use super::command_input_json;
     use super::parse_completed;
     use super::preview;
+    use super::resolve_permission_decision;
     use crate::engine::ConfiguredHandler;
+    use crate::engine::HandlerKind;
     use crate::engine::command_runner::CommandRunResult;
     use crate::events::common;
 
@@ -298,6 +396,7 @@ mod tests {
             PreToolUseHandlerData {
                 should_block: true,
                 block_reason: Some("do not run that".to_string()),
+                ..Default::default()
             }
         );
         assert_eq!(parsed.completed.run.status, HookRunStatus::Blocked);
@@ -327,6 +426,7 @@ mod tests {
             PreToolUseHandlerData {
                 should_block: true,
                 block_reason: Some("do not run that".to_string()),
+                ..Default::default()
             }
         );
         assert_eq!(parsed.completed.run.status, HookRunStatus::Blocked);
@@ -340,7 +440,7 @@ mod tests {
     }
 
     #[test]
-    fn unsupported_permission_decision_fails_open() {
+    fn permission_decision_ask_requests_approval() {
         let parsed = parse_completed(
             &handler(),
             run_result(
@@ -354,20 +454,113 @@ mod tests {
         assert_eq!(
             parsed.data,
             PreToolUseHandlerData {
-                should_block: false,
-                block_reason: None,
+                permission_decision: Some(PreToolUsePermissionDecision::Ask {
+                    reason: Some("please confirm".to_string()),
+                }),
+                ..Default::default()
+            }
+        );
+        assert_eq!(parsed.completed.run.status, HookRunStatus::Completed);
+        assert_eq!(parsed.completed.run.entries, vec![]);
+    }
+
+    #[test]
+    fn permission_decision_allow_carries_updated_input() {
+        let parsed = parse_completed(
+            &handler(),
+            run_result(
+                Some(0),
+                r#"{"hookSpecificOutput":{"hookEventName":"PreToolUse","permissionDecision":"allow","updatedInput":{"command":"git commit -m wip"}}}"#,
+                "",
+            ),
+            Some("turn-1".to_string()),
+        );
+
+        assert_eq!(
+            parsed.data,
+            PreToolUseHandlerData {
+                permission_decision: Some(PreToolUsePermissionDecision::Allow),
+                updated_input: Some(serde_json::json!({ "command": "git commit -m wip" })),
+                ..Default::default()
             }
         );
+        assert_eq!(parsed.completed.run.status, HookRunStatus::Completed);
+    }
+
+    #[test]
+    fn non_object_updated_input_fails_open() {
+        let parsed = parse_completed(
+            &handler(),
+            run_result(
+                Some(0),
+                r#"{"hookSpecificOutput":{"hookEventName":"PreToolUse","updatedInput":"rm -rf /"}}"#,
+                "",
+            ),
+            Some("turn-1".to_string()),
+        );
+
+        assert_eq!(parsed.data, PreToolUseHandlerData::default());
         assert_eq!(parsed.completed.run.status, HookRunStatus::Failed);
         assert_eq!(
             parsed.completed.run.entries,
             vec![HookOutputEntry {
                 kind: HookOutputEntryKind::Error,
-                text: "PreToolUse hook returned unsupported permissionDecision:ask".to_string(),
+                text: "PreToolUse hook returned updatedInput that is not a JSON object".to_string(),
             }]
         );
     }
 
+    #[test]
+    fn ask_overrides_allow_from_other_handlers() {
+        let decisions = [
+            PreToolUsePermissionDecision::Allow,
+            PreToolUsePermissionDecision::Ask { reason: None },
+            PreToolUsePermissionDecision::Allow,
+        ];
+
+        assert_eq!(
+            resolve_permission_decision(decisions.iter()),
+            Some(PreToolUsePermissionDecision::Ask { reason: None })
+        );
+    }
+
+    #[test]
+    fn allow_only_counts_from_the_hook_whose_rewrite_wins() {
+        let allow_original = PreToolUseHandlerData {
+            permission_decision: Some(PreToolUsePermissionDecision::Allow),
+            ..Default::default()
+        };
+        let rewrite = PreToolUseHandlerData {
+            updated_input: Some(serde_json::json!({ "command": "rm -rf build" })),
+            ..Default::default()
+        };
+        let allow_rewrite = PreToolUseHandlerData {
+            permission_decision: Some(PreToolUsePermissionDecision::Allow),
+            updated_input: Some(serde_json::json!({ "command": "git status" })),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            resolve_unblocked_outcome(&[&allow_original, &rewrite]),
+            (None, Some(serde_json::json!({ "command": "rm -rf build" })))
+        );
+        assert_eq!(
+            resolve_unblocked_outcome(&[&allow_original, &allow_rewrite]),
+            (
+                Some(PreToolUsePermissionDecision::Allow),
+                Some(serde_json::json!({ "command": "git status" }))
+            )
+        );
+        assert_eq!(
+            resolve_unblocked_outcome(&[&allow_rewrite, &rewrite]),
+            (None, Some(serde_json::json!({ "command": "rm -rf build" })))
+        );
+        assert_eq!(
+            resolve_unblocked_outcome(&[&allow_original]),
+            (Some(PreToolUsePermissionDecision::Allow), None)
+        );
+    }
+
     #[test]
     fn deprecated_approve_decision_fails_open() {
         let parsed = parse_completed(
@@ -376,13 +569,7 @@ mod tests {
             Some("turn-1".to_string()),
         );
 
-        assert_eq!(
-            parsed.data,
-            PreToolUseHandlerData {
-                should_block: false,
-                block_reason: None,
-            }
-        );
+        assert_eq!(parsed.data, PreToolUseHandlerData::default());
         assert_eq!(parsed.completed.run.status, HookRunStatus::Failed);
         assert_eq!(
             parsed.completed.run.entries,
@@ -405,13 +592,7 @@ mod tests {
             Some("turn-1".to_string()),
         );
 
-        assert_eq!(
-            parsed.data,
-            PreToolUseHandlerData {
-                should_block: false,
-                block_reason: None,
-            }
-        );
+        assert_eq!(parsed.data, PreToolUseHandlerData::default());
         assert_eq!(parsed.completed.run.status, HookRunStatus::Failed);
         assert_eq!(
             parsed.completed.run.entries,
@@ -430,13 +611,7 @@ mod tests {
             Some("turn-1".to_string()),
         );
 
-        assert_eq!(
-            parsed.data,
-            PreToolUseHandlerData {
-                should_block: false,
-                block_reason: None,
-            }
-        );
+        assert_eq!(parsed.data, PreToolUseHandlerData::default());
         assert_eq!(parsed.completed.run.status, HookRunStatus::Completed);
         assert_eq!(parsed.completed.run.entries, vec![]);
     }
@@ -449,13 +624,7 @@ mod tests {
             Some("turn-1".to_string()),
         );
 
-        assert_eq!(
-            parsed.data,
-            PreToolUseHandlerData {
-                should_block: false,
-                block_reason: None,
-            }
-        );
+        assert_eq!(parsed.data, PreToolUseHandlerData::default());
         assert_eq!(parsed.completed.run.status, HookRunStatus::Failed);
         assert_eq!(
             parsed.completed.run.entries,
@@ -479,6 +648,7 @@ mod tests {
             PreToolUseHandlerData {
                 should_block: true,
                 block_reason: Some("blocked by policy".to_string()),
+                ..Default::default()
             }
         );
         assert_eq!(parsed.completed.run.status, HookRunStatus::Blocked);
@@ -536,8 +706,11 @@ mod tests {
             event_name: HookEventName::PreToolUse,
             is_managed: false,
             matcher: Some("^Bash$".to_string()),
-            command: "echo hook".to_string(),
+            kind: HandlerKind::Command {
+                command: "echo hook".to_string(),
+            },
             timeout_sec: 5,
+            execution_mode: HookExecutionMode::Sync,
             status_message: None,
             source_path: test_path_buf("/tmp/hooks.json").abs(),
             source: codex_protocol::protocol::HookSource::User,
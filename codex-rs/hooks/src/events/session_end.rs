@@ -0,0 +1,223 @@
+use std::path::PathBuf;
+
+use codex_protocol::ThreadId;
+use codex_protocol::protocol::HookCompletedEvent;
+use codex_protocol::protocol::HookEventName;
+use codex_protocol::protocol::HookRunSummary;
+use codex_utils_absolute_path::AbsolutePathBuf;
+
+use super::common;
+use crate::engine::ConfiguredHandler;
+use crate::engine::HookRuntime;
+use crate::engine::async_hooks::AsyncHookRuns;
+use crate::engine::command_runner::CommandRunResult;
+use crate::engine::dispatcher;
+use crate::engine::output_parser;
+use crate::schema::NullableString;
+use crate::schema::SessionEndCommandInput;
+
+#[derive(Debug, Clone, Copy)]
+pub enum SessionEndReason {
+    Exit,
+}
+
+impl SessionEndReason {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Exit => "exit",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SessionEndRequest {
+    pub session_id: ThreadId,
+    pub cwd: AbsolutePathBuf,
+    pub transcript_path: Option<PathBuf>,
+    pub model: String,
+    pub permission_mode: String,
+    pub reason: SessionEndReason,
+}
+
+#[derive(Debug)]
+pub struct SessionEndOutcome {
+    pub hook_events: Vec<HookCompletedEvent>,
+    pub async_hooks: AsyncHookRuns,
+}
+
+pub(crate) fn preview(
+    handlers: &[ConfiguredHandler],
+    request: &SessionEndRequest,
+) -> Vec<HookRunSummary> {
+    dispatcher::select_handlers(
+        handlers,
+        HookEventName::SessionEnd,
+        Some(request.reason.as_str()),
+    )
+    .into_iter()
+    .map(|handler| dispatcher::running_summary(&handler))
+    .collect()
+}
+
+pub(crate) async fn run(
+    handlers: &[ConfiguredHandler],
+    runtime: &HookRuntime,
+    request: SessionEndRequest,
+) -> SessionEndOutcome {
+    let matched = dispatcher::select_handlers(
+        handlers,
+        HookEventName::SessionEnd,
+        Some(request.reason.as_str()),
+    );
+    if matched.is_empty() {
+        return SessionEndOutcome {
+            hook_events: Vec::new(),
+            async_hooks: AsyncHookRuns::default(),
+        };
+    }
+
+    let input_json = match serde_json::to_string(&SessionEndCommandInput {
+        session_id: request.session_id.to_string(),
+        transcript_path: NullableString::from_path(request.transcript_path.clone()),
+        cwd: request.cwd.display().to_string(),
+        hook_event_name: "SessionEnd".to_string(),
+        model: request.model.clone(),
+        permission_mode: request.permission_mode.clone(),
+        reason: request.reason.as_str().to_string(),
+    }) {
+        Ok(input_json) => input_json,
+        Err(error) => {
+            return SessionEndOutcome {
+                hook_events: common::serialization_failure_hook_events(
+                    matched,
+                    /*turn_id*/ None,
+                    format!("failed to serialize session end hook input: {error}"),
+                ),
+                async_hooks: AsyncHookRuns::default(),
+            };
+        }
+    };
+
+    let (results, async_runs) = dispatcher::execute_handlers(
+        runtime,
+        matched,
+        input_json,
+        request.cwd.as_path(),
+        /*turn_id*/ None,
+        parse_completed,
+    )
+    .await;
+
+    SessionEndOutcome {
+        hook_events: results.into_iter().map(|result| result.completed).collect(),
+        async_hooks: async_runs,
+    }
+}
+
+fn parse_completed(
+    handler: &ConfiguredHandler,
+    run_result: CommandRunResult,
+    turn_id: Option<String>,
+) -> dispatcher::ParsedHandler<()> {
+    common::parse_observer_completed(
+        handler,
+        run_result,
+        turn_id,
+        "session end",
+        output_parser::parse_session_end,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use codex_protocol::protocol::HookEventName;
+    use codex_protocol::protocol::HookExecutionMode;
+    use codex_protocol::protocol::HookOutputEntry;
+    use codex_protocol::protocol::HookOutputEntryKind;
+    use codex_protocol::protocol::HookRunStatus;
+    use codex_utils_absolute_path::test_support::PathBufExt;
+    use codex_utils_absolute_path::test_support::test_path_buf;
+    use pretty_assertions::assert_eq;
+
+    use super::parse_completed;
+    use crate::engine::ConfiguredHandler;
+    use crate::engine::HandlerKind;
+    use crate::engine::command_runner::CommandRunResult;
+
+    #[test]
+    fn plain_stdout_is_ignored() {
+        let parsed = parse_completed(
+            &handler(),
+            run_result(Some(0), "archived transcript\n"),
+            /*turn_id*/ None,
+        );
+
+        assert_eq!(parsed.completed.run.status, HookRunStatus::Completed);
+        assert_eq!(parsed.completed.run.entries, Vec::new());
+    }
+
+    #[test]
+    fn system_message_becomes_warning() {
+        let parsed = parse_completed(
+            &handler(),
+            run_result(Some(0), r#"{"systemMessage":"transcript upload skipped"}"#),
+            /*turn_id*/ None,
+        );
+
+        assert_eq!(parsed.completed.run.status, HookRunStatus::Completed);
+        assert_eq!(
+            parsed.completed.run.entries,
+            vec![HookOutputEntry {
+                kind: HookOutputEntryKind::Warning,
+                text: "transcript upload skipped".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn invalid_json_like_stdout_fails() {
+        let parsed = parse_completed(
+            &handler(),
+            run_result(Some(0), r#"{"systemMessage":"#),
+            /*turn_id*/ None,
+        );
+
+        assert_eq!(parsed.completed.run.status, HookRunStatus::Failed);
+        assert_eq!(
+            parsed.completed.run.entries,
+            vec![HookOutputEntry {
+                kind: HookOutputEntryKind::Error,
+                text: "hook returned invalid session end JSON output".to_string(),
+            }]
+        );
+    }
+
+    fn handler() -> ConfiguredHandler {
+        ConfiguredHandler {
+            event_name: HookEventName::SessionEnd,
+            is_managed: false,
+            matcher: None,
+            kind: HandlerKind::Command {
+                command: "echo hook".to_string(),
+            },
+            timeout_sec: 600,
+            execution_mode: HookExecutionMode::Sync,
+            status_message: None,
+            source_path: test_path_buf("/tmp/hooks.json").abs(),
+            source: codex_protocol::protocol::HookSource::User,
+            display_order: 0,
+        }
+    }
+
+    fn run_result(exit_code: Option<i32>, stdout: &str) -> CommandRunResult {
+        CommandRunResult {
+            started_at: 1,
+            completed_at: 2,
+            duration_ms: 1,
+            exit_code,
+            stdout: stdout.to_string(),
+            stderr: String::new(),
+            error: None,
+        }
+    }
+}
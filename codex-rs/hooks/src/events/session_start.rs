@@ -10,8 +10,9 @@ use codex_protocol::protocol::HookRunSummary;
 use codex_utils_absolute_path::AbsolutePathBuf;
 
 use super::common;
-use crate::engine::CommandShell;
 use crate::engine::ConfiguredHandler;
+use crate::engine::HookRuntime;
+use crate::engine::async_hooks::AsyncHookRuns;
 use crate::engine::command_runner::CommandRunResult;
 use crate::engine::dispatcher;
 use crate::engine::output_parser;
@@ -47,6 +48,7 @@ pub struct SessionStartRequest {
 #[derive(Debug)]
 pub struct SessionStartOutcome {
     pub hook_events: Vec<HookCompletedEvent>,
+    pub async_hooks: AsyncHookRuns,
     pub should_stop: bool,
     pub stop_reason: Option<String>,
     pub additional_contexts: Vec<String>,
@@ -75,7 +77,7 @@ pub(crate) fn preview(
 
 pub(crate) async fn run(
     handlers: &[ConfiguredHandler],
-    shell: &CommandShell,
+    runtime: &HookRuntime,
     request: SessionStartRequest,
     turn_id: Option<String>,
 ) -> SessionStartOutcome {
@@ -87,6 +89,7 @@ pub(crate) async fn run(
     if matched.is_empty() {
         return SessionStartOutcome {
             hook_events: Vec::new(),
+            async_hooks: AsyncHookRuns::default(),
             should_stop: false,
             stop_reason: None,
             additional_contexts: Vec::new(),
@@ -111,8 +114,8 @@ pub(crate) async fn run(
         }
     };
 
-    let results = dispatcher::execute_handlers(
-        shell,
+    let (results, async_runs) = dispatcher::execute_handlers(
+        runtime,
         matched,
         input_json,
         request.cwd.as_path(),
@@ -133,6 +136,7 @@ pub(crate) async fn run(
 
     SessionStartOutcome {
         hook_events: results.into_iter().map(|result| result.completed).collect(),
+        async_hooks: async_runs,
         should_stop,
         stop_reason,
         additional_contexts,
@@ -240,6 +244,7 @@ fn parse_completed(
 fn serialization_failure_outcome(hook_events: Vec<HookCompletedEvent>) -> SessionStartOutcome {
     SessionStartOutcome {
         hook_events,
+        async_hooks: AsyncHookRuns::default(),
         should_stop: false,
         stop_reason: None,
         additional_contexts: Vec::new(),
@@ -249,6 +254,7 @@ fn serialization_failure_outcome(hook_events: Vec<HookCompletedEvent>) -> Sessio
 #[cfg(test)]
 mod tests {
     use codex_protocol::protocol::HookEventName;
+    use codex_protocol::protocol::HookExecutionMode;
     use codex_protocol::protocol::HookOutputEntry;
     use codex_protocol::protocol::HookOutputEntryKind;
     use codex_protocol::protocol::HookRunStatus;
@@ -259,6 +265,7 @@ mod tests {
     use super::SessionStartHandlerData;
     use super::parse_completed;
     use crate::engine::ConfiguredHandler;
+    use crate::engine::HandlerKind;
     use crate::engine::command_runner::CommandRunResult;
 
     #[test]
@@ -358,8 +365,11 @@ mod tests {
             event_name: HookEventName::SessionStart,
             is_managed: false,
             matcher: None,
-            command: "echo hook".to_string(),
+            kind: HandlerKind::Command {
+                command: "echo hook".to_string(),
+            },
             timeout_sec: 600,
+            execution_mode: HookExecutionMode::Sync,
             status_message: None,
             source_path: test_path_buf("/tmp/hooks.json").abs(),
             source: codex_protocol::protocol::HookSource::User,
@@ -11,8 +11,9 @@ use codex_protocol::protocol::HookRunSummary;
 use codex_utils_absolute_path::AbsolutePathBuf;
 
 use super::common;
-use crate::engine::CommandShell;
 use crate::engine::ConfiguredHandler;
+use crate::engine::HookRuntime;
+use crate::engine::async_hooks::AsyncHookRuns;
 use crate::engine::command_runner::CommandRunResult;
 use crate::engine::dispatcher;
 use crate::engine::output_parser;
@@ -34,6 +35,7 @@ pub struct StopRequest {
 #[derive(Debug)]
 pub struct StopOutcome {
     pub hook_events: Vec<HookCompletedEvent>,
+    pub async_hooks: AsyncHookRuns,
     pub should_stop: bool,
     pub stop_reason: Option<String>,
     pub should_block: bool,
@@ -62,7 +64,7 @@ pub(crate) fn preview(
 
 pub(crate) async fn run(
     handlers: &[ConfiguredHandler],
-    shell: &CommandShell,
+    runtime: &HookRuntime,
     request: StopRequest,
 ) -> StopOutcome {
     let matched =
@@ -70,6 +72,7 @@ pub(crate) async fn run(
     if matched.is_empty() {
         return StopOutcome {
             hook_events: Vec::new(),
+            async_hooks: AsyncHookRuns::default(),
             should_stop: false,
             stop_reason: None,
             should_block: false,
@@ -99,8 +102,8 @@ pub(crate) async fn run(
         }
     };
 
-    let results = dispatcher::execute_handlers(
-        shell,
+    let (results, async_runs) = dispatcher::execute_handlers(
+        runtime,
         matched,
         input_json,
         request.cwd.as_path(),
@@ -113,6 +116,7 @@ pub(crate) async fn run(
 
     StopOutcome {
         hook_events: results.into_iter().map(|result| result.completed).collect(),
+        async_hooks: async_runs,
         should_stop: aggregate.should_stop,
         stop_reason: aggregate.stop_reason,
         should_block: aggregate.should_block,
@@ -301,6 +305,7 @@ fn aggregate_results<'a>(
 fn serialization_failure_outcome(hook_events: Vec<HookCompletedEvent>) -> StopOutcome {
     StopOutcome {
         hook_events,
+        async_hooks: AsyncHookRuns::default(),
         should_stop: false,
         stop_reason: None,
         should_block: false,
@@ -312,6 +317,7 @@ fn serialization_failure_outcome(hook_events: Vec<HookCompletedEvent>) -> StopOu
 #[cfg(test)]
 mod tests {
     use codex_protocol::protocol::HookEventName;
+    use codex_protocol::protocol::HookExecutionMode;
     use codex_protocol::protocol::HookOutputEntry;
     use codex_protocol::protocol::HookOutputEntryKind;
     use codex_protocol::protocol::HookRunStatus;
@@ -325,6 +331,7 @@ mod tests {
     use super::aggregate_results;
     use super::parse_completed;
     use crate::engine::ConfiguredHandler;
+    use crate::engine::HandlerKind;
     use crate::engine::command_runner::CommandRunResult;
 
     #[test]
@@ -525,8 +532,11 @@ mod tests {
             event_name: HookEventName::Stop,
             is_managed: false,
             matcher: None,
-            command: "echo hook".to_string(),
+            kind: HandlerKind::Command {
+                command: "echo hook".to_string(),
+            },
             timeout_sec: 600,
+            execution_mode: HookExecutionMode::Sync,
             status_message: None,
             source_path: test_path_buf("/tmp/hooks.json").abs(),
             source: codex_protocol::protocol::HookSource::User,
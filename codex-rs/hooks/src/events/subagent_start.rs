@@ -0,0 +1,125 @@
+use std::path::PathBuf;
+
+use codex_protocol::ThreadId;
+use codex_protocol::protocol::HookCompletedEvent;
+use codex_protocol::protocol::HookEventName;
+use codex_protocol::protocol::HookRunSummary;
+use codex_utils_absolute_path::AbsolutePathBuf;
+
+use super::common;
+use crate::engine::ConfiguredHandler;
+use crate::engine::HookRuntime;
+use crate::engine::async_hooks::AsyncHookRuns;
+use crate::engine::command_runner::CommandRunResult;
+use crate::engine::dispatcher;
+use crate::engine::output_parser;
+use crate::schema::NullableString;
+use crate::schema::SubagentStartCommandInput;
+
+#[derive(Debug, Clone)]
+pub struct SubagentStartRequest {
+    pub session_id: ThreadId,
+    pub turn_id: String,
+    pub cwd: AbsolutePathBuf,
+    pub transcript_path: Option<PathBuf>,
+    pub model: String,
+    pub permission_mode: String,
+    pub agent_id: ThreadId,
+    /// Role the sub-agent was spawned with; matchers select on this value.
+    pub agent_type: String,
+    pub agent_path: Option<String>,
+    pub agent_nickname: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct SubagentStartOutcome {
+    pub hook_events: Vec<HookCompletedEvent>,
+    pub async_hooks: AsyncHookRuns,
+}
+
+pub(crate) fn preview(
+    handlers: &[ConfiguredHandler],
+    request: &SubagentStartRequest,
+) -> Vec<HookRunSummary> {
+    dispatcher::select_handlers(
+        handlers,
+        HookEventName::SubagentStart,
+        Some(request.agent_type.as_str()),
+    )
+    .into_iter()
+    .map(|handler| dispatcher::running_summary(&handler))
+    .collect()
+}
+
+pub(crate) async fn run(
+    handlers: &[ConfiguredHandler],
+    runtime: &HookRuntime,
+    request: SubagentStartRequest,
+) -> SubagentStartOutcome {
+    let matched = dispatcher::select_handlers(
+        handlers,
+        HookEventName::SubagentStart,
+        Some(request.agent_type.as_str()),
+    );
+    if matched.is_empty() {
+        return SubagentStartOutcome {
+            hook_events: Vec::new(),
+            async_hooks: AsyncHookRuns::default(),
+        };
+    }
+
+    let input_json = match serde_json::to_string(&SubagentStartCommandInput {
+        session_id: request.session_id.to_string(),
+        turn_id: request.turn_id.clone(),
+        transcript_path: NullableString::from_path(request.transcript_path.clone()),
+        cwd: request.cwd.display().to_string(),
+        hook_event_name: "SubagentStart".to_string(),
+        model: request.model.clone(),
+        permission_mode: request.permission_mode.clone(),
+        agent_id: request.agent_id.to_string(),
+        agent_type: request.agent_type.clone(),
+        agent_path: NullableString::from_string(request.agent_path.clone()),
+        agent_nickname: NullableString::from_string(request.agent_nickname.clone()),
+    }) {
+        Ok(input_json) => input_json,
+        Err(error) => {
+            return SubagentStartOutcome {
+                hook_events: common::serialization_failure_hook_events(
+                    matched,
+                    Some(request.turn_id),
+                    format!("failed to serialize subagent start hook input: {error}"),
+                ),
+                async_hooks: AsyncHookRuns::default(),
+            };
+        }
+    };
+
+    let (results, async_runs) = dispatcher::execute_handlers(
+        runtime,
+        matched,
+        input_json,
+        request.cwd.as_path(),
+        Some(request.turn_id),
+        parse_completed,
+    )
+    .await;
+
+    SubagentStartOutcome {
+        hook_events: results.into_iter().map(|result| result.completed).collect(),
+        async_hooks: async_runs,
+    }
+}
+
+fn parse_completed(
+    handler: &ConfiguredHandler,
+    run_result: CommandRunResult,
+    turn_id: Option<String>,
+) -> dispatcher::ParsedHandler<()> {
+    common::parse_observer_completed(
+        handler,
+        run_result,
+        turn_id,
+        "subagent start",
+        output_parser::parse_subagent_start,
+    )
+}
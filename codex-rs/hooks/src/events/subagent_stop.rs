@@ -0,0 +1,156 @@
+use std::path::PathBuf;
+
+use codex_protocol::ThreadId;
+use codex_protocol::protocol::HookCompletedEvent;
+use codex_protocol::protocol::HookEventName;
+use codex_protocol::protocol::HookRunSummary;
+use codex_protocol::protocol::TokenUsage;
+use codex_utils_absolute_path::AbsolutePathBuf;
+
+use super::common;
+use crate::engine::ConfiguredHandler;
+use crate::engine::HookRuntime;
+use crate::engine::async_hooks::AsyncHookRuns;
+use crate::engine::command_runner::CommandRunResult;
+use crate::engine::dispatcher;
+use crate::engine::output_parser;
+use crate::schema::NullableString;
+use crate::schema::SubagentStopCommandInput;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubagentStopStatus {
+    Completed,
+    Errored,
+    Shutdown,
+    NotFound,
+}
+
+impl SubagentStopStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Completed => "completed",
+            Self::Errored => "errored",
+            Self::Shutdown => "shutdown",
+            Self::NotFound => "not_found",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SubagentStopRequest {
+    pub session_id: ThreadId,
+    pub turn_id: String,
+    pub cwd: AbsolutePathBuf,
+    pub transcript_path: Option<PathBuf>,
+    pub model: String,
+    pub permission_mode: String,
+    pub agent_id: ThreadId,
+    /// Role the sub-agent was spawned with; matchers select on this value.
+    pub agent_type: String,
+    pub agent_path: Option<String>,
+    pub agent_nickname: Option<String>,
+    pub status: SubagentStopStatus,
+    pub last_assistant_message: Option<String>,
+    pub token_usage: Option<TokenUsage>,
+}
+
+#[derive(Debug)]
+pub struct SubagentStopOutcome {
+    pub hook_events: Vec<HookCompletedEvent>,
+    pub async_hooks: AsyncHookRuns,
+}
+
+pub(crate) fn preview(
+    handlers: &[ConfiguredHandler],
+    request: &SubagentStopRequest,
+) -> Vec<HookRunSummary> {
+    dispatcher::select_handlers(
+        handlers,
+        HookEventName::SubagentStop,
+        Some(request.agent_type.as_str()),
+    )
+    .into_iter()
+    .map(|handler| dispatcher::running_summary(&handler))
+    .collect()
+}
+
+pub(crate) async fn run(
+    handlers: &[ConfiguredHandler],
+    runtime: &HookRuntime,
+    request: SubagentStopRequest,
+) -> SubagentStopOutcome {
+    let matched = dispatcher::select_handlers(
+        handlers,
+        HookEventName::SubagentStop,
+        Some(request.agent_type.as_str()),
+    );
+    if matched.is_empty() {
+        return SubagentStopOutcome {
+            hook_events: Vec::new(),
+            async_hooks: AsyncHookRuns::default(),
+        };
+    }
+
+    let input_json = serde_json::to_value(&request.token_usage).and_then(|token_usage| {
+        serde_json::to_string(&SubagentStopCommandInput {
+            session_id: request.session_id.to_string(),
+            turn_id: request.turn_id.clone(),
+            transcript_path: NullableString::from_path(request.transcript_path.clone()),
+            cwd: request.cwd.display().to_string(),
+            hook_event_name: "SubagentStop".to_string(),
+            model: request.model.clone(),
+            permission_mode: request.permission_mode.clone(),
+            agent_id: request.agent_id.to_string(),
+            agent_type: request.agent_type.clone(),
+            agent_path: NullableString::from_string(request.agent_path.clone()),
+            agent_nickname: NullableString::from_string(request.agent_nickname.clone()),
+            status: request.status.as_str().to_string(),
+            last_assistant_message: NullableString::from_string(
+                request.last_assistant_message.clone(),
+            ),
+            token_usage,
+        })
+    });
+    let input_json = match input_json {
+        Ok(input_json) => input_json,
+        Err(error) => {
+            return SubagentStopOutcome {
+                hook_events: common::serialization_failure_hook_events(
+                    matched,
+                    Some(request.turn_id),
+                    format!("failed to serialize subagent stop hook input: {error}"),
+                ),
+                async_hooks: AsyncHookRuns::default(),
+            };
+        }
+    };
+
+    let (results, async_runs) = dispatcher::execute_handlers(
+        runtime,
+        matched,
+        input_json,
+        request.cwd.as_path(),
+        Some(request.turn_id),
+        parse_completed,
+    )
+    .await;
+
+    SubagentStopOutcome {
+        hook_events: results.into_iter().map(|result| result.completed).collect(),
+        async_hooks: async_runs,
+    }
+}
+
+fn parse_completed(
+    handler: &ConfiguredHandler,
+    run_result: CommandRunResult,
+    turn_id: Option<String>,
+) -> dispatcher::ParsedHandler<()> {
+    common::parse_observer_completed(
+        handler,
+        run_result,
+        turn_id,
+        "subagent stop",
+        output_parser::parse_subagent_stop,
+    )
+}
@@ -5,18 +5,36 @@ mod registry;
 mod schema;
 mod types;
 
+pub use engine::async_hooks::AsyncHookRuns;
+pub use engine::model_hooks::ModelHookRequest;
+pub use engine::model_hooks::ModelHookRunner;
+pub use events::notification::NotificationOutcome;
+pub use events::notification::NotificationRequest;
+pub use events::notification::NotificationType;
 pub use events::permission_request::PermissionRequestDecision;
 pub use events::permission_request::PermissionRequestOutcome;
 pub use events::permission_request::PermissionRequestRequest;
 pub use events::post_tool_use::PostToolUseOutcome;
 pub use events::post_tool_use::PostToolUseRequest;
+pub use events::pre_compact::PreCompactOutcome;
+pub use events::pre_compact::PreCompactRequest;
+pub use events::pre_compact::PreCompactTrigger;
 pub use events::pre_tool_use::PreToolUseOutcome;
+pub use events::pre_tool_use::PreToolUsePermissionDecision;
 pub use events::pre_tool_use::PreToolUseRequest;
+pub use events::session_end::SessionEndOutcome;
+pub use events::session_end::SessionEndReason;
+pub use events::session_end::SessionEndRequest;
 pub use events::session_start::SessionStartOutcome;
 pub use events::session_start::SessionStartRequest;
 pub use events::session_start::SessionStartSource;
 pub use events::stop::StopOutcome;
 pub use events::stop::StopRequest;
+pub use events::subagent_start::SubagentStartOutcome;
+pub use events::subagent_start::SubagentStartRequest;
+pub use events::subagent_stop::SubagentStopOutcome;
+pub use events::subagent_stop::SubagentStopRequest;
+pub use events::subagent_stop::SubagentStopStatus;
 pub use events::user_prompt_submit::UserPromptSubmitOutcome;
 pub use events::user_prompt_submit::UserPromptSubmitRequest;
 pub use legacy_notify::legacy_notify_json;
@@ -1,18 +1,31 @@
+use std::sync::Arc;
+
 use codex_config::ConfigLayerStack;
 use tokio::process::Command;
 
 use crate::engine::ClaudeHooksEngine;
 use crate::engine::CommandShell;
+use crate::engine::model_hooks::ModelHookRunner;
+use crate::events::notification::NotificationOutcome;
+use crate::events::notification::NotificationRequest;
 use crate::events::permission_request::PermissionRequestOutcome;
 use crate::events::permission_request::PermissionRequestRequest;
 use crate::events::post_tool_use::PostToolUseOutcome;
 use crate::events::post_tool_use::PostToolUseRequest;
+use crate::events::pre_compact::PreCompactOutcome;
+use crate::events::pre_compact::PreCompactRequest;
 use crate::events::pre_tool_use::PreToolUseOutcome;
 use crate::events::pre_tool_use::PreToolUseRequest;
+use crate::events::session_end::SessionEndOutcome;
+use crate::events::session_end::SessionEndRequest;
 use crate::events::session_start::SessionStartOutcome;
 use crate::events::session_start::SessionStartRequest;
 use crate::events::stop::StopOutcome;
 use crate::events::stop::StopRequest;
+use crate::events::subagent_start::SubagentStartOutcome;
+use crate::events::subagent_start::SubagentStartRequest;
+use crate::events::subagent_stop::SubagentStopOutcome;
+use crate::events::subagent_stop::SubagentStopRequest;
 use crate::events::user_prompt_submit::UserPromptSubmitOutcome;
 use crate::events::user_prompt_submit::UserPromptSubmitRequest;
 use crate::types::Hook;
@@ -27,6 +40,8 @@ pub struct HooksConfig {
     pub config_layer_stack: Option<ConfigLayerStack>,
     pub shell_program: Option<String>,
     pub shell_args: Vec<String>,
+    /// Runs `prompt` and `agent` hooks. Without one, those hooks fail when they fire.
+    pub model_hook_runner: Option<Arc<dyn ModelHookRunner>>,
 }
 
 #[derive(Clone)]
@@ -57,6 +72,7 @@ impl Hooks {
                 program: config.shell_program.unwrap_or_default(),
                 args: config.shell_args,
             },
+            config.model_hook_runner,
         );
         Self {
             after_agent,
@@ -91,6 +107,11 @@ impl Hooks {
         outcomes
     }
 
+    /// Whether any configured handler listens for `event_name`, regardless of matcher.
+    pub fn has_handlers(&self, event_name: codex_protocol::protocol::HookEventName) -> bool {
+        self.engine.has_handlers(event_name)
+    }
+
     pub fn preview_session_start(
         &self,
         request: &SessionStartRequest,
@@ -166,6 +187,61 @@ impl Hooks {
     pub async fn run_stop(&self, request: StopRequest) -> StopOutcome {
         self.engine.run_stop(request).await
     }
+
+    pub fn preview_pre_compact(
+        &self,
+        request: &PreCompactRequest,
+    ) -> Vec<codex_protocol::protocol::HookRunSummary> {
+        self.engine.preview_pre_compact(request)
+    }
+
+    pub async fn run_pre_compact(&self, request: PreCompactRequest) -> PreCompactOutcome {
+        self.engine.run_pre_compact(request).await
+    }
+
+    pub fn preview_session_end(
+        &self,
+        request: &SessionEndRequest,
+    ) -> Vec<codex_protocol::protocol::HookRunSummary> {
+        self.engine.preview_session_end(request)
+    }
+
+    pub async fn run_session_end(&self, request: SessionEndRequest) -> SessionEndOutcome {
+        self.engine.run_session_end(request).await
+    }
+
+    pub fn preview_subagent_start(
+        &self,
+        request: &SubagentStartRequest,
+    ) -> Vec<codex_protocol::protocol::HookRunSummary> {
+        self.engine.preview_subagent_start(request)
+    }
+
+    pub async fn run_subagent_start(&self, request: SubagentStartRequest) -> SubagentStartOutcome {
+        self.engine.run_subagent_start(request).await
+    }
+
+    pub fn preview_subagent_stop(
+        &self,
+        request: &SubagentStopRequest,
+    ) -> Vec<codex_protocol::protocol::HookRunSummary> {
+        self.engine.preview_subagent_stop(request)
+    }
+
+    pub async fn run_subagent_stop(&self, request: SubagentStopRequest) -> SubagentStopOutcome {
+        self.engine.run_subagent_stop(request).await
+    }
+
+    pub fn preview_notification(
+        &self,
+        request: &NotificationRequest,
+    ) -> Vec<codex_protocol::protocol::HookRunSummary> {
+        self.engine.preview_notification(request)
+    }
+
+    pub async fn run_notification(&self, request: NotificationRequest) -> NotificationOutcome {
+        self.engine.run_notification(request).await
+    }
 }
 
 pub fn command_from_argv(argv: &[String]) -> Option<Command> {
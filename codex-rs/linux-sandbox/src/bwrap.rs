@@ -6,6 +6,10 @@
 //! - sensitive subpaths such as `.git` and `.codex` remain read-only even when
 //!   their parent root is writable.
 //!
+//! In overlay workspace mode the workspace root is mounted with overlayfs
+//! instead of a writable bind, so writes land in a per-turn upper directory and
+//! the real tree stays untouched until the overlay is committed.
+//!
 //! The overall Linux sandbox is composed of:
 //! - seccomp + `PR_SET_NO_NEW_PRIVS` applied in-process, and
 //! - bubblewrap used to construct the filesystem view before exec.
@@ -26,6 +30,7 @@ use codex_protocol::error::CodexErr;
 use codex_protocol::error::Result;
 use codex_protocol::protocol::FileSystemSandboxPolicy;
 use codex_protocol::protocol::WritableRoot;
+use codex_sandboxing::overlay::OverlayWorkspace;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use globset::GlobBuilder;
 use globset::GlobSet;
@@ -50,7 +55,7 @@ const LINUX_PLATFORM_DEFAULT_READ_ROOTS: &[&str] = &[
 const MAX_UNREADABLE_GLOB_MATCHES: usize = 8192;
 
 /// Options that control how bubblewrap is invoked.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) struct BwrapOptions {
     /// Whether to mount a fresh `/proc` inside the sandbox.
     ///
@@ -64,6 +69,9 @@ pub(crate) struct BwrapOptions {
     /// Keep this uncapped by default so existing nested deny-read matches are
     /// masked before the sandboxed command starts.
     pub glob_scan_max_depth: Option<usize>,
+    /// Mount the overlay's lower directory through overlayfs when it is one of
+    /// the writable roots, instead of binding it writable.
+    pub overlay_workspace: Option<OverlayWorkspace>,
 }
 
 impl Default for BwrapOptions {
@@ -72,6 +80,7 @@ impl Default for BwrapOptions {
             mount_proc: true,
             network_mode: BwrapNetworkMode::FullAccess,
             glob_scan_max_depth: None,
+            overlay_workspace: None,
         }
     }
 }
@@ -185,6 +194,7 @@ fn create_bwrap_flags(
         options
             .glob_scan_max_depth
             .or(file_system_sandbox_policy.glob_scan_max_depth),
+        options.overlay_workspace.as_ref(),
     )?;
     let normalized_command_cwd = normalize_command_cwd_for_bwrap(command_cwd);
     let mut args = Vec::new();
@@ -230,7 +240,10 @@ fn create_bwrap_flags(
 /// 3. Unreadable ancestors of writable roots are masked before their child
 ///    mounts are rebound so nested writable carveouts can be reopened safely.
 /// 4. `--bind <root> <root>` re-enables writes for allowed roots, including
-///    writable subpaths under `/dev` (for example, `/dev/shm`).
+///    writable subpaths under `/dev` (for example, `/dev/shm`). The overlay
+///    workspace root is mounted with `--overlay-src <root> --overlay <upper>
+///    <work> <root>` instead, and writable roots nested inside it are left to
+///    the overlay so their writes are captured too.
 /// 5. `--ro-bind <subpath> <subpath>` re-applies read-only protections under
 ///    those writable roots so protected subpaths win.
 /// 6. Nested unreadable carveouts under a writable root are masked after that
@@ -239,6 +252,7 @@ fn create_filesystem_args(
     file_system_sandbox_policy: &FileSystemSandboxPolicy,
     cwd: &Path,
     glob_scan_max_depth: Option<usize>,
+    overlay_workspace: Option<&OverlayWorkspace>,
 ) -> Result<BwrapArgs> {
     let unreadable_globs = file_system_sandbox_policy.get_unreadable_globs_with_cwd(cwd);
     // Bubblewrap requires bind mount targets to exist. Skip missing writable
@@ -401,9 +415,20 @@ fn create_filesystem_args(
         }
 
         let mount_root = symlink_target.as_deref().unwrap_or(root);
-        args.push("--bind".to_string());
-        args.push(path_to_string(mount_root));
-        args.push(path_to_string(mount_root));
+        match overlay_workspace {
+            Some(overlay_workspace)
+                if root == overlay_workspace.lower_dir.as_path()
+                    || mount_root == overlay_workspace.lower_dir.as_path() =>
+            {
+                append_overlay_workspace_args(&mut args, overlay_workspace, mount_root);
+            }
+            Some(overlay_workspace) if root.starts_with(&overlay_workspace.lower_dir) => {}
+            _ => {
+                args.push("--bind".to_string());
+                args.push(path_to_string(mount_root));
+                args.push(path_to_string(mount_root));
+            }
+        }
 
         let mut read_only_subpaths: Vec<PathBuf> = writable_root
             .read_only_subpaths
@@ -785,6 +810,19 @@ fn append_mount_target_parent_dir_args(args: &mut Vec<String>, mount_target: &Pa
     }
 }
 
+fn append_overlay_workspace_args(
+    args: &mut Vec<String>,
+    overlay_workspace: &OverlayWorkspace,
+    mount_root: &Path,
+) {
+    args.push("--overlay-src".to_string());
+    args.push(path_to_string(mount_root));
+    args.push("--overlay".to_string());
+    args.push(path_to_string(overlay_workspace.upper_dir.as_path()));
+    args.push(path_to_string(overlay_workspace.work_dir.as_path()));
+    args.push(path_to_string(mount_root));
+}
+
 fn append_read_only_subpath_args(
     args: &mut Vec<String>,
     subpath: &Path,
@@ -1222,9 +1260,13 @@ mod tests {
             },
         ]);
 
-        let args =
-            create_filesystem_args(&policy, temp_dir.path(), NO_UNREADABLE_GLOB_SCAN_MAX_DEPTH)
-                .expect("filesystem args");
+        let args = create_filesystem_args(
+            &policy,
+            temp_dir.path(),
+            NO_UNREADABLE_GLOB_SCAN_MAX_DEPTH,
+            /*overlay_workspace*/ None,
+        )
+        .expect("filesystem args");
 
         assert!(args.args.windows(3).any(|window| {
             window == ["--bind", real_root_str.as_str(), real_root_str.as_str()]
@@ -1267,9 +1309,13 @@ mod tests {
             access: FileSystemAccessMode::Write,
         }]);
 
-        let args =
-            create_filesystem_args(&policy, temp_dir.path(), NO_UNREADABLE_GLOB_SCAN_MAX_DEPTH)
-                .expect("filesystem args");
+        let args = create_filesystem_args(
+            &policy,
+            temp_dir.path(),
+            NO_UNREADABLE_GLOB_SCAN_MAX_DEPTH,
+            /*overlay_workspace*/ None,
+        )
+        .expect("filesystem args");
 
         assert!(args.args.windows(3).any(|window| {
             window
@@ -1306,9 +1352,13 @@ mod tests {
             access: FileSystemAccessMode::Write,
         }]);
 
-        let err =
-            create_filesystem_args(&policy, temp_dir.path(), NO_UNREADABLE_GLOB_SCAN_MAX_DEPTH)
-                .expect_err("protected symlinked subpath should fail closed");
+        let err = create_filesystem_args(
+            &policy,
+            temp_dir.path(),
+            NO_UNREADABLE_GLOB_SCAN_MAX_DEPTH,
+            /*overlay_workspace*/ None,
+        )
+        .expect_err("protected symlinked subpath should fail closed");
         let message = err.to_string();
 
         assert!(
@@ -1347,9 +1397,13 @@ mod tests {
             },
         ]);
 
-        let err =
-            create_filesystem_args(&policy, temp_dir.path(), NO_UNREADABLE_GLOB_SCAN_MAX_DEPTH)
-                .expect_err("deny-read path crossing writable symlink should fail closed");
+        let err = create_filesystem_args(
+            &policy,
+            temp_dir.path(),
+            NO_UNREADABLE_GLOB_SCAN_MAX_DEPTH,
+            /*overlay_workspace*/ None,
+        )
+        .expect_err("deny-read path crossing writable symlink should fail closed");
         let message = err.to_string();
 
         assert!(
@@ -1381,6 +1435,7 @@ mod tests {
             &FileSystemSandboxPolicy::from(&policy),
             temp_dir.path(),
             NO_UNREADABLE_GLOB_SCAN_MAX_DEPTH,
+            /*overlay_workspace*/ None,
         )
         .expect("filesystem args");
         let existing_root = path_to_string(&existing_root);
@@ -1398,6 +1453,78 @@ mod tests {
         );
     }
 
+    #[test]
+    fn mounts_overlay_workspace_instead_of_binding_it() {
+        let temp_dir = TempDir::new().expect("temp dir");
+        let workspace = temp_dir.path().join("workspace");
+        let nested = workspace.join("nested");
+        let outside = temp_dir.path().join("outside");
+        std::fs::create_dir_all(&nested).expect("create nested root");
+        std::fs::create_dir(&outside).expect("create outside root");
+        let state_dir =
+            AbsolutePathBuf::try_from(temp_dir.path().join("overlay")).expect("absolute state dir");
+        let overlay_workspace = OverlayWorkspace::new(
+            AbsolutePathBuf::try_from(workspace.as_path()).expect("absolute workspace"),
+            &state_dir,
+        );
+
+        let policy = SandboxPolicy::WorkspaceWrite {
+            writable_roots: vec![
+                AbsolutePathBuf::try_from(workspace.as_path()).expect("absolute workspace"),
+                AbsolutePathBuf::try_from(nested.as_path()).expect("absolute nested root"),
+                AbsolutePathBuf::try_from(outside.as_path()).expect("absolute outside root"),
+            ],
+            read_only_access: Default::default(),
+            network_access: false,
+            exclude_tmpdir_env_var: true,
+            exclude_slash_tmp: true,
+        };
+
+        let args = create_filesystem_args(
+            &FileSystemSandboxPolicy::from(&policy),
+            temp_dir.path(),
+            NO_UNREADABLE_GLOB_SCAN_MAX_DEPTH,
+            Some(&overlay_workspace),
+        )
+        .expect("filesystem args");
+        let workspace = path_to_string(&workspace);
+        let nested = path_to_string(&nested);
+        let outside = path_to_string(&outside);
+        let upper_dir = path_to_string(overlay_workspace.upper_dir.as_path());
+        let work_dir = path_to_string(overlay_workspace.work_dir.as_path());
+
+        assert!(
+            args.args.windows(6).any(|window| {
+                window
+                    == [
+                        "--overlay-src",
+                        workspace.as_str(),
+                        "--overlay",
+                        upper_dir.as_str(),
+                        work_dir.as_str(),
+                        workspace.as_str(),
+                    ]
+            }),
+            "workspace root should be mounted through the overlay: {:?}",
+            args.args,
+        );
+        assert!(
+            !args
+                .args
+                .windows(2)
+                .any(|window| window[0] == "--bind"
+                    && (window[1] == workspace || window[1] == nested)),
+            "workspace and nested roots should not be bound writable: {:?}",
+            args.args,
+        );
+        assert!(
+            args.args
+                .windows(3)
+                .any(|window| window == ["--bind", outside.as_str(), outside.as_str()]),
+            "roots outside the workspace should still be bound writable",
+        );
+    }
+
     #[test]
     fn mounts_dev_before_writable_dev_binds() {
         let sandbox_policy = SandboxPolicy::WorkspaceWrite {
@@ -1412,6 +1539,7 @@ mod tests {
             &FileSystemSandboxPolicy::from(&sandbox_policy),
             Path::new("/"),
             NO_UNREADABLE_GLOB_SCAN_MAX_DEPTH,
+            /*overlay_workspace*/ None,
         )
         .expect("bwrap fs args");
         assert_eq!(
@@ -1464,6 +1592,7 @@ mod tests {
             &FileSystemSandboxPolicy::from(&policy),
             temp_dir.path(),
             NO_UNREADABLE_GLOB_SCAN_MAX_DEPTH,
+            /*overlay_workspace*/ None,
         )
         .expect("filesystem args");
 
@@ -1498,6 +1627,7 @@ mod tests {
             &FileSystemSandboxPolicy::from(&policy),
             temp_dir.path(),
             NO_UNREADABLE_GLOB_SCAN_MAX_DEPTH,
+            /*overlay_workspace*/ None,
         )
         .expect("filesystem args");
 
@@ -1539,9 +1669,13 @@ mod tests {
             },
         ]);
 
-        let args =
-            create_filesystem_args(&policy, temp_dir.path(), NO_UNREADABLE_GLOB_SCAN_MAX_DEPTH)
-                .expect("filesystem args");
+        let args = create_filesystem_args(
+            &policy,
+            temp_dir.path(),
+            NO_UNREADABLE_GLOB_SCAN_MAX_DEPTH,
+            /*overlay_workspace*/ None,
+        )
+        .expect("filesystem args");
 
         assert!(args.args.windows(3).any(|window| {
             window
@@ -1618,9 +1752,13 @@ mod tests {
             },
         ]);
 
-        let args =
-            create_filesystem_args(&policy, temp_dir.path(), NO_UNREADABLE_GLOB_SCAN_MAX_DEPTH)
-                .expect("filesystem args");
+        let args = create_filesystem_args(
+            &policy,
+            temp_dir.path(),
+            NO_UNREADABLE_GLOB_SCAN_MAX_DEPTH,
+            /*overlay_workspace*/ None,
+        )
+        .expect("filesystem args");
         let docs_str = path_to_string(docs.as_path());
         let docs_public_str = path_to_string(docs_public.as_path());
         let docs_ro_index = args
@@ -1672,9 +1810,13 @@ mod tests {
             },
         ]);
 
-        let args =
-            create_filesystem_args(&policy, temp_dir.path(), NO_UNREADABLE_GLOB_SCAN_MAX_DEPTH)
-                .expect("filesystem args");
+        let args = create_filesystem_args(
+            &policy,
+            temp_dir.path(),
+            NO_UNREADABLE_GLOB_SCAN_MAX_DEPTH,
+            /*overlay_workspace*/ None,
+        )
+        .expect("filesystem args");
         let blocked_str = path_to_string(blocked.as_path());
         let allowed_str = path_to_string(allowed.as_path());
         let blocked_none_index = args
@@ -1741,9 +1883,13 @@ mod tests {
             },
         ]);
 
-        let args =
-            create_filesystem_args(&policy, temp_dir.path(), NO_UNREADABLE_GLOB_SCAN_MAX_DEPTH)
-                .expect("filesystem args");
+        let args = create_filesystem_args(
+            &policy,
+            temp_dir.path(),
+            NO_UNREADABLE_GLOB_SCAN_MAX_DEPTH,
+            /*overlay_workspace*/ None,
+        )
+        .expect("filesystem args");
         let blocked_str = path_to_string(blocked.as_path());
         let allowed_dir_str = path_to_string(allowed_dir.as_path());
         let allowed_file_str = path_to_string(allowed_file.as_path());
@@ -1818,9 +1964,13 @@ mod tests {
             },
         ]);
 
-        let args =
-            create_filesystem_args(&policy, temp_dir.path(), NO_UNREADABLE_GLOB_SCAN_MAX_DEPTH)
-                .expect("filesystem args");
+        let args = create_filesystem_args(
+            &policy,
+            temp_dir.path(),
+            NO_UNREADABLE_GLOB_SCAN_MAX_DEPTH,
+            /*overlay_workspace*/ None,
+        )
+        .expect("filesystem args");
         let blocked_none_index = args
             .args
             .windows(4)
@@ -1865,9 +2015,13 @@ mod tests {
             },
         ]);
 
-        let args =
-            create_filesystem_args(&policy, temp_dir.path(), NO_UNREADABLE_GLOB_SCAN_MAX_DEPTH)
-                .expect("filesystem args");
+        let args = create_filesystem_args(
+            &policy,
+            temp_dir.path(),
+            NO_UNREADABLE_GLOB_SCAN_MAX_DEPTH,
+            /*overlay_workspace*/ None,
+        )
+        .expect("filesystem args");
         let blocked_str = path_to_string(blocked.as_path());
 
         assert!(
@@ -1909,9 +2063,13 @@ mod tests {
             },
         ]);
 
-        let args =
-            create_filesystem_args(&policy, temp_dir.path(), NO_UNREADABLE_GLOB_SCAN_MAX_DEPTH)
-                .expect("filesystem args");
+        let args = create_filesystem_args(
+            &policy,
+            temp_dir.path(),
+            NO_UNREADABLE_GLOB_SCAN_MAX_DEPTH,
+            /*overlay_workspace*/ None,
+        )
+        .expect("filesystem args");
         let blocked_file_str = path_to_string(blocked_file.as_path());
 
         assert_eq!(args.preserved_files.len(), 1);
@@ -1941,8 +2099,13 @@ mod tests {
         let policy =
             default_policy_with_unreadable_glob(format!("{}/**/*.env", temp_dir.path().display()));
 
-        let args =
-            create_filesystem_args(&policy, temp_dir.path(), Some(2)).expect("filesystem args");
+        let args = create_filesystem_args(
+            &policy,
+            temp_dir.path(),
+            Some(2),
+            /*overlay_workspace*/ None,
+        )
+        .expect("filesystem args");
 
         assert_file_masked(&args.args, &root_env);
         assert_file_masked(&args.args, &nested_env);
@@ -1973,8 +2136,13 @@ mod tests {
         let policy =
             default_policy_with_unreadable_glob(format!("{}/**/*.env", link_root.display()));
 
-        let args =
-            create_filesystem_args(&policy, temp_dir.path(), Some(2)).expect("filesystem args");
+        let args = create_filesystem_args(
+            &policy,
+            temp_dir.path(),
+            Some(2),
+            /*overlay_workspace*/ None,
+        )
+        .expect("filesystem args");
 
         assert_file_masked(&args.args, &real_secret);
     }
@@ -19,6 +19,7 @@ use codex_protocol::protocol::FileSystemSandboxPolicy;
 use codex_protocol::protocol::NetworkSandboxPolicy;
 use codex_protocol::protocol::SandboxPolicy;
 use codex_sandboxing::landlock::CODEX_LINUX_SANDBOX_ARG0;
+use codex_sandboxing::overlay::OverlayWorkspace;
 
 #[derive(Debug, Parser)]
 /// CLI surface for the Linux sandbox helper.
@@ -86,6 +87,11 @@ pub struct LandlockCommand {
     #[arg(long = "no-proc", default_value_t = false)]
     pub no_proc: bool,
 
+    /// Internal: mount the overlay's lower directory through overlayfs so
+    /// writes land in its upper directory instead of the real workspace.
+    #[arg(long = "overlay-workspace", hide = true)]
+    pub overlay_workspace: Option<OverlayWorkspace>,
+
     /// Full command args to run under the Linux sandbox helper.
     #[arg(trailing_var_arg = true)]
     pub command: Vec<String>,
@@ -110,6 +116,7 @@ pub fn run_main() -> ! {
         allow_network_for_proxy,
         proxy_route_spec,
         no_proc,
+        overlay_workspace,
         command,
     } = LandlockCommand::parse();
 
@@ -117,6 +124,7 @@ pub fn run_main() -> ! {
         panic!("No command specified to execute.");
     }
     ensure_inner_stage_mode_is_valid(apply_seccomp_then_exec, use_legacy_landlock);
+    ensure_overlay_workspace_mode_is_valid(overlay_workspace.as_ref(), use_legacy_landlock);
     let EffectiveSandboxPolicies {
         sandbox_policy,
         file_system_sandbox_policy,
@@ -200,10 +208,10 @@ pub fn run_main() -> ! {
             &sandbox_policy_cwd,
             command_cwd.as_deref(),
             &file_system_sandbox_policy,
-            network_sandbox_policy,
+            bwrap_network_mode(network_sandbox_policy, allow_network_for_proxy),
             inner,
             !no_proc,
-            allow_network_for_proxy,
+            overlay_workspace,
         );
     }
 
@@ -382,6 +390,15 @@ fn ensure_inner_stage_mode_is_valid(apply_seccomp_then_exec: bool, use_legacy_la
     }
 }
 
+fn ensure_overlay_workspace_mode_is_valid(
+    overlay_workspace: Option<&OverlayWorkspace>,
+    use_legacy_landlock: bool,
+) {
+    if overlay_workspace.is_some() && use_legacy_landlock {
+        panic!("--overlay-workspace is incompatible with --use-legacy-landlock");
+    }
+}
+
 fn ensure_legacy_landlock_mode_supports_policy(
     use_legacy_landlock: bool,
     file_system_sandbox_policy: &FileSystemSandboxPolicy,
@@ -402,12 +419,11 @@ fn run_bwrap_with_proc_fallback(
     sandbox_policy_cwd: &Path,
     command_cwd: Option<&Path>,
     file_system_sandbox_policy: &FileSystemSandboxPolicy,
-    network_sandbox_policy: NetworkSandboxPolicy,
+    network_mode: BwrapNetworkMode,
     inner: Vec<String>,
     mount_proc: bool,
-    allow_network_for_proxy: bool,
+    overlay_workspace: Option<OverlayWorkspace>,
 ) -> ! {
-    let network_mode = bwrap_network_mode(network_sandbox_policy, allow_network_for_proxy);
     let mut mount_proc = mount_proc;
     let command_cwd = command_cwd.unwrap_or(sandbox_policy_cwd);
 
@@ -424,9 +440,18 @@ fn run_bwrap_with_proc_fallback(
         mount_proc = false;
     }
 
+    // Core only lays out the overlay paths; the first sandboxed command of the
+    // turn creates them.
+    if let Some(overlay_workspace) = overlay_workspace.as_ref()
+        && let Err(err) = overlay_workspace.create_dirs()
+    {
+        panic!("failed to create overlay workspace directories: {err}");
+    }
+
     let options = BwrapOptions {
         mount_proc,
         network_mode,
+        overlay_workspace,
         ..Default::default()
     };
     let mut bwrap_args = build_bwrap_argv(
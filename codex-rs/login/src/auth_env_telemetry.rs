@@ -67,6 +67,7 @@ mod tests {
             auth: None,
             aws: None,
             wire_api: WireApi::Responses,
+            messages: None,
             query_params: None,
             http_headers: None,
             env_http_headers: None,
@@ -370,6 +370,7 @@ async fn run_codex_tool_session_inner(
                     | EventMsg::ItemCompleted(_)
                     | EventMsg::HookStarted(_)
                     | EventMsg::HookCompleted(_)
+                    | EventMsg::NetworkTrafficRecorded(_)
                     | EventMsg::AgentMessageContentDelta(_)
                     | EventMsg::ReasoningContentDelta(_)
                     | EventMsg::ReasoningRawContentDelta(_)
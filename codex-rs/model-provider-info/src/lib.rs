@@ -27,6 +27,8 @@ const DEFAULT_STREAM_IDLE_TIMEOUT_MS: u64 = 300_000;
 const DEFAULT_STREAM_MAX_RETRIES: u64 = 5;
 const DEFAULT_REQUEST_MAX_RETRIES: u64 = 4;
 pub const DEFAULT_WEBSOCKET_CONNECT_TIMEOUT_MS: u64 = 15_000;
+const DEFAULT_ANTHROPIC_VERSION: &str = "2023-06-01";
+const DEFAULT_MESSAGES_MAX_TOKENS: u64 = 32_000;
 /// Hard cap for user-configured `stream_max_retries`.
 const MAX_STREAM_MAX_RETRIES: u64 = 100;
 /// Hard cap for user-configured `request_max_retries`.
@@ -37,7 +39,6 @@ pub const OPENAI_PROVIDER_ID: &str = "openai";
 const AMAZON_BEDROCK_PROVIDER_NAME: &str = "Amazon Bedrock";
 pub const AMAZON_BEDROCK_PROVIDER_ID: &str = "amazon-bedrock";
 pub const AMAZON_BEDROCK_DEFAULT_BASE_URL: &str = "https://bedrock-mantle.us-east-1.api.aws/v1";
-const CHAT_WIRE_API_REMOVED_ERROR: &str = "`wire_api = \"chat\"` is no longer supported.\nHow to fix: set `wire_api = \"responses\"` in your provider config.\nMore info: https://github.com/openai/codex/discussions/7782";
 pub const LEGACY_OLLAMA_CHAT_PROVIDER_ID: &str = "ollama-chat";
 pub const OLLAMA_CHAT_PROVIDER_REMOVED_ERROR: &str = "`ollama-chat` is no longer supported.\nHow to fix: replace `ollama-chat` with `ollama` in `model_provider`, `oss_provider`, or `--local-provider`.\nMore info: https://github.com/openai/codex/discussions/7782";
 
@@ -48,12 +49,20 @@ pub enum WireApi {
     /// The Responses API exposed by OpenAI at `/v1/responses`.
     #[default]
     Responses,
+    /// The Chat Completions API at `/v1/chat/completions`, for providers that
+    /// do not implement the Responses API.
+    Chat,
+    /// The Anthropic Messages API at `/v1/messages`, for providers and
+    /// gateways that only expose that format.
+    Messages,
 }
 
 impl fmt::Display for WireApi {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let value = match self {
             Self::Responses => "responses",
+            Self::Chat => "chat",
+            Self::Messages => "messages",
         };
         f.write_str(value)
     }
@@ -67,8 +76,12 @@ impl<'de> Deserialize<'de> for WireApi {
         let value = String::deserialize(deserializer)?;
         match value.as_str() {
             "responses" => Ok(Self::Responses),
-            "chat" => Err(serde::de::Error::custom(CHAT_WIRE_API_REMOVED_ERROR)),
-            _ => Err(serde::de::Error::unknown_variant(&value, &["responses"])),
+            "chat" => Ok(Self::Chat),
+            "messages" => Ok(Self::Messages),
+            _ => Err(serde::de::Error::unknown_variant(
+                &value,
+                &["responses", "chat", "messages"],
+            )),
         }
     }
 }
@@ -99,6 +112,8 @@ pub struct ModelProviderInfo {
     /// Which wire protocol this provider expects.
     #[serde(default)]
     pub wire_api: WireApi,
+    /// Messages API settings, used when `wire_api = "messages"`.
+    pub messages: Option<ModelProviderMessagesInfo>,
     /// Optional query parameters to append to the base URL.
     pub query_params: Option<HashMap<String, String>>,
     /// Additional HTTP headers to include in requests to this provider where
@@ -140,8 +155,62 @@ pub struct ModelProviderAwsAuthInfo {
     pub region: Option<String>,
 }
 
+/// Messages API configuration for a model provider.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct ModelProviderMessagesInfo {
+    /// Value sent in the `anthropic-version` header. Defaults to `2023-06-01`.
+    pub anthropic_version: Option<String>,
+    /// Beta flags sent in the `anthropic-beta` header.
+    #[serde(default)]
+    pub anthropic_beta: Vec<String>,
+    /// Maximum number of output tokens per request. The Messages API requires
+    /// this value; defaults to 32000.
+    pub max_tokens: Option<u64>,
+    /// Whether to mark the system prompt, tools, and latest message as prompt
+    /// cache breakpoints. Defaults to true.
+    pub prompt_caching: Option<bool>,
+    /// Token budget for extended thinking. Thinking is disabled when unset.
+    pub thinking_budget_tokens: Option<u64>,
+}
+
+impl ModelProviderMessagesInfo {
+    /// Effective `anthropic-version` header value.
+    pub fn anthropic_version(&self) -> &str {
+        self.anthropic_version
+            .as_deref()
+            .unwrap_or(DEFAULT_ANTHROPIC_VERSION)
+    }
+
+    /// Effective `max_tokens` for each request.
+    pub fn max_tokens(&self) -> u64 {
+        self.max_tokens.unwrap_or(DEFAULT_MESSAGES_MAX_TOKENS)
+    }
+
+    /// Whether prompt cache breakpoints should be sent.
+    pub fn prompt_caching(&self) -> bool {
+        self.prompt_caching.unwrap_or(true)
+    }
+}
+
 impl ModelProviderInfo {
     pub fn validate(&self) -> std::result::Result<(), String> {
+        if self.messages.is_some() && self.wire_api != WireApi::Messages {
+            return Err("provider messages requires wire_api = \"messages\"".to_string());
+        }
+
+        // The Messages API rejects requests whose thinking budget does not
+        // leave room for output, so surface it at config load instead.
+        if let Some(messages) = self.messages.as_ref()
+            && let Some(thinking_budget_tokens) = messages.thinking_budget_tokens
+            && thinking_budget_tokens >= messages.max_tokens()
+        {
+            return Err(format!(
+                "provider messages.thinking_budget_tokens ({thinking_budget_tokens}) must be less than max_tokens ({})",
+                messages.max_tokens()
+            ));
+        }
+
         if self.aws.is_some() {
             if self.supports_websockets {
                 // TODO(celia-oai): Support AWS SigV4 signing for WebSocket
@@ -320,6 +389,7 @@ impl ModelProviderInfo {
             auth: None,
             aws: None,
             wire_api: WireApi::Responses,
+            messages: None,
             query_params: None,
             http_headers: Some(
                 [("version".to_string(), env!("CARGO_PKG_VERSION").to_string())]
@@ -362,6 +432,7 @@ impl ModelProviderInfo {
                 region: None,
             })),
             wire_api: WireApi::Responses,
+            messages: None,
             query_params: None,
             http_headers: None,
             env_http_headers: None,
@@ -493,6 +564,7 @@ pub fn create_oss_provider_with_base_url(base_url: &str, wire_api: WireApi) -> M
         auth: None,
         aws: None,
         wire_api,
+        messages: None,
         query_params: None,
         http_headers: None,
         env_http_headers: None,
@@ -20,6 +20,7 @@ base_url = "http://localhost:11434/v1"
         auth: None,
         aws: None,
         wire_api: WireApi::Responses,
+        messages: None,
         query_params: None,
         http_headers: None,
         env_http_headers: None,
@@ -52,6 +53,7 @@ query_params = { api-version = "2025-04-01-preview" }
         auth: None,
         aws: None,
         wire_api: WireApi::Responses,
+        messages: None,
         query_params: Some(maplit::hashmap! {
             "api-version".to_string() => "2025-04-01-preview".to_string(),
         }),
@@ -87,6 +89,7 @@ env_http_headers = { "X-Example-Env-Header" = "EXAMPLE_ENV_VAR" }
         auth: None,
         aws: None,
         wire_api: WireApi::Responses,
+        messages: None,
         query_params: None,
         http_headers: Some(maplit::hashmap! {
             "X-Example-Header".to_string() => "example-value".to_string(),
@@ -107,16 +110,104 @@ env_http_headers = { "X-Example-Env-Header" = "EXAMPLE_ENV_VAR" }
 }
 
 #[test]
-fn test_deserialize_chat_wire_api_shows_helpful_error() {
+fn test_deserialize_chat_wire_api() {
     let provider_toml = r#"
-name = "OpenAI using Chat Completions"
-base_url = "https://api.openai.com/v1"
-env_key = "OPENAI_API_KEY"
+name = "On-prem Chat Completions"
+base_url = "http://localhost:8000/v1"
 wire_api = "chat"
         "#;
 
-    let err = toml::from_str::<ModelProviderInfo>(provider_toml).unwrap_err();
-    assert!(err.to_string().contains(CHAT_WIRE_API_REMOVED_ERROR));
+    let provider: ModelProviderInfo = toml::from_str(provider_toml).unwrap();
+    assert_eq!(provider.wire_api, WireApi::Chat);
+    assert_eq!(provider.wire_api.to_string(), "chat");
+}
+
+#[test]
+fn test_deserialize_messages_wire_api() {
+    let provider_toml = r#"
+name = "Messages gateway"
+base_url = "https://gateway.example.com/v1"
+wire_api = "messages"
+env_http_headers = { "x-api-key" = "GATEWAY_API_KEY" }
+
+[messages]
+anthropic_beta = ["interleaved-thinking-2025-05-14"]
+max_tokens = 8192
+thinking_budget_tokens = 4096
+        "#;
+
+    let provider: ModelProviderInfo = toml::from_str(provider_toml).unwrap();
+    assert_eq!(provider.wire_api, WireApi::Messages);
+    assert_eq!(provider.wire_api.to_string(), "messages");
+    let messages = provider.messages.clone().unwrap();
+    assert_eq!(
+        messages,
+        ModelProviderMessagesInfo {
+            anthropic_version: None,
+            anthropic_beta: vec!["interleaved-thinking-2025-05-14".to_string()],
+            max_tokens: Some(8192),
+            prompt_caching: None,
+            thinking_budget_tokens: Some(4096),
+        }
+    );
+    assert_eq!(messages.anthropic_version(), "2023-06-01");
+    assert_eq!(messages.max_tokens(), 8192);
+    assert!(messages.prompt_caching());
+    assert_eq!(provider.validate(), Ok(()));
+}
+
+#[test]
+fn test_validate_provider_messages_requires_messages_wire_api() {
+    let provider = ModelProviderInfo {
+        messages: Some(ModelProviderMessagesInfo::default()),
+        ..ModelProviderInfo::create_openai_provider(/*base_url*/ None)
+    };
+
+    assert_eq!(
+        provider.validate(),
+        Err("provider messages requires wire_api = \"messages\"".to_string())
+    );
+}
+
+#[test]
+fn test_validate_provider_messages_thinking_budget_below_max_tokens() {
+    let provider_with = |messages| ModelProviderInfo {
+        wire_api: WireApi::Messages,
+        messages: Some(messages),
+        ..ModelProviderInfo::create_openai_provider(/*base_url*/ None)
+    };
+
+    assert_eq!(
+        provider_with(ModelProviderMessagesInfo {
+            max_tokens: Some(8192),
+            thinking_budget_tokens: Some(8192),
+            ..Default::default()
+        })
+        .validate(),
+        Err(
+            "provider messages.thinking_budget_tokens (8192) must be less than max_tokens (8192)"
+                .to_string()
+        )
+    );
+    assert_eq!(
+        provider_with(ModelProviderMessagesInfo {
+            thinking_budget_tokens: Some(64_000),
+            ..Default::default()
+        })
+        .validate(),
+        Err(
+            "provider messages.thinking_budget_tokens (64000) must be less than max_tokens (32000)"
+                .to_string()
+        )
+    );
+    assert_eq!(
+        provider_with(ModelProviderMessagesInfo {
+            thinking_budget_tokens: Some(16_000),
+            ..Default::default()
+        })
+        .validate(),
+        Ok(())
+    );
 }
 
 #[test]
@@ -150,6 +241,7 @@ fn test_supports_remote_compaction_for_azure_name() {
         auth: None,
         aws: None,
         wire_api: WireApi::Responses,
+        messages: None,
         query_params: None,
         http_headers: None,
         env_http_headers: None,
@@ -175,6 +267,7 @@ fn test_supports_remote_compaction_for_non_openai_non_azure_provider() {
         auth: None,
         aws: None,
         wire_api: WireApi::Responses,
+        messages: None,
         query_params: None,
         http_headers: None,
         env_http_headers: None,
@@ -255,6 +348,7 @@ fn test_create_amazon_bedrock_provider() {
                 region: None,
             }),
             wire_api: WireApi::Responses,
+            messages: None,
             query_params: None,
             http_headers: None,
             env_http_headers: None,
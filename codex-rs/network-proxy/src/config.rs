@@ -101,6 +101,71 @@ impl NetworkDomainPermissions {
     }
 }
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum NetworkRequestScheme {
+    Http,
+    Https,
+}
+
+impl NetworkRequestScheme {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Http => "http",
+            Self::Https => "https",
+        }
+    }
+}
+
+/// Variant order matches [`NetworkDomainPermission`]: deny wins when several rules match.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum NetworkRequestRuleDecision {
+    Allow,
+    Deny,
+}
+
+/// A request-level rule keyed on scheme, host, path and HTTP method.
+///
+/// Request rules are only enforceable when the proxy sees the HTTP request: plain HTTP, or HTTPS
+/// when MITM is enabled. An allow rule grants access to matching requests even when the host is
+/// not in the domain allowlist; domain denies and the local/private network guard still apply.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct NetworkRequestRule {
+    /// Restrict the rule to one scheme; matches both `http` and `https` when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scheme: Option<NetworkRequestScheme>,
+    /// Host pattern, using the same syntax as `domains` entries.
+    pub host: String,
+    /// Glob matched against the request path (without the query). `*` also matches `/`, so
+    /// `/*/archive/*` covers `/owner/repo/archive/main.zip`. Matches every path when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// HTTP methods the rule applies to; matches every method when empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub methods: Vec<String>,
+    pub decision: NetworkRequestRuleDecision,
+}
+
+/// A header the MITM proxy adds to requests for matching hosts, with its value read from
+/// `codex-secrets` so the sandboxed command never holds the credential.
+///
+/// Injection only happens for HTTPS requests the proxy terminates (MITM enabled). Any
+/// client-supplied header with the same name is replaced, and the secret value is redacted from
+/// upstream responses before they reach the client.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct NetworkCredentialInjection {
+    /// Host pattern, using the same syntax as `domains` entries.
+    pub host: String,
+    /// Header to set, e.g. `Authorization`.
+    pub header: String,
+    /// Name of the secret holding the header value (e.g. `NPM_TOKEN`).
+    pub secret: String,
+    /// Text prepended to the secret value, e.g. `Bearer `.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prefix: Option<String>,
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum NetworkUnixSocketPermission {
@@ -139,6 +204,13 @@ pub struct NetworkProxySettings {
     pub allow_local_binding: bool,
     #[serde(default)]
     pub mitm: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rules: Vec<NetworkRequestRule>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub credentials: Vec<NetworkCredentialInjection>,
+    /// Report every proxied request to the traffic observer, when one is set.
+    #[serde(default)]
+    pub record_traffic: bool,
 }
 
 impl Default for NetworkProxySettings {
@@ -157,6 +229,9 @@ impl Default for NetworkProxySettings {
             unix_sockets: None,
             allow_local_binding: false,
             mitm: false,
+            rules: Vec::new(),
+            credentials: Vec::new(),
+            record_traffic: false,
         }
     }
 }
@@ -226,6 +301,18 @@ impl NetworkProxySettings {
         self.domains = (!domains.entries.is_empty()).then_some(domains);
     }
 
+    pub fn add_request_rule(&mut self, rule: NetworkRequestRule) {
+        if !self.rules.contains(&rule) {
+            self.rules.push(rule);
+        }
+    }
+
+    pub fn add_credential_injection(&mut self, credential: NetworkCredentialInjection) {
+        if !self.credentials.contains(&credential) {
+            self.credentials.push(credential);
+        }
+    }
+
     pub fn set_allow_unix_sockets(&mut self, allow_unix_sockets: Vec<String>) {
         self.set_unix_socket_entries(allow_unix_sockets, NetworkUnixSocketPermission::Allow);
     }
@@ -588,6 +675,9 @@ mod tests {
                 unix_sockets: None,
                 allow_local_binding: false,
                 mitm: false,
+                rules: Vec::new(),
+                credentials: Vec::new(),
+                record_traffic: false,
             }
         );
     }
@@ -610,6 +700,50 @@ mod tests {
         assert_eq!(config.network, expected);
     }
 
+    #[test]
+    fn request_rules_deserialize_with_optional_fields() {
+        let config: NetworkProxyConfig = serde_json::from_str(
+            r#"{
+                "network": {
+                    "rules": [
+                        {
+                            "host": "github.com",
+                            "path": "/*/archive/*",
+                            "methods": ["GET"],
+                            "decision": "allow"
+                        },
+                        {
+                            "scheme": "https",
+                            "host": "api.github.com",
+                            "decision": "deny"
+                        }
+                    ]
+                }
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.network.rules,
+            vec![
+                NetworkRequestRule {
+                    scheme: None,
+                    host: "github.com".to_string(),
+                    path: Some("/*/archive/*".to_string()),
+                    methods: vec!["GET".to_string()],
+                    decision: NetworkRequestRuleDecision::Allow,
+                },
+                NetworkRequestRule {
+                    scheme: Some(NetworkRequestScheme::Https),
+                    host: "api.github.com".to_string(),
+                    path: None,
+                    methods: Vec::new(),
+                    decision: NetworkRequestRuleDecision::Deny,
+                },
+            ]
+        );
+    }
+
     #[test]
     fn set_allowed_domains_preserves_existing_deny_for_same_pattern() {
         let mut settings = NetworkProxySettings::default();
@@ -0,0 +1,479 @@
+use crate::config::NetworkCredentialInjection;
+use crate::policy::compile_allowlist_globset;
+use crate::responses::text_response;
+use anyhow::Context as _;
+use anyhow::Result;
+use anyhow::ensure;
+use async_trait::async_trait;
+use codex_secrets::SecretName;
+use codex_secrets::SecretScope;
+use codex_secrets::SecretsManager;
+use globset::GlobSet;
+use rama_core::bytes::Bytes;
+use rama_core::error::BoxError;
+use rama_core::futures::stream::Stream;
+use rama_http::Body;
+use rama_http::BodyDataStream;
+use rama_http::HeaderMap;
+use rama_http::HeaderName;
+use rama_http::HeaderValue;
+use rama_http::Response;
+use rama_http::StatusCode;
+use rama_http::header::ACCEPT_ENCODING;
+use rama_http::header::CONTENT_ENCODING;
+use rama_http::header::CONTENT_LENGTH;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::Context as TaskContext;
+use std::task::Poll;
+use tracing::warn;
+
+const REDACTED_SECRET: &[u8] = b"[REDACTED_SECRET]";
+
+/// Source of secret values for MITM credential injection.
+#[async_trait]
+pub trait NetworkCredentialSource: Send + Sync + 'static {
+    /// Returns the value of `name`, or `None` when the secret is not set.
+    async fn resolve_secret(&self, name: &SecretName) -> Result<Option<String>>;
+}
+
+/// Reads credentials from `codex-secrets`, preferring the environment scope over the global one.
+#[derive(Clone)]
+pub struct SecretsCredentialSource {
+    manager: SecretsManager,
+    environment: Option<SecretScope>,
+}
+
+impl SecretsCredentialSource {
+    pub fn new(manager: SecretsManager, environment_id: Option<String>) -> Result<Self> {
+        let environment = environment_id.map(SecretScope::environment).transpose()?;
+        Ok(Self {
+            manager,
+            environment,
+        })
+    }
+}
+
+#[async_trait]
+impl NetworkCredentialSource for SecretsCredentialSource {
+    async fn resolve_secret(&self, name: &SecretName) -> Result<Option<String>> {
+        let manager = self.manager.clone();
+        let environment = self.environment.clone();
+        let name = name.clone();
+        // The local backend decrypts an on-disk file and may hit the OS keyring.
+        tokio::task::spawn_blocking(move || {
+            if let Some(scope) = environment.as_ref()
+                && let Some(value) = manager.get(scope, &name)?
+            {
+                return Ok(Some(value));
+            }
+            manager.get(&SecretScope::Global, &name)
+        })
+        .await
+        .context("secret lookup task failed")?
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct CompiledCredentialInjections {
+    injections: Vec<CompiledCredentialInjection>,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct CompiledCredentialInjection {
+    hosts: GlobSet,
+    header: HeaderName,
+    secret: SecretName,
+    prefix: String,
+}
+
+impl CompiledCredentialInjections {
+    pub(crate) fn for_host(&self, host: &str) -> Vec<CompiledCredentialInjection> {
+        self.injections
+            .iter()
+            .filter(|injection| injection.hosts.is_match(host))
+            .cloned()
+            .collect()
+    }
+}
+
+pub(crate) fn compile_credential_injections(
+    credentials: &[NetworkCredentialInjection],
+) -> Result<CompiledCredentialInjections> {
+    let mut injections = Vec::with_capacity(credentials.len());
+    for (index, credential) in credentials.iter().enumerate() {
+        injections.push(
+            compile_credential_injection(credential)
+                .with_context(|| format!("invalid network.credentials[{index}]"))?,
+        );
+    }
+    Ok(CompiledCredentialInjections { injections })
+}
+
+fn compile_credential_injection(
+    credential: &NetworkCredentialInjection,
+) -> Result<CompiledCredentialInjection> {
+    ensure!(!credential.host.trim().is_empty(), "host cannot be empty");
+    let hosts = compile_allowlist_globset(std::slice::from_ref(&credential.host))?;
+    let header = HeaderName::from_bytes(credential.header.trim().as_bytes())
+        .with_context(|| format!("invalid header name {:?}", credential.header))?;
+    let secret = SecretName::new(&credential.secret)?;
+    let prefix = credential.prefix.clone().unwrap_or_default();
+    HeaderValue::from_str(&prefix).with_context(|| format!("invalid header prefix {prefix:?}"))?;
+    Ok(CompiledCredentialInjection {
+        hosts,
+        header,
+        secret,
+        prefix,
+    })
+}
+
+/// Credentials resolved for one upstream request.
+#[derive(Default)]
+pub(crate) struct ResolvedCredentials {
+    headers: Vec<(HeaderName, HeaderValue)>,
+    secrets: Vec<Vec<u8>>,
+}
+
+impl ResolvedCredentials {
+    pub(crate) async fn resolve(
+        injections: Vec<CompiledCredentialInjection>,
+        source: &dyn NetworkCredentialSource,
+    ) -> Result<Self> {
+        let mut resolved = Self::default();
+        for injection in injections {
+            let Some(value) = source.resolve_secret(&injection.secret).await? else {
+                warn!(
+                    "network credential secret {} is not set; forwarding request without {}",
+                    injection.secret, injection.header
+                );
+                continue;
+            };
+            let mut header_value = HeaderValue::from_str(&format!("{}{value}", injection.prefix))
+                .with_context(|| {
+                format!(
+                    "secret {} is not a valid {} header value",
+                    injection.secret, injection.header
+                )
+            })?;
+            header_value.set_sensitive(true);
+            resolved.headers.push((injection.header, header_value));
+            if !value.is_empty() {
+                resolved.secrets.push(value.into_bytes());
+            }
+        }
+        Ok(resolved)
+    }
+
+    /// Sets the credential headers on `headers`, replacing any values supplied by the client.
+    ///
+    /// Returns the redactor for the upstream response, or `None` when nothing was injected.
+    pub(crate) fn apply(self, headers: &mut HeaderMap) -> Option<CredentialRedactor> {
+        if self.headers.is_empty() {
+            return None;
+        }
+        for (name, value) in self.headers {
+            headers.insert(name, value);
+        }
+        // Ask for an identity-encoded response so the redactor sees the plain bytes. Omitting the
+        // header would allow any encoding.
+        headers.insert(ACCEPT_ENCODING, HeaderValue::from_static("identity"));
+        Some(CredentialRedactor::new(self.secrets))
+    }
+}
+
+/// Replaces injected secret values in upstream responses before they reach the client.
+#[derive(Clone)]
+pub(crate) struct CredentialRedactor {
+    secrets: Arc<[Vec<u8>]>,
+    max_secret_len: usize,
+}
+
+impl CredentialRedactor {
+    fn new(mut secrets: Vec<Vec<u8>>) -> Self {
+        // Longest first so a secret that contains another one is redacted as a whole.
+        secrets.sort_by(|left, right| right.len().cmp(&left.len()).then_with(|| left.cmp(right)));
+        secrets.dedup();
+        let max_secret_len = secrets.first().map_or(0, Vec::len);
+        Self {
+            secrets: secrets.into(),
+            max_secret_len,
+        }
+    }
+
+    /// Redacts injected secrets from the headers and body of `resp`.
+    ///
+    /// Secrets cannot be found in a compressed body, so a response that ignores the identity
+    /// `Accept-Encoding` sent upstream is replaced with a `502 Bad Gateway`.
+    pub(crate) fn redact_response(&self, resp: Response) -> Response {
+        if self.secrets.is_empty() {
+            return resp;
+        }
+        if let Some(encoding) = resp.headers().get(CONTENT_ENCODING)
+            && !encoding.as_bytes().eq_ignore_ascii_case(b"identity")
+        {
+            warn!(
+                "blocking upstream response with content-encoding {encoding:?}: injected credentials cannot be redacted from an encoded body"
+            );
+            return text_response(
+                StatusCode::BAD_GATEWAY,
+                "upstream response is encoded and cannot be checked for injected credentials",
+            );
+        }
+        let (mut parts, body) = resp.into_parts();
+        for value in parts.headers.values_mut() {
+            let redacted = self.redact_all(value.as_bytes());
+            if redacted != value.as_bytes()
+                && let Ok(redacted) = HeaderValue::from_bytes(&redacted)
+            {
+                *value = redacted;
+            }
+        }
+        // Redaction changes the body length.
+        parts.headers.remove(CONTENT_LENGTH);
+        let body = Body::from_stream(RedactStream {
+            inner: Box::pin(body.into_data_stream()),
+            redactor: self.clone(),
+            pending: Vec::new(),
+            done: false,
+        });
+        Response::from_parts(parts, body)
+    }
+
+    fn redact_all(&self, input: &[u8]) -> Vec<u8> {
+        let mut pending = input.to_vec();
+        self.redact_pending(&mut pending, /*flush*/ true)
+    }
+
+    /// Redacts and drains the prefix of `pending` that can no longer be part of a secret split
+    /// across chunks; everything is drained when `flush` is set.
+    fn redact_pending(&self, pending: &mut Vec<u8>, flush: bool) -> Vec<u8> {
+        let limit = if flush {
+            pending.len()
+        } else {
+            pending
+                .len()
+                .saturating_sub(self.max_secret_len.saturating_sub(1))
+        };
+        let mut output = Vec::with_capacity(limit);
+        let mut index = 0;
+        while index < limit {
+            let rest = &pending[index..];
+            if let Some(secret) = self.secrets.iter().find(|secret| rest.starts_with(secret)) {
+                output.extend_from_slice(REDACTED_SECRET);
+                index += secret.len();
+            } else {
+                output.push(pending[index]);
+                index += 1;
+            }
+        }
+        pending.drain(..index.min(pending.len()));
+        output
+    }
+}
+
+struct RedactStream {
+    inner: Pin<Box<BodyDataStream>>,
+    redactor: CredentialRedactor,
+    pending: Vec<u8>,
+    done: bool,
+}
+
+impl Stream for RedactStream {
+    type Item = Result<Bytes, BoxError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            if this.done {
+                return Poll::Ready(None);
+            }
+            match this.inner.as_mut().poll_next(cx) {
+                Poll::Ready(Some(Ok(bytes))) => {
+                    this.pending.extend_from_slice(&bytes);
+                    let output = this
+                        .redactor
+                        .redact_pending(&mut this.pending, /*flush*/ false);
+                    if !output.is_empty() {
+                        return Poll::Ready(Some(Ok(Bytes::from(output))));
+                    }
+                }
+                Poll::Ready(Some(Err(err))) => return Poll::Ready(Some(Err(err))),
+                Poll::Ready(None) => {
+                    this.done = true;
+                    let output = this
+                        .redactor
+                        .redact_pending(&mut this.pending, /*flush*/ true);
+                    if !output.is_empty() {
+                        return Poll::Ready(Some(Ok(Bytes::from(output))));
+                    }
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::collections::HashMap;
+
+    struct StaticSource(HashMap<String, String>);
+
+    #[async_trait]
+    impl NetworkCredentialSource for StaticSource {
+        async fn resolve_secret(&self, name: &SecretName) -> Result<Option<String>> {
+            Ok(self.0.get(name.as_str()).cloned())
+        }
+    }
+
+    fn credential(host: &str, header: &str, secret: &str) -> NetworkCredentialInjection {
+        NetworkCredentialInjection {
+            host: host.to_string(),
+            header: header.to_string(),
+            secret: secret.to_string(),
+            prefix: Some("Bearer ".to_string()),
+        }
+    }
+
+    #[test]
+    fn compile_rejects_invalid_secret_names() {
+        let err = compile_credential_injections(&[credential(
+            "registry.npmjs.org",
+            "authorization",
+            "npm-token",
+        )])
+        .unwrap_err();
+
+        assert_eq!(err.to_string(), "invalid network.credentials[0]");
+    }
+
+    #[tokio::test]
+    async fn resolved_credentials_replace_client_headers() {
+        let injections = compile_credential_injections(&[
+            credential("*.npmjs.org", "authorization", "NPM_TOKEN"),
+            credential("github.com", "authorization", "GITHUB_TOKEN"),
+        ])
+        .unwrap();
+        let source = StaticSource(HashMap::from([(
+            "NPM_TOKEN".to_string(),
+            "npm-secret".to_string(),
+        )]));
+        let mut headers = HeaderMap::new();
+        headers.insert("authorization", HeaderValue::from_static("Bearer sandbox"));
+        headers.insert(ACCEPT_ENCODING, HeaderValue::from_static("gzip"));
+
+        let resolved =
+            ResolvedCredentials::resolve(injections.for_host("registry.npmjs.org"), &source)
+                .await
+                .unwrap();
+        let redactor = resolved.apply(&mut headers);
+
+        assert!(redactor.is_some());
+        assert_eq!(
+            headers.get("authorization"),
+            Some(&HeaderValue::from_static("Bearer npm-secret"))
+        );
+        assert!(headers.get("authorization").unwrap().is_sensitive());
+        assert_eq!(
+            headers.get(ACCEPT_ENCODING),
+            Some(&HeaderValue::from_static("identity"))
+        );
+    }
+
+    #[tokio::test]
+    async fn missing_secrets_leave_the_request_untouched() {
+        let injections =
+            compile_credential_injections(&[credential("github.com", "authorization", "GH")])
+                .unwrap();
+        let source = StaticSource(HashMap::new());
+        let mut headers = HeaderMap::new();
+        headers.insert(ACCEPT_ENCODING, HeaderValue::from_static("gzip"));
+
+        let resolved = ResolvedCredentials::resolve(injections.for_host("github.com"), &source)
+            .await
+            .unwrap();
+
+        assert!(resolved.apply(&mut headers).is_none());
+        assert_eq!(
+            headers.get(ACCEPT_ENCODING),
+            Some(&HeaderValue::from_static("gzip"))
+        );
+    }
+
+    #[test]
+    fn redactor_handles_secrets_split_across_chunks() {
+        let redactor = CredentialRedactor::new(vec![b"s3cr3t".to_vec()]);
+        let mut pending = Vec::new();
+        let mut output = Vec::new();
+        for chunk in [&b"token=s3"[..], b"cr", b"3t; again s3cr3t", b" end"] {
+            pending.extend_from_slice(chunk);
+            output.extend(redactor.redact_pending(&mut pending, /*flush*/ false));
+        }
+        output.extend(redactor.redact_pending(&mut pending, /*flush*/ true));
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "token=[REDACTED_SECRET]; again [REDACTED_SECRET] end"
+        );
+    }
+
+    #[test]
+    fn redacted_responses_drop_content_length() {
+        let redactor = CredentialRedactor::new(vec![b"npm-secret".to_vec()]);
+        let resp = Response::builder()
+            .header(CONTENT_LENGTH, "25")
+            .header("x-echo", "Bearer npm-secret")
+            .body(Body::from("{\"token\":\"npm-secret\"}".to_string()))
+            .unwrap();
+
+        let resp = redactor.redact_response(resp);
+
+        assert_eq!(resp.headers().get(CONTENT_LENGTH), None);
+        assert_eq!(
+            resp.headers().get("x-echo"),
+            Some(&HeaderValue::from_static("Bearer [REDACTED_SECRET]"))
+        );
+    }
+
+    #[test]
+    fn encoded_responses_are_blocked() {
+        let redactor = CredentialRedactor::new(vec![b"npm-secret".to_vec()]);
+        // A gzip member header followed by an opaque deflate stream; the redactor cannot see
+        // whether the compressed bytes contain the secret.
+        let gzip_body: &[u8] = &[
+            0x1f, 0x8b, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x03, 0xab, 0x56, 0x2a, 0xc9,
+        ];
+        let resp = Response::builder()
+            .header(CONTENT_ENCODING, "gzip")
+            .header("x-echo", "Bearer npm-secret")
+            .body(Body::from(gzip_body.to_vec()))
+            .unwrap();
+
+        let resp = redactor.redact_response(resp);
+
+        assert_eq!(resp.status(), StatusCode::BAD_GATEWAY);
+        assert_eq!(resp.headers().get(CONTENT_ENCODING), None);
+        assert_eq!(resp.headers().get("x-echo"), None);
+    }
+
+    #[test]
+    fn identity_encoded_responses_are_redacted() {
+        let redactor = CredentialRedactor::new(vec![b"npm-secret".to_vec()]);
+        let resp = Response::builder()
+            .header(CONTENT_ENCODING, "identity")
+            .header("x-echo", "Bearer npm-secret")
+            .body(Body::from("ok"))
+            .unwrap();
+
+        let resp = redactor.redact_response(resp);
+
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(
+            resp.headers().get("x-echo"),
+            Some(&HeaderValue::from_static("Bearer [REDACTED_SECRET]"))
+        );
+    }
+}
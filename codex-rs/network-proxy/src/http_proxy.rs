@@ -1,6 +1,8 @@
 use crate::config::NetworkMode;
+use crate::config::NetworkRequestScheme;
 use crate::mitm;
 use crate::network_policy::BlockDecisionAuditEventArgs;
+use crate::network_policy::ConnectPolicyDecision;
 use crate::network_policy::NetworkDecision;
 use crate::network_policy::NetworkDecisionSource;
 use crate::network_policy::NetworkPolicyDecider;
@@ -8,9 +10,12 @@ use crate::network_policy::NetworkPolicyDecision;
 use crate::network_policy::NetworkPolicyRequest;
 use crate::network_policy::NetworkPolicyRequestArgs;
 use crate::network_policy::NetworkProtocol;
+use crate::network_policy::RequestRuleEnforcement;
 use crate::network_policy::emit_allow_decision_audit_event;
 use crate::network_policy::emit_block_decision_audit_event;
+use crate::network_policy::evaluate_connect_policy;
 use crate::network_policy::evaluate_host_policy;
+use crate::network_policy::evaluate_request_rules;
 use crate::policy::normalize_host;
 use crate::reasons::REASON_METHOD_NOT_ALLOWED;
 use crate::reasons::REASON_MITM_REQUIRED;
@@ -26,6 +31,8 @@ use crate::runtime::unix_socket_permissions_supported;
 use crate::state::BlockedRequest;
 use crate::state::BlockedRequestArgs;
 use crate::state::NetworkProxyState;
+use crate::traffic::TrafficRecording;
+use crate::traffic::TrafficRequest;
 use crate::upstream::UpstreamClient;
 use crate::upstream::proxy_for_connect;
 use anyhow::Context as _;
@@ -192,6 +199,14 @@ async fn http_connect_accept(
         .await);
     }
 
+    let mitm_state = match app_state.mitm_state().await {
+        Ok(state) => state,
+        Err(err) => {
+            error!("failed to load MITM state: {err}");
+            return Err(text_response(StatusCode::INTERNAL_SERVER_ERROR, "error"));
+        }
+    };
+
     let request = NetworkPolicyRequest::new(NetworkPolicyRequestArgs {
         protocol: NetworkProtocol::HttpsConnect,
         host: host.clone(),
@@ -202,11 +217,22 @@ async fn http_connect_accept(
         exec_policy_hint: None,
     });
 
-    match evaluate_host_policy(&app_state, policy_decider.as_ref(), &request).await {
-        Ok(NetworkDecision::Deny {
-            reason,
-            source,
-            decision,
+    let request_rules = match evaluate_connect_policy(
+        &app_state,
+        policy_decider.as_ref(),
+        &request,
+        mitm_state.is_some(),
+    )
+    .await
+    {
+        Ok(ConnectPolicyDecision {
+            decision:
+                NetworkDecision::Deny {
+                    reason,
+                    source,
+                    decision,
+                },
+            ..
         }) => {
             let details = PolicyDecisionDetails {
                 decision,
@@ -233,29 +259,25 @@ async fn http_connect_accept(
             warn!("CONNECT blocked (client={client}, host={host}, reason={reason})");
             return Err(blocked_text_with_details(&reason, &details));
         }
-        Ok(NetworkDecision::Allow) => {
+        Ok(ConnectPolicyDecision {
+            decision: NetworkDecision::Allow,
+            request_rules,
+        }) => {
             let client = client.as_deref().unwrap_or_default();
             info!("CONNECT allowed (client={client}, host={host})");
+            request_rules
         }
         Err(err) => {
             error!("failed to evaluate host for CONNECT {host}: {err}");
             return Err(text_response(StatusCode::INTERNAL_SERVER_ERROR, "error"));
         }
-    }
+    };
 
     let mode = app_state
         .network_mode()
         .await
         .map_err(|err| internal_error("failed to read network mode", err))?;
 
-    let mitm_state = match app_state.mitm_state().await {
-        Ok(state) => state,
-        Err(err) => {
-            error!("failed to load MITM state: {err}");
-            return Err(text_response(StatusCode::INTERNAL_SERVER_ERROR, "error"));
-        }
-    };
-
     if mode == NetworkMode::Limited && mitm_state.is_none() {
         // Limited mode is designed to be read-only. Without MITM, a CONNECT tunnel would hide the
         // inner HTTP method/headers from the proxy, effectively bypassing method policy.
@@ -304,6 +326,9 @@ async fn http_connect_accept(
     if let Some(mitm_state) = mitm_state {
         req.extensions_mut().insert(mitm_state);
     }
+    if let Some(request_rules) = request_rules {
+        req.extensions_mut().insert(request_rules);
+    }
 
     Ok((
         Response::builder()
@@ -330,7 +355,13 @@ async fn http_connect_proxy(upgraded: Upgraded) -> Result<(), Infallible> {
         return Ok(());
     };
 
-    if mode == NetworkMode::Limited
+    // Limited mode and request rules both need the inner HTTP requests, so intercept the tunnel.
+    let needs_mitm = mode == NetworkMode::Limited
+        || upgraded
+            .extensions()
+            .get::<RequestRuleEnforcement>()
+            .is_some();
+    if needs_mitm
         && upgraded
             .extensions()
             .get::<Arc<mitm::MitmState>>()
@@ -633,7 +664,19 @@ async fn http_plain_proxy(
         exec_policy_hint: None,
     });
 
-    match evaluate_host_policy(&app_state, policy_decider.as_ref(), &request).await {
+    let decision = match evaluate_request_rules(
+        &app_state,
+        &request,
+        NetworkRequestScheme::Http,
+        req.uri().path(),
+    )
+    .await
+    {
+        Ok(Some(decision)) => Ok(decision),
+        Ok(None) => evaluate_host_policy(&app_state, policy_decider.as_ref(), &request).await,
+        Err(err) => Err(err),
+    };
+    match decision {
         Ok(NetworkDecision::Deny {
             reason,
             source,
@@ -737,10 +780,41 @@ async fn http_plain_proxy(
 
     // Strip hop-by-hop headers only after extracting metadata used for policy correlation.
     remove_hop_by_hop_request_headers(req.headers_mut());
+    let recording = match app_state.traffic_observer().await {
+        Ok(observer) => observer.map(|observer| {
+            TrafficRecording::start(
+                observer,
+                TrafficRequest {
+                    protocol: "http",
+                    method: req.method().as_str(),
+                    url: &req.uri().to_string(),
+                    headers: req.headers(),
+                    client: client_addr(&req),
+                },
+            )
+        }),
+        Err(err) => {
+            warn!("failed to read traffic recording config: {err}");
+            None
+        }
+    };
+    let req = match &recording {
+        Some(recording) => {
+            let (parts, body) = req.into_parts();
+            Request::from_parts(parts, recording.record_request_body(body))
+        }
+        None => req,
+    };
     match client.serve(req).await {
-        Ok(resp) => Ok(resp),
+        Ok(resp) => Ok(match recording {
+            Some(recording) => recording.record_response(resp),
+            None => resp,
+        }),
         Err(err) => {
             warn!("upstream request failed: {err}");
+            if let Some(recording) = recording {
+                recording.record_failure("upstream failure");
+            }
             Ok(text_response(StatusCode::BAD_GATEWAY, "upstream failure"))
         }
     }
@@ -2,6 +2,7 @@
 
 mod certs;
 mod config;
+mod credentials;
 mod http_proxy;
 mod mitm;
 mod network_policy;
@@ -12,16 +13,23 @@ mod responses;
 mod runtime;
 mod socks5;
 mod state;
+mod traffic;
 mod upstream;
 
+pub use config::NetworkCredentialInjection;
 pub use config::NetworkDomainPermission;
 pub use config::NetworkDomainPermissionEntry;
 pub use config::NetworkDomainPermissions;
 pub use config::NetworkMode;
 pub use config::NetworkProxyConfig;
+pub use config::NetworkRequestRule;
+pub use config::NetworkRequestRuleDecision;
+pub use config::NetworkRequestScheme;
 pub use config::NetworkUnixSocketPermission;
 pub use config::NetworkUnixSocketPermissions;
 pub use config::host_and_port_from_network_addr;
+pub use credentials::NetworkCredentialSource;
+pub use credentials::SecretsCredentialSource;
 pub use network_policy::NetworkDecision;
 pub use network_policy::NetworkDecisionSource;
 pub use network_policy::NetworkPolicyDecider;
@@ -60,3 +68,8 @@ pub use state::PartialNetworkConfig;
 pub use state::PartialNetworkProxyConfig;
 pub use state::build_config_state;
 pub use state::validate_policy_against_constraints;
+pub use traffic::JsonlTrafficRecorder;
+pub use traffic::NetworkTrafficBody;
+pub use traffic::NetworkTrafficEntry;
+pub use traffic::NetworkTrafficHeader;
+pub use traffic::NetworkTrafficObserver;
@@ -1,7 +1,16 @@
 use crate::certs::ManagedMitmCa;
 use crate::config::NetworkMode;
+use crate::config::NetworkRequestScheme;
+use crate::network_policy::NetworkDecision;
+use crate::network_policy::NetworkDecisionSource;
+use crate::network_policy::NetworkPolicyRequest;
+use crate::network_policy::NetworkPolicyRequestArgs;
+use crate::network_policy::NetworkProtocol;
+use crate::network_policy::RequestRuleEnforcement;
+use crate::network_policy::evaluate_request_rules;
 use crate::policy::normalize_host;
 use crate::reasons::REASON_METHOD_NOT_ALLOWED;
+use crate::reasons::REASON_NOT_ALLOWED;
 use crate::responses::blocked_text_response;
 use crate::responses::text_response;
 use crate::runtime::HostBlockDecision;
@@ -9,6 +18,8 @@ use crate::runtime::HostBlockReason;
 use crate::state::BlockedRequest;
 use crate::state::BlockedRequestArgs;
 use crate::state::NetworkProxyState;
+use crate::traffic::TrafficRecording;
+use crate::traffic::TrafficRequest;
 use crate::upstream::UpstreamClient;
 use anyhow::Context as _;
 use anyhow::Result;
@@ -57,6 +68,7 @@ struct MitmPolicyContext {
     target_host: String,
     target_port: u16,
     mode: NetworkMode,
+    request_rules: Option<RequestRuleEnforcement>,
     app_state: Arc<NetworkProxyState>,
 }
 
@@ -139,11 +151,16 @@ pub(crate) async fn mitm_tunnel(upgraded: Upgraded) -> Result<()> {
         .get::<NetworkMode>()
         .copied()
         .unwrap_or(NetworkMode::Full);
+    let request_rules = upgraded
+        .extensions()
+        .get::<RequestRuleEnforcement>()
+        .copied();
     let request_ctx = Arc::new(MitmRequestContext {
         policy: MitmPolicyContext {
             target_host,
             target_port,
             mode,
+            request_rules,
             app_state,
         },
         mitm,
@@ -207,12 +224,41 @@ async fn forward_request(req: Request, request_ctx: &MitmRequestContext) -> Resu
     let path = path_and_query(req.uri());
     let log_path = path_for_log(req.uri());
 
+    let credentials = request_ctx
+        .policy
+        .app_state
+        .resolve_credentials(&target_host)
+        .await?;
+    let traffic_observer = request_ctx.policy.app_state.traffic_observer().await?;
+    let client = req
+        .extensions()
+        .get::<SocketInfo>()
+        .map(|info| info.peer_addr().to_string());
+
     let (mut parts, body) = req.into_parts();
     let authority = authority_header_value(&target_host, target_port);
     parts.uri = build_https_uri(&authority, &path)?;
     parts
         .headers
         .insert(HOST, HeaderValue::from_str(&authority)?);
+    // Record the request as the client sent it, before injected credentials are added.
+    let recording = traffic_observer.map(|observer| {
+        TrafficRecording::start(
+            observer,
+            TrafficRequest {
+                protocol: "https",
+                method: &method,
+                url: &parts.uri.to_string(),
+                headers: &parts.headers,
+                client,
+            },
+        )
+    });
+    let redactor = credentials.apply(&mut parts.headers);
+    let body = match &recording {
+        Some(recording) => recording.record_request_body(body),
+        None => body,
+    };
 
     let inspect = mitm.inspect_enabled();
     let max_body_bytes = mitm.max_body_bytes();
@@ -231,7 +277,23 @@ async fn forward_request(req: Request, request_ctx: &MitmRequestContext) -> Resu
     };
 
     let upstream_req = Request::from_parts(parts, body);
-    let upstream_resp = mitm.upstream.serve(upstream_req).await?;
+    let upstream_resp = match mitm.upstream.serve(upstream_req).await {
+        Ok(resp) => resp,
+        Err(err) => {
+            if let Some(recording) = recording {
+                recording.record_failure("upstream failure");
+            }
+            return Err(err.into());
+        }
+    };
+    let upstream_resp = match redactor {
+        Some(redactor) => redactor.redact_response(upstream_resp),
+        None => upstream_resp,
+    };
+    let upstream_resp = match recording {
+        Some(recording) => recording.record_response(upstream_resp),
+        None => upstream_resp,
+    };
     respond_with_inspection(
         upstream_resp,
         inspect,
@@ -305,6 +367,61 @@ async fn mitm_blocking_response(
         return Ok(Some(blocked_text_response(reason)));
     }
 
+    if let Some(request_rules) = policy.request_rules {
+        let request = NetworkPolicyRequest::new(NetworkPolicyRequestArgs {
+            protocol: NetworkProtocol::HttpsConnect,
+            host: policy.target_host.clone(),
+            port: policy.target_port,
+            client_addr: client.clone(),
+            method: Some(method.clone()),
+            command: None,
+            exec_policy_hint: None,
+        });
+        let decision = match evaluate_request_rules(
+            &policy.app_state,
+            &request,
+            NetworkRequestScheme::Https,
+            req.uri().path(),
+        )
+        .await?
+        {
+            Some(decision) => decision,
+            None if request_rules == RequestRuleEnforcement::RulesOnly => {
+                NetworkDecision::deny_with_source(
+                    REASON_NOT_ALLOWED,
+                    NetworkDecisionSource::BaselinePolicy,
+                )
+            }
+            None => NetworkDecision::Allow,
+        };
+        if let NetworkDecision::Deny {
+            reason,
+            source,
+            decision,
+        } = decision
+        {
+            let _ = policy
+                .app_state
+                .record_blocked(BlockedRequest::new(BlockedRequestArgs {
+                    host: policy.target_host.clone(),
+                    reason: reason.clone(),
+                    client: client.clone(),
+                    method: Some(method.clone()),
+                    mode: Some(policy.mode),
+                    protocol: "https".to_string(),
+                    decision: Some(decision.as_str().to_string()),
+                    source: Some(source.as_str().to_string()),
+                    port: Some(policy.target_port),
+                }))
+                .await;
+            warn!(
+                "MITM blocked by request rules (host={}, method={method}, path={log_path}, reason={reason})",
+                policy.target_host
+            );
+            return Ok(Some(blocked_text_response(&reason)));
+        }
+    }
+
     if !policy.mode.allows_method(&method) {
         let _ = policy
             .app_state
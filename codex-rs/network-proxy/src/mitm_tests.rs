@@ -1,8 +1,12 @@
 use super::*;
 
 use crate::config::NetworkProxySettings;
+use crate::config::NetworkRequestRule;
+use crate::config::NetworkRequestRuleDecision;
 use crate::reasons::REASON_METHOD_NOT_ALLOWED;
+use crate::reasons::REASON_NOT_ALLOWED;
 use crate::reasons::REASON_NOT_ALLOWED_LOCAL;
+use crate::reasons::REASON_REQUEST_DENIED;
 use crate::runtime::network_proxy_state_for_policy;
 use pretty_assertions::assert_eq;
 use rama_http::Body;
@@ -20,6 +24,7 @@ fn policy_ctx(
         target_host: target_host.to_string(),
         target_port,
         mode,
+        request_rules: None,
         app_state,
     }
 }
@@ -126,3 +131,125 @@ async fn mitm_policy_rechecks_local_private_target_after_connect() {
     assert_eq!(blocked[0].host, "10.0.0.1");
     assert_eq!(blocked[0].port, Some(443));
 }
+
+#[tokio::test]
+async fn mitm_policy_enforces_request_rules_on_inner_requests() {
+    let app_state = Arc::new(network_proxy_state_for_policy({
+        let mut network = NetworkProxySettings::default();
+        network.rules = vec![
+            NetworkRequestRule {
+                scheme: None,
+                host: "github.com".to_string(),
+                path: Some("/*/archive/*".to_string()),
+                methods: vec!["GET".to_string()],
+                decision: NetworkRequestRuleDecision::Allow,
+            },
+            NetworkRequestRule {
+                scheme: None,
+                host: "github.com".to_string(),
+                path: Some("/*/archive/*".to_string()),
+                methods: vec!["DELETE".to_string()],
+                decision: NetworkRequestRuleDecision::Deny,
+            },
+        ];
+        network
+    }));
+    let ctx = MitmPolicyContext {
+        request_rules: Some(RequestRuleEnforcement::RulesOnly),
+        ..policy_ctx(
+            app_state.clone(),
+            NetworkMode::Full,
+            "github.com",
+            /*target_port*/ 443,
+        )
+    };
+    let request = |method: Method, uri: &str| {
+        Request::builder()
+            .method(method)
+            .uri(uri)
+            .header(HOST, "github.com")
+            .body(Body::empty())
+            .unwrap()
+    };
+
+    let archive = mitm_blocking_response(
+        &request(Method::GET, "/openai/codex/archive/main.zip"),
+        &ctx,
+    )
+    .await
+    .unwrap();
+    let unmatched = mitm_blocking_response(&request(Method::GET, "/openai/codex"), &ctx)
+        .await
+        .unwrap()
+        .expect("requests outside the allow rules should be blocked");
+    let denied = mitm_blocking_response(
+        &request(Method::DELETE, "/openai/codex/archive/main.zip"),
+        &ctx,
+    )
+    .await
+    .unwrap()
+    .expect("deny rules should block matching requests");
+
+    assert!(archive.is_none());
+    assert_eq!(unmatched.status(), StatusCode::FORBIDDEN);
+    assert_eq!(denied.status(), StatusCode::FORBIDDEN);
+    assert_eq!(
+        denied.headers().get("x-proxy-error").unwrap(),
+        "blocked-by-request-rule"
+    );
+
+    let blocked = app_state.drain_blocked().await.unwrap();
+    assert_eq!(
+        blocked
+            .iter()
+            .map(|entry| entry.reason.as_str())
+            .collect::<Vec<_>>(),
+        vec![REASON_NOT_ALLOWED, REASON_REQUEST_DENIED]
+    );
+    assert_eq!(blocked[1].source.as_deref(), Some("baseline_policy"));
+}
+
+#[tokio::test]
+async fn mitm_policy_block_is_recorded_as_traffic_when_enabled() {
+    let app_state = Arc::new(network_proxy_state_for_policy({
+        let mut network = NetworkProxySettings::default();
+        network.set_allowed_domains(vec!["example.com".to_string()]);
+        network.record_traffic = true;
+        network
+    }));
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    app_state
+        .set_traffic_observer(Some(Arc::new(
+            move |entry: crate::traffic::NetworkTrafficEntry| {
+                let tx = tx.clone();
+                async move {
+                    let _ = tx.send(entry);
+                }
+            },
+        )))
+        .await;
+    let ctx = policy_ctx(
+        app_state,
+        NetworkMode::Limited,
+        "example.com",
+        /*target_port*/ 443,
+    );
+    let req = Request::builder()
+        .method(Method::POST)
+        .uri("/v1/responses")
+        .header(HOST, "example.com")
+        .body(Body::empty())
+        .unwrap();
+
+    mitm_blocking_response(&req, &ctx)
+        .await
+        .unwrap()
+        .expect("POST should be blocked in limited mode");
+
+    let entry = rx.try_recv().expect("blocked request should be recorded");
+    assert_eq!(entry.url, "https://example.com:443");
+    assert_eq!(entry.method.as_deref(), Some("POST"));
+    assert_eq!(entry.decision, "deny");
+    assert_eq!(entry.reason.as_deref(), Some(REASON_METHOD_NOT_ALLOWED));
+    assert_eq!(entry.status, None);
+}
@@ -1,4 +1,8 @@
+use crate::config::NetworkRequestRuleDecision;
+use crate::config::NetworkRequestScheme;
+use crate::reasons::REASON_MITM_REQUIRED;
 use crate::reasons::REASON_POLICY_DENIED;
+use crate::reasons::REASON_REQUEST_DENIED;
 use crate::runtime::HostBlockDecision;
 use crate::runtime::HostBlockReason;
 use crate::state::NetworkProxyState;
@@ -13,6 +17,7 @@ const AUDIT_TARGET: &str = "codex_otel.network_proxy";
 const POLICY_DECISION_EVENT_NAME: &str = "codex.network_proxy.policy_decision";
 const POLICY_SCOPE_DOMAIN: &str = "domain";
 const POLICY_SCOPE_NON_DOMAIN: &str = "non_domain";
+const POLICY_SCOPE_REQUEST: &str = "request";
 const POLICY_DECISION_ALLOW: &str = "allow";
 const POLICY_DECISION_DENY: &str = "deny";
 const POLICY_REASON_ALLOW: &str = "allow";
@@ -207,6 +212,7 @@ fn emit_non_domain_policy_decision_audit_event(
             server_port: args.server_port,
             method: args.method,
             client_addr: args.client_addr,
+            path: None,
             policy_override: false,
         },
     );
@@ -222,6 +228,7 @@ struct PolicyAuditEventArgs<'a> {
     server_port: u16,
     method: Option<&'a str>,
     client_addr: Option<&'a str>,
+    path: Option<&'a str>,
     policy_override: bool,
 }
 
@@ -250,6 +257,7 @@ fn emit_policy_audit_event(state: &NetworkProxyState, args: PolicyAuditEventArgs
         server.port = args.server_port,
         http.request.method = args.method.unwrap_or(DEFAULT_METHOD),
         client.address = args.client_addr.unwrap_or(DEFAULT_CLIENT_ADDRESS),
+        url.path = args.path,
         network.policy.override = args.policy_override,
     );
 }
@@ -292,6 +300,15 @@ pub(crate) async fn evaluate_host_policy(
     request: &NetworkPolicyRequest,
 ) -> Result<NetworkDecision> {
     let host_decision = state.host_blocked(&request.host, request.port).await?;
+    Ok(decide_host_policy(state, decider, request, host_decision).await)
+}
+
+async fn decide_host_policy(
+    state: &NetworkProxyState,
+    decider: Option<&Arc<dyn NetworkPolicyDecider>>,
+    request: &NetworkPolicyRequest,
+    host_decision: HostBlockDecision,
+) -> NetworkDecision {
     let (decision, policy_override) = match host_decision {
         HostBlockDecision::Allowed => (NetworkDecision::Allow, false),
         HostBlockDecision::Blocked(HostBlockReason::NotAllowed) => {
@@ -351,11 +368,152 @@ pub(crate) async fn evaluate_host_policy(
             server_port: request.port,
             method: request.method.as_deref(),
             client_addr: request.client_addr.as_deref(),
+            path: None,
             policy_override,
         },
     );
 
-    Ok(decision)
+    decision
+}
+
+/// How a MITM tunnel applies request rules to the requests it terminates.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum RequestRuleEnforcement {
+    /// The CONNECT passed host-level policy; requests that match no rule are allowed.
+    HostAllowed,
+    /// Only allow rules made the host reachable; requests that match no rule are denied.
+    RulesOnly,
+}
+
+pub(crate) struct ConnectPolicyDecision {
+    pub(crate) decision: NetworkDecision,
+    /// Set when the tunnel must be intercepted so request rules apply to its inner requests.
+    pub(crate) request_rules: Option<RequestRuleEnforcement>,
+}
+
+/// Decide whether a CONNECT should be allowed, taking request rules for the host into account.
+///
+/// Request rules can only be enforced once MITM terminates TLS. Without MITM, a host with deny
+/// rules is blocked outright rather than tunneled unchecked, and allow rules grant nothing.
+pub(crate) async fn evaluate_connect_policy(
+    state: &NetworkProxyState,
+    decider: Option<&Arc<dyn NetworkPolicyDecider>>,
+    request: &NetworkPolicyRequest,
+    mitm_enabled: bool,
+) -> Result<ConnectPolicyDecision> {
+    let scope = state
+        .request_rule_scope(NetworkRequestScheme::Https, &request.host)
+        .await?;
+    if scope.is_empty() || (!mitm_enabled && !scope.has_deny) {
+        return Ok(ConnectPolicyDecision {
+            decision: evaluate_host_policy(state, decider, request).await?,
+            request_rules: None,
+        });
+    }
+    if !mitm_enabled {
+        let decision = NetworkDecision::deny_with_source(
+            REASON_MITM_REQUIRED,
+            NetworkDecisionSource::BaselinePolicy,
+        );
+        emit_request_rule_audit_event(state, request, /*path*/ None, &decision);
+        return Ok(ConnectPolicyDecision {
+            decision,
+            request_rules: None,
+        });
+    }
+
+    let host_decision = state.host_blocked(&request.host, request.port).await?;
+    if scope.has_allow && host_decision == HostBlockDecision::Blocked(HostBlockReason::NotAllowed) {
+        let decision = NetworkDecision::Allow;
+        emit_request_rule_audit_event(state, request, /*path*/ None, &decision);
+        return Ok(ConnectPolicyDecision {
+            decision,
+            request_rules: Some(RequestRuleEnforcement::RulesOnly),
+        });
+    }
+
+    let decision = decide_host_policy(state, decider, request, host_decision).await;
+    let request_rules =
+        matches!(decision, NetworkDecision::Allow).then_some(RequestRuleEnforcement::HostAllowed);
+    Ok(ConnectPolicyDecision {
+        decision,
+        request_rules,
+    })
+}
+
+/// Evaluate request rules for a request whose method and path are visible to the proxy.
+///
+/// Returns `None` when no rule matches, leaving the decision to host-level policy.
+pub(crate) async fn evaluate_request_rules(
+    state: &NetworkProxyState,
+    request: &NetworkPolicyRequest,
+    scheme: NetworkRequestScheme,
+    path: &str,
+) -> Result<Option<NetworkDecision>> {
+    let method = request.method.as_deref().unwrap_or_default();
+    let Some(rule_decision) = state
+        .request_rule_decision(scheme, &request.host, method, path)
+        .await?
+    else {
+        return Ok(None);
+    };
+
+    let decision = match rule_decision {
+        NetworkRequestRuleDecision::Deny => NetworkDecision::deny_with_source(
+            REASON_REQUEST_DENIED,
+            NetworkDecisionSource::BaselinePolicy,
+        ),
+        // Domain denies and the local/private network guard still win over allow rules.
+        NetworkRequestRuleDecision::Allow => {
+            match state.host_blocked(&request.host, request.port).await? {
+                HostBlockDecision::Allowed
+                | HostBlockDecision::Blocked(HostBlockReason::NotAllowed) => NetworkDecision::Allow,
+                HostBlockDecision::Blocked(reason) => NetworkDecision::deny_with_source(
+                    reason.as_str(),
+                    NetworkDecisionSource::BaselinePolicy,
+                ),
+            }
+        }
+    };
+    emit_request_rule_audit_event(state, request, Some(path), &decision);
+    Ok(Some(decision))
+}
+
+fn emit_request_rule_audit_event(
+    state: &NetworkProxyState,
+    request: &NetworkPolicyRequest,
+    path: Option<&str>,
+    decision: &NetworkDecision,
+) {
+    let (policy_decision, source, reason) = match decision {
+        NetworkDecision::Allow => (
+            POLICY_DECISION_ALLOW,
+            NetworkDecisionSource::BaselinePolicy,
+            POLICY_REASON_ALLOW,
+        ),
+        NetworkDecision::Deny {
+            reason,
+            source,
+            decision,
+        } => (decision.as_str(), *source, reason.as_str()),
+    };
+
+    emit_policy_audit_event(
+        state,
+        PolicyAuditEventArgs {
+            scope: POLICY_SCOPE_REQUEST,
+            decision: policy_decision,
+            source: source.as_str(),
+            reason,
+            protocol: request.protocol,
+            server_address: request.host.as_str(),
+            server_port: request.port,
+            method: request.method.as_deref(),
+            client_addr: request.client_addr.as_deref(),
+            path,
+            policy_override: false,
+        },
+    );
 }
 
 fn map_decider_decision(decision: NetworkDecision) -> NetworkDecision {
@@ -536,6 +694,7 @@ mod tests {
     use crate::config::NetworkMode;
     use crate::config::NetworkProxyConfig;
     use crate::config::NetworkProxySettings;
+    use crate::config::NetworkRequestRule;
     use crate::reasons::REASON_DENIED;
     use crate::reasons::REASON_METHOD_NOT_ALLOWED;
     use crate::reasons::REASON_NOT_ALLOWED;
@@ -884,6 +1043,235 @@ mod tests {
         );
     }
 
+    fn settings_with_request_rules(
+        allowed_domains: &[&str],
+        rules: Vec<NetworkRequestRule>,
+    ) -> NetworkProxySettings {
+        let mut network = NetworkProxySettings::default();
+        if !allowed_domains.is_empty() {
+            network.set_allowed_domains(
+                allowed_domains
+                    .iter()
+                    .map(|domain| (*domain).to_string())
+                    .collect(),
+            );
+        }
+        network.rules = rules;
+        network
+    }
+
+    fn request_rule(
+        host: &str,
+        path: &str,
+        method: &str,
+        decision: NetworkRequestRuleDecision,
+    ) -> NetworkRequestRule {
+        NetworkRequestRule {
+            scheme: None,
+            host: host.to_string(),
+            path: Some(path.to_string()),
+            methods: vec![method.to_string()],
+            decision,
+        }
+    }
+
+    fn policy_request(protocol: NetworkProtocol, host: &str, method: &str) -> NetworkPolicyRequest {
+        NetworkPolicyRequest::new(NetworkPolicyRequestArgs {
+            protocol,
+            host: host.to_string(),
+            port: 443,
+            client_addr: None,
+            method: Some(method.to_string()),
+            command: None,
+            exec_policy_hint: None,
+        })
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn evaluate_request_rules_denies_matching_request_on_allowlisted_host() {
+        let state = network_proxy_state_for_policy(settings_with_request_rules(
+            &["api.github.com"],
+            vec![request_rule(
+                "api.github.com",
+                "/*",
+                "POST",
+                NetworkRequestRuleDecision::Deny,
+            )],
+        ));
+        let request = policy_request(NetworkProtocol::HttpsConnect, "api.github.com", "POST");
+
+        let (decision, events) = capture_events(|| async {
+            evaluate_request_rules(
+                &state,
+                &request,
+                NetworkRequestScheme::Https,
+                "/repos/openai/codex/issues",
+            )
+            .await
+            .unwrap()
+        })
+        .await;
+        assert_eq!(
+            decision,
+            Some(NetworkDecision::Deny {
+                reason: REASON_REQUEST_DENIED.to_string(),
+                source: NetworkDecisionSource::BaselinePolicy,
+                decision: NetworkPolicyDecision::Deny,
+            })
+        );
+
+        let event = find_event_by_name(&events, POLICY_DECISION_EVENT_NAME)
+            .expect("expected policy decision audit event");
+        assert_eq!(
+            event.field("network.policy.scope"),
+            Some(POLICY_SCOPE_REQUEST)
+        );
+        assert_eq!(event.field("network.policy.decision"), Some("deny"));
+        assert_eq!(
+            event.field("network.policy.source"),
+            Some("baseline_policy")
+        );
+        assert_eq!(event.field("http.request.method"), Some("POST"));
+        assert_eq!(event.field("url.path"), Some("/repos/openai/codex/issues"));
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn evaluate_request_rules_allows_matching_request_without_domain_allowlist() {
+        let state = network_proxy_state_for_policy(settings_with_request_rules(
+            &[],
+            vec![request_rule(
+                "github.com",
+                "/*/archive/*",
+                "GET",
+                NetworkRequestRuleDecision::Allow,
+            )],
+        ));
+
+        let archive = evaluate_request_rules(
+            &state,
+            &policy_request(NetworkProtocol::Http, "github.com", "GET"),
+            NetworkRequestScheme::Http,
+            "/openai/codex/archive/main.zip",
+        )
+        .await
+        .unwrap();
+        let upload = evaluate_request_rules(
+            &state,
+            &policy_request(NetworkProtocol::Http, "github.com", "POST"),
+            NetworkRequestScheme::Http,
+            "/openai/codex/archive/main.zip",
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(archive, Some(NetworkDecision::Allow));
+        assert_eq!(upload, None);
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn evaluate_request_rules_allow_does_not_override_domain_deny() {
+        let state = network_proxy_state_for_policy({
+            let mut network = settings_with_request_rules(
+                &[],
+                vec![request_rule(
+                    "github.com",
+                    "/*",
+                    "GET",
+                    NetworkRequestRuleDecision::Allow,
+                )],
+            );
+            network.set_denied_domains(vec!["github.com".to_string()]);
+            network
+        });
+
+        let decision = evaluate_request_rules(
+            &state,
+            &policy_request(NetworkProtocol::Http, "github.com", "GET"),
+            NetworkRequestScheme::Http,
+            "/openai/codex",
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            decision,
+            Some(NetworkDecision::Deny {
+                reason: REASON_DENIED.to_string(),
+                source: NetworkDecisionSource::BaselinePolicy,
+                decision: NetworkPolicyDecision::Deny,
+            })
+        );
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn evaluate_connect_policy_requires_mitm_for_deny_rules() {
+        let state = network_proxy_state_for_policy(settings_with_request_rules(
+            &["api.github.com"],
+            vec![request_rule(
+                "api.github.com",
+                "/*",
+                "POST",
+                NetworkRequestRuleDecision::Deny,
+            )],
+        ));
+        let request = policy_request(NetworkProtocol::HttpsConnect, "api.github.com", "CONNECT");
+
+        let connect = evaluate_connect_policy(
+            &state, /*decider*/ None, &request, /*mitm_enabled*/ false,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            connect.decision,
+            NetworkDecision::Deny {
+                reason: REASON_MITM_REQUIRED.to_string(),
+                source: NetworkDecisionSource::BaselinePolicy,
+                decision: NetworkPolicyDecision::Deny,
+            }
+        );
+        assert_eq!(connect.request_rules, None);
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn evaluate_connect_policy_defers_unlisted_host_to_allow_rules_with_mitm() {
+        let state = network_proxy_state_for_policy(settings_with_request_rules(
+            &[],
+            vec![request_rule(
+                "github.com",
+                "/*/archive/*",
+                "GET",
+                NetworkRequestRuleDecision::Allow,
+            )],
+        ));
+        let request = policy_request(NetworkProtocol::HttpsConnect, "github.com", "CONNECT");
+
+        let with_mitm = evaluate_connect_policy(
+            &state, /*decider*/ None, &request, /*mitm_enabled*/ true,
+        )
+        .await
+        .unwrap();
+        let without_mitm = evaluate_connect_policy(
+            &state, /*decider*/ None, &request, /*mitm_enabled*/ false,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(with_mitm.decision, NetworkDecision::Allow);
+        assert_eq!(
+            with_mitm.request_rules,
+            Some(RequestRuleEnforcement::RulesOnly)
+        );
+        assert_eq!(
+            without_mitm.decision,
+            NetworkDecision::Deny {
+                reason: REASON_NOT_ALLOWED.to_string(),
+                source: NetworkDecisionSource::BaselinePolicy,
+                decision: NetworkPolicyDecision::Deny,
+            }
+        );
+    }
+
     #[test]
     fn ask_uses_decider_source_and_ask_decision() {
         assert_eq!(
@@ -1,10 +1,14 @@
 #[cfg(test)]
 use crate::config::NetworkMode;
+use crate::config::NetworkRequestRule;
+use crate::config::NetworkRequestRuleDecision;
+use crate::config::NetworkRequestScheme;
 use anyhow::Context;
 use anyhow::Result;
 use anyhow::bail;
 use anyhow::ensure;
 use globset::GlobBuilder;
+use globset::GlobMatcher;
 use globset::GlobSet;
 use globset::GlobSetBuilder;
 use std::collections::HashSet;
@@ -198,6 +202,192 @@ fn compile_globset_with_policy(
     Ok(builder.build()?)
 }
 
+/// Request rules compiled for matching against a concrete request.
+#[derive(Clone, Debug, Default)]
+pub struct CompiledRequestRules {
+    rules: Vec<CompiledRequestRule>,
+}
+
+#[derive(Clone, Debug)]
+struct CompiledRequestRule {
+    scheme: Option<NetworkRequestScheme>,
+    hosts: GlobSet,
+    path: Option<GlobMatcher>,
+    methods: Vec<String>,
+    decision: NetworkRequestRuleDecision,
+}
+
+/// Which kinds of request rules could apply to a host, before the request itself is known.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct RequestRuleScope {
+    pub(crate) has_allow: bool,
+    pub(crate) has_deny: bool,
+}
+
+impl RequestRuleScope {
+    pub(crate) fn is_empty(self) -> bool {
+        !self.has_allow && !self.has_deny
+    }
+}
+
+impl CompiledRequestRule {
+    fn matches_host(&self, scheme: NetworkRequestScheme, host: &str) -> bool {
+        self.scheme.is_none_or(|rule_scheme| rule_scheme == scheme) && self.hosts.is_match(host)
+    }
+}
+
+impl CompiledRequestRules {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Returns the decision of the rules matching the request, with deny winning over allow, or
+    /// `None` when no rule matches. The path is canonicalized first so that equivalent spellings
+    /// (`/a//b`, `/a/./b`, `/x/../a/b`, `/%61/b`) all match the same rules.
+    pub(crate) fn decision_for(
+        &self,
+        scheme: NetworkRequestScheme,
+        host: &str,
+        method: &str,
+        path: &str,
+    ) -> Option<NetworkRequestRuleDecision> {
+        let path = canonical_request_path(path);
+        let path = path.as_str();
+        self.rules
+            .iter()
+            .filter(|rule| {
+                rule.matches_host(scheme, host)
+                    && (rule.methods.is_empty()
+                        || rule
+                            .methods
+                            .iter()
+                            .any(|candidate| candidate.eq_ignore_ascii_case(method)))
+                    && rule.path.as_ref().is_none_or(|glob| glob.is_match(path))
+            })
+            .map(|rule| rule.decision)
+            .max()
+    }
+
+    pub(crate) fn scope_for_host(
+        &self,
+        scheme: NetworkRequestScheme,
+        host: &str,
+    ) -> RequestRuleScope {
+        let mut scope = RequestRuleScope::default();
+        for rule in self
+            .rules
+            .iter()
+            .filter(|rule| rule.matches_host(scheme, host))
+        {
+            match rule.decision {
+                NetworkRequestRuleDecision::Allow => scope.has_allow = true,
+                NetworkRequestRuleDecision::Deny => scope.has_deny = true,
+            }
+        }
+        scope
+    }
+}
+
+/// Canonicalizes a request path for rule matching: drops the query and fragment, decodes
+/// percent-escaped unreserved characters, collapses empty segments and resolves `.`/`..`.
+fn canonical_request_path(path: &str) -> String {
+    let path = path.split(['?', '#']).next().unwrap_or(path);
+    let decoded = decode_unreserved_escapes(path);
+    let mut segments = Vec::new();
+    for segment in decoded.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                segments.pop();
+            }
+            segment => segments.push(segment),
+        }
+    }
+    let mut canonical = format!("/{}", segments.join("/"));
+    if !segments.is_empty()
+        && (decoded.ends_with('/') || decoded.ends_with("/.") || decoded.ends_with("/.."))
+    {
+        canonical.push('/');
+    }
+    canonical
+}
+
+/// Decodes `%XX` escapes of RFC 3986 unreserved characters; every other escape is kept as is
+/// since decoding it (e.g. `%2F`) would change the path's meaning.
+fn decode_unreserved_escapes(path: &str) -> String {
+    let mut decoded = String::with_capacity(path.len());
+    let mut rest = path;
+    while let Some(index) = rest.find('%') {
+        decoded.push_str(&rest[..index]);
+        let escape = &rest[index..];
+        let unreserved = escape
+            .get(1..3)
+            .filter(|hex| hex.bytes().all(|byte| byte.is_ascii_hexdigit()))
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            .filter(|byte| {
+                byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~')
+            });
+        match unreserved {
+            Some(byte) => {
+                decoded.push(char::from(byte));
+                rest = &escape[3..];
+            }
+            None => {
+                decoded.push('%');
+                rest = &escape[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+pub(crate) fn compile_request_rules(rules: &[NetworkRequestRule]) -> Result<CompiledRequestRules> {
+    let mut compiled = Vec::with_capacity(rules.len());
+    for (index, rule) in rules.iter().enumerate() {
+        compiled.push(
+            compile_request_rule(rule)
+                .with_context(|| format!("invalid network.rules[{index}]"))?,
+        );
+    }
+    Ok(CompiledRequestRules { rules: compiled })
+}
+
+fn compile_request_rule(rule: &NetworkRequestRule) -> Result<CompiledRequestRule> {
+    ensure!(!rule.host.trim().is_empty(), "host cannot be empty");
+    let hosts = compile_allowlist_globset(std::slice::from_ref(&rule.host))?;
+    let path = match rule.path.as_deref() {
+        Some(path) => {
+            ensure!(
+                path.starts_with('/'),
+                "path must start with `/` (got {path:?})"
+            );
+            let glob = GlobBuilder::new(path)
+                .literal_separator(false)
+                .build()
+                .with_context(|| format!("invalid path glob: {path}"))?;
+            Some(glob.compile_matcher())
+        }
+        None => None,
+    };
+    let mut methods = Vec::with_capacity(rule.methods.len());
+    for method in &rule.methods {
+        let method = method.trim();
+        ensure!(
+            !method.is_empty() && method.bytes().all(|b| b.is_ascii_alphabetic()),
+            "invalid HTTP method {method:?}"
+        );
+        methods.push(method.to_ascii_uppercase());
+    }
+    Ok(CompiledRequestRule {
+        scheme: rule.scheme,
+        hosts,
+        path,
+        methods,
+        decision: rule.decision,
+    })
+}
+
 #[derive(Debug, Clone)]
 pub(crate) enum DomainPattern {
     ApexAndSubdomains(String),
@@ -392,6 +582,147 @@ mod tests {
         assert_eq!(true, set.is_match("::1"));
     }
 
+    fn request_rule(
+        host: &str,
+        path: Option<&str>,
+        methods: &[&str],
+        decision: NetworkRequestRuleDecision,
+    ) -> NetworkRequestRule {
+        NetworkRequestRule {
+            scheme: None,
+            host: host.to_string(),
+            path: path.map(str::to_string),
+            methods: methods.iter().map(|method| (*method).to_string()).collect(),
+            decision,
+        }
+    }
+
+    #[test]
+    fn request_rules_match_path_glob_and_method() {
+        let rules = compile_request_rules(&[request_rule(
+            "github.com",
+            Some("/*/archive/*"),
+            &["get"],
+            NetworkRequestRuleDecision::Allow,
+        )])
+        .unwrap();
+
+        assert_eq!(
+            rules.decision_for(
+                NetworkRequestScheme::Https,
+                "github.com",
+                "GET",
+                "/openai/codex/archive/refs/heads/main.zip?raw=1",
+            ),
+            Some(NetworkRequestRuleDecision::Allow)
+        );
+        assert_eq!(
+            rules.decision_for(
+                NetworkRequestScheme::Https,
+                "github.com",
+                "POST",
+                "/openai/codex/archive/main.zip",
+            ),
+            None
+        );
+        assert_eq!(
+            rules.decision_for(
+                NetworkRequestScheme::Https,
+                "github.com",
+                "GET",
+                "/openai/codex/releases",
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn request_rules_match_canonical_paths() {
+        let rules = compile_request_rules(&[request_rule(
+            "api.github.com",
+            Some("/repos/*/releases"),
+            &[],
+            NetworkRequestRuleDecision::Deny,
+        )])
+        .unwrap();
+
+        for path in [
+            "/repos/openai/releases",
+            "//repos/openai//releases",
+            "/repos/./openai/releases",
+            "/repos/openai/../openai/releases",
+            "/%72epos/openai/%72eleases?page=2",
+            "/repos/openai/%2e%2e/openai/releases",
+        ] {
+            assert_eq!(
+                rules.decision_for(NetworkRequestScheme::Https, "api.github.com", "GET", path),
+                Some(NetworkRequestRuleDecision::Deny),
+                "{path}"
+            );
+        }
+        assert_eq!(
+            canonical_request_path("/a/%2Fb/%7euser/../c/"),
+            "/a/%2Fb/c/".to_string()
+        );
+        assert_eq!(canonical_request_path("/../.."), "/".to_string());
+    }
+
+    #[test]
+    fn request_rules_prefer_deny_and_respect_scheme() {
+        let rules = compile_request_rules(&[
+            request_rule(
+                "**.github.com",
+                /*path*/ None,
+                &[],
+                NetworkRequestRuleDecision::Allow,
+            ),
+            NetworkRequestRule {
+                scheme: Some(NetworkRequestScheme::Https),
+                ..request_rule(
+                    "api.github.com",
+                    Some("/*"),
+                    &["POST"],
+                    NetworkRequestRuleDecision::Deny,
+                )
+            },
+        ])
+        .unwrap();
+
+        assert_eq!(
+            rules.decision_for(NetworkRequestScheme::Https, "api.github.com", "POST", "/x"),
+            Some(NetworkRequestRuleDecision::Deny)
+        );
+        assert_eq!(
+            rules.decision_for(NetworkRequestScheme::Http, "api.github.com", "POST", "/x"),
+            Some(NetworkRequestRuleDecision::Allow)
+        );
+        assert_eq!(
+            rules.scope_for_host(NetworkRequestScheme::Https, "api.github.com"),
+            RequestRuleScope {
+                has_allow: true,
+                has_deny: true,
+            }
+        );
+        assert!(
+            rules
+                .scope_for_host(NetworkRequestScheme::Https, "example.com")
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn compile_request_rules_rejects_relative_paths() {
+        let err = compile_request_rules(&[request_rule(
+            "github.com",
+            Some("archive/*"),
+            &[],
+            NetworkRequestRuleDecision::Allow,
+        )])
+        .unwrap_err();
+
+        assert_eq!(err.to_string(), "invalid network.rules[0]");
+    }
+
     #[test]
     fn is_loopback_host_handles_localhost_variants() {
         assert!(is_loopback_host(&Host::parse("localhost").unwrap()));
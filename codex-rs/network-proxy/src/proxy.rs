@@ -1,4 +1,5 @@
 use crate::config;
+use crate::credentials::NetworkCredentialSource;
 use crate::http_proxy;
 use crate::network_policy::NetworkPolicyDecider;
 use crate::runtime::BlockedRequestObserver;
@@ -6,6 +7,7 @@ use crate::runtime::ConfigState;
 use crate::runtime::unix_socket_permissions_supported;
 use crate::socks5;
 use crate::state::NetworkProxyState;
+use crate::traffic::NetworkTrafficObserver;
 use anyhow::Context;
 use anyhow::Result;
 use clap::Parser;
@@ -98,6 +100,8 @@ pub struct NetworkProxyBuilder {
     managed_by_codex: bool,
     policy_decider: Option<Arc<dyn NetworkPolicyDecider>>,
     blocked_request_observer: Option<Arc<dyn BlockedRequestObserver>>,
+    credential_source: Option<Arc<dyn NetworkCredentialSource>>,
+    traffic_observer: Option<Arc<dyn NetworkTrafficObserver>>,
 }
 
 impl Default for NetworkProxyBuilder {
@@ -109,6 +113,8 @@ impl Default for NetworkProxyBuilder {
             managed_by_codex: true,
             policy_decider: None,
             blocked_request_observer: None,
+            credential_source: None,
+            traffic_observer: None,
         }
     }
 }
@@ -163,6 +169,32 @@ impl NetworkProxyBuilder {
         self
     }
 
+    pub fn credential_source<S>(mut self, source: S) -> Self
+    where
+        S: NetworkCredentialSource,
+    {
+        self.credential_source = Some(Arc::new(source));
+        self
+    }
+
+    pub fn credential_source_arc(mut self, source: Arc<dyn NetworkCredentialSource>) -> Self {
+        self.credential_source = Some(source);
+        self
+    }
+
+    pub fn traffic_observer<O>(mut self, observer: O) -> Self
+    where
+        O: NetworkTrafficObserver,
+    {
+        self.traffic_observer = Some(Arc::new(observer));
+        self
+    }
+
+    pub fn traffic_observer_arc(mut self, observer: Arc<dyn NetworkTrafficObserver>) -> Self {
+        self.traffic_observer = Some(observer);
+        self
+    }
+
     pub async fn build(self) -> Result<NetworkProxy> {
         let state = self.state.ok_or_else(|| {
             anyhow::anyhow!(
@@ -172,6 +204,12 @@ impl NetworkProxyBuilder {
         state
             .set_blocked_request_observer(self.blocked_request_observer.clone())
             .await;
+        state
+            .set_credential_source(self.credential_source.clone())
+            .await;
+        state
+            .set_traffic_observer(self.traffic_observer.clone())
+            .await;
         let current_cfg = state.current_cfg().await?;
         let (requested_http_addr, requested_socks_addr, reserved_listeners) = if self
             .managed_by_codex
@@ -605,6 +643,11 @@ impl NetworkProxy {
         );
     }
 
+    /// Replaces the observer that receives recorded traffic on a running proxy.
+    pub async fn set_traffic_observer(&self, observer: Option<Arc<dyn NetworkTrafficObserver>>) {
+        self.state.set_traffic_observer(observer).await;
+    }
+
     pub async fn replace_config_state(&self, new_state: ConfigState) -> Result<()> {
         let current_cfg = self.state.current_cfg().await?;
         anyhow::ensure!(
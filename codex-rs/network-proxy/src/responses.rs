@@ -7,6 +7,7 @@ use crate::reasons::REASON_MITM_REQUIRED;
 use crate::reasons::REASON_NOT_ALLOWED;
 use crate::reasons::REASON_NOT_ALLOWED_LOCAL;
 use crate::reasons::REASON_PROXY_DISABLED;
+use crate::reasons::REASON_REQUEST_DENIED;
 use rama_http::Body;
 use rama_http::Response;
 use rama_http::StatusCode;
@@ -54,6 +55,7 @@ pub fn blocked_header_value(reason: &str) -> &'static str {
         REASON_DENIED => "blocked-by-denylist",
         REASON_METHOD_NOT_ALLOWED => "blocked-by-method-policy",
         REASON_MITM_REQUIRED => "blocked-by-mitm-required",
+        REASON_REQUEST_DENIED => "blocked-by-request-rule",
         _ => "blocked-by-policy",
     }
 }
@@ -66,6 +68,7 @@ pub fn blocked_message(reason: &str) -> &'static str {
         REASON_METHOD_NOT_ALLOWED => "Method not allowed in limited mode.",
         REASON_MITM_REQUIRED => "MITM required for limited HTTPS.",
         REASON_PROXY_DISABLED => "network proxy is disabled",
+        REASON_REQUEST_DENIED => "Request denied by a network request rule.",
         _ => "Request blocked by network policy.",
     }
 }
@@ -1,9 +1,16 @@
 use crate::config::NetworkDomainPermission;
 use crate::config::NetworkMode;
 use crate::config::NetworkProxyConfig;
+use crate::config::NetworkRequestRuleDecision;
+use crate::config::NetworkRequestScheme;
 use crate::config::ValidatedUnixSocketPath;
+use crate::credentials::CompiledCredentialInjections;
+use crate::credentials::NetworkCredentialSource;
+use crate::credentials::ResolvedCredentials;
 use crate::mitm::MitmState;
+use crate::policy::CompiledRequestRules;
 use crate::policy::Host;
+use crate::policy::RequestRuleScope;
 use crate::policy::is_loopback_host;
 use crate::policy::is_non_public_ip;
 use crate::policy::normalize_host;
@@ -14,6 +21,8 @@ use crate::state::NetworkProxyConstraintError;
 use crate::state::NetworkProxyConstraints;
 use crate::state::build_config_state;
 use crate::state::validate_policy_against_constraints;
+use crate::traffic::NetworkTrafficEntry;
+use crate::traffic::NetworkTrafficObserver;
 use anyhow::Context;
 use anyhow::Result;
 use async_trait::async_trait;
@@ -156,6 +165,8 @@ pub struct ConfigState {
     pub config: NetworkProxyConfig,
     pub allow_set: GlobSet,
     pub deny_set: GlobSet,
+    pub request_rules: CompiledRequestRules,
+    pub credentials: CompiledCredentialInjections,
     pub mitm: Option<Arc<MitmState>>,
     pub constraints: NetworkProxyConstraints,
     pub blocked: VecDeque<BlockedRequest>,
@@ -201,6 +212,8 @@ pub struct NetworkProxyState {
     state: Arc<RwLock<ConfigState>>,
     reloader: Arc<dyn ConfigReloader>,
     blocked_request_observer: Arc<RwLock<Option<Arc<dyn BlockedRequestObserver>>>>,
+    credential_source: Arc<RwLock<Option<Arc<dyn NetworkCredentialSource>>>>,
+    traffic_observer: Arc<RwLock<Option<Arc<dyn NetworkTrafficObserver>>>>,
     audit_metadata: NetworkProxyAuditMetadata,
 }
 
@@ -218,6 +231,8 @@ impl Clone for NetworkProxyState {
             state: self.state.clone(),
             reloader: self.reloader.clone(),
             blocked_request_observer: self.blocked_request_observer.clone(),
+            credential_source: self.credential_source.clone(),
+            traffic_observer: self.traffic_observer.clone(),
             audit_metadata: self.audit_metadata.clone(),
         }
     }
@@ -268,6 +283,8 @@ impl NetworkProxyState {
             state: Arc::new(RwLock::new(state)),
             reloader,
             blocked_request_observer: Arc::new(RwLock::new(blocked_request_observer)),
+            credential_source: Arc::new(RwLock::new(None)),
+            traffic_observer: Arc::new(RwLock::new(None)),
             audit_metadata,
         }
     }
@@ -280,6 +297,35 @@ impl NetworkProxyState {
         *observer = blocked_request_observer;
     }
 
+    pub async fn set_credential_source(
+        &self,
+        credential_source: Option<Arc<dyn NetworkCredentialSource>>,
+    ) {
+        let mut source = self.credential_source.write().await;
+        *source = credential_source;
+    }
+
+    pub async fn set_traffic_observer(
+        &self,
+        traffic_observer: Option<Arc<dyn NetworkTrafficObserver>>,
+    ) {
+        let mut observer = self.traffic_observer.write().await;
+        *observer = traffic_observer;
+    }
+
+    /// Returns the traffic observer when `network.record_traffic` is enabled.
+    pub(crate) async fn traffic_observer(&self) -> Result<Option<Arc<dyn NetworkTrafficObserver>>> {
+        self.reload_if_needed().await?;
+        let record_traffic = {
+            let guard = self.state.read().await;
+            guard.config.network.record_traffic
+        };
+        if !record_traffic {
+            return Ok(None);
+        }
+        Ok(self.traffic_observer.read().await.clone())
+    }
+
     pub fn audit_metadata(&self) -> &NetworkProxyAuditMetadata {
         &self.audit_metadata
     }
@@ -419,6 +465,7 @@ impl NetworkProxyState {
         self.reload_if_needed().await?;
         let blocked_for_observer = entry.clone();
         let blocked_request_observer = self.blocked_request_observer.read().await.clone();
+        let traffic_entry = NetworkTrafficEntry::blocked(&entry);
         let violation_line = blocked_request_violation_log_line(&entry);
         let host = entry.host.clone();
         let reason = entry.reason.clone();
@@ -426,7 +473,7 @@ impl NetworkProxyState {
         let source = entry.source.clone();
         let protocol = entry.protocol.clone();
         let port = entry.port;
-        let (total, buffered) = {
+        let (total, buffered, record_traffic) = {
             let mut guard = self.state.write().await;
             guard.blocked.push_back(entry);
             guard.blocked_total = guard.blocked_total.saturating_add(1);
@@ -434,7 +481,11 @@ impl NetworkProxyState {
             while guard.blocked.len() > MAX_BLOCKED_EVENTS {
                 guard.blocked.pop_front();
             }
-            (total, guard.blocked.len())
+            (
+                total,
+                guard.blocked.len(),
+                guard.config.network.record_traffic,
+            )
         };
         debug!(
             "recorded blocked request telemetry (\
@@ -447,6 +498,9 @@ impl NetworkProxyState {
         if let Some(observer) = blocked_request_observer {
             observer.on_blocked_request(blocked_for_observer).await;
         }
+        if record_traffic && let Some(observer) = self.traffic_observer.read().await.clone() {
+            observer.on_network_traffic(traffic_entry).await;
+        }
         Ok(())
     }
 
@@ -520,6 +574,54 @@ impl NetworkProxyState {
         Ok(false)
     }
 
+    pub(crate) async fn request_rule_decision(
+        &self,
+        scheme: NetworkRequestScheme,
+        host: &str,
+        method: &str,
+        path: &str,
+    ) -> Result<Option<NetworkRequestRuleDecision>> {
+        self.reload_if_needed().await?;
+        let guard = self.state.read().await;
+        if guard.request_rules.is_empty() {
+            return Ok(None);
+        }
+        Ok(guard
+            .request_rules
+            .decision_for(scheme, &normalize_host(host), method, path))
+    }
+
+    pub(crate) async fn request_rule_scope(
+        &self,
+        scheme: NetworkRequestScheme,
+        host: &str,
+    ) -> Result<RequestRuleScope> {
+        self.reload_if_needed().await?;
+        let guard = self.state.read().await;
+        Ok(guard
+            .request_rules
+            .scope_for_host(scheme, &normalize_host(host)))
+    }
+
+    /// Resolves the credentials configured for `host`; secrets are only read when a
+    /// `network.credentials` entry matches.
+    pub(crate) async fn resolve_credentials(&self, host: &str) -> Result<ResolvedCredentials> {
+        self.reload_if_needed().await?;
+        let injections = {
+            let guard = self.state.read().await;
+            guard.credentials.for_host(&normalize_host(host))
+        };
+        if injections.is_empty() {
+            return Ok(ResolvedCredentials::default());
+        }
+        let source = self.credential_source.read().await.clone();
+        let Some(source) = source else {
+            warn!("network credentials are configured for {host} but no credential source is set");
+            return Ok(ResolvedCredentials::default());
+        };
+        ResolvedCredentials::resolve(injections, source.as_ref()).await
+    }
+
     pub async fn method_allowed(&self, method: &str) -> Result<bool> {
         self.reload_if_needed().await?;
         let guard = self.state.read().await;
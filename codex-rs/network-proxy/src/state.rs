@@ -2,10 +2,12 @@ use crate::config::NetworkDomainPermissions;
 use crate::config::NetworkMode;
 use crate::config::NetworkProxyConfig;
 use crate::config::NetworkUnixSocketPermissions;
+use crate::credentials::compile_credential_injections;
 use crate::mitm::MitmState;
 use crate::policy::DomainPattern;
 use crate::policy::compile_allowlist_globset;
 use crate::policy::compile_denylist_globset;
+use crate::policy::compile_request_rules;
 use crate::policy::is_global_wildcard_domain_pattern;
 use crate::runtime::ConfigState;
 use serde::Deserialize;
@@ -65,6 +67,8 @@ pub fn build_config_state(
         .map_err(NetworkProxyConstraintError::into_anyhow)?;
     let deny_set = compile_denylist_globset(&denied_domains)?;
     let allow_set = compile_allowlist_globset(&allowed_domains)?;
+    let request_rules = compile_request_rules(&config.network.rules)?;
+    let credentials = compile_credential_injections(&config.network.credentials)?;
     let mitm = if config.network.mitm {
         Some(Arc::new(MitmState::new(
             config.network.allow_upstream_proxy,
@@ -76,6 +80,8 @@ pub fn build_config_state(
         config,
         allow_set,
         deny_set,
+        request_rules,
+        credentials,
         mitm,
         constraints,
         blocked: std::collections::VecDeque::new(),
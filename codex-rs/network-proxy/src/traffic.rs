@@ -0,0 +1,493 @@
+use crate::runtime::BlockedRequest;
+use anyhow::Context as _;
+use anyhow::Result;
+use async_trait::async_trait;
+use codex_secrets::redact_secrets;
+use rama_core::bytes::Bytes;
+use rama_core::error::BoxError;
+use rama_core::futures::stream::Stream;
+use rama_http::Body;
+use rama_http::BodyDataStream;
+use rama_http::HeaderMap;
+use rama_http::Response;
+use rama_http::header::AUTHORIZATION;
+use rama_http::header::COOKIE;
+use rama_http::header::PROXY_AUTHORIZATION;
+use rama_http::header::SET_COOKIE;
+use serde::Serialize;
+use std::future::Future;
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::PoisonError;
+use std::task::Context as TaskContext;
+use std::task::Poll;
+use std::time::Instant;
+use time::OffsetDateTime;
+use tokio::io::AsyncWriteExt;
+use tracing::debug;
+use tracing::warn;
+
+/// Bodies are recorded up to this many bytes; `size` still reports the full length.
+const MAX_RECORDED_BODY_BYTES: usize = 16 * 1024;
+const REDACTED_HEADER_VALUE: &str = "[REDACTED]";
+const DECISION_ALLOW: &str = "allow";
+const DECISION_DENY: &str = "deny";
+
+/// One proxied request, recorded when `network.record_traffic` is enabled.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct NetworkTrafficEntry {
+    /// Unix timestamp in milliseconds at which the proxy received the request.
+    pub started_at_ms: i64,
+    /// Time until the response body completed, or until the request was rejected.
+    pub duration_ms: u64,
+    pub protocol: String,
+    pub method: Option<String>,
+    pub url: String,
+    /// `None` when the request was blocked or the upstream request failed.
+    pub status: Option<u16>,
+    pub request_headers: Vec<NetworkTrafficHeader>,
+    pub response_headers: Vec<NetworkTrafficHeader>,
+    pub request_body: Option<NetworkTrafficBody>,
+    pub response_body: Option<NetworkTrafficBody>,
+    /// `allow` for forwarded requests; the policy decision otherwise.
+    pub decision: String,
+    /// Which policy layer blocked the request; not tracked for allowed requests.
+    pub source: Option<String>,
+    pub reason: Option<String>,
+    pub client: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct NetworkTrafficHeader {
+    pub name: String,
+    pub value: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct NetworkTrafficBody {
+    /// Full body length in bytes.
+    pub size: u64,
+    /// Lossy UTF-8 rendering of the recorded prefix, with known secret patterns redacted.
+    pub text: String,
+    pub truncated: bool,
+}
+
+impl NetworkTrafficEntry {
+    pub(crate) fn blocked(request: &BlockedRequest) -> Self {
+        let url = match request.port {
+            Some(port) => format!("{}://{}:{port}", request.protocol, request.host),
+            None => format!("{}://{}", request.protocol, request.host),
+        };
+        Self {
+            started_at_ms: unix_timestamp_ms(),
+            duration_ms: 0,
+            protocol: request.protocol.clone(),
+            method: request.method.clone(),
+            url,
+            status: None,
+            request_headers: Vec::new(),
+            response_headers: Vec::new(),
+            request_body: None,
+            response_body: None,
+            decision: request
+                .decision
+                .clone()
+                .unwrap_or_else(|| DECISION_DENY.to_string()),
+            source: request.source.clone(),
+            reason: Some(request.reason.clone()),
+            client: request.client.clone(),
+        }
+    }
+}
+
+#[async_trait]
+pub trait NetworkTrafficObserver: Send + Sync + 'static {
+    async fn on_network_traffic(&self, entry: NetworkTrafficEntry);
+}
+
+#[async_trait]
+impl<O: NetworkTrafficObserver + ?Sized> NetworkTrafficObserver for Arc<O> {
+    async fn on_network_traffic(&self, entry: NetworkTrafficEntry) {
+        (**self).on_network_traffic(entry).await
+    }
+}
+
+#[async_trait]
+impl<F, Fut> NetworkTrafficObserver for F
+where
+    F: Fn(NetworkTrafficEntry) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = ()> + Send,
+{
+    async fn on_network_traffic(&self, entry: NetworkTrafficEntry) {
+        (self)(entry).await
+    }
+}
+
+/// Appends traffic entries to a JSONL file, one JSON object per line.
+pub struct JsonlTrafficRecorder {
+    path: PathBuf,
+    file: tokio::sync::Mutex<Option<tokio::fs::File>>,
+}
+
+impl JsonlTrafficRecorder {
+    /// The file and its parent directory are created on the first recorded entry.
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            file: tokio::sync::Mutex::new(None),
+        }
+    }
+
+    pub async fn record(&self, entry: &NetworkTrafficEntry) -> Result<()> {
+        let mut line = serde_json::to_vec(entry).context("failed to serialize traffic entry")?;
+        line.push(b'\n');
+        let mut file = self.file.lock().await;
+        if file.is_none() {
+            if let Some(parent) = self.path.parent() {
+                tokio::fs::create_dir_all(parent)
+                    .await
+                    .with_context(|| format!("failed to create {}", parent.display()))?;
+            }
+            let opened = tokio::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&self.path)
+                .await
+                .with_context(|| format!("failed to open {}", self.path.display()))?;
+            *file = Some(opened);
+        }
+        let Some(file) = file.as_mut() else {
+            return Ok(());
+        };
+        file.write_all(&line)
+            .await
+            .with_context(|| format!("failed to write {}", self.path.display()))?;
+        file.flush().await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl NetworkTrafficObserver for JsonlTrafficRecorder {
+    async fn on_network_traffic(&self, entry: NetworkTrafficEntry) {
+        if let Err(err) = self.record(&entry).await {
+            warn!("failed to record network traffic: {err:#}");
+        }
+    }
+}
+
+pub(crate) struct TrafficRequest<'a> {
+    pub(crate) protocol: &'a str,
+    pub(crate) method: &'a str,
+    pub(crate) url: &'a str,
+    pub(crate) headers: &'a HeaderMap,
+    pub(crate) client: Option<String>,
+}
+
+/// Tracks one forwarded request until its response body completes.
+pub(crate) struct TrafficRecording {
+    observer: Arc<dyn NetworkTrafficObserver>,
+    entry: NetworkTrafficEntry,
+    started: Instant,
+    request_body: Arc<Mutex<BodyCapture>>,
+}
+
+impl TrafficRecording {
+    pub(crate) fn start(
+        observer: Arc<dyn NetworkTrafficObserver>,
+        request: TrafficRequest,
+    ) -> Self {
+        let entry = NetworkTrafficEntry {
+            started_at_ms: unix_timestamp_ms(),
+            duration_ms: 0,
+            protocol: request.protocol.to_string(),
+            method: Some(request.method.to_string()),
+            url: redact_secrets(request.url.to_string()),
+            status: None,
+            request_headers: recorded_headers(request.headers),
+            response_headers: Vec::new(),
+            request_body: None,
+            response_body: None,
+            decision: DECISION_ALLOW.to_string(),
+            source: None,
+            reason: None,
+            client: request.client,
+        };
+        Self {
+            observer,
+            entry,
+            started: Instant::now(),
+            request_body: Arc::new(Mutex::new(BodyCapture::default())),
+        }
+    }
+
+    pub(crate) fn record_request_body(&self, body: Body) -> Body {
+        Body::from_stream(CaptureStream {
+            inner: Box::pin(body.into_data_stream()),
+            capture: Arc::clone(&self.request_body),
+        })
+    }
+
+    /// Wraps the response body; the entry is emitted once the body ends or is dropped.
+    pub(crate) fn record_response(mut self, resp: Response) -> Response {
+        self.entry.status = Some(resp.status().as_u16());
+        self.entry.response_headers = recorded_headers(resp.headers());
+        let (parts, body) = resp.into_parts();
+        let body = Body::from_stream(ResponseRecordStream {
+            inner: Box::pin(body.into_data_stream()),
+            capture: BodyCapture::default(),
+            recording: Some(self),
+        });
+        Response::from_parts(parts, body)
+    }
+
+    pub(crate) fn record_failure(mut self, reason: &str) {
+        self.entry.reason = Some(reason.to_string());
+        self.finish(/*response_body*/ None);
+    }
+
+    fn finish(mut self, response_body: Option<NetworkTrafficBody>) {
+        self.entry.duration_ms =
+            u64::try_from(self.started.elapsed().as_millis()).unwrap_or(u64::MAX);
+        self.entry.request_body = self
+            .request_body
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .to_body();
+        self.entry.response_body = response_body;
+        emit_traffic(self.observer, self.entry);
+    }
+}
+
+fn emit_traffic(observer: Arc<dyn NetworkTrafficObserver>, entry: NetworkTrafficEntry) {
+    // Entries are emitted from body streams, so hand them off instead of blocking the poll.
+    match tokio::runtime::Handle::try_current() {
+        Ok(handle) => {
+            handle.spawn(async move { observer.on_network_traffic(entry).await });
+        }
+        Err(_) => debug!("dropping network traffic entry outside of a tokio runtime"),
+    }
+}
+
+fn recorded_headers(headers: &HeaderMap) -> Vec<NetworkTrafficHeader> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            let sensitive = value.is_sensitive()
+                || *name == AUTHORIZATION
+                || *name == PROXY_AUTHORIZATION
+                || *name == COOKIE
+                || *name == SET_COOKIE
+                || name.as_str() == "x-api-key";
+            let value = if sensitive {
+                REDACTED_HEADER_VALUE.to_string()
+            } else {
+                redact_secrets(String::from_utf8_lossy(value.as_bytes()).into_owned())
+            };
+            NetworkTrafficHeader {
+                name: name.as_str().to_string(),
+                value,
+            }
+        })
+        .collect()
+}
+
+#[derive(Default)]
+struct BodyCapture {
+    size: u64,
+    bytes: Vec<u8>,
+    seen: bool,
+}
+
+impl BodyCapture {
+    fn push(&mut self, chunk: &[u8]) {
+        self.seen = true;
+        self.size = self.size.saturating_add(chunk.len() as u64);
+        let remaining = MAX_RECORDED_BODY_BYTES.saturating_sub(self.bytes.len());
+        self.bytes
+            .extend_from_slice(&chunk[..remaining.min(chunk.len())]);
+    }
+
+    fn to_body(&self) -> Option<NetworkTrafficBody> {
+        if !self.seen {
+            return None;
+        }
+        Some(NetworkTrafficBody {
+            size: self.size,
+            text: redact_secrets(String::from_utf8_lossy(&self.bytes).into_owned()),
+            truncated: self.size > self.bytes.len() as u64,
+        })
+    }
+}
+
+struct CaptureStream {
+    inner: Pin<Box<BodyDataStream>>,
+    capture: Arc<Mutex<BodyCapture>>,
+}
+
+impl Stream for CaptureStream {
+    type Item = Result<Bytes, BoxError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let poll = this.inner.as_mut().poll_next(cx);
+        if let Poll::Ready(Some(Ok(bytes))) = &poll {
+            this.capture
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .push(bytes);
+        }
+        poll
+    }
+}
+
+struct ResponseRecordStream {
+    inner: Pin<Box<BodyDataStream>>,
+    capture: BodyCapture,
+    recording: Option<TrafficRecording>,
+}
+
+impl ResponseRecordStream {
+    fn finish(&mut self) {
+        if let Some(recording) = self.recording.take() {
+            recording.finish(self.capture.to_body());
+        }
+    }
+}
+
+impl Stream for ResponseRecordStream {
+    type Item = Result<Bytes, BoxError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let poll = this.inner.as_mut().poll_next(cx);
+        match &poll {
+            Poll::Ready(Some(Ok(bytes))) => this.capture.push(bytes),
+            Poll::Ready(Some(Err(_))) | Poll::Ready(None) => this.finish(),
+            Poll::Pending => {}
+        }
+        poll
+    }
+}
+
+impl Drop for ResponseRecordStream {
+    fn drop(&mut self) {
+        // Clients may hang up before reading the whole body; record what was seen.
+        self.finish();
+    }
+}
+
+fn unix_timestamp_ms() -> i64 {
+    i64::try_from(OffsetDateTime::now_utc().unix_timestamp_nanos() / 1_000_000).unwrap_or(i64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use rama_http::HeaderValue;
+
+    #[test]
+    fn recorded_headers_redact_credentials() {
+        let mut headers = HeaderMap::new();
+        headers.insert(AUTHORIZATION, HeaderValue::from_static("Bearer abc"));
+        headers.insert("x-api-key", HeaderValue::from_static("key"));
+        let mut injected = HeaderValue::from_static("injected");
+        injected.set_sensitive(true);
+        headers.insert("x-custom-token", injected);
+        headers.insert("accept", HeaderValue::from_static("application/json"));
+
+        let recorded = recorded_headers(&headers);
+
+        assert_eq!(
+            recorded,
+            vec![
+                NetworkTrafficHeader {
+                    name: "authorization".to_string(),
+                    value: REDACTED_HEADER_VALUE.to_string(),
+                },
+                NetworkTrafficHeader {
+                    name: "x-api-key".to_string(),
+                    value: REDACTED_HEADER_VALUE.to_string(),
+                },
+                NetworkTrafficHeader {
+                    name: "x-custom-token".to_string(),
+                    value: REDACTED_HEADER_VALUE.to_string(),
+                },
+                NetworkTrafficHeader {
+                    name: "accept".to_string(),
+                    value: "application/json".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn body_capture_truncates_but_reports_full_size() {
+        let mut capture = BodyCapture::default();
+        assert_eq!(capture.to_body(), None);
+
+        capture.push(&vec![b'a'; MAX_RECORDED_BODY_BYTES]);
+        capture.push(b"tail");
+
+        let body = capture.to_body().expect("body should be recorded");
+        assert_eq!(body.size, MAX_RECORDED_BODY_BYTES as u64 + 4);
+        assert_eq!(body.text.len(), MAX_RECORDED_BODY_BYTES);
+        assert!(body.truncated);
+    }
+
+    #[test]
+    fn blocked_entry_uses_policy_decision_and_source() {
+        let entry = NetworkTrafficEntry::blocked(&BlockedRequest::new(
+            crate::runtime::BlockedRequestArgs {
+                host: "example.com".to_string(),
+                reason: "denied".to_string(),
+                client: Some("127.0.0.1:1234".to_string()),
+                method: Some("POST".to_string()),
+                mode: None,
+                protocol: "http".to_string(),
+                decision: Some("deny".to_string()),
+                source: Some("baseline_policy".to_string()),
+                port: Some(80),
+            },
+        ));
+
+        assert_eq!(entry.url, "http://example.com:80");
+        assert_eq!(entry.decision, "deny");
+        assert_eq!(entry.source.as_deref(), Some("baseline_policy"));
+        assert_eq!(entry.reason.as_deref(), Some("denied"));
+        assert_eq!(entry.status, None);
+    }
+
+    #[tokio::test]
+    async fn jsonl_recorder_appends_one_line_per_entry() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("logs").join("thread.jsonl");
+        let recorder = JsonlTrafficRecorder::new(path.clone());
+        let entry = NetworkTrafficEntry::blocked(&BlockedRequest::new(
+            crate::runtime::BlockedRequestArgs {
+                host: "example.com".to_string(),
+                reason: "not_allowed".to_string(),
+                client: None,
+                method: Some("GET".to_string()),
+                mode: None,
+                protocol: "https".to_string(),
+                decision: None,
+                source: None,
+                port: None,
+            },
+        ));
+
+        recorder.record(&entry).await.expect("record first entry");
+        recorder.record(&entry).await.expect("record second entry");
+
+        let contents = std::fs::read_to_string(&path).expect("read log");
+        let lines = contents.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 2);
+        let parsed: serde_json::Value = serde_json::from_str(lines[0]).expect("valid json");
+        assert_eq!(parsed["url"], "https://example.com");
+        assert_eq!(parsed["decision"], "deny");
+    }
+}
@@ -58,11 +58,42 @@ pub enum SandboxErr {
     #[error("command was killed by a signal")]
     Signal(i32),
 
+    /// Command was killed for exceeding a resource limit of the permissions
+    /// profile
+    #[error("command exceeded its {limit} limit")]
+    ResourceLimitExceeded {
+        limit: ResourceLimitKind,
+        output: Box<ExecToolCallOutput>,
+    },
+
     /// Error from linux landlock
     #[error("Landlock was not able to fully enforce all sandbox rules")]
     LandlockRestrict,
 }
 
+/// Resource limit that ended a command, when Codex can tell which one it was.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResourceLimitKind {
+    Memory,
+    CpuTime,
+    Processes,
+    OutputBytes,
+    WallTime,
+}
+
+impl std::fmt::Display for ResourceLimitKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let limit = match self {
+            Self::Memory => "memory",
+            Self::CpuTime => "CPU time",
+            Self::Processes => "process count",
+            Self::OutputBytes => "output size",
+            Self::WallTime => "wall time",
+        };
+        f.write_str(limit)
+    }
+}
+
 #[derive(Error, Debug)]
 pub enum CodexErr {
     #[error("turn aborted. Something went wrong? Hit `/feedback` to report the issue.")]
@@ -621,6 +652,12 @@ pub fn get_error_message_ui(e: &CodexErr) -> String {
                 output.duration.as_millis()
             )
         }
+        CodexErr::Sandbox(SandboxErr::ResourceLimitExceeded { limit, output }) => {
+            format!(
+                "error: command was killed after {} ms for exceeding its {limit} limit",
+                output.duration.as_millis()
+            )
+        }
         _ => e.to_string(),
     };
 
@@ -170,6 +170,27 @@ fn sandbox_denied_reports_exit_code_when_no_output_available() {
     );
 }
 
+#[test]
+fn resource_limit_exceeded_names_the_limit() {
+    let output = ExecToolCallOutput {
+        exit_code: 137,
+        duration: Duration::from_millis(1_500),
+        ..Default::default()
+    };
+    let err = CodexErr::Sandbox(SandboxErr::ResourceLimitExceeded {
+        limit: ResourceLimitKind::Memory,
+        output: Box::new(output),
+    });
+    assert_eq!(
+        err.to_string(),
+        "sandbox error: command exceeded its memory limit"
+    );
+    assert_eq!(
+        get_error_message_ui(&err),
+        "error: command was killed after 1500 ms for exceeding its memory limit"
+    );
+}
+
 #[test]
 fn usage_limit_reached_error_formats_free_plan() {
     let err = UsageLimitReachedError {
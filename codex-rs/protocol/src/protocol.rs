@@ -1708,6 +1708,10 @@ pub enum EventMsg {
     HookStarted(HookStartedEvent),
     HookCompleted(HookCompletedEvent),
 
+    /// A request that went through the managed network proxy while
+    /// `network.record_traffic` is enabled.
+    NetworkTrafficRecorded(NetworkTrafficRecordedEvent),
+
     AgentMessageContentDelta(AgentMessageContentDeltaEvent),
     PlanDelta(PlanDeltaEvent),
     ReasoningContentDelta(ReasoningContentDeltaEvent),
@@ -1744,6 +1748,11 @@ pub enum HookEventName {
     SessionStart,
     UserPromptSubmit,
     Stop,
+    PreCompact,
+    SessionEnd,
+    SubagentStart,
+    SubagentStop,
+    Notification,
 }
 
 #[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
@@ -1846,6 +1855,45 @@ pub struct HookCompletedEvent {
     pub run: HookRunSummary,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+pub struct NetworkTrafficRecordedEvent {
+    /// Unix timestamp in milliseconds at which the proxy received the request.
+    #[ts(type = "number")]
+    pub started_at_ms: i64,
+    #[ts(type = "number")]
+    pub duration_ms: u64,
+    pub protocol: String,
+    pub method: Option<String>,
+    pub url: String,
+    /// `None` when the request was blocked or the upstream request failed.
+    pub status: Option<u16>,
+    pub request_headers: Vec<NetworkTrafficHeader>,
+    pub response_headers: Vec<NetworkTrafficHeader>,
+    pub request_body: Option<NetworkTrafficBody>,
+    pub response_body: Option<NetworkTrafficBody>,
+    /// `allow` for forwarded requests; the policy decision otherwise.
+    pub decision: String,
+    /// Which policy layer blocked the request; not tracked for allowed requests.
+    pub source: Option<String>,
+    pub reason: Option<String>,
+    pub client: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+pub struct NetworkTrafficHeader {
+    pub name: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+pub struct NetworkTrafficBody {
+    /// Full body length in bytes; `text` holds at most the recorded prefix.
+    #[ts(type = "number")]
+    pub size: u64,
+    pub text: String,
+    pub truncated: bool,
+}
+
 #[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
 #[serde(rename_all = "snake_case")]
 pub enum RealtimeConversationVersion {
@@ -3764,6 +3812,16 @@ pub enum ReviewDecision {
     /// remainder of the session.
     ApprovedForSession,
 
+    /// User has approved only part of a proposed patch. Files that are not
+    /// listed, and hunks that are not selected, are rejected.
+    ApprovedPartialPatch {
+        accepted_files: Vec<PatchFileApproval>,
+    },
+
+    /// User has approved an edited patch that is applied instead of the
+    /// proposed one.
+    ApprovedEditedPatch { patch: String },
+
     /// User chose to persist a network policy rule (allow/deny) for future
     /// requests to the same host.
     NetworkPolicyAmendment {
@@ -3791,6 +3849,8 @@ impl ReviewDecision {
             ReviewDecision::Approved => "approved",
             ReviewDecision::ApprovedExecpolicyAmendment { .. } => "approved_with_amendment",
             ReviewDecision::ApprovedForSession => "approved_for_session",
+            ReviewDecision::ApprovedPartialPatch { .. } => "approved_partial_patch",
+            ReviewDecision::ApprovedEditedPatch { .. } => "approved_edited_patch",
             ReviewDecision::NetworkPolicyAmendment {
                 network_policy_amendment,
             } => match network_policy_amendment.action {
@@ -3820,6 +3880,45 @@ pub enum FileChange {
     },
 }
 
+impl FileChange {
+    /// Returns the `@@` hunks of an update's unified diff, in order. These are
+    /// what [`PatchFileApproval::hunks`] indexes into. Adds and deletes have no
+    /// hunks.
+    pub fn unified_diff_hunks(&self) -> Vec<&str> {
+        let FileChange::Update { unified_diff, .. } = self else {
+            return Vec::new();
+        };
+        let mut hunks = Vec::new();
+        let mut hunk_start = None;
+        let mut offset = 0;
+        for line in unified_diff.split_inclusive('\n') {
+            if line.starts_with("@@") {
+                if let Some(start) = hunk_start {
+                    hunks.push(&unified_diff[start..offset]);
+                }
+                hunk_start = Some(offset);
+            }
+            offset += line.len();
+        }
+        if let Some(start) = hunk_start {
+            hunks.push(&unified_diff[start..]);
+        }
+        hunks
+    }
+}
+
+/// The part of one file in a proposed patch that the user accepted.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+pub struct PatchFileApproval {
+    /// Path as it appears in the approval request's `changes`.
+    pub path: PathBuf,
+    /// Zero-based indices of the accepted `@@` hunks in the file's
+    /// `unified_diff`. `None` accepts every change to the file.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub hunks: Option<Vec<u32>>,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct Chunk {
     /// 1-based line index of the first line in the original file
@@ -5170,6 +5269,59 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn partial_patch_review_decision_round_trips() -> Result<()> {
+        let decision = ReviewDecision::ApprovedPartialPatch {
+            accepted_files: vec![
+                PatchFileApproval {
+                    path: PathBuf::from("src/lib.rs"),
+                    hunks: Some(vec![0, 2]),
+                },
+                PatchFileApproval {
+                    path: PathBuf::from("README.md"),
+                    hunks: None,
+                },
+            ],
+        };
+        let value = serde_json::to_value(&decision)?;
+
+        assert_eq!(
+            value,
+            json!({
+                "approved_partial_patch": {
+                    "accepted_files": [
+                        {"path": "src/lib.rs", "hunks": [0, 2]},
+                        {"path": "README.md"},
+                    ],
+                },
+            })
+        );
+        assert_eq!(serde_json::from_value::<ReviewDecision>(value)?, decision);
+        Ok(())
+    }
+
+    #[test]
+    fn unified_diff_hunks_splits_on_hunk_headers() {
+        let change = FileChange::Update {
+            unified_diff:
+                "--- a/lib.rs\n+++ b/lib.rs\n@@ -1,2 +1,2 @@\n-a\n+b\n c\n@@ -9 +9 @@\n-y\n+z\n"
+                    .to_string(),
+            move_path: None,
+        };
+
+        assert_eq!(
+            change.unified_diff_hunks(),
+            vec!["@@ -1,2 +1,2 @@\n-a\n+b\n c\n", "@@ -9 +9 @@\n-y\n+z\n"]
+        );
+        assert_eq!(
+            FileChange::Add {
+                content: "new\n".to_string(),
+            }
+            .unified_diff_hunks(),
+            Vec::<&str>::new()
+        );
+    }
+
     #[test]
     fn turn_aborted_event_deserializes_without_turn_id() -> Result<()> {
         let event: EventMsg = serde_json::from_value(json!({
@@ -280,6 +280,7 @@ pub(crate) fn tool_runtime_trace_event(event: &EventMsg) -> Option<ToolRuntimeTr
         | EventMsg::ItemCompleted(_)
         | EventMsg::HookStarted(_)
         | EventMsg::HookCompleted(_)
+        | EventMsg::NetworkTrafficRecorded(_)
         | EventMsg::AgentMessageContentDelta(_)
         | EventMsg::PlanDelta(_)
         | EventMsg::ReasoningContentDelta(_)
@@ -360,6 +361,7 @@ pub(crate) fn wrapped_protocol_event_type(event: &EventMsg) -> Option<&'static s
         | EventMsg::ItemCompleted(_)
         | EventMsg::HookStarted(_)
         | EventMsg::HookCompleted(_)
+        | EventMsg::NetworkTrafficRecorded(_)
         | EventMsg::AgentMessageContentDelta(_)
         | EventMsg::PlanDelta(_)
         | EventMsg::ReasoningContentDelta(_)
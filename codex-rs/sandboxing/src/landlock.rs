@@ -3,6 +3,8 @@ use codex_protocol::permissions::NetworkSandboxPolicy;
 use codex_protocol::protocol::SandboxPolicy;
 use std::path::Path;
 
+use crate::overlay::OverlayWorkspace;
+
 /// Basename used when the Codex executable self-invokes as the Linux sandbox
 /// helper.
 pub const CODEX_LINUX_SANDBOX_ARG0: &str = "codex-linux-sandbox";
@@ -19,8 +21,10 @@ pub fn allow_network_for_proxy(enforce_managed_network: bool) -> bool {
 ///
 /// The helper performs the actual sandboxing (bubblewrap by default + seccomp) after
 /// parsing these arguments. Policy JSON flags are emitted before helper feature
-/// flags so the argv order matches the helper's CLI shape. See
-/// `docs/linux_sandbox.md` for the Linux semantics.
+/// flags so the argv order matches the helper's CLI shape. When
+/// `overlay_workspace` is set, the helper mounts its lower directory through
+/// overlayfs instead of binding it writable. See `docs/linux_sandbox.md` for
+/// the Linux semantics.
 #[allow(clippy::too_many_arguments)]
 pub fn create_linux_sandbox_command_args_for_policies(
     command: Vec<String>,
@@ -31,6 +35,7 @@ pub fn create_linux_sandbox_command_args_for_policies(
     sandbox_policy_cwd: &Path,
     use_legacy_landlock: bool,
     allow_network_for_proxy: bool,
+    overlay_workspace: Option<&OverlayWorkspace>,
 ) -> Vec<String> {
     let sandbox_policy_json = serde_json::to_string(sandbox_policy)
         .unwrap_or_else(|err| panic!("failed to serialize sandbox policy: {err}"));
@@ -65,6 +70,12 @@ pub fn create_linux_sandbox_command_args_for_policies(
     if allow_network_for_proxy {
         linux_cmd.push("--allow-network-for-proxy".to_string());
     }
+    if let Some(overlay_workspace) = overlay_workspace {
+        let overlay_workspace_json = serde_json::to_string(overlay_workspace)
+            .unwrap_or_else(|err| panic!("failed to serialize overlay workspace: {err}"));
+        linux_cmd.push("--overlay-workspace".to_string());
+        linux_cmd.push(overlay_workspace_json);
+    }
     linux_cmd.push("--".to_string());
     linux_cmd.extend(command);
     linux_cmd
@@ -1,4 +1,5 @@
 use super::*;
+use codex_utils_absolute_path::AbsolutePathBuf;
 use pretty_assertions::assert_eq;
 
 #[test]
@@ -69,6 +70,7 @@ fn split_policy_flags_are_included() {
         cwd,
         /*use_legacy_landlock*/ true,
         /*allow_network_for_proxy*/ false,
+        /*overlay_workspace*/ None,
     );
 
     assert_eq!(
@@ -88,6 +90,40 @@ fn split_policy_flags_are_included() {
     );
 }
 
+#[test]
+fn overlay_workspace_flag_precedes_the_command() {
+    let sandbox_policy = SandboxPolicy::new_read_only_policy();
+    let overlay_workspace = OverlayWorkspace {
+        lower_dir: AbsolutePathBuf::from_absolute_path("/repo").expect("absolute"),
+        upper_dir: AbsolutePathBuf::from_absolute_path("/state/upper").expect("absolute"),
+        work_dir: AbsolutePathBuf::from_absolute_path("/state/work").expect("absolute"),
+    };
+
+    let args = create_linux_sandbox_command_args_for_policies(
+        vec!["/bin/true".to_string()],
+        Path::new("/repo"),
+        &sandbox_policy,
+        &FileSystemSandboxPolicy::from(&sandbox_policy),
+        NetworkSandboxPolicy::from(&sandbox_policy),
+        Path::new("/repo"),
+        /*use_legacy_landlock*/ false,
+        /*allow_network_for_proxy*/ false,
+        Some(&overlay_workspace),
+    );
+
+    let flag_index = args
+        .iter()
+        .position(|arg| arg == "--overlay-workspace")
+        .expect("overlay flag");
+    assert_eq!(
+        args[flag_index + 1]
+            .parse::<OverlayWorkspace>()
+            .expect("parse overlay"),
+        overlay_workspace
+    );
+    assert_eq!(args[flag_index + 2..], ["--", "/bin/true"]);
+}
+
 #[test]
 fn proxy_network_requires_managed_requirements() {
     assert_eq!(
@@ -2,6 +2,7 @@
 mod bwrap;
 pub mod landlock;
 mod manager;
+pub mod overlay;
 pub mod policy_transforms;
 #[cfg(target_os = "macos")]
 pub mod seatbelt;
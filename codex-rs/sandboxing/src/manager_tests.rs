@@ -95,6 +95,7 @@ fn transform_preserves_unrestricted_file_system_policy_for_restricted_network()
             sandbox_policy_cwd: cwd.as_path(),
             codex_linux_sandbox_exe: None,
             use_legacy_landlock: false,
+            overlay_workspace: None,
             windows_sandbox_level: WindowsSandboxLevel::Disabled,
             windows_sandbox_private_desktop: false,
         })
@@ -147,6 +148,7 @@ fn transform_additional_permissions_enable_network_for_external_sandbox() {
             sandbox_policy_cwd: cwd.as_path(),
             codex_linux_sandbox_exe: None,
             use_legacy_landlock: false,
+            overlay_workspace: None,
             windows_sandbox_level: WindowsSandboxLevel::Disabled,
             windows_sandbox_private_desktop: false,
         })
@@ -215,6 +217,7 @@ fn transform_additional_permissions_preserves_denied_entries() {
             sandbox_policy_cwd: cwd.as_path(),
             codex_linux_sandbox_exe: None,
             use_legacy_landlock: false,
+            overlay_workspace: None,
             windows_sandbox_level: WindowsSandboxLevel::Disabled,
             windows_sandbox_private_desktop: false,
         })
@@ -269,6 +272,7 @@ fn transform_linux_seccomp_request(
             sandbox_policy_cwd: cwd.as_path(),
             codex_linux_sandbox_exe: Some(codex_linux_sandbox_exe),
             use_legacy_landlock: false,
+            overlay_workspace: None,
             windows_sandbox_level: WindowsSandboxLevel::Disabled,
             windows_sandbox_private_desktop: false,
         })
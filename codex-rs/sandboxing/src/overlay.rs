@@ -0,0 +1,381 @@
+//! Copy-on-write overlay workspaces for the Linux sandbox.
+//!
+//! In overlay mode the workspace root is mounted with overlayfs inside
+//! bubblewrap: the real tree is the read-only lower layer and every write made
+//! by sandboxed commands lands in a per-turn upper directory. Deletions are
+//! recorded by overlayfs as whiteouts (0/0 character devices) and replaced
+//! directories as opaque directories. At the end of the turn the upper
+//! directory is turned into a list of file changes that can be committed into
+//! the real tree or discarded.
+use std::collections::BTreeSet;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use codex_utils_absolute_path::AbsolutePathBuf;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Extended attribute overlayfs sets on opaque directories when mounted with
+/// `userxattr`, which bubblewrap always does for `--overlay`.
+#[cfg(target_os = "linux")]
+const OPAQUE_XATTR: &str = "user.overlay.opaque";
+
+/// The layers of one turn's overlay workspace.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OverlayWorkspace {
+    /// The real workspace root, mounted as the overlay's lower layer.
+    pub lower_dir: AbsolutePathBuf,
+    /// Receives every write made through the overlay.
+    pub upper_dir: AbsolutePathBuf,
+    /// Scratch directory overlayfs requires on the same filesystem as
+    /// `upper_dir`.
+    pub work_dir: AbsolutePathBuf,
+}
+
+/// How a file in the real workspace differs from its overlay view.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OverlayChangeKind {
+    Added,
+    Modified,
+    Deleted,
+}
+
+/// One file changed through the overlay, identified by its real path.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OverlayChange {
+    pub path: PathBuf,
+    pub kind: OverlayChangeKind,
+}
+
+/// Where the overlay view of a path comes from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum OverlayEntry {
+    /// The path is untouched by the overlay; read it from the real tree.
+    Lower,
+    /// The overlay holds its own copy of the path at this location.
+    Upper(PathBuf),
+    /// The path was removed through the overlay.
+    Removed,
+}
+
+impl FromStr for OverlayWorkspace {
+    type Err = serde_json::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str(s)
+    }
+}
+
+impl OverlayWorkspace {
+    /// Lays out the upper and work directories for `lower_dir` under
+    /// `state_dir`, which should be unique to the turn.
+    pub fn new(lower_dir: AbsolutePathBuf, state_dir: &AbsolutePathBuf) -> Self {
+        Self {
+            lower_dir,
+            upper_dir: state_dir.join("upper"),
+            work_dir: state_dir.join("work"),
+        }
+    }
+
+    /// Creates the upper and work directories if they do not exist yet.
+    pub fn create_dirs(&self) -> io::Result<()> {
+        fs::create_dir_all(&self.upper_dir)?;
+        fs::create_dir_all(&self.work_dir)
+    }
+
+    /// Resolves the overlay view of `path`. Paths outside the lower directory
+    /// are always [`OverlayEntry::Lower`].
+    pub fn resolve(&self, path: &Path) -> OverlayEntry {
+        let Ok(relative) = path.strip_prefix(&self.lower_dir) else {
+            return OverlayEntry::Lower;
+        };
+        let components = relative.components().collect::<Vec<_>>();
+        let mut upper_path = self.upper_dir.to_path_buf();
+        let mut hidden_by_opaque_dir = false;
+        for (index, component) in components.iter().enumerate() {
+            upper_path.push(component);
+            let Ok(metadata) = fs::symlink_metadata(&upper_path) else {
+                return if hidden_by_opaque_dir {
+                    OverlayEntry::Removed
+                } else {
+                    OverlayEntry::Lower
+                };
+            };
+            if is_whiteout(&metadata) {
+                return OverlayEntry::Removed;
+            }
+            if index + 1 == components.len() {
+                return OverlayEntry::Upper(upper_path);
+            }
+            if !metadata.is_dir() {
+                // A file in the upper layer hides everything below the
+                // directory it replaced.
+                return OverlayEntry::Removed;
+            }
+            hidden_by_opaque_dir |= is_opaque_dir(&upper_path);
+        }
+        OverlayEntry::Lower
+    }
+
+    /// Lists the files whose overlay view differs from the real tree, sorted
+    /// by path. Directories are not reported on their own.
+    pub fn changes(&self) -> io::Result<Vec<OverlayChange>> {
+        let mut candidates = BTreeSet::new();
+        self.collect_candidates(self.upper_dir.as_path(), &mut candidates)?;
+
+        let mut changes = Vec::new();
+        for path in candidates {
+            let before = lower_file_metadata(&path);
+            let after = match self.resolve(&path) {
+                OverlayEntry::Lower => Some(path.clone()),
+                OverlayEntry::Upper(upper_path) => Some(upper_path),
+                OverlayEntry::Removed => None,
+            }
+            .filter(|source| fs::symlink_metadata(source).is_ok_and(|metadata| !metadata.is_dir()));
+            let kind = match (before, after) {
+                (None, None) => continue,
+                (None, Some(_)) => OverlayChangeKind::Added,
+                (Some(_), None) => OverlayChangeKind::Deleted,
+                (Some(_), Some(source)) => {
+                    if source == path || same_file_contents(&path, &source)? {
+                        continue;
+                    }
+                    OverlayChangeKind::Modified
+                }
+            };
+            changes.push(OverlayChange { path, kind });
+        }
+        Ok(changes)
+    }
+
+    /// Applies `changes` to the real tree. Deletions run first so a file can
+    /// replace a directory it removed, and directories the overlay removed are
+    /// pruned once the deleted files inside them are gone.
+    pub fn commit(&self, changes: &[OverlayChange]) -> io::Result<()> {
+        for change in changes
+            .iter()
+            .filter(|change| change.kind == OverlayChangeKind::Deleted)
+        {
+            match fs::remove_file(&change.path) {
+                Ok(()) => {}
+                Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+                Err(err) => return Err(err),
+            }
+            self.prune_removed_dirs(&change.path);
+        }
+        for change in changes
+            .iter()
+            .filter(|change| change.kind != OverlayChangeKind::Deleted)
+        {
+            let OverlayEntry::Upper(source) = self.resolve(&change.path) else {
+                return Err(io::Error::other(format!(
+                    "overlay has no copy of {}",
+                    change.path.display()
+                )));
+            };
+            copy_into_place(&source, &change.path)?;
+        }
+        Ok(())
+    }
+
+    /// Removes the upper and work directories along with their parent state
+    /// directory.
+    pub fn discard(&self) -> io::Result<()> {
+        for dir in [&self.upper_dir, &self.work_dir] {
+            // overlayfs leaves mode-000 directories in the work dir.
+            make_dirs_removable(dir.as_path())?;
+            match fs::remove_dir_all(dir) {
+                Ok(()) => {}
+                Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+                Err(err) => return Err(err),
+            }
+        }
+        if let Some(state_dir) = self.upper_dir.parent() {
+            let _ = fs::remove_dir(state_dir);
+        }
+        Ok(())
+    }
+
+    fn collect_candidates(
+        &self,
+        upper_dir: &Path,
+        candidates: &mut BTreeSet<PathBuf>,
+    ) -> io::Result<()> {
+        let entries = match fs::read_dir(upper_dir) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
+            Err(err) => return Err(err),
+        };
+        for entry in entries {
+            let entry = entry?;
+            let upper_path = entry.path();
+            let Ok(relative) = upper_path.strip_prefix(&self.upper_dir) else {
+                continue;
+            };
+            let lower_path = self.lower_dir.join(relative).into_path_buf();
+            let metadata = fs::symlink_metadata(&upper_path)?;
+            if metadata.is_dir() {
+                if is_opaque_dir(&upper_path) {
+                    collect_lower_files(&lower_path, candidates)?;
+                }
+                self.collect_candidates(&upper_path, candidates)?;
+            } else {
+                // Whiteouts and files that replace a lower directory both hide
+                // every lower file below them.
+                collect_lower_files(&lower_path, candidates)?;
+                if !is_whiteout(&metadata) {
+                    candidates.insert(lower_path);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn prune_removed_dirs(&self, deleted_path: &Path) {
+        for dir in deleted_path.ancestors().skip(1) {
+            if dir == self.lower_dir.as_path() || !dir.starts_with(&self.lower_dir) {
+                break;
+            }
+            if self.resolve(dir) != OverlayEntry::Removed || fs::remove_dir(dir).is_err() {
+                break;
+            }
+        }
+    }
+}
+
+fn lower_file_metadata(path: &Path) -> Option<fs::Metadata> {
+    fs::symlink_metadata(path)
+        .ok()
+        .filter(|metadata| !metadata.is_dir())
+}
+
+fn collect_lower_files(path: &Path, files: &mut BTreeSet<PathBuf>) -> io::Result<()> {
+    let Ok(metadata) = fs::symlink_metadata(path) else {
+        return Ok(());
+    };
+    if !metadata.is_dir() {
+        files.insert(path.to_path_buf());
+        return Ok(());
+    }
+    for entry in fs::read_dir(path)? {
+        collect_lower_files(&entry?.path(), files)?;
+    }
+    Ok(())
+}
+
+fn same_file_contents(left: &Path, right: &Path) -> io::Result<bool> {
+    let left_metadata = fs::symlink_metadata(left)?;
+    let right_metadata = fs::symlink_metadata(right)?;
+    if left_metadata.file_type().is_symlink() || right_metadata.file_type().is_symlink() {
+        return Ok(left_metadata.file_type().is_symlink()
+            && right_metadata.file_type().is_symlink()
+            && fs::read_link(left)? == fs::read_link(right)?);
+    }
+    if left_metadata.len() != right_metadata.len()
+        || file_mode(&left_metadata) != file_mode(&right_metadata)
+    {
+        return Ok(false);
+    }
+    Ok(fs::read(left)? == fs::read(right)?)
+}
+
+fn copy_into_place(source: &Path, destination: &Path) -> io::Result<()> {
+    if let Some(parent) = destination.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    if let Ok(metadata) = fs::symlink_metadata(destination) {
+        if metadata.is_dir() {
+            fs::remove_dir_all(destination)?;
+        } else if metadata.file_type().is_symlink() {
+            fs::remove_file(destination)?;
+        }
+    }
+    if fs::symlink_metadata(source)?.file_type().is_symlink() {
+        #[cfg(unix)]
+        return std::os::unix::fs::symlink(fs::read_link(source)?, destination);
+        #[cfg(not(unix))]
+        return Err(io::Error::other(format!(
+            "cannot copy symlink {}",
+            source.display()
+        )));
+    }
+    fs::copy(source, destination).map(|_| ())
+}
+
+fn make_dirs_removable(dir: &Path) -> io::Result<()> {
+    let Ok(metadata) = fs::symlink_metadata(dir) else {
+        return Ok(());
+    };
+    if !metadata.is_dir() {
+        return Ok(());
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(dir, fs::Permissions::from_mode(0o700))?;
+    }
+    for entry in fs::read_dir(dir)? {
+        make_dirs_removable(&entry?.path())?;
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn file_mode(metadata: &fs::Metadata) -> u32 {
+    use std::os::unix::fs::PermissionsExt;
+    metadata.permissions().mode() & 0o777
+}
+
+#[cfg(not(unix))]
+fn file_mode(_metadata: &fs::Metadata) -> u32 {
+    0
+}
+
+#[cfg(unix)]
+fn is_whiteout(metadata: &fs::Metadata) -> bool {
+    use std::os::unix::fs::FileTypeExt;
+    use std::os::unix::fs::MetadataExt;
+    metadata.file_type().is_char_device() && metadata.rdev() == 0
+}
+
+#[cfg(not(unix))]
+fn is_whiteout(_metadata: &fs::Metadata) -> bool {
+    false
+}
+
+#[cfg(target_os = "linux")]
+fn is_opaque_dir(path: &Path) -> bool {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let (Ok(path), Ok(name)) = (
+        CString::new(path.as_os_str().as_bytes()),
+        CString::new(OPAQUE_XATTR),
+    ) else {
+        return false;
+    };
+    let mut value = [0u8; 1];
+    // SAFETY: both strings are NUL-terminated and `value` outlives the call.
+    let len = unsafe {
+        libc::lgetxattr(
+            path.as_ptr(),
+            name.as_ptr(),
+            value.as_mut_ptr().cast(),
+            value.len(),
+        )
+    };
+    len == 1 && value[0] == b'y'
+}
+
+#[cfg(not(target_os = "linux"))]
+fn is_opaque_dir(_path: &Path) -> bool {
+    false
+}
+
+#[cfg(test)]
+#[path = "overlay_tests.rs"]
+mod tests;
@@ -0,0 +1,151 @@
+use super::*;
+use pretty_assertions::assert_eq;
+use tempfile::TempDir;
+
+struct OverlayFixture {
+    _root: TempDir,
+    overlay: OverlayWorkspace,
+}
+
+impl OverlayFixture {
+    fn new() -> Self {
+        let root = TempDir::new().expect("tempdir");
+        let lower_dir =
+            AbsolutePathBuf::from_absolute_path(root.path().join("workspace")).expect("absolute");
+        let state_dir =
+            AbsolutePathBuf::from_absolute_path(root.path().join("overlay")).expect("absolute");
+        fs::create_dir_all(&lower_dir).expect("create lower dir");
+        let overlay = OverlayWorkspace::new(lower_dir, &state_dir);
+        overlay.create_dirs().expect("create overlay dirs");
+        Self {
+            _root: root,
+            overlay,
+        }
+    }
+
+    fn write_lower(&self, relative: &str, contents: &str) {
+        write_file(
+            &self.overlay.lower_dir.join(relative).into_path_buf(),
+            contents,
+        );
+    }
+
+    fn write_upper(&self, relative: &str, contents: &str) {
+        write_file(
+            &self.overlay.upper_dir.join(relative).into_path_buf(),
+            contents,
+        );
+    }
+
+    fn lower_path(&self, relative: &str) -> PathBuf {
+        self.overlay.lower_dir.join(relative).into_path_buf()
+    }
+}
+
+fn write_file(path: &Path, contents: &str) {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).expect("create parent");
+    }
+    fs::write(path, contents).expect("write file");
+}
+
+#[test]
+fn changes_skip_unchanged_copy_ups() {
+    let fixture = OverlayFixture::new();
+    fixture.write_lower("a.txt", "one\n");
+    fixture.write_lower("b.txt", "same\n");
+    fixture.write_upper("a.txt", "two\n");
+    fixture.write_upper("b.txt", "same\n");
+    fixture.write_upper("new/c.txt", "new\n");
+
+    assert_eq!(
+        fixture.overlay.changes().expect("changes"),
+        vec![
+            OverlayChange {
+                path: fixture.lower_path("a.txt"),
+                kind: OverlayChangeKind::Modified,
+            },
+            OverlayChange {
+                path: fixture.lower_path("new/c.txt"),
+                kind: OverlayChangeKind::Added,
+            },
+        ]
+    );
+}
+
+#[test]
+fn file_replacing_a_directory_hides_its_lower_files() {
+    let fixture = OverlayFixture::new();
+    fixture.write_lower("dir/inner.txt", "inner\n");
+    fixture.write_upper("dir", "now a file\n");
+
+    assert_eq!(
+        fixture
+            .overlay
+            .resolve(&fixture.lower_path("dir/inner.txt")),
+        OverlayEntry::Removed
+    );
+    let changes = fixture.overlay.changes().expect("changes");
+    assert_eq!(
+        changes,
+        vec![
+            OverlayChange {
+                path: fixture.lower_path("dir"),
+                kind: OverlayChangeKind::Added,
+            },
+            OverlayChange {
+                path: fixture.lower_path("dir/inner.txt"),
+                kind: OverlayChangeKind::Deleted,
+            },
+        ]
+    );
+
+    fixture.overlay.commit(&changes).expect("commit");
+    assert_eq!(
+        fs::read_to_string(fixture.lower_path("dir")).expect("read committed file"),
+        "now a file\n"
+    );
+}
+
+#[test]
+fn commit_copies_changes_and_discard_removes_overlay_dirs() {
+    let fixture = OverlayFixture::new();
+    fixture.write_lower("src/lib.rs", "fn old() {}\n");
+    fixture.write_upper("src/lib.rs", "fn new() {}\n");
+    fixture.write_upper("src/added.rs", "fn added() {}\n");
+
+    let changes = fixture.overlay.changes().expect("changes");
+    fixture.overlay.commit(&changes).expect("commit");
+    fixture.overlay.discard().expect("discard");
+
+    assert_eq!(
+        fs::read_to_string(fixture.lower_path("src/lib.rs")).expect("read lib.rs"),
+        "fn new() {}\n"
+    );
+    assert_eq!(
+        fs::read_to_string(fixture.lower_path("src/added.rs")).expect("read added.rs"),
+        "fn added() {}\n"
+    );
+    assert!(!fixture.overlay.upper_dir.as_path().exists());
+    assert!(!fixture.overlay.work_dir.as_path().exists());
+}
+
+#[test]
+fn resolve_reads_untouched_and_outside_paths_from_lower() {
+    let fixture = OverlayFixture::new();
+    fixture.write_lower("kept.txt", "kept\n");
+    fixture.write_upper("edited.txt", "edited\n");
+
+    assert_eq!(
+        fixture.overlay.resolve(&fixture.lower_path("kept.txt")),
+        OverlayEntry::Lower
+    );
+    assert_eq!(
+        fixture.overlay.resolve(&fixture.lower_path("edited.txt")),
+        OverlayEntry::Upper(fixture.overlay.upper_dir.join("edited.txt").into_path_buf())
+    );
+    assert_eq!(
+        fixture.overlay.resolve(Path::new("/outside/workspace.txt")),
+        OverlayEntry::Lower
+    );
+}
@@ -0,0 +1,566 @@
+//! Secrets backend that delegates to an external helper executable.
+//!
+//! Each operation runs the helper once, in the spirit of git credential
+//! helpers. Codex writes a single JSON request line to the helper's stdin and
+//! reads a single JSON response from its stdout:
+//!
+//! ```text
+//! -> {"action":"get","scope":"global","name":"GITHUB_TOKEN"}
+//! <- {"value":"ghp_..."}
+//! -> {"action":"list","scope":"environment","environment_id":"my-repo"}
+//! <- {"secrets":[{"scope":"environment","environment_id":"my-repo","name":"NPM_TOKEN"}]}
+//! ```
+//!
+//! `set` carries a `value` and expects `{}`; `delete` expects `{"deleted":bool}`;
+//! `list` without a scope lists every scope. A helper reports failure with
+//! `{"error":"..."}` or a non-zero exit status.
+
+use std::collections::HashMap;
+use std::io;
+use std::io::Read;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::Child;
+use std::process::Command;
+use std::process::ExitStatus;
+use std::process::Stdio;
+use std::sync::Mutex;
+use std::sync::PoisonError;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+use std::time::Instant;
+
+use anyhow::Context;
+use anyhow::Result;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::SecretListEntry;
+use super::SecretName;
+use super::SecretScope;
+use super::SecretsBackend;
+
+/// How long a helper invocation may run before it is killed.
+pub const DEFAULT_COMMAND_TIMEOUT: Duration = Duration::from_secs(10);
+
+const WAIT_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Settings for [`CommandSecretsBackend`], read from the `[secrets]` table.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct CommandBackendConfig {
+    /// Helper executable. Bare names are resolved through `PATH`.
+    pub program: PathBuf,
+    /// Extra arguments passed to the helper before it reads the request.
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Milliseconds a single helper invocation may take. Defaults to 10000.
+    pub timeout_ms: Option<u64>,
+    /// Seconds a fetched value is reused before asking the helper again.
+    /// Defaults to `0`, which asks the helper on every lookup.
+    pub cache_ttl_secs: Option<u64>,
+}
+
+impl CommandBackendConfig {
+    pub fn timeout(&self) -> Duration {
+        self.timeout_ms
+            .map_or(DEFAULT_COMMAND_TIMEOUT, Duration::from_millis)
+    }
+
+    pub fn cache_ttl(&self) -> Duration {
+        Duration::from_secs(self.cache_ttl_secs.unwrap_or(0))
+    }
+}
+
+#[derive(Debug)]
+struct CachedValue {
+    value: Option<String>,
+    fetched_at: Instant,
+}
+
+/// Backend that runs a configured helper for every operation. Successful
+/// lookups are cached for `cache_ttl`; `set` and `delete` update the cache so a
+/// write is visible to the next read without another round trip.
+#[derive(Debug)]
+pub struct CommandSecretsBackend {
+    config: CommandBackendConfig,
+    cache: Mutex<HashMap<String, CachedValue>>,
+}
+
+impl CommandSecretsBackend {
+    pub fn new(config: CommandBackendConfig) -> Self {
+        Self {
+            config,
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn set(&self, scope: &SecretScope, name: &SecretName, value: &str) -> Result<()> {
+        anyhow::ensure!(!value.is_empty(), "secret value must not be empty");
+        self.call(&HelperRequest::new(HelperAction::Set, Some(scope), Some(name)).value(value))?;
+        self.store_cached(scope, name, Some(value.to_string()));
+        Ok(())
+    }
+
+    pub fn get(&self, scope: &SecretScope, name: &SecretName) -> Result<Option<String>> {
+        if let Some(value) = self.cached(scope, name) {
+            return Ok(value);
+        }
+        let response = self.call(&HelperRequest::new(
+            HelperAction::Get,
+            Some(scope),
+            Some(name),
+        ))?;
+        self.store_cached(scope, name, response.value.clone());
+        Ok(response.value)
+    }
+
+    pub fn delete(&self, scope: &SecretScope, name: &SecretName) -> Result<bool> {
+        let response = self.call(&HelperRequest::new(
+            HelperAction::Delete,
+            Some(scope),
+            Some(name),
+        ))?;
+        self.store_cached(scope, name, /*value*/ None);
+        Ok(response.deleted.unwrap_or(false))
+    }
+
+    pub fn list(&self, scope_filter: Option<&SecretScope>) -> Result<Vec<SecretListEntry>> {
+        let response = self.call(&HelperRequest::new(
+            HelperAction::List,
+            scope_filter,
+            /*name*/ None,
+        ))?;
+        response
+            .secrets
+            .into_iter()
+            .map(WireEntry::into_entry)
+            .filter(|entry| match (entry, scope_filter) {
+                (Ok(entry), Some(scope)) => &entry.scope == scope,
+                _ => true,
+            })
+            .collect()
+    }
+
+    fn cached(&self, scope: &SecretScope, name: &SecretName) -> Option<Option<String>> {
+        let ttl = self.config.cache_ttl();
+        if ttl.is_zero() {
+            return None;
+        }
+        let cache = self.cache.lock().unwrap_or_else(PoisonError::into_inner);
+        cache
+            .get(&scope.canonical_key(name))
+            .filter(|cached| cached.fetched_at.elapsed() < ttl)
+            .map(|cached| cached.value.clone())
+    }
+
+    fn store_cached(&self, scope: &SecretScope, name: &SecretName, value: Option<String>) {
+        if self.config.cache_ttl().is_zero() {
+            return;
+        }
+        let mut cache = self.cache.lock().unwrap_or_else(PoisonError::into_inner);
+        cache.insert(
+            scope.canonical_key(name),
+            CachedValue {
+                value,
+                fetched_at: Instant::now(),
+            },
+        );
+    }
+
+    fn call(&self, request: &HelperRequest<'_>) -> Result<HelperResponse> {
+        let program = self.config.program.display();
+        let action = request.action.as_str();
+        let mut payload = serde_json::to_vec(request)?;
+        payload.push(b'\n');
+
+        let mut child = Command::new(&self.config.program)
+            .args(&self.config.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .with_context(|| format!("failed to start secrets helper `{program}`"))?;
+        let stdout = read_in_background(child.stdout.take());
+        let stderr = read_in_background(child.stderr.take());
+        if let Some(mut stdin) = child.stdin.take() {
+            // A helper may exit without reading its input; the exit status and
+            // response below report that case.
+            let _ = stdin.write_all(&payload);
+        }
+
+        let timeout = self.config.timeout();
+        let deadline = Instant::now() + timeout;
+        let status = wait_until(&mut child, deadline)?.with_context(|| {
+            format!(
+                "secrets helper `{program}` timed out after {}ms during `{action}`",
+                timeout.as_millis()
+            )
+        })?;
+        // A background process started by the helper can keep its pipes open
+        // after it exits, so reading stops at the deadline rather than at EOF.
+        let read_deadline = deadline.max(Instant::now() + WAIT_POLL_INTERVAL);
+
+        if !status.success() {
+            let stderr = collect_output(&stderr, read_deadline);
+            let stderr = String::from_utf8_lossy(&stderr);
+            anyhow::bail!(
+                "secrets helper `{program}` failed during `{action}` ({status}): {}",
+                stderr.trim()
+            );
+        }
+        let stdout = collect_output(&stdout, read_deadline);
+        let response: HelperResponse = serde_json::from_slice(&stdout).with_context(|| {
+            format!("secrets helper `{program}` returned an invalid response to `{action}`")
+        })?;
+        if let Some(error) = response.error {
+            anyhow::bail!("secrets helper `{program}` rejected `{action}`: {error}");
+        }
+        Ok(response)
+    }
+}
+
+impl SecretsBackend for CommandSecretsBackend {
+    fn set(&self, scope: &SecretScope, name: &SecretName, value: &str) -> Result<()> {
+        CommandSecretsBackend::set(self, scope, name, value)
+    }
+
+    fn get(&self, scope: &SecretScope, name: &SecretName) -> Result<Option<String>> {
+        CommandSecretsBackend::get(self, scope, name)
+    }
+
+    fn delete(&self, scope: &SecretScope, name: &SecretName) -> Result<bool> {
+        CommandSecretsBackend::delete(self, scope, name)
+    }
+
+    fn list(&self, scope_filter: Option<&SecretScope>) -> Result<Vec<SecretListEntry>> {
+        CommandSecretsBackend::list(self, scope_filter)
+    }
+}
+
+/// Reads `pipe` on a helper thread, forwarding chunks as they arrive. The
+/// channel disconnects at end of file.
+fn read_in_background(pipe: Option<impl Read + Send + 'static>) -> mpsc::Receiver<Vec<u8>> {
+    let (tx, rx) = mpsc::channel();
+    if let Some(mut pipe) = pipe {
+        thread::spawn(move || {
+            let mut buf = [0u8; 8_192];
+            loop {
+                match pipe.read(&mut buf) {
+                    Ok(0) => break,
+                    Ok(n) => {
+                        if tx.send(buf[..n].to_vec()).is_err() {
+                            break;
+                        }
+                    }
+                    Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                    Err(_) => break,
+                }
+            }
+        });
+    }
+    rx
+}
+
+/// Collects the chunks read from a pipe until it reaches end of file or
+/// `deadline` passes, whichever comes first.
+fn collect_output(output: &mpsc::Receiver<Vec<u8>>, deadline: Instant) -> Vec<u8> {
+    let mut collected = Vec::new();
+    loop {
+        let chunk = match deadline.checked_duration_since(Instant::now()) {
+            Some(remaining) => output.recv_timeout(remaining).ok(),
+            None => output.try_recv().ok(),
+        };
+        match chunk {
+            Some(chunk) => collected.extend_from_slice(&chunk),
+            None => return collected,
+        }
+    }
+}
+
+/// Waits for `child`, killing it once `deadline` passes. Returns `Ok(None)` on
+/// timeout.
+fn wait_until(child: &mut Child, deadline: Instant) -> Result<Option<ExitStatus>> {
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(Some(status));
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Ok(None);
+        }
+        thread::sleep(WAIT_POLL_INTERVAL);
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum HelperAction {
+    Get,
+    Set,
+    Delete,
+    List,
+}
+
+impl HelperAction {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Get => "get",
+            Self::Set => "set",
+            Self::Delete => "delete",
+            Self::List => "list",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "scope", rename_all = "lowercase")]
+enum WireScope {
+    Global,
+    Environment { environment_id: String },
+}
+
+impl From<&SecretScope> for WireScope {
+    fn from(scope: &SecretScope) -> Self {
+        match scope {
+            SecretScope::Global => Self::Global,
+            SecretScope::Environment(environment_id) => Self::Environment {
+                environment_id: environment_id.clone(),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct HelperRequest<'a> {
+    action: HelperAction,
+    #[serde(flatten)]
+    scope: Option<WireScope>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    value: Option<&'a str>,
+}
+
+impl<'a> HelperRequest<'a> {
+    fn new(
+        action: HelperAction,
+        scope: Option<&SecretScope>,
+        name: Option<&'a SecretName>,
+    ) -> Self {
+        Self {
+            action,
+            scope: scope.map(WireScope::from),
+            name: name.map(SecretName::as_str),
+            value: None,
+        }
+    }
+
+    fn value(mut self, value: &'a str) -> Self {
+        self.value = Some(value);
+        self
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct HelperResponse {
+    value: Option<String>,
+    deleted: Option<bool>,
+    #[serde(default)]
+    secrets: Vec<WireEntry>,
+    error: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WireEntry {
+    #[serde(flatten)]
+    scope: WireScope,
+    name: String,
+}
+
+impl WireEntry {
+    fn into_entry(self) -> Result<SecretListEntry> {
+        let scope = match self.scope {
+            WireScope::Global => SecretScope::Global,
+            WireScope::Environment { environment_id } => SecretScope::environment(environment_id)?,
+        };
+        let name = SecretName::new(&self.name)
+            .with_context(|| format!("secrets helper listed an invalid name `{}`", self.name))?;
+        Ok(SecretListEntry { scope, name })
+    }
+}
+
+#[cfg(test)]
+#[cfg(unix)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+    use std::path::Path;
+
+    /// Writes a stub helper that appends each request to `requests.jsonl` and
+    /// replies with the contents of `response.json`, both next to the script.
+    fn write_stub_helper(dir: &Path, response: &str) -> PathBuf {
+        let script = dir.join("helper.sh");
+        fs::write(
+            &script,
+            "#!/bin/sh\nset -e\nhere=$(dirname \"$0\")\ncat >> \"$here/requests.jsonl\"\ncat \"$here/response.json\"\n",
+        )
+        .expect("write stub helper");
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).expect("chmod stub");
+        set_stub_response(dir, response);
+        script
+    }
+
+    fn set_stub_response(dir: &Path, response: &str) {
+        fs::write(dir.join("response.json"), response).expect("write stub response");
+    }
+
+    fn recorded_requests(dir: &Path) -> Vec<serde_json::Value> {
+        fs::read_to_string(dir.join("requests.jsonl"))
+            .unwrap_or_default()
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("request is JSON"))
+            .collect()
+    }
+
+    fn backend(program: PathBuf, cache_ttl_secs: Option<u64>) -> CommandSecretsBackend {
+        CommandSecretsBackend::new(CommandBackendConfig {
+            program,
+            args: Vec::new(),
+            timeout_ms: None,
+            cache_ttl_secs,
+        })
+    }
+
+    #[test]
+    fn round_trips_requests_through_the_helper() -> Result<()> {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let helper = write_stub_helper(dir.path(), r#"{"value":"s3cr3t"}"#);
+        let backend = backend(helper, /*cache_ttl_secs*/ None);
+        let scope = SecretScope::environment("my-repo")?;
+        let name = SecretName::new("NPM_TOKEN")?;
+
+        assert_eq!(backend.get(&scope, &name)?, Some("s3cr3t".to_string()));
+
+        set_stub_response(dir.path(), "{}");
+        backend.set(&SecretScope::Global, &name, "value-1")?;
+
+        set_stub_response(
+            dir.path(),
+            r#"{"secrets":[{"scope":"global","name":"NPM_TOKEN"},{"scope":"environment","environment_id":"my-repo","name":"NPM_TOKEN"}]}"#,
+        );
+        assert_eq!(
+            backend.list(Some(&scope))?,
+            vec![SecretListEntry {
+                scope: scope.clone(),
+                name,
+            }]
+        );
+
+        assert_eq!(
+            recorded_requests(dir.path()),
+            vec![
+                serde_json::json!({
+                    "action": "get",
+                    "scope": "environment",
+                    "environment_id": "my-repo",
+                    "name": "NPM_TOKEN",
+                }),
+                serde_json::json!({
+                    "action": "set",
+                    "scope": "global",
+                    "name": "NPM_TOKEN",
+                    "value": "value-1",
+                }),
+                serde_json::json!({
+                    "action": "list",
+                    "scope": "environment",
+                    "environment_id": "my-repo",
+                }),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn cache_ttl_reuses_values_until_a_write() -> Result<()> {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let helper = write_stub_helper(dir.path(), r#"{"value":"first"}"#);
+        let backend = backend(helper, /*cache_ttl_secs*/ Some(60));
+        let name = SecretName::new("API_KEY")?;
+
+        assert_eq!(
+            backend.get(&SecretScope::Global, &name)?,
+            Some("first".to_string())
+        );
+        set_stub_response(dir.path(), r#"{"value":"second"}"#);
+        assert_eq!(
+            backend.get(&SecretScope::Global, &name)?,
+            Some("first".to_string())
+        );
+        assert_eq!(recorded_requests(dir.path()).len(), 1);
+
+        set_stub_response(dir.path(), r#"{"deleted":true}"#);
+        assert!(backend.delete(&SecretScope::Global, &name)?);
+        assert_eq!(backend.get(&SecretScope::Global, &name)?, None);
+        assert_eq!(recorded_requests(dir.path()).len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn helper_errors_and_timeouts_are_reported() -> Result<()> {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let helper = write_stub_helper(dir.path(), r#"{"error":"vault is sealed"}"#);
+        let name = SecretName::new("API_KEY")?;
+
+        let err = backend(helper, /*cache_ttl_secs*/ None)
+            .get(&SecretScope::Global, &name)
+            .expect_err("helper error should surface");
+        assert!(err.to_string().contains("vault is sealed"), "{err:#}");
+
+        let slow = CommandSecretsBackend::new(CommandBackendConfig {
+            program: PathBuf::from("sleep"),
+            args: vec!["5".to_string()],
+            timeout_ms: Some(100),
+            cache_ttl_secs: None,
+        });
+        let started = Instant::now();
+        let err = slow
+            .get(&SecretScope::Global, &name)
+            .expect_err("slow helper should time out");
+        assert!(err.to_string().contains("timed out"), "{err:#}");
+        assert!(started.elapsed() < Duration::from_secs(5));
+        Ok(())
+    }
+
+    #[test]
+    fn background_process_holding_the_pipes_does_not_block_the_response() -> Result<()> {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let script = dir.path().join("helper.sh");
+        fs::write(
+            &script,
+            "#!/bin/sh\ncat > /dev/null\nprintf '{\"value\":\"s3cr3t\"}'\nsleep 5 &\n",
+        )
+        .expect("write stub helper");
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).expect("chmod stub");
+        let backend = CommandSecretsBackend::new(CommandBackendConfig {
+            program: script,
+            args: Vec::new(),
+            timeout_ms: Some(500),
+            cache_ttl_secs: None,
+        });
+
+        let started = Instant::now();
+        assert_eq!(
+            backend.get(&SecretScope::Global, &SecretName::new("API_KEY")?)?,
+            Some("s3cr3t".to_string())
+        );
+        assert!(started.elapsed() < Duration::from_secs(5));
+        Ok(())
+    }
+}
@@ -13,11 +13,18 @@ use serde::Serialize;
 use sha2::Digest;
 use sha2::Sha256;
 
+mod command;
 mod local;
 mod sanitizer;
 
+pub use command::CommandBackendConfig;
+pub use command::CommandSecretsBackend;
+pub use command::DEFAULT_COMMAND_TIMEOUT;
 pub use local::LocalSecretsBackend;
+pub use sanitizer::Redactor;
+pub use sanitizer::merge_process_redactor;
 pub use sanitizer::redact_secrets;
+pub use sanitizer::secret_env_values;
 
 const KEYRING_SERVICE: &str = "codex";
 
@@ -79,11 +86,17 @@ pub struct SecretListEntry {
     pub name: SecretName,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default)]
-#[serde(rename_all = "lowercase")]
+/// Where secrets are stored. Configured by the `[secrets]` table, for example
+/// `backend = "command"` with the [`CommandBackendConfig`] fields alongside.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default)]
+#[serde(tag = "backend", rename_all = "lowercase")]
 pub enum SecretsBackendKind {
+    /// Encrypted file under `CODEX_HOME`, keyed from the OS keyring.
     #[default]
     Local,
+    /// External helper executable speaking the JSON protocol described in
+    /// [`CommandSecretsBackend`].
+    Command(CommandBackendConfig),
 }
 
 pub trait SecretsBackend: Send + Sync {
@@ -91,6 +104,17 @@ pub trait SecretsBackend: Send + Sync {
     fn get(&self, scope: &SecretScope, name: &SecretName) -> Result<Option<String>>;
     fn delete(&self, scope: &SecretScope, name: &SecretName) -> Result<bool>;
     fn list(&self, scope_filter: Option<&SecretScope>) -> Result<Vec<SecretListEntry>>;
+
+    /// Returns every stored secret value across all scopes.
+    fn values(&self) -> Result<Vec<String>> {
+        let mut values = Vec::new();
+        for entry in self.list(/*scope_filter*/ None)? {
+            if let Some(value) = self.get(&entry.scope, &entry.name)? {
+                values.push(value);
+            }
+        }
+        Ok(values)
+    }
 }
 
 #[derive(Clone)]
@@ -105,6 +129,7 @@ impl SecretsManager {
                 let keyring_store: Arc<dyn KeyringStore> = Arc::new(DefaultKeyringStore);
                 Arc::new(LocalSecretsBackend::new(codex_home, keyring_store))
             }
+            SecretsBackendKind::Command(config) => Arc::new(CommandSecretsBackend::new(config)),
         };
         Self { backend }
     }
@@ -118,6 +143,7 @@ impl SecretsManager {
             SecretsBackendKind::Local => {
                 Arc::new(LocalSecretsBackend::new(codex_home, keyring_store))
             }
+            SecretsBackendKind::Command(config) => Arc::new(CommandSecretsBackend::new(config)),
         };
         Self { backend }
     }
@@ -137,6 +163,11 @@ impl SecretsManager {
     pub fn list(&self, scope_filter: Option<&SecretScope>) -> Result<Vec<SecretListEntry>> {
         self.backend.list(scope_filter)
     }
+
+    /// Returns every stored secret value, for scrubbing them from output.
+    pub fn values(&self) -> Result<Vec<String>> {
+        self.backend.values()
+    }
 }
 
 pub fn environment_id_from_cwd(cwd: &Path) -> String {
@@ -204,6 +235,30 @@ mod tests {
         assert_eq!(env_id, format!("cwd-{short}"));
     }
 
+    #[test]
+    fn backend_kind_reads_command_settings() -> Result<()> {
+        let kind: SecretsBackendKind = serde_json::from_value(serde_json::json!({
+            "backend": "command",
+            "program": "vault-helper",
+            "args": ["--mount", "codex"],
+            "cache_ttl_secs": 30,
+        }))?;
+        assert_eq!(
+            kind,
+            SecretsBackendKind::Command(CommandBackendConfig {
+                program: PathBuf::from("vault-helper"),
+                args: vec!["--mount".to_string(), "codex".to_string()],
+                timeout_ms: None,
+                cache_ttl_secs: Some(30),
+            })
+        );
+        assert_eq!(
+            serde_json::from_value::<SecretsBackendKind>(serde_json::json!({"backend": "local"}))?,
+            SecretsBackendKind::Local
+        );
+        Ok(())
+    }
+
     #[test]
     fn manager_round_trips_local_backend() -> Result<()> {
         let codex_home = tempfile::tempdir().expect("tempdir");
@@ -222,6 +277,7 @@ mod tests {
         let listed = manager.list(/*scope_filter*/ None)?;
         assert_eq!(listed.len(), 1);
         assert_eq!(listed[0].name, name);
+        assert_eq!(manager.values()?, vec!["token-1".to_string()]);
 
         assert!(manager.delete(&scope, &name)?);
         assert_eq!(manager.get(&scope, &name)?, None);
@@ -107,6 +107,11 @@ impl LocalSecretsBackend {
         Ok(entries)
     }
 
+    pub fn values(&self) -> Result<Vec<String>> {
+        let file = self.load_file()?;
+        Ok(file.secrets.into_values().collect())
+    }
+
     fn secrets_dir(&self) -> PathBuf {
         self.codex_home.join("secrets")
     }
@@ -196,6 +201,10 @@ impl SecretsBackend for LocalSecretsBackend {
     fn list(&self, scope_filter: Option<&SecretScope>) -> Result<Vec<SecretListEntry>> {
         LocalSecretsBackend::list(self, scope_filter)
     }
+
+    fn values(&self) -> Result<Vec<String>> {
+        LocalSecretsBackend::values(self)
+    }
 }
 
 fn write_file_atomically(path: &Path, contents: &[u8]) -> Result<()> {
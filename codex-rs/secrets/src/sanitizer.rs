@@ -1,5 +1,18 @@
-use regex::Regex;
+use std::collections::BTreeSet;
+use std::sync::Arc;
 use std::sync::LazyLock;
+use std::sync::RwLock;
+
+use anyhow::Context;
+use anyhow::Result;
+use regex::Captures;
+use regex::Regex;
+
+const REDACTED_SECRET: &str = "[REDACTED_SECRET]";
+/// Known values shorter than this are too likely to appear in unrelated text.
+const MIN_KNOWN_VALUE_LEN: usize = 8;
+/// Shannon entropy, in bits per character, above which a token looks random.
+const MIN_TOKEN_ENTROPY_BITS: f64 = 4.0;
 
 static OPENAI_KEY_REGEX: LazyLock<Regex> = LazyLock::new(|| compile_regex(r"sk-[A-Za-z0-9]{20,}"));
 static AWS_ACCESS_KEY_ID_REGEX: LazyLock<Regex> =
@@ -9,18 +22,184 @@ static BEARER_TOKEN_REGEX: LazyLock<Regex> =
 static SECRET_ASSIGNMENT_REGEX: LazyLock<Regex> = LazyLock::new(|| {
     compile_regex(r#"(?i)\b(api[_-]?key|token|secret|password)\b(\s*[:=]\s*)(["']?)[^\s"']{8,}"#)
 });
+/// Candidates for entropy detection. `/` and `.` are excluded so paths and
+/// dotted identifiers are never considered.
+static HIGH_ENTROPY_CANDIDATE_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| compile_regex(r"[A-Za-z0-9_\-+=]{24,}"));
+
+static PROCESS_REDACTOR: RwLock<Option<Arc<Redactor>>> = RwLock::new(None);
 
 /// Remove secret and keys from a String. This is done on best effort basis following some
-/// well-known REGEX.
+/// well-known REGEX, plus the rules merged in with [`merge_process_redactor`].
 pub fn redact_secrets(input: String) -> String {
-    let redacted = OPENAI_KEY_REGEX.replace_all(&input, "[REDACTED_SECRET]");
-    let redacted = AWS_ACCESS_KEY_ID_REGEX.replace_all(&redacted, "[REDACTED_SECRET]");
+    let redactor = PROCESS_REDACTOR
+        .read()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .clone();
+    match redactor {
+        Some(redactor) => redactor.redact(&input),
+        None => redact_builtin_patterns(&input),
+    }
+}
+
+/// Adds the rules of `redactor` to what [`redact_secrets`] applies for the
+/// rest of the process, so log writers and other callers without a session
+/// pick up configured rules. Rules from earlier calls are kept, so sessions
+/// sharing a process never drop each other's secrets.
+pub fn merge_process_redactor(redactor: &Redactor) {
+    let mut guard = PROCESS_REDACTOR
+        .write()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    let merged = match guard.as_deref() {
+        Some(existing) => existing.merged_with(redactor),
+        None => redactor.clone(),
+    };
+    *guard = Some(Arc::new(merged));
+}
+
+/// Configurable redaction engine. It always applies the built-in patterns and
+/// can additionally scrub exact known values, match user-defined regexes and
+/// flag random-looking tokens.
+#[derive(Debug, Clone, Default)]
+pub struct Redactor {
+    patterns: Vec<Regex>,
+    high_entropy_tokens: bool,
+    known_values: Vec<String>,
+}
+
+impl Redactor {
+    /// Builds a redactor that applies `patterns` on top of the built-in rules.
+    /// Every match of a pattern is replaced as a whole.
+    pub fn new(patterns: &[String]) -> Result<Self> {
+        let patterns = patterns
+            .iter()
+            .map(|pattern| {
+                Regex::new(pattern)
+                    .with_context(|| format!("invalid redaction pattern `{pattern}`"))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self {
+            patterns,
+            ..Self::default()
+        })
+    }
+
+    /// Also redact long tokens that mix upper- and lower-case letters with
+    /// digits and have high Shannon entropy.
+    pub fn with_high_entropy_tokens(mut self, enabled: bool) -> Self {
+        self.high_entropy_tokens = enabled;
+        self
+    }
+
+    /// Scrubs every occurrence of `values` verbatim. Values shorter than eight
+    /// characters are ignored.
+    pub fn with_known_values(mut self, values: impl IntoIterator<Item = String>) -> Self {
+        let values: BTreeSet<String> = self
+            .known_values
+            .drain(..)
+            .chain(values)
+            .filter(|value| value.len() >= MIN_KNOWN_VALUE_LEN)
+            .collect();
+        self.known_values = values.into_iter().collect();
+        // Replace longer values first so a value that contains another one is
+        // scrubbed whole.
+        self.known_values
+            .sort_by_key(|value| std::cmp::Reverse(value.len()));
+        self
+    }
+
+    /// Returns a redactor that scrubs whatever either `self` or `other`
+    /// would scrub.
+    pub fn merged_with(&self, other: &Redactor) -> Self {
+        let mut patterns = self.patterns.clone();
+        for pattern in &other.patterns {
+            if !patterns
+                .iter()
+                .any(|existing| existing.as_str() == pattern.as_str())
+            {
+                patterns.push(pattern.clone());
+            }
+        }
+        Self {
+            patterns,
+            high_entropy_tokens: self.high_entropy_tokens || other.high_entropy_tokens,
+            known_values: Vec::new(),
+        }
+        .with_known_values(self.known_values.iter().chain(&other.known_values).cloned())
+    }
+
+    pub fn redact(&self, input: &str) -> String {
+        let mut redacted = input.to_string();
+        for value in &self.known_values {
+            if redacted.contains(value.as_str()) {
+                redacted = redacted.replace(value.as_str(), REDACTED_SECRET);
+            }
+        }
+        let mut redacted = redact_builtin_patterns(&redacted);
+        for pattern in &self.patterns {
+            redacted = pattern.replace_all(&redacted, REDACTED_SECRET).into_owned();
+        }
+        if self.high_entropy_tokens {
+            redacted = HIGH_ENTROPY_CANDIDATE_REGEX
+                .replace_all(&redacted, |captures: &Captures<'_>| {
+                    let token = &captures[0];
+                    if is_high_entropy_token(token) {
+                        REDACTED_SECRET.to_string()
+                    } else {
+                        token.to_string()
+                    }
+                })
+                .into_owned();
+        }
+        redacted
+    }
+}
+
+/// Returns the values of environment variables whose names end in `_TOKEN`
+/// or `_KEY`, for use with [`Redactor::with_known_values`].
+pub fn secret_env_values(vars: impl IntoIterator<Item = (String, String)>) -> Vec<String> {
+    vars.into_iter()
+        .filter(|(name, _)| {
+            let name = name.to_ascii_uppercase();
+            name.ends_with("_TOKEN") || name.ends_with("_KEY")
+        })
+        .map(|(_, value)| value)
+        .collect()
+}
+
+fn redact_builtin_patterns(input: &str) -> String {
+    let redacted = OPENAI_KEY_REGEX.replace_all(input, REDACTED_SECRET);
+    let redacted = AWS_ACCESS_KEY_ID_REGEX.replace_all(&redacted, REDACTED_SECRET);
     let redacted = BEARER_TOKEN_REGEX.replace_all(&redacted, "Bearer [REDACTED_SECRET]");
     let redacted = SECRET_ASSIGNMENT_REGEX.replace_all(&redacted, "$1$2$3[REDACTED_SECRET]");
 
     redacted.to_string()
 }
 
+fn is_high_entropy_token(token: &str) -> bool {
+    let has_lower = token.bytes().any(|byte| byte.is_ascii_lowercase());
+    let has_upper = token.bytes().any(|byte| byte.is_ascii_uppercase());
+    let has_digit = token.bytes().any(|byte| byte.is_ascii_digit());
+    // Hex digests, UUIDs and identifiers lack at least one of these classes.
+    has_lower && has_upper && has_digit && shannon_entropy(token) >= MIN_TOKEN_ENTROPY_BITS
+}
+
+fn shannon_entropy(token: &str) -> f64 {
+    let mut counts = [0usize; 256];
+    for byte in token.bytes() {
+        counts[usize::from(byte)] += 1;
+    }
+    let len = token.len() as f64;
+    counts
+        .iter()
+        .filter(|count| **count > 0)
+        .map(|count| {
+            let probability = *count as f64 / len;
+            -probability * probability.log2()
+        })
+        .sum()
+}
+
 fn compile_regex(pattern: &str) -> Regex {
     match Regex::new(pattern) {
         Ok(regex) => regex,
@@ -32,10 +211,83 @@ fn compile_regex(pattern: &str) -> Regex {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use pretty_assertions::assert_eq;
 
     #[test]
     fn load_regex() {
         // The goal of this test is just to compile all the regex to prevent the panic
         let _ = redact_secrets("secret".to_string());
+        let _ = Redactor::default()
+            .with_high_entropy_tokens(/*enabled*/ true)
+            .redact("secret");
+    }
+
+    #[test]
+    fn custom_patterns_and_known_values_are_redacted() -> Result<()> {
+        let redactor = Redactor::new(&[r"itk_[a-z0-9]{12}".to_string()])?
+            .with_known_values(["hunter2-but-longer".to_string(), "short".to_string()]);
+
+        assert_eq!(
+            redactor.redact("token itk_abcdef123456 and hunter2-but-longer, short"),
+            "token [REDACTED_SECRET] and [REDACTED_SECRET], short"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn merged_redactor_applies_rules_from_both() -> Result<()> {
+        let first = Redactor::new(&[r"itk_[a-z0-9]{12}".to_string()])?
+            .with_known_values(["first-session-secret".to_string()]);
+        let second = Redactor::new(&[r"itk_[a-z0-9]{12}".to_string(), r"xtk_\d{6}".to_string()])?
+            .with_known_values(["second-session-secret".to_string()])
+            .with_high_entropy_tokens(/*enabled*/ true);
+
+        let merged = first.merged_with(&second);
+
+        assert_eq!(
+            merged.redact(
+                "itk_abcdef123456 xtk_123456 first-session-secret second-session-secret \
+                 Zx8Qm2Lp9Rt4Vb7Nc1Kd6Hs3"
+            ),
+            "[REDACTED_SECRET] [REDACTED_SECRET] [REDACTED_SECRET] [REDACTED_SECRET] \
+             [REDACTED_SECRET]"
+        );
+        assert_eq!(merged.patterns.len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_pattern_names_the_pattern() {
+        let err = Redactor::new(&["(unclosed".to_string()]).expect_err("pattern is invalid");
+        assert_eq!(err.to_string(), "invalid redaction pattern `(unclosed`");
+    }
+
+    #[test]
+    fn high_entropy_tokens_spare_digests_and_identifiers() {
+        let redactor = Redactor::default().with_high_entropy_tokens(/*enabled*/ true);
+        let input = "key Zx8Qm2Lp9Rt4Vb7Nc1Kd6Hs3 sha 4b825dc642cb6eb9a060e54bf8d69288fbee4904 \
+                     id 9f1c2e4a-7b3d-4e5f-8a6b-0c1d2e3f4a5b fn parse_rollout_item_for_persistence";
+
+        assert_eq!(
+            redactor.redact(input),
+            "key [REDACTED_SECRET] sha 4b825dc642cb6eb9a060e54bf8d69288fbee4904 \
+             id 9f1c2e4a-7b3d-4e5f-8a6b-0c1d2e3f4a5b fn parse_rollout_item_for_persistence"
+        );
+    }
+
+    #[test]
+    fn secret_env_values_match_token_and_key_suffixes() {
+        let vars = [
+            ("GITHUB_TOKEN", "ghp-value"),
+            ("internal_api_key", "key-value"),
+            ("PATH", "/usr/bin"),
+            ("TOKEN_FILE", "/tmp/token"),
+        ]
+        .map(|(name, value)| (name.to_string(), value.to_string()));
+
+        assert_eq!(
+            secret_env_values(vars),
+            vec!["ghp-value".to_string(), "key-value".to_string()]
+        );
     }
 }
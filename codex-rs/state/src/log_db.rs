@@ -3,6 +3,8 @@
 //! This module provides a `tracing_subscriber::Layer` that captures events and
 //! inserts them into the dedicated `logs` SQLite database. The writer runs in a
 //! background task and batches inserts to keep logging overhead low.
+//! Messages and feedback bodies pass through `codex_secrets::redact_secrets`
+//! before they are queued, so configured redaction rules also apply here.
 //!
 //! ## Usage
 //!
@@ -23,6 +25,7 @@ use std::time::Duration;
 use std::time::SystemTime;
 use std::time::UNIX_EPOCH;
 
+use codex_secrets::redact_secrets;
 use tokio::sync::mpsc;
 use tokio::sync::oneshot;
 use tracing::Event;
@@ -145,8 +148,8 @@ where
             ts_nanos: now.subsec_nanos() as i64,
             level: metadata.level().as_str().to_string(),
             target: metadata.target().to_string(),
-            message: visitor.message,
-            feedback_log_body: Some(feedback_log_body),
+            message: visitor.message.map(redact_secrets),
+            feedback_log_body: Some(redact_secrets(feedback_log_body)),
             thread_id,
             process_uuid: Some(self.process_uuid.clone()),
             module_path: metadata.module_path().map(ToString::to_string),
@@ -0,0 +1,277 @@
+//! Behavioral suite shared by every [`ThreadStore`] implementation.
+//!
+//! The same suite runs against [`LocalThreadStore`] directly and against a [`RemoteThreadStore`]
+//! talking to a [`RemoteThreadStoreServer`] that wraps a local store, so the remote client,
+//! server, and proto mapping must preserve local behavior end to end.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use codex_protocol::ThreadId;
+use codex_protocol::models::BaseInstructions;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::RolloutItem;
+use codex_protocol::protocol::SessionSource;
+use codex_protocol::protocol::UserMessageEvent;
+use pretty_assertions::assert_eq;
+use tempfile::TempDir;
+use tokio_stream::StreamExt;
+use uuid::Uuid;
+
+use crate::AppendThreadItemsParams;
+use crate::ArchiveThreadParams;
+use crate::CreateThreadParams;
+use crate::ListThreadsParams;
+use crate::LoadThreadHistoryParams;
+use crate::LocalThreadStore;
+use crate::ReadThreadParams;
+use crate::RemoteThreadStore;
+use crate::RemoteThreadStoreServer;
+use crate::SortDirection;
+use crate::ThreadEventPersistenceMode;
+use crate::ThreadMetadataPatch;
+use crate::ThreadSortKey;
+use crate::ThreadStore;
+use crate::ThreadStoreError;
+use crate::UpdateThreadMetadataParams;
+use crate::local::test_support::test_config;
+use crate::local::test_support::write_archived_session_file;
+use crate::local::test_support::write_session_file;
+
+#[tokio::test]
+async fn local_thread_store_passes_conformance_suite() {
+    let home = TempDir::new().expect("temp dir");
+    let store = LocalThreadStore::new(test_config(home.path()));
+
+    run_conformance_suite(&store, home.path()).await;
+}
+
+#[tokio::test]
+async fn remote_thread_store_passes_conformance_suite() {
+    let home = TempDir::new().expect("temp dir");
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind thread store server");
+    let addr = listener.local_addr().expect("thread store server addr");
+    let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel();
+    let server =
+        RemoteThreadStoreServer::new(Arc::new(LocalThreadStore::new(test_config(home.path()))));
+    let server = tokio::spawn(server.serve_with_shutdown(listener, async {
+        let _ = shutdown_rx.await;
+    }));
+
+    let store = RemoteThreadStore::new(format!("http://{addr}"));
+    run_conformance_suite(&store, home.path()).await;
+
+    let _ = shutdown_tx.send(());
+    server
+        .await
+        .expect("join thread store server")
+        .expect("thread store server");
+}
+
+async fn run_conformance_suite(store: &dyn ThreadStore, home: &Path) {
+    let active_uuid = Uuid::from_u128(901);
+    let active_id = ThreadId::from_string(&active_uuid.to_string()).expect("valid thread id");
+    let archived_uuid = Uuid::from_u128(902);
+    let archived_id = ThreadId::from_string(&archived_uuid.to_string()).expect("valid thread id");
+    write_session_file(home, "2025-01-03T12-00-00", active_uuid).expect("session file");
+    write_archived_session_file(home, "2025-01-03T13-00-00", archived_uuid)
+        .expect("archived session file");
+
+    let thread = store
+        .read_thread(ReadThreadParams {
+            thread_id: active_id,
+            include_archived: false,
+            include_history: true,
+        })
+        .await
+        .expect("read active thread");
+    assert_eq!(thread.thread_id, active_id);
+    assert_eq!(thread.preview, "Hello from user");
+    assert_eq!(thread.model_provider, "test-provider");
+    assert_eq!(thread.cli_version, "test_version");
+    assert_eq!(thread.source, SessionSource::Cli);
+    assert_eq!(thread.archived_at, None);
+    assert_eq!(
+        thread.history.expect("history should load").thread_id,
+        active_id
+    );
+
+    let err = store
+        .read_thread(ReadThreadParams {
+            thread_id: archived_id,
+            include_archived: false,
+            include_history: false,
+        })
+        .await
+        .expect_err("active-only read should fail for archived thread");
+    let ThreadStoreError::InvalidRequest { message } = err else {
+        panic!("expected invalid request error");
+    };
+    assert_eq!(
+        message,
+        format!("no rollout found for thread id {archived_id}")
+    );
+
+    assert_eq!(
+        list_thread_ids(store, /*archived*/ false).await,
+        vec![active_id]
+    );
+    assert_eq!(
+        list_thread_ids(store, /*archived*/ true).await,
+        vec![archived_id]
+    );
+
+    let err = store
+        .list_threads(ListThreadsParams {
+            cursor: Some("not-a-cursor".to_string()),
+            ..list_params(/*archived*/ false)
+        })
+        .await
+        .expect_err("invalid cursor should fail");
+    assert!(matches!(err, ThreadStoreError::InvalidRequest { .. }));
+
+    let thread = store
+        .update_thread_metadata(UpdateThreadMetadataParams {
+            thread_id: active_id,
+            patch: ThreadMetadataPatch {
+                name: Some("Renamed thread".to_string()),
+                ..Default::default()
+            },
+            include_archived: false,
+        })
+        .await
+        .expect("rename thread");
+    assert_eq!(thread.name.as_deref(), Some("Renamed thread"));
+
+    store
+        .archive_thread(ArchiveThreadParams {
+            thread_id: active_id,
+        })
+        .await
+        .expect("archive thread");
+    let thread = store
+        .unarchive_thread(ArchiveThreadParams {
+            thread_id: archived_id,
+        })
+        .await
+        .expect("unarchive thread");
+    assert_eq!(thread.thread_id, archived_id);
+    assert_eq!(thread.archived_at, None);
+    assert_eq!(thread.preview, "Archived user message");
+    assert_eq!(
+        list_thread_ids(store, /*archived*/ false).await,
+        vec![archived_id]
+    );
+    assert_eq!(
+        list_thread_ids(store, /*archived*/ true).await,
+        vec![active_id]
+    );
+
+    let live_id = ThreadId::default();
+    store
+        .create_thread(CreateThreadParams {
+            thread_id: live_id,
+            forked_from_id: None,
+            source: SessionSource::Exec,
+            base_instructions: BaseInstructions::default(),
+            dynamic_tools: Vec::new(),
+            event_persistence_mode: ThreadEventPersistenceMode::Limited,
+        })
+        .await
+        .expect("create live thread");
+    store
+        .append_items(AppendThreadItemsParams {
+            thread_id: live_id,
+            items: vec![user_message_item("live write")],
+        })
+        .await
+        .expect("append live item");
+    store
+        .persist_thread(live_id)
+        .await
+        .expect("persist live thread");
+    store
+        .flush_thread(live_id)
+        .await
+        .expect("flush live thread");
+    let history = store
+        .load_history(LoadThreadHistoryParams {
+            thread_id: live_id,
+            include_archived: false,
+        })
+        .await
+        .expect("load live history");
+    assert_eq!(history.thread_id, live_id);
+    assert!(history.items.iter().any(|item| {
+        matches!(
+            item,
+            RolloutItem::EventMsg(EventMsg::UserMessage(event)) if event.message == "live write"
+        )
+    }));
+    let streamed = store
+        .stream_history(LoadThreadHistoryParams {
+            thread_id: live_id,
+            include_archived: false,
+        })
+        .await
+        .expect("stream live history")
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()
+        .expect("streamed history items");
+    assert_eq!(
+        serde_json::to_value(&streamed).expect("serialize streamed history"),
+        serde_json::to_value(&history.items).expect("serialize loaded history")
+    );
+
+    store
+        .shutdown_thread(live_id)
+        .await
+        .expect("shutdown live thread");
+    let err = store
+        .append_items(AppendThreadItemsParams {
+            thread_id: live_id,
+            items: vec![user_message_item("write after shutdown")],
+        })
+        .await
+        .expect_err("shutdown should remove the live thread writer");
+    assert!(matches!(err, ThreadStoreError::ThreadNotFound { thread_id } if thread_id == live_id));
+}
+
+async fn list_thread_ids(store: &dyn ThreadStore, archived: bool) -> Vec<ThreadId> {
+    store
+        .list_threads(list_params(archived))
+        .await
+        .expect("list threads")
+        .items
+        .into_iter()
+        .map(|thread| thread.thread_id)
+        .collect()
+}
+
+fn list_params(archived: bool) -> ListThreadsParams {
+    ListThreadsParams {
+        page_size: 10,
+        cursor: None,
+        sort_key: ThreadSortKey::CreatedAt,
+        sort_direction: SortDirection::Desc,
+        allowed_sources: Vec::new(),
+        model_providers: None,
+        cwd_filters: None,
+        archived,
+        search_term: None,
+        use_state_db_only: false,
+    }
+}
+
+fn user_message_item(message: &str) -> RolloutItem {
+    RolloutItem::EventMsg(EventMsg::UserMessage(UserMessageEvent {
+        message: message.to_string(),
+        images: None,
+        local_images: Vec::new(),
+        text_elements: Vec::new(),
+    }))
+}
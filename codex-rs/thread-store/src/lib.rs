@@ -4,6 +4,8 @@
 //! Implementations are responsible for resolving that id to local rollout files, RPC requests, or
 //! any other backing store.
 
+#[cfg(test)]
+mod conformance_tests;
 mod error;
 mod live_thread;
 mod local;
@@ -17,6 +19,9 @@ pub use live_thread::LiveThread;
 pub use live_thread::LiveThreadInitGuard;
 pub use local::LocalThreadStore;
 pub use remote::RemoteThreadStore;
+pub use remote::RemoteThreadStoreOptions;
+pub use remote::RemoteThreadStoreServer;
+pub use store::RolloutItemStream;
 pub use store::ThreadStore;
 pub use types::AppendThreadItemsParams;
 pub use types::ArchiveThreadParams;
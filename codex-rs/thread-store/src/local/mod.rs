@@ -8,7 +8,7 @@ mod unarchive_thread;
 mod update_thread_metadata;
 
 #[cfg(test)]
-mod test_support;
+pub(crate) mod test_support;
 
 use async_trait::async_trait;
 use codex_protocol::ThreadId;
@@ -29,6 +29,7 @@ use crate::ListThreadsParams;
 use crate::LoadThreadHistoryParams;
 use crate::ReadThreadParams;
 use crate::ResumeThreadParams;
+use crate::RolloutItemStream;
 use crate::StoredThread;
 use crate::StoredThreadHistory;
 use crate::ThreadPage;
@@ -203,6 +204,29 @@ impl ThreadStore for LocalThreadStore {
         })
     }
 
+    async fn stream_history(
+        &self,
+        params: LoadThreadHistoryParams,
+    ) -> ThreadStoreResult<RolloutItemStream> {
+        let rollout_path = match live_writer::rollout_path(self, params.thread_id).await {
+            Ok(rollout_path) => rollout_path,
+            Err(_) => read_thread::read_thread(
+                self,
+                ReadThreadParams {
+                    thread_id: params.thread_id,
+                    include_archived: params.include_archived,
+                    include_history: false,
+                },
+            )
+            .await?
+            .rollout_path
+            .ok_or_else(|| ThreadStoreError::Internal {
+                message: format!("failed to load history for thread {}", params.thread_id),
+            })?,
+        };
+        read_thread::stream_history_items(rollout_path).await
+    }
+
     async fn read_thread(&self, params: ReadThreadParams) -> ThreadStoreResult<StoredThread> {
         read_thread::read_thread(self, params).await
     }
@@ -1,6 +1,7 @@
 use chrono::DateTime;
 use chrono::Utc;
 use codex_protocol::protocol::AskForApproval;
+use codex_protocol::protocol::RolloutLine;
 use codex_protocol::protocol::SandboxPolicy;
 use codex_protocol::protocol::SessionMetaLine;
 use codex_protocol::protocol::SessionSource;
@@ -12,11 +13,13 @@ use codex_rollout::read_session_meta_line;
 use codex_rollout::read_thread_item_from_rollout;
 use codex_state::StateRuntime;
 use codex_state::ThreadMetadata;
+use tokio::io::AsyncBufReadExt;
 
 use super::LocalThreadStore;
 use super::helpers::git_info_from_parts;
 use super::helpers::stored_thread_from_rollout_item;
 use crate::ReadThreadParams;
+use crate::RolloutItemStream;
 use crate::StoredThread;
 use crate::StoredThreadHistory;
 use crate::ThreadStoreError;
@@ -166,6 +169,46 @@ async fn load_history_items(
     Ok(items)
 }
 
+/// Number of parsed items buffered ahead of a slow [`stream_history_items`] consumer.
+const HISTORY_STREAM_BUFFER: usize = 256;
+
+/// Streams the items of the rollout at `path` as they are read. Like
+/// [`RolloutRecorder::load_rollout_items`], lines that do not parse are skipped.
+pub(super) async fn stream_history_items(
+    path: std::path::PathBuf,
+) -> ThreadStoreResult<RolloutItemStream> {
+    let history_error = |path: &std::path::Path, err: std::io::Error| ThreadStoreError::Internal {
+        message: format!("failed to load thread history {}: {err}", path.display()),
+    };
+    let file = tokio::fs::File::open(&path)
+        .await
+        .map_err(|err| history_error(&path, err))?;
+    let (tx, rx) = tokio::sync::mpsc::channel(HISTORY_STREAM_BUFFER);
+    tokio::spawn(async move {
+        let mut lines = tokio::io::BufReader::new(file).lines();
+        loop {
+            let line = match lines.next_line().await {
+                Ok(Some(line)) => line,
+                Ok(None) => break,
+                Err(err) => {
+                    let _ = tx.send(Err(history_error(&path, err))).await;
+                    break;
+                }
+            };
+            if line.trim().is_empty() {
+                continue;
+            }
+            let Ok(rollout_line) = serde_json::from_str::<RolloutLine>(&line) else {
+                continue;
+            };
+            if tx.send(Ok(rollout_line.item)).await.is_err() {
+                break;
+            }
+        }
+    });
+    Ok(Box::pin(tokio_stream::wrappers::ReceiverStream::new(rx)))
+}
+
 async fn read_sqlite_metadata(
     store: &LocalThreadStore,
     thread_id: codex_protocol::ThreadId,
@@ -7,7 +7,7 @@ use codex_rollout::ARCHIVED_SESSIONS_SUBDIR;
 use codex_rollout::RolloutConfig;
 use uuid::Uuid;
 
-pub(super) fn test_config(codex_home: &Path) -> RolloutConfig {
+pub(crate) fn test_config(codex_home: &Path) -> RolloutConfig {
     RolloutConfig {
         codex_home: codex_home.to_path_buf(),
         sqlite_home: codex_home.to_path_buf(),
@@ -17,7 +17,7 @@ pub(super) fn test_config(codex_home: &Path) -> RolloutConfig {
     }
 }
 
-pub(super) fn write_session_file(root: &Path, ts: &str, uuid: Uuid) -> std::io::Result<PathBuf> {
+pub(crate) fn write_session_file(root: &Path, ts: &str, uuid: Uuid) -> std::io::Result<PathBuf> {
     write_session_file_with(
         root,
         root.join("sessions/2025/01/03"),
@@ -28,7 +28,7 @@ pub(super) fn write_session_file(root: &Path, ts: &str, uuid: Uuid) -> std::io::
     )
 }
 
-pub(super) fn write_archived_session_file(
+pub(crate) fn write_archived_session_file(
     root: &Path,
     ts: &str,
     uuid: Uuid,
@@ -43,7 +43,7 @@ pub(super) fn write_archived_session_file(
     )
 }
 
-pub(super) fn write_session_file_with(
+pub(crate) fn write_session_file_with(
     root: &Path,
     day_dir: PathBuf,
     ts: &str,
@@ -62,7 +62,7 @@ pub(super) fn write_session_file_with(
     )
 }
 
-pub(super) fn write_session_file_with_fork(
+pub(crate) fn write_session_file_with_fork(
     root: &Path,
     day_dir: PathBuf,
     ts: &str,
@@ -317,9 +317,10 @@ pub(super) fn stored_thread_from_proto(
     })
 }
 
-#[cfg(test)]
-pub(super) fn stored_thread_to_proto(thread: StoredThread) -> proto::StoredThread {
-    proto::StoredThread {
+pub(super) fn stored_thread_to_proto(
+    thread: StoredThread,
+) -> ThreadStoreResult<proto::StoredThread> {
+    Ok(proto::StoredThread {
         thread_id: thread.thread_id.to_string(),
         forked_from_id: thread.forked_from_id.map(|thread_id| thread_id.to_string()),
         preview: thread.preview,
@@ -341,24 +342,27 @@ pub(super) fn stored_thread_to_proto(thread: StoredThread) -> proto::StoredThrea
         rollout_path: thread
             .rollout_path
             .map(|path| path.to_string_lossy().into_owned()),
-        approval_mode_json: Some(serialize_json(&thread.approval_mode, "approval_mode").unwrap()),
-        sandbox_policy_json: Some(
-            serialize_json(&thread.sandbox_policy, "sandbox_policy").unwrap(),
-        ),
+        approval_mode_json: Some(serialize_json(&thread.approval_mode, "approval_mode")?),
+        sandbox_policy_json: Some(serialize_json(&thread.sandbox_policy, "sandbox_policy")?),
         token_usage_json: thread
             .token_usage
             .as_ref()
-            .map(|usage| serialize_json(usage, "token_usage").unwrap()),
-        history: thread.history.map(stored_thread_history_to_proto),
-    }
+            .map(|usage| serialize_json(usage, "token_usage"))
+            .transpose()?,
+        history: thread
+            .history
+            .map(stored_thread_history_to_proto)
+            .transpose()?,
+    })
 }
 
-#[cfg(test)]
-fn stored_thread_history_to_proto(history: StoredThreadHistory) -> proto::StoredThreadHistory {
-    proto::StoredThreadHistory {
+pub(super) fn stored_thread_history_to_proto(
+    history: StoredThreadHistory,
+) -> ThreadStoreResult<proto::StoredThreadHistory> {
+    Ok(proto::StoredThreadHistory {
         thread_id: history.thread_id.to_string(),
-        items_json: rollout_items_json(&history.items).unwrap(),
-    }
+        items_json: rollout_items_json(&history.items)?,
+    })
 }
 
 fn datetime_from_unix(timestamp: i64) -> ThreadStoreResult<DateTime<Utc>> {
@@ -367,7 +371,9 @@ fn datetime_from_unix(timestamp: i64) -> ThreadStoreResult<DateTime<Utc>> {
     })
 }
 
-fn session_source_from_proto(source: &proto::SessionSource) -> ThreadStoreResult<SessionSource> {
+pub(super) fn session_source_from_proto(
+    source: &proto::SessionSource,
+) -> ThreadStoreResult<SessionSource> {
     let kind = proto::SessionSourceKind::try_from(source.kind).unwrap_or_default();
     Ok(match kind {
         proto::SessionSourceKind::Unknown => SessionSource::Unknown,
@@ -426,7 +432,6 @@ fn git_info_from_proto(info: proto::GitInfo) -> GitInfo {
     }
 }
 
-#[cfg(test)]
 fn git_info_to_proto(info: GitInfo) -> proto::GitInfo {
     proto::GitInfo {
         sha: info.commit_hash.map(|sha| sha.0),
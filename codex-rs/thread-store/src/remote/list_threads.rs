@@ -1,3 +1,4 @@
+use super::Idempotency;
 use super::RemoteThreadStore;
 use super::helpers::proto_session_source;
 use super::helpers::proto_sort_direction;
@@ -44,12 +45,13 @@ pub(super) async fn list_threads(
     };
 
     let response = store
-        .client()
-        .await?
-        .list_threads(request)
+        .call(
+            Idempotency::Idempotent,
+            request,
+            |mut client, request| async move { client.list_threads(request).await },
+        )
         .await
-        .map_err(remote_status_to_error)?
-        .into_inner();
+        .map_err(remote_status_to_error)?;
 
     let items = response
         .threads
@@ -87,6 +89,9 @@ mod tests {
 
     #[tonic::async_trait]
     impl thread_store_server::ThreadStore for TestServer {
+        type LoadHistoryStreamStream =
+            tokio_stream::Empty<Result<proto::StoredThreadHistory, Status>>;
+
         async fn list_threads(
             &self,
             request: Request<proto::ListThreadsRequest>,
@@ -271,7 +276,7 @@ mod tests {
 
         assert_eq!(stored.rollout_path, None);
         assert!(stored.history.is_none());
-        let roundtripped = stored_thread_to_proto(stored);
+        let roundtripped = stored_thread_to_proto(stored).expect("stored thread to proto");
         assert_eq!(roundtripped.thread_id, thread.thread_id);
         assert_eq!(roundtripped.forked_from_id, thread.forked_from_id);
         assert_eq!(roundtripped.source, thread.source);
@@ -1,8 +1,16 @@
 mod helpers;
 mod list_threads;
+mod server;
+
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
 
 use async_trait::async_trait;
 use codex_protocol::ThreadId;
+use tokio::sync::OnceCell;
+use tonic::transport::Channel;
+use tonic::transport::Endpoint;
 
 use crate::AppendThreadItemsParams;
 use crate::ArchiveThreadParams;
@@ -20,32 +28,150 @@ use crate::ThreadStoreResult;
 use crate::UpdateThreadMetadataParams;
 use proto::thread_store_client::ThreadStoreClient;
 
+pub use server::RemoteThreadStoreServer;
+
 #[path = "proto/codex.thread_store.v1.rs"]
 mod proto;
 
+/// Connection, deadline, and retry settings for [`RemoteThreadStore`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RemoteThreadStoreOptions {
+    /// Maximum time to establish a connection to the store.
+    pub connect_timeout: Duration,
+    /// Deadline applied to each RPC attempt and propagated to the server as `grpc-timeout`.
+    pub request_timeout: Duration,
+    /// Additional attempts for idempotent RPCs that fail with a transient status.
+    pub max_retries: u32,
+    /// Delay before the first retry. Each later retry doubles it, up to `max_backoff`.
+    pub initial_backoff: Duration,
+    /// Upper bound on the delay between retries.
+    pub max_backoff: Duration,
+}
+
+impl Default for RemoteThreadStoreOptions {
+    fn default() -> Self {
+        Self {
+            connect_timeout: Duration::from_secs(10),
+            request_timeout: Duration::from_secs(30),
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(2),
+        }
+    }
+}
+
 /// gRPC-backed [`ThreadStore`] implementation for deployments whose durable thread data lives
 /// outside the app-server process.
+///
+/// All clones share one lazily connected HTTP/2 channel, which reconnects on demand after the
+/// connection drops, so concurrent threads multiplex over a single connection.
 #[derive(Clone, Debug)]
 pub struct RemoteThreadStore {
     endpoint: String,
+    options: RemoteThreadStoreOptions,
+    channel: Arc<OnceCell<Channel>>,
+}
+
+/// Whether an RPC can be retried after a transient failure without risking a duplicate write.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Idempotency {
+    Idempotent,
+    NonIdempotent,
 }
 
 impl RemoteThreadStore {
     pub fn new(endpoint: impl Into<String>) -> Self {
+        Self::with_options(endpoint, RemoteThreadStoreOptions::default())
+    }
+
+    pub fn with_options(endpoint: impl Into<String>, options: RemoteThreadStoreOptions) -> Self {
         Self {
             endpoint: endpoint.into(),
+            options,
+            channel: Arc::new(OnceCell::new()),
         }
     }
 
-    async fn client(&self) -> ThreadStoreResult<ThreadStoreClient<tonic::transport::Channel>> {
-        ThreadStoreClient::connect(self.endpoint.clone())
-            .await
-            .map_err(|err| ThreadStoreError::Internal {
-                message: format!("failed to connect to remote thread store: {err}"),
+    async fn client(&self) -> Result<ThreadStoreClient<Channel>, tonic::Status> {
+        let channel = self
+            .channel
+            .get_or_try_init(|| async {
+                let endpoint = Endpoint::from_shared(self.endpoint.clone()).map_err(|err| {
+                    tonic::Status::invalid_argument(format!(
+                        "invalid remote thread store endpoint {}: {err}",
+                        self.endpoint
+                    ))
+                })?;
+                Ok::<_, tonic::Status>(
+                    endpoint
+                        .connect_timeout(self.options.connect_timeout)
+                        .connect_lazy(),
+                )
             })
+            .await?;
+        Ok(ThreadStoreClient::new(channel.clone()))
+    }
+
+    /// Issues `rpc` with a per-attempt deadline, retrying transient failures with exponential
+    /// backoff when the RPC is idempotent.
+    async fn call<Req, Resp, F, Fut>(
+        &self,
+        idempotency: Idempotency,
+        request: Req,
+        rpc: F,
+    ) -> Result<Resp, tonic::Status>
+    where
+        Req: Clone,
+        F: Fn(ThreadStoreClient<Channel>, tonic::Request<Req>) -> Fut,
+        Fut: Future<Output = Result<tonic::Response<Resp>, tonic::Status>>,
+    {
+        let client = self.client().await?;
+        let mut retries = 0;
+        let mut backoff = self.options.initial_backoff;
+        loop {
+            let mut attempt = tonic::Request::new(request.clone());
+            attempt.set_timeout(self.options.request_timeout);
+            // `grpc-timeout` only bounds the server side; enforce the deadline locally too so a
+            // stalled connection surfaces as a retryable `DeadlineExceeded`.
+            let result =
+                tokio::time::timeout(self.options.request_timeout, rpc(client.clone(), attempt))
+                    .await
+                    .unwrap_or_else(|_| Err(self.deadline_exceeded()));
+            match result {
+                Ok(response) => return Ok(response.into_inner()),
+                Err(status)
+                    if idempotency == Idempotency::Idempotent
+                        && retries < self.options.max_retries
+                        && is_retryable(&status) =>
+                {
+                    retries += 1;
+                    tracing::debug!(
+                        "retrying remote thread store request after {backoff:?} (attempt {retries}): {status}"
+                    );
+                    tokio::time::sleep(backoff).await;
+                    backoff = (backoff * 2).min(self.options.max_backoff);
+                }
+                Err(status) => return Err(status),
+            }
+        }
+    }
+
+    fn deadline_exceeded(&self) -> tonic::Status {
+        tonic::Status::deadline_exceeded(format!(
+            "remote thread store request timed out after {:?}",
+            self.options.request_timeout
+        ))
     }
 }
 
+/// Statuses that indicate the request may succeed if sent again.
+fn is_retryable(status: &tonic::Status) -> bool {
+    matches!(
+        status.code(),
+        tonic::Code::Unavailable | tonic::Code::DeadlineExceeded | tonic::Code::ResourceExhausted
+    )
+}
+
 #[async_trait]
 impl ThreadStore for RemoteThreadStore {
     fn as_any(&self) -> &dyn std::any::Any {
@@ -65,11 +191,13 @@ impl ThreadStore for RemoteThreadStore {
             )
             .into(),
         };
-        self.client()
-            .await?
-            .create_thread(request)
-            .await
-            .map_err(|status| helpers::remote_status_to_thread_error(status, thread_id))?;
+        self.call(
+            Idempotency::NonIdempotent,
+            request,
+            |mut client, request| async move { client.create_thread(request).await },
+        )
+        .await
+        .map_err(|status| helpers::remote_status_to_thread_error(status, thread_id))?;
         Ok(())
     }
 
@@ -92,11 +220,13 @@ impl ThreadStore for RemoteThreadStore {
             )
             .into(),
         };
-        self.client()
-            .await?
-            .resume_thread(request)
-            .await
-            .map_err(|status| helpers::remote_status_to_thread_error(status, thread_id))?;
+        self.call(
+            Idempotency::NonIdempotent,
+            request,
+            |mut client, request| async move { client.resume_thread(request).await },
+        )
+        .await
+        .map_err(|status| helpers::remote_status_to_thread_error(status, thread_id))?;
         Ok(())
     }
 
@@ -106,47 +236,57 @@ impl ThreadStore for RemoteThreadStore {
             thread_id: thread_id.to_string(),
             items_json: helpers::rollout_items_json(&params.items)?,
         };
-        self.client()
-            .await?
-            .append_items(request)
-            .await
-            .map_err(|status| helpers::remote_status_to_thread_error(status, thread_id))?;
+        self.call(
+            Idempotency::NonIdempotent,
+            request,
+            |mut client, request| async move { client.append_items(request).await },
+        )
+        .await
+        .map_err(|status| helpers::remote_status_to_thread_error(status, thread_id))?;
         Ok(())
     }
 
     async fn persist_thread(&self, thread_id: ThreadId) -> ThreadStoreResult<()> {
-        self.client()
-            .await?
-            .persist_thread(helpers::proto_thread_id_request(thread_id))
-            .await
-            .map_err(|status| helpers::remote_status_to_thread_error(status, thread_id))?;
+        self.call(
+            Idempotency::Idempotent,
+            helpers::proto_thread_id_request(thread_id),
+            |mut client, request| async move { client.persist_thread(request).await },
+        )
+        .await
+        .map_err(|status| helpers::remote_status_to_thread_error(status, thread_id))?;
         Ok(())
     }
 
     async fn flush_thread(&self, thread_id: ThreadId) -> ThreadStoreResult<()> {
-        self.client()
-            .await?
-            .flush_thread(helpers::proto_thread_id_request(thread_id))
-            .await
-            .map_err(|status| helpers::remote_status_to_thread_error(status, thread_id))?;
+        self.call(
+            Idempotency::Idempotent,
+            helpers::proto_thread_id_request(thread_id),
+            |mut client, request| async move { client.flush_thread(request).await },
+        )
+        .await
+        .map_err(|status| helpers::remote_status_to_thread_error(status, thread_id))?;
         Ok(())
     }
 
     async fn shutdown_thread(&self, thread_id: ThreadId) -> ThreadStoreResult<()> {
-        self.client()
-            .await?
-            .shutdown_thread(helpers::proto_thread_id_request(thread_id))
-            .await
-            .map_err(|status| helpers::remote_status_to_thread_error(status, thread_id))?;
+        self.call(
+            Idempotency::NonIdempotent,
+            helpers::proto_thread_id_request(thread_id),
+            |mut client, request| async move { client.shutdown_thread(request).await },
+        )
+        .await
+        .map_err(|status| helpers::remote_status_to_thread_error(status, thread_id))?;
         Ok(())
     }
 
     async fn discard_thread(&self, thread_id: ThreadId) -> ThreadStoreResult<()> {
-        self.client()
-            .await?
-            .discard_thread(helpers::proto_thread_id_request(thread_id))
-            .await
-            .map_err(|status| helpers::remote_status_to_thread_error(status, thread_id))?;
+        self.call(
+            Idempotency::NonIdempotent,
+            helpers::proto_thread_id_request(thread_id),
+            |mut client, request| async move { client.discard_thread(request).await },
+        )
+        .await
+        .map_err(|status| helpers::remote_status_to_thread_error(status, thread_id))?;
         Ok(())
     }
 
@@ -155,32 +295,74 @@ impl ThreadStore for RemoteThreadStore {
         params: LoadThreadHistoryParams,
     ) -> ThreadStoreResult<StoredThreadHistory> {
         let thread_id = params.thread_id;
-        let response = self
-            .client()
-            .await?
-            .load_history(proto::LoadThreadHistoryRequest {
-                thread_id: thread_id.to_string(),
-                include_archived: params.include_archived,
-            })
+        let map_status =
+            |status: tonic::Status| helpers::remote_status_to_thread_error(status, thread_id);
+        let request = proto::LoadThreadHistoryRequest {
+            thread_id: thread_id.to_string(),
+            include_archived: params.include_archived,
+        };
+        let stream = self
+            .call(
+                Idempotency::Idempotent,
+                request.clone(),
+                |mut client, request| async move { client.load_history_stream(request).await },
+            )
+            .await;
+        let mut stream = match stream {
+            Ok(stream) => stream,
+            // Servers that predate `LoadHistoryStream` only implement the unary RPC.
+            Err(status) if status.code() == tonic::Code::Unimplemented => {
+                let response = self
+                    .call(
+                        Idempotency::Idempotent,
+                        request,
+                        |mut client, request| async move { client.load_history(request).await },
+                    )
+                    .await
+                    .map_err(map_status)?;
+                return helpers::stored_thread_history_from_proto(response);
+            }
+            Err(status) => return Err(map_status(status)),
+        };
+
+        let mut history = proto::StoredThreadHistory {
+            thread_id: request.thread_id,
+            items_json: Vec::new(),
+        };
+        // The deadline in `call` only covers opening the stream; bound every read too so a server
+        // that stalls mid-stream cannot hang the load.
+        while let Some(chunk) = tokio::time::timeout(self.options.request_timeout, stream.message())
             .await
-            .map_err(|status| helpers::remote_status_to_thread_error(status, thread_id))?
-            .into_inner();
-        helpers::stored_thread_history_from_proto(response)
+            .unwrap_or_else(|_| Err(self.deadline_exceeded()))
+            .map_err(map_status)?
+        {
+            if chunk.thread_id != history.thread_id {
+                return Err(ThreadStoreError::Internal {
+                    message: format!(
+                        "remote thread store streamed history for thread {} while loading {thread_id}",
+                        chunk.thread_id
+                    ),
+                });
+            }
+            history.items_json.extend(chunk.items_json);
+        }
+        helpers::stored_thread_history_from_proto(history)
     }
 
     async fn read_thread(&self, params: ReadThreadParams) -> ThreadStoreResult<StoredThread> {
         let thread_id = params.thread_id;
         let response = self
-            .client()
-            .await?
-            .read_thread(proto::ReadThreadRequest {
-                thread_id: thread_id.to_string(),
-                include_archived: params.include_archived,
-                include_history: params.include_history,
-            })
+            .call(
+                Idempotency::Idempotent,
+                proto::ReadThreadRequest {
+                    thread_id: thread_id.to_string(),
+                    include_archived: params.include_archived,
+                    include_history: params.include_history,
+                },
+                |mut client, request| async move { client.read_thread(request).await },
+            )
             .await
-            .map_err(|status| helpers::remote_status_to_thread_error(status, thread_id))?
-            .into_inner();
+            .map_err(|status| helpers::remote_status_to_thread_error(status, thread_id))?;
         let thread = response.thread.ok_or_else(|| ThreadStoreError::Internal {
             message: "remote thread store omitted read_thread response thread".to_string(),
         })?;
@@ -197,16 +379,17 @@ impl ThreadStore for RemoteThreadStore {
     ) -> ThreadStoreResult<StoredThread> {
         let thread_id = params.thread_id;
         let response = self
-            .client()
-            .await?
-            .update_thread_metadata(proto::UpdateThreadMetadataRequest {
-                thread_id: thread_id.to_string(),
-                patch: Some(helpers::proto_metadata_patch(params.patch)),
-                include_archived: params.include_archived,
-            })
+            .call(
+                Idempotency::NonIdempotent,
+                proto::UpdateThreadMetadataRequest {
+                    thread_id: thread_id.to_string(),
+                    patch: Some(helpers::proto_metadata_patch(params.patch)),
+                    include_archived: params.include_archived,
+                },
+                |mut client, request| async move { client.update_thread_metadata(request).await },
+            )
             .await
-            .map_err(|status| helpers::remote_status_to_thread_error(status, thread_id))?
-            .into_inner();
+            .map_err(|status| helpers::remote_status_to_thread_error(status, thread_id))?;
         let thread = response.thread.ok_or_else(|| ThreadStoreError::Internal {
             message: "remote thread store omitted update_thread_metadata response thread"
                 .to_string(),
@@ -216,13 +399,15 @@ impl ThreadStore for RemoteThreadStore {
 
     async fn archive_thread(&self, params: ArchiveThreadParams) -> ThreadStoreResult<()> {
         let thread_id = params.thread_id;
-        self.client()
-            .await?
-            .archive_thread(proto::ArchiveThreadRequest {
+        self.call(
+            Idempotency::NonIdempotent,
+            proto::ArchiveThreadRequest {
                 thread_id: thread_id.to_string(),
-            })
-            .await
-            .map_err(|status| helpers::remote_status_to_thread_error(status, thread_id))?;
+            },
+            |mut client, request| async move { client.archive_thread(request).await },
+        )
+        .await
+        .map_err(|status| helpers::remote_status_to_thread_error(status, thread_id))?;
         Ok(())
     }
 
@@ -232,17 +417,328 @@ impl ThreadStore for RemoteThreadStore {
     ) -> ThreadStoreResult<StoredThread> {
         let thread_id = params.thread_id;
         let response = self
-            .client()
-            .await?
-            .unarchive_thread(proto::ArchiveThreadRequest {
-                thread_id: thread_id.to_string(),
-            })
+            .call(
+                Idempotency::NonIdempotent,
+                proto::ArchiveThreadRequest {
+                    thread_id: thread_id.to_string(),
+                },
+                |mut client, request| async move { client.unarchive_thread(request).await },
+            )
             .await
-            .map_err(|status| helpers::remote_status_to_thread_error(status, thread_id))?
-            .into_inner();
+            .map_err(|status| helpers::remote_status_to_thread_error(status, thread_id))?;
         let thread = response.thread.ok_or_else(|| ThreadStoreError::Internal {
             message: "remote thread store omitted unarchive_thread response thread".to_string(),
         })?;
         helpers::stored_thread_from_proto(thread)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+
+    use pretty_assertions::assert_eq;
+    use tonic::Request;
+    use tonic::Response;
+    use tonic::Status;
+    use tonic::transport::Server;
+
+    use super::proto::thread_store_server;
+    use super::proto::thread_store_server::ThreadStoreServer;
+    use super::*;
+
+    const THREAD_ID: &str = "11111111-1111-1111-1111-111111111111";
+
+    /// Fails the first `transient_failures` reads with `UNAVAILABLE` and every append. Only
+    /// implements the unary `LoadHistory` RPC unless `stall_history_stream` is set, in which case
+    /// `LoadHistoryStream` sends one chunk and then never finishes.
+    #[derive(Default)]
+    struct FlakyServer {
+        transient_failures: usize,
+        stall_history_stream: bool,
+        read_calls: Arc<AtomicUsize>,
+        append_calls: Arc<AtomicUsize>,
+    }
+
+    #[tonic::async_trait]
+    impl thread_store_server::ThreadStore for FlakyServer {
+        type LoadHistoryStreamStream = std::pin::Pin<
+            Box<dyn tokio_stream::Stream<Item = Result<proto::StoredThreadHistory, Status>> + Send>,
+        >;
+
+        async fn load_history_stream(
+            &self,
+            request: Request<proto::LoadThreadHistoryRequest>,
+        ) -> Result<Response<Self::LoadHistoryStreamStream>, Status> {
+            if !self.stall_history_stream {
+                return Err(Status::unimplemented("not implemented"));
+            }
+            let chunk = proto::StoredThreadHistory {
+                thread_id: request.into_inner().thread_id,
+                items_json: Vec::new(),
+            };
+            let stream: Self::LoadHistoryStreamStream = Box::pin(tokio_stream::StreamExt::chain(
+                tokio_stream::once(Ok(chunk)),
+                tokio_stream::pending(),
+            ));
+            Ok(Response::new(stream))
+        }
+
+        async fn read_thread(
+            &self,
+            request: Request<proto::ReadThreadRequest>,
+        ) -> Result<Response<proto::StoredThreadResponse>, Status> {
+            assert!(request.metadata().get("grpc-timeout").is_some());
+            let call = self.read_calls.fetch_add(1, Ordering::SeqCst);
+            if call < self.transient_failures {
+                return Err(Status::unavailable("store is restarting"));
+            }
+            Ok(Response::new(proto::StoredThreadResponse {
+                thread: Some(proto::StoredThread {
+                    thread_id: request.into_inner().thread_id,
+                    preview: "hello".to_string(),
+                    ..Default::default()
+                }),
+            }))
+        }
+
+        async fn append_items(
+            &self,
+            _request: Request<proto::AppendThreadItemsRequest>,
+        ) -> Result<Response<proto::Empty>, Status> {
+            self.append_calls.fetch_add(1, Ordering::SeqCst);
+            Err(Status::unavailable("store is restarting"))
+        }
+
+        async fn load_history(
+            &self,
+            request: Request<proto::LoadThreadHistoryRequest>,
+        ) -> Result<Response<proto::StoredThreadHistory>, Status> {
+            Ok(Response::new(proto::StoredThreadHistory {
+                thread_id: request.into_inner().thread_id,
+                items_json: Vec::new(),
+            }))
+        }
+
+        async fn list_threads(
+            &self,
+            _request: Request<proto::ListThreadsRequest>,
+        ) -> Result<Response<proto::ListThreadsResponse>, Status> {
+            Err(Status::unimplemented("not implemented"))
+        }
+    }
+
+    async fn spawn_server(server: FlakyServer) -> (String, tokio::sync::oneshot::Sender<()>) {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind test server");
+        let addr = listener.local_addr().expect("test server addr");
+        let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel();
+        tokio::spawn(async move {
+            Server::builder()
+                .add_service(ThreadStoreServer::new(server))
+                .serve_with_incoming_shutdown(
+                    tokio_stream::wrappers::TcpListenerStream::new(listener),
+                    async {
+                        let _ = shutdown_rx.await;
+                    },
+                )
+                .await
+        });
+        (format!("http://{addr}"), shutdown_tx)
+    }
+
+    fn fast_retry_options() -> RemoteThreadStoreOptions {
+        RemoteThreadStoreOptions {
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(5),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn idempotent_calls_retry_transient_failures() {
+        let read_calls = Arc::new(AtomicUsize::new(0));
+        let (endpoint, _shutdown) = spawn_server(FlakyServer {
+            transient_failures: 2,
+            read_calls: Arc::clone(&read_calls),
+            ..Default::default()
+        })
+        .await;
+        let store = RemoteThreadStore::with_options(endpoint, fast_retry_options());
+        let thread_id = ThreadId::from_string(THREAD_ID).expect("valid thread id");
+
+        let thread = store
+            .read_thread(ReadThreadParams {
+                thread_id,
+                include_archived: false,
+                include_history: false,
+            })
+            .await
+            .expect("read should succeed after retries");
+
+        assert_eq!(thread.thread_id, thread_id);
+        assert_eq!(thread.preview, "hello");
+        assert_eq!(read_calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn retries_stop_after_max_retries() {
+        let read_calls = Arc::new(AtomicUsize::new(0));
+        let (endpoint, _shutdown) = spawn_server(FlakyServer {
+            transient_failures: usize::MAX,
+            read_calls: Arc::clone(&read_calls),
+            ..Default::default()
+        })
+        .await;
+        let store = RemoteThreadStore::with_options(
+            endpoint,
+            RemoteThreadStoreOptions {
+                max_retries: 1,
+                ..fast_retry_options()
+            },
+        );
+
+        let err = store
+            .read_thread(ReadThreadParams {
+                thread_id: ThreadId::from_string(THREAD_ID).expect("valid thread id"),
+                include_archived: false,
+                include_history: false,
+            })
+            .await
+            .expect_err("read should fail once retries are exhausted");
+
+        assert!(matches!(err, ThreadStoreError::Internal { .. }));
+        assert_eq!(read_calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn non_idempotent_calls_are_not_retried() {
+        let append_calls = Arc::new(AtomicUsize::new(0));
+        let (endpoint, _shutdown) = spawn_server(FlakyServer {
+            append_calls: Arc::clone(&append_calls),
+            ..Default::default()
+        })
+        .await;
+        let store = RemoteThreadStore::with_options(endpoint, fast_retry_options());
+
+        let err = store
+            .append_items(AppendThreadItemsParams {
+                thread_id: ThreadId::from_string(THREAD_ID).expect("valid thread id"),
+                items: Vec::new(),
+            })
+            .await
+            .expect_err("append should fail");
+
+        assert!(matches!(err, ThreadStoreError::Internal { .. }));
+        assert_eq!(append_calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn load_history_falls_back_to_unary_rpc() {
+        let (endpoint, _shutdown) = spawn_server(FlakyServer::default()).await;
+        let store = RemoteThreadStore::new(endpoint);
+        let thread_id = ThreadId::from_string(THREAD_ID).expect("valid thread id");
+
+        let history = store
+            .load_history(LoadThreadHistoryParams {
+                thread_id,
+                include_archived: false,
+            })
+            .await
+            .expect("load history");
+
+        assert_eq!(history.thread_id, thread_id);
+        assert!(history.items.is_empty());
+    }
+
+    #[tokio::test]
+    async fn stalled_attempts_time_out_and_are_retried() {
+        // Accept connections but never answer, so only the client-side deadline ends each
+        // attempt.
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind stalled server");
+        let addr = listener.local_addr().expect("stalled server addr");
+        let accepts = Arc::new(AtomicUsize::new(0));
+        let server_accepts = Arc::clone(&accepts);
+        tokio::spawn(async move {
+            let mut connections = Vec::new();
+            while let Ok((stream, _)) = listener.accept().await {
+                server_accepts.fetch_add(1, Ordering::SeqCst);
+                connections.push(stream);
+            }
+        });
+        let store = RemoteThreadStore::with_options(
+            format!("http://{addr}"),
+            RemoteThreadStoreOptions {
+                request_timeout: Duration::from_millis(100),
+                max_retries: 1,
+                ..fast_retry_options()
+            },
+        );
+
+        let err = tokio::time::timeout(
+            Duration::from_secs(10),
+            store.read_thread(ReadThreadParams {
+                thread_id: ThreadId::from_string(THREAD_ID).expect("valid thread id"),
+                include_archived: false,
+                include_history: false,
+            }),
+        )
+        .await
+        .expect("stalled read should hit its deadline")
+        .expect_err("stalled read should fail");
+
+        assert!(
+            matches!(&err, ThreadStoreError::Internal { message } if message.contains("timed out")),
+            "unexpected error: {err:?}"
+        );
+        assert!(accepts.load(Ordering::SeqCst) >= 1);
+    }
+
+    #[tokio::test]
+    async fn load_history_stream_stalling_after_first_chunk_times_out() {
+        let (endpoint, _shutdown) = spawn_server(FlakyServer {
+            stall_history_stream: true,
+            ..Default::default()
+        })
+        .await;
+        let store = RemoteThreadStore::with_options(
+            endpoint,
+            RemoteThreadStoreOptions {
+                request_timeout: Duration::from_millis(100),
+                ..fast_retry_options()
+            },
+        );
+
+        let err = tokio::time::timeout(
+            Duration::from_secs(10),
+            store.load_history(LoadThreadHistoryParams {
+                thread_id: ThreadId::from_string(THREAD_ID).expect("valid thread id"),
+                include_archived: false,
+            }),
+        )
+        .await
+        .expect("stalled stream should hit its deadline")
+        .expect_err("stalled stream should fail");
+
+        assert!(
+            matches!(&err, ThreadStoreError::Internal { message } if message.contains("timed out")),
+            "unexpected error: {err:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn invalid_endpoint_is_rejected() {
+        let store = RemoteThreadStore::new("not a uri");
+
+        let err = store
+            .flush_thread(ThreadId::from_string(THREAD_ID).expect("valid thread id"))
+            .await
+            .expect_err("invalid endpoint should fail");
+
+        assert!(matches!(err, ThreadStoreError::InvalidRequest { .. }));
+    }
+}
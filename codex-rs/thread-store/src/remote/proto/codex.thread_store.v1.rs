@@ -657,6 +657,27 @@ pub mod thread_store_client {
             ));
             self.inner.unary(req, path, codec).await
         }
+        pub async fn load_history_stream(
+            &mut self,
+            request: impl tonic::IntoRequest<super::LoadThreadHistoryRequest>,
+        ) -> std::result::Result<
+            tonic::Response<tonic::codec::Streaming<super::StoredThreadHistory>>,
+            tonic::Status,
+        > {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/codex.thread_store.v1.ThreadStore/LoadHistoryStream",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new(
+                "codex.thread_store.v1.ThreadStore",
+                "LoadHistoryStream",
+            ));
+            self.inner.server_streaming(req, path, codec).await
+        }
         pub async fn read_thread(
             &mut self,
             request: impl tonic::IntoRequest<super::ReadThreadRequest>,
@@ -823,6 +844,19 @@ pub mod thread_store_server {
             let _ = request;
             Err(tonic::Status::unimplemented("not implemented"))
         }
+        /// Server streaming response type for the LoadHistoryStream method.
+        type LoadHistoryStreamStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<super::StoredThreadHistory, tonic::Status>,
+            > + std::marker::Send
+            + 'static;
+        async fn load_history_stream(
+            &self,
+            request: tonic::Request<super::LoadThreadHistoryRequest>,
+        ) -> std::result::Result<tonic::Response<Self::LoadHistoryStreamStream>, tonic::Status>
+        {
+            let _ = request;
+            Err(tonic::Status::unimplemented("not implemented"))
+        }
         async fn read_thread(
             &self,
             request: tonic::Request<super::ReadThreadRequest>,
@@ -1017,6 +1051,50 @@ pub mod thread_store_server {
                     super::StoredThreadHistory,
                     load_history
                 ),
+                "/codex.thread_store.v1.ThreadStore/LoadHistoryStream" => {
+                    #[allow(non_camel_case_types)]
+                    struct LoadHistoryStreamSvc<T: ThreadStore>(pub Arc<T>);
+                    impl<T: ThreadStore>
+                        tonic::server::ServerStreamingService<super::LoadThreadHistoryRequest>
+                        for LoadHistoryStreamSvc<T>
+                    {
+                        type Response = super::StoredThreadHistory;
+                        type ResponseStream = T::LoadHistoryStreamStream;
+                        type Future =
+                            BoxFuture<tonic::Response<Self::ResponseStream>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::LoadThreadHistoryRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as ThreadStore>::load_history_stream(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = LoadHistoryStreamSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/codex.thread_store.v1.ThreadStore/ReadThread" => unary_service!(
                     ReadThreadSvc,
                     super::ReadThreadRequest,
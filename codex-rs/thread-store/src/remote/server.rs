@@ -0,0 +1,609 @@
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use codex_protocol::ThreadId;
+use codex_protocol::protocol::SessionSource;
+use codex_protocol::protocol::ThreadMemoryMode;
+use tokio_stream::StreamExt;
+use tonic::Request;
+use tonic::Response;
+use tonic::Status;
+
+use super::helpers;
+use super::proto;
+use super::proto::thread_store_server;
+use crate::AppendThreadItemsParams;
+use crate::ArchiveThreadParams;
+use crate::CreateThreadParams;
+use crate::GitInfoPatch;
+use crate::ListThreadsParams;
+use crate::LoadThreadHistoryParams;
+use crate::OptionalStringPatch;
+use crate::ReadThreadParams;
+use crate::ResumeThreadParams;
+use crate::SortDirection;
+use crate::ThreadEventPersistenceMode;
+use crate::ThreadMetadataPatch;
+use crate::ThreadSortKey;
+use crate::ThreadStore;
+use crate::ThreadStoreError;
+use crate::ThreadStoreResult;
+use crate::UpdateThreadMetadataParams;
+
+/// Soft cap on the serialized items carried by one `LoadHistoryStream` chunk. A single item larger
+/// than this is still sent, alone in its own chunk.
+const HISTORY_CHUNK_BYTES: usize = 1024 * 1024;
+
+/// Number of `LoadHistoryStream` chunks buffered ahead of a slow client.
+const HISTORY_STREAM_BUFFER: usize = 4;
+
+/// gRPC server that exposes any [`ThreadStore`] over `codex.thread_store.v1`, the protocol spoken
+/// by [`RemoteThreadStore`](super::RemoteThreadStore).
+///
+/// Wrapping a [`LocalThreadStore`](crate::LocalThreadStore) gives a reference remote deployment
+/// backed by local rollout files and SQLite.
+#[derive(Clone)]
+pub struct RemoteThreadStoreServer {
+    store: Arc<dyn ThreadStore>,
+}
+
+impl std::fmt::Debug for RemoteThreadStoreServer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RemoteThreadStoreServer")
+            .finish_non_exhaustive()
+    }
+}
+
+impl RemoteThreadStoreServer {
+    pub fn new(store: Arc<dyn ThreadStore>) -> Self {
+        Self { store }
+    }
+
+    /// Serves requests accepted on `listener` until the process exits.
+    pub async fn serve(self, listener: tokio::net::TcpListener) -> ThreadStoreResult<()> {
+        self.serve_with_shutdown(listener, std::future::pending())
+            .await
+    }
+
+    /// Serves requests accepted on `listener` until `shutdown` resolves.
+    pub async fn serve_with_shutdown(
+        self,
+        listener: tokio::net::TcpListener,
+        shutdown: impl Future<Output = ()>,
+    ) -> ThreadStoreResult<()> {
+        tonic::transport::Server::builder()
+            .add_service(thread_store_server::ThreadStoreServer::new(
+                ThreadStoreService { store: self.store },
+            ))
+            .serve_with_incoming_shutdown(
+                tokio_stream::wrappers::TcpListenerStream::new(listener),
+                shutdown,
+            )
+            .await
+            .map_err(|err| ThreadStoreError::Internal {
+                message: format!("thread store server failed: {err}"),
+            })
+    }
+}
+
+struct ThreadStoreService {
+    store: Arc<dyn ThreadStore>,
+}
+
+impl ThreadStoreService {
+    async fn load_history_proto(
+        &self,
+        request: proto::LoadThreadHistoryRequest,
+    ) -> Result<proto::StoredThreadHistory, Status> {
+        let history = self
+            .store
+            .load_history(LoadThreadHistoryParams {
+                thread_id: parse_thread_id(&request.thread_id, "thread_id")?,
+                include_archived: request.include_archived,
+            })
+            .await
+            .map_err(thread_store_error_to_status)?;
+        helpers::stored_thread_history_to_proto(history).map_err(thread_store_error_to_status)
+    }
+}
+
+#[tonic::async_trait]
+impl thread_store_server::ThreadStore for ThreadStoreService {
+    async fn create_thread(
+        &self,
+        request: Request<proto::CreateThreadRequest>,
+    ) -> Result<Response<proto::Empty>, Status> {
+        let request = request.into_inner();
+        let params = CreateThreadParams {
+            thread_id: parse_thread_id(&request.thread_id, "thread_id")?,
+            forked_from_id: request
+                .forked_from_id
+                .as_deref()
+                .map(|thread_id| parse_thread_id(thread_id, "forked_from_id"))
+                .transpose()?,
+            source: request
+                .source
+                .as_ref()
+                .map(helpers::session_source_from_proto)
+                .transpose()
+                .map_err(thread_store_error_to_status)?
+                .unwrap_or(SessionSource::Unknown),
+            base_instructions: decode_json(&request.base_instructions_json, "base_instructions")?,
+            dynamic_tools: decode_json_vec(&request.dynamic_tools_json, "dynamic_tool")?,
+            event_persistence_mode: event_persistence_mode_from_proto(
+                request.event_persistence_mode,
+            ),
+        };
+        self.store
+            .create_thread(params)
+            .await
+            .map_err(thread_store_error_to_status)?;
+        Ok(Response::new(proto::Empty {}))
+    }
+
+    async fn resume_thread(
+        &self,
+        request: Request<proto::ResumeThreadRequest>,
+    ) -> Result<Response<proto::Empty>, Status> {
+        let request = request.into_inner();
+        let params = ResumeThreadParams {
+            thread_id: parse_thread_id(&request.thread_id, "thread_id")?,
+            // Remote clients must not be able to point the store at arbitrary files on the
+            // server, so the thread is always resolved from its id.
+            rollout_path: None,
+            history: request
+                .has_history
+                .then(|| decode_json_vec(&request.history_json, "rollout_item"))
+                .transpose()?,
+            include_archived: request.include_archived,
+            event_persistence_mode: event_persistence_mode_from_proto(
+                request.event_persistence_mode,
+            ),
+        };
+        self.store
+            .resume_thread(params)
+            .await
+            .map_err(thread_store_error_to_status)?;
+        Ok(Response::new(proto::Empty {}))
+    }
+
+    async fn append_items(
+        &self,
+        request: Request<proto::AppendThreadItemsRequest>,
+    ) -> Result<Response<proto::Empty>, Status> {
+        let request = request.into_inner();
+        let params = AppendThreadItemsParams {
+            thread_id: parse_thread_id(&request.thread_id, "thread_id")?,
+            items: decode_json_vec(&request.items_json, "rollout_item")?,
+        };
+        self.store
+            .append_items(params)
+            .await
+            .map_err(thread_store_error_to_status)?;
+        Ok(Response::new(proto::Empty {}))
+    }
+
+    async fn persist_thread(
+        &self,
+        request: Request<proto::ThreadIdRequest>,
+    ) -> Result<Response<proto::Empty>, Status> {
+        let thread_id = parse_thread_id(&request.into_inner().thread_id, "thread_id")?;
+        self.store
+            .persist_thread(thread_id)
+            .await
+            .map_err(thread_store_error_to_status)?;
+        Ok(Response::new(proto::Empty {}))
+    }
+
+    async fn flush_thread(
+        &self,
+        request: Request<proto::ThreadIdRequest>,
+    ) -> Result<Response<proto::Empty>, Status> {
+        let thread_id = parse_thread_id(&request.into_inner().thread_id, "thread_id")?;
+        self.store
+            .flush_thread(thread_id)
+            .await
+            .map_err(thread_store_error_to_status)?;
+        Ok(Response::new(proto::Empty {}))
+    }
+
+    async fn shutdown_thread(
+        &self,
+        request: Request<proto::ThreadIdRequest>,
+    ) -> Result<Response<proto::Empty>, Status> {
+        let thread_id = parse_thread_id(&request.into_inner().thread_id, "thread_id")?;
+        self.store
+            .shutdown_thread(thread_id)
+            .await
+            .map_err(thread_store_error_to_status)?;
+        Ok(Response::new(proto::Empty {}))
+    }
+
+    async fn discard_thread(
+        &self,
+        request: Request<proto::ThreadIdRequest>,
+    ) -> Result<Response<proto::Empty>, Status> {
+        let thread_id = parse_thread_id(&request.into_inner().thread_id, "thread_id")?;
+        self.store
+            .discard_thread(thread_id)
+            .await
+            .map_err(thread_store_error_to_status)?;
+        Ok(Response::new(proto::Empty {}))
+    }
+
+    type LoadHistoryStreamStream =
+        tokio_stream::wrappers::ReceiverStream<Result<proto::StoredThreadHistory, Status>>;
+
+    async fn load_history(
+        &self,
+        request: Request<proto::LoadThreadHistoryRequest>,
+    ) -> Result<Response<proto::StoredThreadHistory>, Status> {
+        let history = self.load_history_proto(request.into_inner()).await?;
+        Ok(Response::new(history))
+    }
+
+    async fn load_history_stream(
+        &self,
+        request: Request<proto::LoadThreadHistoryRequest>,
+    ) -> Result<Response<Self::LoadHistoryStreamStream>, Status> {
+        let request = request.into_inner();
+        let thread_id = parse_thread_id(&request.thread_id, "thread_id")?;
+        let mut items = self
+            .store
+            .stream_history(LoadThreadHistoryParams {
+                thread_id,
+                include_archived: request.include_archived,
+            })
+            .await
+            .map_err(thread_store_error_to_status)?;
+        let (tx, rx) = tokio::sync::mpsc::channel(HISTORY_STREAM_BUFFER);
+        tokio::spawn(async move {
+            let mut chunker = HistoryChunker::new(thread_id.to_string(), HISTORY_CHUNK_BYTES);
+            while let Some(item) = items.next().await {
+                let item_json =
+                    item.and_then(|item| helpers::serialize_json(&item, "rollout_item"));
+                let item_json = match item_json {
+                    Ok(item_json) => item_json,
+                    Err(err) => {
+                        let _ = tx.send(Err(thread_store_error_to_status(err))).await;
+                        return;
+                    }
+                };
+                if let Some(chunk) = chunker.push(item_json)
+                    && tx.send(Ok(chunk)).await.is_err()
+                {
+                    return;
+                }
+            }
+            if let Some(chunk) = chunker.finish() {
+                let _ = tx.send(Ok(chunk)).await;
+            }
+        });
+        Ok(Response::new(tokio_stream::wrappers::ReceiverStream::new(
+            rx,
+        )))
+    }
+
+    async fn read_thread(
+        &self,
+        request: Request<proto::ReadThreadRequest>,
+    ) -> Result<Response<proto::StoredThreadResponse>, Status> {
+        let request = request.into_inner();
+        let thread = self
+            .store
+            .read_thread(ReadThreadParams {
+                thread_id: parse_thread_id(&request.thread_id, "thread_id")?,
+                include_archived: request.include_archived,
+                include_history: request.include_history,
+            })
+            .await
+            .map_err(thread_store_error_to_status)?;
+        stored_thread_response(thread)
+    }
+
+    async fn list_threads(
+        &self,
+        request: Request<proto::ListThreadsRequest>,
+    ) -> Result<Response<proto::ListThreadsResponse>, Status> {
+        let request = request.into_inner();
+        let params = ListThreadsParams {
+            page_size: request.page_size as usize,
+            cursor: request.cursor,
+            sort_key: match proto::ThreadSortKey::try_from(request.sort_key).unwrap_or_default() {
+                proto::ThreadSortKey::CreatedAt => ThreadSortKey::CreatedAt,
+                proto::ThreadSortKey::UpdatedAt => ThreadSortKey::UpdatedAt,
+            },
+            sort_direction: match proto::SortDirection::try_from(request.sort_direction)
+                .unwrap_or_default()
+            {
+                proto::SortDirection::Asc => SortDirection::Asc,
+                proto::SortDirection::Desc => SortDirection::Desc,
+            },
+            allowed_sources: request
+                .allowed_sources
+                .iter()
+                .map(helpers::session_source_from_proto)
+                .collect::<ThreadStoreResult<Vec<_>>>()
+                .map_err(thread_store_error_to_status)?,
+            model_providers: request.model_provider_filter.map(|filter| filter.values),
+            cwd_filters: request
+                .cwd_filter
+                .map(|filter| filter.values.into_iter().map(PathBuf::from).collect()),
+            archived: request.archived,
+            search_term: request.search_term,
+            use_state_db_only: request.use_state_db_only,
+        };
+        let page = self
+            .store
+            .list_threads(params)
+            .await
+            .map_err(thread_store_error_to_status)?;
+        let threads = page
+            .items
+            .into_iter()
+            .map(helpers::stored_thread_to_proto)
+            .collect::<ThreadStoreResult<Vec<_>>>()
+            .map_err(thread_store_error_to_status)?;
+        Ok(Response::new(proto::ListThreadsResponse {
+            threads,
+            next_cursor: page.next_cursor,
+        }))
+    }
+
+    async fn update_thread_metadata(
+        &self,
+        request: Request<proto::UpdateThreadMetadataRequest>,
+    ) -> Result<Response<proto::StoredThreadResponse>, Status> {
+        let request = request.into_inner();
+        let thread = self
+            .store
+            .update_thread_metadata(UpdateThreadMetadataParams {
+                thread_id: parse_thread_id(&request.thread_id, "thread_id")?,
+                patch: request
+                    .patch
+                    .map(metadata_patch_from_proto)
+                    .transpose()?
+                    .unwrap_or_default(),
+                include_archived: request.include_archived,
+            })
+            .await
+            .map_err(thread_store_error_to_status)?;
+        stored_thread_response(thread)
+    }
+
+    async fn archive_thread(
+        &self,
+        request: Request<proto::ArchiveThreadRequest>,
+    ) -> Result<Response<proto::Empty>, Status> {
+        let thread_id = parse_thread_id(&request.into_inner().thread_id, "thread_id")?;
+        self.store
+            .archive_thread(ArchiveThreadParams { thread_id })
+            .await
+            .map_err(thread_store_error_to_status)?;
+        Ok(Response::new(proto::Empty {}))
+    }
+
+    async fn unarchive_thread(
+        &self,
+        request: Request<proto::ArchiveThreadRequest>,
+    ) -> Result<Response<proto::StoredThreadResponse>, Status> {
+        let thread_id = parse_thread_id(&request.into_inner().thread_id, "thread_id")?;
+        let thread = self
+            .store
+            .unarchive_thread(ArchiveThreadParams { thread_id })
+            .await
+            .map_err(thread_store_error_to_status)?;
+        stored_thread_response(thread)
+    }
+}
+
+/// Groups streamed item JSON into chunks of at most `max_bytes` each. At least one chunk is
+/// produced so empty histories still report their thread id.
+struct HistoryChunker {
+    thread_id: String,
+    max_bytes: usize,
+    chunk: Vec<String>,
+    chunk_bytes: usize,
+    emitted_chunk: bool,
+}
+
+impl HistoryChunker {
+    fn new(thread_id: String, max_bytes: usize) -> Self {
+        Self {
+            thread_id,
+            max_bytes,
+            chunk: Vec::new(),
+            chunk_bytes: 0,
+            emitted_chunk: false,
+        }
+    }
+
+    /// Adds one item, returning the previous chunk if the item does not fit in it.
+    fn push(&mut self, item_json: String) -> Option<proto::StoredThreadHistory> {
+        let full_chunk = (!self.chunk.is_empty()
+            && self.chunk_bytes + item_json.len() > self.max_bytes)
+            .then(|| self.take_chunk());
+        self.chunk_bytes += item_json.len();
+        self.chunk.push(item_json);
+        full_chunk
+    }
+
+    /// Returns the final chunk, if one still needs to be sent.
+    fn finish(mut self) -> Option<proto::StoredThreadHistory> {
+        (!self.chunk.is_empty() || !self.emitted_chunk).then(|| self.take_chunk())
+    }
+
+    fn take_chunk(&mut self) -> proto::StoredThreadHistory {
+        self.emitted_chunk = true;
+        self.chunk_bytes = 0;
+        proto::StoredThreadHistory {
+            thread_id: self.thread_id.clone(),
+            items_json: std::mem::take(&mut self.chunk),
+        }
+    }
+}
+
+/// Maps store errors onto the status codes that
+/// [`remote_status_to_thread_error`](helpers::remote_status_to_thread_error) maps back.
+fn thread_store_error_to_status(err: ThreadStoreError) -> Status {
+    match err {
+        ThreadStoreError::ThreadNotFound { .. } => Status::not_found(err.to_string()),
+        ThreadStoreError::InvalidRequest { message } => Status::invalid_argument(message),
+        ThreadStoreError::Conflict { message } => Status::failed_precondition(message),
+        ThreadStoreError::Internal { message } => Status::internal(message),
+    }
+}
+
+fn stored_thread_response(
+    thread: crate::StoredThread,
+) -> Result<Response<proto::StoredThreadResponse>, Status> {
+    let thread = helpers::stored_thread_to_proto(thread).map_err(thread_store_error_to_status)?;
+    Ok(Response::new(proto::StoredThreadResponse {
+        thread: Some(thread),
+    }))
+}
+
+fn parse_thread_id(value: &str, field_name: &str) -> Result<ThreadId, Status> {
+    ThreadId::from_string(value)
+        .map_err(|err| Status::invalid_argument(format!("invalid {field_name}: {err}")))
+}
+
+fn decode_json<T: serde::de::DeserializeOwned>(json: &str, field_name: &str) -> Result<T, Status> {
+    serde_json::from_str(json)
+        .map_err(|err| Status::invalid_argument(format!("invalid {field_name}: {err}")))
+}
+
+fn decode_json_vec<T: serde::de::DeserializeOwned>(
+    values: &[String],
+    field_name: &str,
+) -> Result<Vec<T>, Status> {
+    values
+        .iter()
+        .map(|value| decode_json(value, field_name))
+        .collect()
+}
+
+fn event_persistence_mode_from_proto(mode: i32) -> ThreadEventPersistenceMode {
+    match proto::ThreadEventPersistenceMode::try_from(mode).unwrap_or_default() {
+        proto::ThreadEventPersistenceMode::Limited => ThreadEventPersistenceMode::Limited,
+        proto::ThreadEventPersistenceMode::Extended => ThreadEventPersistenceMode::Extended,
+    }
+}
+
+fn metadata_patch_from_proto(
+    patch: proto::ThreadMetadataPatch,
+) -> Result<ThreadMetadataPatch, Status> {
+    let memory_mode = patch
+        .memory_mode
+        .map(
+            |memory_mode| match proto::ThreadMemoryMode::try_from(memory_mode) {
+                Ok(proto::ThreadMemoryMode::Enabled) => Ok(ThreadMemoryMode::Enabled),
+                Ok(proto::ThreadMemoryMode::Disabled) => Ok(ThreadMemoryMode::Disabled),
+                Err(_) => Err(Status::invalid_argument(format!(
+                    "invalid memory_mode: {memory_mode}"
+                ))),
+            },
+        )
+        .transpose()?;
+    let git_info = patch
+        .git_info
+        .map(|git_info| {
+            Ok::<_, Status>(GitInfoPatch {
+                sha: optional_string_patch_from_proto(git_info.sha, "git_info.sha")?,
+                branch: optional_string_patch_from_proto(git_info.branch, "git_info.branch")?,
+                origin_url: optional_string_patch_from_proto(
+                    git_info.origin_url,
+                    "git_info.origin_url",
+                )?,
+            })
+        })
+        .transpose()?;
+    Ok(ThreadMetadataPatch {
+        name: patch.name,
+        memory_mode,
+        git_info,
+    })
+}
+
+fn optional_string_patch_from_proto(
+    patch: Option<proto::OptionalStringPatch>,
+    field_name: &str,
+) -> Result<OptionalStringPatch, Status> {
+    let Some(patch) = patch else {
+        return Ok(None);
+    };
+    match proto::OptionalStringPatchKind::try_from(patch.kind) {
+        Ok(proto::OptionalStringPatchKind::Unset) => Ok(None),
+        Ok(proto::OptionalStringPatchKind::Clear) => Ok(Some(None)),
+        Ok(proto::OptionalStringPatchKind::Set) => match patch.value {
+            Some(value) => Ok(Some(Some(value))),
+            None => Err(Status::invalid_argument(format!(
+                "{field_name} set patch is missing a value"
+            ))),
+        },
+        Err(_) => Err(Status::invalid_argument(format!(
+            "invalid {field_name} patch kind: {}",
+            patch.kind
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn history_chunks(
+        history: proto::StoredThreadHistory,
+        max_bytes: usize,
+    ) -> Vec<proto::StoredThreadHistory> {
+        let mut chunker = HistoryChunker::new(history.thread_id, max_bytes);
+        let mut chunks = history
+            .items_json
+            .into_iter()
+            .filter_map(|item_json| chunker.push(item_json))
+            .collect::<Vec<_>>();
+        chunks.extend(chunker.finish());
+        chunks
+    }
+
+    #[test]
+    fn history_chunks_split_on_byte_budget() {
+        let history = proto::StoredThreadHistory {
+            thread_id: "thread".to_string(),
+            items_json: ["aaaa", "bb", "cccccc", "d"].map(str::to_string).to_vec(),
+        };
+
+        let chunks = history_chunks(history, /*max_bytes*/ 6)
+            .into_iter()
+            .map(|chunk| {
+                assert_eq!(chunk.thread_id, "thread");
+                chunk.items_json
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            chunks,
+            vec![
+                vec!["aaaa".to_string(), "bb".to_string()],
+                vec!["cccccc".to_string()],
+                vec!["d".to_string()],
+            ]
+        );
+    }
+
+    #[test]
+    fn history_chunks_keep_thread_id_for_empty_history() {
+        let history = proto::StoredThreadHistory {
+            thread_id: "thread".to_string(),
+            items_json: Vec::new(),
+        };
+
+        assert_eq!(
+            history_chunks(history.clone(), /*max_bytes*/ 6),
+            vec![history]
+        );
+    }
+}
@@ -1,6 +1,9 @@
 use async_trait::async_trait;
 use codex_protocol::ThreadId;
+use codex_protocol::protocol::RolloutItem;
 use std::any::Any;
+use std::pin::Pin;
+use tokio_stream::Stream;
 
 use crate::AppendThreadItemsParams;
 use crate::ArchiveThreadParams;
@@ -15,6 +18,9 @@ use crate::ThreadPage;
 use crate::ThreadStoreResult;
 use crate::UpdateThreadMetadataParams;
 
+/// Persisted rollout items of one thread, in replay order.
+pub type RolloutItemStream = Pin<Box<dyn Stream<Item = ThreadStoreResult<RolloutItem>> + Send>>;
+
 /// Storage-neutral thread persistence boundary.
 #[async_trait]
 pub trait ThreadStore: Any + Send + Sync {
@@ -53,6 +59,19 @@ pub trait ThreadStore: Any + Send + Sync {
         params: LoadThreadHistoryParams,
     ) -> ThreadStoreResult<StoredThreadHistory>;
 
+    /// Streams the same items as [`ThreadStore::load_history`] so that large histories can be
+    /// forwarded without holding them in memory. The default implementation loads the whole
+    /// history first.
+    async fn stream_history(
+        &self,
+        params: LoadThreadHistoryParams,
+    ) -> ThreadStoreResult<RolloutItemStream> {
+        let history = self.load_history(params).await?;
+        Ok(Box::pin(tokio_stream::iter(
+            history.items.into_iter().map(Ok),
+        )))
+    }
+
     /// Reads a thread summary and optionally its persisted history.
     async fn read_thread(&self, params: ReadThreadParams) -> ThreadStoreResult<StoredThread>;
 
@@ -24,6 +24,11 @@ Each operation starts with one of three headers:
 *** Delete File: <path> - remove an existing file. Nothing follows.
 *** Update File: <path> - patch an existing file in place (optionally with a rename).
 
+Binary files, symlinks, and permission bits have their own, rarely needed headers:
+*** Add Binary File: <path> / *** Update Binary File: <path> - write a whole binary file. Every following line is a + line of base64.
+*** Add Symlink: <path> - create a symlink. Followed by exactly one *** Link to: <target> line.
+*** Set Mode: <path> - change permission bits. Followed by exactly one *** Mode: <octal> line, e.g. 755.
+
 May be immediately followed by *** Move to: <new path> if you want to rename the file.
 Then one or more “hunks”, each introduced by @@ (optionally followed by a hunk header).
 Within a hunk each line starts with:
@@ -50,11 +55,15 @@ The full grammar definition is below:
 Patch := Begin { FileOp } End
 Begin := "*** Begin Patch" NEWLINE
 End := "*** End Patch" NEWLINE
-FileOp := AddFile | DeleteFile | UpdateFile
+FileOp := AddFile | DeleteFile | UpdateFile | AddBinaryFile | UpdateBinaryFile | AddSymlink | SetMode
 AddFile := "*** Add File: " path NEWLINE { "+" line NEWLINE }
 DeleteFile := "*** Delete File: " path NEWLINE
 UpdateFile := "*** Update File: " path NEWLINE [ MoveTo ] { Hunk }
 MoveTo := "*** Move to: " newPath NEWLINE
+AddBinaryFile := "*** Add Binary File: " path NEWLINE { "+" base64 NEWLINE }
+UpdateBinaryFile := "*** Update Binary File: " path NEWLINE { "+" base64 NEWLINE }
+AddSymlink := "*** Add Symlink: " path NEWLINE "*** Link to: " target NEWLINE
+SetMode := "*** Set Mode: " path NEWLINE "*** Mode: " octalMode NEWLINE
 Hunk := "@@" [ header ] NEWLINE { HunkLine } [ "*** End of File" NEWLINE ]
 HunkLine := (" " | "-" | "+") text NEWLINE
 
@@ -133,8 +133,12 @@ pub use tool_spec::ToolSpec;
 pub use tool_spec::WebSearchToolOptions;
 pub use tool_spec::create_image_generation_tool;
 pub use tool_spec::create_local_shell_tool;
+pub use tool_spec::create_tools_json_for_chat_completions_api;
+pub use tool_spec::create_tools_json_for_messages_api;
 pub use tool_spec::create_tools_json_for_responses_api;
 pub use tool_spec::create_web_search_tool;
+pub use tool_spec::flattened_namespaced_tool_names;
+pub use tool_spec::freeform_tool_names;
 pub use tool_suggest::TOOL_SUGGEST_APPROVAL_KIND_VALUE;
 pub use tool_suggest::ToolSuggestArgs;
 pub use tool_suggest::ToolSuggestMeta;
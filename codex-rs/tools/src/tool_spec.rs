@@ -1,8 +1,11 @@
 use crate::FreeformTool;
+use crate::FreeformToolFormat;
 use crate::JsonSchema;
 use crate::LoadableToolSpec;
 use crate::ResponsesApiNamespace;
+use crate::ResponsesApiNamespaceTool;
 use crate::ResponsesApiTool;
+use crate::ToolName;
 use codex_protocol::config_types::WebSearchConfig;
 use codex_protocol::config_types::WebSearchContextSize;
 use codex_protocol::config_types::WebSearchFilters as ConfigWebSearchFilters;
@@ -12,6 +15,9 @@ use codex_protocol::config_types::WebSearchUserLocationType;
 use codex_protocol::openai_models::WebSearchToolType;
 use serde::Serialize;
 use serde_json::Value;
+use serde_json::json;
+use std::collections::HashMap;
+use std::collections::HashSet;
 
 const WEB_SEARCH_TEXT_AND_IMAGE_CONTENT_TYPES: [&str; 2] = ["text", "image"];
 
@@ -163,6 +169,164 @@ pub fn create_tools_json_for_responses_api(
     Ok(tools_json)
 }
 
+/// Returns JSON values that are compatible with Function Calling in the
+/// Chat Completions API.
+///
+/// Chat Completions has no namespaces, so namespaced functions are flattened
+/// to their model-visible `{namespace}{name}` form; use
+/// [`flattened_namespaced_tool_names`] to map calls back. Freeform tools
+/// become functions taking their raw text through a single `input` string
+/// argument; use [`freeform_tool_names`] to recognize their calls. Hosted
+/// and tool search tools are omitted.
+pub fn create_tools_json_for_chat_completions_api(
+    tools: &[ToolSpec],
+) -> Result<Vec<Value>, serde_json::Error> {
+    let mut tools_json = Vec::new();
+
+    for tool in tools {
+        match tool {
+            ToolSpec::Function(tool) => {
+                tools_json.push(chat_completions_function_json(&tool.name, tool)?);
+            }
+            ToolSpec::Namespace(namespace) => {
+                for ResponsesApiNamespaceTool::Function(tool) in &namespace.tools {
+                    let name = ToolName::namespaced(namespace.name.clone(), tool.name.clone());
+                    tools_json.push(chat_completions_function_json(&name.display(), tool)?);
+                }
+            }
+            ToolSpec::Freeform(tool) => {
+                tools_json.push(json!({
+                    "type": "function",
+                    "function": {
+                        "name": tool.name,
+                        "description": freeform_tool_description(tool),
+                        "strict": false,
+                        "parameters": freeform_tool_parameters(),
+                    },
+                }));
+            }
+            ToolSpec::ToolSearch { .. }
+            | ToolSpec::LocalShell {}
+            | ToolSpec::ImageGeneration { .. }
+            | ToolSpec::WebSearch { .. } => {}
+        }
+    }
+
+    Ok(tools_json)
+}
+
+/// Returns JSON values that are compatible with tool use in the Messages API.
+///
+/// Namespaced functions are flattened the same way as for Chat Completions;
+/// use [`flattened_namespaced_tool_names`] to map calls back. Freeform tools
+/// take their raw text through a single `input` string property, with the
+/// grammar appended to the description; use [`freeform_tool_names`] to
+/// recognize their calls. Hosted and tool search tools are omitted.
+pub fn create_tools_json_for_messages_api(
+    tools: &[ToolSpec],
+) -> Result<Vec<Value>, serde_json::Error> {
+    let mut tools_json = Vec::new();
+
+    for tool in tools {
+        match tool {
+            ToolSpec::Function(tool) => {
+                tools_json.push(messages_function_json(&tool.name, tool)?);
+            }
+            ToolSpec::Namespace(namespace) => {
+                for ResponsesApiNamespaceTool::Function(tool) in &namespace.tools {
+                    let name = ToolName::namespaced(namespace.name.clone(), tool.name.clone());
+                    tools_json.push(messages_function_json(&name.display(), tool)?);
+                }
+            }
+            ToolSpec::Freeform(tool) => {
+                tools_json.push(json!({
+                    "name": tool.name,
+                    "description": freeform_tool_description(tool),
+                    "input_schema": freeform_tool_parameters(),
+                }));
+            }
+            ToolSpec::ToolSearch { .. }
+            | ToolSpec::LocalShell {}
+            | ToolSpec::ImageGeneration { .. }
+            | ToolSpec::WebSearch { .. } => {}
+        }
+    }
+
+    Ok(tools_json)
+}
+
+/// Names of the freeform tools that [`create_tools_json_for_chat_completions_api`]
+/// and [`create_tools_json_for_messages_api`] wrap in an `input` object.
+pub fn freeform_tool_names(tools: &[ToolSpec]) -> HashSet<String> {
+    tools
+        .iter()
+        .filter_map(|tool| match tool {
+            ToolSpec::Freeform(tool) => Some(tool.name.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Maps the flattened function names produced by
+/// [`create_tools_json_for_chat_completions_api`] and
+/// [`create_tools_json_for_messages_api`] back to their namespaced
+/// [`ToolName`]s.
+pub fn flattened_namespaced_tool_names(tools: &[ToolSpec]) -> HashMap<String, ToolName> {
+    let mut names = HashMap::new();
+    for tool in tools {
+        if let ToolSpec::Namespace(namespace) = tool {
+            for ResponsesApiNamespaceTool::Function(tool) in &namespace.tools {
+                let name = ToolName::namespaced(namespace.name.clone(), tool.name.clone());
+                names.insert(name.display(), name);
+            }
+        }
+    }
+    names
+}
+
+fn chat_completions_function_json(
+    name: &str,
+    tool: &ResponsesApiTool,
+) -> Result<Value, serde_json::Error> {
+    Ok(json!({
+        "type": "function",
+        "function": {
+            "name": name,
+            "description": tool.description,
+            "strict": tool.strict,
+            "parameters": serde_json::to_value(&tool.parameters)?,
+        },
+    }))
+}
+
+fn freeform_tool_description(tool: &FreeformTool) -> String {
+    let FreeformToolFormat {
+        syntax, definition, ..
+    } = &tool.format;
+    format!(
+        "{}\n\nThe `input` must match this {syntax} grammar:\n{definition}",
+        tool.description
+    )
+}
+
+fn freeform_tool_parameters() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "input": {"type": "string"},
+        },
+        "required": ["input"],
+    })
+}
+
+fn messages_function_json(name: &str, tool: &ResponsesApiTool) -> Result<Value, serde_json::Error> {
+    Ok(json!({
+        "name": name,
+        "description": tool.description,
+        "input_schema": serde_json::to_value(&tool.parameters)?,
+    }))
+}
+
 #[derive(Debug, Clone, Serialize, PartialEq)]
 pub struct ResponsesApiWebSearchFilters {
     #[serde(skip_serializing_if = "Option::is_none")]
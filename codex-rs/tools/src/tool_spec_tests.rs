@@ -9,7 +9,12 @@ use crate::FreeformToolFormat;
 use crate::JsonSchema;
 use crate::ResponsesApiNamespaceTool;
 use crate::ResponsesApiTool;
+use crate::ToolName;
+use crate::create_tools_json_for_chat_completions_api;
+use crate::create_tools_json_for_messages_api;
 use crate::create_tools_json_for_responses_api;
+use crate::flattened_namespaced_tool_names;
+use crate::freeform_tool_names;
 use codex_protocol::config_types::WebSearchContextSize;
 use codex_protocol::config_types::WebSearchFilters as ConfigWebSearchFilters;
 use codex_protocol::config_types::WebSearchUserLocation as ConfigWebSearchUserLocation;
@@ -17,6 +22,8 @@ use codex_protocol::config_types::WebSearchUserLocationType;
 use pretty_assertions::assert_eq;
 use serde_json::json;
 use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::collections::HashSet;
 
 #[test]
 fn tool_spec_name_covers_all_variants() {
@@ -174,6 +181,188 @@ fn create_tools_json_for_responses_api_includes_top_level_name() {
     );
 }
 
+#[test]
+fn create_tools_json_for_chat_completions_api_flattens_namespaces_and_wraps_freeform_tools() {
+    let tools = [
+        ToolSpec::Function(ResponsesApiTool {
+            name: "demo".to_string(),
+            description: "A demo tool".to_string(),
+            strict: false,
+            defer_loading: None,
+            parameters: JsonSchema::object(
+                BTreeMap::new(),
+                /*required*/ None,
+                /*additional_properties*/ None,
+            ),
+            output_schema: None,
+        }),
+        ToolSpec::Namespace(ResponsesApiNamespace {
+            name: "mcp__demo__".to_string(),
+            description: "Demo tools".to_string(),
+            tools: vec![ResponsesApiNamespaceTool::Function(ResponsesApiTool {
+                name: "lookup_order".to_string(),
+                description: "Look up an order".to_string(),
+                strict: false,
+                defer_loading: None,
+                parameters: JsonSchema::object(
+                    BTreeMap::from([(
+                        "order_id".to_string(),
+                        JsonSchema::string(/*description*/ None),
+                    )]),
+                    /*required*/ None,
+                    /*additional_properties*/ None,
+                ),
+                output_schema: None,
+            })],
+        }),
+        ToolSpec::LocalShell {},
+        ToolSpec::Freeform(FreeformTool {
+            name: "apply_patch".to_string(),
+            description: "Apply a patch".to_string(),
+            format: FreeformToolFormat {
+                r#type: "grammar".to_string(),
+                syntax: "lark".to_string(),
+                definition: "start: \"patch\"".to_string(),
+            },
+        }),
+    ];
+
+    assert_eq!(
+        create_tools_json_for_chat_completions_api(&tools).expect("serialize tools"),
+        vec![
+            json!({
+                "type": "function",
+                "function": {
+                    "name": "demo",
+                    "description": "A demo tool",
+                    "strict": false,
+                    "parameters": {
+                        "type": "object",
+                        "properties": {},
+                    },
+                },
+            }),
+            json!({
+                "type": "function",
+                "function": {
+                    "name": "mcp__demo__lookup_order",
+                    "description": "Look up an order",
+                    "strict": false,
+                    "parameters": {
+                        "type": "object",
+                        "properties": {
+                            "order_id": { "type": "string" },
+                        },
+                    },
+                },
+            }),
+            json!({
+                "type": "function",
+                "function": {
+                    "name": "apply_patch",
+                    "description": "Apply a patch\n\nThe `input` must match this lark grammar:\nstart: \"patch\"",
+                    "strict": false,
+                    "parameters": {
+                        "type": "object",
+                        "properties": {
+                            "input": { "type": "string" },
+                        },
+                        "required": ["input"],
+                    },
+                },
+            }),
+        ]
+    );
+    assert_eq!(
+        flattened_namespaced_tool_names(&tools),
+        HashMap::from([(
+            "mcp__demo__lookup_order".to_string(),
+            ToolName::namespaced("mcp__demo__", "lookup_order"),
+        )])
+    );
+}
+
+#[test]
+fn create_tools_json_for_messages_api_wraps_freeform_input() {
+    let tools = [
+        ToolSpec::Function(ResponsesApiTool {
+            name: "demo".to_string(),
+            description: "A demo tool".to_string(),
+            strict: false,
+            defer_loading: None,
+            parameters: JsonSchema::object(
+                BTreeMap::new(),
+                /*required*/ None,
+                /*additional_properties*/ None,
+            ),
+            output_schema: None,
+        }),
+        ToolSpec::Namespace(ResponsesApiNamespace {
+            name: "mcp__demo__".to_string(),
+            description: "Demo tools".to_string(),
+            tools: vec![ResponsesApiNamespaceTool::Function(ResponsesApiTool {
+                name: "lookup_order".to_string(),
+                description: "Look up an order".to_string(),
+                strict: false,
+                defer_loading: None,
+                parameters: JsonSchema::object(
+                    BTreeMap::new(),
+                    /*required*/ None,
+                    /*additional_properties*/ None,
+                ),
+                output_schema: None,
+            })],
+        }),
+        ToolSpec::LocalShell {},
+        ToolSpec::Freeform(FreeformTool {
+            name: "apply_patch".to_string(),
+            description: "Apply a patch".to_string(),
+            format: FreeformToolFormat {
+                r#type: "grammar".to_string(),
+                syntax: "lark".to_string(),
+                definition: "start: \"patch\"".to_string(),
+            },
+        }),
+    ];
+
+    assert_eq!(
+        create_tools_json_for_messages_api(&tools).expect("serialize tools"),
+        vec![
+            json!({
+                "name": "demo",
+                "description": "A demo tool",
+                "input_schema": {
+                    "type": "object",
+                    "properties": {},
+                },
+            }),
+            json!({
+                "name": "mcp__demo__lookup_order",
+                "description": "Look up an order",
+                "input_schema": {
+                    "type": "object",
+                    "properties": {},
+                },
+            }),
+            json!({
+                "name": "apply_patch",
+                "description": "Apply a patch\n\nThe `input` must match this lark grammar:\nstart: \"patch\"",
+                "input_schema": {
+                    "type": "object",
+                    "properties": {
+                        "input": { "type": "string" },
+                    },
+                    "required": ["input"],
+                },
+            }),
+        ]
+    );
+    assert_eq!(
+        freeform_tool_names(&tools),
+        HashSet::from(["apply_patch".to_string()])
+    );
+}
+
 #[test]
 fn namespace_tool_spec_serializes_expected_wire_shape() {
     assert_eq!(
@@ -354,6 +354,9 @@ fn server_notification_thread_target(
         ServerNotification::HookStarted(notification) => Some(notification.thread_id.as_str()),
         ServerNotification::TurnCompleted(notification) => Some(notification.thread_id.as_str()),
         ServerNotification::HookCompleted(notification) => Some(notification.thread_id.as_str()),
+        ServerNotification::NetworkTrafficRecorded(notification) => {
+            Some(notification.thread_id.as_str())
+        }
         ServerNotification::TurnDiffUpdated(notification) => Some(notification.thread_id.as_str()),
         ServerNotification::TurnPlanUpdated(notification) => Some(notification.thread_id.as_str()),
         ServerNotification::ItemStarted(notification) => Some(notification.thread_id.as_str()),
@@ -440,6 +443,8 @@ fn server_notification_thread_target(
         | ServerNotification::ConfigWarning(_)
         | ServerNotification::FuzzyFileSearchSessionUpdated(_)
         | ServerNotification::FuzzyFileSearchSessionCompleted(_)
+        | ServerNotification::ContentSearchSessionUpdated(_)
+        | ServerNotification::ContentSearchSessionCompleted(_)
         | ServerNotification::CommandExecOutputDelta(_)
         | ServerNotification::FsChanged(_)
         | ServerNotification::WindowsWorldWritableWarning(_)
@@ -399,6 +399,16 @@ fn file_change_decision(decision: &ReviewDecision) -> Result<FileChangeApprovalD
     match decision {
         ReviewDecision::Approved => Ok(FileChangeApprovalDecision::Accept),
         ReviewDecision::ApprovedForSession => Ok(FileChangeApprovalDecision::AcceptForSession),
+        ReviewDecision::ApprovedPartialPatch { accepted_files } => {
+            Ok(FileChangeApprovalDecision::AcceptPartial {
+                files: accepted_files.iter().cloned().map(Into::into).collect(),
+            })
+        }
+        ReviewDecision::ApprovedEditedPatch { patch } => {
+            Ok(FileChangeApprovalDecision::AcceptEdited {
+                patch: patch.clone(),
+            })
+        }
         ReviewDecision::Denied => Ok(FileChangeApprovalDecision::Decline),
         ReviewDecision::TimedOut => Ok(FileChangeApprovalDecision::Decline),
         ReviewDecision::Abort => Ok(FileChangeApprovalDecision::Cancel),
@@ -8,8 +8,10 @@ use crate::bottom_pane::BottomPaneView;
 use crate::bottom_pane::CancellationEvent;
 use crate::bottom_pane::list_selection_view::ListSelectionView;
 use crate::bottom_pane::list_selection_view::SelectionItem;
+use crate::bottom_pane::list_selection_view::SelectionToggle;
 use crate::bottom_pane::list_selection_view::SelectionViewParams;
 use crate::diff_render::DiffSummary;
+use crate::diff_render::display_path_for;
 use crate::exec_command::strip_bash_lc_and_escape;
 use crate::history_cell;
 use crate::key_hint;
@@ -31,6 +33,7 @@ use codex_protocol::protocol::NetworkApprovalContext;
 use codex_protocol::protocol::NetworkPolicyRuleAction;
 #[cfg(test)]
 use codex_protocol::protocol::Op;
+use codex_protocol::protocol::PatchFileApproval;
 use codex_protocol::protocol::ReviewDecision;
 use codex_protocol::request_permissions::PermissionGrantScope;
 use codex_protocol::request_permissions::RequestPermissionProfile;
@@ -141,6 +144,9 @@ pub(crate) struct ApprovalOverlay {
     list: ListSelectionView,
     options: Vec<ApprovalOption>,
     current_complete: bool,
+    /// Toggle rows shown while choosing which parts of a patch to apply. They
+    /// precede `options` in the list.
+    patch_selection: Option<Vec<PatchSelectionEntry>>,
     done: bool,
     features: Features,
 }
@@ -154,6 +160,7 @@ impl ApprovalOverlay {
             list: ListSelectionView::new(Default::default(), app_event_tx),
             options: Vec::new(),
             current_complete: false,
+            patch_selection: None,
             done: false,
             features,
         };
@@ -184,6 +191,7 @@ impl ApprovalOverlay {
 
     fn set_current(&mut self, request: ApprovalRequest) {
         self.current_complete = false;
+        self.patch_selection = None;
         let header = build_header(&request);
         let (options, params) = Self::build_options(&request, header, &self.features);
         self.current_request = Some(request);
@@ -222,8 +230,8 @@ impl ApprovalOverlay {
                 permissions_options(),
                 "Would you like to grant these permissions?".to_string(),
             ),
-            ApprovalRequest::ApplyPatch { .. } => (
-                patch_options(),
+            ApprovalRequest::ApplyPatch { cwd, changes, .. } => (
+                patch_options(patch_selection_entries(changes, cwd).len() > 1),
                 "Would you like to make the following edits?".to_string(),
             ),
             ApprovalRequest::McpElicitation { server_name, .. } => (
@@ -238,35 +246,73 @@ impl ApprovalOverlay {
             header,
         ]));
 
-        let items = options
+        let items = options.iter().map(option_item).collect();
+
+        let params = SelectionViewParams {
+            footer_hint: Some(approval_footer_hint(request)),
+            items,
+            header,
+            ..Default::default()
+        };
+
+        (options, params)
+    }
+
+    /// Replaces the option list with one toggle per file and hunk so the user
+    /// can pick which parts of the patch to apply.
+    fn enter_patch_selection(&mut self) {
+        let Some(request) = self.current_request.as_ref() else {
+            return;
+        };
+        let ApprovalRequest::ApplyPatch { cwd, changes, .. } = request else {
+            return;
+        };
+        let entries = patch_selection_entries(changes, cwd);
+        let options = patch_selection_options();
+        let mut items: Vec<SelectionItem> = entries
             .iter()
-            .map(|opt| SelectionItem {
-                name: opt.label.clone(),
-                display_shortcut: opt
-                    .display_shortcut
-                    .or_else(|| opt.additional_shortcuts.first().copied()),
+            .map(|entry| SelectionItem {
+                name: entry.label.clone(),
+                toggle: Some(SelectionToggle {
+                    is_on: true,
+                    action: Box::new(|_is_on, _tx| {}),
+                }),
                 dismiss_on_select: false,
                 ..Default::default()
             })
             .collect();
-
+        items.extend(options.iter().map(option_item));
+        let header = Box::new(ColumnRenderable::with([
+            Line::from("Choose which changes to apply".bold()).into(),
+            Line::from("").into(),
+            build_header(request),
+        ]));
         let params = SelectionViewParams {
-            footer_hint: Some(approval_footer_hint(request)),
+            footer_hint: Some(patch_selection_footer_hint()),
             items,
             header,
             ..Default::default()
         };
-
-        (options, params)
+        self.list = ListSelectionView::new(params, self.app_event_tx.clone());
+        self.options = options;
+        self.patch_selection = Some(entries);
     }
 
     fn apply_selection(&mut self, actual_idx: usize) {
         if self.current_complete {
             return;
         }
-        let Some(option) = self.options.get(actual_idx) else {
+        let option_offset = self.patch_selection.as_ref().map_or(0, Vec::len);
+        let Some(option) = actual_idx
+            .checked_sub(option_offset)
+            .and_then(|idx| self.options.get(idx))
+        else {
             return;
         };
+        if matches!(option.decision, ApprovalDecision::SelectPatchChanges) {
+            self.enter_patch_selection();
+            return;
+        }
         if let Some(request) = self.current_request.as_ref() {
             match (request, &option.decision) {
                 (ApprovalRequest::Exec { id, command, .. }, ApprovalDecision::Review(decision)) => {
@@ -283,6 +329,13 @@ impl ApprovalOverlay {
                 (ApprovalRequest::ApplyPatch { id, .. }, ApprovalDecision::Review(decision)) => {
                     self.handle_patch_decision(id, decision.clone());
                 }
+                (ApprovalRequest::ApplyPatch { id, .. }, ApprovalDecision::ApplyPatchSelection) => {
+                    if let Some(entries) = self.patch_selection.as_ref() {
+                        let decision =
+                            patch_selection_decision(entries, &self.list.toggle_states());
+                        self.handle_patch_decision(id, decision);
+                    }
+                }
                 (
                     ApprovalRequest::McpElicitation {
                         server_name,
@@ -450,7 +503,8 @@ impl ApprovalOverlay {
                     .iter()
                     .position(|opt| opt.shortcuts().any(|s| s.is_press(*e)))
                 {
-                    self.apply_selection(idx);
+                    let option_offset = self.patch_selection.as_ref().map_or(0, Vec::len);
+                    self.apply_selection(option_offset + idx);
                     true
                 } else {
                     false
@@ -690,6 +744,10 @@ enum ApprovalDecision {
     Review(ReviewDecision),
     Permissions(PermissionsDecision),
     McpElicitation(ElicitationAction),
+    /// Switch to choosing individual files and hunks of a patch.
+    SelectPatchChanges,
+    /// Apply the files and hunks left toggled on.
+    ApplyPatchSelection,
 }
 
 #[derive(Clone, Copy)]
@@ -893,8 +951,19 @@ fn path_label(base: &str, subpath: &Option<PathBuf>) -> String {
     }
 }
 
-fn patch_options() -> Vec<ApprovalOption> {
-    vec![
+fn option_item(option: &ApprovalOption) -> SelectionItem {
+    SelectionItem {
+        name: option.label.clone(),
+        display_shortcut: option
+            .display_shortcut
+            .or_else(|| option.additional_shortcuts.first().copied()),
+        dismiss_on_select: false,
+        ..Default::default()
+    }
+}
+
+fn patch_options(can_select_changes: bool) -> Vec<ApprovalOption> {
+    let mut options = vec![
         ApprovalOption {
             label: "Yes, proceed".to_string(),
             decision: ApprovalDecision::Review(ReviewDecision::Approved),
@@ -907,6 +976,32 @@ fn patch_options() -> Vec<ApprovalOption> {
             display_shortcut: None,
             additional_shortcuts: vec![key_hint::plain(KeyCode::Char('a'))],
         },
+    ];
+    if can_select_changes {
+        options.push(ApprovalOption {
+            label: "Yes, but only the changes I choose".to_string(),
+            decision: ApprovalDecision::SelectPatchChanges,
+            display_shortcut: None,
+            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('p'))],
+        });
+    }
+    options.push(ApprovalOption {
+        label: "No, and tell Codex what to do differently".to_string(),
+        decision: ApprovalDecision::Review(ReviewDecision::Abort),
+        display_shortcut: Some(key_hint::plain(KeyCode::Esc)),
+        additional_shortcuts: vec![key_hint::plain(KeyCode::Char('n'))],
+    });
+    options
+}
+
+fn patch_selection_options() -> Vec<ApprovalOption> {
+    vec![
+        ApprovalOption {
+            label: "Apply the selected changes".to_string(),
+            decision: ApprovalDecision::ApplyPatchSelection,
+            display_shortcut: None,
+            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('y'))],
+        },
         ApprovalOption {
             label: "No, and tell Codex what to do differently".to_string(),
             decision: ApprovalDecision::Review(ReviewDecision::Abort),
@@ -916,6 +1011,92 @@ fn patch_options() -> Vec<ApprovalOption> {
     ]
 }
 
+fn patch_selection_footer_hint() -> Line<'static> {
+    Line::from(vec![
+        "Press ".into(),
+        key_hint::plain(KeyCode::Char(' ')).into(),
+        " to toggle a change, ".into(),
+        key_hint::plain(KeyCode::Char('y')).into(),
+        " to apply or ".into(),
+        key_hint::plain(KeyCode::Esc).into(),
+        " to cancel".into(),
+    ])
+}
+
+/// One toggle row when choosing the parts of a patch to apply: either a whole
+/// file or one `@@` hunk of an updated file.
+#[derive(Debug)]
+struct PatchSelectionEntry {
+    path: PathBuf,
+    hunk: Option<u32>,
+    label: String,
+}
+
+/// Lists each changed file, followed by its hunks when it has more than one.
+fn patch_selection_entries(
+    changes: &HashMap<PathBuf, FileChange>,
+    cwd: &AbsolutePathBuf,
+) -> Vec<PatchSelectionEntry> {
+    let mut changes: Vec<(&PathBuf, &FileChange)> = changes.iter().collect();
+    changes.sort_by_key(|(path, _)| *path);
+    let mut entries = Vec::new();
+    for (path, change) in changes {
+        entries.push(PatchSelectionEntry {
+            path: path.clone(),
+            hunk: None,
+            label: display_path_for(path, cwd.as_path()),
+        });
+        let hunks = change.unified_diff_hunks();
+        if hunks.len() < 2 {
+            continue;
+        }
+        for (index, hunk) in (0u32..).zip(hunks) {
+            let header = hunk.lines().next().unwrap_or_default();
+            entries.push(PatchSelectionEntry {
+                path: path.clone(),
+                hunk: Some(index),
+                label: format!("  {header}"),
+            });
+        }
+    }
+    entries
+}
+
+/// Builds the decision for the toggled-on entries. A file row that is off
+/// rejects the whole file; otherwise only its toggled-on hunks are applied.
+fn patch_selection_decision(entries: &[PatchSelectionEntry], is_on: &[bool]) -> ReviewDecision {
+    let mut accepted_files = Vec::new();
+    let mut rejected_any = false;
+    let mut rows = entries.iter().zip(is_on.iter().copied()).peekable();
+    while let Some((file, file_on)) = rows.next() {
+        let mut hunks = Vec::new();
+        let mut rejected_hunk = false;
+        while let Some((entry, on)) = rows.next_if(|(entry, _)| entry.hunk.is_some()) {
+            match entry.hunk {
+                Some(hunk) if on => hunks.push(hunk),
+                _ => rejected_hunk = true,
+            }
+        }
+        if !file_on || (rejected_hunk && hunks.is_empty()) {
+            rejected_any = true;
+            continue;
+        }
+        rejected_any |= rejected_hunk;
+        accepted_files.push(PatchFileApproval {
+            path: file.path.clone(),
+            hunks: rejected_hunk.then_some(hunks),
+        });
+    }
+
+    if !rejected_any {
+        ReviewDecision::Approved
+    } else if accepted_files.is_empty() {
+        ReviewDecision::Denied
+    } else {
+        ReviewDecision::ApprovedPartialPatch { accepted_files }
+    }
+}
+
 fn permissions_options() -> Vec<ApprovalOption> {
     vec![
         ApprovalOption {
@@ -1153,6 +1334,102 @@ mod tests {
         );
     }
 
+    fn make_multi_file_patch_request() -> ApprovalRequest {
+        ApprovalRequest::ApplyPatch {
+            thread_id: ThreadId::new(),
+            thread_label: None,
+            id: "patch".to_string(),
+            reason: None,
+            cwd: absolute_path("/tmp"),
+            changes: HashMap::from([
+                (
+                    PathBuf::from("/tmp/Cargo.lock"),
+                    FileChange::Update {
+                        unified_diff: "@@ -1 +1 @@\n-a\n+b\n".to_string(),
+                        move_path: None,
+                    },
+                ),
+                (
+                    PathBuf::from("/tmp/src/lib.rs"),
+                    FileChange::Update {
+                        unified_diff: "@@ -1 +1 @@\n-x\n+y\n@@ -9 +9 @@\n-z\n+w\n".to_string(),
+                        move_path: None,
+                    },
+                ),
+            ]),
+        }
+    }
+
+    #[test]
+    fn patch_selection_rejects_toggled_off_file_and_hunk() {
+        let (tx, mut rx) = unbounded_channel::<AppEvent>();
+        let tx = AppEventSender::new(tx);
+        let mut view = ApprovalOverlay::new(
+            make_multi_file_patch_request(),
+            tx,
+            Features::with_defaults(),
+        );
+        view.handle_key_event(KeyEvent::new(KeyCode::Char('p'), KeyModifiers::NONE));
+        assert!(!view.is_complete());
+
+        // Rows: Cargo.lock, src/lib.rs, its first hunk, its second hunk.
+        view.handle_key_event(KeyEvent::new(KeyCode::Char(' '), KeyModifiers::NONE));
+        view.handle_key_event(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+        view.handle_key_event(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+        view.handle_key_event(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+        view.handle_key_event(KeyEvent::new(KeyCode::Char(' '), KeyModifiers::NONE));
+        view.handle_key_event(KeyEvent::new(KeyCode::Char('y'), KeyModifiers::NONE));
+
+        let mut decisions = Vec::new();
+        while let Ok(ev) = rx.try_recv() {
+            if let AppEvent::SubmitThreadOp {
+                op: Op::PatchApproval { decision, .. },
+                ..
+            } = ev
+            {
+                decisions.push(decision);
+            }
+        }
+        assert_eq!(
+            decisions,
+            vec![ReviewDecision::ApprovedPartialPatch {
+                accepted_files: vec![PatchFileApproval {
+                    path: PathBuf::from("/tmp/src/lib.rs"),
+                    hunks: Some(vec![0]),
+                }],
+            }]
+        );
+        assert!(view.is_complete());
+    }
+
+    #[test]
+    fn patch_selection_decision_collapses_full_and_empty_selections() {
+        let request = make_multi_file_patch_request();
+        let ApprovalRequest::ApplyPatch { cwd, changes, .. } = &request else {
+            panic!("expected a patch request");
+        };
+        let entries = patch_selection_entries(changes, cwd);
+        assert_eq!(entries.len(), 4);
+
+        assert_eq!(
+            patch_selection_decision(&entries, &[true, true, true, true]),
+            ReviewDecision::Approved
+        );
+        assert_eq!(
+            patch_selection_decision(&entries, &[false, true, false, false]),
+            ReviewDecision::Denied
+        );
+        assert_eq!(
+            patch_selection_decision(&entries, &[false, true, true, true]),
+            ReviewDecision::ApprovedPartialPatch {
+                accepted_files: vec![PatchFileApproval {
+                    path: PathBuf::from("/tmp/src/lib.rs"),
+                    hunks: None,
+                }],
+            }
+        );
+    }
+
     #[test]
     fn exec_prefix_option_emits_execpolicy_amendment() {
         let (tx, mut rx) = unbounded_channel::<AppEvent>();
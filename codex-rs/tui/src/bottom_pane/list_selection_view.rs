@@ -648,6 +648,15 @@ impl ListSelectionView {
         self.last_selected_actual_idx.take()
     }
 
+    /// Returns whether each item's toggle is on, in item order. Items without
+    /// a toggle report `false`.
+    pub(crate) fn toggle_states(&self) -> Vec<bool> {
+        self.active_items()
+            .iter()
+            .map(|item| item.toggle.as_ref().is_some_and(|toggle| toggle.is_on))
+            .collect()
+    }
+
     fn rows_width(total_width: u16) -> u16 {
         total_width.saturating_sub(2)
     }
@@ -1093,5 +1093,10 @@ fn hook_event_label(event_name: codex_protocol::protocol::HookEventName) -> &'st
         codex_protocol::protocol::HookEventName::SessionStart => "SessionStart",
         codex_protocol::protocol::HookEventName::UserPromptSubmit => "UserPromptSubmit",
         codex_protocol::protocol::HookEventName::Stop => "Stop",
+        codex_protocol::protocol::HookEventName::PreCompact => "PreCompact",
+        codex_protocol::protocol::HookEventName::SessionEnd => "SessionEnd",
+        codex_protocol::protocol::HookEventName::SubagentStart => "SubagentStart",
+        codex_protocol::protocol::HookEventName::SubagentStop => "SubagentStop",
+        codex_protocol::protocol::HookEventName::Notification => "Notification",
     }
 }
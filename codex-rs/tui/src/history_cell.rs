@@ -816,7 +816,8 @@ pub fn new_approval_decision_cell(
     use codex_protocol::protocol::ReviewDecision::*;
 
     let (symbol, summary): (Span<'static>, Vec<Span<'static>>) = match decision {
-        Approved => {
+        // Patch-only decisions never reach exec approvals, so they read as a plain approval.
+        Approved | ApprovedPartialPatch { .. } | ApprovedEditedPatch { .. } => {
             let snippet = Span::from(exec_snippet(&command)).dim();
             (
                 "✔ ".green(),
@@ -2,6 +2,7 @@ pub mod pipe;
 mod process;
 pub mod process_group;
 pub mod pty;
+pub mod resource_limits;
 #[cfg(test)]
 mod tests;
 #[cfg(windows)]
@@ -20,6 +20,10 @@ use tokio::task::JoinHandle;
 use crate::process::ChildTerminator;
 use crate::process::ProcessHandle;
 use crate::process::SpawnedProcess;
+use crate::resource_limits::ExceededLimitSlot;
+use crate::resource_limits::OutputLimit;
+use crate::resource_limits::ResourceLimiter;
+use crate::resource_limits::ResourceLimits;
 
 #[cfg(target_os = "linux")]
 use libc;
@@ -68,8 +72,11 @@ fn kill_process(pid: u32) -> io::Result<()> {
     }
 }
 
-async fn read_output_stream<R>(mut reader: R, output_tx: mpsc::Sender<Vec<u8>>)
-where
+async fn read_output_stream<R>(
+    mut reader: R,
+    output_tx: mpsc::Sender<Vec<u8>>,
+    output_limit: Option<Arc<OutputLimit>>,
+) where
     R: AsyncRead + Unpin,
 {
     let mut buf = vec![0u8; 8_192];
@@ -78,6 +85,9 @@ where
             Ok(0) => break,
             Ok(n) => {
                 let _ = output_tx.send(buf[..n].to_vec()).await;
+                if let Some(output_limit) = output_limit.as_ref() {
+                    output_limit.record(n);
+                }
             }
             Err(ref e) if e.kind() == ErrorKind::Interrupted => continue,
             Err(_) => break,
@@ -91,6 +101,7 @@ enum PipeStdinMode {
     Null,
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn spawn_process_with_stdin_mode(
     program: &str,
     args: &[String],
@@ -99,10 +110,12 @@ async fn spawn_process_with_stdin_mode(
     arg0: &Option<String>,
     stdin_mode: PipeStdinMode,
     inherited_fds: &[i32],
+    resource_limits: &ResourceLimits,
 ) -> Result<SpawnedProcess> {
     if program.is_empty() {
         anyhow::bail!("missing program for pipe spawn");
     }
+    let limiter = ResourceLimiter::new(resource_limits);
 
     #[cfg(not(unix))]
     let _ = inherited_fds;
@@ -117,11 +130,16 @@ async fn spawn_process_with_stdin_mode(
     #[cfg(unix)]
     let inherited_fds = inherited_fds.to_vec();
     #[cfg(unix)]
+    let pre_exec_limits = limiter.pre_exec();
+    #[cfg(unix)]
     unsafe {
         command.pre_exec(move || {
             crate::process_group::detach_from_tty()?;
             #[cfg(target_os = "linux")]
             crate::process_group::set_parent_death_signal(parent_pid)?;
+            // SAFETY: `apply` only makes async-signal-safe calls, which is
+            // what `pre_exec` requires between fork and exec.
+            pre_exec_limits.apply()?;
             crate::pty::close_inherited_fds_except(&inherited_fds);
             Ok(())
         });
@@ -157,6 +175,8 @@ async fn spawn_process_with_stdin_mode(
     let stdin = child.stdin.take();
     let stdout = child.stdout.take();
     let stderr = child.stderr.take();
+    let exceeded_limit = ExceededLimitSlot::default();
+    let output_limit = OutputLimit::new(resource_limits, pid, &exceeded_limit);
 
     let (writer_tx, mut writer_rx) = mpsc::channel::<Vec<u8>>(128);
     let (stdout_tx, stdout_rx) = mpsc::channel::<Vec<u8>>(128);
@@ -176,14 +196,15 @@ async fn spawn_process_with_stdin_mode(
 
     let stdout_handle = stdout.map(|stdout| {
         let stdout_tx = stdout_tx.clone();
+        let output_limit = output_limit.clone();
         tokio::spawn(async move {
-            read_output_stream(BufReader::new(stdout), stdout_tx).await;
+            read_output_stream(BufReader::new(stdout), stdout_tx, output_limit).await;
         })
     });
     let stderr_handle = stderr.map(|stderr| {
         let stderr_tx = stderr_tx.clone();
         tokio::spawn(async move {
-            read_output_stream(BufReader::new(stderr), stderr_tx).await;
+            read_output_stream(BufReader::new(stderr), stderr_tx, output_limit).await;
         })
     });
     let mut reader_abort_handles = Vec::new();
@@ -207,11 +228,28 @@ async fn spawn_process_with_stdin_mode(
     let wait_exit_status = Arc::clone(&exit_status);
     let exit_code = Arc::new(StdMutex::new(None));
     let wait_exit_code = Arc::clone(&exit_code);
+    let wait_exceeded_limit = Arc::clone(&exceeded_limit);
+    #[cfg(unix)]
+    crate::resource_limits::spawn_wall_time_killer(
+        resource_limits.wall_time,
+        process_group_id,
+        Arc::clone(&exit_status),
+        Arc::clone(&exceeded_limit),
+    );
     let wait_handle: JoinHandle<()> = tokio::spawn(async move {
         let code = match child.wait().await {
-            Ok(status) => status.code().unwrap_or(-1),
+            Ok(status) => {
+                #[cfg(unix)]
+                if let Some(limit) = limiter.exceeded_kernel_limit(status) {
+                    crate::resource_limits::record_exceeded_limit(&wait_exceeded_limit, limit);
+                }
+                status.code().unwrap_or(-1)
+            }
             Err(_) => -1,
         };
+        #[cfg(not(unix))]
+        let _ = wait_exceeded_limit;
+        drop(limiter);
         wait_exit_status.store(true, std::sync::atomic::Ordering::SeqCst);
         if let Ok(mut guard) = wait_exit_code.lock() {
             *guard = Some(code);
@@ -233,6 +271,7 @@ async fn spawn_process_with_stdin_mode(
         wait_handle,
         exit_status,
         exit_code,
+        exceeded_limit,
         /*pty_handles*/ None,
         /*resizer*/ None,
     );
@@ -253,7 +292,17 @@ pub async fn spawn_process(
     env: &HashMap<String, String>,
     arg0: &Option<String>,
 ) -> Result<SpawnedProcess> {
-    spawn_process_with_stdin_mode(program, args, cwd, env, arg0, PipeStdinMode::Piped, &[]).await
+    spawn_process_with_stdin_mode(
+        program,
+        args,
+        cwd,
+        env,
+        arg0,
+        PipeStdinMode::Piped,
+        &[],
+        &ResourceLimits::default(),
+    )
+    .await
 }
 
 /// Spawn a process using regular pipes, but close stdin immediately.
@@ -264,11 +313,21 @@ pub async fn spawn_process_no_stdin(
     env: &HashMap<String, String>,
     arg0: &Option<String>,
 ) -> Result<SpawnedProcess> {
-    spawn_process_no_stdin_with_inherited_fds(program, args, cwd, env, arg0, &[]).await
+    spawn_process_no_stdin_with_inherited_fds(
+        program,
+        args,
+        cwd,
+        env,
+        arg0,
+        &[],
+        &ResourceLimits::default(),
+    )
+    .await
 }
 
 /// Spawn a process using regular pipes, close stdin immediately, and preserve
-/// selected inherited file descriptors across exec on Unix.
+/// selected inherited file descriptors across exec on Unix. `resource_limits`
+/// applies to the process and its descendants.
 pub async fn spawn_process_no_stdin_with_inherited_fds(
     program: &str,
     args: &[String],
@@ -276,6 +335,7 @@ pub async fn spawn_process_no_stdin_with_inherited_fds(
     env: &HashMap<String, String>,
     arg0: &Option<String>,
     inherited_fds: &[i32],
+    resource_limits: &ResourceLimits,
 ) -> Result<SpawnedProcess> {
     spawn_process_with_stdin_mode(
         program,
@@ -285,6 +345,7 @@ pub async fn spawn_process_no_stdin_with_inherited_fds(
         arg0,
         PipeStdinMode::Null,
         inherited_fds,
+        resource_limits,
     )
     .await
 }
@@ -34,6 +34,15 @@ use crate::process::PtyHandles;
 use crate::process::PtyMasterHandle;
 use crate::process::SpawnedProcess;
 use crate::process::TerminalSize;
+#[cfg(unix)]
+use crate::resource_limits::ExceededLimitSlot;
+#[cfg(unix)]
+use crate::resource_limits::OutputLimit;
+#[cfg(unix)]
+use crate::resource_limits::ResourceLimiter;
+use crate::resource_limits::ResourceLimits;
+#[cfg(unix)]
+use crate::resource_limits::record_exceeded_limit;
 
 /// Returns true when ConPTY support is available (Windows only).
 #[cfg(windows)]
@@ -107,11 +116,23 @@ pub async fn spawn_process(
     arg0: &Option<String>,
     size: TerminalSize,
 ) -> Result<SpawnedProcess> {
-    spawn_process_with_inherited_fds(program, args, cwd, env, arg0, size, &[]).await
+    spawn_process_with_inherited_fds(
+        program,
+        args,
+        cwd,
+        env,
+        arg0,
+        size,
+        &[],
+        &ResourceLimits::default(),
+    )
+    .await
 }
 
 /// Spawn a process attached to a PTY, preserving any inherited file
-/// descriptors listed in `inherited_fds` across exec on Unix.
+/// descriptors listed in `inherited_fds` across exec on Unix. On Unix,
+/// `resource_limits` applies to the process and its descendants.
+#[allow(clippy::too_many_arguments)]
 pub async fn spawn_process_with_inherited_fds(
     program: &str,
     args: &[String],
@@ -120,18 +141,29 @@ pub async fn spawn_process_with_inherited_fds(
     arg0: &Option<String>,
     size: TerminalSize,
     inherited_fds: &[i32],
+    resource_limits: &ResourceLimits,
 ) -> Result<SpawnedProcess> {
     if program.is_empty() {
         anyhow::bail!("missing program for PTY spawn");
     }
 
     #[cfg(not(unix))]
-    let _ = inherited_fds;
+    let _ = (inherited_fds, resource_limits);
 
+    // portable-pty has no pre_exec hook, so limits also need the Unix path.
     #[cfg(unix)]
-    if !inherited_fds.is_empty() {
-        return spawn_process_preserving_fds(program, args, cwd, env, arg0, size, inherited_fds)
-            .await;
+    if !inherited_fds.is_empty() || !resource_limits.is_empty() {
+        return spawn_process_preserving_fds(
+            program,
+            args,
+            cwd,
+            env,
+            arg0,
+            size,
+            inherited_fds,
+            resource_limits,
+        )
+        .await;
     }
 
     spawn_process_portable(program, args, cwd, env, arg0, size).await
@@ -241,6 +273,7 @@ async fn spawn_process_portable(
         wait_handle,
         exit_status,
         exit_code,
+        /*exceeded_limit*/ Arc::default(),
         Some(handles),
         /*resizer*/ None,
     );
@@ -254,6 +287,7 @@ async fn spawn_process_portable(
 }
 
 #[cfg(unix)]
+#[allow(clippy::too_many_arguments)]
 async fn spawn_process_preserving_fds(
     program: &str,
     args: &[String],
@@ -262,7 +296,10 @@ async fn spawn_process_preserving_fds(
     arg0: &Option<String>,
     size: TerminalSize,
     inherited_fds: &[RawFd],
+    resource_limits: &ResourceLimits,
 ) -> Result<SpawnedProcess> {
+    let limiter = ResourceLimiter::new(resource_limits);
+    let pre_exec_limits = limiter.pre_exec();
     let (master, slave) = open_unix_pty(size)?;
     let mut command = StdCommand::new(program);
     if let Some(arg0) = arg0 {
@@ -317,6 +354,9 @@ async fn spawn_process_preserving_fds(
                     return Err(std::io::Error::last_os_error());
                 }
 
+                // SAFETY: `apply` only makes async-signal-safe calls, which
+                // is what `pre_exec` requires between fork and exec.
+                pre_exec_limits.apply()?;
                 close_inherited_fds_except(&inherited_fds);
                 Ok(())
             });
@@ -325,6 +365,8 @@ async fn spawn_process_preserving_fds(
     let mut child = command.spawn()?;
     drop(slave);
     let process_group_id = child.id();
+    let exceeded_limit = ExceededLimitSlot::default();
+    let output_limit = OutputLimit::new(resource_limits, process_group_id, &exceeded_limit);
 
     let (writer_tx, mut writer_rx) = mpsc::channel::<Vec<u8>>(128);
     let (stdout_tx, stdout_rx) = mpsc::channel::<Vec<u8>>(128);
@@ -337,6 +379,9 @@ async fn spawn_process_preserving_fds(
                 Ok(0) => break,
                 Ok(n) => {
                     let _ = stdout_tx.blocking_send(buf[..n].to_vec());
+                    if let Some(output_limit) = output_limit.as_ref() {
+                        output_limit.record(n);
+                    }
                 }
                 Err(ref e) if e.kind() == ErrorKind::Interrupted => continue,
                 Err(ref e) if e.kind() == ErrorKind::WouldBlock => {
@@ -366,11 +411,24 @@ async fn spawn_process_preserving_fds(
     let wait_exit_status = Arc::clone(&exit_status);
     let exit_code = Arc::new(StdMutex::new(None));
     let wait_exit_code = Arc::clone(&exit_code);
+    let wait_exceeded_limit = Arc::clone(&exceeded_limit);
+    crate::resource_limits::spawn_wall_time_killer(
+        resource_limits.wall_time,
+        process_group_id,
+        Arc::clone(&exit_status),
+        Arc::clone(&exceeded_limit),
+    );
     let wait_handle: JoinHandle<()> = tokio::task::spawn_blocking(move || {
         let code = match child.wait() {
-            Ok(status) => status.code().unwrap_or(-1),
+            Ok(status) => {
+                if let Some(limit) = limiter.exceeded_kernel_limit(status) {
+                    record_exceeded_limit(&wait_exceeded_limit, limit);
+                }
+                status.code().unwrap_or(-1)
+            }
             Err(_) => -1,
         };
+        drop(limiter);
         wait_exit_status.store(true, std::sync::atomic::Ordering::SeqCst);
         if let Ok(mut guard) = wait_exit_code.lock() {
             *guard = Some(code);
@@ -395,6 +453,7 @@ async fn spawn_process_preserving_fds(
         wait_handle,
         exit_status,
         exit_code,
+        exceeded_limit,
         Some(handles),
         /*resizer*/ None,
     );
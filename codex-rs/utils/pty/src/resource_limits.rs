@@ -0,0 +1,489 @@
+//! Per-command resource limits for spawned processes.
+//!
+//! Kernel-enforced limits are applied in two layers:
+//! - `setrlimit(2)` in `pre_exec`, inherited by every descendant of the
+//!   command (CPU seconds, open files, and fallbacks for memory and process
+//!   count).
+//! - On Linux, when a delegated cgroup v2 parent is configured, a fresh leaf
+//!   cgroup per command with `memory.max` and `pids.max`. The leaf also
+//!   records whether the kernel enforced one of those limits, and tearing it
+//!   down kills anything the command left behind.
+//!
+//! Output size and wall time are not kernel limits; the code reading the
+//! command's output enforces them.
+
+use std::io;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex as StdMutex;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+/// Resource profile for a single spawned command. `None` leaves a resource
+/// unlimited.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResourceLimits {
+    /// Maximum resident memory in bytes. Enforced with `memory.max` in the
+    /// cgroup leaf, or approximated with `RLIMIT_DATA` without one.
+    pub max_rss_bytes: Option<u64>,
+    /// CPU time in seconds. The kernel sends `SIGXCPU` when it runs out and
+    /// `SIGKILL` one second later.
+    pub cpu_seconds: Option<u64>,
+    /// Maximum processes and threads. Enforced with `pids.max` in the cgroup
+    /// leaf, or with `RLIMIT_NPROC` on top of what the user already runs.
+    pub max_pids: Option<u64>,
+    /// Maximum open file descriptors per process.
+    pub max_open_files: Option<u64>,
+    /// Combined stdout and stderr bytes before the command is killed.
+    pub max_output_bytes: Option<u64>,
+    /// Wall-clock time before the command is killed.
+    pub wall_time: Option<Duration>,
+    /// Delegated cgroup v2 directory to create per-command leaves under.
+    pub cgroup_parent: Option<PathBuf>,
+}
+
+impl ResourceLimits {
+    pub fn is_empty(&self) -> bool {
+        self.max_rss_bytes.is_none()
+            && self.cpu_seconds.is_none()
+            && self.max_pids.is_none()
+            && self.max_open_files.is_none()
+            && self.max_output_bytes.is_none()
+            && self.wall_time.is_none()
+    }
+
+    fn has_kernel_limits(&self) -> bool {
+        self.max_rss_bytes.is_some()
+            || self.cpu_seconds.is_some()
+            || self.max_pids.is_some()
+            || self.max_open_files.is_some()
+    }
+}
+
+/// Limit the kernel enforced inside a command's cgroup leaf.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CgroupLimit {
+    Memory,
+    Pids,
+}
+
+/// Limit that ended a command, when it can be told apart from an ordinary
+/// failure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExceededResourceLimit {
+    Memory,
+    CpuTime,
+    Processes,
+    OutputBytes,
+    WallTime,
+}
+
+impl From<CgroupLimit> for ExceededResourceLimit {
+    fn from(limit: CgroupLimit) -> Self {
+        match limit {
+            CgroupLimit::Memory => Self::Memory,
+            CgroupLimit::Pids => Self::Processes,
+        }
+    }
+}
+
+/// First limit that ended a command, shared between the tasks enforcing the
+/// limits and the process handle that reports it.
+pub(crate) type ExceededLimitSlot = Arc<StdMutex<Option<ExceededResourceLimit>>>;
+
+/// Records `limit` unless an earlier one already ended the command.
+pub(crate) fn record_exceeded_limit(slot: &ExceededLimitSlot, limit: ExceededResourceLimit) {
+    if let Ok(mut guard) = slot.lock()
+        && guard.is_none()
+    {
+        *guard = Some(limit);
+    }
+}
+
+/// Kernel-side state for one command: the rlimits to apply before `exec` and,
+/// on Linux, the cgroup leaf the command runs in. Keep it alive until the
+/// command exits; dropping it removes the leaf.
+#[derive(Debug, Default)]
+pub struct ResourceLimiter {
+    #[cfg(unix)]
+    rlimits: RlimitPlan,
+    #[cfg(target_os = "linux")]
+    cgroup: Option<CgroupLeaf>,
+}
+
+impl ResourceLimiter {
+    /// Prepares the limits for a command about to be spawned. If the cgroup
+    /// leaf cannot be created, memory and process limits fall back to
+    /// rlimits; [`ResourceLimiter::has_cgroup`] tells callers which happened.
+    pub fn new(limits: &ResourceLimits) -> Self {
+        if !limits.has_kernel_limits() {
+            return Self::default();
+        }
+
+        #[cfg(target_os = "linux")]
+        let cgroup = limits
+            .cgroup_parent
+            .as_ref()
+            .and_then(|parent| CgroupLeaf::create(parent, limits).ok());
+
+        Self {
+            #[cfg(target_os = "linux")]
+            rlimits: RlimitPlan::new(limits, cgroup.is_some()),
+            #[cfg(all(unix, not(target_os = "linux")))]
+            rlimits: RlimitPlan::new(limits, /*has_cgroup*/ false),
+            #[cfg(target_os = "linux")]
+            cgroup,
+        }
+    }
+
+    pub fn has_cgroup(&self) -> bool {
+        #[cfg(target_os = "linux")]
+        {
+            self.cgroup.is_some()
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            false
+        }
+    }
+
+    /// Returns the async-signal-safe setup to run in the child's `pre_exec`.
+    #[cfg(unix)]
+    pub fn pre_exec(&self) -> PreExecLimits {
+        PreExecLimits {
+            rlimits: self.rlimits,
+            #[cfg(target_os = "linux")]
+            cgroup_procs_fd: self.cgroup.as_ref().map(CgroupLeaf::procs_fd),
+        }
+    }
+
+    /// Reports a cgroup limit the kernel enforced while the command ran.
+    pub fn exceeded_limit(&self) -> Option<CgroupLimit> {
+        #[cfg(target_os = "linux")]
+        {
+            self.cgroup.as_ref().and_then(CgroupLeaf::exceeded_limit)
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            None
+        }
+    }
+
+    /// Works out which kernel-enforced limit, if any, ended a command that
+    /// exited with `status`.
+    #[cfg(unix)]
+    pub(crate) fn exceeded_kernel_limit(
+        &self,
+        status: std::process::ExitStatus,
+    ) -> Option<ExceededResourceLimit> {
+        use std::os::unix::process::ExitStatusExt;
+
+        if status.success() {
+            return None;
+        }
+        if let Some(limit) = self.exceeded_limit() {
+            return Some(limit.into());
+        }
+        (self.rlimits.cpu.is_some() && status.signal() == Some(libc::SIGXCPU))
+            .then_some(ExceededResourceLimit::CpuTime)
+    }
+}
+
+/// Child-side half of [`ResourceLimiter`], copied into `pre_exec` closures.
+#[cfg(unix)]
+#[derive(Debug, Clone, Copy)]
+pub struct PreExecLimits {
+    rlimits: RlimitPlan,
+    #[cfg(target_os = "linux")]
+    cgroup_procs_fd: Option<std::os::fd::RawFd>,
+}
+
+#[cfg(unix)]
+impl PreExecLimits {
+    /// Moves the child into its cgroup leaf and applies the rlimits. Only
+    /// makes async-signal-safe calls, so it can run between fork and exec.
+    pub fn apply(&self) -> io::Result<()> {
+        #[cfg(target_os = "linux")]
+        if let Some(fd) = self.cgroup_procs_fd {
+            // Writing "0" moves the writing process into the cgroup.
+            // SAFETY: `fd` is the leaf's open `cgroup.procs` file, which the
+            // parent keeps alive until the child exits, and the buffer is a
+            // one-byte static string.
+            let written = unsafe { libc::write(fd, b"0".as_ptr().cast(), 1) };
+            if written != 1 {
+                return Err(io::Error::last_os_error());
+            }
+        }
+        self.rlimits.apply()
+    }
+}
+
+/// Soft and hard values per resource. They are capped to the current hard
+/// limit in the child, since an unprivileged process cannot raise it.
+#[cfg(unix)]
+#[derive(Debug, Clone, Copy, Default)]
+struct RlimitPlan {
+    cpu: Option<(u64, u64)>,
+    data: Option<(u64, u64)>,
+    nproc: Option<(u64, u64)>,
+    nofile: Option<(u64, u64)>,
+}
+
+#[cfg(unix)]
+impl RlimitPlan {
+    fn new(limits: &ResourceLimits, has_cgroup: bool) -> Self {
+        let nproc = if has_cgroup {
+            None
+        } else {
+            // RLIMIT_NPROC counts every process of the user, not just this
+            // command's, so budget on top of what is already running.
+            limits.max_pids.and_then(|max_pids| {
+                running_tasks_for_current_user().map(|running| running.saturating_add(max_pids))
+            })
+        };
+        Self {
+            // The hard limit one second later turns an ignored SIGXCPU into a
+            // SIGKILL.
+            cpu: limits
+                .cpu_seconds
+                .map(|seconds| (seconds, seconds.saturating_add(1))),
+            data: limits
+                .max_rss_bytes
+                .filter(|_| !has_cgroup)
+                .map(|bytes| (bytes, bytes)),
+            nproc: nproc.map(|count| (count, count)),
+            nofile: limits.max_open_files.map(|count| (count, count)),
+        }
+    }
+
+    fn apply(&self) -> io::Result<()> {
+        for (resource, limit) in [
+            (libc::RLIMIT_CPU, self.cpu),
+            (libc::RLIMIT_DATA, self.data),
+            (libc::RLIMIT_NPROC, self.nproc),
+            (libc::RLIMIT_NOFILE, self.nofile),
+        ] {
+            let Some((soft, hard)) = limit else {
+                continue;
+            };
+            let mut current = libc::rlimit {
+                rlim_cur: libc::RLIM_INFINITY,
+                rlim_max: libc::RLIM_INFINITY,
+            };
+            // SAFETY: `current` is a valid, writable `rlimit` for the call.
+            if unsafe { libc::getrlimit(resource, &mut current) } != 0 {
+                return Err(io::Error::last_os_error());
+            }
+            let hard = (hard as libc::rlim_t).min(current.rlim_max);
+            let limit = libc::rlimit {
+                rlim_cur: (soft as libc::rlim_t).min(hard),
+                rlim_max: hard,
+            };
+            // SAFETY: `limit` is a valid `rlimit` that outlives the call.
+            if unsafe { libc::setrlimit(resource, &limit) } != 0 {
+                return Err(io::Error::last_os_error());
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn running_tasks_for_current_user() -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+
+    // SAFETY: getuid takes no arguments and cannot fail.
+    let uid = unsafe { libc::getuid() };
+    let mut tasks = 0u64;
+    for entry in std::fs::read_dir("/proc").ok()?.flatten() {
+        if !entry
+            .file_name()
+            .to_str()
+            .is_some_and(|name| name.bytes().all(|byte| byte.is_ascii_digit()))
+        {
+            continue;
+        }
+        if entry.metadata().is_ok_and(|metadata| metadata.uid() == uid)
+            && let Ok(threads) = std::fs::read_dir(entry.path().join("task"))
+        {
+            tasks = tasks.saturating_add(threads.count() as u64);
+        }
+    }
+    Some(tasks)
+}
+
+#[cfg(all(unix, not(target_os = "linux")))]
+fn running_tasks_for_current_user() -> Option<u64> {
+    // Without a cheap way to count the user's processes, a bare RLIMIT_NPROC
+    // would starve unrelated work, so the limit is skipped.
+    None
+}
+
+/// Leaf cgroup holding a single command and its descendants.
+#[cfg(target_os = "linux")]
+#[derive(Debug)]
+struct CgroupLeaf {
+    path: PathBuf,
+    procs: std::fs::File,
+}
+
+#[cfg(target_os = "linux")]
+impl CgroupLeaf {
+    fn create(parent: &std::path::Path, limits: &ResourceLimits) -> io::Result<Self> {
+        use std::sync::atomic::AtomicU64;
+        use std::sync::atomic::Ordering;
+
+        static NEXT_LEAF: AtomicU64 = AtomicU64::new(0);
+        let path = parent.join(format!(
+            "codex-exec-{}-{}",
+            std::process::id(),
+            NEXT_LEAF.fetch_add(1, Ordering::Relaxed)
+        ));
+        std::fs::create_dir(&path)?;
+        match Self::configure(&path, limits) {
+            Ok(procs) => Ok(Self { path, procs }),
+            Err(err) => {
+                let _ = std::fs::remove_dir(&path);
+                Err(err)
+            }
+        }
+    }
+
+    fn configure(path: &std::path::Path, limits: &ResourceLimits) -> io::Result<std::fs::File> {
+        if let Some(bytes) = limits.max_rss_bytes {
+            std::fs::write(path.join("memory.max"), bytes.to_string())?;
+            // Keep the command from dodging the limit by swapping. Not every
+            // kernel exposes swap accounting.
+            match std::fs::write(path.join("memory.swap.max"), "0") {
+                Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err),
+                _ => {}
+            }
+        }
+        if let Some(max_pids) = limits.max_pids {
+            std::fs::write(path.join("pids.max"), max_pids.to_string())?;
+        }
+        std::fs::OpenOptions::new()
+            .write(true)
+            .open(path.join("cgroup.procs"))
+    }
+
+    fn procs_fd(&self) -> std::os::fd::RawFd {
+        use std::os::fd::AsRawFd;
+
+        self.procs.as_raw_fd()
+    }
+
+    fn exceeded_limit(&self) -> Option<CgroupLimit> {
+        if self.event_count("memory.events", "oom_kill") > 0 {
+            Some(CgroupLimit::Memory)
+        } else if self.event_count("pids.events", "max") > 0 {
+            Some(CgroupLimit::Pids)
+        } else {
+            None
+        }
+    }
+
+    fn event_count(&self, file: &str, key: &str) -> u64 {
+        std::fs::read_to_string(self.path.join(file))
+            .ok()
+            .and_then(|events| {
+                events.lines().find_map(|line| {
+                    let (name, count) = line.split_once(' ')?;
+                    (name == key).then(|| count.trim().parse().ok()).flatten()
+                })
+            })
+            .unwrap_or(0)
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl Drop for CgroupLeaf {
+    fn drop(&mut self) {
+        // Kill anything the command left running (cgroup.kill needs Linux
+        // 5.14). Emptying the leaf takes the kernel a moment, so the removal
+        // retries run on a blocking thread when dropped inside a runtime.
+        let _ = std::fs::write(self.path.join("cgroup.kill"), "1");
+        let path = std::mem::take(&mut self.path);
+        match tokio::runtime::Handle::try_current() {
+            Ok(runtime) => {
+                runtime.spawn_blocking(move || remove_cgroup_leaf(&path));
+            }
+            Err(_) => remove_cgroup_leaf(&path),
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn remove_cgroup_leaf(path: &std::path::Path) {
+    for _ in 0..20 {
+        if std::fs::remove_dir(path).is_ok() {
+            return;
+        }
+        std::thread::sleep(Duration::from_millis(5));
+    }
+}
+
+/// Kills a command's process group once its combined stdout and stderr pass
+/// `max_output_bytes`. The stream readers share one instance.
+#[derive(Debug)]
+pub(crate) struct OutputLimit {
+    max_bytes: u64,
+    total_bytes: AtomicU64,
+    process_group_id: u32,
+    exceeded_limit: ExceededLimitSlot,
+}
+
+impl OutputLimit {
+    pub(crate) fn new(
+        limits: &ResourceLimits,
+        process_group_id: u32,
+        exceeded_limit: &ExceededLimitSlot,
+    ) -> Option<Arc<Self>> {
+        limits.max_output_bytes.map(|max_bytes| {
+            Arc::new(Self {
+                max_bytes,
+                total_bytes: AtomicU64::new(0),
+                process_group_id,
+                exceeded_limit: Arc::clone(exceeded_limit),
+            })
+        })
+    }
+
+    /// Counts `bytes` read from the command, killing it on the read that
+    /// crosses the limit.
+    pub(crate) fn record(&self, bytes: usize) {
+        let bytes = bytes as u64;
+        let previous = self.total_bytes.fetch_add(bytes, Ordering::Relaxed);
+        if previous <= self.max_bytes && previous.saturating_add(bytes) > self.max_bytes {
+            record_exceeded_limit(&self.exceeded_limit, ExceededResourceLimit::OutputBytes);
+            #[cfg(unix)]
+            let _ = crate::process_group::kill_process_group(self.process_group_id);
+            // Without process groups the reader cannot kill the command;
+            // callers still see the limit reported.
+            #[cfg(not(unix))]
+            let _ = self.process_group_id;
+        }
+    }
+}
+
+/// Kills the process group once `wall_time` elapses, unless the process has
+/// exited by then.
+#[cfg(unix)]
+pub(crate) fn spawn_wall_time_killer(
+    wall_time: Option<Duration>,
+    process_group_id: u32,
+    exited: Arc<std::sync::atomic::AtomicBool>,
+    exceeded_limit: ExceededLimitSlot,
+) {
+    let Some(wall_time) = wall_time else {
+        return;
+    };
+    tokio::spawn(async move {
+        tokio::time::sleep(wall_time).await;
+        if !exited.load(std::sync::atomic::Ordering::SeqCst) {
+            record_exceeded_limit(&exceeded_limit, ExceededResourceLimit::WallTime);
+            let _ = crate::process_group::kill_process_group(process_group_id);
+        }
+    });
+}
@@ -11,6 +11,8 @@ use crate::combine_output_receivers;
 use crate::pipe::spawn_process_no_stdin_with_inherited_fds;
 #[cfg(unix)]
 use crate::pty::spawn_process_with_inherited_fds;
+use crate::resource_limits::ExceededResourceLimit;
+use crate::resource_limits::ResourceLimits;
 use crate::spawn_from_driver;
 use crate::spawn_pipe_process;
 use crate::spawn_pipe_process_no_stdin;
@@ -801,6 +803,7 @@ async fn pty_spawn_can_preserve_inherited_fds() -> anyhow::Result<()> {
         &None,
         TerminalSize::default(),
         &[write_end.as_raw_fd()],
+        &ResourceLimits::default(),
     )
     .await?;
 
@@ -853,6 +856,7 @@ async fn pty_preserving_inherited_fds_keeps_python_repl_running() -> anyhow::Res
         &None,
         TerminalSize::default(),
         &[preserved_fd.as_raw_fd()],
+        &ResourceLimits::default(),
     )
     .await?;
     drop(read_end);
@@ -919,6 +923,7 @@ async fn pty_spawn_with_inherited_fds_reports_exec_failures() -> anyhow::Result<
         &None,
         TerminalSize::default(),
         &[write_end.as_raw_fd()],
+        &ResourceLimits::default(),
     )
     .await;
 
@@ -971,6 +976,7 @@ async fn pty_spawn_with_inherited_fds_supports_resize() -> anyhow::Result<()> {
             cols: 101,
         },
         &[write_end.as_raw_fd()],
+        &ResourceLimits::default(),
     )
     .await?;
 
@@ -1038,6 +1044,7 @@ async fn pipe_spawn_no_stdin_can_preserve_inherited_fds() -> anyhow::Result<()>
         &env_map,
         &None,
         &[write_end.as_raw_fd()],
+        &ResourceLimits::default(),
     )
     .await?;
 
@@ -1053,3 +1060,166 @@ async fn pipe_spawn_no_stdin_can_preserve_inherited_fds() -> anyhow::Result<()>
 
     Ok(())
 }
+
+#[cfg(unix)]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn spawns_apply_resource_limits_to_child() -> anyhow::Result<()> {
+    let env_map: HashMap<String, String> = std::env::vars().collect();
+    let resource_limits = ResourceLimits {
+        cpu_seconds: Some(7),
+        max_open_files: Some(64),
+        ..Default::default()
+    };
+    let script = "ulimit -t; ulimit -n".to_string();
+
+    let pipe = spawn_process_no_stdin_with_inherited_fds(
+        "/bin/sh",
+        &["-c".to_string(), script.clone()],
+        Path::new("."),
+        &env_map,
+        &None,
+        &[],
+        &resource_limits,
+    )
+    .await?;
+    let (_session, output_rx, exit_rx) = combine_spawned_output(pipe);
+    let (output, code) = collect_output_until_exit(output_rx, exit_rx, /*timeout_ms*/ 2_000).await;
+    assert_eq!(String::from_utf8_lossy(&output), "7\n64\n");
+    assert_eq!(code, 0);
+
+    let pty = spawn_process_with_inherited_fds(
+        "/bin/sh",
+        &["-c".to_string(), script],
+        Path::new("."),
+        &env_map,
+        &None,
+        TerminalSize::default(),
+        &[],
+        &resource_limits,
+    )
+    .await?;
+    let (_session, output_rx, exit_rx) = combine_spawned_output(pty);
+    let (output, code) = collect_output_until_exit(output_rx, exit_rx, /*timeout_ms*/ 2_000).await;
+    assert_eq!(
+        String::from_utf8_lossy(&output).replace("\r\n", "\n"),
+        "7\n64\n"
+    );
+    assert_eq!(code, 0);
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn pipe_spawn_kills_process_group_after_wall_time() -> anyhow::Result<()> {
+    let env_map: HashMap<String, String> = std::env::vars().collect();
+    let resource_limits = ResourceLimits {
+        wall_time: Some(std::time::Duration::from_millis(200)),
+        ..Default::default()
+    };
+
+    let start = std::time::Instant::now();
+    let spawned = spawn_process_no_stdin_with_inherited_fds(
+        "/bin/sh",
+        &["-c".to_string(), "sleep 30".to_string()],
+        Path::new("."),
+        &env_map,
+        &None,
+        &[],
+        &resource_limits,
+    )
+    .await?;
+    let (session, output_rx, exit_rx) = combine_spawned_output(spawned);
+    let (_, code) = collect_output_until_exit(output_rx, exit_rx, /*timeout_ms*/ 5_000).await;
+
+    assert_eq!(code, -1, "expected the child to be killed by a signal");
+    assert!(start.elapsed() < std::time::Duration::from_secs(5));
+    assert_eq!(
+        session.exceeded_resource_limit(),
+        Some(ExceededResourceLimit::WallTime)
+    );
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn spawns_kill_process_group_after_max_output_bytes() -> anyhow::Result<()> {
+    let env_map: HashMap<String, String> = std::env::vars().collect();
+    let resource_limits = ResourceLimits {
+        max_output_bytes: Some(1_024),
+        ..Default::default()
+    };
+    let script = "while :; do echo 0123456789; done".to_string();
+
+    let pipe = spawn_process_no_stdin_with_inherited_fds(
+        "/bin/sh",
+        &["-c".to_string(), script.clone()],
+        Path::new("."),
+        &env_map,
+        &None,
+        &[],
+        &resource_limits,
+    )
+    .await?;
+    let (session, output_rx, exit_rx) = combine_spawned_output(pipe);
+    let (_, code) = collect_output_until_exit(output_rx, exit_rx, /*timeout_ms*/ 5_000).await;
+    assert_eq!(code, -1, "expected the pipe child to be killed by a signal");
+    assert_eq!(
+        session.exceeded_resource_limit(),
+        Some(ExceededResourceLimit::OutputBytes)
+    );
+
+    let pty = spawn_process_with_inherited_fds(
+        "/bin/sh",
+        &["-c".to_string(), script],
+        Path::new("."),
+        &env_map,
+        &None,
+        TerminalSize::default(),
+        &[],
+        &resource_limits,
+    )
+    .await?;
+    let (session, output_rx, exit_rx) = combine_spawned_output(pty);
+    let (_, code) = collect_output_until_exit(output_rx, exit_rx, /*timeout_ms*/ 5_000).await;
+    assert_eq!(code, -1, "expected the PTY child to be killed by a signal");
+    assert_eq!(
+        session.exceeded_resource_limit(),
+        Some(ExceededResourceLimit::OutputBytes)
+    );
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn pty_spawn_reports_cpu_time_limit() -> anyhow::Result<()> {
+    let env_map: HashMap<String, String> = std::env::vars().collect();
+    let resource_limits = ResourceLimits {
+        cpu_seconds: Some(1),
+        ..Default::default()
+    };
+
+    let spawned = spawn_process_with_inherited_fds(
+        "/bin/sh",
+        &["-c".to_string(), "while :; do :; done".to_string()],
+        Path::new("."),
+        &env_map,
+        &None,
+        TerminalSize::default(),
+        &[],
+        &resource_limits,
+    )
+    .await?;
+    let (session, output_rx, exit_rx) = combine_spawned_output(spawned);
+    let (_, code) = collect_output_until_exit(output_rx, exit_rx, /*timeout_ms*/ 10_000).await;
+
+    assert_eq!(code, -1, "expected the child to be killed by a signal");
+    assert_eq!(
+        session.exceeded_resource_limit(),
+        Some(ExceededResourceLimit::CpuTime)
+    );
+
+    Ok(())
+}